@@ -3,12 +3,12 @@
 //! Benchmarks the complete analysis pipeline: scan -> parse -> analyze
 
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
-use drift_core::scanner::{Scanner, ScanConfig};
+use drift_core::scanner::{HashAlgorithm, Scanner, ScanConfig};
 use drift_core::parsers::ParserManager;
 use drift_core::call_graph::{StreamingBuilder, BuilderConfig};
 use drift_core::boundaries::BoundaryScanner;
 use drift_core::coupling::CouplingAnalyzer;
-use drift_core::test_topology::TestTopologyAnalyzer;
+use drift_core::test_topology::{RiskOptions, TestTopologyAnalyzer};
 use drift_core::error_handling::ErrorHandlingAnalyzer;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -110,8 +110,15 @@ fn bench_scanner(c: &mut Criterion) {
                 patterns: vec!["**/*.ts".to_string()],
                 extra_ignores: vec![],
                 compute_hashes: true,
+                hash_algorithm: HashAlgorithm::XxHash64,
                 max_file_size: 10 * 1024 * 1024,
                 threads: 0,
+                follow_symlinks: false,
+                respect_gitignore: true,
+                on_progress: None,
+                progress_interval: 100,
+                max_depth: None,
+                max_files_per_dir: None,
             };
             let scanner = Scanner::new(config);
             scanner.scan()
@@ -190,7 +197,7 @@ fn bench_test_topology(c: &mut Criterion) {
     c.bench_function("test_topology", |b| {
         b.iter(|| {
             let mut analyzer = TestTopologyAnalyzer::new();
-            analyzer.analyze(&files)
+            analyzer.analyze(&files, &RiskOptions::default())
         })
     });
 }
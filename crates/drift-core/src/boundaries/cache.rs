@@ -0,0 +1,73 @@
+//! Boundary cache - Per-file scan results persisted between runs
+//!
+//! One JSON entry per scanned file under `.drift/lake/boundaries/`, named by
+//! a hash of the file path. An entry is reused only while the file's content
+//! hash and the scanner's rules key (detector version plus configured
+//! sensitive field rules) both match; anything else is a miss and is
+//! overwritten by the fresh result.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::FileBoundaryResult;
+
+/// Version of the boundary detection rules; bump whenever detection output
+/// changes so results cached by older versions are rescanned
+pub const BOUNDARY_RULES_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    file: String,
+    hash: String,
+    rules: String,
+    result: FileBoundaryResult,
+}
+
+/// On-disk cache of per-file boundary results
+pub struct BoundaryCache {
+    dir: PathBuf,
+}
+
+impl BoundaryCache {
+    /// Cache for the project at `root` (`.drift/lake/boundaries/`)
+    pub fn new(root: &Path) -> Self {
+        Self {
+            dir: root.join(".drift").join("lake").join("boundaries"),
+        }
+    }
+
+    /// Cached result for `file` when it was scanned with this content and rules
+    pub fn get(&self, file: &str, hash: &str, rules: &str) -> Option<FileBoundaryResult> {
+        let content = fs::read_to_string(self.entry_path(file)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        (entry.file == file && entry.hash == hash && entry.rules == rules).then_some(entry.result)
+    }
+
+    /// Store the result of scanning `file`
+    pub fn put(&self, file: &str, hash: &str, rules: &str, result: &FileBoundaryResult) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+        let entry = CacheEntry {
+            file: file.to_string(),
+            hash: hash.to_string(),
+            rules: rules.to_string(),
+            result: result.clone(),
+        };
+        let json = serde_json::to_string(&entry)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(self.entry_path(file), json)
+            .map_err(|e| format!("Failed to write boundary cache: {}", e))
+    }
+
+    fn entry_path(&self, file: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", xxh3_64(file.as_bytes())))
+    }
+}
+
+/// Hash of file contents used to key cache entries
+pub fn content_hash(source: &str) -> String {
+    format!("{:016x}", xxh3_64(source.as_bytes()))
+}
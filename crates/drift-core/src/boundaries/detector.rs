@@ -1,39 +1,51 @@
 //! Data access detector - Detects database access patterns in source code
 //!
 //! AST-first approach: Uses tree-sitter parsed CallSite data to detect
-//! database access patterns. Regex is only used as fallback for SQL strings
-//! embedded in code that can't be captured via AST.
+//! database access patterns. SQL strings embedded in code, which the AST
+//! can't see into, are found by regex and read by the `.sql` file parser.
 
 use regex::Regex;
+use super::sql::SqlFileParser;
 use super::types::*;
-use crate::parsers::{ParseResult, CallSite};
+use crate::parsers::{ParseResult, CallSite, ClassInfo, Language};
 
-/// Data access detector - AST-first with regex fallbacks for SQL strings
+/// Data access detector - AST-first with a fallback for SQL strings
 pub struct DataAccessDetector {
-    // Regex fallbacks for SQL strings (AST can't parse SQL inside strings)
-    sql_select: Regex,
-    sql_insert: Regex,
-    sql_update: Regex,
-    sql_delete: Regex,
+    // Start of an embedded SQL statement (AST can't parse SQL inside strings)
+    sql_start: Regex,
+    sql_parser: SqlFileParser,
 }
 
 impl DataAccessDetector {
     pub fn new() -> Self {
         Self {
-            // SQL regex - only used for raw SQL strings that AST can't parse
-            sql_select: Regex::new(r"(?i)SELECT\s+.+\s+FROM\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap(),
-            sql_insert: Regex::new(r"(?i)INSERT\s+INTO\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap(),
-            sql_update: Regex::new(r"(?i)UPDATE\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap(),
-            sql_delete: Regex::new(r"(?i)DELETE\s+FROM\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap(),
+            sql_start: Regex::new(r"(?i)\b(?:WITH|SELECT|INSERT|UPDATE|DELETE|TRUNCATE)\b").unwrap(),
+            sql_parser: SqlFileParser::new(),
         }
     }
     
     /// Detect data access from AST-parsed call sites (primary method)
     pub fn detect_from_ast(&self, result: &ParseResult, file: &str) -> Vec<DataAccessPoint> {
         let mut access_points = Vec::new();
+        let uses_mongoose = result.imports.iter().any(|i| i.source == "mongoose");
         
         for call in &result.calls {
-            if let Some(access) = self.detect_from_call_site(call, file) {
+            // Ruby model calls look like those of several JS/Python ORMs, so
+            // ActiveRecord gets the first say for Ruby files. Mongoose and
+            // Sequelize models share `findOne` and `create`; Mongoose wins in
+            // files importing it.
+            let access = if result.language == Language::Ruby {
+                self.detect_activerecord_call(call, file)
+                    .or_else(|| self.detect_activerecord_instance_call(call, file))
+            } else if uses_mongoose {
+                self.detect_mongoose_model_call(call, file)
+            } else {
+                None
+            };
+            let access = access
+                .or_else(|| self.detect_nosql_call(call, file))
+                .or_else(|| self.detect_from_call_site(call, file));
+            if let Some(access) = access {
                 access_points.push(access);
             }
         }
@@ -41,6 +53,47 @@ impl DataAccessDetector {
         access_points
     }
     
+    /// Detect ORM model classes from AST-parsed class declarations
+    pub fn detect_models_from_ast(&self, result: &ParseResult, file: &str) -> Vec<ORMModel> {
+        result.classes.iter()
+            .filter_map(|class| match result.language {
+                Language::Php => self.detect_eloquent_model(class, file),
+                _ => None,
+            })
+            .collect()
+    }
+    
+    /// Laravel Eloquent: `class User extends Model` with optional `$table` / `$fillable`
+    fn detect_eloquent_model(&self, class: &ClassInfo, file: &str) -> Option<ORMModel> {
+        let base = class.extends.as_deref()?;
+        if base.rsplit('\\').next() != Some("Model") {
+            return None;
+        }
+        
+        let property_value = |name: &str| {
+            class.properties.iter()
+                .find(|p| p.name == name)
+                .and_then(|p| p.value.as_deref())
+        };
+        let table = property_value("table")
+            .and_then(|v| quoted_strings(v).into_iter().next());
+        let fields = property_value("fillable")
+            .map(quoted_strings)
+            .unwrap_or_default();
+        let confidence = if table.is_some() || !fields.is_empty() { 0.95 } else { 0.85 };
+        
+        Some(ORMModel {
+            name: class.name.clone(),
+            table_name: table.unwrap_or_else(|| eloquent_table_name(&class.name)),
+            fields,
+            relations: Vec::new(),
+            file: file.to_string(),
+            line: class.range.start.line,
+            framework: "eloquent".to_string(),
+            confidence,
+        })
+    }
+    
     /// Detect data access from a single AST call site
     fn detect_from_call_site(&self, call: &CallSite, file: &str) -> Option<DataAccessPoint> {
         let receiver = call.receiver.as_deref();
@@ -57,9 +110,45 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.9,
                 framework: Some("supabase".to_string()),
+                transaction_id: None,
             });
         }
         
+        // Prisma: prisma.user.findMany(), this.prisma.user.findMany(), prisma.post.create()
+        // Ahead of the Supabase chain rule, which would take update/delete/upsert
+        if let Some(recv) = receiver {
+            // Handle both "prisma.user" and "this.prisma.user" patterns
+            let prisma_table = if recv.starts_with("this.prisma.") {
+                Some(recv.strip_prefix("this.prisma.").unwrap_or("unknown"))
+            } else if recv.starts_with("prisma.") {
+                Some(recv.strip_prefix("prisma.").unwrap_or("unknown"))
+            } else if recv.contains(".prisma.") {
+                // Handle other patterns like "self.prisma.user"
+                recv.split(".prisma.").nth(1)
+            } else {
+                None
+            };
+            
+            if let Some(table) = prisma_table {
+                let operation = match callee {
+                    "create" | "createMany" | "createManyAndReturn" | "update" | "updateMany" |
+                    "updateManyAndReturn" | "upsert" => DataOperation::Write,
+                    "delete" | "deleteMany" => DataOperation::Delete,
+                    _ => DataOperation::Read,
+                };
+                return Some(DataAccessPoint {
+                    table: table.to_string(),
+                    operation,
+                    fields: Vec::new(),
+                    file: file.to_string(),
+                    line: call.range.start.line,
+                    confidence: 0.95,
+                    framework: Some("prisma".to_string()),
+                    transaction_id: None,
+                });
+            }
+        }
+        
         // Supabase chain methods: .select(), .insert(), .update(), .delete(), .upsert()
         // These are called on the result of supabase.from('table')
         let is_supabase_chain = matches!(callee,
@@ -85,6 +174,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.7, // Lower confidence since we can't confirm it's Supabase
                     framework: Some("supabase-chain".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -99,6 +189,7 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.9,
                 framework: Some("supabase-rpc".to_string()),
+                transaction_id: None,
             });
         }
         
@@ -118,6 +209,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("supabase-auth".to_string()),
+                    transaction_id: None,
                 });
             }
             
@@ -135,38 +227,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("supabase-storage".to_string()),
-                });
-            }
-        }
-        
-        // Prisma: prisma.user.findMany(), this.prisma.user.findMany(), prisma.post.create()
-        if let Some(recv) = receiver {
-            // Handle both "prisma.user" and "this.prisma.user" patterns
-            let prisma_table = if recv.starts_with("this.prisma.") {
-                Some(recv.strip_prefix("this.prisma.").unwrap_or("unknown"))
-            } else if recv.starts_with("prisma.") {
-                Some(recv.strip_prefix("prisma.").unwrap_or("unknown"))
-            } else if recv.contains(".prisma.") {
-                // Handle other patterns like "self.prisma.user"
-                recv.split(".prisma.").nth(1)
-            } else {
-                None
-            };
-            
-            if let Some(table) = prisma_table {
-                let operation = match callee {
-                    "create" | "createMany" | "update" | "updateMany" | "upsert" => DataOperation::Write,
-                    "delete" | "deleteMany" => DataOperation::Delete,
-                    _ => DataOperation::Read,
-                };
-                return Some(DataAccessPoint {
-                    table: table.to_string(),
-                    operation,
-                    fields: Vec::new(),
-                    file: file.to_string(),
-                    line: call.range.start.line,
-                    confidence: 0.95,
-                    framework: Some("prisma".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -181,6 +242,7 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.9,
                 framework: Some("typeorm".to_string()),
+                transaction_id: None,
             });
         }
         
@@ -204,6 +266,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("sequelize".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -226,6 +289,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("django".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -249,6 +313,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("gorm".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -265,6 +330,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("diesel".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -290,6 +356,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("drizzle".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -309,32 +376,13 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.85,
                 framework: Some("knex".to_string()),
+                transaction_id: None,
             });
         }
         
         // Mongoose: Model.find(), Model.findOne(), Model.save(), Model.deleteOne()
-        if let Some(recv) = receiver {
-            let is_mongoose = matches!(callee,
-                "find" | "findOne" | "findById" | "findOneAndUpdate" | "findOneAndDelete" |
-                "save" | "create" | "insertMany" | "updateOne" | "updateMany" |
-                "deleteOne" | "deleteMany" | "remove" | "aggregate" | "countDocuments"
-            );
-            if is_mongoose && recv.chars().next().map_or(false, |c| c.is_uppercase()) {
-                let operation = match callee {
-                    "save" | "create" | "insertMany" | "updateOne" | "updateMany" | "findOneAndUpdate" => DataOperation::Write,
-                    "deleteOne" | "deleteMany" | "remove" | "findOneAndDelete" => DataOperation::Delete,
-                    _ => DataOperation::Read,
-                };
-                return Some(DataAccessPoint {
-                    table: recv.to_lowercase(),
-                    operation,
-                    fields: Vec::new(),
-                    file: file.to_string(),
-                    line: call.range.start.line,
-                    confidence: 0.85,
-                    framework: Some("mongoose".to_string()),
-                });
-            }
+        if let Some(access) = self.detect_mongoose_model_call(call, file) {
+            return Some(access);
         }
         
         // Kysely: db.selectFrom('table'), db.insertInto('table'), db.updateTable('table')
@@ -354,6 +402,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("kysely".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -380,6 +429,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("mikroorm".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -396,6 +446,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("typeorm".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -420,6 +471,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("typeorm".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -440,6 +492,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("objection".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -465,6 +518,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.8,
                     framework: Some("bookshelf".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -481,6 +535,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("node-postgres".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -503,6 +558,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("mysql2".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -521,6 +577,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("better-sqlite3".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -540,6 +597,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("better-sqlite3".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -568,6 +626,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("sqlalchemy".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -592,6 +651,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.8,
                     framework: Some("tortoise".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -617,6 +677,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("peewee".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -633,6 +694,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("sqlmodel".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -648,6 +710,7 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.7,
                 framework: Some("ponyorm".to_string()),
+                transaction_id: None,
             });
         }
         if callee == "commit" && receiver.is_none() {
@@ -659,6 +722,7 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.7,
                 framework: Some("ponyorm".to_string()),
+                transaction_id: None,
             });
         }
         
@@ -682,6 +746,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("asyncpg".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -707,6 +772,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("psycopg".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -737,6 +803,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("pymongo".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -762,6 +829,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("spring-data-jpa".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -786,6 +854,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("jpa".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -814,6 +883,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("mybatis".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -840,6 +910,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.95,
                         framework: Some("jdbc".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -867,6 +938,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("jooq".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -897,6 +969,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("panache".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -931,6 +1004,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("entity-framework".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -970,6 +1044,7 @@ impl DataAccessDetector {
                                 line: call.range.start.line,
                                 confidence: 0.9,
                                 framework: Some("entity-framework".to_string()),
+                                transaction_id: None,
                             });
                         }
                     }
@@ -993,6 +1068,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.7,
                     framework: Some("linq".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -1018,6 +1094,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("dapper".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1045,6 +1122,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("nhibernate".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1070,6 +1148,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.95,
                         framework: Some("ado-net".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1099,6 +1178,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("eloquent-relationship".to_string()),
+                        transaction_id: None,
                     });
                 }
                 
@@ -1121,6 +1201,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("eloquent".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1152,6 +1233,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("eloquent".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1178,6 +1260,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.75,
                         framework: Some("eloquent".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1192,6 +1275,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("laravel-db".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -1218,6 +1302,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("doctrine".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1236,6 +1321,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("doctrine".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1258,6 +1344,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.95,
                         framework: Some("pdo".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1277,6 +1364,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("pdo".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1303,6 +1391,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.8,
                         framework: Some("yii".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1330,6 +1419,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("cakephp".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1355,6 +1445,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("sqlx-go".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -1383,6 +1474,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("ent".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1408,6 +1500,7 @@ impl DataAccessDetector {
                     line: call.range.start.line,
                     confidence: 0.9,
                     framework: Some("bun".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -1432,6 +1525,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.9,
                         framework: Some("pgx".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1441,17 +1535,61 @@ impl DataAccessDetector {
         // Rust ORMs (additional)
         // =========================================================================
         
+        // sqlx (Rust) macros: sqlx::query!("SELECT ..."), query_as!(User, "SELECT ...")
+        if let Some(path) = callee.strip_suffix('!') {
+            let name = path.rsplit("::").next().unwrap_or(path);
+            let is_sqlx = matches!(name,
+                "query" | "query_as" | "query_scalar" | "query_unchecked" | "query_as_unchecked" |
+                "query_scalar_unchecked" | "query_file" | "query_file_as" | "query_file_scalar"
+            );
+            if is_sqlx && (path == name || path.contains("sqlx")) {
+                let (table, operation) = call.string_arg.as_deref()
+                    .and_then(|sql| self.sql_statement(sql))
+                    .unwrap_or_else(|| ("unknown".to_string(), DataOperation::Read));
+                return Some(DataAccessPoint {
+                    table,
+                    operation,
+                    fields: Vec::new(),
+                    file: file.to_string(),
+                    line: call.range.start.line,
+                    confidence: 0.9,
+                    framework: Some("sqlx-rust".to_string()),
+                    transaction_id: None,
+                });
+            }
+        }
+        
         // sqlx (Rust): sqlx::query(), sqlx::query_as()
         if callee == "query" || callee == "query_as" || callee == "query_scalar" {
             if receiver.map_or(false, |r| r.contains("sqlx")) {
+                let (table, operation) = call.string_arg.as_deref()
+                    .and_then(|sql| self.sql_statement(sql))
+                    .unwrap_or_else(|| ("unknown".to_string(), DataOperation::Read));
                 return Some(DataAccessPoint {
-                    table: "unknown".to_string(),
-                    operation: DataOperation::Read,
+                    table,
+                    operation,
                     fields: Vec::new(),
                     file: file.to_string(),
                     line: call.range.start.line,
                     confidence: 0.85,
                     framework: Some("sqlx-rust".to_string()),
+                    transaction_id: None,
+                });
+            }
+        }
+        
+        // Diesel raw SQL: diesel::sql_query("SELECT ...")
+        if callee == "sql_query" && receiver.is_some_and(|r| r.contains("diesel")) {
+            if let Some((table, operation)) = call.string_arg.as_deref().and_then(|sql| self.sql_statement(sql)) {
+                return Some(DataAccessPoint {
+                    table,
+                    operation,
+                    fields: Vec::new(),
+                    file: file.to_string(),
+                    line: call.range.start.line,
+                    confidence: 0.85,
+                    framework: Some("diesel".to_string()),
+                    transaction_id: None,
                 });
             }
         }
@@ -1477,6 +1615,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("seaorm".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1496,6 +1635,7 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.8,
                 framework: Some("exposed".to_string()),
+                transaction_id: None,
             });
         }
         
@@ -1522,6 +1662,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("exposed".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1545,6 +1686,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.85,
                         framework: Some("ktorm".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1555,35 +1697,8 @@ impl DataAccessDetector {
         // =========================================================================
         
         // ActiveRecord: Model.find(), Model.where(), Model.create()
-        if let Some(recv) = receiver {
-            if recv.chars().next().map_or(false, |c| c.is_uppercase()) {
-                let is_activerecord = matches!(callee,
-                    "find" | "find_by" | "find_by!" | "find_or_create_by" | "find_or_initialize_by" |
-                    "where" | "all" | "first" | "last" | "take" | "pluck" | "select" |
-                    "create" | "create!" | "new" | "build" | "save" | "save!" |
-                    "update" | "update!" | "update_all" | "update_attribute" | "update_attributes" |
-                    "destroy" | "destroy!" | "destroy_all" | "delete" | "delete_all" |
-                    "includes" | "joins" | "left_joins" | "eager_load" | "preload"
-                );
-                if is_activerecord {
-                    let operation = match callee {
-                        "create" | "create!" | "save" | "save!" | "update" | "update!" | 
-                        "update_all" | "update_attribute" | "update_attributes" |
-                        "find_or_create_by" => DataOperation::Write,
-                        "destroy" | "destroy!" | "destroy_all" | "delete" | "delete_all" => DataOperation::Delete,
-                        _ => DataOperation::Read,
-                    };
-                    return Some(DataAccessPoint {
-                        table: recv.to_lowercase(),
-                        operation,
-                        fields: Vec::new(),
-                        file: file.to_string(),
-                        line: call.range.start.line,
-                        confidence: 0.8,
-                        framework: Some("activerecord".to_string()),
-                    });
-                }
-            }
+        if let Some(access) = self.detect_activerecord_call(call, file) {
+            return Some(access);
         }
         
         // =========================================================================
@@ -1608,6 +1723,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.7,
                         framework: Some("http-client".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1631,6 +1747,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.7,
                         framework: Some("http-client".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1646,59 +1763,10 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.6,
                 framework: Some("fetch".to_string()),
+                transaction_id: None,
             });
         }
         
-        // =========================================================================
-        // Redis (all languages)
-        // =========================================================================
-        
-        // Redis: redis.get(), redis.set(), redis.hget(), redis.lpush()
-        if let Some(recv) = receiver {
-            if recv == "redis" || recv == "r" || recv == "cache" || recv.contains("redis") || recv.ends_with("Redis") {
-                let is_redis = matches!(callee,
-                    // String operations
-                    "get" | "set" | "setex" | "setnx" | "mget" | "mset" | "incr" | "decr" | "incrby" | "decrby" |
-                    "append" | "getset" | "strlen" | "getrange" | "setrange" |
-                    // Hash operations
-                    "hget" | "hset" | "hmget" | "hmset" | "hgetall" | "hdel" | "hexists" | "hincrby" | "hkeys" | "hvals" |
-                    // List operations
-                    "lpush" | "rpush" | "lpop" | "rpop" | "lrange" | "llen" | "lindex" | "lset" | "lrem" |
-                    // Set operations
-                    "sadd" | "srem" | "smembers" | "sismember" | "scard" | "sunion" | "sinter" | "sdiff" |
-                    // Sorted set operations
-                    "zadd" | "zrem" | "zrange" | "zrangebyscore" | "zscore" | "zcard" | "zincrby" |
-                    // Key operations
-                    "del" | "exists" | "expire" | "ttl" | "keys" | "scan" | "type" |
-                    // Pub/Sub
-                    "publish" | "subscribe" | "unsubscribe" |
-                    // Transactions
-                    "multi" | "exec" | "watch" | "unwatch" |
-                    // Lua scripting
-                    "eval" | "evalsha"
-                );
-                if is_redis {
-                    let operation = match callee {
-                        "get" | "mget" | "hget" | "hmget" | "hgetall" | "hkeys" | "hvals" | "hexists" |
-                        "lrange" | "llen" | "lindex" | "smembers" | "sismember" | "scard" |
-                        "zrange" | "zrangebyscore" | "zscore" | "zcard" |
-                        "exists" | "ttl" | "keys" | "scan" | "type" => DataOperation::Read,
-                        "del" | "hdel" | "lrem" | "srem" | "zrem" => DataOperation::Delete,
-                        _ => DataOperation::Write,
-                    };
-                    return Some(DataAccessPoint {
-                        table: "redis".to_string(),
-                        operation,
-                        fields: Vec::new(),
-                        file: file.to_string(),
-                        line: call.range.start.line,
-                        confidence: 0.85,
-                        framework: Some("redis".to_string()),
-                    });
-                }
-            }
-        }
-        
         // =========================================================================
         // Message Queues (Celery, Bull, RabbitMQ, etc.)
         // =========================================================================
@@ -1713,6 +1781,7 @@ impl DataAccessDetector {
                 line: call.range.start.line,
                 confidence: 0.7,
                 framework: Some("celery".to_string()),
+                transaction_id: None,
             });
         }
         
@@ -1734,6 +1803,7 @@ impl DataAccessDetector {
                         line: call.range.start.line,
                         confidence: 0.75,
                         framework: Some("bull".to_string()),
+                        transaction_id: None,
                     });
                 }
             }
@@ -1742,82 +1812,253 @@ impl DataAccessDetector {
         None
     }
     
-    /// Regex fallback: Detect SQL in raw source (for embedded SQL strings)
-    pub fn detect_sql_in_source(&self, source: &str, file: &str) -> Vec<DataAccessPoint> {
-        let mut access_points = Vec::new();
-        let lines: Vec<&str> = source.lines().collect();
-        
-        for (i, line) in lines.iter().enumerate() {
-            let line_num = (i + 1) as u32;
-            
-            // Only check lines that look like they contain SQL strings
-            if !line.contains("SELECT") && !line.contains("INSERT") && 
-               !line.contains("UPDATE") && !line.contains("DELETE") &&
-               !line.contains("select") && !line.contains("insert") &&
-               !line.contains("update") && !line.contains("delete") {
-                continue;
+    /// ActiveRecord class-level calls: Model.find(), Model.where(), Model.create()
+    ///
+    /// Relation chains (`User.where(...).order(:name)`) are attributed to the model at their root.
+    fn detect_activerecord_call(&self, call: &CallSite, file: &str) -> Option<DataAccessPoint> {
+        let receiver = call.receiver.as_deref()?;
+        let recv = receiver.split(['.', '(']).next().unwrap_or(receiver);
+        let is_chain = recv.len() < receiver.len();
+        let callee = call.callee.as_str();
+        if recv.chars().next().is_some_and(|c| c.is_uppercase()) {
+            let is_activerecord = matches!(callee,
+                "find" | "find_by" | "find_by!" | "find_or_create_by" | "find_or_initialize_by" |
+                "where" | "all" | "first" | "last" | "take" | "pluck" | "select" |
+                "create" | "create!" | "new" | "build" | "save" | "save!" |
+                "update" | "update!" | "update_all" | "update_attribute" | "update_attributes" |
+                "destroy" | "destroy!" | "destroy_all" | "delete" | "delete_all" |
+                "includes" | "joins" | "left_joins" | "eager_load" | "preload"
+            ) || (is_chain && matches!(callee,
+                "order" | "limit" | "offset" | "group" | "having" | "distinct" | "count" | "not"
+            ));
+            if is_activerecord {
+                let operation = match callee {
+                    "create" | "create!" | "save" | "save!" | "update" | "update!" | 
+                    "update_all" | "update_attribute" | "update_attributes" |
+                    "find_or_create_by" => DataOperation::Write,
+                    "destroy" | "destroy!" | "destroy_all" | "delete" | "delete_all" => DataOperation::Delete,
+                    _ => DataOperation::Read,
+                };
+                return Some(DataAccessPoint {
+                    table: recv.to_lowercase(),
+                    operation,
+                    fields: Vec::new(),
+                    file: file.to_string(),
+                    line: call.range.start.line,
+                    confidence: 0.8,
+                    framework: Some("activerecord".to_string()),
+                    transaction_id: None,
+                });
             }
-            
-            if let Some(caps) = self.sql_select.captures(line) {
-                if let Some(table) = caps.get(1) {
-                    access_points.push(DataAccessPoint {
-                        table: table.as_str().to_string(),
-                        operation: DataOperation::Read,
-                        fields: Vec::new(),
-                        file: file.to_string(),
-                        line: line_num,
-                        confidence: 0.85,
-                        framework: Some("sql".to_string()),
-                    });
+        }
+        
+        None
+    }
+    
+    /// ActiveRecord instance calls: user.save, user.update!(...), order.destroy
+    ///
+    /// Only used for Ruby files - the method names are too generic elsewhere.
+    fn detect_activerecord_instance_call(&self, call: &CallSite, file: &str) -> Option<DataAccessPoint> {
+        let recv = call.receiver.as_deref()?;
+        let is_variable = recv.chars().next().is_some_and(|c| c.is_lowercase() || c == '@')
+            && recv.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '@');
+        if !is_variable || recv == "self" {
+            return None;
+        }
+        
+        let operation = match call.callee.as_str() {
+            "save" | "save!" | "update" | "update!" | "update_attribute" | "update_column" |
+            "increment!" | "decrement!" | "toggle!" | "touch" => DataOperation::Write,
+            "destroy" | "destroy!" | "delete" => DataOperation::Delete,
+            "reload" => DataOperation::Read,
+            _ => return None,
+        };
+        Some(DataAccessPoint {
+            table: recv.trim_start_matches('@').to_string(),
+            operation,
+            fields: Vec::new(),
+            file: file.to_string(),
+            line: call.range.start.line,
+            confidence: 0.7,
+            framework: Some("activerecord".to_string()),
+            transaction_id: None,
+        })
+    }
+    
+    /// Mongoose model calls: User.find(), Order.findByIdAndUpdate(), User.create()
+    ///
+    /// The table is the collection Mongoose derives from the model name
+    /// (`User` -> `users`). Chains (`User.find({}).exec()`) count once, at the
+    /// model call.
+    fn detect_mongoose_model_call(&self, call: &CallSite, file: &str) -> Option<DataAccessPoint> {
+        let receiver = call.receiver.as_deref()?;
+        let model = receiver.split(['.', '(']).next().unwrap_or(receiver);
+        let is_model = model.chars().next().is_some_and(|c| c.is_uppercase())
+            && !matches!(model, "Object" | "Array" | "Promise" | "Reflect" | "JSON" | "Math" | "Date" | "Map" | "Set" | "String" | "Number");
+        if !is_model {
+            return None;
+        }
+        
+        let operation = match call.callee.as_str() {
+            "find" | "findOne" | "findById" | "aggregate" | "countDocuments" |
+            "estimatedDocumentCount" | "distinct" | "exists" => DataOperation::Read,
+            "save" | "create" | "insertMany" | "updateOne" | "updateMany" | "replaceOne" |
+            "findOneAndUpdate" | "findOneAndReplace" | "findByIdAndUpdate" | "bulkWrite" => DataOperation::Write,
+            "deleteOne" | "deleteMany" | "remove" | "findOneAndDelete" | "findByIdAndDelete" |
+            "findOneAndRemove" | "findByIdAndRemove" => DataOperation::Delete,
+            _ => return None,
+        };
+        Some(DataAccessPoint {
+            table: pluralize(&model.to_lowercase()),
+            operation,
+            fields: Vec::new(),
+            file: file.to_string(),
+            line: call.range.start.line,
+            confidence: 0.85,
+            framework: Some("mongoose".to_string()),
+            transaction_id: None,
+        })
+    }
+    
+    /// Document and key-value stores: DynamoDB, the MongoDB driver and Redis
+    ///
+    /// Their clients are recognized by receiver, so these run before the ORM
+    /// rules, whose generic method names (`update`, `delete`) would claim them.
+    fn detect_nosql_call(&self, call: &CallSite, file: &str) -> Option<DataAccessPoint> {
+        let callee = call.callee.as_str();
+        let receiver = call.receiver.as_deref();
+        let access = |table: String, operation: DataOperation, confidence: f32, framework: &str| DataAccessPoint {
+            table,
+            operation,
+            fields: Vec::new(),
+            file: file.to_string(),
+            line: call.range.start.line,
+            confidence,
+            framework: Some(framework.to_string()),
+            transaction_id: None,
+        };
+        
+        // DynamoDB v2 clients: docClient.get({ TableName: 'Users', ... }), dynamodb.putItem(...)
+        if let Some(recv) = receiver {
+            let lower = recv.to_lowercase();
+            if lower.contains("dynamo") || lower.contains("docclient") || lower.contains("documentclient") || lower == "ddb" {
+                let operation = match callee {
+                    "get" | "query" | "scan" | "batchGet" | "transactGet" |
+                    "getItem" | "batchGetItem" | "transactGetItems" => Some(DataOperation::Read),
+                    "put" | "update" | "batchWrite" | "transactWrite" |
+                    "putItem" | "updateItem" | "batchWriteItem" | "transactWriteItems" => Some(DataOperation::Write),
+                    "delete" | "deleteItem" => Some(DataOperation::Delete),
+                    _ => None,
+                };
+                if let Some(operation) = operation {
+                    let table = call.string_arg.clone().unwrap_or_else(|| "unknown".to_string());
+                    return Some(access(table, operation, 0.85, "dynamodb"));
                 }
             }
-            
-            if let Some(caps) = self.sql_insert.captures(line) {
-                if let Some(table) = caps.get(1) {
-                    access_points.push(DataAccessPoint {
-                        table: table.as_str().to_string(),
-                        operation: DataOperation::Write,
-                        fields: Vec::new(),
-                        file: file.to_string(),
-                        line: line_num,
-                        confidence: 0.85,
-                        framework: Some("sql".to_string()),
-                    });
-                }
+        }
+        
+        // DynamoDB v3 commands: client.send(new GetCommand({ TableName: 'Users', ... }))
+        if receiver.is_none() {
+            let operation = match callee {
+                "GetCommand" | "QueryCommand" | "ScanCommand" | "BatchGetCommand" | "TransactGetCommand" |
+                "GetItemCommand" | "BatchGetItemCommand" | "TransactGetItemsCommand" => Some(DataOperation::Read),
+                "PutCommand" | "UpdateCommand" | "BatchWriteCommand" | "TransactWriteCommand" |
+                "PutItemCommand" | "UpdateItemCommand" | "BatchWriteItemCommand" | "TransactWriteItemsCommand" => Some(DataOperation::Write),
+                "DeleteCommand" | "DeleteItemCommand" => Some(DataOperation::Delete),
+                _ => None,
+            };
+            if let (Some(operation), Some(table)) = (operation, &call.string_arg) {
+                return Some(access(table.clone(), operation, 0.85, "dynamodb"));
             }
-            
-            if let Some(caps) = self.sql_update.captures(line) {
-                if let Some(table) = caps.get(1) {
-                    access_points.push(DataAccessPoint {
-                        table: table.as_str().to_string(),
-                        operation: DataOperation::Write,
-                        fields: Vec::new(),
-                        file: file.to_string(),
-                        line: line_num,
-                        confidence: 0.85,
-                        framework: Some("sql".to_string()),
-                    });
+        }
+        
+        // MongoDB driver: db.collection('orders').updateOne(...)
+        if let Some(recv) = receiver {
+            if let Some(start) = recv.rfind("collection(") {
+                let arg = &recv[start + "collection(".len()..];
+                let name: String = arg.trim_start_matches(['\'', '"', '`'])
+                    .chars()
+                    .take_while(|c| !matches!(c, '\'' | '"' | '`' | ')'))
+                    .collect();
+                let operation = match callee {
+                    "find" | "findOne" | "aggregate" | "countDocuments" | "estimatedDocumentCount" | "distinct" => Some(DataOperation::Read),
+                    "insertOne" | "insertMany" | "updateOne" | "updateMany" | "replaceOne" |
+                    "findOneAndUpdate" | "findOneAndReplace" | "bulkWrite" => Some(DataOperation::Write),
+                    "deleteOne" | "deleteMany" | "findOneAndDelete" => Some(DataOperation::Delete),
+                    _ => None,
+                };
+                if let Some(operation) = operation.filter(|_| !name.is_empty()) {
+                    return Some(access(name, operation, 0.85, "mongoose"));
                 }
             }
-            
-            if let Some(caps) = self.sql_delete.captures(line) {
-                if let Some(table) = caps.get(1) {
-                    access_points.push(DataAccessPoint {
-                        table: table.as_str().to_string(),
-                        operation: DataOperation::Delete,
-                        fields: Vec::new(),
-                        file: file.to_string(),
-                        line: line_num,
-                        confidence: 0.85,
-                        framework: Some("sql".to_string()),
-                    });
+        }
+        
+        // Redis: redis.get('user:1'), client.hGetAll(`session:${id}`), r.lpush(...)
+        //
+        // node-redis v4 spells commands in camelCase, so they match case-insensitively.
+        // The table is the key pattern, with interpolations as `*`.
+        if let Some(recv) = receiver {
+            if recv == "redis" || recv == "r" || recv == "cache" || recv.contains("redis") || recv.ends_with("Redis") {
+                let command = callee.to_lowercase();
+                let operation = match command.as_str() {
+                    "get" | "mget" | "hget" | "hmget" | "hgetall" | "hkeys" | "hvals" | "hexists" |
+                    "lrange" | "llen" | "lindex" | "smembers" | "sismember" | "scard" |
+                    "zrange" | "zrangebyscore" | "zscore" | "zcard" |
+                    "exists" | "ttl" | "keys" | "scan" | "type" | "strlen" | "getrange" |
+                    "sunion" | "sinter" | "sdiff" => Some(DataOperation::Read),
+                    "del" | "hdel" | "lrem" | "srem" | "zrem" | "unlink" => Some(DataOperation::Delete),
+                    // String, hash, list, set and sorted set writes
+                    "set" | "setex" | "setnx" | "mset" | "incr" | "decr" | "incrby" | "decrby" |
+                    "append" | "getset" | "setrange" | "hset" | "hmset" | "hincrby" |
+                    "lpush" | "rpush" | "lpop" | "rpop" | "lset" | "sadd" | "zadd" | "zincrby" |
+                    "expire" |
+                    // Pub/Sub, transactions and Lua scripting
+                    "publish" | "subscribe" | "unsubscribe" | "multi" | "exec" | "watch" | "unwatch" |
+                    "eval" | "evalsha" => Some(DataOperation::Write),
+                    _ => None,
+                };
+                if let Some(operation) = operation {
+                    let table = call.string_arg.as_deref().map_or_else(|| "redis".to_string(), redis_key_pattern);
+                    return Some(access(table, operation, 0.85, "redis"));
                 }
             }
         }
         
+        None
+    }
+    
+    /// Fallback: Detect SQL in raw source (for embedded SQL strings)
+    ///
+    /// Each statement yields one access point per table it touches.
+    pub fn detect_sql_in_source(&self, source: &str, file: &str) -> Vec<DataAccessPoint> {
+        let mut access_points = Vec::new();
+        let mut pos = 0;
+        
+        while let Some(start) = self.sql_start.find_at(source, pos) {
+            let end = sql_statement_end(source, start.start());
+            // Statement lines are 1-based within the snippet
+            let first_line = source[..start.start()].matches('\n').count() as u32;
+            for access in self.sql_parser.parse(&source[start.start()..end], file).access_points {
+                access_points.push(DataAccessPoint {
+                    line: first_line + access.line,
+                    confidence: 0.85,
+                    ..access
+                });
+            }
+            pos = end.max(start.end());
+        }
+        
         access_points
     }
     
+    /// Primary table and operation of a single SQL statement (e.g. a string
+    /// passed to a query macro): the written table, else the first one read
+    fn sql_statement(&self, sql: &str) -> Option<(String, DataOperation)> {
+        self.sql_parser.parse(sql, "").access_points.into_iter()
+            .next()
+            .map(|access| (access.table, access.operation))
+    }
+    
     /// Combined detection: AST-first, then SQL regex fallback
     pub fn detect(&self, source: &str, file: &str) -> Vec<DataAccessPoint> {
         // For backward compatibility - this method uses regex only
@@ -1826,6 +2067,85 @@ impl DataAccessDetector {
     }
 }
 
+/// End of an embedded SQL statement starting at `start`
+///
+/// A statement that opens a string literal runs to the closing quote, which
+/// may be lines later (template literals, triple-quoted strings). Otherwise it
+/// ends at the first `;` or line break.
+fn sql_statement_end(source: &str, start: usize) -> usize {
+    let rest = &source[start..];
+    match source[..start].trim_end().chars().last() {
+        Some(quote @ ('"' | '\'' | '`')) => {
+            let mut escaped = false;
+            for (i, ch) in rest.char_indices() {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == quote {
+                    return start + i;
+                }
+            }
+            source.len()
+        }
+        _ => start + rest.find([';', '\n']).unwrap_or(rest.len()),
+    }
+}
+
+/// String literals in a PHP value (`'users'`, `['name', "email"]`)
+fn quoted_strings(value: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.by_ref().find(|c| *c == '\'' || *c == '"') {
+        let literal: String = chars.by_ref().take_while(|c| *c != ch).collect();
+        strings.push(literal);
+    }
+    strings
+}
+
+/// Eloquent's default table name: the snake_case plural of the model name
+/// (`User` -> `users`, `OrderItem` -> `order_items`, `Category` -> `categories`)
+fn eloquent_table_name(model: &str) -> String {
+    let mut snake = String::new();
+    for (i, ch) in model.chars().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(ch.to_lowercase());
+    }
+    
+    pluralize(&snake)
+}
+
+/// English plural as ORMs derive table names (`category` -> `categories`)
+fn pluralize(word: &str) -> String {
+    let ends_with_consonant_y = word.ends_with('y')
+        && !word[..word.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u']);
+    if ends_with_consonant_y {
+        format!("{}ies", &word[..word.len() - 1])
+    } else if word.ends_with('s') || word.ends_with('x') || word.ends_with("ch") || word.ends_with("sh") {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// Redis key with interpolations replaced by `*` (`user:${id}` -> `user:*`)
+fn redis_key_pattern(key: &str) -> String {
+    let mut pattern = String::new();
+    let mut chars = key.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let interpolation = ch == '{' || (ch == '$' && chars.peek() == Some(&'{'));
+        if interpolation {
+            chars.by_ref().find(|c| *c == '}');
+            pattern.push('*');
+        } else {
+            pattern.push(ch);
+        }
+    }
+    pattern
+}
+
 impl Default for DataAccessDetector {
     fn default() -> Self {
         Self::new()
@@ -1851,4 +2171,259 @@ mod tests {
         assert_eq!(access[1].table, "orders");
         assert_eq!(access[1].operation, DataOperation::Write);
     }
+    
+    #[test]
+    fn test_detect_embedded_multi_table_sql() {
+        let detector = DataAccessDetector::new();
+        let source = r#"
+const report = await db.query(`
+  WITH recent AS (SELECT * FROM orders WHERE created_at > $1)
+  SELECT u.email, r.total
+  FROM recent r
+  JOIN users u ON u.id = r.user_id
+`, [since]);
+// select the rows to update below
+cursor.execute("DELETE FROM sessions USING users WHERE sessions.user_id = users.id")
+"#;
+        let access = detector.detect_sql_in_source(source, "src/report.ts");
+        
+        let found: Vec<(&str, DataOperation, u32)> = access.iter()
+            .map(|a| (a.table.as_str(), a.operation, a.line))
+            .collect();
+        assert_eq!(found, vec![
+            ("users", DataOperation::Read, 3),
+            ("orders", DataOperation::Read, 3),
+            ("sessions", DataOperation::Delete, 9),
+            ("users", DataOperation::Read, 9),
+        ]);
+    }
+    
+    #[test]
+    fn test_detect_ef_linq_query_syntax() {
+        let detector = DataAccessDetector::new();
+        let mut parser = crate::parsers::ParserManager::new();
+        let source = r#"
+public class UserService
+{
+    public List<User> GetActive()
+    {
+        return (from u in _context.Users where u.Active select u).ToList();
+    }
+}
+"#;
+        let result = parser.parse_file("UserService.cs", source).unwrap();
+        let access = detector.detect_from_ast(&result, "UserService.cs");
+        
+        let users = access.iter().find(|a| a.table == "users").unwrap();
+        assert_eq!(users.operation, DataOperation::Read);
+        assert_eq!(users.framework.as_deref(), Some("entity-framework"));
+    }
+    
+    #[test]
+    fn test_detect_rust_query_macros() {
+        let detector = DataAccessDetector::new();
+        let mut parser = crate::parsers::ParserManager::new();
+        let source = r##"
+async fn handlers(pool: &PgPool, conn: &mut PgConnection) {
+    let user = sqlx::query!("SELECT id, email FROM users WHERE id = $1", id).fetch_one(pool).await;
+    let orders = query_as!(Order, r#"
+        SELECT *
+        FROM orders
+        WHERE user_id = $1
+    "#, id).fetch_all(pool).await;
+    sqlx::query!("DELETE FROM sessions WHERE user_id = $1", id).execute(pool).await;
+    diesel::sql_query("UPDATE accounts SET active = false").execute(conn);
+    println!("SELECT is not a query here");
+}
+"##;
+        let result = parser.parse_file("src/handlers.rs", source).unwrap();
+        let access = detector.detect_from_ast(&result, "src/handlers.rs");
+        
+        let found: Vec<(&str, DataOperation, Option<&str>)> = access.iter()
+            .map(|a| (a.table.as_str(), a.operation, a.framework.as_deref()))
+            .collect();
+        assert_eq!(found, vec![
+            ("users", DataOperation::Read, Some("sqlx-rust")),
+            ("orders", DataOperation::Read, Some("sqlx-rust")),
+            ("sessions", DataOperation::Delete, Some("sqlx-rust")),
+            ("accounts", DataOperation::Write, Some("diesel")),
+        ]);
+        // Each access is attributed to the line of its macro call
+        assert_eq!(access[1].line, 3);
+    }
+    
+    #[test]
+    fn test_detect_eloquent_models() {
+        use crate::parsers::{PropertyInfo, Range, Visibility};
+        
+        let detector = DataAccessDetector::new();
+        let property = |name: &str, value: &str| PropertyInfo {
+            name: name.to_string(),
+            type_annotation: None,
+            is_static: false,
+            is_readonly: false,
+            visibility: Visibility::Protected,
+            tags: None,
+            value: Some(value.to_string()),
+            is_computed: false,
+            decorators: Vec::new(),
+        };
+        let model = |name: &str, extends: &str, properties: Vec<PropertyInfo>| ClassInfo {
+            name: name.to_string(),
+            extends: Some(extends.to_string()),
+            implements: Vec::new(),
+            is_exported: true,
+            is_abstract: false,
+            methods: Vec::new(),
+            properties,
+            range: Range::new(4, 0, 10, 1),
+            decorators: Vec::new(),
+            namespace: None,
+            is_partial: false,
+            type_parameters: Vec::new(),
+        };
+        
+        let mut result = ParseResult::new(Language::Php);
+        result.classes.push(model("User", "Model", vec![
+            property("table", "'app_users'"),
+            property("fillable", "['name', \"email\"]"),
+        ]));
+        result.classes.push(model("OrderCategory", "\\Illuminate\\Database\\Eloquent\\Model", Vec::new()));
+        result.classes.push(model("UserController", "Controller", Vec::new()));
+        
+        let models = detector.detect_models_from_ast(&result, "app/Models/User.php");
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].table_name, "app_users");
+        assert_eq!(models[0].fields, vec!["name", "email"]);
+        assert_eq!(models[0].framework, "eloquent");
+        assert_eq!(models[1].table_name, "order_categories");
+        assert!(models[1].fields.is_empty());
+    }
+    
+    #[test]
+    fn test_detect_activerecord_in_ruby() {
+        let detector = DataAccessDetector::new();
+        let mut parser = crate::parsers::ParserManager::new();
+        let source = r#"
+class UsersController < ApplicationController
+  def index
+    @users = User.where(active: true).order(:name)
+    Post.all
+  end
+
+  def update
+    @user = User.find_by(email: params[:email])
+    @user.update!(user_params)
+    Audit.create!(action: "update")
+  end
+
+  def destroy
+    user.destroy
+  end
+end
+"#;
+        let result = parser.parse_file("app/controllers/users_controller.rb", source).unwrap();
+        let access = detector.detect_from_ast(&result, "app/controllers/users_controller.rb");
+        
+        let found: Vec<(&str, DataOperation)> = access.iter()
+            .map(|a| (a.table.as_str(), a.operation))
+            .collect();
+        assert_eq!(found, vec![
+            ("user", DataOperation::Read),
+            ("user", DataOperation::Read),
+            ("post", DataOperation::Read),
+            ("user", DataOperation::Read),
+            ("user", DataOperation::Write),
+            ("audit", DataOperation::Write),
+            ("user", DataOperation::Delete),
+        ]);
+        assert!(access.iter().all(|a| a.framework.as_deref() == Some("activerecord")));
+    }
+
+    
+    fn detect_in(path: &str, source: &str) -> Vec<(String, DataOperation, String)> {
+        let detector = DataAccessDetector::new();
+        let mut parser = crate::parsers::ParserManager::new();
+        let result = parser.parse_file(path, source).unwrap();
+        detector.detect_from_ast(&result, path).into_iter()
+            .map(|a| (a.table, a.operation, a.framework.unwrap_or_default()))
+            .collect()
+    }
+    
+    fn access(table: &str, operation: DataOperation, framework: &str) -> (String, DataOperation, String) {
+        (table.to_string(), operation, framework.to_string())
+    }
+    
+    #[test]
+    fn test_detect_mongoose() {
+        let source = r#"
+import mongoose from 'mongoose';
+import { User, Category } from './models';
+
+export async function handler(req, res) {
+  const users = await User.find({ active: true }).exec();
+  User.findOne({ email: req.body.email }, (err, user) => res.json(user));
+  await Category.create({ name: req.body.name });
+  await User.findByIdAndDelete(req.params.id);
+  await db.collection('orders').updateOne({ _id: id }, { $set: { paid: true } });
+  mongoose.connection.collection("orders").find({}).toArray(function (err, docs) {});
+}
+"#;
+        assert_eq!(detect_in("src/users.ts", source), vec![
+            access("users", DataOperation::Read, "mongoose"),
+            access("users", DataOperation::Read, "mongoose"),
+            access("categories", DataOperation::Write, "mongoose"),
+            access("users", DataOperation::Delete, "mongoose"),
+            access("orders", DataOperation::Write, "mongoose"),
+            access("orders", DataOperation::Read, "mongoose"),
+        ]);
+        
+        // Without the import, methods Sequelize shares stay with Sequelize
+        let found = detect_in("src/users.ts", "User.findOne({ where: { id } });\nUser.findByIdAndUpdate(id, body);\n");
+        assert_eq!(found[0].2, "sequelize");
+        assert_eq!(found[1], access("users", DataOperation::Write, "mongoose"));
+    }
+    
+    #[test]
+    fn test_detect_dynamodb() {
+        let source = r#"
+const docClient = new AWS.DynamoDB.DocumentClient();
+
+export async function getUser(id) {
+  const { Item } = await docClient.get({ TableName: 'Users', Key: { id } }).promise();
+  docClient.put({ TableName: "Users", Item: user }, (err, data) => {});
+  docClient.query(params, function (err, data) {});
+  await client.send(new DeleteCommand({ TableName: `Sessions`, Key: { id } }));
+  await docClient.update({ TableName: 'Users', Key: { id } }).promise();
+}
+"#;
+        assert_eq!(detect_in("src/users.ts", source), vec![
+            access("Users", DataOperation::Read, "dynamodb"),
+            access("Users", DataOperation::Write, "dynamodb"),
+            access("unknown", DataOperation::Read, "dynamodb"),
+            access("Sessions", DataOperation::Delete, "dynamodb"),
+            access("Users", DataOperation::Write, "dynamodb"),
+        ]);
+    }
+    
+    #[test]
+    fn test_detect_redis() {
+        let source = r#"
+export async function profile(id, sid) {
+  const cached = await redis.get(`user:${id}`);
+  redis.set(`user:${id}:profile`, json, (err) => {});
+  redisClient.hGetAll(`session:${sid}`).then((session) => session);
+  redis.del(key, function (err) {});
+}
+"#;
+        assert_eq!(detect_in("src/cache.ts", source), vec![
+            access("user:*", DataOperation::Read, "redis"),
+            access("user:*:profile", DataOperation::Write, "redis"),
+            access("session:*", DataOperation::Read, "redis"),
+            access("redis", DataOperation::Delete, "redis"),
+        ]);
+        
+        let python = "def profile(user_id):\n    return r.get(f\"user:{user_id}\")\n";
+        assert_eq!(detect_in("app/cache.py", python), vec![access("user:*", DataOperation::Read, "redis")]);
+    }
 }
@@ -0,0 +1,212 @@
+//! External call detector - HTTP requests that send data out of the system
+//!
+//! AST-first like `DataAccessDetector`: HTTP client calls are recognized from
+//! parsed call sites, and the request URL from their first string argument.
+//! Interpolated URLs keep their static host (`https://api.x.com/${id}` ->
+//! `api.x.com`); URLs without one are reported as written.
+
+use super::types::{DataAccessPoint, DataOperation, ExternalAccessPoint};
+use crate::parsers::{CallSite, ParseResult};
+
+/// Table that reachability uses for requests to `host`
+pub const EXTERNAL_TABLE_PREFIX: &str = "external:";
+
+/// External call detector
+pub struct ExternalCallDetector;
+
+impl ExternalCallDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Detect outbound HTTP requests from AST-parsed call sites
+    pub fn detect_from_ast(&self, result: &ParseResult, file: &str) -> Vec<ExternalAccessPoint> {
+        result.calls.iter()
+            .filter_map(|call| self.detect_from_call_site(call, file))
+            .collect()
+    }
+
+    fn detect_from_call_site(&self, call: &CallSite, file: &str) -> Option<ExternalAccessPoint> {
+        let callee = call.callee.as_str();
+        let receiver = call.receiver.as_deref();
+        let recv = receiver.unwrap_or("").to_lowercase();
+        let verb = |name: &str| match name.to_lowercase().as_str() {
+            method @ ("get" | "post" | "put" | "patch" | "delete" | "head" | "options") => Some(method.to_uppercase()),
+            "request" => Some("UNKNOWN".to_string()),
+            _ => None,
+        };
+
+        let (method, framework, confidence) = match receiver {
+            // fetch(url) / fetch(url, { method: 'POST' }): options aren't visible
+            None | Some("window" | "globalThis") if callee == "fetch" => {
+                let method = if call.arg_count <= 1 { "GET" } else { "UNKNOWN" };
+                (method.to_string(), "fetch", 0.8)
+            }
+            None if callee == "axios" || callee == "got" || callee == "ky" => {
+                let method = if call.arg_count <= 1 { "GET" } else { "UNKNOWN" };
+                (method.to_string(), callee, 0.8)
+            }
+            // axios.post(), got.get(), ky.put(), superagent.delete()
+            Some(r) if matches!(r, "axios" | "got" | "ky" | "superagent") || recv.contains("axios") => {
+                let framework = if recv.contains("axios") { "axios" } else { r };
+                (verb(callee)?, framework, 0.9)
+            }
+            // Angular HttpClient: this.http.get(), this.httpClient.post()
+            Some(_) if matches!(recv.as_str(), "http" | "this.http" | "httpclient" | "this.httpclient") => {
+                (verb(callee)?, "http-client", 0.85)
+            }
+            // Python requests/httpx/aiohttp: requests.post(), httpx.get(), session.get()
+            Some(r @ ("requests" | "httpx")) => (verb(callee)?, r, 0.9),
+            Some("session" | "aiohttp" | "client") if call.string_arg.as_deref().is_some_and(is_url) => {
+                (verb(callee)?, "requests", 0.75)
+            }
+            // Go net/http: http.Get(), http.Post(), http.PostForm()
+            Some("http") if matches!(callee, "Get" | "Post" | "PostForm" | "Head") => {
+                let method = if callee == "PostForm" { "POST".to_string() } else { callee.to_uppercase() };
+                (method, "net/http", 0.85)
+            }
+            // .NET HttpClient: client.GetAsync(), _http.PostAsJsonAsync(), client.SendAsync()
+            Some(_) if recv.contains("client") || recv.contains("http") => {
+                let method = callee.strip_suffix("Async")
+                    .map(|name| name.trim_end_matches("AsJson").trim_end_matches("FromJson"))
+                    .and_then(|name| match name {
+                        "Get" | "GetString" | "GetByteArray" | "GetStream" => Some("GET".to_string()),
+                        "Post" | "Put" | "Patch" | "Delete" => Some(name.to_uppercase()),
+                        "Send" => Some("UNKNOWN".to_string()),
+                        _ => None,
+                    })?;
+                (method, "httpclient", 0.85)
+            }
+            // Spring RestTemplate: restTemplate.getForObject(), restTemplate.exchange()
+            Some(_) if recv.contains("resttemplate") => {
+                let method = match callee {
+                    "getForObject" | "getForEntity" => "GET",
+                    "postForObject" | "postForEntity" | "postForLocation" => "POST",
+                    "put" => "PUT",
+                    "patchForObject" => "PATCH",
+                    "delete" => "DELETE",
+                    "exchange" | "execute" => "UNKNOWN",
+                    _ => return None,
+                };
+                (method.to_string(), "resttemplate", 0.85)
+            }
+            _ => return None,
+        };
+
+        Some(ExternalAccessPoint {
+            url_or_host: call.string_arg.as_deref().map_or_else(|| "unknown".to_string(), url_host),
+            method,
+            file: file.to_string(),
+            line: call.range.start.line,
+            framework: framework.to_string(),
+            confidence,
+        })
+    }
+}
+
+impl Default for ExternalCallDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExternalAccessPoint {
+    /// The request as a data access on `external:<host>`, so reachability can
+    /// treat third-party hosts like tables: reads for GET, HEAD and OPTIONS,
+    /// deletes for DELETE, and writes for anything that may carry a body
+    pub fn as_data_access(&self) -> DataAccessPoint {
+        let operation = match self.method.as_str() {
+            "GET" | "HEAD" | "OPTIONS" => DataOperation::Read,
+            "DELETE" => DataOperation::Delete,
+            _ => DataOperation::Write,
+        };
+        DataAccessPoint {
+            table: format!("{}{}", EXTERNAL_TABLE_PREFIX, self.url_or_host),
+            operation,
+            fields: Vec::new(),
+            file: self.file.clone(),
+            line: self.line,
+            confidence: self.confidence,
+            framework: Some(self.framework.clone()),
+            transaction_id: None,
+        }
+    }
+}
+
+fn is_url(arg: &str) -> bool {
+    arg.starts_with("http://") || arg.starts_with("https://")
+}
+
+/// Static host of a URL (`https://api.stripe.com/v1/charges` -> `api.stripe.com`)
+///
+/// Interpolations (`${...}`, `{...}`) end the static part, so a host they
+/// interrupt (`https://${host}/v1`) leaves the URL as written.
+fn url_host(url: &str) -> String {
+    let Some((_, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let end = rest.find(['/', '?', '#', '$', '{']).unwrap_or(rest.len());
+    let authority = &rest[..end];
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    if host.is_empty() || rest[end..].starts_with(['$', '{']) {
+        return url.to_string();
+    }
+    host.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(path: &str, source: &str) -> Vec<(String, String, String)> {
+        let mut parser = crate::parsers::ParserManager::new();
+        let result = parser.parse_file(path, source).unwrap();
+        ExternalCallDetector::new().detect_from_ast(&result, path).into_iter()
+            .map(|e| (e.url_or_host, e.method, e.framework))
+            .collect()
+    }
+
+    fn call(host: &str, method: &str, framework: &str) -> (String, String, String) {
+        (host.to_string(), method.to_string(), framework.to_string())
+    }
+
+    #[test]
+    fn test_detect_external_calls() {
+        let source = r#"
+export async function syncUser(user) {
+  await fetch(`https://api.segment.io/v1/identify/${user.id}`, { method: 'POST', body: JSON.stringify(user) });
+  const res = await axios.post('https://hooks.slack.com/services/T000', { text: user.email });
+  await this.http.get(`${environment.apiUrl}/profile`);
+  await fetch('/api/me');
+  cache.get('user');
+}
+"#;
+        assert_eq!(detect("src/sync.ts", source), vec![
+            call("api.segment.io", "UNKNOWN", "fetch"),
+            call("hooks.slack.com", "POST", "axios"),
+            call("${environment.apiUrl}/profile", "GET", "http-client"),
+            call("/api/me", "GET", "fetch"),
+        ]);
+
+        let python = "def notify(user):\n    requests.post(f\"https://api.mailgun.net/v3/{domain}/messages\", data=user.email)\n";
+        assert_eq!(detect("app/notify.py", python), vec![call("api.mailgun.net", "POST", "requests")]);
+
+        let csharp = r#"
+public class BillingClient
+{
+    public async Task Charge(Order order)
+    {
+        await _httpClient.PostAsJsonAsync($"https://api.stripe.com/v1/charges/{order.Id}", order);
+    }
+}
+"#;
+        assert_eq!(detect("Billing/BillingClient.cs", csharp), vec![call("api.stripe.com", "POST", "httpclient")]);
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("https://user:pw@api.example.com:8443/v1?q=1"), "api.example.com:8443");
+        assert_eq!(url_host("https://${host}/v1"), "https://${host}/v1");
+        assert_eq!(url_host("http://internal"), "internal");
+    }
+}
@@ -4,26 +4,66 @@
 //! Regex is only used as fallback for:
 //! - SQL strings embedded in code
 //! - Sensitive field names in string literals
+//!
+//! `.sql` files are read by `SqlFileParser`; the tables they define are
+//! cross-referenced against ORM models found in code. `schema.prisma` models
+//! are read by `PrismaSchemaParser` and name the tables behind Prisma client
+//! calls. Django, SQLAlchemy, TypeORM and Entity Framework models, with their
+//! relations, are read by `ModelDetector`. Outbound HTTP requests are found by
+//! `ExternalCallDetector`, and access points inside transaction scopes are
+//! tagged by `TransactionDetector`.
+//!
+//! With a `BoundaryCache`, `scan_files` reuses the results of files whose
+//! content and rules haven't changed since the last scan.
+//! `check_tenant_scoping` flags queries on tenant tables that skip the tenant
+//! filter.
 
 mod types;
 mod detector;
 mod sensitive;
+mod sql;
+mod prisma;
+mod models;
+mod external;
+mod transactions;
+mod cache;
+mod tenant;
 
 pub use types::*;
 pub use detector::DataAccessDetector;
 pub use sensitive::SensitiveFieldDetector;
+pub use sql::{SqlFileParser, SqlFileResult};
+pub use prisma::PrismaSchemaParser;
+pub use models::ModelDetector;
+pub use external::{ExternalCallDetector, EXTERNAL_TABLE_PREFIX};
+pub use transactions::{annotate_transactions, TransactionDetector, TransactionScope};
+pub use cache::{content_hash, BoundaryCache, BOUNDARY_RULES_VERSION};
+pub use tenant::{TenantScopingChecker, IGNORE_MARKER};
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::fs;
 use std::time::Instant;
 
-use crate::parsers::ParserManager;
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::parsers::{ClassInfo, ParserManager};
 
 /// Boundary scanner - AST-first with regex fallbacks
 pub struct BoundaryScanner {
     parser: ParserManager,
     access_detector: DataAccessDetector,
     sensitive_detector: SensitiveFieldDetector,
+    sql_parser: SqlFileParser,
+    prisma_parser: PrismaSchemaParser,
+    model_detector: ModelDetector,
+    external_detector: ExternalCallDetector,
+    transaction_detector: TransactionDetector,
+    report_untransacted_writes: bool,
+    cache: Option<BoundaryCache>,
+    /// Identifies the rules results were produced with, for the cache
+    rules_key: String,
 }
 
 impl BoundaryScanner {
@@ -32,33 +72,111 @@ impl BoundaryScanner {
             parser: ParserManager::new(),
             access_detector: DataAccessDetector::new(),
             sensitive_detector: SensitiveFieldDetector::new(),
+            sql_parser: SqlFileParser::new(),
+            prisma_parser: PrismaSchemaParser::new(),
+            model_detector: ModelDetector::new(),
+            external_detector: ExternalCallDetector::new(),
+            transaction_detector: TransactionDetector::new(),
+            report_untransacted_writes: false,
+            cache: None,
+            rules_key: BOUNDARY_RULES_VERSION.to_string(),
         }
     }
     
+    /// Scanner whose sensitive field detection adds the configured rules
+    pub fn with_config(config: &SensitiveFieldConfig) -> Result<Self, String> {
+        let config_json = serde_json::to_string(config).unwrap_or_default();
+        Ok(Self {
+            sensitive_detector: SensitiveFieldDetector::with_config(config)?,
+            rules_key: format!("{}:{:016x}", BOUNDARY_RULES_VERSION, xxh3_64(config_json.as_bytes())),
+            ..Self::new()
+        })
+    }
+    
+    /// Reuse per-file results cached under `root`'s `.drift/lake/boundaries/`
+    /// in `scan_files`, and cache the files it has to rescan
+    pub fn with_cache(mut self, root: &Path) -> Self {
+        self.cache = Some(BoundaryCache::new(root));
+        self
+    }
+    
+    /// Also report writes made outside any transaction in files that use
+    /// transactions elsewhere, in `BoundaryScanResult::untransacted_writes`
+    pub fn with_untransacted_writes(mut self) -> Self {
+        self.report_untransacted_writes = true;
+        self
+    }
+    
     /// Scan a single file using AST-first approach
     pub fn scan_file(&mut self, path: &Path) -> Option<FileBoundaryResult> {
         let source = fs::read_to_string(path).ok()?;
+        self.scan_source(path, &source)
+    }
+    
+    fn scan_source(&mut self, path: &Path, source: &str) -> Option<FileBoundaryResult> {
         let file_str = path.to_string_lossy().to_string();
         
+        // Migrations and SQL scripts: table definitions plus DML statements
+        if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("sql")) {
+            let mut sql = self.sql_parser.parse(source, &file_str);
+            let transactions = self.transaction_detector.detect_in_sql(source, &file_str);
+            annotate_transactions(&mut sql.access_points, &transactions, 1);
+            return Some(FileBoundaryResult {
+                sensitive_fields: self.sensitive_detector.detect_with_access(source, &file_str, &sql.access_points),
+                file: file_str,
+                access_points: sql.access_points,
+                models: Vec::new(),
+                tables: sql.tables,
+                external_calls: Vec::new(),
+                transactions,
+            });
+        }
+        
+        if path.extension().is_some_and(|e| e == "prisma") {
+            return Some(FileBoundaryResult {
+                sensitive_fields: self.sensitive_detector.detect(source, &file_str),
+                models: self.prisma_parser.parse(source, &file_str),
+                file: file_str,
+                access_points: Vec::new(),
+                tables: Vec::new(),
+                external_calls: Vec::new(),
+                transactions: Vec::new(),
+            });
+        }
+        
         // Try AST parsing first
-        let mut access_points = if let Some(result) = self.parser.parse_file(&file_str, &source) {
-            // Primary: detect from AST call sites
-            self.access_detector.detect_from_ast(&result, &file_str)
+        let (mut access_points, models, external_calls, transactions) = if let Some(result) = self.parser.parse_file(&file_str, source) {
+            // Primary: detect from AST call sites and class declarations
+            let mut models = self.access_detector.detect_models_from_ast(&result, &file_str);
+            models.extend(self.model_detector.detect(&result, source, &file_str));
+            (
+                self.access_detector.detect_from_ast(&result, &file_str),
+                models,
+                self.external_detector.detect_from_ast(&result, &file_str),
+                self.transaction_detector.detect_from_ast(&result, source, &file_str),
+            )
         } else {
-            Vec::new()
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new())
         };
+        annotate_transactions(&mut access_points, &transactions, 0);
         
         // Fallback: detect SQL in raw source (for embedded SQL strings)
-        let sql_access = self.access_detector.detect_sql_in_source(&source, &file_str);
+        let mut sql_access = self.access_detector.detect_sql_in_source(source, &file_str);
+        annotate_transactions(&mut sql_access, &transactions, 1);
         access_points.extend(sql_access);
         
-        // Sensitive fields (regex-based - field names are in strings/identifiers)
-        let sensitive_fields = self.sensitive_detector.detect(&source, &file_str);
+        // Sensitive fields (regex-based - field names are in strings/identifiers),
+        // tied to tables where queries name them
+        let sensitive_fields = self.sensitive_detector.detect_with_access(source, &file_str, &access_points);
         
         Some(FileBoundaryResult {
             file: file_str,
             access_points,
             sensitive_fields,
+            models,
+            tables: Vec::new(),
+            external_calls,
+            transactions,
         })
     }
     
@@ -67,25 +185,88 @@ impl BoundaryScanner {
         let start = Instant::now();
         let mut all_access = Vec::new();
         let mut all_sensitive = Vec::new();
+        let mut all_models = Vec::new();
+        let mut all_tables = Vec::new();
+        let mut all_external = Vec::new();
+        let mut untransacted_writes = Vec::new();
         let mut files_scanned = 0;
+        let mut cache_hits = 0;
+        let mut cache_misses = 0;
         
         for file in files {
             let path = Path::new(file);
-            if let Some(result) = self.scan_file(path) {
+            let Ok(source) = fs::read_to_string(path) else {
+                continue;
+            };
+            let hash = content_hash(&source);
+            let cached = self.cache.as_ref().and_then(|cache| cache.get(file, &hash, &self.rules_key));
+            let result = if cached.is_some() {
+                cache_hits += 1;
+                cached
+            } else {
+                cache_misses += 1;
+                let result = self.scan_source(path, &source);
+                if let (Some(cache), Some(result)) = (&self.cache, &result) {
+                    // Best effort: an entry that can't be written is rescanned next time
+                    let _ = cache.put(file, &hash, &self.rules_key, result);
+                }
+                result
+            };
+            
+            if let Some(result) = result {
+                if self.report_untransacted_writes && !result.transactions.is_empty() {
+                    untransacted_writes.extend(result.access_points.iter()
+                        .filter(|a| a.operation != DataOperation::Read && !a.in_transaction())
+                        .cloned());
+                }
                 all_access.extend(result.access_points);
                 all_sensitive.extend(result.sensitive_fields);
+                all_models.extend(result.models);
+                all_tables.extend(result.tables);
+                all_external.extend(result.external_calls);
                 files_scanned += 1;
             }
         }
         
+        resolve_prisma_tables(&mut all_access, &all_models);
+        let unmigrated_fields = find_unmigrated_fields(&all_models, &all_tables);
+        
         BoundaryScanResult {
             access_points: all_access,
             sensitive_fields: all_sensitive,
-            models: Vec::new(),
+            models: all_models,
+            unmigrated_fields,
+            external_calls: all_external,
+            untransacted_writes,
             files_scanned,
+            cache_hits,
+            cache_misses,
             duration_ms: start.elapsed().as_millis() as u64,
         }
     }
+    
+    /// Scan `files` and flag accesses to tenant-scoped tables whose query
+    /// never names the scoping column
+    pub fn check_tenant_scoping(&mut self, files: &[String], config: &TenantScopingConfig) -> Result<Vec<TenantViolation>, String> {
+        let checker = TenantScopingChecker::new(config)?;
+        let result = self.scan_files(files);
+        
+        let mut by_file: HashMap<String, Vec<DataAccessPoint>> = HashMap::new();
+        for access in result.access_points {
+            by_file.entry(access.file.clone()).or_default().push(access);
+        }
+        
+        let mut violations = Vec::new();
+        for file in files {
+            let Some(access_points) = by_file.get(file) else {
+                continue;
+            };
+            if let Ok(source) = fs::read_to_string(file) {
+                violations.extend(checker.check_file(&source, file, access_points));
+            }
+        }
+        Ok(violations)
+    }
 }
 
 impl Default for BoundaryScanner {
@@ -95,11 +276,74 @@ impl Default for BoundaryScanner {
 }
 
 /// Result for a single file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileBoundaryResult {
     pub file: String,
     pub access_points: Vec<DataAccessPoint>,
     pub sensitive_fields: Vec<SensitiveField>,
+    pub models: Vec<ORMModel>,
+    /// Tables defined by a `.sql` file
+    pub tables: Vec<ClassInfo>,
+    /// Outbound HTTP requests
+    pub external_calls: Vec<ExternalAccessPoint>,
+    /// Transaction scopes
+    pub transactions: Vec<TransactionScope>,
+}
+
+/// Point Prisma client calls at their model's table
+///
+/// Calls name the model's client accessor (`prisma.orderItem` for
+/// `OrderItem`), which differs from the table whenever `@@map` is used.
+fn resolve_prisma_tables(access_points: &mut [DataAccessPoint], models: &[ORMModel]) {
+    let tables: HashMap<String, &str> = models.iter()
+        .filter(|m| m.framework == "prisma")
+        .map(|m| {
+            let mut chars = m.name.chars();
+            let accessor = chars.next().map(|c| c.to_lowercase().chain(chars).collect()).unwrap_or_default();
+            (accessor, m.table_name.as_str())
+        })
+        .collect();
+    for access in access_points.iter_mut().filter(|a| a.framework.as_deref() == Some("prisma")) {
+        if let Some(table) = tables.get(&access.table) {
+            access.table = table.to_string();
+        }
+    }
+}
+
+/// Model fields with no column in the migrations that define the model's table
+///
+/// Models whose table never appears in a migration are skipped, since their
+/// schema is managed somewhere drift cannot see. Names are compared ignoring
+/// case and underscores so `createdAt` matches `created_at`.
+fn find_unmigrated_fields(models: &[ORMModel], tables: &[ClassInfo]) -> Vec<UnmigratedField> {
+    let normalize = |name: &str| name.replace('_', "").to_lowercase();
+    
+    let mut columns: HashMap<String, HashSet<String>> = HashMap::new();
+    for table in tables {
+        columns.entry(table.name.to_lowercase())
+            .or_default()
+            .extend(table.properties.iter().map(|p| normalize(&p.name)));
+    }
+    
+    let mut unmigrated = Vec::new();
+    for model in models {
+        let table = model.table_name.rsplit('.').next().unwrap_or(&model.table_name);
+        let Some(known) = columns.get(&table.to_lowercase()) else {
+            continue;
+        };
+        for field in &model.fields {
+            if !known.contains(&normalize(field)) {
+                unmigrated.push(UnmigratedField {
+                    model: model.name.clone(),
+                    table: model.table_name.clone(),
+                    field: field.clone(),
+                    file: model.file.clone(),
+                    line: model.line,
+                });
+            }
+        }
+    }
+    unmigrated
 }
 
 #[cfg(test)]
@@ -115,4 +359,116 @@ mod tests {
         let access = scanner.access_detector.detect_sql_in_source(source, "test.ts");
         assert!(!access.is_empty());
     }
+    
+    #[test]
+    fn test_scan_files_flags_unmigrated_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let migration = dir.path().join("001_create_users.sql");
+        fs::write(&migration, "CREATE TABLE users (id SERIAL PRIMARY KEY, email TEXT);\nALTER TABLE users ADD COLUMN created_at TIMESTAMP;\nDELETE FROM users WHERE email IS NULL;").unwrap();
+        
+        let mut scanner = BoundaryScanner::new();
+        let models = vec![ORMModel {
+            name: "User".to_string(),
+            table_name: "users".to_string(),
+            fields: vec!["email".to_string(), "createdAt".to_string(), "nickname".to_string()],
+            relations: Vec::new(),
+            file: "app/Models/User.php".to_string(),
+            line: 5,
+            framework: "eloquent".to_string(),
+            confidence: 0.95,
+        }];
+        let file = scanner.scan_file(&migration).unwrap();
+        assert_eq!(file.tables.len(), 2);
+        assert_eq!(file.access_points.len(), 1);
+        assert_eq!(file.access_points[0].operation, DataOperation::Delete);
+        
+        let unmigrated = find_unmigrated_fields(&models, &file.tables);
+        assert_eq!(unmigrated.len(), 1);
+        assert_eq!(unmigrated[0].field, "nickname");
+        
+        // Tables without any migration are not reported
+        assert!(find_unmigrated_fields(&models, &[]).is_empty());
+        
+        let result = scanner.scan_files(&[migration.to_string_lossy().to_string()]);
+        assert_eq!(result.files_scanned, 1);
+        assert!(result.unmigrated_fields.is_empty());
+    }
+    
+    #[test]
+    fn test_scan_files_prisma_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = dir.path().join("schema.prisma");
+        fs::write(&schema, "model User {\n  id    Int    @id\n  email String\n  posts Post[]\n  @@map(\"users\")\n}\n\nmodel Post {\n  id     Int  @id\n  author User @relation(fields: [authorId], references: [id])\n  authorId Int\n}\n").unwrap();
+        let service = dir.path().join("users.ts");
+        fs::write(&service, "export async function cleanup(prisma) {\n  const users = await prisma.user.findMany();\n  await prisma.post.upsert({ where: { id: 1 }, create: {}, update: {} });\n  await this.prisma.user.deleteMany({ where: { email: null } });\n}\n").unwrap();
+        
+        let mut scanner = BoundaryScanner::new();
+        let result = scanner.scan_files(&[
+            schema.to_string_lossy().to_string(),
+            service.to_string_lossy().to_string(),
+        ]);
+        
+        let models: Vec<(&str, &str)> = result.models.iter().map(|m| (m.name.as_str(), m.table_name.as_str())).collect();
+        assert_eq!(models, vec![("User", "users"), ("Post", "Post")]);
+        assert_eq!(result.models[1].relations[0].target, "User");
+        
+        let access: Vec<(&str, DataOperation)> = result.access_points.iter().map(|a| (a.table.as_str(), a.operation)).collect();
+        assert_eq!(access, vec![
+            ("users", DataOperation::Read),
+            ("Post", DataOperation::Write),
+            ("users", DataOperation::Delete),
+        ]);
+        assert!(result.models.iter().all(|m| m.confidence >= 0.9));
+        assert!(result.access_points.iter().all(|a| a.confidence >= 0.9));
+    }
+    
+    #[test]
+    fn test_scan_files_untransacted_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("backfill.sql");
+        fs::write(&script, "BEGIN;\nUPDATE accounts SET balance = 0;\nCOMMIT;\nDELETE FROM sessions;\n").unwrap();
+        let report = dir.path().join("report.sql");
+        fs::write(&report, "DELETE FROM reports;\n").unwrap();
+        let files = [script.to_string_lossy().to_string(), report.to_string_lossy().to_string()];
+        
+        let result = BoundaryScanner::new().with_untransacted_writes().scan_files(&files);
+        let transactions: Vec<(&str, bool)> = result.access_points.iter().map(|a| (a.table.as_str(), a.in_transaction())).collect();
+        assert_eq!(transactions, vec![("accounts", true), ("sessions", false), ("reports", false)]);
+        assert_eq!(result.access_points[0].transaction_id, Some(format!("{}:1", files[0])));
+        
+        // Only files that use transactions elsewhere are reported
+        let untransacted: Vec<&str> = result.untransacted_writes.iter().map(|a| a.table.as_str()).collect();
+        assert_eq!(untransacted, vec!["sessions"]);
+        assert!(BoundaryScanner::new().scan_files(&files).untransacted_writes.is_empty());
+    }
+    
+    #[test]
+    fn test_scan_files_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let users = dir.path().join("users.sql");
+        fs::write(&users, "SELECT email FROM users;\n").unwrap();
+        let orders = dir.path().join("orders.sql");
+        fs::write(&orders, "DELETE FROM orders;\n").unwrap();
+        let files = [users.to_string_lossy().to_string(), orders.to_string_lossy().to_string()];
+        let scan = |scanner: BoundaryScanner| {
+            let result = scanner.with_cache(dir.path()).scan_files(&files);
+            (result.cache_hits, result.cache_misses, result.access_points.len(), result.sensitive_fields.len())
+        };
+        
+        assert_eq!(scan(BoundaryScanner::new()), (0, 2, 2, 1));
+        assert_eq!(scan(BoundaryScanner::new()), (2, 0, 2, 1));
+        assert!(dir.path().join(".drift/lake/boundaries").is_dir());
+        
+        // Changed content and changed rules are both rescanned
+        fs::write(&orders, "DELETE FROM orders;\nDELETE FROM carts;\n").unwrap();
+        assert_eq!(scan(BoundaryScanner::new()), (1, 1, 3, 1));
+        let config = SensitiveFieldConfig {
+            rules: vec![SensitiveFieldRule { pattern: "email".to_string(), suppress: true, ..Default::default() }],
+        };
+        assert_eq!(scan(BoundaryScanner::with_config(&config).unwrap()), (0, 2, 3, 0));
+        
+        // Without a cache every file is scanned
+        let uncached = BoundaryScanner::new().scan_files(&files);
+        assert_eq!((uncached.cache_hits, uncached.cache_misses), (0, 2));
+    }
 }
@@ -0,0 +1,732 @@
+//! ORM model detector - Models and their relations from class declarations
+//!
+//! Read from the AST, since relation targets and loading options live in
+//! call and decorator arguments the parsers don't keep:
+//! - Django: `models.Model` subclasses; `ForeignKey`, `OneToOneField` and
+//!   `ManyToManyField` relate them
+//! - SQLAlchemy: classes with `__tablename__` or `Column`s; `relationship()`
+//!   relates them, eagerly with `lazy="joined"`, `"selectin"`, ...
+//! - TypeORM: `@Entity` classes; `@ManyToOne`, `@OneToMany`, `@OneToOne`
+//!   and `@ManyToMany` relate them, eagerly with `{ eager: true }`
+//! - Entity Framework: classes named by a `DbSet<T>`, marked `[Table]` or
+//!   keyed by `[Key]`/`Id`; collection and reference navigation properties
+//!   relate them
+//!
+//! Model lines are 0-based rows, like AST ranges.
+
+use std::collections::HashMap;
+
+use tree_sitter::Node;
+
+use super::types::{ModelRelation, ORMModel, RelationKind};
+use crate::parsers::{Language, ParseResult};
+
+/// SQLAlchemy `lazy` strategies that load the relation with its model
+const EAGER_LAZY_STRATEGIES: &[&str] = &["joined", "selectin", "subquery", "immediate"];
+
+/// Collection types of Entity Framework collection navigations
+const EF_COLLECTIONS: &[&str] = &["ICollection", "IList", "List", "HashSet", "ISet", "IEnumerable", "Collection"];
+
+/// Capitalized .NET types that are never entities
+const DOTNET_VALUE_TYPES: &[&str] = &[
+    "String", "DateTime", "DateTimeOffset", "DateOnly", "TimeOnly", "TimeSpan", "Guid", "Decimal",
+    "Boolean", "Byte", "Int16", "Int32", "Int64", "Double", "Single", "Char", "Object", "Uri",
+];
+
+/// ORM model detector for Python, TypeScript/JavaScript and C#
+pub struct ModelDetector;
+
+impl ModelDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Models declared in a parsed file
+    pub fn detect(&self, result: &ParseResult, source: &str, file: &str) -> Vec<ORMModel> {
+        let Some(tree) = result.tree.as_ref() else {
+            return Vec::new();
+        };
+        let src = source.as_bytes();
+        let root = tree.root_node();
+        let classes = |kinds: &[&str]| descendants(root).into_iter().filter(|n| kinds.contains(&n.kind())).collect::<Vec<_>>();
+        match result.language {
+            Language::Python => classes(&["class_definition"]).into_iter()
+                .filter_map(|class| python_model(class, src, file))
+                .collect(),
+            Language::TypeScript | Language::JavaScript => classes(&["class_declaration", "abstract_class_declaration", "class"]).into_iter()
+                .filter_map(|class| typeorm_model(class, src, file))
+                .collect(),
+            Language::CSharp => {
+                let db_sets = ef_db_sets(root, src);
+                classes(&["class_declaration"]).into_iter()
+                    .filter_map(|class| ef_model(class, src, file, &db_sets))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Default for ModelDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Django and SQLAlchemy
+// ============================================================================
+
+/// `name = call(...)` (or `name: T = call(...)`) in a class body
+struct ClassAssignment<'a> {
+    name: String,
+    annotation: Option<String>,
+    value: Node<'a>,
+}
+
+fn python_model(class: Node, src: &[u8], file: &str) -> Option<ORMModel> {
+    let name = text(class.child_by_field_name("name")?, src).to_string();
+    let bases: Vec<&str> = class.child_by_field_name("superclasses")
+        .map(|args| named_children(args).into_iter().map(|b| text(b, src)).collect())
+        .unwrap_or_default();
+    let body = class.child_by_field_name("body")?;
+    let assignments = python_assignments(body, src);
+
+    let string_value = |assignments: &[ClassAssignment], key: &str| assignments.iter()
+        .find(|a| a.name == key)
+        .filter(|a| a.value.kind() == "string")
+        .map(|a| unquote(text(a.value, src)));
+    let calls = |callees: &[&str]| assignments.iter()
+        .filter(|a| a.value.kind() == "call" && callees.contains(&callee(a.value, src)))
+        .count();
+
+    let table = string_value(&assignments, "__tablename__");
+    if table.is_some() || calls(&["Column", "mapped_column", "relationship"]) > 0 {
+        return Some(sqlalchemy_model(name, table, &assignments, class, src, file));
+    }
+
+    let is_django_field = |a: &&ClassAssignment| {
+        a.value.kind() == "call" && {
+            let callee = callee(a.value, src);
+            callee.ends_with("Field") || callee == "ForeignKey"
+        }
+    };
+    if !bases.iter().any(|b| b.ends_with("Model")) || !assignments.iter().any(|a| is_django_field(&a)) {
+        return None;
+    }
+    // `class Meta: db_table = "..."`
+    let db_table = named_children(body).into_iter()
+        .filter(|n| n.kind() == "class_definition")
+        .filter(|n| n.child_by_field_name("name").is_some_and(|m| text(m, src) == "Meta"))
+        .find_map(|meta| string_value(&python_assignments(meta.child_by_field_name("body")?, src), "db_table"));
+
+    let mut model = orm_model(&name, db_table.clone().unwrap_or_else(|| name.to_lowercase()), class, file, "django");
+    model.confidence = if db_table.is_some() { 0.95 } else { 0.85 };
+    for assignment in assignments.iter().filter(is_django_field) {
+        let (kind, is_list) = match callee(assignment.value, src) {
+            "ForeignKey" => (RelationKind::BelongsTo, false),
+            "OneToOneField" => (RelationKind::HasOne, false),
+            "ManyToManyField" => (RelationKind::ManyToMany, true),
+            _ => {
+                model.fields.push(assignment.name.clone());
+                continue;
+            }
+        };
+        let args = call_arguments(assignment.value);
+        let target = args.iter()
+            .find(|a| a.kind() != "keyword_argument")
+            .copied()
+            .or_else(|| keyword_argument(&args, "to", src))
+            .map(|t| python_model_name(t, src))
+            .unwrap_or_default();
+        model.relations.push(ModelRelation {
+            field: assignment.name.clone(),
+            target: if target == "self" { name.clone() } else { target },
+            kind,
+            is_list,
+            // Django names the column after the field
+            fields: if is_list { Vec::new() } else { vec![format!("{}_id", assignment.name)] },
+            references: Vec::new(),
+            eager: false,
+        });
+    }
+    Some(model)
+}
+
+fn sqlalchemy_model(
+    name: String,
+    table: Option<String>,
+    assignments: &[ClassAssignment],
+    class: Node,
+    src: &[u8],
+    file: &str,
+) -> ORMModel {
+    let mut model = orm_model(&name, table.clone().unwrap_or_else(|| name.to_lowercase()), class, file, "sqlalchemy");
+    model.confidence = if table.is_some() { 0.95 } else { 0.85 };
+
+    // Columns first: relations look up the foreign keys they hold
+    let mut foreign_keys: Vec<(String, String)> = Vec::new();
+    for assignment in assignments.iter().filter(|a| a.value.kind() == "call") {
+        if !matches!(callee(assignment.value, src), "Column" | "mapped_column") {
+            continue;
+        }
+        model.fields.push(assignment.name.clone());
+        // `ForeignKey("users.id")`
+        let reference = descendants(assignment.value).into_iter()
+            .filter(|n| n.kind() == "call" && callee(*n, src) == "ForeignKey")
+            .find_map(|fk| call_arguments(fk).into_iter().find(|a| a.kind() == "string"));
+        if let Some(reference) = reference {
+            foreign_keys.push((assignment.name.clone(), unquote(text(reference, src))));
+        }
+    }
+
+    for assignment in assignments.iter().filter(|a| a.value.kind() == "call" && callee(a.value, src) == "relationship") {
+        let args = call_arguments(assignment.value);
+        let annotation = assignment.annotation.as_deref().unwrap_or("");
+        let target = args.iter()
+            .find(|a| a.kind() != "keyword_argument")
+            .map(|t| python_model_name(*t, src))
+            .or_else(|| annotated_model(annotation))
+            .unwrap_or_default();
+        let keyword = |key: &str| keyword_argument(&args, key, src).map(|v| text(v, src).to_string());
+        let uselist = keyword("uselist");
+        let eager = keyword("lazy").is_some_and(|lazy| lazy == "False" || EAGER_LAZY_STRATEGIES.contains(&unquote(&lazy).as_str()));
+        let is_list = match uselist.as_deref() {
+            Some("False") => false,
+            Some("True") => true,
+            _ => ["List[", "list[", "Set[", "set["].iter().any(|l| annotation.contains(l))
+                || (assignment.annotation.is_none() && keyword("secondary").is_none() && !has_foreign_key(&foreign_keys, &target)),
+        };
+        let foreign_key = (!is_list).then(|| foreign_keys.iter().find(|(_, r)| references_model(r, &target))).flatten();
+        let kind = if keyword("secondary").is_some() {
+            RelationKind::ManyToMany
+        } else if is_list {
+            RelationKind::HasMany
+        } else if foreign_key.is_some() {
+            RelationKind::BelongsTo
+        } else {
+            RelationKind::HasOne
+        };
+        model.relations.push(ModelRelation {
+            field: assignment.name.clone(),
+            target,
+            kind,
+            is_list: is_list || kind == RelationKind::ManyToMany,
+            fields: foreign_key.map(|(column, _)| vec![column.clone()]).unwrap_or_default(),
+            references: foreign_key
+                .and_then(|(_, r)| r.rsplit_once('.'))
+                .map(|(_, column)| vec![column.to_string()])
+                .unwrap_or_default(),
+            eager,
+        });
+    }
+    model
+}
+
+fn python_assignments<'a>(body: Node<'a>, src: &[u8]) -> Vec<ClassAssignment<'a>> {
+    named_children(body).into_iter()
+        .filter(|n| n.kind() == "expression_statement")
+        .filter_map(|statement| statement.named_child(0))
+        .filter(|n| n.kind() == "assignment")
+        .filter_map(|assignment| {
+            let left = assignment.child_by_field_name("left").filter(|l| l.kind() == "identifier")?;
+            Some(ClassAssignment {
+                name: text(left, src).to_string(),
+                annotation: assignment.child_by_field_name("type").map(|t| text(t, src).to_string()),
+                value: assignment.child_by_field_name("right")?,
+            })
+        })
+        .collect()
+}
+
+/// Model named by a relation argument: `User`, `"User"`, `"auth.User"`
+fn python_model_name(node: Node, src: &[u8]) -> String {
+    let name = unquote(text(node, src));
+    name.rsplit('.').next().unwrap_or(&name).to_string()
+}
+
+/// Model inside a `Mapped[List["Post"]]` annotation
+fn annotated_model(annotation: &str) -> Option<String> {
+    let inner = annotation.rsplit('[').next()?.trim_end_matches(']');
+    let name = unquote(inner);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Whether a `ForeignKey("users.id")` reference points at `model`'s table
+fn references_model(reference: &str, model: &str) -> bool {
+    let table = reference.split('.').next().unwrap_or(reference).to_lowercase();
+    !model.is_empty() && table.starts_with(&model.to_lowercase())
+}
+
+fn has_foreign_key(foreign_keys: &[(String, String)], model: &str) -> bool {
+    foreign_keys.iter().any(|(_, r)| references_model(r, model))
+}
+
+fn keyword_argument<'a>(args: &[Node<'a>], key: &str, src: &[u8]) -> Option<Node<'a>> {
+    args.iter()
+        .filter(|a| a.kind() == "keyword_argument")
+        .find(|a| a.child_by_field_name("name").is_some_and(|n| text(n, src) == key))
+        .and_then(|a| a.child_by_field_name("value"))
+}
+
+// ============================================================================
+// TypeORM
+// ============================================================================
+
+fn typeorm_model(class: Node, src: &[u8], file: &str) -> Option<ORMModel> {
+    let name = text(class.child_by_field_name("name")?, src).to_string();
+    // `@Entity() export class User` puts the decorator on the export
+    let mut decorators = decorators_of(class);
+    if let Some(export) = class.parent().filter(|p| p.kind() == "export_statement") {
+        decorators.extend(decorators_of(export));
+    }
+    let entity = decorators.iter().find(|d| decorator_name(**d, src) == "Entity")?;
+    let entity_args = decorator_arguments(*entity);
+    let table = entity_args.iter()
+        .find(|a| a.kind() == "string")
+        .map(|a| unquote(text(*a, src)))
+        .or_else(|| entity_args.iter().find_map(|a| object_property(*a, "name", src)).map(|v| unquote(text(v, src))));
+
+    let mut model = orm_model(&name, table.clone().unwrap_or_else(|| snake_case(&name)), class, file, "typeorm");
+    model.confidence = 0.95;
+    let body = class.child_by_field_name("body")?;
+    let mut pending: Vec<Node> = Vec::new();
+    for member in named_children(body) {
+        if member.kind() == "decorator" {
+            pending.push(member);
+            continue;
+        }
+        let mut member_decorators = std::mem::take(&mut pending);
+        if !matches!(member.kind(), "public_field_definition" | "field_definition") {
+            continue;
+        }
+        member_decorators.extend(decorators_of(member));
+        let Some(field) = member.child_by_field_name("name").or_else(|| member.child_by_field_name("property")) else { continue };
+        let field = text(field, src).to_string();
+
+        let join_column = member_decorators.iter()
+            .find(|d| decorator_name(**d, src) == "JoinColumn")
+            .and_then(|d| decorator_arguments(*d).into_iter().find_map(|a| object_property(a, "name", src)))
+            .map(|v| unquote(text(v, src)));
+        for decorator in &member_decorators {
+            let decorator_name = decorator_name(*decorator, src);
+            let (kind, is_list) = match decorator_name {
+                "ManyToOne" => (RelationKind::BelongsTo, false),
+                "OneToOne" => (RelationKind::HasOne, false),
+                "OneToMany" => (RelationKind::HasMany, true),
+                "ManyToMany" => (RelationKind::ManyToMany, true),
+                _ => {
+                    if decorator_name.ends_with("Column") && decorator_name != "JoinColumn" {
+                        model.fields.push(field.clone());
+                    }
+                    continue;
+                }
+            };
+            let args = decorator_arguments(*decorator);
+            // `() => User`, or the entity's name as a string
+            let target = args.first()
+                .map(|a| match a.kind() {
+                    "arrow_function" => a.child_by_field_name("body").map_or("", |b| text(b, src)).to_string(),
+                    _ => unquote(text(*a, src)),
+                })
+                .unwrap_or_default();
+            let eager = args.iter()
+                .filter_map(|a| object_property(*a, "eager", src))
+                .any(|v| text(v, src) == "true");
+            let fields = match (kind, &join_column) {
+                (_, Some(column)) => vec![column.clone()],
+                // TypeORM's default join column
+                (RelationKind::BelongsTo, None) => vec![format!("{}Id", field)],
+                _ => Vec::new(),
+            };
+            model.relations.push(ModelRelation {
+                field: field.clone(),
+                target,
+                kind,
+                is_list,
+                fields,
+                references: Vec::new(),
+                eager,
+            });
+        }
+    }
+    Some(model)
+}
+
+fn decorators_of(node: Node) -> Vec<Node> {
+    named_children(node).into_iter().filter(|n| n.kind() == "decorator").collect()
+}
+
+/// `Entity` for `@Entity("users")` and `@Entity`
+fn decorator_name<'a>(decorator: Node, src: &'a [u8]) -> &'a str {
+    let Some(expression) = decorator.named_child(0) else { return "" };
+    let function = match expression.kind() {
+        "call_expression" => expression.child_by_field_name("function"),
+        _ => Some(expression),
+    };
+    function.map_or("", |f| {
+        let name = text(f, src);
+        name.rsplit('.').next().unwrap_or(name)
+    })
+}
+
+fn decorator_arguments(decorator: Node) -> Vec<Node> {
+    decorator.named_child(0)
+        .filter(|e| e.kind() == "call_expression")
+        .and_then(|call| call.child_by_field_name("arguments"))
+        .map(named_children)
+        .unwrap_or_default()
+}
+
+/// Value of `key` in an object literal
+fn object_property<'a>(object: Node<'a>, key: &str, src: &[u8]) -> Option<Node<'a>> {
+    if object.kind() != "object" {
+        return None;
+    }
+    named_children(object).into_iter()
+        .filter(|p| p.kind() == "pair")
+        .find(|p| p.child_by_field_name("key").is_some_and(|k| unquote(text(k, src)) == key))
+        .and_then(|p| p.child_by_field_name("value"))
+}
+
+/// TypeORM's default table name: `UserProfile` -> `user_profile`
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+// ============================================================================
+// Entity Framework
+// ============================================================================
+
+/// A C# property: name, type, attributes (name and first argument), virtual
+struct CsProperty {
+    name: String,
+    type_name: String,
+    attributes: Vec<(String, Option<String>)>,
+    is_virtual: bool,
+}
+
+/// Entity type -> `DbSet<T>` property name, for every `DbSet` in the file
+fn ef_db_sets(root: Node, src: &[u8]) -> HashMap<String, String> {
+    descendants(root).into_iter()
+        .filter(|n| n.kind() == "property_declaration")
+        .filter_map(|property| {
+            let (generic, argument) = generic_type(text(property.child_by_field_name("type")?, src))?;
+            let name = text(property.child_by_field_name("name")?, src);
+            (generic == "DbSet").then(|| (argument, name.to_string()))
+        })
+        .collect()
+}
+
+fn ef_model(class: Node, src: &[u8], file: &str, db_sets: &HashMap<String, String>) -> Option<ORMModel> {
+    let name = text(class.child_by_field_name("name")?, src).to_string();
+    let class_attributes = cs_attributes(class, src);
+    let properties: Vec<CsProperty> = class.child_by_field_name("body")
+        .map(|body| named_children(body).into_iter()
+            .filter(|n| n.kind() == "property_declaration")
+            .filter_map(|n| cs_property(n, src))
+            .collect())
+        .unwrap_or_default();
+
+    let has_attribute = |attributes: &[(String, Option<String>)], attribute: &str| attributes.iter().any(|(a, _)| a == attribute);
+    let table_attribute = class_attributes.iter().find(|(a, _)| a == "Table").and_then(|(_, table)| table.clone());
+    let declared = table_attribute.is_some()
+        || db_sets.contains_key(&name)
+        || properties.iter().any(|p| has_attribute(&p.attributes, "Key"));
+    // Foreign keys named by `[ForeignKey("Author")]` on the key property
+    let foreign_key_of = |navigation: &str| properties.iter()
+        .find(|p| p.attributes.iter().any(|(a, arg)| a == "ForeignKey" && arg.as_deref() == Some(navigation)))
+        .map(|p| p.name.clone());
+
+    let mut fields = Vec::new();
+    let mut relations = Vec::new();
+    for property in &properties {
+        let type_name = property.type_name.trim_end_matches('?');
+        if let Some((collection, target)) = generic_type(type_name) {
+            if EF_COLLECTIONS.contains(&collection.as_str()) && is_entity_type(&target) {
+                relations.push(ModelRelation {
+                    field: property.name.clone(),
+                    target,
+                    kind: RelationKind::HasMany,
+                    is_list: true,
+                    fields: Vec::new(),
+                    references: Vec::new(),
+                    eager: false,
+                });
+                continue;
+            }
+        }
+        let target = type_name.rsplit('.').next().unwrap_or(type_name);
+        let foreign_key = property.attributes.iter()
+            .find(|(a, _)| a == "ForeignKey")
+            .and_then(|(_, key)| key.clone())
+            .or_else(|| foreign_key_of(&property.name))
+            .or_else(|| {
+                let key = format!("{}Id", property.name);
+                properties.iter().any(|p| p.name == key).then_some(key)
+            });
+        if is_entity_type(target) && (foreign_key.is_some() || property.is_virtual) {
+            relations.push(ModelRelation {
+                field: property.name.clone(),
+                target: target.to_string(),
+                kind: if foreign_key.is_some() { RelationKind::BelongsTo } else { RelationKind::HasOne },
+                is_list: false,
+                fields: foreign_key.into_iter().collect(),
+                references: Vec::new(),
+                eager: false,
+            });
+        } else {
+            fields.push(property.name.clone());
+        }
+    }
+
+    // Without an attribute or `DbSet`, EF's key convention plus a navigation
+    let keyed = fields.iter().any(|f| f == "Id" || *f == format!("{}Id", name));
+    if !declared && (!keyed || relations.is_empty()) {
+        return None;
+    }
+    let table = table_attribute.or_else(|| db_sets.get(&name).cloned()).unwrap_or_else(|| name.clone());
+    let mut model = orm_model(&name, table, class, file, "ef-core");
+    model.fields = fields;
+    model.relations = relations;
+    model.confidence = if declared { 0.9 } else { 0.7 };
+    Some(model)
+}
+
+fn cs_property(node: Node, src: &[u8]) -> Option<CsProperty> {
+    Some(CsProperty {
+        name: text(node.child_by_field_name("name")?, src).to_string(),
+        type_name: text(node.child_by_field_name("type")?, src).to_string(),
+        attributes: cs_attributes(node, src),
+        is_virtual: named_children(node).into_iter().any(|n| n.kind() == "modifier" && text(n, src) == "virtual"),
+    })
+}
+
+/// Attributes of a declaration, with their first argument unquoted
+fn cs_attributes(node: Node, src: &[u8]) -> Vec<(String, Option<String>)> {
+    named_children(node).into_iter()
+        .filter(|n| n.kind() == "attribute_list")
+        .flat_map(named_children)
+        .filter(|n| n.kind() == "attribute")
+        .filter_map(|attribute| {
+            let name = text(attribute.child_by_field_name("name")?, src);
+            let name = name.strip_suffix("Attribute").unwrap_or(name);
+            let argument = named_children(attribute).into_iter()
+                .find(|n| n.kind() == "attribute_argument_list")
+                .and_then(|args| args.named_child(0))
+                .map(|arg| unquote(text(arg, src)));
+            Some((name.rsplit('.').next().unwrap_or(name).to_string(), argument))
+        })
+        .collect()
+}
+
+/// `("ICollection", "Post")` for `ICollection<Post>`
+fn generic_type(type_name: &str) -> Option<(String, String)> {
+    let (generic, rest) = type_name.split_once('<')?;
+    let argument = rest.strip_suffix('>')?.trim();
+    (!argument.contains(',')).then(|| (generic.trim().to_string(), argument.to_string()))
+}
+
+/// A capitalized, non-.NET type, which may be an entity
+fn is_entity_type(type_name: &str) -> bool {
+    type_name.starts_with(|c: char| c.is_ascii_uppercase())
+        && type_name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !DOTNET_VALUE_TYPES.contains(&type_name)
+}
+
+// ============================================================================
+// Shared
+// ============================================================================
+
+fn orm_model(name: &str, table: String, class: Node, file: &str, framework: &str) -> ORMModel {
+    ORMModel {
+        name: name.to_string(),
+        table_name: table,
+        fields: Vec::new(),
+        relations: Vec::new(),
+        file: file.to_string(),
+        line: class.start_position().row as u32,
+        framework: framework.to_string(),
+        confidence: 0.9,
+    }
+}
+
+/// Last segment of a Python call's function: `ForeignKey` for `models.ForeignKey(...)`
+fn callee<'a>(call: Node, src: &'a [u8]) -> &'a str {
+    call.child_by_field_name("function").map_or("", |f| {
+        let name = text(f, src);
+        name.rsplit('.').next().unwrap_or(name)
+    })
+}
+
+fn call_arguments(call: Node) -> Vec<Node> {
+    call.child_by_field_name("arguments").map(named_children).unwrap_or_default()
+}
+
+fn text<'a>(node: Node, src: &'a [u8]) -> &'a str {
+    node.utf8_text(src).unwrap_or("")
+}
+
+/// A string literal's content; anything else unchanged
+fn unquote(literal: &str) -> String {
+    let literal = literal.trim().trim_start_matches(['r', 'b', 'u', 'f', '@']);
+    literal.trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string()
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+/// `node` and everything below it, in document order
+fn descendants(node: Node) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        nodes.push(node);
+        let mut children = named_children(node);
+        children.reverse();
+        stack.extend(children);
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ParserManager;
+
+    fn models(path: &str, source: &str) -> Vec<ORMModel> {
+        let result = ParserManager::new().parse_file(path, source).unwrap();
+        ModelDetector::new().detect(&result, source, path)
+    }
+
+    /// `(field, target, kind, fields, eager)` of each relation
+    fn relations(model: &ORMModel) -> Vec<(&str, &str, RelationKind, Vec<&str>, bool)> {
+        model.relations.iter()
+            .map(|r| (r.field.as_str(), r.target.as_str(), r.kind, r.fields.iter().map(String::as_str).collect(), r.eager))
+            .collect()
+    }
+
+    #[test]
+    fn test_django_and_sqlalchemy_relations() {
+        let django = models("blog/models.py", concat!(
+            "from django.db import models\n",
+            "\n",
+            "class Post(models.Model):\n",
+            "    title = models.CharField(max_length=200)\n",
+            "    author = models.ForeignKey('auth.User', on_delete=models.CASCADE)\n",
+            "    tags = models.ManyToManyField(Tag)\n",
+            "    parent = models.OneToOneField('self', null=True, on_delete=models.SET_NULL)\n",
+            "\n",
+            "    class Meta:\n",
+            "        db_table = 'blog_posts'\n",
+            "\n",
+            "class PostForm(forms.Form):\n",
+            "    title = forms.CharField()\n",
+        ));
+        assert_eq!(django.len(), 1);
+        assert_eq!((django[0].name.as_str(), django[0].table_name.as_str(), django[0].framework.as_str()), ("Post", "blog_posts", "django"));
+        assert_eq!(django[0].fields, vec!["title"]);
+        assert_eq!(relations(&django[0]), vec![
+            ("author", "User", RelationKind::BelongsTo, vec!["author_id"], false),
+            ("tags", "Tag", RelationKind::ManyToMany, vec![], false),
+            ("parent", "Post", RelationKind::HasOne, vec!["parent_id"], false),
+        ]);
+
+        let sqlalchemy = models("app/models.py", concat!(
+            "class User(Base):\n",
+            "    __tablename__ = 'users'\n",
+            "    id = Column(Integer, primary_key=True)\n",
+            "    email = Column(String)\n",
+            "    posts: Mapped[List['Post']] = relationship(back_populates='author', lazy='selectin')\n",
+            "    profile = relationship('Profile', uselist=False)\n",
+            "\n",
+            "class Post(Base):\n",
+            "    __tablename__ = 'posts'\n",
+            "    id = Column(Integer, primary_key=True)\n",
+            "    author_id = Column(Integer, ForeignKey('users.id'))\n",
+            "    author = relationship('User', back_populates='posts')\n",
+        ));
+        assert_eq!(sqlalchemy.len(), 2);
+        assert_eq!((sqlalchemy[0].table_name.as_str(), sqlalchemy[0].fields.clone()), ("users", vec!["id".to_string(), "email".to_string()]));
+        assert_eq!(relations(&sqlalchemy[0]), vec![
+            ("posts", "Post", RelationKind::HasMany, vec![], true),
+            ("profile", "Profile", RelationKind::HasOne, vec![], false),
+        ]);
+        assert_eq!(relations(&sqlalchemy[1]), vec![("author", "User", RelationKind::BelongsTo, vec!["author_id"], false)]);
+        assert_eq!(sqlalchemy[1].relations[0].references, vec!["id"]);
+    }
+
+    #[test]
+    fn test_typeorm_and_ef_relations() {
+        let typeorm = models("src/entities/post.ts", concat!(
+            "@Entity('posts')\n",
+            "export class Post {\n",
+            "  @PrimaryGeneratedColumn() id: number;\n",
+            "  @Column() title: string;\n",
+            "  @ManyToOne(() => User, (user) => user.posts, { eager: true })\n",
+            "  @JoinColumn({ name: 'author_id' })\n",
+            "  author: User;\n",
+            "  @OneToMany(() => Comment, (comment) => comment.post)\n",
+            "  comments: Comment[];\n",
+            "}\n",
+            "\n",
+            "@Entity()\n",
+            "export class UserProfile {\n",
+            "  @ManyToMany(() => Tag)\n",
+            "  @JoinTable()\n",
+            "  tags: Tag[];\n",
+            "}\n",
+        ));
+        assert_eq!(typeorm.len(), 2);
+        assert_eq!((typeorm[0].table_name.as_str(), typeorm[0].fields.clone()), ("posts", vec!["id".to_string(), "title".to_string()]));
+        assert_eq!(relations(&typeorm[0]), vec![
+            ("author", "User", RelationKind::BelongsTo, vec!["author_id"], true),
+            ("comments", "Comment", RelationKind::HasMany, vec![], false),
+        ]);
+        assert_eq!(typeorm[1].table_name, "user_profile");
+        assert_eq!(relations(&typeorm[1]), vec![("tags", "Tag", RelationKind::ManyToMany, vec![], false)]);
+
+        let ef = models("Data/Blog.cs", concat!(
+            "public class BlogContext : DbContext {\n",
+            "    public DbSet<Blog> Blogs { get; set; }\n",
+            "}\n",
+            "\n",
+            "public class Blog {\n",
+            "    public int BlogId { get; set; }\n",
+            "    public string Url { get; set; }\n",
+            "    public ICollection<Post> Posts { get; set; }\n",
+            "    public DateTime CreatedAt { get; set; }\n",
+            "}\n",
+            "\n",
+            "public class Post {\n",
+            "    public int Id { get; set; }\n",
+            "    public int BlogId { get; set; }\n",
+            "    public Blog Blog { get; set; }\n",
+            "    [ForeignKey(\"EditorRef\")]\n",
+            "    public User Editor { get; set; }\n",
+            "}\n",
+            "\n",
+            "public class PostDto {\n",
+            "    public int Id { get; set; }\n",
+            "    public string Title { get; set; }\n",
+            "}\n",
+        ));
+        let names: Vec<(&str, &str, f32)> = ef.iter().map(|m| (m.name.as_str(), m.table_name.as_str(), m.confidence)).collect();
+        assert_eq!(names, vec![("Blog", "Blogs", 0.9), ("Post", "Post", 0.7)]);
+        assert_eq!(ef[0].fields, vec!["BlogId", "Url", "CreatedAt"]);
+        assert_eq!(relations(&ef[0]), vec![("Posts", "Post", RelationKind::HasMany, vec![], false)]);
+        assert_eq!(relations(&ef[1]), vec![
+            ("Blog", "Blog", RelationKind::BelongsTo, vec!["BlogId"], false),
+            ("Editor", "User", RelationKind::BelongsTo, vec!["EditorRef"], false),
+        ]);
+    }
+}
@@ -0,0 +1,193 @@
+//! Prisma schema parser - Models from `schema.prisma`
+//!
+//! Line-based, since the schema language is one declaration per line:
+//! - `model User { ... }` becomes an `ORMModel` with its scalar fields
+//! - `@@map("users")` sets the table name (the model name otherwise)
+//! - fields whose type is another model become `ModelRelation`s: lists
+//!   are has-many (many-to-many when the target lists this model back),
+//!   fields holding the foreign key belong to their target
+
+use std::collections::HashSet;
+
+use super::types::{ModelRelation, ORMModel, RelationKind};
+
+/// Prisma schema parser
+pub struct PrismaSchemaParser;
+
+impl PrismaSchemaParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a `.prisma` file into its models
+    pub fn parse(&self, source: &str, file: &str) -> Vec<ORMModel> {
+        let lines: Vec<&str> = source.lines()
+            .map(|line| line.split("//").next().unwrap_or(line).trim())
+            .collect();
+
+        // Relation fields are recognized by their type naming another model
+        let model_names: HashSet<&str> = lines.iter()
+            .filter_map(|line| block_name(line, "model"))
+            .collect();
+
+        let mut models = Vec::new();
+        let mut current: Option<ORMModel> = None;
+        for (i, line) in lines.iter().enumerate() {
+            let Some(model) = current.as_mut() else {
+                if let Some(name) = block_name(line, "model") {
+                    current = Some(ORMModel {
+                        name: name.to_string(),
+                        table_name: name.to_string(),
+                        fields: Vec::new(),
+                        relations: Vec::new(),
+                        file: file.to_string(),
+                        line: i as u32,
+                        framework: "prisma".to_string(),
+                        confidence: 0.95,
+                    });
+                }
+                continue;
+            };
+
+            if line.starts_with('}') {
+                models.extend(current.take());
+            } else if let Some(args) = line.strip_prefix("@@map(") {
+                if let Some(table) = quoted(args) {
+                    model.table_name = table;
+                }
+            } else if !line.is_empty() && !line.starts_with('@') {
+                let mut parts = line.split_whitespace();
+                let (Some(name), Some(field_type)) = (parts.next(), parts.next()) else {
+                    continue;
+                };
+                let target = field_type.trim_end_matches(['?', '!']).trim_end_matches("[]");
+                if model_names.contains(target) {
+                    let is_list = field_type.ends_with("[]");
+                    let fields = relation_list(line, "fields");
+                    model.relations.push(ModelRelation {
+                        field: name.to_string(),
+                        target: target.to_string(),
+                        kind: match (is_list, fields.is_empty()) {
+                            (true, _) => RelationKind::HasMany,
+                            (false, false) => RelationKind::BelongsTo,
+                            (false, true) => RelationKind::HasOne,
+                        },
+                        is_list,
+                        fields,
+                        references: relation_list(line, "references"),
+                        // Prisma loads relations only when a query includes them
+                        eager: false,
+                    });
+                } else {
+                    model.fields.push(name.to_string());
+                }
+            }
+        }
+
+        // Lists on both sides are an implicit many-to-many
+        let lists: HashSet<(String, String)> = models.iter()
+            .flat_map(|m| m.relations.iter().filter(|r| r.is_list).map(|r| (m.name.clone(), r.target.clone())))
+            .collect();
+        for model in &mut models {
+            for relation in model.relations.iter_mut().filter(|r| r.is_list) {
+                if lists.contains(&(relation.target.clone(), model.name.clone())) {
+                    relation.kind = RelationKind::ManyToMany;
+                }
+            }
+        }
+
+        models
+    }
+}
+
+impl Default for PrismaSchemaParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Name of a `<keyword> Name {` block opener
+fn block_name<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(keyword)?.strip_suffix('{')?;
+    let name = rest.trim();
+    (rest.starts_with(char::is_whitespace) && !name.is_empty()).then_some(name)
+}
+
+/// First string literal in attribute arguments (`"users")`)
+fn quoted(args: &str) -> Option<String> {
+    let start = args.find('"')? + 1;
+    let len = args[start..].find('"')?;
+    Some(args[start..start + len].to_string())
+}
+
+/// Names listed in a `@relation` argument (`fields: [authorId]`)
+fn relation_list(line: &str, argument: &str) -> Vec<String> {
+    let Some(relation) = line.find("@relation(").map(|i| &line[i..]) else {
+        return Vec::new();
+    };
+    let Some(list) = relation.split(&format!("{}:", argument)).nth(1)
+        .and_then(|rest| rest.trim_start().strip_prefix('['))
+        .and_then(|rest| rest.split(']').next())
+    else {
+        return Vec::new();
+    };
+    list.split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prisma_schema() {
+        let schema = r#"
+datasource db {
+  provider = "postgresql"
+  url      = env("DATABASE_URL")
+}
+
+model User {
+  id        Int      @id @default(autoincrement())
+  email     String   @unique
+  posts     Post[]
+  createdAt DateTime @default(now()) @map("created_at")
+
+  @@map("users")
+}
+
+// Posts keep the default table name
+model Post {
+  id       Int     @id
+  title    String
+  author   User?   @relation("Authored", fields: [authorId], references: [id])
+  authorId Int?
+  status   Status
+}
+
+enum Status {
+  DRAFT
+  PUBLISHED
+}
+"#;
+        let models = PrismaSchemaParser::new().parse(schema, "prisma/schema.prisma");
+        assert_eq!(models.len(), 2);
+
+        let user = &models[0];
+        assert_eq!((user.name.as_str(), user.table_name.as_str(), user.line), ("User", "users", 6));
+        assert_eq!(user.fields, vec!["id", "email", "createdAt"]);
+        assert_eq!(user.relations.len(), 1);
+        assert_eq!((user.relations[0].target.as_str(), user.relations[0].is_list), ("Post", true));
+
+        let post = &models[1];
+        assert_eq!(post.table_name, "Post");
+        assert_eq!(post.fields, vec!["id", "title", "authorId", "status"]);
+        let author = &post.relations[0];
+        assert_eq!((author.field.as_str(), author.target.as_str(), author.is_list), ("author", "User", false));
+        assert_eq!((user.relations[0].kind, author.kind), (RelationKind::HasMany, RelationKind::BelongsTo));
+        assert_eq!(author.fields, vec!["authorId"]);
+        assert_eq!(author.references, vec!["id"]);
+    }
+}
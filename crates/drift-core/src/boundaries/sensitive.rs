@@ -1,7 +1,15 @@
 //! Sensitive field detector - Detects PII, credentials, financial, health data
+//!
+//! Built-in patterns can be extended, overridden or suppressed with
+//! `SensitiveFieldRule`s, which may also introduce custom sensitivity labels.
+
+use std::ops::Range;
 
 use regex::Regex;
-use super::types::{SensitiveField, SensitivityType};
+use super::types::{DataAccessPoint, SensitiveField, SensitiveFieldConfig, SensitivityType};
+
+/// Confidence added when a query names the field as a column of its table
+const QUERIED_FIELD_BOOST: f32 = 0.2;
 
 /// Pattern with specificity score
 struct SensitivePattern {
@@ -9,12 +17,22 @@ struct SensitivePattern {
     specificity: f32,
 }
 
+/// A compiled `SensitiveFieldRule`
+struct CustomPattern {
+    pattern: Regex,
+    sensitivity_type: SensitivityType,
+    label: Option<String>,
+    confidence: f32,
+    suppress: bool,
+}
+
 /// Sensitive field detector
 pub struct SensitiveFieldDetector {
     pii_patterns: Vec<SensitivePattern>,
     credential_patterns: Vec<SensitivePattern>,
     financial_patterns: Vec<SensitivePattern>,
     health_patterns: Vec<SensitivePattern>,
+    custom_patterns: Vec<CustomPattern>,
     false_positive_patterns: Vec<Regex>,
 }
 
@@ -61,6 +79,7 @@ impl SensitiveFieldDetector {
                 SensitivePattern { pattern: Regex::new(r"(?i)\bmedical_record\b").unwrap(), specificity: 0.95 },
                 SensitivePattern { pattern: Regex::new(r"(?i)\bhealth_record\b").unwrap(), specificity: 0.95 },
             ],
+            custom_patterns: Vec::new(),
             false_positive_patterns: vec![
                 Regex::new(r"(?i)(?:get|set|is|has|check|validate)(?:Password|Email|Phone)").unwrap(),
                 Regex::new(r"(?i)function\s+\w*(?:password|email|phone)\w*\s*\(").unwrap(),
@@ -73,6 +92,27 @@ impl SensitiveFieldDetector {
         }
     }
     
+    /// Built-in patterns plus the configured rules
+    pub fn with_config(config: &SensitiveFieldConfig) -> Result<Self, String> {
+        let mut detector = Self::new();
+        for rule in &config.rules {
+            let pattern = Regex::new(&rule.pattern)
+                .map_err(|e| format!("Invalid sensitive field pattern '{}': {}", rule.pattern, e))?;
+            let sensitivity_type = SensitivityType::from_label(&rule.sensitivity);
+            if sensitivity_type == SensitivityType::Custom && rule.sensitivity.trim().is_empty() && !rule.suppress {
+                return Err(format!("Sensitive field pattern '{}' has no sensitivity", rule.pattern));
+            }
+            detector.custom_patterns.push(CustomPattern {
+                pattern,
+                sensitivity_type,
+                label: (sensitivity_type == SensitivityType::Custom).then(|| rule.sensitivity.trim().to_string()),
+                confidence: rule.confidence,
+                suppress: rule.suppress,
+            });
+        }
+        Ok(detector)
+    }
+    
     /// Detect sensitive fields in source code
     pub fn detect(&self, source: &str, file: &str) -> Vec<SensitiveField> {
         let mut fields = Vec::new();
@@ -93,19 +133,108 @@ impl SensitiveFieldDetector {
                 .any(|p| p.is_match(line));
             
             // Check each category
+            let mut found = Vec::new();
             self.check_patterns(line, file, line_num, &self.pii_patterns, 
-                SensitivityType::Pii, is_false_positive, &mut fields);
+                SensitivityType::Pii, is_false_positive, &mut found);
             self.check_patterns(line, file, line_num, &self.credential_patterns,
-                SensitivityType::Credentials, is_false_positive, &mut fields);
+                SensitivityType::Credentials, is_false_positive, &mut found);
             self.check_patterns(line, file, line_num, &self.financial_patterns,
-                SensitivityType::Financial, is_false_positive, &mut fields);
+                SensitivityType::Financial, is_false_positive, &mut found);
             self.check_patterns(line, file, line_num, &self.health_patterns,
-                SensitivityType::Health, is_false_positive, &mut fields);
+                SensitivityType::Health, is_false_positive, &mut found);
+            self.check_custom_patterns(line, file, line_num, is_false_positive, &mut found);
+            fields.extend(found.into_iter().map(|(_, field)| field));
         }
         
         fields
     }
     
+    /// `detect`, with fields that the access points name tied to their tables
+    ///
+    /// A column in `SELECT ssn FROM users` is certainly a field of `users`,
+    /// unlike a name that merely appears in the source, so it is reported with
+    /// its table at boosted confidence, replacing untied detections of it on
+    /// the same line.
+    pub fn detect_with_access(&self, source: &str, file: &str, access_points: &[DataAccessPoint]) -> Vec<SensitiveField> {
+        let mut fields = self.detect(source, file);
+        for access in access_points {
+            for field in &access.fields {
+                let Some((sensitivity_type, label, specificity)) = self.classify_field(field) else {
+                    continue;
+                };
+                let tied = |f: &SensitiveField| f.field.eq_ignore_ascii_case(field) && f.line == access.line;
+                fields.retain(|f| f.table.is_some() || !tied(f));
+                if fields.iter().any(|f| tied(f) && f.table.as_deref() == Some(access.table.as_str())) {
+                    continue;
+                }
+                fields.push(SensitiveField {
+                    field: field.clone(),
+                    table: Some(access.table.clone()),
+                    sensitivity_type,
+                    label,
+                    file: file.to_string(),
+                    line: access.line,
+                    confidence: (specificity + QUERIED_FIELD_BOOST).min(0.99),
+                });
+            }
+        }
+        fields
+    }
+    
+    /// Sensitivity of a field name: the last matching configured rule, else
+    /// the most specific built-in pattern
+    fn classify_field(&self, field: &str) -> Option<(SensitivityType, Option<String>, f32)> {
+        if let Some(custom) = self.custom_patterns.iter().rev().find(|c| c.pattern.is_match(field)) {
+            return (!custom.suppress && custom.confidence >= 0.5)
+                .then(|| (custom.sensitivity_type, custom.label.clone(), custom.confidence));
+        }
+        [
+            (&self.pii_patterns, SensitivityType::Pii),
+            (&self.credential_patterns, SensitivityType::Credentials),
+            (&self.financial_patterns, SensitivityType::Financial),
+            (&self.health_patterns, SensitivityType::Health),
+        ]
+        .into_iter()
+        .flat_map(|(patterns, sensitivity_type)| patterns.iter().map(move |p| (p, sensitivity_type)))
+        .filter(|(p, _)| p.pattern.is_match(field))
+        .map(|(p, sensitivity_type)| (sensitivity_type, None, p.specificity))
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+    }
+    
+    /// Apply configured rules to a line; each replaces the matches it overlaps
+    fn check_custom_patterns(
+        &self,
+        line: &str,
+        file: &str,
+        line_num: u32,
+        is_false_positive: bool,
+        found: &mut Vec<(Range<usize>, SensitiveField)>,
+    ) {
+        for custom in &self.custom_patterns {
+            let Some(m) = custom.pattern.find(line) else {
+                continue;
+            };
+            found.retain(|(span, _)| span.end <= m.start() || m.end() <= span.start);
+            
+            let mut confidence = custom.confidence;
+            if is_false_positive {
+                confidence = (confidence - 0.4).max(0.1);
+            }
+            if custom.suppress || confidence < 0.5 {
+                continue;
+            }
+            found.push((m.range(), SensitiveField {
+                field: m.as_str().to_string(),
+                table: None,
+                sensitivity_type: custom.sensitivity_type,
+                label: custom.label.clone(),
+                file: file.to_string(),
+                line: line_num,
+                confidence,
+            }));
+        }
+    }
+    
     fn check_patterns(
         &self,
         line: &str,
@@ -114,7 +243,7 @@ impl SensitiveFieldDetector {
         patterns: &[SensitivePattern],
         sensitivity_type: SensitivityType,
         is_false_positive: bool,
-        fields: &mut Vec<SensitiveField>,
+        fields: &mut Vec<(Range<usize>, SensitiveField)>,
     ) {
         for sp in patterns {
             if let Some(m) = sp.pattern.find(line) {
@@ -130,14 +259,15 @@ impl SensitiveFieldDetector {
                     continue;
                 }
                 
-                fields.push(SensitiveField {
+                fields.push((m.range(), SensitiveField {
                     field: m.as_str().to_string(),
                     table: None,
                     sensitivity_type,
+                    label: None,
                     file: file.to_string(),
                     line: line_num,
                     confidence,
-                });
+                }));
                 break; // One match per type per line
             }
         }
@@ -153,6 +283,7 @@ impl Default for SensitiveFieldDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::boundaries::SensitiveFieldRule;
     
     #[test]
     fn test_detect_pii() {
@@ -199,4 +330,67 @@ mod tests {
         // The function name pattern should reduce confidence
         assert!(fields.is_empty() || fields.iter().all(|f| f.confidence < 0.8));
     }
+    
+    #[test]
+    fn test_tie_queried_fields_to_tables() {
+        let detector = SensitiveFieldDetector::new();
+        let source = "const row = await db.query('SELECT ssn, u.email AS contact FROM users u');\nconst ssn = row.ssn;\n";
+        let access = crate::boundaries::DataAccessDetector::new().detect_sql_in_source(source, "test.ts");
+        assert_eq!(access[0].fields, vec!["ssn", "email"]);
+        
+        let fields = detector.detect_with_access(source, "test.ts", &access);
+        let tied: Vec<(&str, Option<&str>, u32)> = fields.iter()
+            .map(|f| (f.field.as_str(), f.table.as_deref(), f.line))
+            .collect();
+        // The untied ssn on line 1 is replaced; the one on line 2 stays
+        assert_eq!(tied, vec![("ssn", None, 2), ("ssn", Some("users"), 1), ("email", Some("users"), 1)]);
+        assert!(fields[1].confidence > fields[0].confidence);
+        assert!(fields[1].confidence >= 0.95);
+    }
+    
+    #[test]
+    fn test_custom_rules() {
+        let rule = |pattern: &str, sensitivity: &str, confidence: f32, suppress: bool| SensitiveFieldRule {
+            pattern: pattern.to_string(),
+            sensitivity: sensitivity.to_string(),
+            confidence,
+            suppress,
+        };
+        let detector = SensitiveFieldDetector::with_config(&SensitiveFieldConfig {
+            rules: vec![
+                rule(r"(?i)\bfingerprint_hash\b", "biometric", 0.9, false),
+                rule(r"(?i)\blat_lng\b", "geolocation", 0.8, false),
+                // Domain name for a built-in type, and a builtin that is noise here
+                rule(r"(?i)\bemail\b", "pii", 0.95, false),
+                rule(r"(?i)\baddress\b", "", 0.0, true),
+            ],
+        }).unwrap();
+        let source = r#"
+            const user = {
+                fingerprint_hash: scan.hash,
+                lat_lng: position,
+                email: form.email,
+                address: wallet.address,
+            };
+        "#;
+        
+        let fields = detector.detect(source, "test.ts");
+        let found: Vec<(&str, &str, f32)> = fields.iter()
+            .map(|f| (f.field.as_str(), f.sensitivity_label(), f.confidence))
+            .collect();
+        assert_eq!(found, vec![
+            ("fingerprint_hash", "biometric", 0.9),
+            ("lat_lng", "geolocation", 0.8),
+            ("email", "pii", 0.95),
+        ]);
+        assert_eq!(fields[0].sensitivity_type, SensitivityType::Custom);
+        assert_eq!(fields[2].sensitivity_type, SensitivityType::Pii);
+        
+        // Queried columns use the same rules
+        assert_eq!(detector.classify_field("fingerprint_hash").map(|c| c.1), Some(Some("biometric".to_string())));
+        assert!(detector.classify_field("address").is_none());
+        
+        let invalid = SensitiveFieldDetector::with_config(&SensitiveFieldConfig { rules: vec![rule("(", "pii", 0.9, false)] });
+        assert!(invalid.is_err());
+    }
 }
@@ -0,0 +1,871 @@
+//! SQL file parser - Schema definitions and DML from `.sql` migrations
+//!
+//! Hand-rolled statement splitter and tokenizer (no grammar needed):
+//! - `CREATE TABLE` becomes a `ClassInfo` with one `PropertyInfo` per column
+//! - `ALTER TABLE ... ADD COLUMN / RENAME COLUMN` becomes a partial `ClassInfo`
+//! - `SELECT` / `INSERT` / `UPDATE` / `DELETE` / `TRUNCATE` become one
+//!   `DataAccessPoint` per table they touch (joins, subqueries and CTEs included),
+//!   with the columns each selects, inserts or sets (`*` for all columns)
+
+use std::collections::{HashMap, HashSet};
+
+use crate::parsers::{ClassInfo, Position, PropertyInfo, Range, Visibility};
+use super::types::{DataAccessPoint, DataOperation};
+
+/// Tables and data access found in a single SQL file
+#[derive(Debug, Clone, Default)]
+pub struct SqlFileResult {
+    /// Table definitions (`is_partial` for ALTER TABLE)
+    pub tables: Vec<ClassInfo>,
+    /// DML statements
+    pub access_points: Vec<DataAccessPoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    /// Bare word (keyword or identifier)
+    Word,
+    /// Quoted identifier ("name", `name`, [name])
+    Ident,
+    /// String literal ('text', $$body$$)
+    Str,
+    /// Number or other literal
+    Literal,
+    Symbol,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    line: u32,
+}
+
+impl Token {
+    fn is_kw(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        self.kind == TokenKind::Symbol && self.text == symbol
+    }
+
+    fn is_name(&self) -> bool {
+        matches!(self.kind, TokenKind::Word | TokenKind::Ident)
+    }
+}
+
+/// Keywords that end a column's type and start its constraints
+const COLUMN_CONSTRAINTS: &[&str] = &[
+    "NOT", "NULL", "DEFAULT", "PRIMARY", "REFERENCES", "UNIQUE", "CHECK", "CONSTRAINT",
+    "GENERATED", "COLLATE", "AUTO_INCREMENT", "AUTOINCREMENT", "IDENTITY", "AS", "ON", "COMMENT",
+];
+
+/// Table-level constraints inside CREATE TABLE / ALTER TABLE ADD
+const TABLE_CONSTRAINTS: &[&str] = &[
+    "CONSTRAINT", "PRIMARY", "FOREIGN", "UNIQUE", "CHECK", "INDEX", "KEY", "EXCLUDE", "LIKE", "FULLTEXT", "SPATIAL",
+];
+
+/// Clauses that can follow a table reference, so are never its alias
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "WHERE", "JOIN", "INNER", "LEFT", "RIGHT", "FULL", "CROSS", "NATURAL", "OUTER", "ON", "USING",
+    "GROUP", "ORDER", "LIMIT", "OFFSET", "FETCH", "UNION", "EXCEPT", "INTERSECT", "SET", "RETURNING",
+    "HAVING", "WINDOW", "FOR", "VALUES", "SELECT", "WHEN",
+];
+
+/// Functions whose arguments use `FROM` (`EXTRACT(YEAR FROM created_at)`)
+const FROM_FUNCTIONS: &[&str] = &["EXTRACT", "SUBSTRING", "TRIM", "POSITION", "OVERLAY"];
+
+/// SQL migration / script parser
+pub struct SqlFileParser;
+
+impl SqlFileParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a `.sql` file into table definitions and data access points
+    pub fn parse(&self, source: &str, file: &str) -> SqlFileResult {
+        let mut result = SqlFileResult::default();
+
+        for statement in split_statements(tokenize(source)) {
+            let Some(first) = statement.first() else {
+                continue;
+            };
+            if first.is_kw("CREATE") {
+                result.tables.extend(self.parse_create_table(&statement));
+            } else if first.is_kw("ALTER") {
+                result.tables.extend(self.parse_alter_table(&statement));
+            } else {
+                result.access_points.extend(self.parse_dml(&statement, file));
+            }
+        }
+
+        result
+    }
+
+    /// `CREATE [TEMP] TABLE [IF NOT EXISTS] name (columns...)`
+    fn parse_create_table(&self, tokens: &[Token]) -> Option<ClassInfo> {
+        let table_pos = tokens.iter().take(5).position(|t| t.is_kw("TABLE"))?;
+        let mut pos = skip_keywords(tokens, table_pos + 1, &["IF", "NOT", "EXISTS"]);
+        let (schema, name, next) = qualified_name(tokens, pos)?;
+        pos = next;
+
+        // `CREATE TABLE x AS SELECT ...` has no column list
+        let mut properties = Vec::new();
+        if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+            let close = matching_paren(tokens, pos)?;
+            for definition in split_top_level(&tokens[pos + 1..close]) {
+                properties.extend(self.parse_column(definition));
+            }
+        }
+
+        Some(table(name, schema, properties, tokens, false))
+    }
+
+    /// `ALTER TABLE name ADD [COLUMN] col type, RENAME [COLUMN] a TO b`
+    fn parse_alter_table(&self, tokens: &[Token]) -> Option<ClassInfo> {
+        if !tokens.get(1).is_some_and(|t| t.is_kw("TABLE")) {
+            return None;
+        }
+        let pos = skip_keywords(tokens, 2, &["IF", "EXISTS", "ONLY"]);
+        let (schema, name, pos) = qualified_name(tokens, pos)?;
+
+        let mut properties = Vec::new();
+        for action in split_top_level(&tokens[pos..]) {
+            let Some(verb) = action.first() else {
+                continue;
+            };
+            if verb.is_kw("ADD") {
+                let start = skip_keywords(action, 1, &["COLUMN", "IF", "NOT", "EXISTS"]);
+                properties.extend(self.parse_column(&action[start..]));
+            } else if verb.is_kw("RENAME") {
+                // RENAME [COLUMN] old TO new (RENAME TO renames the table itself)
+                let start = skip_keywords(action, 1, &["COLUMN"]);
+                if action.get(start + 1).is_some_and(|t| t.is_kw("TO")) {
+                    if let Some(new_name) = action.get(start + 2).filter(|t| t.is_name()) {
+                        properties.push(column(new_name.text.clone()));
+                    }
+                }
+            }
+        }
+
+        if properties.is_empty() {
+            return None;
+        }
+        Some(table(name, schema, properties, tokens, true))
+    }
+
+    /// A column definition: `name TYPE [constraints...]`
+    fn parse_column(&self, tokens: &[Token]) -> Option<PropertyInfo> {
+        let name = tokens.first().filter(|t| t.is_name())?;
+        if name.kind == TokenKind::Word && TABLE_CONSTRAINTS.iter().any(|k| name.is_kw(k)) {
+            return None;
+        }
+
+        let rest = &tokens[1..];
+        let type_end = rest.iter()
+            .position(|t| COLUMN_CONSTRAINTS.iter().any(|k| t.is_kw(k)))
+            .unwrap_or(rest.len());
+        let type_annotation = Some(join_tokens(&rest[..type_end])).filter(|t| !t.is_empty());
+
+        let mut property = column(name.text.clone());
+        property.type_annotation = type_annotation;
+
+        let constraints = &rest[type_end..];
+        let mut i = 0;
+        while i < constraints.len() {
+            let token = &constraints[i];
+            let clause_end = constraints[i + 1..].iter()
+                .position(|t| COLUMN_CONSTRAINTS.iter().any(|k| t.is_kw(k)))
+                .map(|p| i + 1 + p)
+                .unwrap_or(constraints.len());
+
+            if token.is_kw("DEFAULT") {
+                property.value = Some(join_tokens(&constraints[i + 1..clause_end]));
+            } else if token.is_kw("PRIMARY") {
+                property.decorators.push("PRIMARY KEY".to_string());
+            } else if token.is_kw("UNIQUE") {
+                property.decorators.push("UNIQUE".to_string());
+            } else if token.is_kw("NOT") && constraints.get(i + 1).is_some_and(|t| t.is_kw("NULL")) {
+                property.decorators.push("NOT NULL".to_string());
+                i += 1;
+            } else if token.is_kw("REFERENCES") {
+                property.decorators.push(format!("REFERENCES {}", join_tokens(&constraints[i + 1..clause_end])));
+            } else if token.is_kw("AS") && constraints.get(i + 1).is_some_and(|t| t.is_symbol("(")) {
+                // GENERATED ALWAYS AS (expr) / MySQL `col INT AS (expr)`, not AS IDENTITY
+                property.is_computed = true;
+            }
+            i = clause_end.max(i + 1);
+        }
+
+        Some(property)
+    }
+
+    /// SELECT / INSERT / UPDATE / DELETE / TRUNCATE, optionally after `WITH`
+    ///
+    /// One access point per table: the written table first, then every table
+    /// read through FROM, JOIN or USING, subqueries and UNION branches included.
+    fn parse_dml(&self, tokens: &[Token], file: &str) -> Vec<DataAccessPoint> {
+        let Some(first) = tokens.first() else {
+            return Vec::new();
+        };
+        statement_tables(tokens, &HashSet::new()).into_iter()
+            .map(|(table, operation, fields)| DataAccessPoint {
+                table,
+                operation,
+                fields,
+                file: file.to_string(),
+                line: first.line,
+                confidence: 0.95,
+                framework: Some("sql".to_string()),
+                transaction_id: None,
+            })
+            .collect()
+    }
+}
+
+impl Default for SqlFileParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split SQL source into tokens, dropping comments
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1u32;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start_line = line;
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if (c == '-' && chars.get(i + 1) == Some(&'-')) || c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if matches!(c, '\'' | '"' | '`' | '[') {
+            let close = if c == '[' { ']' } else { c };
+            let mut text = String::new();
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == close {
+                    // Doubled quote is an escaped quote
+                    if chars.get(i + 1) == Some(&close) && close != ']' {
+                        text.push(close);
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                text.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            let kind = if c == '\'' { TokenKind::Str } else { TokenKind::Ident };
+            tokens.push(Token { kind, text, line: start_line });
+        } else if c == '$' && chars.get(i + 1).is_some_and(|n| *n == '$' || n.is_alphabetic() || *n == '_') {
+            // Dollar-quoted body: $$ ... $$ or $tag$ ... $tag$
+            let tag_end = chars[i + 1..].iter().position(|ch| *ch == '$').map(|p| i + 1 + p);
+            let Some(tag_end) = tag_end.filter(|e| chars[i + 1..*e].iter().all(|ch| ch.is_alphanumeric() || *ch == '_')) else {
+                tokens.push(Token { kind: TokenKind::Symbol, text: c.to_string(), line });
+                i += 1;
+                continue;
+            };
+            let tag: Vec<char> = chars[i..=tag_end].to_vec();
+            let body_start = tag_end + 1;
+            let mut j = body_start;
+            while j < chars.len() && !chars[j..].starts_with(&tag) {
+                if chars[j] == '\n' {
+                    line += 1;
+                }
+                j += 1;
+            }
+            let text: String = chars[body_start..j.min(chars.len())].iter().collect();
+            tokens.push(Token { kind: TokenKind::Str, text, line: start_line });
+            i = (j + tag.len()).min(chars.len());
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                i += 1;
+            }
+            tokens.push(Token { kind: TokenKind::Word, text: chars[start..i].iter().collect(), line });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token { kind: TokenKind::Literal, text: chars[start..i].iter().collect(), line });
+        } else {
+            tokens.push(Token { kind: TokenKind::Symbol, text: c.to_string(), line });
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Split tokens into statements on `;`
+fn split_statements(tokens: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut statements = Vec::new();
+    let mut current = Vec::new();
+    for token in tokens {
+        if token.is_symbol(";") {
+            if !current.is_empty() {
+                statements.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(token);
+        }
+    }
+    if !current.is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+/// Tables a statement touches, as (table, operation, fields)
+///
+/// `ctes` holds the names bound by enclosing `WITH` clauses, which are not tables.
+fn statement_tables(tokens: &[Token], ctes: &HashSet<String>) -> Vec<(String, DataOperation, Vec<String>)> {
+    let mut tables = Vec::new();
+    let Some(first) = tokens.first() else {
+        return tables;
+    };
+
+    // WITH [RECURSIVE] name [(columns)] AS [NOT] [MATERIALIZED] (body), ... statement
+    if first.is_kw("WITH") {
+        let mut ctes = ctes.clone();
+        let mut bodies = Vec::new();
+        let mut pos = skip_keywords(tokens, 1, &["RECURSIVE"]);
+        while let Some(name) = tokens.get(pos).filter(|t| t.is_name()) {
+            ctes.insert(name.text.to_lowercase());
+            pos += 1;
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                pos = matching_paren(tokens, pos).map_or(tokens.len(), |close| close + 1);
+            }
+            pos = skip_keywords(tokens, pos, &["AS", "NOT", "MATERIALIZED"]);
+            let Some(close) = tokens.get(pos).filter(|t| t.is_symbol("(")).and_then(|_| matching_paren(tokens, pos)) else {
+                return tables;
+            };
+            bodies.push(&tokens[pos + 1..close]);
+            pos = close + 1;
+            if !tokens.get(pos).is_some_and(|t| t.is_symbol(",")) {
+                break;
+            }
+            pos += 1;
+        }
+        let main = statement_tables(&tokens[pos.min(tokens.len())..], &ctes);
+        for (table, operation, fields) in main.into_iter().chain(bodies.into_iter().flat_map(|body| statement_tables(body, &ctes))) {
+            add_table(&mut tables, table, operation, fields);
+        }
+        return tables;
+    }
+
+    let mut select_list = None;
+    if first.is_kw("INSERT") || first.is_kw("REPLACE") {
+        let pos = skip_keywords(tokens, 1, &["INTO", "IGNORE", "OR", "REPLACE", "ABORT", "ROLLBACK", "FAIL"]);
+        let Some((_, table, pos)) = qualified_name(tokens, pos) else {
+            return tables;
+        };
+        // Explicit column list
+        let mut fields = Vec::new();
+        if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+            if let Some(close) = matching_paren(tokens, pos) {
+                fields = split_top_level(&tokens[pos + 1..close]).iter()
+                    .filter_map(|c| c.first().filter(|t| t.is_name()).map(|t| t.text.clone()))
+                    .collect();
+            }
+        }
+        add_table(&mut tables, table, DataOperation::Write, fields);
+    } else if first.is_kw("UPDATE") {
+        let pos = skip_keywords(tokens, 1, &["ONLY"]);
+        let Some((_, table, pos)) = qualified_name(tokens, pos) else {
+            return tables;
+        };
+        let Some(set) = tokens[pos..].iter().position(|t| t.is_kw("SET")).map(|p| pos + p + 1) else {
+            return tables;
+        };
+        let end = tokens[set..].iter()
+            .position(|t| t.is_kw("WHERE") || t.is_kw("FROM") || t.is_kw("RETURNING"))
+            .map(|p| set + p)
+            .unwrap_or(tokens.len());
+        // `col = ...` or MySQL's `t.col = ...`
+        let fields = split_top_level(&tokens[set..end]).iter()
+            .filter_map(|assignment| match assignment {
+                [_, dot, name, ..] if dot.is_symbol(".") && name.is_name() => Some(name.text.clone()),
+                [name, ..] if name.is_name() => Some(name.text.clone()),
+                _ => None,
+            })
+            .collect();
+        add_table(&mut tables, table, DataOperation::Write, fields);
+    } else if first.is_kw("DELETE") || first.is_kw("TRUNCATE") {
+        let pos = skip_keywords(tokens, 1, &["FROM", "TABLE", "ONLY"]);
+        let Some((_, table, _)) = qualified_name(tokens, pos) else {
+            return tables;
+        };
+        add_table(&mut tables, table, DataOperation::Delete, Vec::new());
+    } else if first.is_kw("SELECT") {
+        select_list = top_level_position(tokens, |t| t.is_kw("FROM")).map(|from| &tokens[1..from]);
+    } else {
+        return tables;
+    }
+
+    let reads = read_tables(tokens, ctes);
+    let mut fields = select_list.map(|list| select_fields(list, &reads)).unwrap_or_default();
+    for read in reads {
+        let table_fields = fields.remove(&read.name.to_lowercase()).unwrap_or_default();
+        add_table(&mut tables, read.name, DataOperation::Read, table_fields);
+    }
+    tables
+}
+
+/// A table named after FROM, JOIN or USING
+struct TableRef {
+    name: String,
+    alias: Option<String>,
+    /// Parenthesis depth, 0 for the statement's own FROM clause
+    depth: usize,
+}
+
+/// Columns of a select list per table (lowercased name)
+///
+/// Aliases are dropped (`email AS contact` is `email`), qualifiers resolve
+/// through table aliases (`u.email`), and unqualified columns belong to the
+/// first table in the FROM clause. `*` is kept as a marker for all columns,
+/// on every table in the FROM clause; expressions name no column.
+fn select_fields(list: &[Token], reads: &[TableRef]) -> HashMap<String, Vec<String>> {
+    let top_level: Vec<&TableRef> = reads.iter().filter(|r| r.depth == 0).collect();
+    let resolve = |qualifier: &str| {
+        top_level.iter()
+            .find(|r| r.alias.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(qualifier)) || r.name.eq_ignore_ascii_case(qualifier))
+            .map(|r| r.name.to_lowercase())
+    };
+    let default = top_level.first().map(|r| r.name.to_lowercase());
+
+    let mut fields: HashMap<String, Vec<String>> = HashMap::new();
+    let mut add = |table: Option<String>, field: &str| {
+        if let Some(table) = table {
+            let list = fields.entry(table).or_default();
+            if !list.iter().any(|f| f == field) {
+                list.push(field.to_string());
+            }
+        }
+    };
+    let list = &list[skip_keywords(list, 0, &["DISTINCT", "ALL"])..];
+    for item in split_top_level(list) {
+        let item = match item {
+            [expr @ .., as_kw, _] if as_kw.is_kw("AS") => expr,
+            // Implicit alias: `email contact`, `u.email contact`, `lower(email) e`
+            [expr @ .., alias] if alias.is_name() && expr.last().is_some_and(|t| t.is_name() || t.is_symbol(")")) => expr,
+            _ => item,
+        };
+        let is_column = |t: &Token| t.is_name() || t.is_symbol("*");
+        match item {
+            [star] if star.is_symbol("*") => {
+                for read in &top_level {
+                    add(Some(read.name.to_lowercase()), "*");
+                }
+            }
+            [name] if name.is_name() => add(default.clone(), &name.text),
+            [qualifier, dot, column] | [_, _, qualifier, dot, column]
+                if dot.is_symbol(".") && qualifier.is_name() && is_column(column) =>
+            {
+                add(resolve(&qualifier.text), &column.text);
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+/// Record a table once; a write or delete replaces an earlier read of it
+fn add_table(tables: &mut Vec<(String, DataOperation, Vec<String>)>, table: String, operation: DataOperation, fields: Vec<String>) {
+    match tables.iter_mut().find(|(known, _, _)| known.eq_ignore_ascii_case(&table)) {
+        Some(existing) => {
+            if existing.1 == DataOperation::Read && operation != DataOperation::Read {
+                *existing = (table, operation, fields);
+            }
+        }
+        None => tables.push((table, operation, fields)),
+    }
+}
+
+/// Tables named after FROM, JOIN and USING anywhere in the statement
+///
+/// Derived tables and table functions are skipped (a subquery's own FROM is
+/// found like any other), as are CTE names.
+fn read_tables(tokens: &[Token], ctes: &HashSet<String>) -> Vec<TableRef> {
+    let mut tables = Vec::new();
+    // Whether each open parenthesis is the argument list of a FROM_FUNCTIONS call
+    let mut parens: Vec<bool> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_symbol("(") {
+            parens.push(i > 0 && FROM_FUNCTIONS.iter().any(|f| tokens[i - 1].is_kw(f)));
+            continue;
+        }
+        if token.is_symbol(")") {
+            parens.pop();
+            continue;
+        }
+        let is_list = token.is_kw("FROM") || token.is_kw("USING");
+        let in_function = parens.last() == Some(&true);
+        // `a IS DISTINCT FROM b` compares values
+        let is_comparison = i > 0 && tokens[i - 1].is_kw("DISTINCT");
+        if !(is_list || token.is_kw("JOIN")) || in_function || is_comparison {
+            continue;
+        }
+
+        let mut pos = i + 1;
+        loop {
+            pos = skip_keywords(tokens, pos, &["ONLY", "LATERAL"]);
+            let Some((_, name, next)) = qualified_name(tokens, pos) else {
+                break;
+            };
+            // Table functions: FROM generate_series(1, 10)
+            if tokens.get(next).is_some_and(|t| t.is_symbol("(")) {
+                break;
+            }
+            let after_alias = skip_alias(tokens, next);
+            if !ctes.contains(&name.to_lowercase()) {
+                let alias = tokens.get(after_alias - 1)
+                    .filter(|t| after_alias > next && t.is_name())
+                    .map(|t| t.text.clone());
+                tables.push(TableRef { name, alias, depth: parens.len() });
+            }
+            pos = after_alias;
+            if !(is_list && tokens.get(pos).is_some_and(|t| t.is_symbol(","))) {
+                break;
+            }
+            pos += 1;
+        }
+    }
+    tables
+}
+
+/// Position after an optional `[AS] alias`
+fn skip_alias(tokens: &[Token], pos: usize) -> usize {
+    match tokens.get(pos) {
+        Some(t) if t.is_kw("AS") => pos + 2,
+        Some(t) if t.kind == TokenKind::Ident => pos + 1,
+        Some(t) if t.kind == TokenKind::Word && !CLAUSE_KEYWORDS.iter().any(|k| t.is_kw(k)) => pos + 1,
+        _ => pos,
+    }
+}
+
+/// Split on commas that are not nested inside parentheses
+fn split_top_level(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_symbol("(") {
+            depth += 1;
+        } else if token.is_symbol(")") {
+            depth -= 1;
+        } else if token.is_symbol(",") && depth == 0 {
+            parts.push(&tokens[start..i]);
+            start = i + 1;
+        }
+    }
+    if start < tokens.len() {
+        parts.push(&tokens[start..]);
+    }
+    parts
+}
+
+/// First token matching `pred` outside parentheses
+fn top_level_position(tokens: &[Token], pred: impl Fn(&Token) -> bool) -> Option<usize> {
+    let mut depth = 0i32;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_symbol("(") {
+            depth += 1;
+        } else if token.is_symbol(")") {
+            depth -= 1;
+        } else if depth == 0 && pred(token) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn matching_paren(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.is_symbol("(") {
+            depth += 1;
+        } else if token.is_symbol(")") {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Advance past any of the given keywords
+fn skip_keywords(tokens: &[Token], mut pos: usize, keywords: &[&str]) -> usize {
+    while tokens.get(pos).is_some_and(|t| keywords.iter().any(|k| t.is_kw(k))) {
+        pos += 1;
+    }
+    pos
+}
+
+/// `[schema.]name` starting at `pos`; returns (schema, name, next position)
+fn qualified_name(tokens: &[Token], pos: usize) -> Option<(Option<String>, String, usize)> {
+    let first = tokens.get(pos).filter(|t| t.is_name())?;
+    if tokens.get(pos + 1).is_some_and(|t| t.is_symbol(".")) {
+        if let Some(second) = tokens.get(pos + 2).filter(|t| t.is_name()) {
+            return Some((Some(first.text.clone()), second.text.clone(), pos + 3));
+        }
+    }
+    Some((None, first.text.clone(), pos + 1))
+}
+
+/// Re-join tokens as source-like text (`VARCHAR(255)`, `NUMERIC(10,2)`)
+fn join_tokens(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    for token in tokens {
+        let attach = token.kind == TokenKind::Symbol && matches!(token.text.as_str(), "(" | ")" | "," | "." | "[" | "]")
+            || text.ends_with(['(', ',', '.', '[', ':']);
+        if !text.is_empty() && !attach {
+            text.push(' ');
+        }
+        match token.kind {
+            TokenKind::Str => {
+                text.push('\'');
+                text.push_str(&token.text);
+                text.push('\'');
+            }
+            _ => text.push_str(&token.text),
+        }
+    }
+    text
+}
+
+fn column(name: String) -> PropertyInfo {
+    PropertyInfo {
+        name,
+        type_annotation: None,
+        is_static: false,
+        is_readonly: false,
+        visibility: Visibility::Public,
+        tags: None,
+        value: None,
+        is_computed: false,
+        decorators: Vec::new(),
+    }
+}
+
+fn table(name: String, schema: Option<String>, properties: Vec<PropertyInfo>, tokens: &[Token], is_partial: bool) -> ClassInfo {
+    let start = tokens.first().map(|t| t.line).unwrap_or(1);
+    let end = tokens.last().map(|t| t.line).unwrap_or(start);
+    ClassInfo {
+        name,
+        extends: None,
+        implements: Vec::new(),
+        is_exported: true,
+        is_abstract: false,
+        methods: Vec::new(),
+        properties,
+        // Ranges are 0-based like parser output
+        range: Range {
+            start: Position { line: start - 1, column: 0 },
+            end: Position { line: end - 1, column: 0 },
+        },
+        decorators: Vec::new(),
+        namespace: schema,
+        is_partial,
+        type_parameters: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_create_and_alter_table() {
+        let source = r#"
+-- 001_create_users.sql
+CREATE TABLE IF NOT EXISTS public.users (
+    id BIGSERIAL PRIMARY KEY,
+    "email" VARCHAR(255) NOT NULL UNIQUE,
+    balance NUMERIC(10, 2) DEFAULT 0.00,
+    org_id INT REFERENCES orgs(id) ON DELETE CASCADE,
+    full_name TEXT GENERATED ALWAYS AS (first || ' ' || last) STORED,
+    CONSTRAINT users_org_fk FOREIGN KEY (org_id) REFERENCES orgs (id)
+);
+
+/* add a column; rename another */
+ALTER TABLE users ADD COLUMN IF NOT EXISTS last_login TIMESTAMP WITH TIME ZONE, RENAME COLUMN full_name TO display_name;
+ALTER TABLE users DROP COLUMN legacy;
+"#;
+        let result = SqlFileParser::new().parse(source, "migrations/001.sql");
+
+        assert_eq!(result.tables.len(), 2);
+        let users = &result.tables[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(users.namespace.as_deref(), Some("public"));
+        assert_eq!(users.range.start.line, 2);
+        let columns: Vec<(&str, Option<&str>)> = users.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(columns, vec![
+            ("id", Some("BIGSERIAL")),
+            ("email", Some("VARCHAR(255)")),
+            ("balance", Some("NUMERIC(10,2)")),
+            ("org_id", Some("INT")),
+            ("full_name", Some("TEXT")),
+        ]);
+        assert_eq!(users.properties[0].decorators, vec!["PRIMARY KEY"]);
+        assert_eq!(users.properties[1].decorators, vec!["NOT NULL", "UNIQUE"]);
+        assert_eq!(users.properties[2].value.as_deref(), Some("0.00"));
+        assert_eq!(users.properties[3].decorators, vec!["REFERENCES orgs(id)"]);
+        assert!(users.properties[4].is_computed);
+
+        let altered = &result.tables[1];
+        assert!(altered.is_partial);
+        let added: Vec<(&str, Option<&str>)> = altered.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(added, vec![("last_login", Some("TIMESTAMP WITH TIME ZONE")), ("display_name", None)]);
+    }
+
+    #[test]
+    fn test_parse_dml_statements() {
+        let source = r#"
+INSERT INTO users (id, email) VALUES (1, 'a;b@example.com');
+UPDATE users SET email = 'x', balance = balance + 1 WHERE id = 1;
+SELECT u.id, email, COUNT(*) FROM users u JOIN orgs o ON o.id = u.org_id;
+DELETE FROM sessions WHERE expires_at < now();
+CREATE FUNCTION touch() RETURNS trigger AS $$
+BEGIN
+  UPDATE audit SET touched = now();
+END;
+$$ LANGUAGE plpgsql;
+"#;
+        let result = SqlFileParser::new().parse(source, "seed.sql");
+
+        let access: Vec<(&str, DataOperation, Vec<&str>, u32)> = result.access_points.iter()
+            .map(|a| (a.table.as_str(), a.operation, a.fields.iter().map(|f| f.as_str()).collect(), a.line))
+            .collect();
+        assert_eq!(access, vec![
+            ("users", DataOperation::Write, vec!["id", "email"], 2),
+            ("users", DataOperation::Write, vec!["email", "balance"], 3),
+            ("users", DataOperation::Read, vec!["id", "email"], 4),
+            ("orgs", DataOperation::Read, vec![], 4),
+            ("sessions", DataOperation::Delete, vec![], 5),
+        ]);
+        assert!(result.tables.is_empty());
+    }
+
+    fn tables(sql: &str) -> Vec<(String, DataOperation)> {
+        SqlFileParser::new().parse(sql, "query.sql").access_points.into_iter()
+            .map(|a| (a.table, a.operation))
+            .collect()
+    }
+
+    fn read(table: &str) -> (String, DataOperation) {
+        (table.to_string(), DataOperation::Read)
+    }
+
+    #[test]
+    fn test_multi_table_statements() {
+        // Joins, with aliases and comma lists
+        assert_eq!(
+            tables("SELECT o.id, u.email FROM orders o JOIN users AS u ON u.id = o.user_id LEFT JOIN public.payments p USING (order_id)"),
+            vec![read("orders"), read("users"), read("payments")],
+        );
+        assert_eq!(tables("SELECT * FROM orders o, users u WHERE u.id = o.user_id"), vec![read("orders"), read("users")]);
+
+        // Subqueries, UNION and function-style FROM
+        assert_eq!(
+            tables("SELECT EXTRACT(YEAR FROM created_at) FROM users WHERE id IN (SELECT user_id FROM orders) UNION SELECT id FROM admins"),
+            vec![read("users"), read("orders"), read("admins")],
+        );
+        assert_eq!(tables("SELECT * FROM generate_series(1, 3) g JOIN (SELECT id FROM items) i ON true"), vec![read("items")]);
+
+        // The written table is distinguished from the ones read
+        assert_eq!(
+            tables("INSERT INTO archive (id, total) SELECT id, total FROM orders WHERE created_at < now()"),
+            vec![("archive".to_string(), DataOperation::Write), read("orders")],
+        );
+        assert_eq!(
+            tables("DELETE FROM sessions USING users WHERE sessions.user_id = users.id AND users.banned"),
+            vec![("sessions".to_string(), DataOperation::Delete), read("users")],
+        );
+        assert_eq!(
+            tables("UPDATE accounts SET balance = balance - t.amount FROM transfers t WHERE t.account_id = accounts.id"),
+            vec![("accounts".to_string(), DataOperation::Write), read("transfers")],
+        );
+    }
+
+    #[test]
+    fn test_ctes_are_not_tables() {
+        assert_eq!(
+            tables("WITH recent AS (SELECT * FROM orders WHERE created_at > now() - interval '1 day'), totals (user_id, total) AS (SELECT user_id, SUM(total) FROM recent GROUP BY user_id) SELECT u.email, t.total FROM users u JOIN totals t ON t.user_id = u.id"),
+            vec![read("users"), read("orders")],
+        );
+        assert_eq!(
+            tables("WITH RECURSIVE tree AS (SELECT id FROM categories UNION ALL SELECT c.id FROM categories c JOIN tree ON c.parent_id = tree.id) SELECT * FROM tree"),
+            vec![read("categories")],
+        );
+        // A data-modifying CTE writes its table even when the main statement only reads it
+        assert_eq!(
+            tables("WITH moved AS (DELETE FROM queue WHERE done RETURNING *) INSERT INTO history SELECT * FROM moved"),
+            vec![("history".to_string(), DataOperation::Write), ("queue".to_string(), DataOperation::Delete)],
+        );
+    }
+
+    fn fields(sql: &str) -> Vec<(String, DataOperation, Vec<String>)> {
+        SqlFileParser::new().parse(sql, "query.sql").access_points.into_iter()
+            .map(|a| (a.table, a.operation, a.fields))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_and_set_fields() {
+        let strings = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            fields("SELECT DISTINCT u.email AS contact, o.total amount, ssn, LOWER(u.name) AS name, p.* FROM users u JOIN orders AS o ON o.user_id = u.id JOIN payments p ON p.order_id = o.id"),
+            vec![
+                ("users".to_string(), DataOperation::Read, strings(&["email", "ssn"])),
+                ("orders".to_string(), DataOperation::Read, strings(&["total"])),
+                ("payments".to_string(), DataOperation::Read, strings(&["*"])),
+            ],
+        );
+        // `*` covers the FROM clause, not subqueries
+        assert_eq!(
+            fields("SELECT * FROM users WHERE id IN (SELECT user_id FROM orders)"),
+            vec![
+                ("users".to_string(), DataOperation::Read, strings(&["*"])),
+                ("orders".to_string(), DataOperation::Read, vec![]),
+            ],
+        );
+        assert_eq!(
+            fields("UPDATE users u SET u.email = ?, password_hash = ? WHERE id = ?"),
+            vec![("users".to_string(), DataOperation::Write, strings(&["email", "password_hash"]))],
+        );
+    }
+}
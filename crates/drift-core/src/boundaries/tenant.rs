@@ -0,0 +1,234 @@
+//! Tenant scoping check - Queries on tenant tables without the tenant filter
+//!
+//! Opt-in analysis over boundary results. Each access to a configured table
+//! is checked against the text of its query:
+//! - SQL statements must name the scoping column in a WHERE or ON clause
+//!   (INSERTs anywhere, since it belongs in their column list)
+//! - ORM calls must name it somewhere in their call chain
+//!   (`where: { tenantId }`, `.where('tenant_id', id)`, `filter(tenant_id=t)`)
+//!
+//! An access is skipped when its lines, or the line above, carry a
+//! `drift-ignore tenant-scope` (or bare `drift-ignore`) comment.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use regex::Regex;
+
+use super::types::{DataAccessPoint, TenantScopingConfig, TenantViolation};
+
+/// Comment marker that suppresses boundary findings on its line and the next
+pub const IGNORE_MARKER: &str = "drift-ignore";
+
+/// Rule name that limits an `IGNORE_MARKER` to tenant scoping
+const IGNORE_RULE: &str = "tenant-scope";
+
+/// Lines followed from an ORM call looking for the end of its chain
+const MAX_QUERY_LINES: usize = 50;
+
+/// Evidence is cut to this many characters
+const MAX_EVIDENCE_CHARS: usize = 200;
+
+/// Tenant scoping checker
+pub struct TenantScopingChecker {
+    tables: HashSet<String>,
+    column: String,
+    identifier: Regex,
+    sql_start: Regex,
+    filter_clause: Regex,
+    insert: Regex,
+}
+
+impl TenantScopingChecker {
+    pub fn new(config: &TenantScopingConfig) -> Result<Self, String> {
+        if config.column.trim().is_empty() {
+            return Err("Tenant scoping needs the scoping column".to_string());
+        }
+        if config.tables.is_empty() {
+            return Err("Tenant scoping needs at least one tenant-scoped table".to_string());
+        }
+        Ok(Self {
+            tables: config.tables.iter().map(|t| unqualified(t)).collect(),
+            column: normalize(config.column.trim()),
+            identifier: Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap(),
+            sql_start: Regex::new(r"(?i)\b(?:WITH|SELECT|INSERT|UPDATE|DELETE|TRUNCATE)\b").unwrap(),
+            filter_clause: Regex::new(r"(?i)\b(?:WHERE|ON)\b").unwrap(),
+            insert: Regex::new(r"(?i)\bINSERT\s+INTO\b").unwrap(),
+        })
+    }
+
+    /// Accesses in `file` to tenant-scoped tables whose query lacks the column
+    pub fn check_file(&self, source: &str, file: &str, access_points: &[DataAccessPoint]) -> Vec<TenantViolation> {
+        let lines: Vec<&str> = source.lines().collect();
+        let is_sql_file = Path::new(file).extension().is_some_and(|e| e.eq_ignore_ascii_case("sql"));
+
+        access_points.iter()
+            .filter(|access| self.tables.contains(&unqualified(&access.table)))
+            .filter_map(|access| {
+                // SQL statements number lines from 1, AST detections from 0
+                let is_sql = access.framework.as_deref() == Some("sql");
+                let row = if is_sql { access.line.saturating_sub(1) } else { access.line } as usize;
+                if row >= lines.len() {
+                    return None;
+                }
+                let (text, last) = if is_sql {
+                    self.sql_text(&lines, row, is_sql_file)
+                } else {
+                    chain_text(&lines, row)
+                };
+                if lines[row.saturating_sub(1)..=last].iter().any(|line| is_ignored(line)) {
+                    return None;
+                }
+                let scoped = if is_sql { self.sql_is_scoped(&text) } else { self.names_column(&text) };
+                (!scoped).then(|| TenantViolation {
+                    file: access.file.clone(),
+                    line: access.line,
+                    table: access.table.clone(),
+                    operation: access.operation,
+                    evidence: evidence(&text),
+                })
+            })
+            .collect()
+    }
+
+    /// Statement starting on `row`, and its last row: up to a `;`, or in code
+    /// to the quote closing the string it's embedded in
+    fn sql_text(&self, lines: &[&str], row: usize, is_sql_file: bool) -> (String, usize) {
+        let first = lines[row];
+        let start = self.sql_start.find(first).map_or(0, |m| m.start());
+        let ends = |text: &str| text.contains(';') || (!is_sql_file && text.contains(['"', '\'', '`']));
+
+        let mut text = first[start..].to_string();
+        let mut last = row;
+        while !ends(if last == row { &first[start..] } else { lines[last] }) && last + 1 < lines.len() {
+            last += 1;
+            if lines[last].trim().is_empty() {
+                break;
+            }
+            text.push('\n');
+            text.push_str(lines[last]);
+        }
+        (text, last)
+    }
+
+    fn sql_is_scoped(&self, statement: &str) -> bool {
+        if self.insert.is_match(statement) {
+            return self.names_column(statement);
+        }
+        self.filter_clause.find(statement)
+            .is_some_and(|clause| self.names_column(&statement[clause.end()..]))
+    }
+
+    fn names_column(&self, text: &str) -> bool {
+        self.identifier.find_iter(text).any(|ident| normalize(ident.as_str()) == self.column)
+    }
+}
+
+/// Call starting on `row` through its closing bracket and any `.method()`
+/// lines chained after it, and its last row
+fn chain_text(lines: &[&str], row: usize) -> (String, usize) {
+    let mut depth = 0i32;
+    let mut last = row;
+    for (i, line) in lines.iter().enumerate().skip(row).take(MAX_QUERY_LINES) {
+        last = i;
+        for ch in line.chars() {
+            match ch {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        let continues = lines.get(i + 1).is_some_and(|next| next.trim_start().starts_with('.'));
+        if depth <= 0 && !continues {
+            break;
+        }
+    }
+    (lines[row..=last].join("\n"), last)
+}
+
+/// Whether `line` carries a marker that suppresses tenant scoping findings
+fn is_ignored(line: &str) -> bool {
+    line.match_indices(IGNORE_MARKER).any(|(i, _)| {
+        let rule = line[i + IGNORE_MARKER.len()..].trim_start();
+        rule.starts_with(IGNORE_RULE) || !rule.starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+/// `tenantId`, `TenantId` and `tenant_id` all normalize to `tenantid`
+fn normalize(name: &str) -> String {
+    name.replace('_', "").to_lowercase()
+}
+
+/// Lowercase table name without its schema (`public.Invoices` -> `invoices`)
+fn unqualified(table: &str) -> String {
+    table.rsplit('.').next().unwrap_or(table).to_lowercase()
+}
+
+/// Query text on one line, cut to `MAX_EVIDENCE_CHARS`
+fn evidence(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= MAX_EVIDENCE_CHARS {
+        return collapsed;
+    }
+    let cut: String = collapsed.chars().take(MAX_EVIDENCE_CHARS).collect();
+    format!("{}...", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boundaries::{DataAccessDetector, SqlFileParser};
+    use crate::parsers::ParserManager;
+
+    fn checker() -> TenantScopingChecker {
+        TenantScopingChecker::new(&TenantScopingConfig {
+            tables: vec!["invoices".to_string(), "public.projects".to_string()],
+            column: "tenant_id".to_string(),
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_tenant_scoping_in_code() {
+        let source = r#"
+export async function listInvoices(tenantId, status) {
+  const scoped = await prisma.invoices.findMany({
+    where: { tenantId, status },
+  });
+  const all = await prisma.invoices.findMany({ where: { status } });
+  // drift-ignore tenant-scope: admin report across tenants
+  const report = await prisma.invoices.count();
+  const projects = await db.query(`
+    SELECT * FROM projects
+    WHERE projects.tenant_id = $1
+  `, [tenantId]);
+  const leaked = await db.query('SELECT tenant_id, total FROM invoices WHERE status = $1', [status]);
+  await prisma.users.findMany();
+}
+"#;
+        let mut parser = ParserManager::new();
+        let result = parser.parse_file("src/invoices.ts", source).unwrap();
+        let detector = DataAccessDetector::new();
+        let mut access = detector.detect_from_ast(&result, "src/invoices.ts");
+        access.extend(detector.detect_sql_in_source(source, "src/invoices.ts"));
+
+        let violations = checker().check_file(source, "src/invoices.ts", &access);
+        let found: Vec<(u32, &str)> = violations.iter().map(|v| (v.line, v.table.as_str())).collect();
+        assert_eq!(found, vec![(5, "invoices"), (13, "invoices")]);
+        assert_eq!(violations[0].evidence, "const all = await prisma.invoices.findMany({ where: { status } });");
+        assert!(violations[1].evidence.starts_with("SELECT tenant_id, total FROM invoices WHERE status"));
+    }
+
+    #[test]
+    fn test_tenant_scoping_in_sql_files() {
+        let sql = "INSERT INTO invoices (tenant_id, total) VALUES (1, 10);\nUPDATE invoices\nSET total = 0\nWHERE id = 3;\nDELETE FROM public.projects p\nUSING invoices i\nWHERE p.tenant_id = i.tenant_id; -- drift-ignore\n";
+        let access = SqlFileParser::new().parse(sql, "fix.sql").access_points;
+        let violations = checker().check_file(sql, "fix.sql", &access);
+        let found: Vec<(u32, &str)> = violations.iter().map(|v| (v.line, v.table.as_str())).collect();
+        assert_eq!(found, vec![(2, "invoices")]);
+        assert_eq!(violations[0].evidence, "UPDATE invoices SET total = 0 WHERE id = 3;");
+
+        assert!(is_ignored("x(); // drift-ignore"));
+        assert!(!is_ignored("x(); // drift-ignore sensitive-fields"));
+        assert!(TenantScopingChecker::new(&TenantScopingConfig { tables: vec!["t".to_string()], column: " ".to_string() }).is_err());
+    }
+}
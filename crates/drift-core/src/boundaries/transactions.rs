@@ -0,0 +1,287 @@
+//! Transaction detector - Scopes whose data access runs in one transaction
+//!
+//! Scopes come from the AST where the framework marks them:
+//! - `@Transactional` methods and classes (Spring), `@atomic` functions (Django)
+//! - callback scopes: `prisma.$transaction(...)`, `db.transaction(...)`
+//! - `with transaction.atomic():` blocks, which run to the end of the block
+//! - explicit begin/commit pairs: EF `BeginTransaction()`/`Commit()`,
+//!   `TransactionScope`, Go `db.Begin()`, `client.query('BEGIN')`
+//!
+//! `.sql` files are read line by line for `BEGIN;` ... `COMMIT;`. Scope lines
+//! are 0-based rows, like AST ranges; a begin with no commit runs to the end
+//! of its function (or file).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::types::DataAccessPoint;
+use crate::parsers::{CallSite, ParseResult, Range};
+
+/// A transaction scope in one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionScope {
+    /// `file:line` where the transaction opens (1-based line)
+    pub id: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub framework: String,
+}
+
+impl TransactionScope {
+    fn new(file: &str, start_line: u32, end_line: u32, framework: &str) -> Self {
+        Self {
+            id: format!("{}:{}", file, start_line + 1),
+            start_line,
+            end_line,
+            framework: framework.to_string(),
+        }
+    }
+}
+
+/// Transaction scope detector
+pub struct TransactionDetector {
+    sql_begin: Regex,
+    sql_end: Regex,
+}
+
+impl TransactionDetector {
+    pub fn new() -> Self {
+        Self {
+            sql_begin: Regex::new(r"(?i)^\s*(BEGIN|START\s+TRANSACTION)(\s+(TRANSACTION|WORK))?\s*;").unwrap(),
+            // Bare `END;` also closes PL/pgSQL blocks, so only the explicit forms count
+            sql_end: Regex::new(r"(?i)^\s*(COMMIT|ROLLBACK|END\s+(TRANSACTION|WORK))(\s+(TRANSACTION|WORK))?\s*;").unwrap(),
+        }
+    }
+
+    /// Detect transaction scopes from AST decorators and call sites
+    pub fn detect_from_ast(&self, result: &ParseResult, source: &str, file: &str) -> Vec<TransactionScope> {
+        let mut scopes = Vec::new();
+
+        // Declarative: the whole method or class runs in a transaction
+        for class in &result.classes {
+            if let Some(framework) = transactional_decorator(&class.decorators) {
+                scopes.push(TransactionScope::new(file, class.range.start.line, class.range.end.line, framework));
+            }
+        }
+        let functions: Vec<(&Range, &[String])> = result.functions.iter()
+            .chain(result.classes.iter().flat_map(|c| c.methods.iter()))
+            .map(|f| (&f.range, f.decorators.as_slice()))
+            .collect();
+        for (range, decorators) in &functions {
+            if let Some(framework) = transactional_decorator(decorators) {
+                scopes.push(TransactionScope::new(file, range.start.line, range.end.line, framework));
+            }
+        }
+
+        // Explicit begin/commit: each begin closes at the next commit in its function
+        let enclosing_end = |line: u32| functions.iter()
+            .filter(|(range, _)| range.start.line <= line && line <= range.end.line)
+            .map(|(range, _)| range.end.line)
+            .min()
+            .unwrap_or(u32::MAX);
+        let mut calls: Vec<&CallSite> = result.calls.iter().collect();
+        calls.sort_by_key(|call| call.range.start.line);
+
+        for (i, call) in calls.iter().enumerate() {
+            let start = call.range.start.line;
+            let callee = call.callee.as_str();
+            let receiver = call.receiver.as_deref();
+
+            // prisma.$transaction(async (tx) => ...), knex.transaction(trx => ...)
+            if callee == "$transaction" {
+                scopes.push(TransactionScope::new(file, start, call.range.end.line, "prisma"));
+            } else if callee == "transaction" && receiver.is_some() && call.arg_count > 0 {
+                scopes.push(TransactionScope::new(file, start, call.range.end.line, "callback"));
+            } else if callee == "atomic" && matches!(receiver, None | Some("transaction")) {
+                scopes.push(TransactionScope::new(file, start, python_block_end(source, start), "django"));
+            } else if let Some(framework) = begin_framework(call) {
+                let end = enclosing_end(start);
+                let commit = calls[i + 1..].iter()
+                    .take_while(|c| c.range.start.line <= end)
+                    .find(|c| is_commit(c))
+                    .map_or(end, |c| c.range.start.line);
+                scopes.push(TransactionScope::new(file, start, commit, framework));
+            }
+        }
+
+        scopes
+    }
+
+    /// Detect `BEGIN;` ... `COMMIT;` blocks in a `.sql` file
+    pub fn detect_in_sql(&self, source: &str, file: &str) -> Vec<TransactionScope> {
+        let mut scopes = Vec::new();
+        let mut open: Option<u32> = None;
+        let mut last = 0;
+        for (i, line) in source.lines().enumerate() {
+            let row = i as u32;
+            last = row;
+            if open.is_none() && self.sql_begin.is_match(line) {
+                open = Some(row);
+            } else if let Some(start) = open.filter(|_| self.sql_end.is_match(line)) {
+                scopes.push(TransactionScope::new(file, start, row, "sql"));
+                open = None;
+            }
+        }
+        scopes.extend(open.map(|start| TransactionScope::new(file, start, last, "sql")));
+        scopes
+    }
+}
+
+impl Default for TransactionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Set `transaction_id` on the access points inside a scope
+///
+/// `first_line` is the number access point lines start from: 0 for AST
+/// detections, 1 for SQL statements. Nested scopes (savepoints, a
+/// `@Transactional` method inside a `@Transactional` class) join the
+/// outermost transaction.
+pub fn annotate_transactions(access_points: &mut [DataAccessPoint], scopes: &[TransactionScope], first_line: u32) {
+    for access in access_points.iter_mut() {
+        let row = access.line.saturating_sub(first_line);
+        access.transaction_id = scopes.iter()
+            .filter(|s| s.start_line <= row && row <= s.end_line)
+            .min_by_key(|s| (s.start_line, std::cmp::Reverse(s.end_line)))
+            .map(|s| s.id.clone());
+    }
+}
+
+/// Framework of a `@Transactional` / `@transaction.atomic` decorator
+fn transactional_decorator(decorators: &[String]) -> Option<&'static str> {
+    decorators.iter().find_map(|decorator| {
+        let name = decorator.trim_start_matches('@').split('(').next().unwrap_or("");
+        match name.rsplit('.').next().unwrap_or(name).trim() {
+            "Transactional" => Some("spring"),
+            "atomic" => Some("django"),
+            _ => None,
+        }
+    })
+}
+
+/// Framework of a call that opens a transaction
+fn begin_framework(call: &CallSite) -> Option<&'static str> {
+    match call.callee.as_str() {
+        "BeginTransaction" | "BeginTransactionAsync" | "TransactionScope" => Some("ef"),
+        "Begin" | "BeginTx" if call.receiver.is_some() => Some("database/sql"),
+        _ => matches!(sql_command(call).as_deref(), Some("BEGIN" | "BEGIN TRANSACTION" | "START TRANSACTION")).then_some("sql"),
+    }
+}
+
+/// Whether a call ends the transaction (commit or rollback)
+fn is_commit(call: &CallSite) -> bool {
+    matches!(
+        call.callee.as_str(),
+        "Commit" | "CommitAsync" | "commit" | "Rollback" | "RollbackAsync" | "rollback" | "Complete"
+    ) || matches!(sql_command(call).as_deref(), Some("COMMIT" | "ROLLBACK"))
+}
+
+/// A bare transaction statement passed as string (`client.query('BEGIN')`)
+fn sql_command(call: &CallSite) -> Option<String> {
+    let arg = call.string_arg.as_deref()?.trim().trim_end_matches(';').trim();
+    (arg.len() <= "START TRANSACTION".len()).then(|| arg.to_uppercase())
+}
+
+/// Last row of the indented block opened on `row` (a `with` statement)
+fn python_block_end(source: &str, row: u32) -> u32 {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut lines = source.lines().enumerate().skip(row as usize);
+    let Some((_, opener)) = lines.next() else {
+        return row;
+    };
+    let base = indent(opener);
+    let mut end = row;
+    for (i, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent(line) <= base {
+            break;
+        }
+        end = i as u32;
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boundaries::DataAccessDetector;
+    use crate::parsers::ParserManager;
+
+    /// (table, transaction id) of each access in `source`
+    fn transactions(path: &str, source: &str) -> Vec<(String, Option<String>)> {
+        let mut parser = ParserManager::new();
+        let result = parser.parse_file(path, source).unwrap();
+        let scopes = TransactionDetector::new().detect_from_ast(&result, source, path);
+        let mut access = DataAccessDetector::new().detect_from_ast(&result, path);
+        annotate_transactions(&mut access, &scopes, 0);
+        access.into_iter().map(|a| (a.table, a.transaction_id)).collect()
+    }
+
+    fn tx(table: &str, id: Option<&str>) -> (String, Option<String>) {
+        (table.to_string(), id.map(str::to_string))
+    }
+
+    #[test]
+    fn test_prisma_transaction() {
+        let source = r#"
+export async function transfer(from, to) {
+  await prisma.$transaction(async (tx) => {
+    await tx.account.update({ where: { id: from } });
+    await prisma.ledger.create({ data: { from, to } });
+  });
+  await prisma.auditLog.create({ data: { from } });
+}
+"#;
+        assert_eq!(transactions("src/transfer.ts", source), vec![
+            tx("unknown", Some("src/transfer.ts:3")),
+            tx("ledger", Some("src/transfer.ts:3")),
+            tx("auditLog", None),
+        ]);
+    }
+
+    #[test]
+    fn test_django_atomic() {
+        let source = r#"
+def transfer(request):
+    with transaction.atomic():
+        Account.objects.filter(id=1).update(balance=0)
+
+        Ledger.objects.create(amount=1)
+    AuditLog.objects.create(action="transfer")
+
+@transaction.atomic
+def close(request):
+    Account.objects.filter(id=1).delete()
+"#;
+        let found = transactions("bank/views.py", source);
+        let mut ids: Vec<Option<&str>> = found.iter().map(|(_, id)| id.as_deref()).collect();
+        ids.dedup();
+        assert_eq!(ids, vec![Some("bank/views.py:3"), None, Some("bank/views.py:9")]);
+    }
+
+    #[test]
+    fn test_begin_commit_pairs() {
+        let source = r#"
+async function save(client, user) {
+  await client.query('BEGIN');
+  await client.query('INSERT INTO users (email) VALUES ($1)', [user.email]);
+  await client.query('COMMIT');
+  await client.query('DELETE FROM sessions');
+}
+"#;
+        let mut parser = ParserManager::new();
+        let result = parser.parse_file("db.js", source).unwrap();
+        let scopes = TransactionDetector::new().detect_from_ast(&result, source, "db.js");
+        assert_eq!(scopes.len(), 1);
+        assert_eq!((scopes[0].start_line, scopes[0].end_line, scopes[0].framework.as_str()), (2, 4, "sql"));
+
+        let sql = "BEGIN;\nUPDATE accounts SET balance = 0;\nCOMMIT;\nDELETE FROM sessions;\nSTART TRANSACTION;\nINSERT INTO audit VALUES (1);\n";
+        let scopes = TransactionDetector::new().detect_in_sql(sql, "fix.sql");
+        let spans: Vec<(u32, u32)> = scopes.iter().map(|s| (s.start_line, s.end_line)).collect();
+        assert_eq!(spans, vec![(0, 2), (4, 5)]);
+    }
+}
@@ -19,6 +19,16 @@ pub struct DataAccessPoint {
     pub confidence: f32,
     /// Framework that was detected
     pub framework: Option<String>,
+    /// Transaction the access runs in (`file:line` where it opens)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+}
+
+impl DataAccessPoint {
+    /// Whether the access runs inside a detected transaction scope
+    pub fn in_transaction(&self) -> bool {
+        self.transaction_id.is_some()
+    }
 }
 
 /// Data operation type
@@ -39,6 +49,9 @@ pub struct SensitiveField {
     pub table: Option<String>,
     /// Type of sensitivity
     pub sensitivity_type: SensitivityType,
+    /// Label of a `Custom` sensitivity type (`biometric`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     /// Source file
     pub file: String,
     /// Line number
@@ -55,6 +68,81 @@ pub enum SensitivityType {
     Credentials,
     Financial,
     Health,
+    /// Configured by a `SensitiveFieldRule`; the field carries the label
+    Custom,
+}
+
+impl SensitivityType {
+    /// The built-in type named `label`, or `Custom`
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "pii" => SensitivityType::Pii,
+            "credentials" => SensitivityType::Credentials,
+            "financial" => SensitivityType::Financial,
+            "health" => SensitivityType::Health,
+            _ => SensitivityType::Custom,
+        }
+    }
+}
+
+impl SensitiveField {
+    /// The sensitivity as a label: the built-in type's name or the custom label
+    pub fn sensitivity_label(&self) -> &str {
+        match self.sensitivity_type {
+            SensitivityType::Pii => "pii",
+            SensitivityType::Credentials => "credentials",
+            SensitivityType::Financial => "financial",
+            SensitivityType::Health => "health",
+            SensitivityType::Custom => self.label.as_deref().unwrap_or("custom"),
+        }
+    }
+}
+
+/// A configured sensitive field rule
+///
+/// Rules apply after the built-in patterns and replace the built-in matches
+/// they overlap, so they can raise or lower a built-in's confidence, relabel
+/// it, or (with `suppress`) drop it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensitiveFieldRule {
+    /// Regex matched against source lines and field names
+    pub pattern: String,
+    /// `pii`, `credentials`, `financial`, `health` or a custom label
+    pub sensitivity: String,
+    /// Base confidence (0.0-1.0); matches below 0.5 are dropped
+    pub confidence: f32,
+    /// Drop overlapping matches instead of reporting one
+    pub suppress: bool,
+}
+
+/// Sensitive field rules added to the built-in dictionary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensitiveFieldConfig {
+    pub rules: Vec<SensitiveFieldRule>,
+}
+
+/// Tables that belong to a tenant, and the column that scopes them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenantScopingConfig {
+    /// Tenant-scoped tables (matched ignoring case and schema)
+    pub tables: Vec<String>,
+    /// Scoping column (`tenant_id`); `tenantId` and `TenantId` match too
+    pub column: String,
+}
+
+/// An access to a tenant-scoped table that never names the scoping column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantViolation {
+    pub file: String,
+    /// Line of the access point
+    pub line: u32,
+    pub table: String,
+    pub operation: DataOperation,
+    /// The query or call chain that was checked
+    pub evidence: String,
 }
 
 /// An ORM model detected in source code
@@ -66,6 +154,9 @@ pub struct ORMModel {
     pub table_name: String,
     /// Fields in the model
     pub fields: Vec<String>,
+    /// Fields referencing other models
+    #[serde(default)]
+    pub relations: Vec<ModelRelation>,
     /// Source file
     pub file: String,
     /// Line number
@@ -76,6 +167,85 @@ pub struct ORMModel {
     pub confidence: f32,
 }
 
+/// A model field holding related models (`author User @relation(...)`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRelation {
+    /// Field name on the model
+    pub field: String,
+    /// Related model name
+    pub target: String,
+    #[serde(default)]
+    pub kind: RelationKind,
+    /// Whether the field holds many related models
+    pub is_list: bool,
+    /// Foreign key fields on this model
+    pub fields: Vec<String>,
+    /// Fields they reference on the related model
+    pub references: Vec<String>,
+    /// Loaded whenever the model is (TypeORM `eager: true`, SQLAlchemy
+    /// `lazy="joined"`)
+    #[serde(default)]
+    pub eager: bool,
+}
+
+/// Cardinality of a model relation, from the model's side
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelationKind {
+    /// Many of this model to one target, through a foreign key here
+    #[default]
+    BelongsTo,
+    /// One target per model
+    HasOne,
+    /// Many targets per model, through a foreign key on the target
+    HasMany,
+    /// Many targets per model, through a join table
+    ManyToMany,
+}
+
+impl RelationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationKind::BelongsTo => "belongs-to",
+            RelationKind::HasOne => "has-one",
+            RelationKind::HasMany => "has-many",
+            RelationKind::ManyToMany => "many-to-many",
+        }
+    }
+}
+
+/// An ORM model field with no matching column in any SQL migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmigratedField {
+    /// Model/entity name
+    pub model: String,
+    /// Table the model maps to
+    pub table: String,
+    /// Field declared in code
+    pub field: String,
+    /// Model source file
+    pub file: String,
+    /// Model line number
+    pub line: u32,
+}
+
+/// An outbound HTTP request detected in source code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalAccessPoint {
+    /// Static host of the URL, or the URL as written when it has none
+    pub url_or_host: String,
+    /// HTTP method (uppercase), or `UNKNOWN` when it isn't visible at the call
+    pub method: String,
+    /// Source file
+    pub file: String,
+    /// Line number
+    pub line: u32,
+    /// HTTP client that was detected
+    pub framework: String,
+    /// Detection confidence (0.0-1.0)
+    pub confidence: f32,
+}
+
 /// Result of boundary scanning
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoundaryScanResult {
@@ -85,8 +255,24 @@ pub struct BoundaryScanResult {
     pub sensitive_fields: Vec<SensitiveField>,
     /// ORM models found
     pub models: Vec<ORMModel>,
+    /// Model fields missing from the tables defined in `.sql` migrations
+    #[serde(default)]
+    pub unmigrated_fields: Vec<UnmigratedField>,
+    /// Outbound HTTP requests found
+    #[serde(default)]
+    pub external_calls: Vec<ExternalAccessPoint>,
+    /// Writes outside any transaction in files that use transactions, when
+    /// requested with `BoundaryScanner::with_untransacted_writes`
+    #[serde(default)]
+    pub untransacted_writes: Vec<DataAccessPoint>,
     /// Files scanned
     pub files_scanned: usize,
+    /// Files whose cached result was reused
+    #[serde(default)]
+    pub cache_hits: usize,
+    /// Files scanned afresh (every file when caching is off)
+    #[serde(default)]
+    pub cache_misses: usize,
     /// Duration in milliseconds
     pub duration_ms: u64,
 }
@@ -0,0 +1,245 @@
+//! Async Job Edges
+//!
+//! Enqueueing background work breaks the call chain: the worker that runs the
+//! job is never called by the code that enqueues it. Rules find both ends so
+//! resolution can link them with `async` calls:
+//! - enqueue sites become `CallKind::Async` calls from the enclosing function,
+//!   targeting the job name
+//! - handler registrations are stored per file, and resolution prefers them
+//!   for async calls before matching the job name against function names
+//!
+//! Built-in rules cover generic `enqueue('job', ...)` queues, Bull and BullMQ
+//! (`emailQueue.add('job', ...)`, `queue.process('job', handler)`,
+//! `new Worker('job', handler)`) and Celery (`send_email.delay(...)` runs the
+//! task function of that name). Projects add their own as `AsyncEdgeRule`s.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::extractor::{ExtractedCall, ExtractionResult};
+use super::types::CallKind;
+
+/// Built-in rules, applied before configured ones
+static BUILTIN_RULES: Lazy<Vec<AsyncEdgeRule>> = Lazy::new(|| {
+    let rule = |name: &str, enqueue: &[&str], handlers: &[&str]| AsyncEdgeRule {
+        name: name.to_string(),
+        enqueue: enqueue.iter().map(|p| p.to_string()).collect(),
+        handlers: handlers.iter().map(|p| p.to_string()).collect(),
+    };
+    vec![
+        rule("queue", &[r#"\.enqueue\(\s*['"](?P<job>[\w:.-]+)['"]"#], &[]),
+        rule(
+            "bull",
+            &[r#"\b\w*[qQ]ueue\.add\(\s*['"](?P<job>[\w:.-]+)['"]"#],
+            &[
+                r#"\.process\(\s*['"](?P<job>[\w:.-]+)['"]\s*,\s*(?:\d+\s*,\s*)?(?P<handler>[\w$.]+)\s*\)"#,
+                r#"\bnew\s+Worker\(\s*['"](?P<job>[\w:.-]+)['"]\s*,\s*(?P<handler>[\w$.]+)\s*[,)]"#,
+            ],
+        ),
+        rule("celery", &[r"\b(?P<job>[A-Za-z_]\w*)\.(?:delay|apply_async)\("], &[]),
+    ]
+});
+
+/// A configured async job convention
+///
+/// Patterns are regexes: enqueue patterns capture the job name as `job`,
+/// handler patterns capture `job` and the handler function's name as
+/// `handler`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AsyncEdgeRule {
+    pub name: String,
+    pub enqueue: Vec<String>,
+    pub handlers: Vec<String>,
+}
+
+/// A function registered to run a job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsyncHandler {
+    pub job: String,
+    /// Bare function name (`sendEmail` for `handlers.sendEmail`)
+    pub handler: String,
+}
+
+/// Compiled built-in and configured `AsyncEdgeRule`s
+#[derive(Debug)]
+pub struct AsyncEdgeRules {
+    enqueue: Vec<Regex>,
+    handlers: Vec<Regex>,
+}
+
+impl Default for AsyncEdgeRules {
+    fn default() -> Self {
+        Self::new(&[]).expect("built-in async edge rules compile")
+    }
+}
+
+impl AsyncEdgeRules {
+    pub fn new(rules: &[AsyncEdgeRule]) -> Result<Self, String> {
+        let mut enqueue = Vec::new();
+        let mut handlers = Vec::new();
+        for rule in BUILTIN_RULES.iter().chain(rules) {
+            let compile = |pattern: &String, groups: &[&str]| {
+                let re = Regex::new(pattern).map_err(|e| format!("Async edge rule '{}': invalid pattern '{}': {}", rule.name, pattern, e))?;
+                match groups.iter().find(|g| !re.capture_names().any(|name| name == Some(**g))) {
+                    Some(missing) => Err(format!("Async edge rule '{}': pattern '{}' has no '{}' group", rule.name, pattern, missing)),
+                    None => Ok(re),
+                }
+            };
+            for pattern in &rule.enqueue {
+                enqueue.push(compile(pattern, &["job"])?);
+            }
+            for pattern in &rule.handlers {
+                handlers.push(compile(pattern, &["job", "handler"])?);
+            }
+        }
+        Ok(Self { enqueue, handlers })
+    }
+
+    /// Add an async call for each enqueue site in `source` to `extraction`,
+    /// and return the handlers the file registers
+    pub fn apply(&self, source: &str, extraction: &mut ExtractionResult) -> Vec<AsyncHandler> {
+        for re in &self.enqueue {
+            for caps in re.captures_iter(source) {
+                let start = caps.get(0).map_or(0, |m| m.start());
+                extraction.calls.push(ExtractedCall {
+                    callee_name: caps["job"].to_string(),
+                    line: source[..start].matches('\n').count() as u32,
+                    receiver: None,
+                    receiver_type: None,
+                    kind: CallKind::Async,
+                });
+            }
+        }
+
+        let mut handlers: Vec<AsyncHandler> = Vec::new();
+        for re in &self.handlers {
+            for caps in re.captures_iter(source) {
+                let handler = &caps["handler"];
+                let handler = AsyncHandler {
+                    job: caps["job"].to_string(),
+                    handler: handler.rsplit('.').next().unwrap_or(handler).to_string(),
+                };
+                if !handlers.contains(&handler) {
+                    handlers.push(handler);
+                }
+            }
+        }
+        handlers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::function;
+    use crate::call_graph::{CallGraphDb, CallEntry, FunctionBatch};
+    use tempfile::tempdir;
+
+    /// Enqueued jobs with their lines, and (job, handler) pairs
+    type Jobs = (Vec<(String, u32)>, Vec<(String, String)>);
+
+    fn jobs(source: &str) -> Jobs {
+        let mut extraction = ExtractionResult { functions: vec![], calls: vec![] };
+        let handlers = AsyncEdgeRules::default().apply(source, &mut extraction);
+        (
+            extraction.calls.into_iter().map(|c| (c.callee_name, c.line)).collect(),
+            handlers.into_iter().map(|h| (h.job, h.handler)).collect(),
+        )
+    }
+
+    #[test]
+    fn test_detect_async_jobs() {
+        let producer = r#"
+export async function signup(req) {
+  await queue.enqueue('sendWelcome', { id: req.body.id });
+  await emailQueue.add("sendReceipt", payload);
+  cache.add('key', value);
+}
+"#;
+        let (enqueued, handlers) = jobs(producer);
+        assert_eq!(enqueued, vec![("sendWelcome".to_string(), 2), ("sendReceipt".to_string(), 3)]);
+        assert!(handlers.is_empty());
+
+        let worker = r#"
+emailQueue.process('sendReceipt', 5, handlers.sendReceipt);
+const worker = new Worker('sendWelcome', sendWelcome, { connection });
+"#;
+        let (_, handlers) = jobs(worker);
+        assert_eq!(handlers, vec![
+            ("sendReceipt".to_string(), "sendReceipt".to_string()),
+            ("sendWelcome".to_string(), "sendWelcome".to_string()),
+        ]);
+
+        let (enqueued, _) = jobs("def checkout(order):\n    charge_card.delay(order.id)\n");
+        assert_eq!(enqueued, vec![("charge_card".to_string(), 1)]);
+
+        let custom = AsyncEdgeRules::new(&[AsyncEdgeRule {
+            name: "jobs".to_string(),
+            enqueue: vec![r#"dispatchJob\(\s*"(?P<job>\w+)""#.to_string()],
+            handlers: vec![],
+        }]).unwrap();
+        let mut extraction = ExtractionResult { functions: vec![], calls: vec![] };
+        custom.apply(r#"dispatchJob("reindex")"#, &mut extraction);
+        assert_eq!(extraction.calls[0].callee_name, "reindex");
+
+        let missing_group = AsyncEdgeRules::new(&[AsyncEdgeRule {
+            name: "broken".to_string(),
+            enqueue: vec![],
+            handlers: vec![r"register\((?P<job>\w+)\)".to_string()],
+        }]);
+        assert!(missing_group.unwrap_err().contains("no 'handler' group"));
+    }
+
+    fn enqueue(job: &str) -> CallEntry {
+        CallEntry {
+            target: job.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 2,
+            receiver: None,
+            receiver_type: None,
+            kind: CallKind::Async,
+        }
+    }
+
+    #[test]
+    fn test_async_job_resolution() {
+        let dir = tempdir().unwrap();
+        let mut db = CallGraphDb::open(&dir.path().join("test.db")).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/api.ts".to_string(),
+            functions: vec![function("src/api.ts:signup:1", vec![enqueue("welcome-email"), enqueue("charge_card")])],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/workers.ts".to_string(),
+            functions: vec![
+                function("src/workers.ts:sendWelcome:1", vec![]),
+                function("src/workers.ts:charge_card:20", vec![]),
+            ],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![AsyncHandler { job: "welcome-email".to_string(), handler: "sendWelcome".to_string() }],
+        }).unwrap();
+        db.resolve_calls().unwrap();
+
+        // Registered handlers first, then the job name as a function name
+        let calls = db.get_calls_from("src/api.ts:signup:1").unwrap();
+        assert_eq!(calls[0].resolved_id.as_deref(), Some("src/workers.ts:sendWelcome:1"));
+        assert_eq!(calls[1].resolved_id.as_deref(), Some("src/workers.ts:charge_card:20"));
+        assert!(calls.iter().all(|c| c.kind == CallKind::Async));
+
+        let strategies = db.get_strategy_counts().unwrap();
+        assert!(strategies.contains(&("async-handler".to_string(), 1)), "{:?}", strategies);
+
+        db.delete_files(&["src/workers.ts".to_string()]).unwrap();
+        assert!(db.get_async_handlers().unwrap().is_empty());
+    }
+}
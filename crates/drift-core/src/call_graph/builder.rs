@@ -13,22 +13,29 @@
 //! Two build modes:
 //! - `build()` - Legacy JSON shard mode (backward compatible)
 //! - `build_sqlite()` - New SQLite mode with parallel parsing (recommended)
+//!
+//! `update_files()` refreshes an existing SQLite graph for a set of changed files.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use std::time::Instant;
 
 use rayon::prelude::*;
 
 use crate::parsers::{ParserManager, Language};
-use crate::scanner::{Scanner, ScanConfig};
-use crate::boundaries::DataAccessDetector;
+use crate::scanner::{detect_language_from_content, is_generated, is_minified, FileInfo, Scanner, ScanConfig};
+use crate::boundaries::{DataAccessDetector, ExternalCallDetector};
 use super::types::*;
-use super::extractor::to_function_entries;
-use super::universal_extractor::UniversalExtractor;
-use super::storage::{ParallelWriter, FunctionBatch};
+use super::extractor::{to_function_entries, CallGraphExtractor};
+use super::universal_extractor::{base_type_name, UniversalExtractor};
+use super::storage::{CallGraphDb, DbStats, ParallelWriter, FunctionBatch, TypeEntry};
+use super::imports::ModuleIndex;
+use super::di::detect_di_bindings;
+use super::async_edges::{AsyncEdgeRule, AsyncEdgeRules};
+use super::entry_points::{EntryPointRule, EntryPointRules};
 
 /// Configuration for the streaming builder
 pub struct BuilderConfig {
@@ -36,8 +43,18 @@ pub struct BuilderConfig {
     pub root_dir: PathBuf,
     /// Batch size for resolution pass
     pub resolution_batch_size: usize,
-    /// Progress callback
-    pub on_progress: Option<Box<dyn Fn(usize, usize, &str) + Send + Sync>>,
+    /// Files written to SQLite per transaction (and buffered per parsing
+    /// thread before being handed to the writer)
+    pub write_batch_size: usize,
+    /// Progress callback, invoked from worker threads while parsing
+    pub on_progress: Option<Box<dyn Fn(BuildProgress) + Send + Sync>>,
+    /// Include files the scanner flagged as minified or generated
+    pub include_generated: bool,
+    /// Project-specific entry point conventions, checked in order
+    pub entry_point_rules: Vec<EntryPointRule>,
+    /// Project-specific enqueue and job handler conventions, added to the
+    /// built-in ones
+    pub async_edge_rules: Vec<AsyncEdgeRule>,
 }
 
 impl Default for BuilderConfig {
@@ -45,7 +62,11 @@ impl Default for BuilderConfig {
         Self {
             root_dir: PathBuf::from("."),
             resolution_batch_size: 50,
+            write_batch_size: 100,
             on_progress: None,
+            include_generated: false,
+            entry_point_rules: Vec::new(),
+            async_edge_rules: Vec::new(),
         }
     }
 }
@@ -57,6 +78,11 @@ pub struct StreamingBuilder {
     extractor: UniversalExtractor,
     shards_dir: PathBuf,
     resolution_index_path: PathBuf,
+    entry_point_rules: EntryPointRules,
+    async_edge_rules: AsyncEdgeRules,
+    /// Why `entry_point_rules` or `async_edge_rules` failed to compile,
+    /// reported by every build
+    rules_error: Option<String>,
 }
 
 impl StreamingBuilder {
@@ -64,6 +90,16 @@ impl StreamingBuilder {
         let drift_dir = config.root_dir.join(".drift");
         let shards_dir = drift_dir.join("lake").join("callgraph").join("files");
         let resolution_index_path = drift_dir.join("lake").join("callgraph").join("resolution-index.ndjson");
+        let (entry_point_rules, entry_error) = match EntryPointRules::new(&config.entry_point_rules) {
+            Ok(rules) => (rules, None),
+            Err(e) => (EntryPointRules::default(), Some(e)),
+        };
+        let (async_edge_rules, async_error) = match AsyncEdgeRules::new(&config.async_edge_rules) {
+            Ok(rules) => (rules, None),
+            Err(e) => (AsyncEdgeRules::default(), Some(e)),
+        };
+        let errors: Vec<String> = entry_error.into_iter().chain(async_error).collect();
+        let rules_error = (!errors.is_empty()).then(|| errors.join("; "));
         
         Self {
             config,
@@ -71,6 +107,9 @@ impl StreamingBuilder {
             extractor: UniversalExtractor::new(),
             shards_dir,
             resolution_index_path,
+            entry_point_rules,
+            async_edge_rules,
+            rules_error,
         }
     }
     
@@ -94,7 +133,7 @@ impl StreamingBuilder {
     /// - ~0.5ms per file on modern hardware
     pub fn build_sqlite(&self, patterns: &[&str]) -> BuildResult {
         let start = Instant::now();
-        let mut errors: Vec<String> = Vec::new();
+        let mut errors: Vec<String> = self.rules_error.iter().cloned().collect();
         
         // Ensure directories exist
         let db_path = self.db_path();
@@ -102,6 +141,8 @@ impl StreamingBuilder {
             fs::create_dir_all(parent).ok();
         }
         
+        self.report(&start, BuildPhase::Scanning, 0, 0, None);
+        
         // Find all matching files
         let scanner = Scanner::new(ScanConfig {
             root: self.config.root_dir.clone(),
@@ -111,55 +152,78 @@ impl StreamingBuilder {
         
         let scan_result = scanner.scan();
         let files: Vec<_> = scan_result.files.iter()
-            .filter(|f| Language::from_path(&f.path).is_some())
+            .filter(|f| self.should_process(f))
             .collect();
         
         let total_files = files.len();
         
-        // Create parallel writer (spawns background thread)
-        let writer = ParallelWriter::new(db_path.clone(), 100); // Batch size of 100 files
+        // Imports resolve against every scanned file, not just the parsed ones
+        let modules = ModuleIndex::new(scan_result.files.iter().map(|f| f.path.as_str()));
+        
+        // Create parallel writer (spawns background thread); resolution runs
+        // afterwards so it can be reported as its own phase
+        let write_batch_size = self.config.write_batch_size.max(1);
+        let writer = ParallelWriter::without_resolution(db_path.clone(), write_batch_size);
         let sender = writer.sender();
         
         // Track progress atomically
         let progress_counter = std::sync::atomic::AtomicUsize::new(0);
         let root_dir = self.config.root_dir.clone();
-        let on_progress = &self.config.on_progress;
         
-        // Parallel parsing with rayon
+        // Parallel parsing with rayon; each thread buffers its parsed files and
+        // hands them to the writer a write batch at a time
+        let send = |batches: Vec<FunctionBatch>, errors: &mut Vec<String>| {
+            if batches.is_empty() {
+                return;
+            }
+            if let Err(mpsc::SendError(batches)) = sender.send(batches) {
+                errors.extend(batches.iter().map(|b| format!("{}: Writer channel closed", b.file)));
+            }
+        };
         let parse_errors: Vec<String> = files
             .par_iter()
-            .filter_map(|file_info| {
-                // Progress callback
-                let current = progress_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                if let Some(ref cb) = on_progress {
-                    cb(current + 1, total_files, &file_info.path);
-                }
-                
-                // Process file
-                match Self::process_file_static(&root_dir, &file_info.path) {
-                    Ok(Some(batch)) => {
-                        // Send to writer thread
-                        if sender.send(batch).is_err() {
-                            Some(format!("{}: Writer channel closed", file_info.path))
-                        } else {
-                            None
-                        }
+            .fold(
+                || (Vec::with_capacity(write_batch_size), Vec::new()),
+                |(mut buffer, mut errors), file_info| {
+                    // Progress callback
+                    let current = progress_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    self.report(&start, BuildPhase::Parsing, current + 1, total_files, Some(&file_info.path));
+                    
+                    // Process file
+                    match Self::process_file_static(&root_dir, &file_info.path, &modules, &self.entry_point_rules, &self.async_edge_rules) {
+                        Ok(Some(batch)) => buffer.push(batch),
+                        Ok(None) => {} // No functions in file
+                        Err(e) => errors.push(format!("{}: {}", file_info.path, e)),
                     }
-                    Ok(None) => None, // No functions in file
-                    Err(e) => Some(format!("{}: {}", file_info.path, e)),
-                }
+                    if buffer.len() >= write_batch_size {
+                        send(std::mem::take(&mut buffer), &mut errors);
+                    }
+                    (buffer, errors)
+                },
+            )
+            .map(|(buffer, mut errors)| {
+                send(buffer, &mut errors);
+                errors
             })
+            .flatten()
             .collect();
         
         errors.extend(parse_errors);
         
         // Drop sender to signal writer to finish, then wait for completion
         drop(sender);
-        let stats = match writer.finish() {
+        self.report(&start, BuildPhase::Writing, total_files, total_files, None);
+        let stats = writer.finish().and_then(|_| {
+            self.report(&start, BuildPhase::Resolving, total_files, total_files, None);
+            let mut db = CallGraphDb::open(&db_path)?;
+            db.resolve_calls()?;
+            db.get_stats()
+        });
+        let stats = match stats {
             Ok(s) => s,
             Err(e) => {
                 errors.push(format!("SQLite error: {}", e));
-                super::storage::DbStats::default()
+                DbStats::default()
             }
         };
         
@@ -179,11 +243,163 @@ impl StreamingBuilder {
             data_accessors: stats.data_accessors,
             errors,
             duration_ms: start.elapsed().as_millis() as u64,
+            functions_added: 0,
+            functions_removed: 0,
+            functions_updated: 0,
         }
     }
     
+    /// Incrementally update the SQLite call graph after `changed_files` changed
+    ///
+    /// Rows for each file are replaced - or only deleted when the file no longer
+    /// exists - and resolution re-runs just for calls from those files and calls
+    /// targeting a function name they define (before or after the change).
+    /// Paths are relative to the project root; absolute paths under it are accepted.
+    pub fn update_files(&self, changed_files: &[String]) -> BuildResult {
+        let start = Instant::now();
+        let mut errors: Vec<String> = self.rules_error.iter().cloned().collect();
+        
+        let mut files: Vec<String> = changed_files.iter().map(|f| self.relative_path(f)).collect();
+        files.sort();
+        files.dedup();
+        
+        let mut db = match CallGraphDb::open(&self.db_path()) {
+            Ok(db) => db,
+            Err(e) => {
+                errors.push(format!("SQLite error: {}", e));
+                return BuildResult {
+                    files_processed: 0,
+                    total_functions: 0,
+                    total_calls: 0,
+                    resolved_calls: 0,
+                    resolution_rate: 0.0,
+                    entry_points: 0,
+                    data_accessors: 0,
+                    errors,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    functions_added: 0,
+                    functions_removed: 0,
+                    functions_updated: 0,
+                };
+            }
+        };
+        
+        // Imports resolve against the files already in the graph plus the changed ones.
+        // Unchanged files keep the imports recorded when they were last parsed.
+        let known_files = db.get_files().unwrap_or_default();
+        let modules = ModuleIndex::new(known_files.iter().chain(&files).map(|f| f.as_str()));
+        
+        // Re-parse the files that still exist, with the same filtering as a full build
+        let root_dir = &self.config.root_dir;
+        let include_generated = self.config.include_generated;
+        let progress_counter = std::sync::atomic::AtomicUsize::new(0);
+        let parsed: Vec<Result<Option<FunctionBatch>, String>> = files
+            .par_iter()
+            .filter(|file| {
+                let Ok(source) = fs::read_to_string(root_dir.join(file)) else { return false };
+                let parseable = Language::from_path(file)
+                    .or_else(|| detect_language_from_content(&source).and_then(Language::from_name))
+                    .is_some();
+                parseable && (include_generated || !(is_minified(file, &source) || is_generated(&source)))
+            })
+            .map(|file| {
+                let processed = Self::process_file_static(root_dir, file, &modules, &self.entry_point_rules, &self.async_edge_rules).map_err(|e| format!("{}: {}", file, e));
+                let current = progress_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                self.report(&start, BuildPhase::Parsing, current + 1, files.len(), Some(file));
+                processed
+            })
+            .collect();
+        
+        let mut batches = Vec::new();
+        for result in parsed {
+            match result {
+                Ok(Some(batch)) => batches.push(batch),
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+        
+        self.report(&start, BuildPhase::Writing, files.len(), files.len(), None);
+        
+        let mut names: HashSet<String> = HashSet::new();
+        let mut before: HashSet<String> = HashSet::new();
+        let mut after: HashSet<String> = HashSet::new();
+        
+        let update = (|| {
+            for (id, name) in db.delete_files(&files)? {
+                before.insert(function_key(&id).to_string());
+                names.insert(name);
+            }
+            db.insert_batches(&batches)?;
+            for func in batches.iter().flat_map(|b| &b.functions) {
+                after.insert(function_key(&func.id).to_string());
+                names.insert(func.name.clone());
+            }
+            let names: Vec<String> = names.into_iter().collect();
+            self.report(&start, BuildPhase::Resolving, files.len(), files.len(), None);
+            db.resolve_calls_for(&files, &names)?;
+            db.get_stats()
+        })();
+        
+        let stats = match update {
+            Ok(s) => s,
+            Err(e) => {
+                errors.push(format!("SQLite error: {}", e));
+                DbStats::default()
+            }
+        };
+        
+        let resolution_rate = if stats.total_calls > 0 {
+            stats.resolved_calls as f32 / stats.total_calls as f32
+        } else {
+            0.0
+        };
+        
+        BuildResult {
+            files_processed: files.len(),
+            total_functions: stats.total_functions,
+            total_calls: stats.total_calls,
+            resolved_calls: stats.resolved_calls,
+            resolution_rate,
+            entry_points: stats.entry_points,
+            data_accessors: stats.data_accessors,
+            errors,
+            duration_ms: start.elapsed().as_millis() as u64,
+            functions_added: after.difference(&before).count(),
+            functions_removed: before.difference(&after).count(),
+            functions_updated: after.intersection(&before).count(),
+        }
+    }
+    
+    /// Send a progress event to the configured callback, if any
+    fn report(&self, start: &Instant, phase: BuildPhase, files_processed: usize, total_files: usize, current_file: Option<&str>) {
+        if let Some(ref on_progress) = self.config.on_progress {
+            on_progress(BuildProgress {
+                phase,
+                files_processed,
+                total_files,
+                elapsed_ms: start.elapsed().as_millis() as u64,
+                current_file: current_file.map(|f| f.to_string()),
+            });
+        }
+    }
+    
+    /// Project-relative form of a changed file path
+    fn relative_path(&self, file: &str) -> String {
+        Path::new(file)
+            .strip_prefix(&self.config.root_dir)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| file.to_string())
+    }
+    
     /// Process a single file (static version for parallel use)
-    fn process_file_static(root_dir: &PathBuf, file: &str) -> Result<Option<FunctionBatch>, String> {
+    fn process_file_static(
+        root_dir: &Path,
+        file: &str,
+        modules: &ModuleIndex,
+        rules: &EntryPointRules,
+        async_rules: &AsyncEdgeRules,
+    ) -> Result<Option<FunctionBatch>, String> {
         use std::cell::RefCell;
         
         // Thread-local parser, extractor, and data access detector to avoid re-initialization overhead
@@ -191,6 +407,7 @@ impl StreamingBuilder {
             static PARSER: RefCell<ParserManager> = RefCell::new(ParserManager::new());
             static EXTRACTOR: UniversalExtractor = UniversalExtractor::new();
             static DATA_ACCESS_DETECTOR: DataAccessDetector = DataAccessDetector::new();
+            static EXTERNAL_CALL_DETECTOR: ExternalCallDetector = ExternalCallDetector::new();
         }
         
         let full_path = root_dir.join(file);
@@ -205,11 +422,16 @@ impl StreamingBuilder {
         }).ok_or_else(|| "Unsupported language".to_string())?;
         
         // Extract functions and calls using thread-local extractor
-        let extraction = EXTRACTOR.with(|extractor| {
-            extractor.extract_from_parse_result(&parse_result)
+        let mut extraction = EXTRACTOR.with(|extractor| {
+            extractor.extract(&parse_result, file)
         });
+        rules.apply(file, &parse_result, &mut extraction);
+        let async_handlers = async_rules.apply(&source, &mut extraction);
         
-        if extraction.functions.is_empty() {
+        // Registrations often live in files without functions (a Nest module)
+        let di_bindings = detect_di_bindings(&parse_result, &source);
+        
+        if extraction.functions.is_empty() && di_bindings.is_empty() && async_handlers.is_empty() {
             return Ok(None);
         }
         
@@ -225,6 +447,12 @@ impl StreamingBuilder {
         });
         data_access.extend(sql_access);
         
+        // Outbound HTTP requests become accesses on `external:<host>` sinks
+        let external_access = EXTERNAL_CALL_DETECTOR.with(|detector| {
+            detector.detect_from_ast(&parse_result, file)
+        });
+        data_access.extend(external_access.iter().map(|call| call.as_data_access()));
+        
         // Convert DataAccessPoint to DataAccessRef for function entries
         let data_access_refs: Vec<DataAccessRef> = data_access
             .into_iter()
@@ -243,16 +471,42 @@ impl StreamingBuilder {
         // Convert to function entries with data access
         let functions = to_function_entries(file, &extraction, &data_access_refs);
         
+        // Project files this file imports, for import-aware resolution
+        let mut imports: Vec<String> = parse_result.imports
+            .iter()
+            .flat_map(|import| modules.resolve(file, import))
+            .filter(|imported| imported != file)
+            .collect();
+        imports.sort();
+        imports.dedup();
+        
+        // Type hierarchy, for resolving calls on typed receivers
+        let types = parse_result.classes
+            .iter()
+            .map(|class| TypeEntry {
+                name: class.name.clone(),
+                supertypes: class.extends.iter()
+                    .chain(&class.implements)
+                    .filter_map(|t| base_type_name(t))
+                    .collect(),
+                is_abstract: class.is_abstract,
+            })
+            .collect();
+        
         Ok(Some(FunctionBatch {
             file: file.to_string(),
             functions,
+            imports,
+            types,
+            di_bindings,
+            async_handlers,
         }))
     }
 
     /// Build call graph with streaming/sharded storage (legacy JSON mode)
     pub fn build(&mut self, patterns: &[&str]) -> BuildResult {
         let start = Instant::now();
-        let mut errors = Vec::new();
+        let mut errors: Vec<String> = self.rules_error.iter().cloned().collect();
         
         // Ensure directories exist
         fs::create_dir_all(&self.shards_dir).ok();
@@ -266,7 +520,7 @@ impl StreamingBuilder {
         
         let scan_result = scanner.scan();
         let files: Vec<_> = scan_result.files.iter()
-            .filter(|f| Language::from_path(&f.path).is_some())
+            .filter(|f| self.should_process(f))
             .collect();
         
         let total_files = files.len();
@@ -277,9 +531,7 @@ impl StreamingBuilder {
         
         // Phase 1: Extract and save shards
         for (i, file_info) in files.iter().enumerate() {
-            if let Some(ref cb) = self.config.on_progress {
-                cb(i + 1, total_files, &file_info.path);
-            }
+            self.report(&start, BuildPhase::Parsing, i + 1, total_files, Some(&file_info.path));
             
             match self.process_file(&file_info.path) {
                 Ok(Some(shard)) => {
@@ -308,6 +560,7 @@ impl StreamingBuilder {
         }
         
         // Phase 2: Resolution pass
+        self.report(&start, BuildPhase::Resolving, total_files, total_files, None);
         let resolved_calls = self.run_resolution_pass();
         
         let resolution_rate = if total_calls > 0 {
@@ -326,6 +579,9 @@ impl StreamingBuilder {
             data_accessors,
             errors,
             duration_ms: start.elapsed().as_millis() as u64,
+            functions_added: 0,
+            functions_removed: 0,
+            functions_updated: 0,
         }
     }
 
@@ -342,7 +598,10 @@ impl StreamingBuilder {
             .ok_or_else(|| "Unsupported language".to_string())?;
         
         // Extract functions and calls
-        let extraction = self.extractor.extract_from_parse_result(&parse_result);
+        let mut extraction = self.extractor.extract(&parse_result, file);
+        self.entry_point_rules.apply(file, &parse_result, &mut extraction);
+        // Shards have nowhere to keep handlers; job names still resolve by name
+        self.async_edge_rules.apply(&source, &mut extraction);
         
         if extraction.functions.is_empty() {
             return Ok(None);
@@ -358,6 +617,10 @@ impl StreamingBuilder {
         let sql_access = data_detector.detect_sql_in_source(&source, file);
         data_access.extend(sql_access);
         
+        // Outbound HTTP requests as `external:<host>` sinks
+        let external_access = ExternalCallDetector::new().detect_from_ast(&parse_result, file);
+        data_access.extend(external_access.iter().map(|call| call.as_data_access()));
+        
         // Convert DataAccessPoint to DataAccessRef
         let data_access_refs: Vec<DataAccessRef> = data_access
             .into_iter()
@@ -540,11 +803,20 @@ impl StreamingBuilder {
             }
             
             if let Ok(entry) = serde_json::from_str::<ResolutionEntry>(&line) {
-                // Build name -> [ids] index
-                function_index
-                    .entry(entry.name.clone())
-                    .or_default()
-                    .push(entry.id.clone());
+                // Build name -> [ids] index (partial class declarations share one ID)
+                let ids = function_index.entry(entry.name.clone()).or_default();
+                if !ids.contains(&entry.id) {
+                    ids.push(entry.id.clone());
+                }
+                
+                // Qualified methods ("User.save") are also indexed as ".save" so
+                // bare `x.save()` calls can fall back to them
+                if let Some((_, method)) = entry.name.rsplit_once('.') {
+                    let ids = function_index.entry(format!(".{}", method)).or_default();
+                    if !ids.contains(&entry.id) {
+                        ids.push(entry.id.clone());
+                    }
+                }
                 
                 // Build id -> file index
                 function_files.insert(entry.id, entry.file);
@@ -562,9 +834,16 @@ impl StreamingBuilder {
         function_index: &HashMap<String, Vec<String>>,
         function_files: &HashMap<String, String>,
     ) -> Resolution {
-        let candidates = match function_index.get(target) {
-            Some(c) if !c.is_empty() => c,
-            _ => return Resolution::unresolved(),
+        // Exact name first, then between qualified ("User.save") and bare ("save") method names
+        let fallback = match target.rsplit_once('.') {
+            Some((_, method)) => method.to_string(),
+            None => format!(".{}", target),
+        };
+        let candidates = match function_index.get(target).filter(|c| !c.is_empty())
+            .or_else(|| function_index.get(&fallback).filter(|c| !c.is_empty()))
+        {
+            Some(c) => c,
+            None => return Resolution::unresolved(),
         };
         
         // Strategy 1: Same file (highest confidence)
@@ -606,6 +885,26 @@ impl StreamingBuilder {
             confidence: 0.4,
         }
     }
+    
+    /// Whether a scanned file should be parsed into the call graph
+    ///
+    /// Minified bundles and generated code are skipped unless
+    /// `include_generated` is set - they produce thousands of meaningless call sites.
+    fn should_process(&self, file: &FileInfo) -> bool {
+        is_parseable(file) && (self.config.include_generated || !(file.is_minified || file.is_generated))
+    }
+}
+
+/// Whether a scanned file can be parsed (by extension, or by the scanner's shebang sniffing)
+fn is_parseable(file: &FileInfo) -> bool {
+    Language::from_path(&file.path)
+        .or_else(|| file.language.as_deref().and_then(Language::from_name))
+        .is_some()
+}
+
+/// Function identity that survives line moves: "file:name:line" without the line
+fn function_key(id: &str) -> &str {
+    id.rsplit_once(':').map(|(key, _)| key).unwrap_or(id)
 }
 
 /// Resolution result
@@ -646,4 +945,70 @@ mod tests {
         // Hash is hex-encoded u64, so 16 chars
         assert!(hash1.len() >= 12);
     }
+    
+    #[test]
+    fn test_update_files_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("main.ts"), "export function main() {\n  helper();\n  legacy();\n}\n").unwrap();
+        fs::write(src.join("utils.ts"), "export function helper() {}\n").unwrap();
+        fs::write(src.join("legacy.ts"), "export function legacy() {}\n").unwrap();
+        
+        let builder = StreamingBuilder::new(BuilderConfig {
+            root_dir: dir.path().to_path_buf(),
+            write_batch_size: 2,
+            ..Default::default()
+        });
+        let full = builder.build_sqlite(&["**/*.ts"]);
+        assert_eq!(full.total_functions, 3);
+        assert_eq!(full.resolved_calls, 2);
+        
+        // helper moves down a line and gains a sibling; legacy.ts is deleted
+        fs::write(src.join("utils.ts"), "\nexport function helper() {}\nexport function format() {}\n").unwrap();
+        fs::remove_file(src.join("legacy.ts")).unwrap();
+        
+        let changed = vec!["src/utils.ts".to_string(), src.join("legacy.ts").to_string_lossy().to_string()];
+        let result = builder.update_files(&changed);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.files_processed, 2);
+        assert_eq!(result.functions_added, 1);
+        assert_eq!(result.functions_removed, 1);
+        assert_eq!(result.functions_updated, 1);
+        assert_eq!(result.total_functions, 3);
+        assert_eq!(result.resolved_calls, 1);
+        
+        let db = CallGraphDb::open(&builder.db_path()).unwrap();
+        let calls = db.get_calls_from("src/main.ts:main:0").unwrap();
+        let helper = calls.iter().find(|c| c.target == "helper").unwrap();
+        assert_eq!(helper.resolved_id.as_deref(), Some("src/utils.ts:helper:1"));
+        assert!(calls.iter().find(|c| c.target == "legacy").unwrap().resolved_id.is_none());
+    }
+    
+    #[test]
+    fn test_build_progress_phases() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "export function a() { b(); }\n").unwrap();
+        fs::write(dir.path().join("b.ts"), "export function b() {}\n").unwrap();
+        
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let builder = StreamingBuilder::new(BuilderConfig {
+            root_dir: dir.path().to_path_buf(),
+            on_progress: Some(Box::new(move |p| sink.lock().unwrap().push(p))),
+            ..Default::default()
+        });
+        let result = builder.build_sqlite(&["**/*.ts"]);
+        assert_eq!(result.resolved_calls, 1);
+        
+        let events = events.lock().unwrap();
+        let mut phases: Vec<BuildPhase> = events.iter().map(|e| e.phase).collect();
+        phases.dedup();
+        assert_eq!(phases, vec![BuildPhase::Scanning, BuildPhase::Parsing, BuildPhase::Writing, BuildPhase::Resolving]);
+        
+        let parsed: Vec<&BuildProgress> = events.iter().filter(|e| e.phase == BuildPhase::Parsing).collect();
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.iter().all(|e| e.total_files == 2 && e.current_file.is_some()));
+        assert_eq!(events.last().unwrap().files_processed, 2);
+    }
 }
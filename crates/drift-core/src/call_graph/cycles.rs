@@ -0,0 +1,321 @@
+//! Recursion Cycles
+//!
+//! Tarjan's strongly connected components over resolved calls. Each component
+//! with more than one function (or a function calling itself) is a recursion
+//! cycle: every member can reach every other member.
+//!
+//! `Condensation` keeps the graph alongside its components so traversals can
+//! step over a cycle as a unit instead of enumerating paths around it.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+
+/// A function inside a recursion cycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleMember {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+}
+
+/// A strongly connected component of the call graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallCycle {
+    /// Members sorted by ID
+    pub functions: Vec<CycleMember>,
+    /// Distinct caller -> callee pairs between members
+    pub internal_edges: usize,
+}
+
+/// Call graph over node indices, condensed into strongly connected components
+pub struct Condensation {
+    ids: Vec<String>,
+    index: HashMap<String, usize>,
+    callees: Vec<Vec<usize>>,
+    callers: Vec<Vec<usize>>,
+    component_of: Vec<usize>,
+    /// Components in reverse topological order (callees before callers)
+    components: Vec<Vec<usize>>,
+}
+
+impl Condensation {
+    /// Build from function IDs and caller -> callee edges; edges to unknown
+    /// functions are dropped
+    pub fn new(
+        ids: impl IntoIterator<Item = String>,
+        edges: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut nodes: Vec<String> = Vec::new();
+        for id in ids {
+            if !index.contains_key(&id) {
+                index.insert(id.clone(), nodes.len());
+                nodes.push(id);
+            }
+        }
+
+        let mut callees: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        let mut callers: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for (from, to) in edges {
+            if let (Some(&from), Some(&to)) = (index.get(&from), index.get(&to)) {
+                callees[from].push(to);
+            }
+        }
+        for (from, targets) in callees.iter_mut().enumerate() {
+            targets.sort_unstable();
+            targets.dedup();
+            for &to in targets.iter() {
+                callers[to].push(from);
+            }
+        }
+
+        let components = tarjan(&callees);
+        let mut component_of = vec![0; nodes.len()];
+        for (c, members) in components.iter().enumerate() {
+            for &node in members {
+                component_of[node] = c;
+            }
+        }
+
+        Self { ids: nodes, index, callees, callers, component_of, components }
+    }
+
+    pub fn node(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    pub fn id(&self, node: usize) -> &str {
+        &self.ids[node]
+    }
+
+    pub fn component(&self, node: usize) -> usize {
+        self.component_of[node]
+    }
+
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+
+    /// Whether a component is a cycle (several members, or a self call)
+    pub fn is_cycle(&self, component: usize) -> bool {
+        match self.components[component].as_slice() {
+            [node] => self.callees[*node].contains(node),
+            _ => true,
+        }
+    }
+
+    /// Calls between members of a component
+    pub fn internal_edges(&self, component: usize) -> usize {
+        self.components[component]
+            .iter()
+            .map(|&node| self.callees[node].iter().filter(|&&to| self.component_of[to] == component).count())
+            .sum()
+    }
+
+    /// Calls leaving a component, as (member, callee) pairs
+    pub fn exits(&self, component: usize) -> Vec<(usize, usize)> {
+        self.components[component]
+            .iter()
+            .flat_map(|&node| {
+                self.callees[node]
+                    .iter()
+                    .filter(move |&&to| self.component_of[to] != component)
+                    .map(move |&to| (node, to))
+            })
+            .collect()
+    }
+
+    /// Nodes that can reach `target` (including itself)
+    pub fn reaching(&self, target: usize) -> Vec<bool> {
+        let mut reaches = vec![false; self.ids.len()];
+        let mut queue = VecDeque::from([target]);
+        reaches[target] = true;
+        while let Some(node) = queue.pop_front() {
+            for &caller in &self.callers[node] {
+                if !reaches[caller] {
+                    reaches[caller] = true;
+                    queue.push_back(caller);
+                }
+            }
+        }
+        reaches
+    }
+
+    /// Shortest path from `from` to `to` through members of their shared
+    /// component, inclusive of both ends
+    pub fn internal_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let component = self.component_of[from];
+        if self.component_of[to] != component {
+            return None;
+        }
+        let mut previous: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        previous.insert(from, from);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = previous[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &next in &self.callees[node] {
+                if self.component_of[next] == component && !previous.contains_key(&next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Iterative Tarjan SCC, so deep call chains can't overflow the stack
+fn tarjan(callees: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let n = callees.len();
+    let mut index = vec![UNVISITED; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut frames: Vec<(usize, usize)> = Vec::new();
+    let mut next_index = 0;
+    let mut components: Vec<Vec<usize>> = Vec::new();
+
+    for start in 0..n {
+        if index[start] != UNVISITED {
+            continue;
+        }
+        index[start] = next_index;
+        lowlink[start] = next_index;
+        next_index += 1;
+        stack.push(start);
+        on_stack[start] = true;
+        frames.push((start, 0));
+
+        while let Some(frame) = frames.last_mut() {
+            let node = frame.0;
+            if let Some(&next) = callees[node].get(frame.1) {
+                frame.1 += 1;
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    lowlink[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    frames.push((next, 0));
+                } else if on_stack[next] {
+                    lowlink[node] = lowlink[node].min(index[next]);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[node]);
+            }
+            if lowlink[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Recursion cycles with at least `min_size` functions, largest first
+pub fn find_call_cycles(db: &CallGraphDb, min_size: usize) -> Result<Vec<CallCycle>, String> {
+    let functions = db.get_all_functions().map_err(|e| e.to_string())?;
+    let names: HashMap<&str, &str> = functions.iter().map(|f| (f.id.as_str(), f.name.as_str())).collect();
+    let graph = Condensation::new(
+        functions.iter().map(|f| f.id.clone()),
+        functions.iter().flat_map(|f| {
+            f.calls.iter().filter_map(|c| c.resolved_id.clone()).map(|to| (f.id.clone(), to))
+        }),
+    );
+
+    let mut cycles: Vec<CallCycle> = (0..graph.components().len())
+        .filter(|&c| graph.components()[c].len() >= min_size && graph.is_cycle(c))
+        .map(|c| {
+            let mut members: Vec<CycleMember> = graph.components()[c]
+                .iter()
+                .map(|&node| {
+                    let id = graph.id(node);
+                    CycleMember {
+                        id: id.to_string(),
+                        name: names.get(id).copied().unwrap_or_default().to_string(),
+                        file: CallGraphDb::extract_file_from_id(id).to_string(),
+                    }
+                })
+                .collect();
+            members.sort_by(|a, b| a.id.cmp(&b.id));
+            CallCycle { functions: members, internal_edges: graph.internal_edges(c) }
+        })
+        .collect();
+    cycles.sort_by(|a, b| {
+        b.functions.len().cmp(&a.functions.len()).then_with(|| a.functions[0].id.cmp(&b.functions[0].id))
+    });
+
+    Ok(cycles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> Condensation {
+        let mut ids: Vec<String> = edges.iter().flat_map(|(a, b)| [a.to_string(), b.to_string()]).collect();
+        ids.sort();
+        ids.dedup();
+        Condensation::new(ids, edges.iter().map(|(a, b)| (a.to_string(), b.to_string())))
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let g = graph(&[
+            ("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"),
+            ("d", "d"), ("d", "e"),
+        ]);
+        let component = |id: &str| g.component(g.node(id).unwrap());
+
+        assert_eq!(component("a"), component("b"));
+        assert_eq!(component("b"), component("c"));
+        assert_ne!(component("c"), component("d"));
+        assert!(g.is_cycle(component("a")));
+        assert!(g.is_cycle(component("d")));
+        assert!(!g.is_cycle(component("e")));
+        assert_eq!(g.internal_edges(component("a")), 3);
+        assert_eq!(g.internal_edges(component("d")), 1);
+        // Callees come before their callers
+        assert!(component("e") < component("d") && component("d") < component("a"));
+
+        let path: Vec<&str> = g.internal_path(g.node("b").unwrap(), g.node("a").unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|n| g.id(n))
+            .collect();
+        assert_eq!(path, vec!["b", "c", "a"]);
+        assert_eq!(g.exits(component("a")), vec![(g.node("c").unwrap(), g.node("d").unwrap())]);
+    }
+
+    #[test]
+    fn test_deep_chain_does_not_overflow() {
+        let edges: Vec<(String, String)> = (0..200_000).map(|i| (i.to_string(), (i + 1).to_string())).collect();
+        let g = Condensation::new((0..=200_000).map(|i| i.to_string()), edges);
+        assert_eq!(g.components().len(), 200_001);
+    }
+}
@@ -0,0 +1,161 @@
+//! Dead Code Detection
+//!
+//! Finds functions that no root reaches through resolved calls. Roots are
+//! framework entry points (route annotations, file conventions), `main`
+//! functions and - for library packages - exported functions. Exports of
+//! application code are not roots on their own: an exported function nothing
+//! calls is still dead.
+//!
+//! Databases built before exports were recorded treat every entry point as a
+//! root, since exported and framework entry points can't be told apart.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+use super::types::FunctionEntry;
+use crate::test_topology::is_test_path;
+
+/// What to report and what to treat as roots
+#[derive(Debug, Clone, Default)]
+pub struct DeadCodeOptions {
+    /// Skip functions in test files (`*.test.ts`, `test_*.py`, ...)
+    pub exclude_tests: bool,
+    /// Skip functions whose name matches any of these regexes (e.g. `^migrate_`)
+    pub exclude_patterns: Vec<String>,
+    /// Path prefixes of library packages, whose exported functions are roots
+    /// ("" or "." for the whole project)
+    pub library_packages: Vec<String>,
+}
+
+/// A function no root reaches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnreachableFunction {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub line: u32,
+    pub is_exported: bool,
+}
+
+/// Every function unreachable from the roots, sorted by file, line and name
+pub fn find_unreachable_functions(db: &CallGraphDb, options: &DeadCodeOptions) -> Result<Vec<UnreachableFunction>, String> {
+    let exclude: Vec<Regex> = options.exclude_patterns
+        .iter()
+        .map(|p| Regex::new(p).map_err(|e| format!("Invalid exclude pattern '{}': {}", p, e)))
+        .collect::<Result<_, _>>()?;
+
+    let functions = db.get_all_functions().map_err(|e| e.to_string())?;
+    let by_id: HashMap<&str, &FunctionEntry> = functions.iter().map(|f| (f.id.as_str(), f)).collect();
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut queue: VecDeque<&FunctionEntry> = functions
+        .iter()
+        .filter(|f| is_root(f, options))
+        .collect();
+    while let Some(func) = queue.pop_front() {
+        if !visited.insert(func.id.as_str()) {
+            continue;
+        }
+        for target in func.calls.iter().filter_map(|c| c.resolved_id.as_deref()) {
+            if let Some(callee) = by_id.get(target) {
+                if !visited.contains(target) {
+                    queue.push_back(callee);
+                }
+            }
+        }
+    }
+
+    let mut unreachable: Vec<UnreachableFunction> = functions
+        .iter()
+        .filter(|f| !visited.contains(f.id.as_str()))
+        .filter(|f| !(options.exclude_tests && is_test_path(CallGraphDb::extract_file_from_id(&f.id))))
+        .filter(|f| !exclude.iter().any(|re| re.is_match(&f.name)))
+        .map(|f| UnreachableFunction {
+            id: f.id.clone(),
+            name: f.name.clone(),
+            file: CallGraphDb::extract_file_from_id(&f.id).to_string(),
+            line: f.start_line,
+            is_exported: f.is_exported,
+        })
+        .collect();
+    unreachable.sort_by(|a, b| {
+        (&a.file, a.line, &a.name, &a.id).cmp(&(&b.file, b.line, &b.name, &b.id))
+    });
+
+    Ok(unreachable)
+}
+
+fn is_root(func: &FunctionEntry, options: &DeadCodeOptions) -> bool {
+    let file = CallGraphDb::extract_file_from_id(&func.id);
+    if is_test_path(file) {
+        return false;
+    }
+    let is_main = func.name == "main" || func.name.ends_with(".main");
+    let is_library_export = func.is_exported && options.library_packages.iter().any(|p| in_package(file, p));
+    func.entry_kind.is_some() || (func.is_entry_point && !func.is_exported) || is_main || is_library_export
+}
+
+fn in_package(file: &str, package: &str) -> bool {
+    let package = package.trim_start_matches("./").trim_end_matches('/');
+    package.is_empty() || package == "." || file == package || file.starts_with(&format!("{}/", package))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::{self, exported, function, resolved_calls};
+    use tempfile::tempdir;
+
+    fn sample_db(dir: &std::path::Path) -> CallGraphDb {
+        let mut list_users = exported("app/routes.py:list_users:3", resolved_calls(&["app/service.py:load_users:1"]));
+        list_users.entry_kind = Some("route".to_string());
+        fixtures::sample_db(dir, vec![
+            list_users,
+            exported("app/service.py:load_users:1", vec![]),
+            exported("app/service.py:unused_export:20", resolved_calls(&["app/service.py:helper:30"])),
+            function("app/service.py:helper:30", vec![]),
+            exported("app/migrations.py:migrate_users:1", vec![]),
+            exported("lib/format.py:format_name:1", vec![]),
+            exported("tests/test_service.py:test_load:1", resolved_calls(&["app/service.py:load_users:1"])),
+        ])
+    }
+
+    fn names(unreachable: &[UnreachableFunction]) -> Vec<&str> {
+        unreachable.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_unreachable_functions_sorted() {
+        let dir = tempdir().unwrap();
+        let db = sample_db(dir.path());
+
+        let unreachable = find_unreachable_functions(&db, &DeadCodeOptions::default()).unwrap();
+
+        assert_eq!(names(&unreachable), vec!["migrate_users", "unused_export", "helper", "format_name", "test_load"]);
+        assert_eq!(unreachable[1].file, "app/service.py");
+        assert_eq!(unreachable[1].line, 20);
+        assert!(unreachable[1].is_exported);
+        assert!(!unreachable[2].is_exported);
+    }
+
+    #[test]
+    fn test_unreachable_functions_options() {
+        let dir = tempdir().unwrap();
+        let db = sample_db(dir.path());
+
+        let unreachable = find_unreachable_functions(&db, &DeadCodeOptions {
+            exclude_tests: true,
+            exclude_patterns: vec!["^migrate_".to_string()],
+            library_packages: vec!["lib".to_string()],
+        }).unwrap();
+        assert_eq!(names(&unreachable), vec!["unused_export", "helper"]);
+
+        assert!(find_unreachable_functions(&db, &DeadCodeOptions {
+            exclude_patterns: vec!["(".to_string()],
+            ..Default::default()
+        }).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Dependency Injection Bindings
+//!
+//! Finds the interface -> implementation bindings a DI container is
+//! configured with, so calls on an interface-typed receiver (an injected
+//! `IUserService`) resolve to the class the container actually provides:
+//! - ASP.NET Core: `services.AddScoped<IUserService, UserService>()` (and
+//!   `AddTransient`, `AddSingleton`, `TryAdd*`)
+//! - NestJS: `{ provide: UserService, useClass: PgUserService }` providers
+//! - Spring: a `@Service` / `@Component` / `@Repository` class binds each
+//!   interface it implements
+//!
+//! Resolution only uses an interface's binding when every binding for it
+//! names the same implementation - two `@Service` implementors, or
+//! per-environment registrations, leave the call to receiver-type resolution.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::parsers::{Language, ParseResult};
+use super::universal_extractor::base_type_name;
+
+/// `AddScoped<IService, Service>` - captures interface and implementation
+static ASPNET_REGISTRATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:Try)?Add(?:Scoped|Transient|Singleton)\s*<\s*([\w.]+)\s*,\s*([\w.]+)\s*>").unwrap()
+});
+
+/// An object literal without nested braces, e.g. a Nest provider
+static OBJECT_LITERAL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^{}]*\}").unwrap());
+static NEST_PROVIDE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bprovide\s*:\s*['"]?([\w$]+)['"]?"#).unwrap());
+static NEST_USE_CLASS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\buseClass\s*:\s*([\w$]+)").unwrap());
+
+/// Spring stereotype annotations that register a class as a bean
+const SPRING_COMPONENTS: &[&str] = &["Service", "Component", "Repository"];
+
+/// An interface bound to the class the container provides for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiBinding {
+    pub interface: String,
+    pub implementation: String,
+}
+
+/// Bindings registered or declared in one parsed file
+pub fn detect_di_bindings(parse_result: &ParseResult, source: &str) -> Vec<DiBinding> {
+    let mut bindings = Vec::new();
+    match parse_result.language {
+        Language::CSharp => {
+            for caps in ASPNET_REGISTRATION.captures_iter(source) {
+                push_binding(&mut bindings, &caps[1], &caps[2]);
+            }
+        }
+        Language::TypeScript | Language::JavaScript => {
+            for object in OBJECT_LITERAL.find_iter(source) {
+                let object = object.as_str();
+                if let (Some(provide), Some(use_class)) = (NEST_PROVIDE.captures(object), NEST_USE_CLASS.captures(object)) {
+                    push_binding(&mut bindings, &provide[1], &use_class[1]);
+                }
+            }
+        }
+        Language::Java | Language::Kotlin => {
+            for class in &parse_result.classes {
+                let is_component = class.decorators.iter().any(|d| {
+                    let name = d.trim_start_matches('@').split('(').next().unwrap_or(d).trim();
+                    SPRING_COMPONENTS.contains(&name.rsplit('.').next().unwrap_or(name))
+                });
+                if is_component {
+                    for interface in &class.implements {
+                        push_binding(&mut bindings, interface, &class.name);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    bindings
+}
+
+/// Add a binding by bare type names, skipping self-bindings
+/// (`AddScoped<UserService, UserService>`)
+fn push_binding(bindings: &mut Vec<DiBinding>, interface: &str, implementation: &str) {
+    let bare = |ty: &str| base_type_name(ty).map(|t| t.rsplit('.').next().unwrap_or(&t).to_string());
+    if let (Some(interface), Some(implementation)) = (bare(interface), bare(implementation)) {
+        let binding = DiBinding { interface, implementation };
+        if binding.interface != binding.implementation && !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::function;
+    use crate::call_graph::{CallEntry, CallKind, CallGraphDb, FunctionBatch, TypeEntry};
+    use crate::parsers::ParserManager;
+    use tempfile::tempdir;
+
+    fn bindings(file: &str, source: &str) -> Vec<(String, String)> {
+        let parse_result = ParserManager::new().parse_file(file, source).unwrap();
+        detect_di_bindings(&parse_result, source)
+            .into_iter()
+            .map(|b| (b.interface, b.implementation))
+            .collect()
+    }
+
+    fn pair(interface: &str, implementation: &str) -> (String, String) {
+        (interface.to_string(), implementation.to_string())
+    }
+
+    #[test]
+    fn test_detect_di_bindings() {
+        let csharp = r#"
+public static class Startup {
+    public static void Configure(IServiceCollection services) {
+        services.AddScoped<IUserService, UserService>();
+        services.TryAddSingleton<Acme.Caching.ICache, Acme.Caching.RedisCache>();
+        services.AddTransient<Clock, Clock>();
+    }
+}
+"#;
+        assert_eq!(bindings("Startup.cs", csharp), vec![pair("IUserService", "UserService"), pair("ICache", "RedisCache")]);
+
+        let nest = r#"
+@Module({
+  providers: [
+    UsersService,
+    { provide: UserRepository, useClass: PgUserRepository },
+    { useClass: SmtpMailer, provide: 'Mailer' },
+    { provide: Config, useValue: config },
+  ],
+})
+export class UsersModule {}
+"#;
+        assert_eq!(bindings("users.module.ts", nest), vec![pair("UserRepository", "PgUserRepository"), pair("Mailer", "SmtpMailer")]);
+
+        let spring = r#"
+@Service
+public class JpaUserService implements UserService, Auditable {
+    public User find(long id) { return null; }
+}
+
+public class UserServiceStub implements UserService {
+    public User find(long id) { return null; }
+}
+"#;
+        assert_eq!(bindings("JpaUserService.java", spring), vec![pair("UserService", "JpaUserService"), pair("Auditable", "JpaUserService")]);
+    }
+
+    fn method_call(target: &str, receiver_type: &str) -> CallEntry {
+        CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 2,
+            receiver: Some("service".to_string()),
+            receiver_type: Some(receiver_type.to_string()),
+            kind: CallKind::Method,
+        }
+    }
+
+    fn implementation(name: &str, interface: &str) -> TypeEntry {
+        TypeEntry { name: name.to_string(), supertypes: vec![interface.to_string()], is_abstract: false }
+    }
+
+    #[test]
+    fn test_di_binding_resolution() {
+        let dir = tempdir().unwrap();
+        let mut db = CallGraphDb::open(&dir.path().join("test.db")).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/Controllers.cs".to_string(),
+            functions: vec![function("src/Controllers.cs:UsersController.Get:1", vec![
+                method_call("GetUser", "IUserService"),
+                method_call("Send", "IMailer"),
+            ])],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        // Alphabetically first, so receiver-type resolution alone would pick the mock
+        db.insert_batch(&FunctionBatch {
+            file: "src/Services.cs".to_string(),
+            functions: vec![
+                function("src/Services.cs:AMockUserService.GetUser:1", vec![]),
+                function("src/Services.cs:UserService.GetUser:10", vec![]),
+                function("src/Services.cs:AMockMailer.Send:20", vec![]),
+                function("src/Services.cs:SmtpMailer.Send:30", vec![]),
+            ],
+            imports: vec![],
+            types: vec![
+                TypeEntry { name: "IUserService".to_string(), supertypes: vec![], is_abstract: true },
+                TypeEntry { name: "IMailer".to_string(), supertypes: vec![], is_abstract: true },
+                implementation("AMockUserService", "IUserService"),
+                implementation("UserService", "IUserService"),
+                implementation("AMockMailer", "IMailer"),
+                implementation("SmtpMailer", "IMailer"),
+            ],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        // IMailer is bound differently per environment, so it stays ambiguous
+        db.insert_batch(&FunctionBatch {
+            file: "src/Startup.cs".to_string(),
+            functions: vec![],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![
+                DiBinding { interface: "IUserService".to_string(), implementation: "UserService".to_string() },
+                DiBinding { interface: "IMailer".to_string(), implementation: "SmtpMailer".to_string() },
+                DiBinding { interface: "IMailer".to_string(), implementation: "AMockMailer".to_string() },
+            ],
+            async_handlers: vec![],
+        }).unwrap();
+        db.resolve_calls().unwrap();
+
+        let calls = db.get_calls_from("src/Controllers.cs:UsersController.Get:1").unwrap();
+        assert_eq!(calls[0].resolved_id.as_deref(), Some("src/Services.cs:UserService.GetUser:10"));
+        assert_eq!(calls[1].resolved_id.as_deref(), Some("src/Services.cs:AMockMailer.Send:20"));
+
+        let strategies = db.get_strategy_counts().unwrap();
+        assert!(strategies.contains(&("di-binding".to_string(), 1)), "{:?}", strategies);
+        assert!(strategies.contains(&("receiver-type".to_string(), 1)), "{:?}", strategies);
+
+        // Removing the registration file drops its bindings
+        assert_eq!(db.get_di_bindings().unwrap().len(), 3);
+        db.delete_files(&["src/Startup.cs".to_string()]).unwrap();
+        assert!(db.get_di_bindings().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! Call Graph Diff
+//!
+//! Compares two call graph databases - e.g. built from a PR's base and head -
+//! to show which functions, calls and data accesses a change introduces.
+//!
+//! Function IDs embed a line number, so any edit above a function would make
+//! it look removed and re-added. Functions are instead matched by file and
+//! qualified name (`src/users.ts:UserService.getUser`), which is also the key
+//! reported for them. Functions sharing a key (overloads) are merged.
+//!
+//! Every list is sorted, so diffing the same databases twice gives identical
+//! results.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+use super::types::FunctionEntry;
+
+/// A function on one side of the diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffFunction {
+    /// "file:qualified_name"
+    pub key: String,
+    pub file: String,
+    pub name: String,
+    /// Start line in the build the function is reported from
+    pub line: u32,
+}
+
+/// A resolved call between two functions, by key
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DiffEdge {
+    pub caller: String,
+    pub callee: String,
+}
+
+/// A data accessor reachable from an entry point in head but not in base
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffAccessor {
+    pub function: DiffFunction,
+    /// Tables it accesses, sorted
+    pub tables: Vec<String>,
+}
+
+/// Differences between a base and a head call graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallGraphDiff {
+    pub added_functions: Vec<DiffFunction>,
+    pub removed_functions: Vec<DiffFunction>,
+    pub added_edges: Vec<DiffEdge>,
+    pub removed_edges: Vec<DiffEdge>,
+    pub newly_reachable_accessors: Vec<DiffAccessor>,
+    pub added_entry_points: Vec<DiffFunction>,
+    pub removed_entry_points: Vec<DiffFunction>,
+}
+
+/// Diff the call graph databases at `base_db_path` and `head_db_path`
+pub fn diff_call_graphs(base_db_path: &Path, head_db_path: &Path) -> Result<CallGraphDiff, String> {
+    let open = |path: &Path| {
+        CallGraphDb::open_readonly(path).map_err(|e| format!("Failed to open call graph database {}: {}", path.display(), e))
+    };
+    let base_db = open(base_db_path)?;
+    let head_db = open(head_db_path)?;
+    let base = Snapshot::load(&base_db)?;
+    let head = Snapshot::load(&head_db)?;
+
+    let mut diff = CallGraphDiff {
+        added_functions: head.functions_missing_from(&base),
+        removed_functions: base.functions_missing_from(&head),
+        added_edges: head.edges.difference(&base.edges).cloned().collect(),
+        removed_edges: base.edges.difference(&head.edges).cloned().collect(),
+        added_entry_points: head.entry_points_missing_from(&base),
+        removed_entry_points: base.entry_points_missing_from(&head),
+        ..Default::default()
+    };
+
+    let reachable_before = base.reachable_from_entry_points();
+    for key in head.reachable_from_entry_points() {
+        let function = &head.functions[key];
+        if !function.is_data_accessor || reachable_before.contains(key) {
+            continue;
+        }
+        let mut tables = BTreeSet::new();
+        for id in &head.ids[key] {
+            let access = head_db.get_data_access(id).map_err(|e| e.to_string())?;
+            tables.extend(access.into_iter().map(|a| a.table));
+        }
+        diff.newly_reachable_accessors.push(DiffAccessor {
+            function: head.describe(key),
+            tables: tables.into_iter().collect(),
+        });
+    }
+
+    Ok(diff)
+}
+
+/// One build's functions and resolved calls, keyed by file and name
+struct Snapshot {
+    /// First function per key (lowest ID)
+    functions: BTreeMap<String, FunctionEntry>,
+    /// Every function ID merged into a key
+    ids: HashMap<String, Vec<String>>,
+    edges: BTreeSet<DiffEdge>,
+}
+
+impl Snapshot {
+    fn load(db: &CallGraphDb) -> Result<Self, String> {
+        let all = db.get_all_functions().map_err(|e| e.to_string())?;
+        let key_of: HashMap<String, String> = all.iter().map(|f| (f.id.clone(), function_key(f))).collect();
+
+        let mut edges = BTreeSet::new();
+        for function in &all {
+            for callee in function.calls.iter().filter_map(|c| c.resolved_id.as_deref()) {
+                if let Some(callee) = key_of.get(callee) {
+                    edges.insert(DiffEdge { caller: key_of[&function.id].clone(), callee: callee.clone() });
+                }
+            }
+        }
+
+        let mut functions: BTreeMap<String, FunctionEntry> = BTreeMap::new();
+        let mut ids: HashMap<String, Vec<String>> = HashMap::new();
+        for function in all {
+            let key = key_of[&function.id].clone();
+            ids.entry(key.clone()).or_default().push(function.id.clone());
+            // Merged functions count as entry points or accessors if any of them is
+            let (is_entry_point, is_data_accessor) = (function.is_entry_point, function.is_data_accessor);
+            let merged = functions.entry(key).or_insert(FunctionEntry { calls: Vec::new(), ..function });
+            merged.is_entry_point |= is_entry_point;
+            merged.is_data_accessor |= is_data_accessor;
+        }
+
+        Ok(Self { functions, ids, edges })
+    }
+
+    fn describe(&self, key: &str) -> DiffFunction {
+        let function = &self.functions[key];
+        DiffFunction {
+            key: key.to_string(),
+            file: CallGraphDb::extract_file_from_id(&function.id).to_string(),
+            name: function.name.clone(),
+            line: function.start_line,
+        }
+    }
+
+    fn functions_missing_from(&self, other: &Snapshot) -> Vec<DiffFunction> {
+        self.functions
+            .keys()
+            .filter(|key| !other.functions.contains_key(*key))
+            .map(|key| self.describe(key))
+            .collect()
+    }
+
+    fn entry_points_missing_from(&self, other: &Snapshot) -> Vec<DiffFunction> {
+        self.functions
+            .iter()
+            .filter(|(key, f)| f.is_entry_point && !other.functions.get(*key).is_some_and(|o| o.is_entry_point))
+            .map(|(key, _)| self.describe(key))
+            .collect()
+    }
+
+    /// Keys reachable from any entry point (including the entry points), sorted
+    fn reachable_from_entry_points(&self) -> BTreeSet<&str> {
+        let mut callees: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            callees.entry(edge.caller.as_str()).or_default().push(edge.callee.as_str());
+        }
+
+        let mut reachable: BTreeSet<&str> = BTreeSet::new();
+        let mut queue: VecDeque<&str> = self.functions
+            .iter()
+            .filter(|(_, f)| f.is_entry_point)
+            .map(|(key, _)| key.as_str())
+            .collect();
+        while let Some(key) = queue.pop_front() {
+            if !reachable.insert(key) {
+                continue;
+            }
+            queue.extend(callees.get(key).into_iter().flatten().filter(|k| !reachable.contains(*k)));
+        }
+        reachable
+    }
+}
+
+fn function_key(function: &FunctionEntry) -> String {
+    format!("{}:{}", CallGraphDb::extract_file_from_id(&function.id), function.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::{access, accessor, exported, resolved_calls, sample_db};
+    use crate::call_graph::DataOperation;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_call_graphs() {
+        let base_dir = tempdir().unwrap();
+        let head_dir = tempdir().unwrap();
+        let base = sample_db(base_dir.path(), vec![
+            exported("api.ts:listUsers:1", resolved_calls(&["users.ts:findUsers:1"])),
+            exported("api.ts:legacy:20", vec![]),
+            accessor("users.ts:findUsers:1", vec![], vec![access("users", DataOperation::Read, &[])]),
+            accessor("audit.ts:readAudit:1", vec![], vec![access("audit_log", DataOperation::Read, &[])]),
+        ]).path().to_path_buf();
+        // Everything in users.ts moved down; listUsers now also reads the audit log
+        let head = sample_db(head_dir.path(), vec![
+            exported("api.ts:listUsers:1", resolved_calls(&["users.ts:findUsers:8", "audit.ts:readAudit:1"])),
+            exported("api.ts:exportUsers:30", resolved_calls(&["users.ts:findUsers:8"])),
+            accessor("users.ts:findUsers:8", vec![], vec![access("users", DataOperation::Read, &[])]),
+            accessor("audit.ts:readAudit:1", vec![], vec![access("audit_log", DataOperation::Read, &[])]),
+        ]).path().to_path_buf();
+
+        let diff = diff_call_graphs(&base, &head).unwrap();
+        let keys = |functions: &[DiffFunction]| functions.iter().map(|f| f.key.clone()).collect::<Vec<_>>();
+
+        assert_eq!(keys(&diff.added_functions), vec!["api.ts:exportUsers"]);
+        assert_eq!(keys(&diff.removed_functions), vec!["api.ts:legacy"]);
+        assert_eq!(diff.removed_functions[0].line, 20);
+        assert_eq!(diff.added_edges, vec![
+            DiffEdge { caller: "api.ts:exportUsers".to_string(), callee: "users.ts:findUsers".to_string() },
+            DiffEdge { caller: "api.ts:listUsers".to_string(), callee: "audit.ts:readAudit".to_string() },
+        ]);
+        assert!(diff.removed_edges.is_empty());
+        assert_eq!(keys(&diff.added_entry_points), vec!["api.ts:exportUsers"]);
+        assert_eq!(keys(&diff.removed_entry_points), vec!["api.ts:legacy"]);
+        assert_eq!(diff.newly_reachable_accessors.len(), 1);
+        assert_eq!(diff.newly_reachable_accessors[0].function.key, "audit.ts:readAudit");
+        assert_eq!(diff.newly_reachable_accessors[0].tables, vec!["audit_log"]);
+
+        let again = diff_call_graphs(&base, &head).unwrap();
+        assert_eq!(serde_json::to_string(&diff).unwrap(), serde_json::to_string(&again).unwrap());
+    }
+}
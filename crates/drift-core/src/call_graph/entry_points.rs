@@ -0,0 +1,267 @@
+//! File-convention entry points
+//!
+//! Some frameworks route requests by where a file lives rather than by
+//! decorators. Next.js app-router files are entry points by location:
+//! the default export of `app/**/page.tsx` and `app/**/layout.tsx`, and the
+//! HTTP-verb exports of `app/**/route.ts`.
+//!
+//! Project-specific conventions (queue consumers, cron registries, CLI
+//! handlers) are configured as `EntryPointRule`s.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::parsers::{ClassInfo, FunctionInfo, ParseResult};
+use super::extractor::ExtractionResult;
+use super::universal_extractor::base_type_name;
+
+/// HTTP methods a Next.js route handler may export
+const NEXTJS_ROUTE_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Entry kinds for the functions in a file, as (function name, kind) pairs
+pub fn convention_entry_points(file: &str, result: &ParseResult) -> Vec<(String, &'static str)> {
+    let Some((stem, ext)) = app_router_file(file) else {
+        return Vec::new();
+    };
+
+    match (stem, ext) {
+        ("page" | "layout", "tsx" | "jsx" | "ts" | "js") => {
+            let kind = if stem == "page" { "nextjs-page" } else { "nextjs-layout" };
+            result.exports.iter()
+                .filter(|e| e.is_default)
+                .map(|e| (e.name.clone(), kind))
+                .collect()
+        }
+        ("route", "ts" | "js") => result.exports.iter()
+            .filter(|e| NEXTJS_ROUTE_METHODS.contains(&e.name.as_str()))
+            .map(|e| (e.name.clone(), "nextjs-route"))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// File stem and extension of a file under an `app/` directory
+fn app_router_file(file: &str) -> Option<(&str, &str)> {
+    let normalized = file.strip_prefix("./").unwrap_or(file);
+    let mut segments: Vec<&str> = normalized.split(['/', '\\']).collect();
+    let name = segments.pop()?;
+    if !segments.contains(&"app") {
+        return None;
+    }
+    name.rsplit_once('.')
+}
+
+/// A configured entry point convention
+///
+/// Every non-empty criterion must match; each lists alternatives. A rule with
+/// no criteria matches nothing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntryPointRule {
+    /// Stored as the entry kind of matching functions
+    pub name: String,
+    /// Regexes matched against the function's decorators, or its class's
+    pub decorators: Vec<String>,
+    /// Names of a base class or interface of the function's class
+    pub base_classes: Vec<String>,
+    /// Globs matched against the project-relative file path
+    pub file_globs: Vec<String>,
+    /// Regexes matched against the function name (without its class)
+    pub function_names: Vec<String>,
+}
+
+struct CompiledRule {
+    name: String,
+    decorators: Vec<Regex>,
+    base_classes: Vec<String>,
+    file_globs: Option<GlobSet>,
+    function_names: Vec<Regex>,
+}
+
+/// Compiled `EntryPointRule`s, applied after extraction
+#[derive(Default)]
+pub struct EntryPointRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl EntryPointRules {
+    pub fn new(rules: &[EntryPointRule]) -> Result<Self, String> {
+        let regexes = |rule: &EntryPointRule, patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Regex::new(p).map_err(|e| format!("Entry point rule '{}': invalid pattern '{}': {}", rule.name, p, e)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let mut compiled = Vec::new();
+        for rule in rules {
+            let file_globs = if rule.file_globs.is_empty() {
+                None
+            } else {
+                let mut builder = GlobSetBuilder::new();
+                for glob in &rule.file_globs {
+                    builder.add(Glob::new(glob).map_err(|e| format!("Entry point rule '{}': invalid glob '{}': {}", rule.name, glob, e))?);
+                }
+                Some(builder.build().map_err(|e| format!("Entry point rule '{}': {}", rule.name, e))?)
+            };
+            compiled.push(CompiledRule {
+                name: rule.name.clone(),
+                decorators: regexes(rule, &rule.decorators)?,
+                base_classes: rule.base_classes.clone(),
+                file_globs,
+                function_names: regexes(rule, &rule.function_names)?,
+            });
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Mark functions matched by a rule as entry points of that rule's kind.
+    /// The first matching rule wins, and rules take precedence over built-in
+    /// conventions.
+    pub fn apply(&self, file: &str, result: &ParseResult, extraction: &mut ExtractionResult) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let file = file.strip_prefix("./").unwrap_or(file).replace('\\', "/");
+
+        let in_class = |f: &FunctionInfo| result.classes.iter().any(|c| c.methods.iter().any(|m| m.range.start == f.range.start));
+        let candidates = result.functions
+            .iter()
+            .filter(|f| !in_class(f))
+            .map(|f| (f.name.clone(), f, None))
+            .chain(result.classes.iter().flat_map(|c| {
+                c.methods.iter().map(move |m| (format!("{}.{}", c.name, m.name), m, Some(c)))
+            }));
+
+        for (name, func, class) in candidates {
+            let Some(rule) = self.rules.iter().find(|r| r.matches(&file, func, class)) else { continue };
+            for extracted in extraction.functions.iter_mut().filter(|f| f.name == name && f.start_line == func.range.start.line) {
+                extracted.entry_kind = Some(rule.name.clone());
+            }
+        }
+    }
+}
+
+impl CompiledRule {
+    fn matches(&self, file: &str, func: &FunctionInfo, class: Option<&ClassInfo>) -> bool {
+        if self.decorators.is_empty() && self.base_classes.is_empty() && self.file_globs.is_none() && self.function_names.is_empty() {
+            return false;
+        }
+        let decorators = || func.decorators.iter().chain(class.into_iter().flat_map(|c| &c.decorators));
+
+        self.file_globs.as_ref().is_none_or(|globs| globs.is_match(file))
+            && (self.function_names.is_empty() || self.function_names.iter().any(|re| re.is_match(&func.name)))
+            && (self.decorators.is_empty() || decorators().any(|d| self.decorators.iter().any(|re| re.is_match(d))))
+            && (self.base_classes.is_empty() || class.is_some_and(|c| {
+                c.extends.iter()
+                    .chain(&c.implements)
+                    .filter_map(|t| base_type_name(t))
+                    .any(|base| self.base_classes.contains(&base))
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{ExportInfo, Language, Range};
+
+    fn exports(names: &[(&str, bool)]) -> ParseResult {
+        let mut result = ParseResult::new(Language::TypeScript);
+        for (name, is_default) in names {
+            result.exports.push(ExportInfo {
+                name: name.to_string(),
+                original_name: None,
+                from_source: None,
+                is_type_only: false,
+                is_default: *is_default,
+                range: Range::new(0, 0, 0, 0),
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_page_and_layout_default_exports() {
+        let result = exports(&[("Page", true), ("metadata", false)]);
+        assert_eq!(convention_entry_points("src/app/users/[id]/page.tsx", &result), vec![("Page".to_string(), "nextjs-page")]);
+
+        let result = exports(&[("RootLayout", true)]);
+        assert_eq!(convention_entry_points("app/layout.tsx", &result), vec![("RootLayout".to_string(), "nextjs-layout")]);
+    }
+
+    #[test]
+    fn test_route_handler_exports() {
+        let result = exports(&[("GET", false), ("POST", false), ("helper", false)]);
+        let entries = convention_entry_points("app/api/users/route.ts", &result);
+        assert_eq!(entries, vec![("GET".to_string(), "nextjs-route"), ("POST".to_string(), "nextjs-route")]);
+    }
+
+    #[test]
+    fn test_files_outside_app_router_ignored() {
+        let result = exports(&[("Page", true), ("GET", false)]);
+        assert!(convention_entry_points("src/components/page.tsx", &result).is_empty());
+        assert!(convention_entry_points("pages/route.ts", &result).is_empty());
+        assert!(convention_entry_points("app/api/route.tsx", &result).is_empty());
+    }
+
+    #[test]
+    fn test_entry_point_rules() {
+        use crate::call_graph::{CallGraphExtractor, UniversalExtractor};
+        use crate::parsers::ParserManager;
+
+        let rules = EntryPointRules::new(&[
+            EntryPointRule {
+                name: "kafka-consumer".to_string(),
+                base_classes: vec!["KafkaConsumer".to_string()],
+                function_names: vec!["^consume$".to_string()],
+                ..Default::default()
+            },
+            EntryPointRule {
+                name: "cron".to_string(),
+                decorators: vec![r"^@?cron\.job".to_string()],
+                ..Default::default()
+            },
+            EntryPointRule {
+                name: "cli-command".to_string(),
+                file_globs: vec!["src/cli/**/*.py".to_string()],
+                function_names: vec!["^handle_".to_string()],
+                ..Default::default()
+            },
+        ]).unwrap();
+
+        let source = "class OrderConsumer(KafkaConsumer):\n    def consume(self, message):\n        pass\n\n    def ack(self):\n        pass\n\n@cron.job('0 * * * *')\ndef nightly():\n    pass\n\ndef handle_sync(args):\n    pass\n";
+        let kinds = |file: &str| {
+            let result = ParserManager::new().parse_file(file, source).unwrap();
+            let mut extraction = UniversalExtractor::new().extract(&result, file);
+            rules.apply(file, &result, &mut extraction);
+            let mut kinds: Vec<(String, String)> = extraction.functions
+                .into_iter()
+                .filter_map(|f| Some((f.name, f.entry_kind?)))
+                .collect();
+            kinds.sort();
+            kinds
+        };
+
+        let owned = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+        };
+        assert_eq!(kinds("src/jobs/orders.py"), owned(&[("OrderConsumer.consume", "kafka-consumer"), ("nightly", "cron")]));
+        assert_eq!(kinds("src/cli/commands/sync.py"), owned(&[
+            ("OrderConsumer.consume", "kafka-consumer"),
+            ("handle_sync", "cli-command"),
+            ("nightly", "cron"),
+        ]));
+
+        assert!(EntryPointRules::new(&[EntryPointRule {
+            name: "broken".to_string(),
+            function_names: vec!["(".to_string()],
+            ..Default::default()
+        }]).is_err());
+    }
+}
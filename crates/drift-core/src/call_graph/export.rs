@@ -0,0 +1,426 @@
+//! Call Graph Export
+//!
+//! Renders the SQLite call graph (or a subgraph of it) for visualization:
+//! - DOT, ready for `dot -Tsvg`
+//! - Mermaid flowcharts, for Markdown
+//! - JSON nodes and edges, for custom tooling
+//!
+//! Entry points and data accessors are highlighted; unresolved calls become
+//! dashed edges to placeholder nodes named after the call target.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+use super::types::FunctionEntry;
+
+const ENTRY_POINT_COLOR: &str = "#c6f6d5";
+const DATA_ACCESSOR_COLOR: &str = "#bee3f8";
+const BOTH_COLOR: &str = "#fefcbf";
+const UNRESOLVED_COLOR: &str = "#a0aec0";
+
+/// Output format for [`export_call_graph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Dot,
+    Json,
+    Mermaid,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Result<Self, String> {
+        match format.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(ExportFormat::Dot),
+            "json" => Ok(ExportFormat::Json),
+            "mermaid" => Ok(ExportFormat::Mermaid),
+            other => Err(format!("Unknown export format '{}' (expected dot, json or mermaid)", other)),
+        }
+    }
+}
+
+/// Which part of the call graph to export
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Only functions reachable from this function ID
+    pub root: Option<String>,
+    /// Maximum call depth from `root`, or from the entry points without one
+    pub max_depth: Option<usize>,
+    /// Only entry points and data accessors, with an edge wherever one reaches
+    /// another through calls in between
+    pub boundaries_only: bool,
+    /// Include unresolved calls as dashed edges
+    pub include_unresolved: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            root: None,
+            max_depth: None,
+            boundaries_only: false,
+            include_unresolved: true,
+        }
+    }
+}
+
+/// A function in the exported graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportNode {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub line: u32,
+    pub is_entry_point: bool,
+    pub is_data_accessor: bool,
+}
+
+/// A call in the exported graph; unresolved calls have no `to`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportEdge {
+    pub from: String,
+    pub to: Option<String>,
+    pub target: String,
+    pub line: u32,
+    pub confidence: f32,
+}
+
+/// The exported (sub)graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportGraph {
+    pub nodes: Vec<ExportNode>,
+    pub edges: Vec<ExportEdge>,
+}
+
+/// Render the call graph stored in `db` in `format`
+pub fn export_call_graph(db: &CallGraphDb, format: ExportFormat, options: &ExportOptions) -> Result<String, String> {
+    let graph = select_subgraph(db, options)?;
+    Ok(match format {
+        ExportFormat::Dot => to_dot(&graph),
+        ExportFormat::Json => serde_json::to_string_pretty(&graph).map_err(|e| e.to_string())?,
+        ExportFormat::Mermaid => to_mermaid(&graph),
+    })
+}
+
+/// Collect the nodes and edges `options` select
+pub fn select_subgraph(db: &CallGraphDb, options: &ExportOptions) -> Result<ExportGraph, String> {
+    let functions: HashMap<String, FunctionEntry> = match &options.root {
+        // Walk out from the root so only the subgraph is loaded
+        Some(root) => {
+            let mut functions = HashMap::new();
+            let mut queue = VecDeque::from([(root.clone(), 0usize)]);
+            while let Some((id, depth)) = queue.pop_front() {
+                if functions.contains_key(&id) {
+                    continue;
+                }
+                let Some(func) = db.get_function(&id).map_err(|e| e.to_string())? else {
+                    if id == *root {
+                        return Err(format!("Function not found: {}", root));
+                    }
+                    continue;
+                };
+                if options.max_depth.is_none_or(|max| depth < max) {
+                    for call in &func.calls {
+                        if let Some(target) = &call.resolved_id {
+                            queue.push_back((target.clone(), depth + 1));
+                        }
+                    }
+                }
+                functions.insert(id, func);
+            }
+            functions
+        }
+        None => {
+            let all = db.get_all_functions().map_err(|e| e.to_string())?;
+            let mut functions: HashMap<String, FunctionEntry> = all.into_iter().map(|f| (f.id.clone(), f)).collect();
+            if let Some(max_depth) = options.max_depth {
+                let roots: Vec<String> = functions.values().filter(|f| f.is_entry_point).map(|f| f.id.clone()).collect();
+                let keep = reachable(&functions, &roots, max_depth);
+                functions.retain(|id, _| keep.contains(id));
+            }
+            functions
+        }
+    };
+
+    let mut ids: Vec<&String> = functions.keys().collect();
+    ids.sort();
+
+    let is_boundary = |f: &FunctionEntry| f.is_entry_point || f.is_data_accessor;
+    let mut edges = Vec::new();
+    for id in &ids {
+        let func = &functions[*id];
+        if options.boundaries_only {
+            if !is_boundary(func) {
+                continue;
+            }
+            for (to, target, line) in boundary_edges(&functions, func) {
+                edges.push(ExportEdge { from: func.id.clone(), to: Some(to), target, line, confidence: 1.0 });
+            }
+            continue;
+        }
+        for call in &func.calls {
+            let to = call.resolved_id.clone().filter(|to| functions.contains_key(to));
+            if to.is_none() && (call.resolved_id.is_some() || !options.include_unresolved) {
+                // Resolved outside the selected subgraph, or unresolved and not wanted
+                continue;
+            }
+            edges.push(ExportEdge {
+                from: func.id.clone(),
+                to,
+                target: call.target.clone(),
+                line: call.line,
+                confidence: call.confidence,
+            });
+        }
+    }
+
+    let nodes = ids
+        .into_iter()
+        .map(|id| &functions[id])
+        .filter(|f| !options.boundaries_only || is_boundary(f))
+        .map(|f| ExportNode {
+            id: f.id.clone(),
+            name: f.name.clone(),
+            file: CallGraphDb::extract_file_from_id(&f.id).to_string(),
+            line: f.start_line,
+            is_entry_point: f.is_entry_point,
+            is_data_accessor: f.is_data_accessor,
+        })
+        .collect();
+
+    Ok(ExportGraph { nodes, edges })
+}
+
+/// IDs reachable from `roots` within `max_depth` resolved calls
+fn reachable(functions: &HashMap<String, FunctionEntry>, roots: &[String], max_depth: usize) -> HashSet<String> {
+    let mut seen: HashSet<String> = roots.iter().cloned().collect();
+    let mut queue: VecDeque<(&str, usize)> = roots.iter().map(|r| (r.as_str(), 0)).collect();
+    while let Some((id, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+        let Some(func) = functions.get(id) else { continue };
+        for target in func.calls.iter().filter_map(|c| c.resolved_id.as_deref()) {
+            if functions.contains_key(target) && seen.insert(target.to_string()) {
+                queue.push_back((target, depth + 1));
+            }
+        }
+    }
+    seen
+}
+
+/// Entry points and data accessors `from` reaches without passing through
+/// another one, with the first call on the path
+fn boundary_edges(functions: &HashMap<String, FunctionEntry>, from: &FunctionEntry) -> Vec<(String, String, u32)> {
+    let mut edges = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::from([from.id.as_str()]);
+    let mut queue: VecDeque<(&str, &str, u32)> = from.calls
+        .iter()
+        .filter_map(|c| c.resolved_id.as_deref().map(|to| (to, c.target.as_str(), c.line)))
+        .collect();
+    while let Some((id, target, line)) = queue.pop_front() {
+        if !seen.insert(id) {
+            continue;
+        }
+        let Some(func) = functions.get(id) else { continue };
+        if func.is_entry_point || func.is_data_accessor {
+            edges.push((id.to_string(), target.to_string(), line));
+            continue;
+        }
+        for next in func.calls.iter().filter_map(|c| c.resolved_id.as_deref()) {
+            queue.push_back((next, target, line));
+        }
+    }
+    edges.sort();
+    edges
+}
+
+fn node_color(node: &ExportNode) -> Option<&'static str> {
+    match (node.is_entry_point, node.is_data_accessor) {
+        (true, true) => Some(BOTH_COLOR),
+        (true, false) => Some(ENTRY_POINT_COLOR),
+        (false, true) => Some(DATA_ACCESSOR_COLOR),
+        (false, false) => None,
+    }
+}
+
+/// Render as a Graphviz digraph
+pub fn to_dot(graph: &ExportGraph) -> String {
+    let mut out = String::from("digraph call_graph {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fillcolor=\"#ffffff\", fontname=\"Helvetica\"];\n");
+    out.push_str("  edge [fontname=\"Helvetica\", fontsize=10];\n");
+
+    for node in &graph.nodes {
+        let label = format!("{}\n{}:{}", node.name, node.file, node.line);
+        match node_color(node) {
+            Some(color) => out.push_str(&format!("  {} [label={}, fillcolor=\"{}\"];\n", dot_quote(&node.id), dot_quote(&label), color)),
+            None => out.push_str(&format!("  {} [label={}];\n", dot_quote(&node.id), dot_quote(&label))),
+        }
+    }
+
+    let mut placeholders: HashSet<&str> = HashSet::new();
+    for edge in &graph.edges {
+        match &edge.to {
+            Some(to) => out.push_str(&format!("  {} -> {};\n", dot_quote(&edge.from), dot_quote(to))),
+            None => {
+                let placeholder = format!("unresolved:{}", edge.target);
+                if placeholders.insert(&edge.target) {
+                    out.push_str(&format!(
+                        "  {} [label={}, shape=plaintext, style=\"\", fontcolor=\"{}\"];\n",
+                        dot_quote(&placeholder), dot_quote(&edge.target), UNRESOLVED_COLOR
+                    ));
+                }
+                out.push_str(&format!(
+                    "  {} -> {} [label=\"unresolved\", style=dashed, color=\"{c}\", fontcolor=\"{c}\"];\n",
+                    dot_quote(&edge.from), dot_quote(&placeholder), c = UNRESOLVED_COLOR
+                ));
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render as a Mermaid flowchart
+pub fn to_mermaid(graph: &ExportGraph) -> String {
+    let mut out = String::from("flowchart LR\n");
+    let ids: HashMap<&str, String> = graph.nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), format!("n{}", i)))
+        .collect();
+
+    for node in &graph.nodes {
+        out.push_str(&format!("  {}[\"{}<br/>{}:{}\"]\n", ids[node.id.as_str()], mermaid_escape(&node.name), mermaid_escape(&node.file), node.line));
+    }
+
+    let mut placeholders: HashMap<&str, String> = HashMap::new();
+    for edge in &graph.edges {
+        let Some(from) = ids.get(edge.from.as_str()) else { continue };
+        match edge.to.as_deref().and_then(|to| ids.get(to)) {
+            Some(to) => out.push_str(&format!("  {} --> {}\n", from, to)),
+            None => {
+                let next = placeholders.len();
+                let placeholder = placeholders.entry(&edge.target).or_insert_with(|| {
+                    let id = format!("u{}", next);
+                    out.push_str(&format!("  {}[\"{}\"]:::unresolved\n", id, mermaid_escape(&edge.target)));
+                    id
+                });
+                out.push_str(&format!("  {} -.->|unresolved| {}\n", from, placeholder));
+            }
+        }
+    }
+
+    out.push_str(&format!("  classDef entry fill:{}\n", ENTRY_POINT_COLOR));
+    out.push_str(&format!("  classDef data fill:{}\n", DATA_ACCESSOR_COLOR));
+    out.push_str(&format!("  classDef both fill:{}\n", BOTH_COLOR));
+    out.push_str(&format!("  classDef unresolved color:{},stroke-dasharray:3\n", UNRESOLVED_COLOR));
+    for node in &graph.nodes {
+        let class = match (node.is_entry_point, node.is_data_accessor) {
+            (true, true) => "both",
+            (true, false) => "entry",
+            (false, true) => "data",
+            (false, false) => continue,
+        };
+        out.push_str(&format!("  class {} {}\n", ids[node.id.as_str()], class));
+    }
+    out
+}
+
+/// DOT double-quoted string
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Mermaid label text; quotes and markup characters become entities
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::{self, function, resolved_call, unresolved_call};
+    use tempfile::tempdir;
+
+    /// handler -> service -> repo.save (data accessor), handler -> log (unresolved)
+    fn sample_db(dir: &std::path::Path) -> CallGraphDb {
+        let mut handler = function("api.ts:handler:1", vec![
+            resolved_call("service.ts:service:1"),
+            unresolved_call("log"),
+        ]);
+        handler.is_entry_point = true;
+        let service = function("service.ts:service:1", vec![resolved_call("repo.ts:save:1")]);
+        let mut save = function("repo.ts:save:1", vec![]);
+        save.is_data_accessor = true;
+        fixtures::sample_db(dir, vec![handler, service, save])
+    }
+
+    #[test]
+    fn test_export_dot() {
+        let dir = tempdir().unwrap();
+        let db = sample_db(dir.path());
+
+        let dot = export_call_graph(&db, ExportFormat::Dot, &ExportOptions::default()).unwrap();
+
+        assert!(dot.starts_with("digraph call_graph {"));
+        assert!(dot.contains(&format!("\"api.ts:handler:1\" [label=\"handler\\napi.ts:1\", fillcolor=\"{}\"]", ENTRY_POINT_COLOR)));
+        assert!(dot.contains(&format!("fillcolor=\"{}\"", DATA_ACCESSOR_COLOR)));
+        assert!(dot.contains("\"api.ts:handler:1\" -> \"service.ts:service:1\";"));
+        assert!(dot.contains("\"api.ts:handler:1\" -> \"unresolved:log\" [label=\"unresolved\", style=dashed"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_export_subgraph_options() {
+        let dir = tempdir().unwrap();
+        let db = sample_db(dir.path());
+
+        let from_service = select_subgraph(&db, &ExportOptions {
+            root: Some("service.ts:service:1".to_string()),
+            ..Default::default()
+        }).unwrap();
+        let ids: Vec<&str> = from_service.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["repo.ts:save:1", "service.ts:service:1"]);
+
+        let shallow = select_subgraph(&db, &ExportOptions {
+            max_depth: Some(1),
+            include_unresolved: false,
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(shallow.nodes.len(), 2);
+        assert_eq!(shallow.edges.len(), 1);
+
+        let boundaries = select_subgraph(&db, &ExportOptions {
+            boundaries_only: true,
+            ..Default::default()
+        }).unwrap();
+        let ids: Vec<&str> = boundaries.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["api.ts:handler:1", "repo.ts:save:1"]);
+        assert_eq!(boundaries.edges.len(), 1);
+        assert_eq!(boundaries.edges[0].to.as_deref(), Some("repo.ts:save:1"));
+        assert_eq!(boundaries.edges[0].target, "service");
+    }
+
+    #[test]
+    fn test_export_mermaid_and_json() {
+        let dir = tempdir().unwrap();
+        let db = sample_db(dir.path());
+
+        let mermaid = export_call_graph(&db, ExportFormat::Mermaid, &ExportOptions::default()).unwrap();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains(" -.->|unresolved| u0"));
+        assert!(mermaid.contains("class n0 entry"));
+
+        let json = export_call_graph(&db, ExportFormat::Json, &ExportOptions::default()).unwrap();
+        let graph: ExportGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 3);
+
+        assert!(ExportFormat::parse("svg").is_err());
+    }
+}
@@ -3,7 +3,7 @@
 //! Defines the interface for extracting functions and calls from source code.
 
 use crate::parsers::{ParseResult, Language};
-use super::types::{FunctionEntry, CallEntry, DataAccessRef};
+use super::types::{FunctionEntry, CallEntry, CallKind, DataAccessRef};
 
 /// Extraction result from a single file
 #[derive(Debug, Clone)]
@@ -22,6 +22,11 @@ pub struct ExtractedFunction {
     pub end_line: u32,
     pub is_exported: bool,
     pub is_async: bool,
+    /// ID shared by every declaration of the same logical entity (C# partial
+    /// classes split across files); None uses the per-file "file:name:line" ID
+    pub logical_id: Option<String>,
+    /// Set when a framework convention or entry point rule makes this an entry point
+    pub entry_kind: Option<String>,
 }
 
 /// An extracted call site
@@ -30,6 +35,9 @@ pub struct ExtractedCall {
     pub callee_name: String,
     pub line: u32,
     pub receiver: Option<String>,
+    /// Receiver type inferred from field and parameter declarations
+    pub receiver_type: Option<String>,
+    pub kind: CallKind,
 }
 
 /// Trait for language-specific call graph extraction
@@ -53,7 +61,8 @@ pub fn to_function_entries(
     let mut entries = Vec::new();
     
     for func in &extraction.functions {
-        let fn_id = format!("{}:{}:{}", file, func.name, func.start_line);
+        let fn_id = func.logical_id.clone()
+            .unwrap_or_else(|| format!("{}:{}:{}", file, func.name, func.start_line));
         
         // Find calls within this function's range
         let fn_calls: Vec<CallEntry> = extraction.calls
@@ -65,6 +74,9 @@ pub fn to_function_entries(
                 resolved: false,
                 confidence: 0.0,
                 line: c.line,
+                receiver: c.receiver.clone(),
+                receiver_type: c.receiver_type.clone(),
+                kind: c.kind,
             })
             .collect();
         
@@ -80,7 +92,9 @@ pub fn to_function_entries(
             name: func.name.clone(),
             start_line: func.start_line,
             end_line: func.end_line,
-            is_entry_point: func.is_exported,
+            is_entry_point: func.is_exported || func.entry_kind.is_some(),
+            is_exported: func.is_exported,
+            entry_kind: func.entry_kind.clone(),
             is_data_accessor: !fn_data_access.is_empty(),
             calls: fn_calls,
             called_by: Vec::new(),
@@ -0,0 +1,124 @@
+//! Call graph fixtures shared by the unit tests
+
+use std::path::Path;
+
+use super::{CallEntry, CallGraphDb, CallKind, DataAccessRef, DataOperation, FunctionBatch, FunctionEntry};
+
+/// Split a `file:name:line` function id into its name and line
+fn parse_id(id: &str) -> (&str, u32) {
+    let mut parts = id.rsplitn(3, ':');
+    let line = parts.next().and_then(|l| l.parse().ok());
+    match (line, parts.next(), parts.next()) {
+        (Some(line), Some(name), Some(_)) => (name, line),
+        _ => panic!("function id '{}' is not file:name:line", id),
+    }
+}
+
+/// A plain function with the name and start line taken from its id
+pub(crate) fn function(id: &str, calls: Vec<CallEntry>) -> FunctionEntry {
+    let (name, start_line) = parse_id(id);
+    FunctionEntry {
+        id: id.to_string(),
+        name: name.to_string(),
+        start_line,
+        end_line: start_line + 9,
+        is_entry_point: false,
+        is_exported: false,
+        entry_kind: None,
+        is_data_accessor: false,
+        calls,
+        called_by: vec![],
+        data_access: vec![],
+    }
+}
+
+/// A plain function whose body ends at `end_line`
+pub(crate) fn spanning(id: &str, end_line: u32, calls: Vec<CallEntry>) -> FunctionEntry {
+    FunctionEntry {
+        end_line,
+        ..function(id, calls)
+    }
+}
+
+/// An exported function, which also makes it an entry point
+pub(crate) fn exported(id: &str, calls: Vec<CallEntry>) -> FunctionEntry {
+    FunctionEntry {
+        is_entry_point: true,
+        is_exported: true,
+        ..function(id, calls)
+    }
+}
+
+/// A data accessor making `data_access`
+pub(crate) fn accessor(id: &str, calls: Vec<CallEntry>, data_access: Vec<DataAccessRef>) -> FunctionEntry {
+    FunctionEntry {
+        is_data_accessor: true,
+        data_access,
+        ..function(id, calls)
+    }
+}
+
+/// An `operation` on `fields` of `table`
+pub(crate) fn access(table: &str, operation: DataOperation, fields: &[&str]) -> DataAccessRef {
+    DataAccessRef {
+        table: table.to_string(),
+        operation,
+        fields: fields.iter().map(|f| f.to_string()).collect(),
+        line: 5,
+    }
+}
+
+/// A direct call already resolved to the function `id`
+pub(crate) fn resolved_call(id: &str) -> CallEntry {
+    CallEntry {
+        target: parse_id(id).0.to_string(),
+        resolved_id: Some(id.to_string()),
+        resolved: true,
+        confidence: 0.95,
+        line: 1,
+        receiver: None,
+        receiver_type: None,
+        kind: CallKind::Direct,
+    }
+}
+
+/// A direct call on `line` resolved to the function `id`
+pub(crate) fn resolved_call_at(id: &str, line: u32) -> CallEntry {
+    CallEntry {
+        line,
+        ..resolved_call(id)
+    }
+}
+
+/// Direct calls resolved to each of `ids`
+pub(crate) fn resolved_calls(ids: &[&str]) -> Vec<CallEntry> {
+    ids.iter().map(|id| resolved_call(id)).collect()
+}
+
+/// A direct call to `target` that nothing resolved
+pub(crate) fn unresolved_call(target: &str) -> CallEntry {
+    CallEntry {
+        target: target.to_string(),
+        resolved_id: None,
+        resolved: false,
+        confidence: 0.0,
+        line: 1,
+        receiver: None,
+        receiver_type: None,
+        kind: CallKind::Direct,
+    }
+}
+
+/// A database under `dir` holding `functions` in a single batch
+pub(crate) fn sample_db(dir: &Path, functions: Vec<FunctionEntry>) -> CallGraphDb {
+    let mut db = CallGraphDb::open(&dir.join("test.db")).unwrap();
+    db.insert_batch(&FunctionBatch {
+        file: "all".to_string(),
+        functions,
+        imports: vec![],
+        types: vec![],
+        di_bindings: vec![],
+        async_handlers: vec![],
+    }).unwrap();
+    db
+}
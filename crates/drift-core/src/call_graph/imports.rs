@@ -0,0 +1,238 @@
+//! Import Resolution
+//!
+//! Maps a file's `ImportInfo`s onto the scanned files they refer to, so call
+//! resolution can prefer functions exported from modules a caller imports.
+//!
+//! - Relative JS/TS imports (`./db`, `../lib/db.js`) resolve by path, trying
+//!   the source as-is, without its extension, and as a directory index
+//! - Python relative imports (`from ..pkg import x`) resolve against the
+//!   importing file's package
+//! - Everything else (Python absolute modules, Java/C# namespaces, Go
+//!   packages, Rust `crate::` paths, path aliases) matches the import path
+//!   against the end of a module path or a directory
+
+use std::collections::HashMap;
+
+use crate::parsers::ImportInfo;
+
+/// File stems that stand for their directory (`pkg/index.ts` answers `./pkg`)
+const PACKAGE_STEMS: &[&str] = &["index", "__init__", "mod"];
+
+/// Scanned files indexed by module path (the path without its extension)
+pub struct ModuleIndex {
+    /// Module path -> file
+    modules: HashMap<String, String>,
+    /// Directory -> files directly inside it
+    dirs: HashMap<String, Vec<String>>,
+    /// Last path segment -> module paths and directories ending in it
+    by_segment: HashMap<String, Vec<String>>,
+}
+
+impl ModuleIndex {
+    pub fn new<'a>(files: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut modules: HashMap<String, String> = HashMap::new();
+        let mut dirs: HashMap<String, Vec<String>> = HashMap::new();
+
+        for file in files {
+            let module = strip_extension(file);
+            modules.entry(module.to_string()).or_insert_with(|| file.to_string());
+            if let Some((dir, stem)) = module.rsplit_once('/') {
+                if PACKAGE_STEMS.contains(&stem) {
+                    modules.entry(dir.to_string()).or_insert_with(|| file.to_string());
+                }
+            }
+            dirs.entry(parent_dir(file).to_string()).or_default().push(file.to_string());
+        }
+
+        let mut by_segment: HashMap<String, Vec<String>> = HashMap::new();
+        for path in modules.keys().chain(dirs.keys()) {
+            let segment = path.rsplit('/').next().unwrap_or(path);
+            by_segment.entry(segment.to_string()).or_default().push(path.clone());
+        }
+        // Package directories are both a module and a directory
+        for paths in by_segment.values_mut() {
+            paths.sort();
+            paths.dedup();
+        }
+
+        Self { modules, dirs, by_segment }
+    }
+
+    /// Files that `import` (made from `from_file`) refers to; empty for
+    /// external packages
+    pub fn resolve(&self, from_file: &str, import: &ImportInfo) -> Vec<String> {
+        let source = import.source.trim();
+
+        if import.relative_level > 0 {
+            // One dot is the file's own package, every extra dot goes up a level
+            let mut package = parent_dir(from_file).to_string();
+            for _ in 1..import.relative_level {
+                package = parent_dir(&package).to_string();
+            }
+            let base = join(&package, &source.replace('.', "/"));
+            let mut files: Vec<String> = self.lookup(&base).into_iter().collect();
+            // `from . import utils` imports sibling modules by name
+            for name in &import.named {
+                files.extend(self.lookup(&join(&base, name)));
+            }
+            return files;
+        }
+
+        if source.starts_with("./") || source.starts_with("../") {
+            let path = join(parent_dir(from_file), source);
+            return self.lookup(&path)
+                .or_else(|| self.lookup(strip_extension(&path)))
+                .into_iter()
+                .collect();
+        }
+
+        self.lookup_suffix(source)
+    }
+
+    fn lookup(&self, module: &str) -> Option<String> {
+        self.modules.get(module).cloned()
+    }
+
+    /// Match a non-relative import path against module paths, then directories.
+    /// Either may end with the other: source roots prefix file paths
+    /// (`src/main/java/com/acme/Repo.java`) and module names prefix import
+    /// paths (`github.com/acme/app/internal/store`). The last segment may name
+    /// an imported item rather than a module (`com.acme.Repo.save`,
+    /// `crate::db::save`), so it is retried without it.
+    fn lookup_suffix(&self, source: &str) -> Vec<String> {
+        let segments: Vec<&str> = source
+            .split(['.', '/', ':', '\\'])
+            .filter(|s| !s.is_empty() && !matches!(*s, "@" | "~" | "crate" | "self" | "super"))
+            .take_while(|s| s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-'))
+            .collect();
+
+        for len in (segments.len().saturating_sub(1).max(1)..=segments.len()).rev() {
+            let suffix = segments[..len].join("/");
+            let Some(candidates) = self.by_segment.get(segments[len - 1]) else { continue };
+            let matching: Vec<&String> = candidates
+                .iter()
+                .filter(|path| {
+                    **path == suffix
+                        || path.ends_with(&format!("/{}", suffix))
+                        || (path.contains('/') && suffix.ends_with(&format!("/{}", path)))
+                })
+                .collect();
+
+            let files: Vec<String> = matching
+                .iter()
+                .filter_map(|path| self.modules.get(*path).cloned())
+                .collect();
+            if !files.is_empty() {
+                return files;
+            }
+
+            // Go packages and namespaces map onto a directory of files
+            let files: Vec<String> = matching
+                .iter()
+                .filter_map(|path| self.dirs.get(*path))
+                .flatten()
+                .cloned()
+                .collect();
+            if !files.is_empty() {
+                return files;
+            }
+        }
+
+        Vec::new()
+    }
+}
+
+fn strip_extension(path: &str) -> &str {
+    match path.rfind('.') {
+        Some(dot) if dot > path.rfind('/').map_or(0, |slash| slash + 1) => &path[..dot],
+        _ => path,
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rfind('/').map_or("", |slash| &path[..slash])
+}
+
+/// Join `relative` onto `dir`, collapsing `.` and `..` segments; absolute
+/// directories stay absolute
+fn join(dir: &str, relative: &str) -> String {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in relative.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    let root = if dir.starts_with('/') { "/" } else { "" };
+    format!("{}{}", root, parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::Range;
+
+    fn import(source: &str, relative_level: usize, named: &[&str]) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            named: named.iter().map(|n| n.to_string()).collect(),
+            default: None,
+            namespace: None,
+            is_type_only: false,
+            relative_level,
+            range: Range::new(0, 0, 0, 0),
+        }
+    }
+
+    #[test]
+    fn test_resolve_relative_js_imports() {
+        let index = ModuleIndex::new([
+            "src/api/users.ts",
+            "src/db/index.ts",
+            "src/db/save.ts",
+            "src/lib/format.js",
+        ]);
+
+        assert_eq!(index.resolve("src/api/users.ts", &import("../db", 0, &[])), vec!["src/db/index.ts"]);
+        assert_eq!(index.resolve("src/api/users.ts", &import("../db/save", 0, &[])), vec!["src/db/save.ts"]);
+        assert_eq!(index.resolve("src/api/users.ts", &import("../lib/format.js", 0, &[])), vec!["src/lib/format.js"]);
+        assert!(index.resolve("src/api/users.ts", &import("react", 0, &[])).is_empty());
+
+        let index = ModuleIndex::new(["/repo/src/api/users.ts", "/repo/src/db/index.ts"]);
+        assert_eq!(index.resolve("/repo/src/api/users.ts", &import("../db", 0, &[])), vec!["/repo/src/db/index.ts"]);
+    }
+
+    #[test]
+    fn test_resolve_python_imports() {
+        let index = ModuleIndex::new([
+            "app/api/views.py",
+            "app/models/__init__.py",
+            "app/models/user.py",
+            "app/utils.py",
+        ]);
+
+        assert_eq!(index.resolve("app/api/views.py", &import("models.user", 2, &["User"])), vec!["app/models/user.py"]);
+        assert_eq!(index.resolve("app/api/views.py", &import("", 2, &["utils"])), vec!["app/utils.py"]);
+        assert_eq!(index.resolve("app/api/views.py", &import("app.models", 0, &["User"])), vec!["app/models/__init__.py"]);
+    }
+
+    #[test]
+    fn test_resolve_qualified_imports() {
+        let index = ModuleIndex::new([
+            "src/main/java/com/acme/Repo.java",
+            "src/db.rs",
+            "internal/store/users.go",
+            "internal/store/orders.go",
+        ]);
+
+        assert_eq!(index.resolve("x.java", &import("com.acme.Repo", 0, &[])), vec!["src/main/java/com/acme/Repo.java"]);
+        assert_eq!(index.resolve("src/main.rs", &import("crate::db::save", 0, &[])), vec!["src/db.rs"]);
+
+        let mut go = index.resolve("cmd/main.go", &import("github.com/acme/app/internal/store", 0, &[]));
+        go.sort();
+        assert_eq!(go, vec!["internal/store/orders.go", "internal/store/users.go"]);
+    }
+}
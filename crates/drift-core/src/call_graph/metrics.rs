@@ -0,0 +1,144 @@
+//! Call Graph Metrics
+//!
+//! Per-function hotspot metrics over resolved calls:
+//! - fan-in: distinct functions calling it
+//! - fan-out: distinct functions it calls
+//! - depth: fewest calls from any entry point (0 for entry points, none when
+//!   no entry point reaches it)
+//!
+//! `compute_graph_metrics` stores them in the `function_metrics` table, which
+//! is emptied whenever the graph changes.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+
+/// Top results per metric when no limit is given
+pub const DEFAULT_TOP_N: usize = 20;
+
+/// Metrics for one function
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionMetrics {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub fan_in: u32,
+    pub fan_out: u32,
+    pub depth: Option<u32>,
+}
+
+/// Highest-ranked functions by each metric
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphMetrics {
+    pub total_functions: usize,
+    /// Deepest depth of any function reachable from an entry point
+    pub max_depth: u32,
+    pub top_fan_in: Vec<FunctionMetrics>,
+    pub top_fan_out: Vec<FunctionMetrics>,
+    /// Deepest reachable functions
+    pub top_depth: Vec<FunctionMetrics>,
+}
+
+/// Compute metrics for every function, store them, and return the `top_n`
+/// functions by each metric (ties sorted by ID)
+pub fn compute_graph_metrics(db: &mut CallGraphDb, top_n: usize) -> Result<GraphMetrics, String> {
+    let functions = db.get_all_functions().map_err(|e| e.to_string())?;
+    let index: HashMap<&str, usize> = functions.iter().enumerate().map(|(i, f)| (f.id.as_str(), i)).collect();
+
+    let mut callees: Vec<HashSet<usize>> = vec![HashSet::new(); functions.len()];
+    let mut callers: Vec<HashSet<usize>> = vec![HashSet::new(); functions.len()];
+    for (from, func) in functions.iter().enumerate() {
+        for to in func.calls.iter().filter_map(|c| c.resolved_id.as_deref()).filter_map(|id| index.get(id)) {
+            callees[from].insert(*to);
+            callers[*to].insert(from);
+        }
+    }
+
+    let mut depth: Vec<Option<u32>> = vec![None; functions.len()];
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    for (i, func) in functions.iter().enumerate() {
+        if func.is_entry_point {
+            depth[i] = Some(0);
+            queue.push_back(i);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        let next = depth[node].map(|d| d + 1);
+        for &callee in &callees[node] {
+            if depth[callee].is_none() {
+                depth[callee] = next;
+                queue.push_back(callee);
+            }
+        }
+    }
+
+    let metrics: Vec<FunctionMetrics> = functions
+        .iter()
+        .enumerate()
+        .map(|(i, f)| FunctionMetrics {
+            id: f.id.clone(),
+            name: f.name.clone(),
+            file: CallGraphDb::extract_file_from_id(&f.id).to_string(),
+            fan_in: callers[i].len() as u32,
+            fan_out: callees[i].len() as u32,
+            depth: depth[i],
+        })
+        .collect();
+    db.store_function_metrics(&metrics).map_err(|e| e.to_string())?;
+
+    let top = |key: fn(&FunctionMetrics) -> u32| {
+        let mut ranked: Vec<&FunctionMetrics> = metrics.iter().filter(|m| key(m) > 0).collect();
+        ranked.sort_by(|a, b| key(b).cmp(&key(a)).then_with(|| a.id.cmp(&b.id)));
+        ranked.into_iter().take(top_n).cloned().collect()
+    };
+
+    Ok(GraphMetrics {
+        total_functions: metrics.len(),
+        max_depth: metrics.iter().filter_map(|m| m.depth).max().unwrap_or(0),
+        top_fan_in: top(|m| m.fan_in),
+        top_fan_out: top(|m| m.fan_out),
+        top_depth: top(|m| m.depth.unwrap_or(0)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::{exported, function, resolved_calls, sample_db};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_compute_graph_metrics() {
+        let dir = tempdir().unwrap();
+        let mut db = sample_db(dir.path(), vec![
+            exported("api.ts:list:1", resolved_calls(&["svc.ts:load:1", "util.ts:log:1", "util.ts:log:1"])),
+            exported("api.ts:create:20", resolved_calls(&["svc.ts:save:10", "util.ts:log:1"])),
+            function("svc.ts:load:1", resolved_calls(&["db.ts:query:1"])),
+            function("svc.ts:save:10", resolved_calls(&["db.ts:query:1", "util.ts:log:1"])),
+            function("db.ts:query:1", vec![]),
+            function("util.ts:log:1", vec![]),
+            function("old.ts:unused:1", resolved_calls(&["util.ts:log:1"])),
+        ]);
+
+        let metrics = compute_graph_metrics(&mut db, 2).unwrap();
+        assert_eq!(metrics.total_functions, 7);
+        assert_eq!(metrics.max_depth, 2);
+
+        let ranked = |list: &[FunctionMetrics]| list.iter().map(|m| (m.name.clone(), m.fan_in, m.fan_out)).collect::<Vec<_>>();
+        // Repeated calls count once
+        assert_eq!(ranked(&metrics.top_fan_in), vec![("log".to_string(), 4, 0), ("query".to_string(), 2, 0)]);
+        assert_eq!(ranked(&metrics.top_fan_out), vec![("create".to_string(), 0, 2), ("list".to_string(), 0, 2)]);
+        assert_eq!(metrics.top_depth[0].id, "db.ts:query:1");
+        assert_eq!(metrics.top_depth[0].depth, Some(2));
+
+        let stored = db.get_function_metrics("old.ts:unused:1").unwrap().unwrap();
+        assert_eq!((stored.fan_in, stored.fan_out, stored.depth), (0, 1, None));
+        assert_eq!(db.get_function_metrics("util.ts:log:1").unwrap().unwrap().depth, Some(1));
+
+        // Changing the graph drops stale metrics
+        db.delete_files(&["old.ts".to_string()]).unwrap();
+        assert!(db.get_function_metrics("util.ts:log:1").unwrap().is_none());
+    }
+}
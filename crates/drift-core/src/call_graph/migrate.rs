@@ -0,0 +1,242 @@
+//! Legacy Shard Migration
+//!
+//! Moves a call graph built in legacy JSON mode (`StreamingBuilder::build`,
+//! one shard per file under `.drift/lake/callgraph/files`) into
+//! `callgraph.db`, so the SQLite-only queries work without a rebuild.
+//!
+//! Calls keep the resolution the legacy build gave them, so reachability over
+//! the migrated database matches the in-memory engine over the same shards.
+//! Shards carry no file imports or types; run a full `build_sqlite` to get
+//! import- and receiver-aware resolution.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage::{CallGraphDb, FunctionBatch};
+use super::types::CallGraphShard;
+
+/// A shard that could not be migrated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardError {
+    pub path: String,
+    pub error: String,
+}
+
+/// Outcome of `migrate_call_graph_to_sqlite`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationResult {
+    pub shards_migrated: usize,
+    pub functions_migrated: usize,
+    pub edges_migrated: usize,
+    pub resolved_edges: usize,
+    /// Shards skipped because they failed to parse or validate
+    pub failed_shards: Vec<ShardError>,
+}
+
+/// Replace `callgraph.db` under `root_dir` with the legacy JSON shards' contents
+///
+/// Fails without touching the database when there are no shards to migrate.
+pub fn migrate_call_graph_to_sqlite(root_dir: &Path) -> Result<MigrationResult, String> {
+    let callgraph_dir = root_dir.join(".drift").join("lake").join("callgraph");
+    let shards_dir = callgraph_dir.join("files");
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&shards_dir)
+        .map_err(|e| format!("No legacy call graph shards in {}: {}", shards_dir.display(), e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        return Err(format!("No legacy call graph shards in {}", shards_dir.display()));
+    }
+
+    let mut result = MigrationResult::default();
+    let mut batches = Vec::new();
+    for path in paths {
+        match load_shard(&path) {
+            Ok(shard) => {
+                result.functions_migrated += shard.functions.len();
+                for call in shard.functions.iter().flat_map(|f| &f.calls) {
+                    result.edges_migrated += 1;
+                    result.resolved_edges += call.resolved_id.is_some() as usize;
+                }
+                batches.push(FunctionBatch {
+                    file: shard.file,
+                    functions: shard.functions,
+                    imports: Vec::new(),
+                    types: Vec::new(),
+                    di_bindings: Vec::new(),
+                    async_handlers: Vec::new(),
+                });
+            }
+            Err(error) => result.failed_shards.push(ShardError {
+                path: path.to_string_lossy().to_string(),
+                error,
+            }),
+        }
+    }
+    result.shards_migrated = batches.len();
+
+    let write = || {
+        let mut db = CallGraphDb::open(&callgraph_dir.join("callgraph.db"))?;
+        db.clear()?;
+        db.insert_batches(&batches)
+    };
+    write().map_err(|e| format!("SQLite error: {}", e))?;
+
+    Ok(result)
+}
+
+/// Parse a shard and check its functions belong to its file
+fn load_shard(path: &Path) -> Result<CallGraphShard, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read shard: {}", e))?;
+    let shard: CallGraphShard = serde_json::from_str(&content).map_err(|e| format!("Invalid shard: {}", e))?;
+
+    if shard.file.is_empty() {
+        return Err("Shard has no file".to_string());
+    }
+    if let Some(func) = shard.functions.iter().find(|f| CallGraphDb::extract_file_from_id(&f.id) != shard.file) {
+        return Err(format!("Function {} does not belong to {}", func.id, shard.file));
+    }
+    if let Some(func) = shard.functions.iter().find(|f| f.name.is_empty()) {
+        return Err(format!("Function {} has no name", func.id));
+    }
+
+    Ok(shard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::{BuilderConfig, DataOperation as CgDataOperation, StreamingBuilder};
+    use crate::reachability::{
+        CallGraph, CallSite, DataAccessPoint, DataOperation, FunctionNode, ReachabilityEngine,
+        ReachabilityOptions, ReachabilityResult, SqliteReachabilityEngine,
+    };
+    use tempfile::tempdir;
+
+    /// The in-memory graph the legacy JSON mode fed to `ReachabilityEngine`
+    fn in_memory_graph(shards_dir: &Path) -> CallGraph {
+        let mut graph = CallGraph::default();
+        for entry in fs::read_dir(shards_dir).unwrap() {
+            let shard = load_shard(&entry.unwrap().path()).unwrap();
+            for f in shard.functions {
+                if f.is_entry_point {
+                    graph.entry_points.push(f.id.clone());
+                }
+                graph.functions.insert(f.id.clone(), FunctionNode {
+                    id: f.id,
+                    qualified_name: f.name.clone(),
+                    name: f.name,
+                    file: shard.file.clone(),
+                    start_line: f.start_line,
+                    end_line: f.end_line,
+                    calls: f.calls.into_iter().map(|c| CallSite {
+                        callee_name: c.target,
+                        resolved: c.resolved_id.is_some(),
+                        resolved_candidates: c.resolved_id.into_iter().collect(),
+                        line: c.line,
+                        confidence: c.confidence,
+                    }).collect(),
+                    data_access: f.data_access.into_iter().map(|a| DataAccessPoint {
+                        table: a.table,
+                        operation: match a.operation {
+                            CgDataOperation::Read => DataOperation::Read,
+                            CgDataOperation::Write => DataOperation::Write,
+                            CgDataOperation::Delete => DataOperation::Delete,
+                        },
+                        fields: a.fields,
+                        file: shard.file.clone(),
+                        line: a.line,
+                        confidence: 1.0,
+                        framework: None,
+                    }).collect(),
+                    is_entry_point: f.is_entry_point,
+                });
+            }
+        }
+        graph
+    }
+
+    /// Tables, (table, path) pairs and sensitive fields
+    type Summary = (Vec<String>, Vec<(String, Vec<String>)>, Vec<String>);
+
+    /// What a reachability result says, independent of traversal order
+    fn summary(result: &ReachabilityResult) -> Summary {
+        let mut tables = result.tables.clone();
+        tables.sort();
+        let mut paths: Vec<(String, Vec<String>)> = result.reachable_access
+            .iter()
+            .map(|r| (r.access.table.clone(), r.path.iter().map(|n| n.function_id.clone()).collect()))
+            .collect();
+        paths.sort();
+        let mut sensitive: Vec<String> = result.sensitive_fields.iter().map(|s| s.field.field.clone()).collect();
+        sensitive.sort();
+        (tables, paths, sensitive)
+    }
+
+    #[test]
+    fn test_migrated_reachability_matches_in_memory() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("api.ts"), "export function listUsers() {\n  return findUsers();\n}\n\nexport function health() {\n  return 'ok';\n}\n").unwrap();
+        fs::write(src.join("users.ts"), "export function findUsers() {\n  return db.query('SELECT id, email, password FROM users');\n}\n").unwrap();
+
+        let mut builder = StreamingBuilder::new(BuilderConfig {
+            root_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let legacy = builder.build(&["**/*.ts"]);
+        assert!(legacy.errors.is_empty(), "{:?}", legacy.errors);
+
+        let shards_dir = dir.path().join(".drift/lake/callgraph/files");
+        fs::write(shards_dir.join("broken.json"), "{ not json").unwrap();
+
+        let result = migrate_call_graph_to_sqlite(dir.path()).unwrap();
+        assert_eq!(result.shards_migrated, 2);
+        assert_eq!(result.functions_migrated, legacy.total_functions);
+        assert_eq!(result.edges_migrated, legacy.total_calls);
+        assert_eq!(result.resolved_edges, legacy.resolved_calls);
+        assert_eq!(result.failed_shards.len(), 1);
+        assert!(result.failed_shards[0].path.ends_with("broken.json"));
+        fs::remove_file(shards_dir.join("broken.json")).unwrap();
+
+        let sqlite = SqliteReachabilityEngine::from_project_root(dir.path()).unwrap();
+        assert!(sqlite.is_available());
+        let graph = in_memory_graph(&shards_dir);
+        let ids: Vec<String> = graph.functions.keys().cloned().collect();
+        let memory = ReachabilityEngine::new(graph);
+
+        let options = ReachabilityOptions::default();
+        let mut reached_users = false;
+        for id in ids {
+            let expected = summary(&memory.get_reachable_data_from_function(&id, &options));
+            let actual = summary(&sqlite.get_reachable_data_from_function(&id, &options));
+            assert_eq!(actual, expected, "reachability from {}", id);
+            reached_users |= id.contains("listUsers") && expected.0.contains(&"users".to_string());
+        }
+        assert!(reached_users);
+    }
+
+    #[test]
+    fn test_invalid_shards_are_reported() {
+        let dir = tempdir().unwrap();
+        assert!(migrate_call_graph_to_sqlite(dir.path()).is_err());
+
+        let shards_dir = dir.path().join(".drift/lake/callgraph/files");
+        fs::create_dir_all(&shards_dir).unwrap();
+        fs::write(
+            shards_dir.join("a.json"),
+            r#"{"file": "src/a.ts", "functions": [{"id": "src/b.ts:b:1", "name": "b", "start_line": 1, "end_line": 2,
+                "is_entry_point": false, "is_data_accessor": false, "calls": [], "called_by": [], "data_access": []}]}"#,
+        ).unwrap();
+
+        let result = migrate_call_graph_to_sqlite(dir.path()).unwrap();
+        assert_eq!(result.shards_migrated, 0);
+        assert_eq!(result.failed_shards[0].error, "Function src/b.ts:b:1 does not belong to src/a.ts");
+    }
+}
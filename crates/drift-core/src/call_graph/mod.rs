@@ -8,16 +8,55 @@
 //! - `UniversalExtractor` - Extracts functions/calls from any language
 //! - `CallGraphDb` - SQLite storage for O(1) queries
 //! - `ParallelWriter` - MPSC channel pattern for parallel builds
+//! - `ModuleIndex` - Resolves imports to project files for call resolution
+//! - `export_call_graph` - DOT / Mermaid / JSON export for visualization
+//! - `find_unreachable_functions` - Dead code detection from entry points
+//! - `Condensation` - Strongly connected components (recursion cycles)
+//! - `find_functions` - Function lookup by name
+//! - `diff_call_graphs` - Changes between two builds
+//! - `migrate_call_graph_to_sqlite` - Legacy JSON shards to SQLite
+//! - `compute_graph_metrics` - Fan-in, fan-out and depth per function
+//! - `find_unresolved_calls` - Calls without a target, with rejected candidates
+//! - `detect_di_bindings` - DI container bindings for interface-typed receivers
+//! - `AsyncEdgeRules` - Async edges from job enqueue sites to their handlers
 //! - Types for shards, entries, and indexes
 
 mod types;
 mod extractor;
 mod universal_extractor;
+mod entry_points;
 mod builder;
 mod storage;
+mod imports;
+mod export;
+mod dead_code;
+mod cycles;
+mod search;
+mod diff;
+mod migrate;
+mod metrics;
+mod unresolved;
+mod di;
+mod async_edges;
+#[cfg(test)]
+pub(crate) mod fixtures;
 
 pub use types::*;
 pub use extractor::{CallGraphExtractor, ExtractionResult, ExtractedFunction, ExtractedCall, to_function_entries};
 pub use universal_extractor::UniversalExtractor;
 pub use builder::{StreamingBuilder, BuilderConfig};
-pub use storage::{CallGraphDb, ParallelWriter, FunctionBatch, DbStats};
+pub use entry_points::{EntryPointRule, EntryPointRules};
+pub use storage::{CallGraphDb, ParallelWriter, FunctionBatch, TypeEntry, DbStats, PruneStats};
+pub(crate) use storage::{CALL_KIND, CONTENT_VERSION_KEY};
+pub use imports::ModuleIndex;
+pub use export::{export_call_graph, select_subgraph, ExportFormat, ExportOptions, ExportGraph, ExportNode, ExportEdge};
+pub use dead_code::{find_unreachable_functions, DeadCodeOptions, UnreachableFunction};
+pub use cycles::{find_call_cycles, CallCycle, CycleMember, Condensation};
+pub use search::{find_functions, FunctionMatch, FunctionSearchOptions, MatchKind};
+pub use diff::{diff_call_graphs, CallGraphDiff, DiffAccessor, DiffEdge, DiffFunction};
+pub use migrate::{migrate_call_graph_to_sqlite, MigrationResult, ShardError};
+pub use metrics::{compute_graph_metrics, FunctionMetrics, GraphMetrics, DEFAULT_TOP_N};
+pub use unresolved::{find_unresolved_calls, UnresolvedCall, UnresolvedCallOptions, UnresolvedReason};
+pub(crate) use unresolved::method_name;
+pub use di::{detect_di_bindings, DiBinding};
+pub use async_edges::{AsyncEdgeRule, AsyncEdgeRules, AsyncHandler};
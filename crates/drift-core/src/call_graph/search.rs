@@ -0,0 +1,163 @@
+//! Function Search
+//!
+//! Looks functions up by name - e.g. a frame from a stack trace - to find the
+//! IDs other call graph queries take. Names are matched both as stored
+//! (`UserService.getUser`) and without their class (`getUser`), ranking exact
+//! matches first, then prefixes, then substrings.
+
+use globset::Glob;
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+
+/// Results returned when no limit is given
+const DEFAULT_LIMIT: usize = 50;
+
+/// Filters for `find_functions`
+#[derive(Debug, Clone, Default)]
+pub struct FunctionSearchOptions {
+    /// Maximum results (default: 50)
+    pub limit: Option<usize>,
+    /// Only functions in files matching this glob (`src/**/*.ts`)
+    pub file_glob: Option<String>,
+    /// Only exported functions
+    pub exported_only: bool,
+}
+
+/// How a function's name matched the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Substring,
+}
+
+impl MatchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchKind::Exact => "exact",
+            MatchKind::Prefix => "prefix",
+            MatchKind::Substring => "substring",
+        }
+    }
+}
+
+/// A function found by `find_functions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionMatch {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub is_entry_point: bool,
+    pub is_exported: bool,
+    pub match_kind: MatchKind,
+    /// Calls this function makes
+    pub outgoing_calls: usize,
+    /// Resolved calls to this function
+    pub incoming_calls: usize,
+}
+
+/// Functions whose name matches `query`, best matches first
+///
+/// Exact matches are case-sensitive; prefix and substring matches ignore
+/// ASCII case. Ties sort by name, then ID.
+pub fn find_functions(db: &CallGraphDb, query: &str, options: &FunctionSearchOptions) -> Result<Vec<FunctionMatch>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Empty search query".to_string());
+    }
+    let file_glob = options.file_glob
+        .as_deref()
+        .map(|g| Glob::new(g).map(|g| g.compile_matcher()).map_err(|e| format!("Invalid file glob '{}': {}", g, e)))
+        .transpose()?;
+
+    let candidates = db.get_functions_containing(query).map_err(|e| e.to_string())?;
+    let mut matches: Vec<(MatchKind, _)> = candidates
+        .into_iter()
+        .filter(|f| !options.exported_only || f.is_exported)
+        .filter(|f| file_glob.as_ref().is_none_or(|g| g.is_match(CallGraphDb::extract_file_from_id(&f.id))))
+        .map(|f| (match_kind(&f.name, query), f))
+        .collect();
+    matches.sort_by(|(a_kind, a), (b_kind, b)| (a_kind, &a.name, &a.id).cmp(&(b_kind, &b.name, &b.id)));
+    matches.truncate(options.limit.unwrap_or(DEFAULT_LIMIT));
+
+    matches
+        .into_iter()
+        .map(|(match_kind, f)| {
+            let (outgoing_calls, incoming_calls) = db.get_call_counts(&f.id).map_err(|e| e.to_string())?;
+            Ok(FunctionMatch {
+                file: CallGraphDb::extract_file_from_id(&f.id).to_string(),
+                id: f.id,
+                name: f.name,
+                start_line: f.start_line,
+                end_line: f.end_line,
+                is_entry_point: f.is_entry_point,
+                is_exported: f.is_exported,
+                match_kind,
+                outgoing_calls,
+                incoming_calls,
+            })
+        })
+        .collect()
+}
+
+fn match_kind(name: &str, query: &str) -> MatchKind {
+    let bare = name.rsplit('.').next().unwrap_or(name);
+    if name == query || bare == query {
+        return MatchKind::Exact;
+    }
+    let query = query.to_ascii_lowercase();
+    if name.to_ascii_lowercase().starts_with(&query) || bare.to_ascii_lowercase().starts_with(&query) {
+        MatchKind::Prefix
+    } else {
+        MatchKind::Substring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::{exported, function, resolved_call, sample_db};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_functions_ranking_and_filters() {
+        let dir = tempdir().unwrap();
+        let db = sample_db(dir.path(), vec![
+            exported("src/api.ts:handler:1", vec![resolved_call("src/users.ts:UserService.getUser:5")]),
+            exported("src/users.ts:UserService.getUser:5", vec![]),
+            function("src/users.ts:getUserById:20", vec![]),
+            exported("lib/cache.py:cached_get_user_100:1", vec![]),
+            exported("src/users.ts:forgetUsername:30", vec![]),
+        ]);
+
+        let found = find_functions(&db, "getUser", &FunctionSearchOptions::default()).unwrap();
+        let ranked: Vec<(&str, MatchKind)> = found.iter().map(|f| (f.name.as_str(), f.match_kind)).collect();
+        assert_eq!(ranked, vec![
+            ("UserService.getUser", MatchKind::Exact),
+            ("getUserById", MatchKind::Prefix),
+            ("forgetUsername", MatchKind::Substring),
+        ]);
+        assert_eq!(found[0].file, "src/users.ts");
+        assert_eq!((found[0].outgoing_calls, found[0].incoming_calls), (0, 1));
+
+        let exported = find_functions(&db, "getuser", &FunctionSearchOptions {
+            exported_only: true,
+            file_glob: Some("src/**".to_string()),
+            limit: Some(1),
+        }).unwrap();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].name, "UserService.getUser");
+        assert_eq!(exported[0].match_kind, MatchKind::Prefix);
+
+        // LIKE wildcards in the query match literally
+        let underscored = find_functions(&db, "_get_", &FunctionSearchOptions::default()).unwrap();
+        assert_eq!(underscored.len(), 1);
+        assert_eq!(underscored[0].name, "cached_get_user_100");
+
+        assert!(find_functions(&db, "  ", &FunctionSearchOptions::default()).is_err());
+    }
+}
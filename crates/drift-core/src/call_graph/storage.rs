@@ -5,17 +5,24 @@
 //!
 //! Key features:
 //! - WAL mode for concurrent reads during writes
-//! - Batched inserts (1000 rows per transaction)
+//! - Batched inserts (multi-row statements, one transaction per write batch)
 //! - Indexed queries for fast caller/callee lookups
 //! - Thread-safe via connection pooling
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use rusqlite::{params, Connection, Result as SqliteResult, Transaction};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Result as SqliteResult, ToSql, Transaction, TransactionBehavior};
 
-use super::types::{FunctionEntry, CallEntry, DataAccessRef, DataOperation};
+use super::async_edges::AsyncHandler;
+use super::di::DiBinding;
+use super::metrics::FunctionMetrics;
+use super::unresolved::method_name;
+use super::types::{FunctionEntry, CallEntry, CallKind, DataAccessRef, DataOperation};
 
 // ============================================================================
 // Schema
@@ -30,7 +37,11 @@ CREATE TABLE IF NOT EXISTS functions (
     start_line INTEGER NOT NULL,
     end_line INTEGER NOT NULL,
     is_entry_point INTEGER DEFAULT 0,
-    is_data_accessor INTEGER DEFAULT 0
+    is_data_accessor INTEGER DEFAULT 0,
+    entry_kind TEXT,
+    is_exported INTEGER,
+    -- Last segment of a qualified name ("save" for "User.save"); NULL when unqualified
+    method_name TEXT
 );
 
 CREATE TABLE IF NOT EXISTS calls (
@@ -40,6 +51,10 @@ CREATE TABLE IF NOT EXISTS calls (
     resolved_id TEXT,
     confidence REAL DEFAULT 0.0,
     line INTEGER NOT NULL,
+    strategy TEXT,
+    receiver TEXT,
+    receiver_type TEXT,
+    kind TEXT,
     FOREIGN KEY (caller_id) REFERENCES functions(id)
 );
 
@@ -53,6 +68,57 @@ CREATE TABLE IF NOT EXISTS data_access (
     FOREIGN KEY (function_id) REFERENCES functions(id)
 );
 
+-- Each field a data access names, so field queries don't scan every access;
+-- kept in step with data_access by the triggers below
+CREATE TABLE IF NOT EXISTS data_access_fields (
+    access_id INTEGER NOT NULL,
+    field TEXT NOT NULL
+);
+
+CREATE TRIGGER IF NOT EXISTS data_access_fields_insert AFTER INSERT ON data_access
+WHEN json_valid(NEW.fields)
+BEGIN
+    INSERT INTO data_access_fields (access_id, field)
+    SELECT NEW.id, value FROM json_each(NEW.fields);
+END;
+
+CREATE TRIGGER IF NOT EXISTS data_access_fields_delete AFTER DELETE ON data_access
+BEGIN
+    DELETE FROM data_access_fields WHERE access_id = OLD.id;
+END;
+
+-- Project files each file imports, for import-aware resolution
+CREATE TABLE IF NOT EXISTS file_imports (
+    file TEXT NOT NULL,
+    imported_file TEXT NOT NULL,
+    PRIMARY KEY (file, imported_file)
+);
+
+-- Declared types and their direct supertypes (NULL when there are none),
+-- for receiver-type resolution
+CREATE TABLE IF NOT EXISTS types (
+    file TEXT NOT NULL,
+    name TEXT NOT NULL,
+    supertype TEXT,
+    is_abstract INTEGER DEFAULT 0
+);
+
+-- Interface -> implementation bindings registered with a DI container,
+-- for resolving calls on interface-typed receivers
+CREATE TABLE IF NOT EXISTS di_bindings (
+    file TEXT NOT NULL,
+    interface TEXT NOT NULL,
+    implementation TEXT NOT NULL
+);
+
+-- Functions registered to run a background job, for resolving async calls
+-- from enqueue sites
+CREATE TABLE IF NOT EXISTS async_handlers (
+    file TEXT NOT NULL,
+    job TEXT NOT NULL,
+    handler TEXT NOT NULL
+);
+
 -- Indexes for fast queries
 CREATE INDEX IF NOT EXISTS idx_functions_name ON functions(name);
 CREATE INDEX IF NOT EXISTS idx_functions_file ON functions(file);
@@ -61,14 +127,39 @@ CREATE INDEX IF NOT EXISTS idx_calls_resolved ON calls(resolved_id);
 CREATE INDEX IF NOT EXISTS idx_calls_caller ON calls(caller_id);
 CREATE INDEX IF NOT EXISTS idx_data_access_function ON data_access(function_id);
 CREATE INDEX IF NOT EXISTS idx_data_access_table ON data_access(table_name);
+CREATE INDEX IF NOT EXISTS idx_data_access_fields_field ON data_access_fields(field);
+CREATE INDEX IF NOT EXISTS idx_data_access_fields_access ON data_access_fields(access_id);
+CREATE INDEX IF NOT EXISTS idx_types_file ON types(file);
+CREATE INDEX IF NOT EXISTS idx_di_bindings_file ON di_bindings(file);
+CREATE INDEX IF NOT EXISTS idx_async_handlers_job ON async_handlers(job);
+CREATE INDEX IF NOT EXISTS idx_async_handlers_file ON async_handlers(file);
 
 -- Metadata table
+-- Per-function metrics from `compute_graph_metrics`; emptied whenever the
+-- graph changes
+CREATE TABLE IF NOT EXISTS function_metrics (
+    function_id TEXT PRIMARY KEY,
+    fan_in INTEGER NOT NULL,
+    fan_out INTEGER NOT NULL,
+    depth INTEGER
+);
+
 CREATE TABLE IF NOT EXISTS metadata (
     key TEXT PRIMARY KEY,
     value TEXT
 );
 "#;
 
+/// A call's kind, for calls stored before kinds were recorded too: those
+/// count as method calls when they have a receiver
+pub(crate) const CALL_KIND: &str = "COALESCE(kind, CASE WHEN receiver IS NULL THEN 'direct' ELSE 'method' END)";
+
+/// How long a write waits for a lock held by another connection
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Metadata key of a token that changes with every write to the graph
+pub(crate) const CONTENT_VERSION_KEY: &str = "content_version";
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -78,6 +169,24 @@ CREATE TABLE IF NOT EXISTS metadata (
 pub struct FunctionBatch {
     pub file: String,
     pub functions: Vec<FunctionEntry>,
+    /// Project files this file imports
+    pub imports: Vec<String>,
+    /// Classes and interfaces declared in this file
+    pub types: Vec<TypeEntry>,
+    /// DI container bindings registered or declared in this file
+    pub di_bindings: Vec<DiBinding>,
+    /// Async job handlers registered in this file
+    pub async_handlers: Vec<AsyncHandler>,
+}
+
+/// A class or interface declaration, for receiver-type resolution
+#[derive(Debug, Clone)]
+pub struct TypeEntry {
+    pub name: String,
+    /// Extended and implemented types
+    pub supertypes: Vec<String>,
+    /// Interface or abstract class - calls on it prefer implementations
+    pub is_abstract: bool,
 }
 
 /// Statistics from the database
@@ -90,6 +199,22 @@ pub struct DbStats {
     pub data_accessors: usize,
 }
 
+/// Rows removed by `CallGraphDb::prune`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    pub functions: usize,
+    pub calls: usize,
+    pub data_access: usize,
+    pub file_imports: usize,
+    pub types: usize,
+    pub di_bindings: usize,
+    pub async_handlers: usize,
+    /// Calls from remaining files whose target was removed, now unresolved
+    pub calls_unresolved: usize,
+    /// Why the `VACUUM` after pruning failed; the rows are removed regardless
+    pub vacuum_error: Option<String>,
+}
+
 // ============================================================================
 // CallGraphDb - Main Database Interface
 // ============================================================================
@@ -109,6 +234,8 @@ impl CallGraphDb {
         }
         
         let conn = Connection::open(path)?;
+        // Wait for concurrent readers and writers instead of failing with SQLITE_BUSY
+        conn.busy_timeout(BUSY_TIMEOUT)?;
         
         // Configure for performance
         conn.execute_batch(
@@ -120,8 +247,53 @@ impl CallGraphDb {
         )?;
         
         // Create schema
+        let had_field_index = conn.prepare("SELECT 1 FROM data_access_fields LIMIT 0").is_ok();
         conn.execute_batch(SCHEMA)?;
         
+        // Databases built before fields were indexed have access the
+        // triggers never saw
+        if !had_field_index {
+            conn.execute_batch(
+                "INSERT INTO data_access_fields (access_id, field)
+                 SELECT data_access.id, json_each.value FROM data_access, json_each(data_access.fields)
+                 WHERE json_valid(data_access.fields)"
+            )?;
+        }
+        
+        // Databases built before entry kinds were recorded lack the column
+        if conn.prepare("SELECT entry_kind FROM functions LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE functions ADD COLUMN entry_kind TEXT")?;
+        }
+        // NULL is_exported marks rows from before exports were recorded
+        if conn.prepare("SELECT is_exported FROM functions LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE functions ADD COLUMN is_exported INTEGER")?;
+        }
+        if conn.prepare("SELECT method_name FROM functions LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE functions ADD COLUMN method_name TEXT")?;
+            let qualified: Vec<(String, String)> = {
+                let mut stmt = conn.prepare("SELECT id, name FROM functions WHERE instr(name, '.') > 0")?;
+                let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                rows.collect::<SqliteResult<_>>()?
+            };
+            for (id, name) in &qualified {
+                conn.execute("UPDATE functions SET method_name = ?1 WHERE id = ?2", params![method_name(name), id])?;
+            }
+        }
+        conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_functions_method_name ON functions(method_name)")?;
+        if conn.prepare("SELECT strategy FROM calls LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE calls ADD COLUMN strategy TEXT")?;
+        }
+        if conn.prepare("SELECT receiver, receiver_type FROM calls LIMIT 0").is_err() {
+            conn.execute_batch(
+                "ALTER TABLE calls ADD COLUMN receiver TEXT;
+                 ALTER TABLE calls ADD COLUMN receiver_type TEXT;"
+            )?;
+        }
+        // NULL kind marks calls from before kinds were recorded; see `CALL_KIND`
+        if conn.prepare("SELECT kind FROM calls LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE calls ADD COLUMN kind TEXT")?;
+        }
+        
         Ok(Self {
             conn,
             db_path: path.to_path_buf(),
@@ -147,9 +319,14 @@ impl CallGraphDb {
             "DELETE FROM data_access;
              DELETE FROM calls;
              DELETE FROM functions;
+             DELETE FROM file_imports;
+             DELETE FROM types;
+             DELETE FROM di_bindings;
+             DELETE FROM async_handlers;
+             DELETE FROM function_metrics;
              DELETE FROM metadata;"
         )?;
-        Ok(())
+        bump_content_version(&self.conn)
     }
     
     /// Get database path
@@ -163,87 +340,244 @@ impl CallGraphDb {
     
     /// Insert a batch of functions (with their calls and data access)
     pub fn insert_batch(&mut self, batch: &FunctionBatch) -> SqliteResult<()> {
-        let tx = self.conn.transaction()?;
-        
-        for func in &batch.functions {
-            Self::insert_function_tx(&tx, func)?;
-        }
-        
-        tx.commit()
+        self.insert_batches(std::slice::from_ref(batch))
     }
     
     /// Insert multiple batches in a single transaction
+    ///
+    /// Either every batch is stored or - when any insert fails - none is.
     pub fn insert_batches(&mut self, batches: &[FunctionBatch]) -> SqliteResult<()> {
         let tx = self.conn.transaction()?;
         
-        for batch in batches {
-            for func in &batch.functions {
-                Self::insert_function_tx(&tx, func)?;
-            }
-        }
+        Self::insert_batches_tx(&tx, batches)?;
         
         tx.commit()
     }
     
-    /// Insert a single function (internal, uses transaction)
-    fn insert_function_tx(tx: &Transaction, func: &FunctionEntry) -> SqliteResult<()> {
-        // Insert function
-        tx.execute(
-            "INSERT OR REPLACE INTO functions (id, name, file, start_line, end_line, is_entry_point, is_data_accessor)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![
-                func.id,
-                func.name,
-                Self::extract_file_from_id(&func.id),
-                func.start_line,
-                func.end_line,
-                func.is_entry_point as i32,
-                func.is_data_accessor as i32,
-            ],
+    /// Insert batches' functions, calls, data access, file imports, types and
+    /// DI bindings
+    /// with multi-row statements (internal, uses transaction)
+    fn insert_batches_tx(tx: &Transaction, batches: &[FunctionBatch]) -> SqliteResult<()> {
+        tx.execute("DELETE FROM function_metrics", [])?;
+        bump_content_version(tx)?;
+        
+        let functions: Vec<&FunctionEntry> = batches.iter().flat_map(|b| &b.functions).collect();
+        let files: Vec<&str> = functions.iter().map(|f| Self::extract_file_from_id(&f.id)).collect();
+        let methods: Vec<Option<&str>> = functions
+            .iter()
+            .map(|f| f.name.contains('.').then(|| method_name(&f.name)))
+            .collect();
+        let function_rows: Vec<[&dyn ToSql; 10]> = functions
+            .iter()
+            .zip(&files)
+            .zip(&methods)
+            .map(|((f, file), method)| -> [&dyn ToSql; 10] {
+                [&f.id, &f.name, file, &f.start_line, &f.end_line, &f.is_entry_point, &f.is_data_accessor, &f.entry_kind, &f.is_exported, method]
+            })
+            .collect();
+        insert_rows(
+            tx,
+            "INSERT OR REPLACE INTO functions (id, name, file, start_line, end_line, is_entry_point, is_data_accessor, entry_kind, is_exported, method_name)",
+            &function_rows,
+        )?;
+        
+        let calls: Vec<(&FunctionEntry, &CallEntry, &str)> = functions
+            .iter()
+            .flat_map(|f| f.calls.iter().map(move |c| (*f, c, c.kind.as_str())))
+            .collect();
+        let call_rows: Vec<[&dyn ToSql; 8]> = calls
+            .iter()
+            .map(|(f, c, kind)| -> [&dyn ToSql; 8] {
+                [&f.id, &c.target, &c.resolved_id, &c.confidence, &c.line, &c.receiver, &c.receiver_type, kind]
+            })
+            .collect();
+        insert_rows(
+            tx,
+            "INSERT INTO calls (caller_id, target, resolved_id, confidence, line, receiver, receiver_type, kind)",
+            &call_rows,
         )?;
         
-        // Insert calls
-        for call in &func.calls {
+        let accesses: Vec<(&FunctionEntry, &DataAccessRef, &str, String)> = functions
+            .iter()
+            .flat_map(|f| f.data_access.iter().map(move |a| (*f, a)))
+            .map(|(f, a)| {
+                let operation = match a.operation {
+                    DataOperation::Read => "read",
+                    DataOperation::Write => "write",
+                    DataOperation::Delete => "delete",
+                };
+                (f, a, operation, serde_json::to_string(&a.fields).unwrap_or_default())
+            })
+            .collect();
+        let access_rows: Vec<[&dyn ToSql; 5]> = accesses
+            .iter()
+            .map(|(f, a, operation, fields)| -> [&dyn ToSql; 5] {
+                [&f.id, &a.table, operation, fields, &a.line]
+            })
+            .collect();
+        insert_rows(
+            tx,
+            "INSERT INTO data_access (function_id, table_name, operation, fields, line)",
+            &access_rows,
+        )?;
+        
+        let import_rows: Vec<[&dyn ToSql; 2]> = batches
+            .iter()
+            .flat_map(|b| b.imports.iter().map(move |imported| -> [&dyn ToSql; 2] { [&b.file, imported] }))
+            .collect();
+        insert_rows(tx, "INSERT OR IGNORE INTO file_imports (file, imported_file)", &import_rows)?;
+        
+        // One row per supertype, or a single row with a NULL supertype
+        let types: Vec<(&String, &TypeEntry, Option<&str>)> = batches
+            .iter()
+            .flat_map(|b| b.types.iter().map(move |ty| (&b.file, ty)))
+            .flat_map(|(file, ty)| {
+                let supertypes: Vec<Option<&str>> = if ty.supertypes.is_empty() {
+                    vec![None]
+                } else {
+                    ty.supertypes.iter().map(|s| Some(s.as_str())).collect()
+                };
+                supertypes.into_iter().map(move |supertype| (file, ty, supertype))
+            })
+            .collect();
+        let type_rows: Vec<[&dyn ToSql; 4]> = types
+            .iter()
+            .map(|(file, ty, supertype)| -> [&dyn ToSql; 4] { [*file, &ty.name, supertype, &ty.is_abstract] })
+            .collect();
+        insert_rows(tx, "INSERT INTO types (file, name, supertype, is_abstract)", &type_rows)?;
+        
+        let binding_rows: Vec<[&dyn ToSql; 3]> = batches
+            .iter()
+            .flat_map(|b| b.di_bindings.iter().map(move |binding| -> [&dyn ToSql; 3] {
+                [&b.file, &binding.interface, &binding.implementation]
+            }))
+            .collect();
+        insert_rows(tx, "INSERT INTO di_bindings (file, interface, implementation)", &binding_rows)?;
+        
+        let handler_rows: Vec<[&dyn ToSql; 3]> = batches
+            .iter()
+            .flat_map(|b| b.async_handlers.iter().map(move |handler| -> [&dyn ToSql; 3] {
+                [&b.file, &handler.job, &handler.handler]
+            }))
+            .collect();
+        insert_rows(tx, "INSERT INTO async_handlers (file, job, handler)", &handler_rows)
+    }
+    
+    /// Delete every function defined in `files`, with its calls, data access,
+    /// file imports, types, DI bindings and async job handlers
+    ///
+    /// Calls from other files that resolved to a deleted function are left
+    /// unresolved. Returns the `(id, name)` of each deleted function.
+    pub fn delete_files(&mut self, files: &[String]) -> SqliteResult<Vec<(String, String)>> {
+        let tx = self.conn.transaction()?;
+        let mut removed = Vec::new();
+        
+        for file in files {
+            {
+                let mut stmt = tx.prepare_cached("SELECT id, name FROM functions WHERE file = ?1")?;
+                let rows = stmt.query_map(params![file], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                for row in rows {
+                    removed.push(row?);
+                }
+            }
             tx.execute(
-                "INSERT INTO calls (caller_id, target, resolved_id, confidence, line)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    func.id,
-                    call.target,
-                    call.resolved_id,
-                    call.confidence,
-                    call.line,
-                ],
+                "DELETE FROM data_access WHERE function_id IN (SELECT id FROM functions WHERE file = ?1)",
+                params![file],
             )?;
-        }
-        
-        // Insert data access
-        for access in &func.data_access {
-            let fields_json = serde_json::to_string(&access.fields).unwrap_or_default();
-            let operation = match access.operation {
-                DataOperation::Read => "read",
-                DataOperation::Write => "write",
-                DataOperation::Delete => "delete",
-            };
-            
             tx.execute(
-                "INSERT INTO data_access (function_id, table_name, operation, fields, line)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    func.id,
-                    access.table,
-                    operation,
-                    fields_json,
-                    access.line,
-                ],
+                "DELETE FROM calls WHERE caller_id IN (SELECT id FROM functions WHERE file = ?1)",
+                params![file],
             )?;
+            tx.execute(
+                "UPDATE calls SET resolved_id = NULL, confidence = 0.0, strategy = NULL
+                 WHERE resolved_id IN (SELECT id FROM functions WHERE file = ?1)",
+                params![file],
+            )?;
+            tx.execute("DELETE FROM functions WHERE file = ?1", params![file])?;
+            tx.execute("DELETE FROM file_imports WHERE file = ?1", params![file])?;
+            tx.execute("DELETE FROM types WHERE file = ?1", params![file])?;
+            tx.execute("DELETE FROM di_bindings WHERE file = ?1", params![file])?;
+            tx.execute("DELETE FROM async_handlers WHERE file = ?1", params![file])?;
         }
+        tx.execute("DELETE FROM function_metrics", [])?;
+        bump_content_version(&tx)?;
         
-        Ok(())
+        tx.commit()?;
+        Ok(removed)
+    }
+    
+    /// Remove everything recorded for files not in `existing_files`, plus
+    /// calls and data access left behind by functions that no longer exist,
+    /// then `VACUUM` to return the space. A failed `VACUUM` is reported in
+    /// `vacuum_error` rather than as an error.
+    ///
+    /// Runs in a single write transaction, so readonly connections keep
+    /// reading their WAL snapshot until it commits.
+    pub fn prune(&mut self, existing_files: &[String]) -> SqliteResult<PruneStats> {
+        let tx = self.conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        
+        tx.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS existing_files (file TEXT PRIMARY KEY);
+             DELETE FROM existing_files;"
+        )?;
+        {
+            let mut stmt = tx.prepare("INSERT OR IGNORE INTO existing_files (file) VALUES (?1)")?;
+            for file in existing_files {
+                stmt.execute(params![file])?;
+            }
+        }
+        
+        tx.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS pruned_functions (id TEXT PRIMARY KEY);
+             DELETE FROM pruned_functions;
+             INSERT INTO pruned_functions
+             SELECT id FROM functions WHERE file NOT IN (SELECT file FROM existing_files);"
+        )?;
+        
+        let mut stats = PruneStats {
+            data_access: tx.execute(
+                "DELETE FROM data_access
+                 WHERE function_id IN (SELECT id FROM pruned_functions)
+                    OR function_id NOT IN (SELECT id FROM functions)",
+                [],
+            )?,
+            calls: tx.execute(
+                "DELETE FROM calls
+                 WHERE caller_id IN (SELECT id FROM pruned_functions)
+                    OR caller_id NOT IN (SELECT id FROM functions)",
+                [],
+            )?,
+            calls_unresolved: tx.execute(
+                "UPDATE calls SET resolved_id = NULL, confidence = 0.0, strategy = NULL
+                 WHERE resolved_id IN (SELECT id FROM pruned_functions)",
+                [],
+            )?,
+            functions: tx.execute("DELETE FROM functions WHERE id IN (SELECT id FROM pruned_functions)", [])?,
+            file_imports: tx.execute(
+                "DELETE FROM file_imports
+                 WHERE file NOT IN (SELECT file FROM existing_files)
+                    OR imported_file NOT IN (SELECT file FROM existing_files)",
+                [],
+            )?,
+            types: tx.execute("DELETE FROM types WHERE file NOT IN (SELECT file FROM existing_files)", [])?,
+            di_bindings: tx.execute("DELETE FROM di_bindings WHERE file NOT IN (SELECT file FROM existing_files)", [])?,
+            async_handlers: tx.execute("DELETE FROM async_handlers WHERE file NOT IN (SELECT file FROM existing_files)", [])?,
+            vacuum_error: None,
+        };
+        
+        tx.execute("DELETE FROM function_metrics", [])?;
+        bump_content_version(&tx)?;
+        tx.execute_batch("DROP TABLE existing_files; DROP TABLE pruned_functions;")?;
+        tx.commit()?;
+        
+        // The prune has committed, so a failed compaction (the database is
+        // busy, say) must not lose the stats
+        stats.vacuum_error = self.conn.execute_batch("VACUUM").err().map(|e| e.to_string());
+        Ok(stats)
     }
     
     /// Extract file path from function ID (format: "file:name:line")
-    fn extract_file_from_id(id: &str) -> &str {
+    pub(crate) fn extract_file_from_id(id: &str) -> &str {
         id.rsplit(':').nth(2).unwrap_or(id)
     }
     
@@ -254,54 +588,279 @@ impl CallGraphDb {
     /// Resolve all calls using SQL JOIN
     /// Returns the number of resolved calls
     pub fn resolve_calls(&mut self) -> SqliteResult<usize> {
-        // Strategy 1: Same file resolution (highest confidence)
-        let same_file_resolved = self.conn.execute(
-            "UPDATE calls SET 
-                resolved_id = (
-                    SELECT f.id FROM functions f 
-                    WHERE f.name = calls.target 
-                    AND f.file = (SELECT file FROM functions WHERE id = calls.caller_id)
-                    LIMIT 1
-                ),
-                confidence = 0.95
-             WHERE resolved_id IS NULL
-             AND EXISTS (
-                SELECT 1 FROM functions f 
-                WHERE f.name = calls.target 
-                AND f.file = (SELECT file FROM functions WHERE id = calls.caller_id)
-             )",
+        self.resolve_calls_where("1")
+    }
+    
+    /// Re-resolve only the calls an incremental update can affect: calls made
+    /// from `files`, and calls anywhere whose target matches one of `names`
+    /// (bare or `Owner.method` form). Matching calls are unresolved first so
+    /// they pick up added functions and drop removed ones.
+    pub fn resolve_calls_for(&mut self, files: &[String], names: &[String]) -> SqliteResult<usize> {
+        self.conn.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS touched_files (file TEXT PRIMARY KEY);
+             CREATE TEMP TABLE IF NOT EXISTS touched_names (name TEXT PRIMARY KEY);
+             DELETE FROM touched_files;
+             DELETE FROM touched_names;"
+        )?;
+        {
+            let tx = self.conn.transaction()?;
+            for file in files {
+                tx.execute("INSERT OR IGNORE INTO touched_files (file) VALUES (?1)", params![file])?;
+            }
+            for name in names {
+                tx.execute("INSERT OR IGNORE INTO touched_names (name) VALUES (?1)", params![name])?;
+                // "User.save" is also reachable through a bare `save()` call
+                if let Some((_, method)) = name.rsplit_once('.') {
+                    tx.execute("INSERT OR IGNORE INTO touched_names (name) VALUES (?1)", params![method])?;
+                }
+            }
+            tx.commit()?;
+        }
+        
+        let targets_touched = "(calls.target IN (SELECT name FROM touched_names)
+                OR (instr(calls.target, '.') > 0
+                    AND substr(calls.target, instr(calls.target, '.') + 1) IN (SELECT name FROM touched_names)))";
+        self.conn.execute(
+            &format!("UPDATE calls SET resolved_id = NULL, confidence = 0.0, strategy = NULL WHERE {}", targets_touched),
             [],
         )?;
         
-        // Strategy 2: Single global candidate (high confidence)
-        let single_candidate_resolved = self.conn.execute(
-            "UPDATE calls SET 
-                resolved_id = (
-                    SELECT f.id FROM functions f 
-                    WHERE f.name = calls.target
-                    LIMIT 1
-                ),
-                confidence = 0.8
-             WHERE resolved_id IS NULL
-             AND (SELECT COUNT(*) FROM functions f WHERE f.name = calls.target) = 1",
+        self.resolve_calls_where(&format!(
+            "(calls.caller_id IN (SELECT id FROM functions WHERE file IN (SELECT file FROM touched_files)) OR {})",
+            targets_touched
+        ))
+    }
+    
+    /// Run the resolution strategies over unresolved calls matching `scope`
+    /// (an SQL condition on `calls`), most specific first:
+    ///
+    /// 1. `async-handler` - for async calls, a function registered to run the
+    ///    enqueued job, preferring one in the registering file
+    /// 2. `di-binding` - a method of the one implementation a DI container
+    ///    binds the receiver's inferred (interface) type to
+    /// 3. `receiver-type` - a method of the receiver's inferred type, its
+    ///    supertypes or its implementations. Calls with an inferred receiver
+    ///    type resolve only these two ways, so `repo.save()` on a
+    ///    `UserRepository` never links to `FileCache.save`.
+    /// 4. `import` - an exported function in a file the caller's file imports
+    /// 5. `same-file` - a function in the caller's file
+    /// 6. `same-directory` - a function in a sibling file
+    /// 7. `global-unique` / `global-ambiguous` - any function with the name
+    /// 8. `method-same-file` / `method-global` - qualified and bare method names
+    ///    matched against each other ("User.new" vs "new", "save" vs "User.save")
+    ///
+    /// Each resolved call records the strategy that matched it.
+    fn resolve_calls_where(&mut self, scope: &str) -> SqliteResult<usize> {
+        let handler_candidate = "h.job = calls.target
+                AND (f.name = h.handler OR substr(f.name, -length(h.handler) - 1) = '.' || h.handler)";
+        let mut resolved = self.conn.execute(
+            &format!(
+                "UPDATE calls SET 
+                    resolved_id = (
+                        SELECT f.id FROM async_handlers h JOIN functions f ON {c}
+                        ORDER BY f.file != h.file, f.id
+                        LIMIT 1
+                    ),
+                    confidence = 0.8,
+                    strategy = 'async-handler'
+                 WHERE resolved_id IS NULL
+                 AND kind = 'async'
+                 AND {scope}
+                 AND EXISTS (SELECT 1 FROM async_handlers h JOIN functions f ON {c})",
+                c = handler_candidate,
+                scope = scope
+            ),
             [],
         )?;
         
-        // Strategy 3: Multiple candidates - pick first (low confidence)
-        let multi_candidate_resolved = self.conn.execute(
-            "UPDATE calls SET 
-                resolved_id = (
-                    SELECT f.id FROM functions f 
-                    WHERE f.name = calls.target
-                    LIMIT 1
-                ),
-                confidence = 0.4
-             WHERE resolved_id IS NULL
-             AND EXISTS (SELECT 1 FROM functions f WHERE f.name = calls.target)",
+        // Interfaces every binding maps to the same implementation
+        let bound = "(SELECT interface, MIN(implementation) AS implementation FROM di_bindings
+                GROUP BY interface HAVING COUNT(DISTINCT implementation) = 1)";
+        let binding_candidate = "b.interface = calls.receiver_type
+                AND f.name = b.implementation || '.' || substr(calls.target, instr(calls.target, '.') + 1)";
+        resolved += self.conn.execute(
+            &format!(
+                "UPDATE calls SET 
+                    resolved_id = (SELECT f.id FROM {b} b JOIN functions f ON {c} LIMIT 1),
+                    confidence = 0.95,
+                    strategy = 'di-binding'
+                 WHERE resolved_id IS NULL
+                 AND receiver_type IS NOT NULL
+                 AND {scope}
+                 AND EXISTS (SELECT 1 FROM {b} b JOIN functions f ON {c})",
+                b = bound,
+                c = binding_candidate,
+                scope = scope
+            ),
+            [],
+        )?;
+        
+        self.build_receiver_scope()?;
+        let receiver_candidate = "s.receiver_type = calls.receiver_type
+                AND f.name = s.type || '.' || substr(calls.target, instr(calls.target, '.') + 1)";
+        resolved += self.conn.execute(
+            &format!(
+                "UPDATE calls SET 
+                    resolved_id = (
+                        SELECT f.id FROM receiver_scope s JOIN functions f ON {c}
+                        ORDER BY s.rank
+                        LIMIT 1
+                    ),
+                    confidence = 0.95,
+                    strategy = 'receiver-type'
+                 WHERE resolved_id IS NULL
+                 AND receiver_type IS NOT NULL
+                 AND {scope}
+                 AND EXISTS (SELECT 1 FROM receiver_scope s JOIN functions f ON {c})",
+                c = receiver_candidate,
+                scope = scope
+            ),
+            [],
+        )?;
+        // Receivers whose type has subtypes dispatch on the runtime type
+        self.conn.execute(
+            &format!(
+                "UPDATE calls SET kind = 'dispatch'
+                 WHERE kind = 'method'
+                 AND receiver_type IN (SELECT supertype FROM types WHERE supertype IS NOT NULL)
+                 AND {}",
+                scope
+            ),
             [],
         )?;
+        let scope = format!("{} AND calls.receiver_type IS NULL", scope);
+        
+        let caller_file = "(SELECT file FROM functions WHERE id = calls.caller_id)";
+        // Qualified target ("User.new") without an exact match falls back to the
+        // bare method name, and a bare target ("save") to qualified methods ("User.save")
+        // through their indexed `method_name`. Each is its own pass so every
+        // lookup stays an index equality.
+        let qualified_method = "instr(calls.target, '.') > 0 AND f.name = substr(calls.target, instr(calls.target, '.') + 1)";
+        let bare_method = "instr(calls.target, '.') = 0 AND f.method_name = calls.target";
+        
+        let imported = |name_match: &str| format!(
+            "{} AND f.is_entry_point = 1
+             AND f.file IN (SELECT imported_file FROM file_imports WHERE file = {})",
+            name_match,
+            caller_file
+        );
+        let imported_exact = imported("f.name = calls.target");
+        let imported_qualified_method = imported(qualified_method);
+        let imported_bare_method = imported(bare_method);
+        let same_file = format!("f.name = calls.target AND f.file = {}", caller_file);
+        // rtrim() with every non-'/' character of the path strips the file name
+        let same_directory = format!(
+            "f.name = calls.target AND f.file != {cf}
+             AND rtrim(f.file, replace(f.file, '/', '')) = rtrim({cf}, replace({cf}, '/', ''))",
+            cf = caller_file
+        );
+        let qualified_method_same_file = format!("{} AND f.file = {}", qualified_method, caller_file);
+        let bare_method_same_file = format!("{} AND f.file = {}", bare_method, caller_file);
+        
+        let strategies: [(&str, f64, &str, bool); 11] = [
+            ("import", 0.9, &imported_exact, false),
+            ("import", 0.9, &imported_qualified_method, false),
+            ("import", 0.9, &imported_bare_method, false),
+            ("same-file", 0.95, &same_file, false),
+            ("same-directory", 0.85, &same_directory, false),
+            ("global-unique", 0.8, "f.name = calls.target", true),
+            ("global-ambiguous", 0.4, "f.name = calls.target", false),
+            ("method-same-file", 0.95, &qualified_method_same_file, false),
+            ("method-same-file", 0.95, &bare_method_same_file, false),
+            ("method-global", 0.4, qualified_method, false),
+            ("method-global", 0.4, bare_method, false),
+        ];
+        
+        for (strategy, confidence, candidate, unique) in strategies {
+            let guard = if unique {
+                format!("(SELECT COUNT(*) FROM functions f WHERE {}) = 1", candidate)
+            } else {
+                format!("EXISTS (SELECT 1 FROM functions f WHERE {})", candidate)
+            };
+            resolved += self.conn.execute(
+                &format!(
+                    "UPDATE calls SET 
+                        resolved_id = (
+                            SELECT f.id FROM functions f 
+                            WHERE {candidate}
+                            LIMIT 1
+                        ),
+                        confidence = ?1,
+                        strategy = ?2
+                     WHERE resolved_id IS NULL
+                     AND {scope}
+                     AND {guard}",
+                    candidate = candidate,
+                    scope = scope,
+                    guard = guard
+                ),
+                params![confidence, strategy],
+            )?;
+        }
+        
+        bump_content_version(&self.conn)?;
+        Ok(resolved)
+    }
+    
+    /// Fill the `receiver_scope` temp table with the types whose methods each
+    /// inferred receiver type may dispatch to, ranked by preference: the type
+    /// itself, then its ancestors, then its subtypes. Interfaces and abstract
+    /// classes rank their implementations first.
+    fn build_receiver_scope(&mut self) -> SqliteResult<()> {
+        self.conn.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS receiver_scope (receiver_type TEXT, type TEXT, rank INTEGER);
+             DELETE FROM receiver_scope;"
+        )?;
         
-        Ok(same_file_resolved + single_candidate_resolved + multi_candidate_resolved)
+        let mut supertypes: HashMap<String, Vec<String>> = HashMap::new();
+        let mut subtypes: HashMap<String, Vec<String>> = HashMap::new();
+        let mut abstract_types: HashSet<String> = HashSet::new();
+        {
+            let mut stmt = self.conn.prepare("SELECT name, supertype, is_abstract FROM types")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, bool>(2)?))
+            })?;
+            for row in rows {
+                let (name, supertype, is_abstract) = row?;
+                if is_abstract {
+                    abstract_types.insert(name.clone());
+                }
+                if let Some(supertype) = supertype {
+                    subtypes.entry(supertype.clone()).or_default().push(name.clone());
+                    supertypes.entry(name).or_default().push(supertype);
+                }
+            }
+        }
+        
+        let receiver_types: Vec<String> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT receiver_type FROM calls
+                 WHERE resolved_id IS NULL AND receiver_type IS NOT NULL"
+            )?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<SqliteResult<_>>()?
+        };
+        
+        let tx = self.conn.transaction()?;
+        for receiver_type in &receiver_types {
+            let ancestors = transitive(receiver_type, &supertypes);
+            let descendants = transitive(receiver_type, &subtypes);
+            let itself = vec![receiver_type.clone()];
+            let ranked = if abstract_types.contains(receiver_type) {
+                [&descendants, &itself, &ancestors]
+            } else {
+                [&itself, &ancestors, &descendants]
+            };
+            for (rank, types) in ranked.into_iter().enumerate() {
+                for ty in types {
+                    tx.execute(
+                        "INSERT INTO receiver_scope (receiver_type, type, rank) VALUES (?1, ?2, ?3)",
+                        params![receiver_type, ty, rank as i64],
+                    )?;
+                }
+            }
+        }
+        tx.commit()
     }
     
     // ========================================================================
@@ -311,7 +870,7 @@ impl CallGraphDb {
     /// Get a function by ID
     pub fn get_function(&self, id: &str) -> SqliteResult<Option<FunctionEntry>> {
         let mut stmt = self.conn.prepare_cached(
-            "SELECT id, name, file, start_line, end_line, is_entry_point, is_data_accessor
+            "SELECT id, name, file, start_line, end_line, is_entry_point, is_data_accessor, entry_kind, is_exported
              FROM functions WHERE id = ?1"
         )?;
         
@@ -325,6 +884,8 @@ impl CallGraphDb {
                 start_line: row.get(3)?,
                 end_line: row.get(4)?,
                 is_entry_point: row.get::<_, i32>(5)? != 0,
+                is_exported: row.get::<_, Option<i32>>(8)?.is_some_and(|e| e != 0),
+                entry_kind: row.get(7)?,
                 is_data_accessor: row.get::<_, i32>(6)? != 0,
                 calls: Vec::new(),
                 called_by: Vec::new(),
@@ -343,11 +904,66 @@ impl CallGraphDb {
         }
     }
     
+    /// Get every function with its calls (data access is not loaded)
+    pub fn get_all_functions(&self) -> SqliteResult<Vec<FunctionEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, start_line, end_line, is_entry_point, is_data_accessor, entry_kind, is_exported
+             FROM functions ORDER BY id"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(FunctionEntry {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                start_line: row.get(2)?,
+                end_line: row.get(3)?,
+                is_entry_point: row.get::<_, i32>(4)? != 0,
+                is_exported: row.get::<_, Option<i32>>(7)?.is_some_and(|e| e != 0),
+                entry_kind: row.get(6)?,
+                is_data_accessor: row.get::<_, i32>(5)? != 0,
+                calls: Vec::new(),
+                called_by: Vec::new(),
+                data_access: Vec::new(),
+            })
+        })?;
+        let mut functions: Vec<FunctionEntry> = rows.collect::<SqliteResult<_>>()?;
+        
+        let index: HashMap<String, usize> = functions.iter()
+            .enumerate()
+            .map(|(i, f)| (f.id.clone(), i))
+            .collect();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT caller_id, target, resolved_id, confidence, line, receiver, receiver_type, {}
+             FROM calls ORDER BY caller_id, line",
+            CALL_KIND
+        ))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let caller_id: String = row.get(0)?;
+            let Some(&i) = index.get(&caller_id) else { continue };
+            let resolved_id: Option<String> = row.get(2)?;
+            functions[i].calls.push(CallEntry {
+                target: row.get(1)?,
+                resolved: resolved_id.is_some(),
+                resolved_id,
+                confidence: row.get(3)?,
+                line: row.get(4)?,
+                receiver: row.get(5)?,
+                receiver_type: row.get(6)?,
+                kind: CallKind::parse(&row.get::<_, String>(7)?).unwrap_or_default(),
+            });
+        }
+        
+        Ok(functions)
+    }
+    
     /// Get all calls from a function
     pub fn get_calls_from(&self, caller_id: &str) -> SqliteResult<Vec<CallEntry>> {
         let mut stmt = self.conn.prepare_cached(
-            "SELECT target, resolved_id, confidence, line
-             FROM calls WHERE caller_id = ?1"
+            &format!(
+                "SELECT target, resolved_id, confidence, line, receiver, receiver_type, {}
+                 FROM calls WHERE caller_id = ?1",
+                CALL_KIND
+            )
         )?;
         
         let rows = stmt.query_map(params![caller_id], |row| {
@@ -357,6 +973,9 @@ impl CallGraphDb {
                 resolved: row.get::<_, Option<String>>(1)?.is_some(),
                 confidence: row.get(2)?,
                 line: row.get(3)?,
+                receiver: row.get(4)?,
+                receiver_type: row.get(5)?,
+                kind: CallKind::parse(&row.get::<_, String>(6)?).unwrap_or_default(),
             })
         })?;
         
@@ -439,6 +1058,112 @@ impl CallGraphDb {
         rows.collect()
     }
     
+    /// Functions whose name contains `text`, ignoring ASCII case (without
+    /// their calls or data access)
+    pub fn get_functions_containing(&self, text: &str) -> SqliteResult<Vec<FunctionEntry>> {
+        let pattern = format!("%{}%", text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT id, name, start_line, end_line, is_entry_point, is_data_accessor, entry_kind, is_exported
+             FROM functions WHERE name LIKE ?1 ESCAPE '\\' ORDER BY id"
+        )?;
+        let rows = stmt.query_map(params![pattern], |row| {
+            Ok(FunctionEntry {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                start_line: row.get(2)?,
+                end_line: row.get(3)?,
+                is_entry_point: row.get::<_, i32>(4)? != 0,
+                is_exported: row.get::<_, Option<i32>>(7)?.is_some_and(|e| e != 0),
+                entry_kind: row.get(6)?,
+                is_data_accessor: row.get::<_, i32>(5)? != 0,
+                calls: Vec::new(),
+                called_by: Vec::new(),
+                data_access: Vec::new(),
+            })
+        })?;
+        rows.collect()
+    }
+    
+    /// Number of calls a function makes, and of resolved calls to it
+    pub fn get_call_counts(&self, id: &str) -> SqliteResult<(usize, usize)> {
+        let outgoing = self.conn.query_row(
+            "SELECT COUNT(*) FROM calls WHERE caller_id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        let incoming = self.conn.query_row(
+            "SELECT COUNT(*) FROM calls WHERE resolved_id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok((outgoing, incoming))
+    }
+    
+    /// `(id, name)` of every function
+    pub fn get_function_names(&self) -> SqliteResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare("SELECT id, name FROM functions ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+    
+    /// Calls without a resolved target, with their caller's ID, ordered by
+    /// the caller's file and the call's line
+    pub fn get_unresolved_calls(&self) -> SqliteResult<Vec<(String, CallEntry)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT calls.caller_id, calls.target, calls.confidence, calls.line, calls.receiver, calls.receiver_type, {}
+             FROM calls JOIN functions f ON f.id = calls.caller_id
+             WHERE calls.resolved_id IS NULL
+             ORDER BY f.file, calls.line, calls.id",
+            CALL_KIND
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, CallEntry {
+                target: row.get(1)?,
+                resolved_id: None,
+                resolved: false,
+                confidence: row.get(2)?,
+                line: row.get(3)?,
+                receiver: row.get(4)?,
+                receiver_type: row.get(5)?,
+                kind: CallKind::parse(&row.get::<_, String>(6)?).unwrap_or_default(),
+            }))
+        })?;
+        rows.collect()
+    }
+    
+    /// Replace the stored function metrics
+    pub fn store_function_metrics(&mut self, metrics: &[FunctionMetrics]) -> SqliteResult<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM function_metrics", [])?;
+        let rows: Vec<[&dyn ToSql; 4]> = metrics
+            .iter()
+            .map(|m| -> [&dyn ToSql; 4] { [&m.id, &m.fan_in, &m.fan_out, &m.depth] })
+            .collect();
+        insert_rows(&tx, "INSERT OR REPLACE INTO function_metrics (function_id, fan_in, fan_out, depth)", &rows)?;
+        tx.commit()
+    }
+    
+    /// Stored metrics for a function, if computed since the graph last changed
+    pub fn get_function_metrics(&self, id: &str) -> SqliteResult<Option<FunctionMetrics>> {
+        self.conn.query_row(
+            "SELECT m.function_id, f.name, m.fan_in, m.fan_out, m.depth
+             FROM function_metrics m JOIN functions f ON f.id = m.function_id
+             WHERE m.function_id = ?1",
+            params![id],
+            |row| {
+                let id: String = row.get(0)?;
+                Ok(FunctionMetrics {
+                    file: Self::extract_file_from_id(&id).to_string(),
+                    id,
+                    name: row.get(1)?,
+                    fan_in: row.get(2)?,
+                    fan_out: row.get(3)?,
+                    depth: row.get(4)?,
+                })
+            },
+        ).optional()
+    }
+    
     /// Get all functions in a file
     pub fn get_functions_in_file(&self, file: &str) -> SqliteResult<Vec<String>> {
         let mut stmt = self.conn.prepare_cached(
@@ -490,6 +1215,51 @@ impl CallGraphDb {
         })
     }
     
+    /// Resolved call counts per resolution strategy, most common first
+    ///
+    /// Calls resolved before strategies were recorded count as "unknown".
+    pub fn get_strategy_counts(&self) -> SqliteResult<Vec<(String, usize)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(strategy, 'unknown'), COUNT(*) FROM calls
+             WHERE resolved_id IS NOT NULL
+             GROUP BY 1 ORDER BY 2 DESC, 1"
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+    
+    /// Every DI binding, with the file that registers it
+    pub fn get_async_handlers(&self) -> SqliteResult<Vec<(String, AsyncHandler)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file, job, handler FROM async_handlers ORDER BY file, job, handler"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, AsyncHandler { job: row.get(1)?, handler: row.get(2)? }))
+        })?;
+        rows.collect()
+    }
+    
+    pub fn get_di_bindings(&self) -> SqliteResult<Vec<(String, DiBinding)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file, interface, implementation FROM di_bindings ORDER BY file, interface, implementation"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, DiBinding { interface: row.get(1)?, implementation: row.get(2)? }))
+        })?;
+        rows.collect()
+    }
+    
+    /// Every file with at least one function, DI binding or async job handler
+    /// in the graph
+    pub fn get_files(&self) -> SqliteResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file FROM functions UNION SELECT file FROM di_bindings
+             UNION SELECT file FROM async_handlers ORDER BY file"
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    }
+    
     /// Set metadata value
     pub fn set_metadata(&self, key: &str, value: &str) -> SqliteResult<()> {
         self.conn.execute(
@@ -499,6 +1269,13 @@ impl CallGraphDb {
         Ok(())
     }
     
+    /// Token identifying the graph's current contents; changes with every
+    /// insert, delete, prune and resolution. None for databases last written
+    /// before versions were recorded.
+    pub fn content_version(&self) -> SqliteResult<Option<String>> {
+        self.get_metadata(CONTENT_VERSION_KEY)
+    }
+    
     /// Get metadata value
     pub fn get_metadata(&self, key: &str) -> SqliteResult<Option<String>> {
         let mut stmt = self.conn.prepare_cached(
@@ -519,8 +1296,11 @@ impl CallGraphDb {
 // ============================================================================
 
 /// Parallel writer that receives batches via channel and writes to SQLite
+///
+/// Producers may send files one at a time or pre-grouped; the writer thread
+/// buffers them and commits every `batch_size` files in one transaction.
 pub struct ParallelWriter {
-    sender: Sender<FunctionBatch>,
+    sender: Sender<Vec<FunctionBatch>>,
     handle: Option<JoinHandle<SqliteResult<DbStats>>>,
 }
 
@@ -530,10 +1310,20 @@ impl ParallelWriter {
     /// Spawns a background thread that receives FunctionBatch items
     /// and writes them to SQLite in batches.
     pub fn new(db_path: PathBuf, batch_size: usize) -> Self {
-        let (sender, receiver) = mpsc::channel::<FunctionBatch>();
+        Self::spawn(db_path, batch_size, true)
+    }
+    
+    /// Create a writer that only stores batches; the caller runs
+    /// `CallGraphDb::resolve_calls()` after `finish()`
+    pub fn without_resolution(db_path: PathBuf, batch_size: usize) -> Self {
+        Self::spawn(db_path, batch_size, false)
+    }
+    
+    fn spawn(db_path: PathBuf, batch_size: usize, resolve: bool) -> Self {
+        let (sender, receiver) = mpsc::channel::<Vec<FunctionBatch>>();
         
         let handle = thread::spawn(move || {
-            Self::writer_thread(db_path, receiver, batch_size)
+            Self::writer_thread(db_path, receiver, batch_size, resolve)
         });
         
         Self {
@@ -543,12 +1333,13 @@ impl ParallelWriter {
     }
     
     /// Send a batch to the writer
-    pub fn send(&self, batch: FunctionBatch) -> Result<(), mpsc::SendError<FunctionBatch>> {
-        self.sender.send(batch)
+    pub fn send(&self, batch: FunctionBatch) -> Result<(), mpsc::SendError<Vec<FunctionBatch>>> {
+        self.sender.send(vec![batch])
     }
     
-    /// Get a clone of the sender (for use in parallel iterators)
-    pub fn sender(&self) -> Sender<FunctionBatch> {
+    /// Get a clone of the sender (for use in parallel iterators), which takes
+    /// batches grouped by the producer
+    pub fn sender(&self) -> Sender<Vec<FunctionBatch>> {
         self.sender.clone()
     }
     
@@ -573,8 +1364,9 @@ impl ParallelWriter {
     /// Writer thread implementation
     fn writer_thread(
         db_path: PathBuf,
-        receiver: Receiver<FunctionBatch>,
+        receiver: Receiver<Vec<FunctionBatch>>,
         batch_size: usize,
+        resolve: bool,
     ) -> SqliteResult<DbStats> {
         let mut db = CallGraphDb::open(&db_path)?;
         
@@ -583,8 +1375,8 @@ impl ParallelWriter {
         
         let mut buffer: Vec<FunctionBatch> = Vec::with_capacity(batch_size);
         
-        for batch in receiver {
-            buffer.push(batch);
+        for batches in receiver {
+            buffer.extend(batches);
             
             if buffer.len() >= batch_size {
                 db.insert_batches(&buffer)?;
@@ -598,13 +1390,59 @@ impl ParallelWriter {
         }
         
         // Run resolution
-        db.resolve_calls()?;
+        if resolve {
+            db.resolve_calls()?;
+        }
         
         // Get final stats
         db.get_stats()
     }
 }
 
+/// Bound parameters per statement, kept under SQLite's historical limit of 999
+const MAX_STATEMENT_PARAMS: usize = 999;
+
+/// Give the graph a new content version, so anything cached against the old
+/// one stops matching. Unique across rebuilds: a recreated database never
+/// repeats an earlier version.
+fn bump_content_version(conn: &Connection) -> SqliteResult<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    let version = format!("{:x}-{:x}", nanos, WRITES.fetch_add(1, Ordering::Relaxed));
+    conn.execute(
+        "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+        params![CONTENT_VERSION_KEY, version],
+    )?;
+    Ok(())
+}
+
+/// Insert `rows` with as few multi-row `INSERT ... VALUES (...), (...)`
+/// statements as the parameter limit allows
+fn insert_rows<const N: usize>(tx: &Transaction, insert: &str, rows: &[[&dyn ToSql; N]]) -> SqliteResult<()> {
+    let row = format!("({})", vec!["?"; N].join(", "));
+    for chunk in rows.chunks(MAX_STATEMENT_PARAMS / N) {
+        let sql = format!("{} VALUES {}", insert, vec![row.as_str(); chunk.len()].join(", "));
+        tx.prepare_cached(&sql)?.execute(params_from_iter(chunk.iter().flatten()))?;
+    }
+    Ok(())
+}
+
+/// Every type reachable from `start` through `edges`, nearest first
+fn transitive(start: &str, edges: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut seen: HashSet<&str> = HashSet::from([start]);
+    let mut queue: VecDeque<&str> = VecDeque::from([start]);
+    let mut found = Vec::new();
+    while let Some(current) = queue.pop_front() {
+        for next in edges.get(current).into_iter().flatten() {
+            if seen.insert(next) {
+                found.push(next.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    found
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -621,6 +1459,8 @@ mod tests {
             start_line: 1,
             end_line: 10,
             is_entry_point: false,
+            is_exported: false,
+            entry_kind: None,
             is_data_accessor: false,
             calls: vec![],
             called_by: vec![],
@@ -653,6 +1493,8 @@ mod tests {
             start_line: 1,
             end_line: 10,
             is_entry_point: true,
+            is_exported: true,
+            entry_kind: Some("nextjs-route".to_string()),
             is_data_accessor: false,
             calls: vec![
                 CallEntry {
@@ -661,6 +1503,9 @@ mod tests {
                     resolved: false,
                     confidence: 0.0,
                     line: 5,
+                    receiver: None,
+                    receiver_type: None,
+                    kind: CallKind::Direct,
                 },
             ],
             called_by: vec![],
@@ -670,6 +1515,10 @@ mod tests {
         let batch = FunctionBatch {
             file: "src/main.ts".to_string(),
             functions: vec![func],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
         };
         
         db.insert_batch(&batch).unwrap();
@@ -684,6 +1533,7 @@ mod tests {
         let loaded = loaded.unwrap();
         assert_eq!(loaded.name, "main");
         assert_eq!(loaded.calls.len(), 1);
+        assert_eq!(loaded.entry_kind.as_deref(), Some("nextjs-route"));
     }
     
     #[test]
@@ -700,6 +1550,8 @@ mod tests {
             start_line: 1,
             end_line: 10,
             is_entry_point: true,
+            is_exported: true,
+            entry_kind: None,
             is_data_accessor: false,
             calls: vec![
                 CallEntry {
@@ -708,6 +1560,9 @@ mod tests {
                     resolved: false,
                     confidence: 0.0,
                     line: 5,
+                    receiver: None,
+                    receiver_type: None,
+                    kind: CallKind::Direct,
                 },
             ],
             called_by: vec![],
@@ -721,6 +1576,8 @@ mod tests {
             start_line: 15,
             end_line: 20,
             is_entry_point: false,
+            is_exported: false,
+            entry_kind: None,
             is_data_accessor: false,
             calls: vec![],
             called_by: vec![],
@@ -730,6 +1587,10 @@ mod tests {
         let batch = FunctionBatch {
             file: "src/main.ts".to_string(),
             functions: vec![caller, callee],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
         };
         
         db.insert_batch(&batch).unwrap();
@@ -749,6 +1610,56 @@ mod tests {
         assert_eq!(calls[0].resolved_id, Some("src/main.ts:helper:15".to_string()));
     }
     
+    #[test]
+    fn test_resolution_qualified_method_fallback() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        
+        let mut db = CallGraphDb::open(&db_path).unwrap();
+        
+        let call = |target: &str| CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 5,
+            receiver: None,
+            receiver_type: None,
+            kind: CallKind::Direct,
+        };
+        let mut caller = create_test_function("src/main.rs:main:1", "main");
+        // Bare method call resolves to the qualified method, qualified call to a bare function
+        caller.calls = vec![call("save"), call("Repo.load")];
+        
+        let batch = FunctionBatch {
+            file: "src/main.rs".to_string(),
+            functions: vec![
+                caller,
+                create_test_function("src/repo.rs:User.save:3", "User.save"),
+                create_test_function("src/repo.rs:load:20", "load"),
+            ],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        };
+        db.insert_batch(&batch).unwrap();
+        
+        assert_eq!(db.resolve_calls().unwrap(), 2);
+        
+        let calls = db.get_calls_from("src/main.rs:main:1").unwrap();
+        let resolved: Vec<Option<&str>> = calls.iter().map(|c| c.resolved_id.as_deref()).collect();
+        assert!(resolved.contains(&Some("src/repo.rs:User.save:3")));
+        assert!(resolved.contains(&Some("src/repo.rs:load:20")));
+        
+        // Bare method lookups go through the index rather than scanning functions
+        let plan: Vec<String> = db.conn
+            .prepare("EXPLAIN QUERY PLAN SELECT f.id FROM functions f WHERE f.method_name = ?1").unwrap()
+            .query_map(["save"], |row| row.get(3)).unwrap()
+            .collect::<SqliteResult<_>>().unwrap();
+        assert!(plan.iter().any(|step| step.contains("idx_functions_method_name")), "{:?}", plan);
+    }
+    
     #[test]
     fn test_get_callers() {
         let dir = tempdir().unwrap();
@@ -762,6 +1673,8 @@ mod tests {
             start_line: 1,
             end_line: 10,
             is_entry_point: true,
+            is_exported: true,
+            entry_kind: None,
             is_data_accessor: false,
             calls: vec![
                 CallEntry {
@@ -770,6 +1683,9 @@ mod tests {
                     resolved: true,
                     confidence: 0.95,
                     line: 5,
+                    receiver: None,
+                    receiver_type: None,
+                    kind: CallKind::Direct,
                 },
             ],
             called_by: vec![],
@@ -779,6 +1695,10 @@ mod tests {
         let batch = FunctionBatch {
             file: "src/main.ts".to_string(),
             functions: vec![caller],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
         };
         
         db.insert_batch(&batch).unwrap();
@@ -787,4 +1707,432 @@ mod tests {
         assert_eq!(callers.len(), 1);
         assert_eq!(callers[0], "src/main.ts:main:1");
     }
+    
+    #[test]
+    fn test_delete_files_and_scoped_resolution() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        
+        let mut db = CallGraphDb::open(&db_path).unwrap();
+        
+        let call = |target: &str| CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 5,
+            receiver: None,
+            receiver_type: None,
+            kind: CallKind::Direct,
+        };
+        let mut main = create_test_function("src/main.ts:main:1", "main");
+        main.calls = vec![call("helper"), call("log")];
+        db.insert_batch(&FunctionBatch { file: "src/main.ts".to_string(), functions: vec![main], imports: vec![], types: vec![], di_bindings: vec![], async_handlers: vec![] }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/utils.ts".to_string(),
+            functions: vec![create_test_function("src/utils.ts:helper:1", "helper")],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/log.ts".to_string(),
+            functions: vec![create_test_function("src/log.ts:log:1", "log")],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.resolve_calls().unwrap();
+        assert_eq!(db.get_stats().unwrap().resolved_calls, 2);
+        
+        // helper moves to a new line in utils.ts
+        let removed = db.delete_files(&["src/utils.ts".to_string()]).unwrap();
+        assert_eq!(removed, vec![("src/utils.ts:helper:1".to_string(), "helper".to_string())]);
+        assert_eq!(db.get_stats().unwrap().resolved_calls, 1);
+        
+        db.insert_batch(&FunctionBatch {
+            file: "src/utils.ts".to_string(),
+            functions: vec![create_test_function("src/utils.ts:helper:7", "helper")],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        let resolved = db.resolve_calls_for(&["src/utils.ts".to_string()], &["helper".to_string()]).unwrap();
+        assert_eq!(resolved, 1);
+        
+        let calls = db.get_calls_from("src/main.ts:main:1").unwrap();
+        let helper = calls.iter().find(|c| c.target == "helper").unwrap();
+        assert_eq!(helper.resolved_id.as_deref(), Some("src/utils.ts:helper:7"));
+        let log = calls.iter().find(|c| c.target == "log").unwrap();
+        assert_eq!(log.resolved_id.as_deref(), Some("src/log.ts:log:1"));
+    }
+    
+    #[test]
+    fn test_prune_removed_files() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        
+        let mut db = CallGraphDb::open(&db_path).unwrap();
+        
+        let call = |target: &str| CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 5,
+            receiver: None,
+            receiver_type: None,
+            kind: CallKind::Direct,
+        };
+        let mut main = create_test_function("src/main.ts:main:1", "main");
+        main.calls = vec![call("helper")];
+        let mut helper = create_test_function("src/old.ts:helper:1", "helper");
+        helper.calls = vec![call("log"), call("format")];
+        helper.data_access = vec![DataAccessRef {
+            table: "users".to_string(),
+            operation: DataOperation::Read,
+            fields: vec![],
+            line: 3,
+        }];
+        db.insert_batch(&FunctionBatch {
+            file: "src/main.ts".to_string(),
+            functions: vec![main],
+            imports: vec!["src/old.ts".to_string()],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/old.ts".to_string(),
+            functions: vec![helper],
+            imports: vec![],
+            types: vec![TypeEntry { name: "Old".to_string(), supertypes: vec![], is_abstract: false }],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.resolve_calls().unwrap();
+        
+        let stats = db.prune(&["src/main.ts".to_string()]).unwrap();
+        assert_eq!(stats, PruneStats {
+            functions: 1,
+            calls: 2,
+            data_access: 1,
+            file_imports: 1,
+            types: 1,
+            di_bindings: 0,
+            async_handlers: 0,
+            calls_unresolved: 1,
+            vacuum_error: None,
+        });
+        assert_eq!(db.get_files().unwrap(), vec!["src/main.ts".to_string()]);
+        assert_eq!(db.get_stats().unwrap().resolved_calls, 0);
+        
+        // Nothing left to prune
+        assert_eq!(db.prune(&["src/main.ts".to_string()]).unwrap(), PruneStats::default());
+    }
+    
+    #[test]
+    fn test_import_resolution_and_strategy_counts() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        
+        let mut db = CallGraphDb::open(&db_path).unwrap();
+        
+        let call = |target: &str| CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 5,
+            receiver: None,
+            receiver_type: None,
+            kind: CallKind::Direct,
+        };
+        let exported = |id: &str, name: &str| {
+            let mut func = create_test_function(id, name);
+            func.is_entry_point = true;
+            func
+        };
+        
+        // Two modules export save(); the caller imports the second one
+        let mut main = create_test_function("src/api/main.ts:main:1", "main");
+        main.calls = vec![call("save"), call("format"), call("load")];
+        db.insert_batch(&FunctionBatch {
+            file: "src/api/main.ts".to_string(),
+            functions: vec![main],
+            imports: vec!["src/db/users.ts".to_string()],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/cache/store.ts".to_string(),
+            functions: vec![exported("src/cache/store.ts:save:1", "save")],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/db/users.ts".to_string(),
+            functions: vec![
+                exported("src/db/users.ts:save:1", "save"),
+                exported("src/db/users.ts:UserStore.load:10", "UserStore.load"),
+            ],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/lib/format.ts".to_string(),
+            functions: vec![create_test_function("src/lib/format.ts:format:1", "format")],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/api/format.ts".to_string(),
+            functions: vec![create_test_function("src/api/format.ts:format:1", "format")],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        
+        assert_eq!(db.resolve_calls().unwrap(), 3);
+        
+        let calls = db.get_calls_from("src/api/main.ts:main:1").unwrap();
+        let save = calls.iter().find(|c| c.target == "save").unwrap();
+        assert_eq!(save.resolved_id.as_deref(), Some("src/db/users.ts:save:1"));
+        // A bare call also reaches an imported method through its method name
+        let load = calls.iter().find(|c| c.target == "load").unwrap();
+        assert_eq!(load.resolved_id.as_deref(), Some("src/db/users.ts:UserStore.load:10"));
+        let format = calls.iter().find(|c| c.target == "format").unwrap();
+        assert_eq!(format.resolved_id.as_deref(), Some("src/api/format.ts:format:1"));
+        
+        let counts = db.get_strategy_counts().unwrap();
+        assert!(counts.contains(&("import".to_string(), 2)));
+        assert!(counts.contains(&("same-directory".to_string(), 1)));
+    }
+    
+    #[test]
+    fn test_receiver_type_resolution() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        
+        let mut db = CallGraphDb::open(&db_path).unwrap();
+        
+        let call = |target: &str, receiver_type: Option<&str>| CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line: 5,
+            receiver: receiver_type.map(|_| "this.dep".to_string()),
+            receiver_type: receiver_type.map(|t| t.to_string()),
+            kind: if receiver_type.is_some() { CallKind::Method } else { CallKind::Direct },
+        };
+        let ty = |name: &str, supertypes: &[&str], is_abstract: bool| TypeEntry {
+            name: name.to_string(),
+            supertypes: supertypes.iter().map(|s| s.to_string()).collect(),
+            is_abstract,
+        };
+        
+        let mut main = create_test_function("src/service.ts:register:1", "register");
+        main.calls = vec![
+            call("save", Some("UserRepository")),
+            call("Save", Some("IUserStore")),
+            call("load", Some("UserRepository")),
+            call("trim", Some("string")),
+            call("save", None),
+        ];
+        db.insert_batch(&FunctionBatch {
+            file: "src/service.ts".to_string(),
+            functions: vec![main],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/repo.ts".to_string(),
+            functions: vec![
+                create_test_function("src/repo.ts:BaseRepository.load:1", "BaseRepository.load"),
+                create_test_function("src/repo.ts:UserRepository.save:10", "UserRepository.save"),
+                create_test_function("src/repo.ts:IUserStore.Save:20", "IUserStore.Save"),
+                create_test_function("src/repo.ts:SqlUserStore.Save:30", "SqlUserStore.Save"),
+            ],
+            imports: vec![],
+            types: vec![
+                ty("BaseRepository", &[], false),
+                ty("UserRepository", &["BaseRepository"], false),
+                ty("IUserStore", &[], true),
+                ty("SqlUserStore", &["IUserStore"], false),
+            ],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "src/cache.ts".to_string(),
+            functions: vec![
+                create_test_function("src/cache.ts:FileCache.save:1", "FileCache.save"),
+                create_test_function("src/cache.ts:trim:10", "trim"),
+            ],
+            imports: vec![],
+            types: vec![ty("FileCache", &[], false)],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        
+        db.resolve_calls().unwrap();
+        
+        let calls = db.get_calls_from("src/service.ts:register:1").unwrap();
+        let resolved = |target: &str, receiver_type: Option<&str>| {
+            calls.iter()
+                .find(|c| c.target == target && c.receiver_type.as_deref() == receiver_type)
+                .and_then(|c| c.resolved_id.clone())
+        };
+        assert_eq!(resolved("save", Some("UserRepository")).as_deref(), Some("src/repo.ts:UserRepository.save:10"));
+        // Interfaces dispatch to their implementations
+        assert_eq!(resolved("Save", Some("IUserStore")).as_deref(), Some("src/repo.ts:SqlUserStore.Save:30"));
+        // Inherited methods resolve on the base class
+        assert_eq!(resolved("load", Some("UserRepository")).as_deref(), Some("src/repo.ts:BaseRepository.load:1"));
+        // A receiver of an external type never falls back to name matching
+        assert_eq!(resolved("trim", Some("string")), None);
+        assert!(resolved("save", None).is_some());
+        
+        // Calls on a type with subtypes are dynamic dispatch
+        let kind = |target: &str| calls.iter().find(|c| c.target == target).map(|c| c.kind);
+        assert_eq!(kind("Save"), Some(CallKind::Dispatch));
+        assert_eq!(kind("load"), Some(CallKind::Method));
+        assert_eq!(kind("save"), Some(CallKind::Method));
+        
+        let counts = db.get_strategy_counts().unwrap();
+        assert!(counts.contains(&("receiver-type".to_string(), 3)));
+    }
+    
+    fn sample_batches(files: usize, functions_per_file: usize) -> Vec<FunctionBatch> {
+        (0..files).map(|f| {
+            let file = format!("src/file{}.ts", f);
+            FunctionBatch {
+                functions: (0..functions_per_file).map(|i| {
+                    let mut func = create_test_function(&format!("{}:fn{}:{}", file, i, i * 10), &format!("fn{}", i));
+                    func.calls = (0..5).map(|c| CallEntry {
+                        target: format!("fn{}", c),
+                        resolved_id: None,
+                        resolved: false,
+                        confidence: 0.0,
+                        line: (i * 10 + c) as u32,
+                        receiver: None,
+                        receiver_type: None,
+                        kind: CallKind::Direct,
+                    }).collect();
+                    func.data_access = vec![DataAccessRef {
+                        table: "users".to_string(),
+                        operation: DataOperation::Read,
+                        fields: vec!["id".to_string()],
+                        line: (i * 10) as u32,
+                    }];
+                    func
+                }).collect(),
+                imports: vec!["src/shared.ts".to_string()],
+                types: vec![TypeEntry { name: format!("Type{}", f), supertypes: vec![], is_abstract: false }],
+                di_bindings: vec![],
+                async_handlers: vec![],
+                file,
+            }
+        }).collect()
+    }
+    
+    #[test]
+    fn test_interrupted_write_rolls_back() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        
+        // Simulate a failure partway through a write batch
+        let db = CallGraphDb::open(&db_path).unwrap();
+        db.conn.execute_batch(
+            "CREATE TRIGGER fail_write BEFORE INSERT ON data_access WHEN NEW.table_name = 'boom'
+             BEGIN SELECT RAISE(ABORT, 'simulated failure'); END;"
+        ).unwrap();
+        drop(db);
+        
+        let mut batches = sample_batches(6, 150);
+        batches[4].functions[100].data_access[0].table = "boom".to_string();
+        
+        let writer = ParallelWriter::without_resolution(db_path.clone(), 3);
+        for batch in batches {
+            writer.send(batch).unwrap_or(());
+        }
+        assert!(writer.finish().is_err());
+        
+        // The first write batch committed; none of the failed one is visible
+        let db = CallGraphDb::open(&db_path).unwrap();
+        let stats = db.get_stats().unwrap();
+        assert_eq!(stats.total_functions, 3 * 150);
+        assert_eq!(stats.total_calls, 3 * 150 * 5);
+        assert_eq!(db.get_files().unwrap().len(), 3);
+        let orphans: i64 = db.conn.query_row(
+            "SELECT COUNT(*) FROM calls WHERE caller_id NOT IN (SELECT id FROM functions)",
+            [],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(orphans, 0);
+        let types: i64 = db.conn.query_row("SELECT COUNT(*) FROM types", [], |row| row.get(0)).unwrap();
+        assert_eq!(types, 3);
+    }
+    
+    /// Compares multi-row batched inserts with the row-at-a-time inserts they
+    /// replaced: `cargo test -p drift-core --release -- --ignored bench_batched_inserts --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_batched_inserts() {
+        let batches = sample_batches(3000, 10);
+        let dir = tempdir().unwrap();
+        
+        let mut db = CallGraphDb::open(&dir.path().join("rows.db")).unwrap();
+        let start = std::time::Instant::now();
+        for chunk in batches.chunks(100) {
+            let tx = db.conn.transaction().unwrap();
+            for func in chunk.iter().flat_map(|b| &b.functions) {
+                tx.execute(
+                    "INSERT OR REPLACE INTO functions (id, name, file, start_line, end_line, is_entry_point, is_data_accessor, entry_kind, is_exported)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    params![func.id, func.name, CallGraphDb::extract_file_from_id(&func.id), func.start_line, func.end_line,
+                        func.is_entry_point, func.is_data_accessor, func.entry_kind, func.is_exported],
+                ).unwrap();
+                for call in &func.calls {
+                    tx.execute(
+                        "INSERT INTO calls (caller_id, target, resolved_id, confidence, line, receiver, receiver_type, kind)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![func.id, call.target, call.resolved_id, call.confidence, call.line, call.receiver, call.receiver_type, call.kind.as_str()],
+                    ).unwrap();
+                }
+                for access in &func.data_access {
+                    tx.execute(
+                        "INSERT INTO data_access (function_id, table_name, operation, fields, line) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![func.id, access.table, "read", serde_json::to_string(&access.fields).unwrap(), access.line],
+                    ).unwrap();
+                }
+            }
+            tx.commit().unwrap();
+        }
+        let row_at_a_time = start.elapsed();
+        
+        let mut db = CallGraphDb::open(&dir.path().join("batched.db")).unwrap();
+        let start = std::time::Instant::now();
+        for chunk in batches.chunks(100) {
+            db.insert_batches(chunk).unwrap();
+        }
+        let batched = start.elapsed();
+        
+        eprintln!("row-at-a-time: {:?}, batched: {:?}", row_at_a_time, batched);
+        assert_eq!(db.get_stats().unwrap().total_calls, 3000 * 10 * 5);
+        assert!(batched < row_at_a_time);
+    }
 }
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 /// A function entry in the call graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionEntry {
-    /// Unique ID: "file:name:line"
+    /// Unique ID: "file:name:line" ("partial:Namespace.Name:0" for C# partial classes)
     pub id: String,
     /// Function name
     pub name: String,
@@ -17,6 +17,14 @@ pub struct FunctionEntry {
     pub end_line: u32,
     /// Is this an entry point (exported, route handler, etc.)?
     pub is_entry_point: bool,
+    /// Is this function exported / public?
+    #[serde(default)]
+    pub is_exported: bool,
+    /// How the entry point was recognized when it follows a framework file
+    /// convention ("nextjs-route", "nextjs-page", ...), carries a route
+    /// annotation ("route") or matches a configured `EntryPointRule` (its name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_kind: Option<String>,
     /// Does this function access data?
     pub is_data_accessor: bool,
     /// Calls made by this function
@@ -41,6 +49,59 @@ pub struct CallEntry {
     pub confidence: f32,
     /// Line number of the call
     pub line: u32,
+    /// Receiver expression as written (`this.repo` in `this.repo.save()`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver: Option<String>,
+    /// Static type inferred for the receiver; restricts resolution to that
+    /// type's methods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_type: Option<String>,
+    /// How the call reaches its target
+    #[serde(default)]
+    pub kind: CallKind,
+}
+
+/// How a call edge reaches its target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallKind {
+    /// Plain call by name (`save()`)
+    #[default]
+    Direct,
+    /// Method call on a receiver (`repo.save()`)
+    Method,
+    /// Function passed as an argument, called later by the callee
+    /// (`app.get('/x', handler)`)
+    Callback,
+    /// Method call on a receiver whose type has subtypes, so the target
+    /// depends on the runtime type
+    Dispatch,
+    /// Background work enqueued for a job handler
+    /// (`queue.enqueue('sendEmail', payload)`)
+    Async,
+}
+
+impl CallKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallKind::Direct => "direct",
+            CallKind::Method => "method",
+            CallKind::Callback => "callback",
+            CallKind::Dispatch => "dispatch",
+            CallKind::Async => "async",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Result<Self, String> {
+        match kind.trim().to_lowercase().as_str() {
+            "direct" => Ok(CallKind::Direct),
+            "method" => Ok(CallKind::Method),
+            "callback" => Ok(CallKind::Callback),
+            "dispatch" => Ok(CallKind::Dispatch),
+            "async" => Ok(CallKind::Async),
+            other => Err(format!("Unknown call kind '{}' (expected direct, method, callback, dispatch or async)", other)),
+        }
+    }
 }
 
 /// A data access reference
@@ -75,6 +136,45 @@ pub struct CallGraphShard {
     pub functions: Vec<FunctionEntry>,
 }
 
+/// Stage of a call graph build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildPhase {
+    /// Walking the project for source files
+    Scanning,
+    /// Extracting functions and calls from each file
+    Parsing,
+    /// Flushing extracted functions to storage
+    Writing,
+    /// Resolving call targets
+    Resolving,
+}
+
+impl BuildPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildPhase::Scanning => "scanning",
+            BuildPhase::Parsing => "parsing",
+            BuildPhase::Writing => "writing",
+            BuildPhase::Resolving => "resolving",
+        }
+    }
+}
+
+/// Progress event emitted while building the call graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildProgress {
+    pub phase: BuildPhase,
+    /// Files parsed so far
+    pub files_processed: usize,
+    /// Files to parse (0 until scanning finishes)
+    pub total_files: usize,
+    /// Milliseconds since the build started
+    pub elapsed_ms: u64,
+    /// File just parsed (parsing phase only)
+    pub current_file: Option<String>,
+}
+
 /// Result of building the call graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildResult {
@@ -96,6 +196,15 @@ pub struct BuildResult {
     pub errors: Vec<String>,
     /// Duration in milliseconds
     pub duration_ms: u64,
+    /// Functions new in the changed files (incremental builds only)
+    #[serde(default)]
+    pub functions_added: usize,
+    /// Functions no longer present in the changed files (incremental builds only)
+    #[serde(default)]
+    pub functions_removed: usize,
+    /// Functions re-extracted from the changed files (incremental builds only)
+    #[serde(default)]
+    pub functions_updated: usize,
 }
 
 /// Call graph index summary
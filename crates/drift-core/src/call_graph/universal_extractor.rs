@@ -3,8 +3,12 @@
 //! Extracts functions and calls from any language using the unified ParseResult.
 //! Also extracts classes as callable entities (for constructor resolution).
 
-use crate::parsers::{ParseResult, Language};
+use std::collections::HashSet;
+
+use crate::parsers::{is_route_decorator, CallSite, ClassInfo, FunctionInfo, ParseResult, Language};
+use super::entry_points::convention_entry_points;
 use super::extractor::{CallGraphExtractor, ExtractionResult, ExtractedFunction, ExtractedCall};
+use super::types::CallKind;
 
 /// Universal extractor that works with any ParseResult
 pub struct UniversalExtractor;
@@ -16,15 +20,25 @@ impl UniversalExtractor {
     
     /// Extract from a ParseResult
     pub fn extract_from_parse_result(&self, result: &ParseResult) -> ExtractionResult {
-        // Extract functions
+        // Extract functions - methods also listed on their class are added
+        // below under "ClassName.methodName" too. Rust and Go methods are only
+        // ever called through a receiver or type path, so they are listed
+        // under the qualified name alone.
+        let qualified_only = matches!(result.language, Language::Rust | Language::Go);
+        let is_class_method = |f: &&FunctionInfo| {
+            result.classes.iter().any(|c| c.methods.iter().any(|m| m.name == f.name && m.range.start == f.range.start))
+        };
         let mut functions: Vec<ExtractedFunction> = result.functions
             .iter()
+            .filter(|f| !(qualified_only && is_class_method(f)))
             .map(|f| ExtractedFunction {
                 name: f.name.clone(),
                 start_line: f.range.start.line,
                 end_line: f.range.end.line,
                 is_exported: f.is_exported,
                 is_async: f.is_async,
+                logical_id: None,
+                entry_kind: route_entry_kind(f),
             })
             .collect();
         
@@ -37,6 +51,8 @@ impl UniversalExtractor {
                 end_line: class.range.end.line,
                 is_exported: class.is_exported,
                 is_async: false,
+                logical_id: partial_class_id(class),
+                entry_kind: None,
             });
             
             // Also add class methods as functions
@@ -49,23 +65,202 @@ impl UniversalExtractor {
                     end_line: method.range.end.line,
                     is_exported: class.is_exported,
                     is_async: method.is_async,
+                    logical_id: None,
+                    entry_kind: route_entry_kind(method),
                 });
             }
         }
         
-        let calls: Vec<ExtractedCall> = result.calls
+        let mut calls: Vec<ExtractedCall> = result.calls
             .iter()
             .map(|c| ExtractedCall {
-                callee_name: c.callee.clone(),
+                callee_name: qualified_callee(result.language, c),
                 line: c.range.start.line,
                 receiver: c.receiver.clone(),
+                receiver_type: receiver_type(result, c),
+                kind: if c.receiver.is_some() { CallKind::Method } else { CallKind::Direct },
             })
             .collect();
+        calls.extend(callback_calls(result));
         
         ExtractionResult { functions, calls }
     }
 }
 
+/// Functions passed as arguments (`app.get('/x', handler)`), as callback
+/// edges from the function making the call
+///
+/// Only identifiers naming a function declared or imported in the file count,
+/// so ordinary variables passed around don't become calls.
+fn callback_calls(result: &ParseResult) -> Vec<ExtractedCall> {
+    let known: HashSet<&str> = result.functions
+        .iter()
+        .map(|f| f.name.as_str())
+        .chain(result.imports.iter().flat_map(|i| i.named.iter().chain(&i.default).map(|n| n.as_str())))
+        .collect();
+    
+    result.argument_refs
+        .iter()
+        .filter(|arg| known.contains(arg.name.as_str()))
+        .map(|arg| ExtractedCall {
+            callee_name: arg.name.clone(),
+            line: arg.range.start.line,
+            receiver: None,
+            receiver_type: None,
+            kind: CallKind::Callback,
+        })
+        .collect()
+}
+
+/// Route handlers are invoked by their framework, so they are entry points
+/// whether or not anything calls them
+fn route_entry_kind(func: &FunctionInfo) -> Option<String> {
+    func.decorators.iter().any(|d| is_route_decorator(d)).then(|| "route".to_string())
+}
+
+/// Call target as resolved against the function index
+///
+/// Rust associated-function calls on a type path (`User::new()`,
+/// `models::User::find()`) target the qualified "User.new" entry; every other
+/// call uses the bare callee name.
+fn qualified_callee(language: Language, call: &CallSite) -> String {
+    if language == Language::Rust {
+        let type_name = call.receiver.as_deref()
+            .and_then(|r| r.rsplit("::").next())
+            .filter(|t| t.chars().next().is_some_and(|ch| ch.is_ascii_uppercase()) && *t != "Self");
+        if let Some(type_name) = type_name {
+            return format!("{}.{}", type_name, call.callee);
+        }
+    }
+    call.callee.clone()
+}
+
+/// Static type of a call's receiver, from the declarations in scope
+///
+/// `this`/`self` is the enclosing class. A receiver naming a field
+/// (`this.repo`, `self.repo`, C#/Java `_repo`) takes the field's declared type,
+/// or the type of the constructor parameter it was injected through; a bare
+/// name may also be a parameter of the enclosing function. Member chains
+/// (`this.db.users`) follow field types of classes declared in the same file.
+/// Anything else - call results, indexing, locals - stays unknown.
+fn receiver_type(result: &ParseResult, call: &CallSite) -> Option<String> {
+    let receiver = call.receiver.as_deref()?.trim().replace("?.", ".").replace("->", ".");
+    let function = result.functions
+        .iter()
+        .filter(|f| f.range.contains(&call.range))
+        .min_by_key(|f| f.range.end.line - f.range.start.line);
+    let class = result.classes
+        .iter()
+        .filter(|c| c.range.contains(&call.range))
+        .min_by_key(|c| c.range.end.line - c.range.start.line)
+        .or_else(|| {
+            // Methods declared outside their type's body (Rust `impl` blocks)
+            let owner = function?.qualified_name.as_deref()?.rsplit_once('.')?.0;
+            result.classes.iter().find(|c| c.name == owner.rsplit('.').next().unwrap_or(owner))
+        });
+    
+    let mut segments = receiver.split('.');
+    let first = segments.next()?;
+    let mut ty = match first {
+        "this" | "self" | "$this" => {
+            let class = class?;
+            match segments.next() {
+                Some(field) => field_type(class, field)?,
+                None => return Some(class.name.clone()),
+            }
+        }
+        name => function
+            .and_then(|f| f.parameters.iter().find(|p| p.name == name))
+            .and_then(|p| p.type_annotation.as_deref())
+            .and_then(base_type_name)
+            .or_else(|| field_type(class?, name))?,
+    };
+    for member in segments {
+        let owner = result.classes.iter().find(|c| c.name == ty)?;
+        ty = field_type(owner, member)?;
+    }
+    Some(ty)
+}
+
+/// Declared type of `name` on `class`: a field or property, or a constructor
+/// parameter (TS parameter properties, C# primary constructors)
+fn field_type(class: &ClassInfo, name: &str) -> Option<String> {
+    if !is_identifier(name) {
+        return None;
+    }
+    let property = class.properties
+        .iter()
+        .find(|p| p.name == name)
+        .and_then(|p| p.type_annotation.as_deref());
+    let constructor_param = || {
+        class.methods
+            .iter()
+            .filter(|m| matches!(m.name.as_str(), "constructor" | "__init__" | "init") || m.name == class.name)
+            .flat_map(|m| &m.parameters)
+            .find(|p| p.name == name)
+            .and_then(|p| p.type_annotation.as_deref())
+    };
+    property.or_else(constructor_param).and_then(base_type_name)
+}
+
+/// Wrappers whose first type argument is the type methods are called on
+const TRANSPARENT_WRAPPERS: &[&str] = &["Optional", "Option", "Box", "Arc", "Rc", "RefCell", "Mutex", "RwLock", "Lazy"];
+
+/// Class name a declared type refers to, without nullability, generic
+/// arguments or namespace qualification: `Optional[UserRepository]`,
+/// `UserRepository | null`, `IRepository<User>?` and `App.Data.AppDbContext`
+/// give "UserRepository", "UserRepository", "IRepository" and "AppDbContext".
+/// Unknown-typed declarations (`any`, `object`, `dynamic`, ...) give `None`.
+pub(crate) fn base_type_name(declared: &str) -> Option<String> {
+    let mut ty = declared.trim().trim_start_matches(':').trim().trim_matches(['"', '\'']);
+    for prefix in ["readonly ", "final ", "const ", "mut ", "&", "*"] {
+        ty = ty.trim_start_matches(prefix).trim_start();
+    }
+    while let Some(open) = ty.find(['<', '[']) {
+        if !TRANSPARENT_WRAPPERS.contains(&ty[..open].trim()) {
+            break;
+        }
+        let inner = ty[open + 1..].trim_end().trim_end_matches(['>', ']']);
+        ty = inner.split(',').next().unwrap_or(inner).trim();
+    }
+    let alternatives: Vec<&str> = ty
+        .split('|')
+        .map(str::trim)
+        .filter(|t| !matches!(*t, "null" | "undefined" | "None"))
+        .collect();
+    if alternatives.len() != 1 {
+        return None;
+    }
+    let ty = alternatives[0]
+        .split(['<', '[', '?', '*', '&'])
+        .next()
+        .unwrap_or("")
+        .trim();
+    let ty = ty.rsplit(['.', ':', '\\']).next().unwrap_or(ty);
+    
+    if !is_identifier(ty) || matches!(ty, "any" | "unknown" | "object" | "Object" | "Any" | "dynamic" | "var" | "auto") {
+        return None;
+    }
+    Some(ty.to_string())
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+}
+
+/// Shared ID for a partial class, so its declarations across files collapse
+/// into one call graph node ("partial:Namespace.Name:0")
+fn partial_class_id(class: &ClassInfo) -> Option<String> {
+    if !class.is_partial {
+        return None;
+    }
+    let qualified = match &class.namespace {
+        Some(ns) => format!("{}.{}", ns, class.name),
+        None => class.name.clone(),
+    };
+    Some(format!("partial:{}:0", qualified))
+}
+
 impl Default for UniversalExtractor {
     fn default() -> Self {
         Self::new()
@@ -78,8 +273,14 @@ impl CallGraphExtractor for UniversalExtractor {
         Language::from_path(file).is_some()
     }
     
-    fn extract(&self, parse_result: &ParseResult, _file: &str) -> ExtractionResult {
-        self.extract_from_parse_result(parse_result)
+    fn extract(&self, parse_result: &ParseResult, file: &str) -> ExtractionResult {
+        let mut extraction = self.extract_from_parse_result(parse_result);
+        for (name, kind) in convention_entry_points(file, parse_result) {
+            for func in extraction.functions.iter_mut().filter(|f| f.name == name) {
+                func.entry_kind = Some(kind.to_string());
+            }
+        }
+        extraction
     }
     
     fn language(&self) -> Language {
@@ -115,6 +316,38 @@ mod tests {
         assert!(extraction.calls.len() >= 2); // console.log and world
     }
     
+    #[test]
+    fn test_call_kinds() {
+        let mut parser = ParserManager::new();
+        let source = r#"
+            import { audit } from './audit';
+            
+            function listUsers(req, res) {
+                return db.query('SELECT 1');
+            }
+            
+            export function register(app, config) {
+                app.get('/users', listUsers);
+                app.use(audit, config);
+                listUsers();
+            }
+        "#;
+        
+        let result = parser.parse(source, Language::TypeScript).unwrap();
+        let extraction = UniversalExtractor::new().extract_from_parse_result(&result);
+        let kinds: Vec<(&str, CallKind)> = extraction.calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.kind))
+            .collect();
+        
+        assert!(kinds.contains(&("get", CallKind::Method)));
+        assert!(kinds.contains(&("listUsers", CallKind::Callback)));
+        assert!(kinds.contains(&("listUsers", CallKind::Direct)));
+        assert!(kinds.contains(&("audit", CallKind::Callback)));
+        // Plain variables passed as arguments are not calls
+        assert!(!kinds.iter().any(|(name, _)| *name == "config"));
+    }
+    
     #[test]
     fn test_extract_classes_as_callables() {
         let mut parser = ParserManager::new();
@@ -144,6 +377,7 @@ mod tests {
         assert!(function_names.contains(&"UserService"), "Should have UserService class as callable");
         // Methods are extracted as top-level functions by the TS parser
         assert!(function_names.contains(&"getUser"), "Should have getUser method");
+        assert!(function_names.contains(&"UserService.getUser"), "Should have getUser qualified by its class");
     }
     
     #[test]
@@ -170,4 +404,222 @@ def main():
         assert!(function_names.contains(&"main"), "Should have main function");
         assert!(function_names.contains(&"AccountService"), "Should have AccountService class as callable");
     }
+    
+    #[test]
+    fn test_extract_rust_impl_methods() {
+        let mut parser = ParserManager::new();
+        let source = r#"
+            pub struct UserRepository;
+            
+            impl UserRepository {
+                pub fn new() -> Self { UserRepository }
+                pub fn save(&self) {}
+            }
+            
+            fn save() {}
+            
+            fn main() {
+                let repo = UserRepository::new();
+                repo.save();
+                save();
+            }
+        "#;
+        
+        let result = parser.parse(source, Language::Rust).unwrap();
+        let extraction = UniversalExtractor::new().extract_from_parse_result(&result);
+        
+        let mut names: Vec<&str> = extraction.functions.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["UserRepository", "UserRepository.new", "UserRepository.save", "main", "save"]);
+        
+        let targets: Vec<&str> = extraction.calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert!(targets.contains(&"UserRepository.new"));
+        assert!(targets.contains(&"save"));
+    }
+
+    #[test]
+    fn test_extract_go_receiver_and_interface_methods() {
+        let mut parser = ParserManager::new();
+        let source = r#"
+            package main
+            
+            type Repository interface {
+                Save(u *User) error
+            }
+            
+            type UserService struct{}
+            
+            func (s *UserService) Save(u *User) error { return nil }
+            
+            func run(repo Repository) {
+                repo.Save(nil)
+            }
+        "#;
+        
+        let result = parser.parse(source, Language::Go).unwrap();
+        let extraction = UniversalExtractor::new().extract_from_parse_result(&result);
+        
+        let mut names: Vec<&str> = extraction.functions.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Repository", "Repository.Save", "UserService", "UserService.Save", "run"]);
+        assert!(extraction.calls.iter().any(|c| c.callee_name == "Save"));
+    }
+    
+    #[test]
+    fn test_partial_classes_share_one_node() {
+        let mut parser = ParserManager::new();
+        let extractor = UniversalExtractor::new();
+        let part_a = "namespace Acme;\npublic partial class Report { public void Render() { } }";
+        let part_b = "namespace Acme;\n\npublic partial class Report { public void Export() { } }";
+        
+        let ids: Vec<String> = [("Report.cs", part_a), ("Report.Export.cs", part_b)]
+            .iter()
+            .map(|(file, source)| {
+                let result = parser.parse_file(file, source).unwrap();
+                let extraction = extractor.extract_from_parse_result(&result);
+                let entries = crate::call_graph::to_function_entries(file, &extraction, &[]);
+                entries.into_iter().find(|e| e.name == "Report").unwrap().id
+            })
+            .collect();
+        
+        assert_eq!(ids[0], "partial:Acme.Report:0");
+        assert_eq!(ids[0], ids[1]);
+    }
+    
+    #[test]
+    fn test_nextjs_app_router_entry_points() {
+        let mut parser = ParserManager::new();
+        let extractor = UniversalExtractor::new();
+        
+        let page = "export default async function UserPage() {\n  const user = await loadUser();\n  return <Profile user={user} />;\n}\n\nfunction loadUser() { return null; }\n";
+        let result = parser.parse_file("app/users/page.tsx", page).unwrap();
+        let extraction = extractor.extract(&result, "app/users/page.tsx");
+        let entries = crate::call_graph::to_function_entries("app/users/page.tsx", &extraction, &[]);
+        let user_page = entries.iter().find(|e| e.name == "UserPage").unwrap();
+        assert!(user_page.is_entry_point);
+        assert_eq!(user_page.entry_kind.as_deref(), Some("nextjs-page"));
+        assert!(entries.iter().find(|e| e.name == "loadUser").unwrap().entry_kind.is_none());
+        
+        let route = "export async function GET(req: Request) { return list(); }\nexport const POST = async (req: Request) => create(req);\n";
+        let result = parser.parse_file("src/app/api/users/route.ts", route).unwrap();
+        let extraction = extractor.extract(&result, "src/app/api/users/route.ts");
+        let mut kinds: Vec<(&str, Option<&str>)> = extraction.functions.iter()
+            .map(|f| (f.name.as_str(), f.entry_kind.as_deref()))
+            .collect();
+        kinds.sort();
+        assert_eq!(kinds, vec![("GET", Some("nextjs-route")), ("POST", Some("nextjs-route"))]);
+    }
+    
+    #[test]
+    fn test_route_decorators_are_entry_points() {
+        let mut parser = ParserManager::new();
+        let source = "@app.get('/users')\ndef list_users():\n    return load_users()\n\ndef load_users():\n    return []\n";
+        let result = parser.parse_file("app/routes.py", source).unwrap();
+        let extraction = UniversalExtractor::new().extract(&result, "app/routes.py");
+        let entries = crate::call_graph::to_function_entries("app/routes.py", &extraction, &[]);
+        
+        let list_users = entries.iter().find(|e| e.name == "list_users").unwrap();
+        assert_eq!(list_users.entry_kind.as_deref(), Some("route"));
+        assert!(entries.iter().find(|e| e.name == "load_users").unwrap().entry_kind.is_none());
+    }
+    
+    fn receiver_types(extraction: &ExtractionResult) -> Vec<(&str, Option<&str>)> {
+        extraction.calls.iter()
+            .map(|c| (c.callee_name.as_str(), c.receiver_type.as_deref()))
+            .collect()
+    }
+    
+    #[test]
+    fn test_receiver_types_csharp_constructor_injection() {
+        let mut parser = ParserManager::new();
+        let source = r#"
+public class UserService
+{
+    private readonly IUserRepository _repo;
+
+    public UserService(IUserRepository repo, FileCache cache)
+    {
+        _repo = repo;
+        cache.Warm();
+    }
+
+    public void Register(User user)
+    {
+        _repo.Save(user);
+        Helpers.Format(user);
+    }
+}
+"#;
+        
+        let result = parser.parse(source, Language::CSharp).unwrap();
+        let extraction = UniversalExtractor::new().extract_from_parse_result(&result);
+        let types = receiver_types(&extraction);
+        
+        assert!(types.contains(&("Save", Some("IUserRepository"))));
+        assert!(types.contains(&("Warm", Some("FileCache"))));
+        assert!(types.contains(&("Format", None)));
+    }
+    
+    #[test]
+    fn test_receiver_types_typescript_class_fields() {
+        let mut parser = ParserManager::new();
+        let source = r#"
+            export class UserService {
+                private cache: FileCache | null;
+                
+                constructor(private readonly repo: UserRepository) {}
+                
+                register(user: User, audit: AuditLog) {
+                    this.repo.save(user);
+                    this.cache?.save(user);
+                    audit.record(user);
+                    fetchUser().save();
+                }
+            }
+        "#;
+        
+        let result = parser.parse(source, Language::TypeScript).unwrap();
+        let extraction = UniversalExtractor::new().extract_from_parse_result(&result);
+        let types = receiver_types(&extraction);
+        
+        assert!(types.contains(&("save", Some("UserRepository"))));
+        assert!(types.contains(&("save", Some("FileCache"))));
+        assert!(types.contains(&("record", Some("AuditLog"))));
+        assert!(types.contains(&("save", None)));
+    }
+    
+    #[test]
+    fn test_receiver_types_python_self_attributes() {
+        let mut parser = ParserManager::new();
+        let source = r#"
+class UserService:
+    def __init__(self, repo: UserRepository):
+        self.repo = repo
+        self.cache = FileCache()
+
+    def register(self, user):
+        self.repo.save(user)
+        self.cache.save(user)
+        self.notify(user)
+"#;
+        
+        let result = parser.parse(source, Language::Python).unwrap();
+        let extraction = UniversalExtractor::new().extract_from_parse_result(&result);
+        let types = receiver_types(&extraction);
+        
+        assert!(types.contains(&("save", Some("UserRepository"))));
+        assert!(types.contains(&("save", Some("FileCache"))));
+        assert!(types.contains(&("notify", Some("UserService"))));
+    }
+    
+    #[test]
+    fn test_base_type_name() {
+        assert_eq!(base_type_name("Optional[UserRepository]").as_deref(), Some("UserRepository"));
+        assert_eq!(base_type_name("UserRepository | null").as_deref(), Some("UserRepository"));
+        assert_eq!(base_type_name("IRepository<User>?").as_deref(), Some("IRepository"));
+        assert_eq!(base_type_name("App.Data.AppDbContext").as_deref(), Some("AppDbContext"));
+        assert_eq!(base_type_name("Arc<Mutex<Db>>").as_deref(), Some("Db"));
+        assert_eq!(base_type_name("any"), None);
+        assert_eq!(base_type_name("A | B"), None);
+    }
 }
@@ -0,0 +1,206 @@
+//! Unresolved Calls
+//!
+//! Lists the calls resolution left without a target, with the functions that
+//! share the callee's method name - the candidates the resolver rejected - and
+//! why. Candidates are derived when queried, so they always reflect the
+//! functions currently in the graph.
+//!
+//! After resolution a call with any same-named function is resolved unless an
+//! inferred receiver type ruled every candidate out, so an unresolved call
+//! either has no candidates (usually a library call) or a receiver type
+//! mismatch.
+
+use std::collections::HashMap;
+
+use globset::Glob;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::storage::CallGraphDb;
+
+/// Candidates reported per call
+const MAX_CANDIDATES: usize = 3;
+
+/// Filters for `find_unresolved_calls`
+#[derive(Debug, Clone, Default)]
+pub struct UnresolvedCallOptions {
+    /// Only calls made from files matching this glob (`src/**/*.ts`)
+    pub file_glob: Option<String>,
+    /// Only calls whose callee name matches this regex
+    pub callee_pattern: Option<String>,
+    /// Maximum results
+    pub limit: Option<usize>,
+}
+
+/// Why a call has no target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnresolvedReason {
+    /// No function in the project has the callee's name
+    NoCandidates,
+    /// Functions with the name exist, but none on the receiver's inferred type
+    ReceiverTypeMismatch,
+    /// Candidates exist and resolution hasn't run since the call was stored
+    Pending,
+}
+
+impl UnresolvedReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnresolvedReason::NoCandidates => "no-candidates",
+            UnresolvedReason::ReceiverTypeMismatch => "receiver-type-mismatch",
+            UnresolvedReason::Pending => "pending",
+        }
+    }
+}
+
+/// A call site without a resolved target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedCall {
+    pub caller_id: String,
+    pub file: String,
+    pub line: u32,
+    pub callee: String,
+    pub receiver: Option<String>,
+    pub receiver_type: Option<String>,
+    pub reason: UnresolvedReason,
+    /// IDs of functions sharing the callee's method name, nearest first
+    pub candidates: Vec<String>,
+}
+
+/// Unresolved calls matching `options`, sorted by file and line
+pub fn find_unresolved_calls(db: &CallGraphDb, options: &UnresolvedCallOptions) -> Result<Vec<UnresolvedCall>, String> {
+    let file_glob = options.file_glob
+        .as_deref()
+        .map(|g| Glob::new(g).map(|g| g.compile_matcher()).map_err(|e| format!("Invalid file glob '{}': {}", g, e)))
+        .transpose()?;
+    let callee_pattern = options.callee_pattern
+        .as_deref()
+        .map(|p| Regex::new(p).map_err(|e| format!("Invalid callee pattern '{}': {}", p, e)))
+        .transpose()?;
+
+    let mut by_method: HashMap<String, Vec<String>> = HashMap::new();
+    for (id, name) in db.get_function_names().map_err(|e| e.to_string())? {
+        by_method.entry(method_name(&name).to_string()).or_default().push(id);
+    }
+
+    let mut unresolved = Vec::new();
+    for (caller_id, call) in db.get_unresolved_calls().map_err(|e| e.to_string())? {
+        let file = CallGraphDb::extract_file_from_id(&caller_id).to_string();
+        if file_glob.as_ref().is_some_and(|g| !g.is_match(&file))
+            || callee_pattern.as_ref().is_some_and(|re| !re.is_match(&call.target))
+        {
+            continue;
+        }
+
+        let mut candidates: Vec<&String> = by_method.get(method_name(&call.target)).into_iter().flatten().collect();
+        candidates.sort_by_key(|id| {
+            let candidate_file = CallGraphDb::extract_file_from_id(id);
+            (candidate_file != file, directory(candidate_file) != directory(&file), id.as_str())
+        });
+        let reason = if candidates.is_empty() {
+            UnresolvedReason::NoCandidates
+        } else if call.receiver_type.is_some() {
+            UnresolvedReason::ReceiverTypeMismatch
+        } else {
+            UnresolvedReason::Pending
+        };
+
+        unresolved.push(UnresolvedCall {
+            candidates: candidates.into_iter().take(MAX_CANDIDATES).cloned().collect(),
+            caller_id,
+            file,
+            line: call.line,
+            callee: call.target,
+            receiver: call.receiver,
+            receiver_type: call.receiver_type,
+            reason,
+        });
+        if options.limit.is_some_and(|limit| unresolved.len() >= limit) {
+            break;
+        }
+    }
+
+    Ok(unresolved)
+}
+
+/// Last segment of a qualified name ("User.save" -> "save")
+pub(crate) fn method_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+fn directory(file: &str) -> &str {
+    file.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::fixtures::function;
+    use crate::call_graph::{CallEntry, CallKind, FunctionBatch, TypeEntry};
+    use tempfile::tempdir;
+
+    fn call(target: &str, line: u32, receiver_type: Option<&str>) -> CallEntry {
+        CallEntry {
+            target: target.to_string(),
+            resolved_id: None,
+            resolved: false,
+            confidence: 0.0,
+            line,
+            receiver: receiver_type.map(|_| "store".to_string()),
+            receiver_type: receiver_type.map(|t| t.to_string()),
+            kind: if receiver_type.is_some() { CallKind::Method } else { CallKind::Direct },
+        }
+    }
+
+    #[test]
+    fn test_find_unresolved_calls() {
+        let dir = tempdir().unwrap();
+        let mut db = CallGraphDb::open(&dir.path().join("test.db")).unwrap();
+        db.insert_batch(&FunctionBatch {
+            file: "all".to_string(),
+            functions: vec![
+                function("src/api/users.ts:handler:1", vec![
+                    call("save", 3, Some("AuditLog")),
+                    call("lodash.get", 4, None),
+                    call("helper", 5, None),
+                ]),
+                function("src/api/orders.ts:handler:1", vec![call("render", 2, None)]),
+                function("src/db/repo.ts:UserRepository.save:1", vec![]),
+                function("src/api/cache.ts:FileCache.save:1", vec![]),
+                function("src/api/users.ts:helper:20", vec![]),
+            ],
+            imports: vec![],
+            types: vec![TypeEntry { name: "AuditLog".to_string(), supertypes: vec![], is_abstract: false }],
+            di_bindings: vec![],
+            async_handlers: vec![],
+        }).unwrap();
+        db.resolve_calls().unwrap();
+
+        let all = find_unresolved_calls(&db, &UnresolvedCallOptions::default()).unwrap();
+        let summary: Vec<(&str, u32, &str, UnresolvedReason)> = all.iter()
+            .map(|c| (c.file.as_str(), c.line, c.callee.as_str(), c.reason))
+            .collect();
+        assert_eq!(summary, vec![
+            ("src/api/orders.ts", 2, "render", UnresolvedReason::NoCandidates),
+            ("src/api/users.ts", 3, "save", UnresolvedReason::ReceiverTypeMismatch),
+            ("src/api/users.ts", 4, "lodash.get", UnresolvedReason::NoCandidates),
+        ]);
+        // Candidates in the caller's directory come first
+        assert_eq!(all[1].candidates, vec!["src/api/cache.ts:FileCache.save:1", "src/db/repo.ts:UserRepository.save:1"]);
+        assert_eq!(all[1].receiver_type.as_deref(), Some("AuditLog"));
+
+        let filtered = find_unresolved_calls(&db, &UnresolvedCallOptions {
+            file_glob: Some("src/api/users.ts".to_string()),
+            callee_pattern: Some(r"^lodash\.".to_string()),
+            limit: None,
+        }).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].callee, "lodash.get");
+
+        assert!(find_unresolved_calls(&db, &UnresolvedCallOptions {
+            callee_pattern: Some("(".to_string()),
+            ..Default::default()
+        }).is_err());
+    }
+}
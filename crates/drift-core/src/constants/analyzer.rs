@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use std::time::Instant;
 use rayon::prelude::*;
 
-use crate::parsers::ParserManager;
+use crate::parsers::{EnumInfo, ParserManager};
 use super::types::*;
 use super::extractor::ConstantExtractor;
 use super::secrets::SecretDetector;
@@ -52,7 +52,7 @@ impl ConstantsAnalyzer {
                     
                     let constants = EXTRACTOR.with(|ext| ext.extract(&result, file_path, &source));
                     let secrets = SECRET_DETECTOR.with(|det| det.detect(&source, file_path));
-                    let magic_numbers = Self::find_magic_numbers(&source, file_path);
+                    let magic_numbers = Self::find_magic_numbers(&source, file_path, &result.enums);
                     
                     Some(FileAnalysis {
                         constants,
@@ -93,7 +93,9 @@ impl ConstantsAnalyzer {
         }
     }
 
-    fn find_magic_numbers(source: &str, file_path: &str) -> Vec<MagicNumber> {
+    /// Numeric literals worth naming; enum bodies are skipped since their
+    /// members already name the value
+    fn find_magic_numbers(source: &str, file_path: &str, enums: &[EnumInfo]) -> Vec<MagicNumber> {
         use regex::Regex;
         use once_cell::sync::Lazy;
 
@@ -114,6 +116,11 @@ impl ConstantsAnalyzer {
         for (line_num, line) in source.lines().enumerate() {
             let trimmed = line.trim();
             
+            let in_enum = enums.iter().any(|e| (e.range.start.line..=e.range.end.line).contains(&(line_num as u32)));
+            if in_enum {
+                continue;
+            }
+            
             // Skip comments and strings
             if trimmed.starts_with("//") || trimmed.starts_with("#") ||
                trimmed.starts_with("*") || trimmed.contains("\"") {
@@ -275,4 +282,21 @@ mod tests {
         let analyzer = ConstantsAnalyzer::new();
         assert!(true); // Just verify it creates without panic
     }
+
+    #[test]
+    fn test_enum_members_are_named_constants() {
+        let source = "export enum Priority {\n  Low = 17,\n  High = 42,\n}\nconst retries = 37;\n";
+        let result = ParserManager::new().parse_file("priority.ts", source).unwrap();
+        
+        let constants = ConstantExtractor::new().extract(&result, "priority.ts", source);
+        let high = constants.iter().find(|c| c.name == "Priority.High").unwrap();
+        assert_eq!(high.value, ConstantValue::Number(42.0));
+        assert_eq!(high.line, 3);
+        assert_eq!(high.declaration_type, "enum");
+        assert!(high.is_exported);
+        
+        let magic = ConstantsAnalyzer::find_magic_numbers(source, "priority.ts", &result.enums);
+        let values: Vec<f64> = magic.iter().map(|m| m.value).collect();
+        assert_eq!(values, vec![37.0]);
+    }
 }
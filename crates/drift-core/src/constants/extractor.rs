@@ -35,9 +35,12 @@ impl ConstantExtractor {
 
     /// Extract constants from a parse result
     pub fn extract(&self, result: &ParseResult, file_path: &str, source: &str) -> Vec<ConstantInfo> {
+        // Enum members are named constants in every language
+        let mut constants = self.extract_enum_members(result, file_path);
+        
         let tree = match &result.tree {
             Some(t) => t,
-            None => return Vec::new(),
+            None => return constants,
         };
 
         let query = match result.language {
@@ -49,15 +52,16 @@ impl ConstantExtractor {
             Language::Php => self.php_query.as_ref(),
             Language::Rust => self.rust_query.as_ref(),
             Language::Cpp | Language::C => self.cpp_query.as_ref(),
+            // No constant query for Ruby, Kotlin or Swift yet
+            Language::Ruby | Language::Kotlin | Language::Swift => None,
         };
 
         let query = match query {
             Some(q) => q,
-            None => return Vec::new(),
+            None => return constants,
         };
 
         let mut cursor = QueryCursor::new();
-        let mut constants = Vec::new();
         let source_bytes = source.as_bytes();
 
         for match_ in cursor.matches(query, tree.root_node(), source_bytes) {
@@ -69,6 +73,30 @@ impl ConstantExtractor {
         constants
     }
 
+    /// One constant per enum member, named `Enum.Member`
+    fn extract_enum_members(&self, result: &ParseResult, file_path: &str) -> Vec<ConstantInfo> {
+        let language = format!("{:?}", result.language).to_lowercase();
+        result.enums.iter()
+            .flat_map(|e| e.variants.iter().map(move |v| (e, v)))
+            .map(|(e, variant)| {
+                let value = variant.value.as_deref()
+                    .map(Self::parse_value)
+                    .unwrap_or_else(|| ConstantValue::String(variant.name.clone()));
+                ConstantInfo {
+                    category: Self::categorize_constant(&e.name, &value),
+                    name: format!("{}.{}", e.name, variant.name),
+                    value,
+                    file: file_path.to_string(),
+                    line: variant.range.start.line + 1,
+                    column: variant.range.start.column,
+                    is_exported: e.is_exported,
+                    language: language.clone(),
+                    declaration_type: "enum".to_string(),
+                }
+            })
+            .collect()
+    }
+
     fn extract_from_match(
         &self,
         match_: &tree_sitter::QueryMatch,
@@ -3,13 +3,25 @@
 //! This module is AST-first: it consumes ParseResult from the parsers module
 //! which already has imports/exports extracted via tree-sitter AST parsing.
 
-use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::time::Instant;
 
+use super::layers::infer_layers;
+use super::rules::{module_of, DependencyRules};
+use super::usage::find_unused_exports;
 use super::types::*;
 use crate::parsers::{ParserManager, ParseResult};
 
+/// Hotspots reported, heaviest first
+const MAX_HOTSPOTS: usize = 10;
+
+/// Hot symbols reported per hotspot
+const MAX_HOT_SYMBOLS: usize = 10;
+
+/// Importing files and imported symbols along one module edge
+type EdgeStats<'a> = (HashSet<&'a str>, HashSet<&'a str>);
+
 /// Coupling analyzer - uses AST-parsed data from ParserManager
 pub struct CouplingAnalyzer {
     parser: ParserManager,
@@ -27,13 +39,7 @@ impl CouplingAnalyzer {
         let start = Instant::now();
         
         // Parse all files via tree-sitter AST
-        let mut file_graphs: HashMap<String, FileGraph> = HashMap::new();
-        
-        for file in files {
-            if let Some(graph) = self.build_file_graph_from_ast(file) {
-                file_graphs.insert(file.clone(), graph);
-            }
-        }
+        let file_graphs = self.build_file_graphs(files);
         
         // Build module map (directory -> files)
         let module_map = self.build_module_map(&file_graphs);
@@ -45,10 +51,13 @@ impl CouplingAnalyzer {
         let cycles = self.detect_cycles(&file_graphs, &module_map);
         
         // Find hotspots
-        let hotspots = self.find_hotspots(&modules);
+        let hotspots = self.find_hotspots(&file_graphs, &module_map);
         
         // Find unused exports
-        let unused_exports = self.find_unused_exports(&file_graphs);
+        let unused_exports = find_unused_exports(&file_graphs);
+        
+        // Infer layers from the module graph
+        let (layers, layer_violations) = infer_layers(&file_graphs, &module_map);
         
         // Calculate health score
         let health_score = self.calculate_health_score(&modules, &cycles);
@@ -61,9 +70,32 @@ impl CouplingAnalyzer {
             health_score,
             files_analyzed: file_graphs.len(),
             duration_ms: start.elapsed().as_millis() as u64,
+            layers,
+            layer_violations,
         }
     }
     
+    /// Check module dependency edges against architectural rules
+    pub fn check_dependency_rules(&mut self, files: &[String], rules: &[DependencyRule]) -> Result<Vec<DependencyViolation>, String> {
+        let rules = DependencyRules::new(rules)?;
+        let file_graphs = self.build_file_graphs(files);
+        let module_map = self.build_module_map(&file_graphs);
+        let cycles = self.detect_cycles(&file_graphs, &module_map);
+        Ok(rules.check(&file_graphs, &module_map, &cycles))
+    }
+    
+    fn build_file_graphs(&mut self, files: &[String]) -> HashMap<String, FileGraph> {
+        let mut file_graphs: HashMap<String, FileGraph> = HashMap::new();
+        
+        for file in files {
+            if let Some(graph) = self.build_file_graph_from_ast(file) {
+                file_graphs.insert(file.clone(), graph);
+            }
+        }
+        
+        file_graphs
+    }
+    
     /// Build file graph from AST-parsed data (no regex needed - tree-sitter handles it)
     fn build_file_graph_from_ast(&mut self, file: &str) -> Option<FileGraph> {
         let source = std::fs::read_to_string(file).ok()?;
@@ -73,22 +105,61 @@ impl CouplingAnalyzer {
         
         let mut graph = FileGraph {
             path: file.to_string(),
+            language: format!("{:?}", result.language).to_lowercase(),
+            types: result.classes.len(),
+            abstract_types: result.classes.iter().filter(|c| c.is_abstract).count(),
             imports: Vec::new(),
             exports: Vec::new(),
+            re_exports: Vec::new(),
+            dynamic_imports: Vec::new(),
         };
         
         // Imports come directly from AST parsing
         for import in result.imports {
-            let source_path = self.resolve_import(&import.source, file);
+            let source_path = if import.relative_level > 0 {
+                self.resolve_python_relative_import(&import.source, import.relative_level, file)
+            } else {
+                self.resolve_import(&import.source, file)
+            };
             graph.imports.push(ImportEdge {
                 source: source_path,
+                specifier: format!("{}{}", ".".repeat(import.relative_level), import.source),
                 symbols: import.named,
+                namespace: import.namespace.is_some(),
                 line: import.range.start.line,
             });
         }
         
+        // import('./x') and require('./x'); computed paths keep their static prefix
+        for call in &result.calls {
+            if call.receiver.is_some() || !matches!(call.callee.as_str(), "import" | "require") {
+                continue;
+            }
+            let Some(path) = call.string_arg.as_deref() else {
+                continue;
+            };
+            let prefix = path.split("${").next().unwrap_or(path);
+            if prefix.is_empty() {
+                continue;
+            }
+            graph.dynamic_imports.push(DynamicImportEdge {
+                target: self.resolve_import(prefix, file),
+                exact: prefix.len() == path.len(),
+                line: call.range.start.line,
+            });
+        }
+        
         // Exports come directly from AST parsing
         for export in result.exports {
+            if let Some(from) = &export.from_source {
+                graph.re_exports.push(ReExportEdge {
+                    source: self.resolve_import(from, file),
+                    original: export.original_name.unwrap_or_else(|| export.name.clone()),
+                    name: export.name,
+                    line: export.range.start.line,
+                });
+                continue;
+            }
             graph.exports.push(ExportNode {
                 name: export.name,
                 line: export.range.start.line,
@@ -130,8 +201,41 @@ impl CouplingAnalyzer {
         let from_dir = Path::new(from_file).parent().unwrap_or(Path::new(""));
         let resolved = from_dir.join(source);
         
-        // Normalize path
-        resolved.to_string_lossy().to_string()
+        // Normalize path: `src/ui/../db` -> `src/db`, so it lands in its module
+        let mut normalized = PathBuf::new();
+        for component in resolved.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir if matches!(normalized.components().next_back(), Some(Component::Normal(_))) => {
+                    normalized.pop();
+                }
+                other => normalized.push(other),
+            }
+        }
+        normalized.to_string_lossy().to_string()
+    }
+    
+    /// Resolve `from ..pkg.mod import x` against the importing file's package
+    ///
+    /// One dot is the file's own package, every extra dot goes up a level.
+    /// `from . import x` (no module) resolves to the package's `__init__.py`.
+    fn resolve_python_relative_import(&self, module: &str, level: usize, from_file: &str) -> String {
+        let mut package = Path::new(from_file).parent().unwrap_or(Path::new("")).to_path_buf();
+        for _ in 1..level {
+            package = package.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        }
+        
+        if module.is_empty() {
+            return package.join("__init__.py").to_string_lossy().to_string();
+        }
+        
+        let target = module.split('.').fold(package, |path, part| path.join(part));
+        let package_init = target.join("__init__.py");
+        if package_init.exists() {
+            return package_init.to_string_lossy().to_string();
+        }
+        
+        target.with_extension("py").to_string_lossy().to_string()
     }
     
     fn build_module_map(&self, file_graphs: &HashMap<String, FileGraph>) -> HashMap<String, Vec<String>> {
@@ -210,9 +314,28 @@ impl CouplingAnalyzer {
                 0.0
             };
             
-            // Abstractness: simplified - count interfaces/abstract classes
-            // For now, use 0.0 as we'd need deeper analysis
-            let abstractness = 0.0;
+            // Abstractness: abstract types / total types, overall and per language
+            let mut by_language: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+            for graph in files.iter().filter_map(|f| file_graphs.get(f)) {
+                let (file_count, types, abstract_types) = by_language.entry(graph.language.as_str()).or_default();
+                *file_count += 1;
+                *types += graph.types;
+                *abstract_types += graph.abstract_types;
+            }
+            let ratio = |abstract_types: usize, types: usize| if types > 0 { abstract_types as f32 / types as f32 } else { 0.0 };
+            let languages: Vec<LanguageMetrics> = by_language.into_iter()
+                .map(|(language, (files, types, abstract_types))| LanguageMetrics {
+                    language: language.to_string(),
+                    files,
+                    types,
+                    abstract_types,
+                    abstractness: ratio(abstract_types, types),
+                })
+                .collect();
+            let abstractness = ratio(
+                languages.iter().map(|l| l.abstract_types).sum(),
+                languages.iter().map(|l| l.types).sum(),
+            );
             
             // Distance from main sequence
             let distance = (abstractness + instability - 1.0).abs();
@@ -225,6 +348,7 @@ impl CouplingAnalyzer {
                 abstractness,
                 distance,
                 files: files.clone(),
+                languages,
             });
         }
         
@@ -333,54 +457,84 @@ impl CouplingAnalyzer {
         rec_stack.remove(node);
     }
     
-    fn find_hotspots(&self, modules: &[ModuleMetrics]) -> Vec<CouplingHotspot> {
-        modules.iter()
-            .filter(|m| m.ca + m.ce >= 3)
-            .take(10)
-            .map(|m| CouplingHotspot {
-                module: m.path.clone(),
-                total_coupling: m.ca + m.ce,
-                incoming: Vec::new(), // Would need to track these during analysis
-                outgoing: Vec::new(),
-            })
-            .collect()
-    }
-    
-    fn find_unused_exports(&self, file_graphs: &HashMap<String, FileGraph>) -> Vec<UnusedExport> {
-        // Build set of all imported symbols
-        let mut imported_symbols: HashSet<(String, String)> = HashSet::new();
+    /// Rank modules by weighted coupling: each edge counts once per file
+    /// importing across it, so a module imported by 40 files outranks one
+    /// imported by a single file
+    fn find_hotspots(
+        &self,
+        file_graphs: &HashMap<String, FileGraph>,
+        module_map: &HashMap<String, Vec<String>>,
+    ) -> Vec<CouplingHotspot> {
+        let mut edges: BTreeMap<(String, String), EdgeStats> = BTreeMap::new();
+        // module -> symbol -> files in other modules importing it
+        let mut symbol_imports: HashMap<String, HashMap<&str, HashSet<&str>>> = HashMap::new();
         
-        for graph in file_graphs.values() {
+        for (file, graph) in file_graphs {
+            let from = module_of(file);
             for import in &graph.imports {
+                let to = module_of(&import.source);
+                if to == from {
+                    continue;
+                }
+                let (files, symbols) = edges.entry((from.clone(), to.clone())).or_default();
+                files.insert(file);
+                symbols.extend(import.symbols.iter().map(String::as_str));
+                let imported = symbol_imports.entry(to).or_default();
                 for symbol in &import.symbols {
-                    imported_symbols.insert((import.source.clone(), symbol.clone()));
+                    imported.entry(symbol).or_default().insert(file);
                 }
             }
         }
         
-        // Find exports that are never imported
-        let mut unused = Vec::new();
-        
-        for (file, graph) in file_graphs {
-            for export in &graph.exports {
-                // Check if this export is imported anywhere
-                let is_used = imported_symbols.iter()
-                    .any(|(source, symbol)| {
-                        source.contains(&graph.path) && symbol == &export.name
-                    });
-                
-                if !is_used && !export.is_default {
-                    unused.push(UnusedExport {
-                        name: export.name.clone(),
-                        file: file.clone(),
-                        line: export.line,
-                        export_type: "unknown".to_string(),
-                    });
+        let mut hotspots: Vec<CouplingHotspot> = module_map.keys()
+            .filter_map(|module| {
+                let mut edge_weights: Vec<EdgeWeight> = edges.iter()
+                    .filter_map(|((from, to), (files, symbols))| {
+                        let (other, direction) = if from == module {
+                            (to, EdgeDirection::Outgoing)
+                        } else if to == module {
+                            (from, EdgeDirection::Incoming)
+                        } else {
+                            return None;
+                        };
+                        Some(EdgeWeight {
+                            module: other.clone(),
+                            direction,
+                            file_count: files.len(),
+                            symbol_count: symbols.len(),
+                        })
+                    })
+                    .collect();
+                let total_coupling: usize = edge_weights.iter().map(|w| w.file_count).sum();
+                if total_coupling < 3 {
+                    return None;
                 }
-            }
-        }
+                edge_weights.sort_by_key(|w| std::cmp::Reverse((w.file_count, w.symbol_count)));
+                
+                let modules = |direction: EdgeDirection| edge_weights.iter()
+                    .filter(|w| w.direction == direction)
+                    .map(|w| w.module.clone())
+                    .collect();
+                let mut hot_symbols: Vec<HotSymbol> = symbol_imports.get(module).into_iter().flatten()
+                    .map(|(name, files)| HotSymbol { name: name.to_string(), file_count: files.len() })
+                    .collect();
+                hot_symbols.sort_by(|a, b| b.file_count.cmp(&a.file_count).then_with(|| a.name.cmp(&b.name)));
+                hot_symbols.truncate(MAX_HOT_SYMBOLS);
+                
+                Some(CouplingHotspot {
+                    module: module.clone(),
+                    total_coupling,
+                    incoming: modules(EdgeDirection::Incoming),
+                    outgoing: modules(EdgeDirection::Outgoing),
+                    edge_weights,
+                    hot_symbols,
+                })
+            })
+            .collect();
         
-        unused
+        hotspots.sort_by(|a, b| b.total_coupling.cmp(&a.total_coupling).then_with(|| a.module.cmp(&b.module)));
+        hotspots.truncate(MAX_HOTSPOTS);
+        hotspots
     }
     
     fn calculate_health_score(&self, modules: &[ModuleMetrics], cycles: &[DependencyCycle]) -> f32 {
@@ -425,4 +579,127 @@ mod tests {
         // Just verify it creates without panic
         assert!(true);
     }
+    
+    fn write_python_package() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("app/api")).unwrap();
+        std::fs::create_dir_all(root.join("app/models")).unwrap();
+        std::fs::write(root.join("app/__init__.py"), "").unwrap();
+        std::fs::write(root.join("app/models/__init__.py"), "class User:\n    pass\n").unwrap();
+        std::fs::write(root.join("app/settings.py"), "DEBUG = True\n").unwrap();
+        std::fs::write(root.join("app/api/__init__.py"), "").unwrap();
+        std::fs::write(root.join("app/api/helpers.py"), "def paginate():\n    pass\n").unwrap();
+        std::fs::write(
+            root.join("app/api/views.py"),
+            "import numpy as np\nfrom ..models import User\nfrom ..settings import DEBUG\nfrom . import helpers\nfrom .helpers import paginate\n",
+        ).unwrap();
+        dir
+    }
+    
+    #[test]
+    fn test_python_relative_imports_resolve_to_module_path() {
+        let dir = write_python_package();
+        let root = dir.path();
+        let views = root.join("app/api/views.py").to_string_lossy().to_string();
+        
+        let mut analyzer = CouplingAnalyzer::new();
+        let graph = analyzer.build_file_graph_from_ast(&views).unwrap();
+        let sources: Vec<&str> = graph.imports.iter().map(|i| i.source.as_str()).collect();
+        
+        let expected = |rel: &str| root.join(rel).to_string_lossy().to_string();
+        assert_eq!(sources, vec![
+            "numpy".to_string(),
+            expected("app/models/__init__.py"),
+            expected("app/settings.py"),
+            expected("app/api/__init__.py"),
+            expected("app/api/helpers.py"),
+        ]);
+    }
+    
+    #[test]
+    fn test_python_relative_imports_couple_packages() {
+        let dir = write_python_package();
+        let root = dir.path();
+        let files: Vec<String> = ["app/__init__.py", "app/settings.py", "app/models/__init__.py", "app/api/views.py", "app/api/helpers.py"]
+            .iter()
+            .map(|f| root.join(f).to_string_lossy().to_string())
+            .collect();
+        
+        let mut analyzer = CouplingAnalyzer::new();
+        let result = analyzer.analyze(&files);
+        
+        let api = root.join("app/api").to_string_lossy().to_string();
+        let app = root.join("app").to_string_lossy().to_string();
+        let models = root.join("app/models").to_string_lossy().to_string();
+        
+        let api_metrics = result.modules.iter().find(|m| m.path == api).unwrap();
+        // app (settings) and app/models, plus the external numpy import
+        assert_eq!(api_metrics.ce, 3);
+        let models_metrics = result.modules.iter().find(|m| m.path == models).unwrap();
+        assert_eq!(models_metrics.ca, 1);
+        let app_metrics = result.modules.iter().find(|m| m.path == app).unwrap();
+        assert_eq!(app_metrics.ca, 1);
+    }
+    
+    #[test]
+    fn test_abstractness_of_interface_module() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/contracts")).unwrap();
+        std::fs::create_dir_all(root.join("src/services")).unwrap();
+        std::fs::write(root.join("src/contracts/repo.ts"), "export interface UserRepo { find(id: string): User }\nexport interface Clock { now(): Date }\n").unwrap();
+        std::fs::write(root.join("src/contracts/IMailer.cs"), "public interface IMailer { void Send(string to); }\n").unwrap();
+        std::fs::write(root.join("src/services/user.ts"), "import { UserRepo } from '../contracts/repo';\nexport class UserService {}\n").unwrap();
+        let files: Vec<String> = ["src/contracts/repo.ts", "src/contracts/IMailer.cs", "src/services/user.ts"].iter()
+            .map(|f| root.join(f).to_string_lossy().to_string())
+            .collect();
+        
+        let result = CouplingAnalyzer::new().analyze(&files);
+        let contracts = root.join("src/contracts").to_string_lossy().to_string();
+        let services = root.join("src/services").to_string_lossy().to_string();
+        
+        let metrics = result.modules.iter().find(|m| m.path == contracts).unwrap();
+        assert_eq!(metrics.abstractness, 1.0);
+        // Stable (I = 0) and fully abstract: on the main sequence
+        assert_eq!(metrics.instability, 0.0);
+        assert_eq!(metrics.distance, 0.0);
+        let languages: Vec<(&str, usize, usize)> = metrics.languages.iter()
+            .map(|l| (l.language.as_str(), l.types, l.abstract_types))
+            .collect();
+        assert_eq!(languages, vec![("csharp", 1, 1), ("typescript", 2, 2)]);
+        
+        let metrics = result.modules.iter().find(|m| m.path == services).unwrap();
+        assert_eq!((metrics.abstractness, metrics.instability, metrics.distance), (0.0, 1.0, 0.0));
+    }
+    
+    #[test]
+    fn test_hotspots_weighted_by_importing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/core")).unwrap();
+        std::fs::create_dir_all(root.join("src/features")).unwrap();
+        std::fs::write(root.join("src/core/util.ts"), "export function format() {}\nexport function parse() {}\n").unwrap();
+        let mut files = vec![root.join("src/core/util.ts")];
+        for (i, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            let path = root.join(format!("src/features/{}.ts", name));
+            let import = if i == 0 { "import { format, parse } from '../core/util';\n" } else { "import { format } from '../core/util';\n" };
+            std::fs::write(&path, import).unwrap();
+            files.push(path);
+        }
+        let files: Vec<String> = files.iter().map(|f| f.to_string_lossy().to_string()).collect();
+        
+        let result = CouplingAnalyzer::new().analyze(&files);
+        let core = root.join("src/core").to_string_lossy().to_string();
+        let features = root.join("src/features").to_string_lossy().to_string();
+        
+        // One importing module, but four importing files
+        let hotspot = result.hotspots.iter().find(|h| h.module == core).unwrap();
+        assert_eq!(hotspot.total_coupling, 4);
+        assert_eq!(hotspot.incoming, vec![features.clone()]);
+        let weight = &hotspot.edge_weights[0];
+        assert_eq!((weight.module.as_str(), weight.direction, weight.file_count, weight.symbol_count), (features.as_str(), EdgeDirection::Incoming, 4, 2));
+        let symbols: Vec<(&str, usize)> = hotspot.hot_symbols.iter().map(|s| (s.name.as_str(), s.file_count)).collect();
+        assert_eq!(symbols, vec![("format", 4), ("parse", 1)]);
+    }
 }
@@ -0,0 +1,211 @@
+//! Coupling diff - How coupling changed between two analyses
+//!
+//! Compares an older and a newer `CouplingAnalysisResult` for CI gating:
+//! per-module metric deltas, cycles introduced and resolved, and the change
+//! in health score. Deltas smaller than the tolerance are dropped, and output
+//! is sorted (modules by path, cycles by their modules) so two runs over the
+//! same results print the same report.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{CouplingAnalysisResult, DependencyCycle, ModuleMetrics};
+
+/// Metric deltas smaller than this are ignored by `CouplingAnalysisResult::diff`
+pub const DEFAULT_COUPLING_TOLERANCE: f32 = 0.01;
+
+/// How a module changed between two analyses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Metric deltas for one module (new minus old; a missing side counts as 0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDelta {
+    pub path: String,
+    pub change: ModuleChange,
+    pub ca: i64,
+    pub ce: i64,
+    pub instability: f32,
+    pub distance: f32,
+}
+
+/// Difference between two coupling analyses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CouplingDiff {
+    /// Modules whose metrics moved by at least the tolerance, sorted by path
+    pub modules: Vec<ModuleDelta>,
+    /// Cycles only in the new result
+    pub introduced_cycles: Vec<DependencyCycle>,
+    /// Cycles only in the old result
+    pub resolved_cycles: Vec<DependencyCycle>,
+    /// New health score minus old (0 within the tolerance)
+    pub health_score_delta: f32,
+}
+
+impl CouplingDiff {
+    /// Whether coupling got worse: a new cycle or a lower health score
+    pub fn is_worse(&self) -> bool {
+        !self.introduced_cycles.is_empty() || self.health_score_delta < 0.0
+    }
+}
+
+impl CouplingAnalysisResult {
+    /// Compare two analyses, ignoring deltas below `DEFAULT_COUPLING_TOLERANCE`
+    pub fn diff(old: &CouplingAnalysisResult, new: &CouplingAnalysisResult) -> CouplingDiff {
+        Self::diff_with_tolerance(old, new, DEFAULT_COUPLING_TOLERANCE)
+    }
+
+    /// Compare two analyses, ignoring metric and score deltas below `tolerance`
+    pub fn diff_with_tolerance(old: &CouplingAnalysisResult, new: &CouplingAnalysisResult, tolerance: f32) -> CouplingDiff {
+        let significant = |delta: f32| if delta.abs() < tolerance { 0.0 } else { delta };
+
+        let old_modules: BTreeMap<&str, &ModuleMetrics> = old.modules.iter().map(|m| (m.path.as_str(), m)).collect();
+        let new_modules: BTreeMap<&str, &ModuleMetrics> = new.modules.iter().map(|m| (m.path.as_str(), m)).collect();
+        let paths: BTreeSet<&str> = old_modules.keys().chain(new_modules.keys()).copied().collect();
+
+        let modules = paths.into_iter()
+            .filter_map(|path| {
+                let (before, after) = (old_modules.get(path), new_modules.get(path));
+                let change = match (before, after) {
+                    (None, _) => ModuleChange::Added,
+                    (_, None) => ModuleChange::Removed,
+                    _ => ModuleChange::Changed,
+                };
+                let metric = |m: Option<&&ModuleMetrics>, f: fn(&ModuleMetrics) -> f32| m.map_or(0.0, |m| f(m));
+                let count = |m: Option<&&ModuleMetrics>, f: fn(&ModuleMetrics) -> usize| m.map_or(0, |m| f(m) as i64);
+                let delta = ModuleDelta {
+                    path: path.to_string(),
+                    change,
+                    ca: count(after, |m| m.ca) - count(before, |m| m.ca),
+                    ce: count(after, |m| m.ce) - count(before, |m| m.ce),
+                    instability: significant(metric(after, |m| m.instability) - metric(before, |m| m.instability)),
+                    distance: significant(metric(after, |m| m.distance) - metric(before, |m| m.distance)),
+                };
+                let moved = delta.ca != 0 || delta.ce != 0 || delta.instability != 0.0 || delta.distance != 0.0;
+                (change != ModuleChange::Changed || moved).then_some(delta)
+            })
+            .collect();
+
+        CouplingDiff {
+            modules,
+            introduced_cycles: cycles_missing_from(&new.cycles, &old.cycles),
+            resolved_cycles: cycles_missing_from(&old.cycles, &new.cycles),
+            health_score_delta: significant(new.health_score - old.health_score),
+        }
+    }
+}
+
+/// Cycles in `cycles` that `other` doesn't have, sorted by their modules
+fn cycles_missing_from(cycles: &[DependencyCycle], other: &[DependencyCycle]) -> Vec<DependencyCycle> {
+    let known: BTreeSet<Vec<String>> = other.iter().map(cycle_key).collect();
+    let mut missing: BTreeMap<Vec<String>, DependencyCycle> = BTreeMap::new();
+    for cycle in cycles {
+        let key = cycle_key(cycle);
+        if !known.contains(&key) {
+            missing.entry(key).or_insert_with(|| cycle.clone());
+        }
+    }
+    missing.into_values().collect()
+}
+
+/// A cycle's modules rotated to start at the smallest, so the same cycle
+/// found from a different starting module compares equal
+fn cycle_key(cycle: &DependencyCycle) -> Vec<String> {
+    let start = cycle.modules.iter()
+        .enumerate()
+        .min_by_key(|(_, module)| module.as_str())
+        .map_or(0, |(i, _)| i);
+    let mut key = cycle.modules.clone();
+    key.rotate_left(start);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coupling::CycleSeverity;
+
+    fn module(path: &str, ca: usize, ce: usize, instability: f32) -> ModuleMetrics {
+        ModuleMetrics {
+            path: path.to_string(),
+            ca,
+            ce,
+            instability,
+            abstractness: 0.0,
+            distance: (instability - 1.0).abs(),
+            files: vec![format!("{}/index.ts", path)],
+            languages: Vec::new(),
+        }
+    }
+
+    fn cycle(modules: &[&str]) -> DependencyCycle {
+        DependencyCycle {
+            modules: modules.iter().map(|m| m.to_string()).collect(),
+            severity: CycleSeverity::Info,
+            files_affected: modules.len(),
+        }
+    }
+
+    fn result(modules: Vec<ModuleMetrics>, cycles: Vec<DependencyCycle>, health_score: f32) -> CouplingAnalysisResult {
+        CouplingAnalysisResult {
+            modules,
+            cycles,
+            hotspots: Vec::new(),
+            unused_exports: Vec::new(),
+            health_score,
+            files_analyzed: 0,
+            duration_ms: 0,
+            layers: Vec::new(),
+            layer_violations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_coupling_diff() {
+        let old = result(
+            vec![module("src/api", 2, 3, 0.6), module("src/db", 4, 0, 0.0), module("src/legacy", 1, 1, 0.5)],
+            vec![cycle(&["src/api", "src/auth"]), cycle(&["src/legacy", "src/db"])],
+            90.0,
+        );
+        let new = result(
+            vec![module("src/db", 5, 0, 0.004), module("src/api", 2, 3, 0.605), module("src/billing", 0, 2, 1.0)],
+            vec![cycle(&["src/auth", "src/api"]), cycle(&["src/billing", "src/db", "src/api"])],
+            84.5,
+        );
+
+        let diff = CouplingAnalysisResult::diff(&old, &new);
+        let modules: Vec<(&str, ModuleChange, i64, f32)> = diff.modules.iter()
+            .map(|m| (m.path.as_str(), m.change, m.ca, m.instability))
+            .collect();
+        assert_eq!(modules, vec![
+            ("src/billing", ModuleChange::Added, 0, 1.0),
+            ("src/db", ModuleChange::Changed, 1, 0.0),
+            ("src/legacy", ModuleChange::Removed, -1, -0.5),
+        ]);
+
+        // The auth cycle is the same one found from another module
+        assert_eq!(diff.introduced_cycles.len(), 1);
+        assert_eq!(diff.introduced_cycles[0].modules, vec!["src/billing", "src/db", "src/api"]);
+        assert_eq!(diff.resolved_cycles[0].modules, vec!["src/legacy", "src/db"]);
+        assert_eq!(diff.health_score_delta, -5.5);
+        assert!(diff.is_worse());
+
+        let same = CouplingAnalysisResult::diff_with_tolerance(&old, &old, 0.5);
+        assert!(same.modules.is_empty() && same.introduced_cycles.is_empty() && !same.is_worse());
+
+        // Results saved from JavaScript are camelCase
+        let saved: CouplingAnalysisResult = serde_json::from_str(r#"{
+            "modules": [], "healthScore": 91.0,
+            "cycles": [{ "modules": ["src/db", "src/legacy"], "severity": "warning", "filesAffected": 2 }]
+        }"#).unwrap();
+        let from_saved = CouplingAnalysisResult::diff(&saved, &old);
+        assert_eq!(from_saved.modules.len(), 3);
+        assert!(from_saved.resolved_cycles.is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+//! Layer inference - Layered architecture from the module import graph
+//!
+//! Cycles are broken first: while the graph has a strongly connected
+//! component, its lightest edge (fewest importing files, then the smallest
+//! `(from, to)` pair) is set aside, on the assumption that the heavier
+//! direction is the intended one. Modules are then layered by the longest
+//! import path below them, and the set-aside edges that point to the same
+//! or a higher layer are reported as upward imports.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use super::rules::module_edges;
+use super::types::*;
+
+type Edge<'a> = (&'a str, &'a str);
+
+/// Layer of every module, top layer first, and the imports going upward
+pub(crate) fn infer_layers(
+    file_graphs: &HashMap<String, FileGraph>,
+    module_map: &HashMap<String, Vec<String>>,
+) -> (Vec<ModuleLayer>, Vec<LayerViolation>) {
+    let edges: BTreeMap<(String, String), Vec<(&str, &ImportEdge)>> = module_edges(file_graphs, module_map)
+        .into_iter()
+        .filter(|((_, to), _)| module_map.contains_key(to))
+        .collect();
+    let weights: HashMap<Edge, usize> = edges.iter()
+        .map(|((from, to), imports)| {
+            let files: HashSet<&str> = imports.iter().map(|(file, _)| *file).collect();
+            ((from.as_str(), to.as_str()), files.len())
+        })
+        .collect();
+    let modules: BTreeSet<&str> = module_map.keys().map(String::as_str).collect();
+
+    let mut kept: BTreeSet<Edge> = weights.keys().copied().collect();
+    loop {
+        let components = cyclic_components(&modules, &kept);
+        if components.is_empty() {
+            break;
+        }
+        for component in components {
+            let lightest = kept.iter()
+                .filter(|(from, to)| component.contains(from) && component.contains(to))
+                .min_by_key(|&&edge| (weights[&edge], edge))
+                .copied();
+            if let Some(edge) = lightest {
+                kept.remove(&edge);
+            }
+        }
+    }
+
+    let mut layer_of: HashMap<&str, usize> = HashMap::new();
+    for module in &modules {
+        assign_layer(module, &kept, &mut layer_of);
+    }
+
+    let mut layers: Vec<ModuleLayer> = modules.iter()
+        .map(|module| ModuleLayer { module: module.to_string(), layer: layer_of[module] })
+        .collect();
+    layers.sort_by(|a, b| b.layer.cmp(&a.layer).then_with(|| a.module.cmp(&b.module)));
+
+    let violations = edges.iter()
+        .filter(|((from, to), _)| !kept.contains(&(from.as_str(), to.as_str())))
+        .filter(|((from, to), _)| layer_of[from.as_str()] <= layer_of[to.as_str()])
+        .map(|((from, to), imports)| {
+            let mut imports: Vec<ImportEvidence> = imports.iter()
+                .map(|(file, import)| ImportEvidence {
+                    file: file.to_string(),
+                    line: import.line,
+                    specifier: import.specifier.clone(),
+                })
+                .collect();
+            imports.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+            LayerViolation {
+                from_module: from.clone(),
+                to_module: to.clone(),
+                from_layer: layer_of[from.as_str()],
+                to_layer: layer_of[to.as_str()],
+                imports,
+            }
+        })
+        .collect();
+
+    (layers, violations)
+}
+
+/// Longest import path below `module` in the acyclic `edges`
+fn assign_layer<'a>(module: &'a str, edges: &BTreeSet<Edge<'a>>, layer_of: &mut HashMap<&'a str, usize>) -> usize {
+    if let Some(&layer) = layer_of.get(module) {
+        return layer;
+    }
+    let layer = edges.range((module, "")..)
+        .take_while(|(from, _)| *from == module)
+        .map(|&(_, to)| assign_layer(to, edges, layer_of) + 1)
+        .max()
+        .unwrap_or(0);
+    layer_of.insert(module, layer);
+    layer
+}
+
+/// Strongly connected components with more than one module (Tarjan)
+fn cyclic_components<'a>(modules: &BTreeSet<&'a str>, edges: &BTreeSet<Edge<'a>>) -> Vec<BTreeSet<&'a str>> {
+    struct Tarjan<'a, 'e> {
+        edges: &'e BTreeSet<Edge<'a>>,
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: HashSet<&'a str>,
+        components: Vec<BTreeSet<&'a str>>,
+    }
+
+    impl<'a> Tarjan<'a, '_> {
+        fn visit(&mut self, module: &'a str) {
+            let index = self.index.len();
+            self.index.insert(module, index);
+            self.low.insert(module, index);
+            self.stack.push(module);
+            self.on_stack.insert(module);
+
+            let edges = self.edges;
+            for &(_, to) in edges.range((module, "")..).take_while(|(from, _)| *from == module) {
+                if !self.index.contains_key(to) {
+                    self.visit(to);
+                    let low = self.low[module].min(self.low[to]);
+                    self.low.insert(module, low);
+                } else if self.on_stack.contains(to) {
+                    let low = self.low[module].min(self.index[to]);
+                    self.low.insert(module, low);
+                }
+            }
+
+            if self.low[module] == self.index[module] {
+                let mut component = BTreeSet::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.insert(member);
+                    if member == module {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for &module in modules {
+        if !tarjan.index.contains_key(module) {
+            tarjan.visit(module);
+        }
+    }
+    tarjan.components
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coupling::*;
+
+    #[test]
+    fn test_infer_layers() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for sub in ["controllers", "services", "repositories", "models"] {
+            std::fs::create_dir_all(root.join("src").join(sub)).unwrap();
+        }
+        let files = [
+            ("src/controllers/user.ts", "import { UserService } from '../services/user';\n"),
+            ("src/services/user.ts", "import { UserRepo } from '../repositories/user';\nimport { User } from '../models/user';\n"),
+            ("src/services/format.ts", "import { User } from '../models/user';\nexport function formatName() {}\n"),
+            ("src/repositories/user.ts", "import { User } from '../models/user';\n"),
+            ("src/models/user.ts", "// upward\nimport { formatName } from '../services/format';\nexport class User {}\n"),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let result = CouplingAnalyzer::new().analyze(&paths);
+        let module = |m: &str| root.join("src").join(m).to_string_lossy().to_string();
+        let layers: Vec<(String, usize)> = result.layers.iter().map(|l| (l.module.clone(), l.layer)).collect();
+        assert_eq!(layers, vec![
+            (module("controllers"), 3),
+            (module("services"), 2),
+            (module("repositories"), 1),
+            (module("models"), 0),
+        ]);
+
+        assert_eq!(result.layer_violations.len(), 1);
+        let violation = &result.layer_violations[0];
+        assert_eq!((violation.from_module.clone(), violation.to_module.clone()), (module("models"), module("services")));
+        assert_eq!((violation.from_layer, violation.to_layer), (0, 2));
+        let imports: Vec<(&str, u32)> = violation.imports.iter().map(|i| (i.file.as_str(), i.line)).collect();
+        assert_eq!(imports, vec![(paths[4].as_str(), 1)]);
+    }
+}
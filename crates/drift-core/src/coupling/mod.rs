@@ -1,10 +1,17 @@
 //! Coupling analysis module
 //!
 //! Analyzes module dependencies, detects cycles, and calculates
-//! Robert C. Martin coupling metrics (Ca, Ce, Instability, Abstractness).
+//! Robert C. Martin coupling metrics (Ca, Ce, Instability, Abstractness),
+//! and checks module dependency edges against architectural rules.
 
 mod types;
 mod analyzer;
+mod diff;
+mod layers;
+mod rules;
+mod usage;
 
 pub use types::*;
 pub use analyzer::CouplingAnalyzer;
+pub use rules::DependencyRules;
+pub use diff::{CouplingDiff, ModuleChange, ModuleDelta, DEFAULT_COUPLING_TOLERANCE};
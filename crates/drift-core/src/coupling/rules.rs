@@ -0,0 +1,193 @@
+//! Dependency rules - Architectural constraints on module dependency edges
+//!
+//! Every import between two modules (or from a module to a package) is an
+//! edge, checked against the rules:
+//! - an edge matching a `forbid` rule is a violation unless an `allow` rule
+//!   matches it too
+//! - a module matched by the `from_glob` of some `allow` rule may only
+//!   depend on what those rules' `to_glob`s match
+//!
+//! Violations carry the imports creating the edge and, when the edge closes
+//! a dependency cycle, that cycle.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher};
+
+use super::types::*;
+
+struct CompiledRule {
+    rule: DependencyRule,
+    from: GlobMatcher,
+    to: GlobMatcher,
+}
+
+/// Compiled `DependencyRule`s
+pub struct DependencyRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl DependencyRules {
+    pub fn new(rules: &[DependencyRule]) -> Result<Self, String> {
+        let glob = |pattern: &str| {
+            Glob::new(pattern)
+                .map(|g| g.compile_matcher())
+                .map_err(|e| format!("Dependency rule: invalid glob '{}': {}", pattern, e))
+        };
+        let rules = rules.iter()
+            .map(|rule| Ok(CompiledRule {
+                rule: rule.clone(),
+                from: glob(&rule.from_glob)?,
+                to: glob(&rule.to_glob)?,
+            }))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Violations among the imports of `file_graphs`, sorted by edge
+    pub fn check(
+        &self,
+        file_graphs: &HashMap<String, FileGraph>,
+        module_map: &HashMap<String, Vec<String>>,
+        cycles: &[DependencyCycle],
+    ) -> Vec<DependencyViolation> {
+        module_edges(file_graphs, module_map).into_iter()
+            .filter_map(|((from, to), mut imports)| {
+                imports.sort_by(|a, b| (a.0, a.1.line).cmp(&(b.0, b.1.line)));
+                let broken: Vec<(&CompiledRule, &str, &ImportEdge)> = imports.into_iter()
+                    .filter_map(|(file, import)| Some((self.broken_rule(&from, &to, file, import)?, file, import)))
+                    .collect();
+                let rule = broken.first()?.0.rule.clone();
+                Some(DependencyViolation {
+                    cycle: cycles.iter().find(|c| closes_edge(c, &from, &to)).cloned(),
+                    from_module: from,
+                    to_module: to,
+                    rule,
+                    imports: broken.into_iter()
+                        .map(|(_, file, import)| ImportEvidence {
+                            file: file.to_string(),
+                            line: import.line,
+                            specifier: import.specifier.clone(),
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Rule an import from `file` breaks, if any
+    fn broken_rule(&self, from: &str, to: &str, file: &str, import: &ImportEdge) -> Option<&CompiledRule> {
+        let matches_from = |rule: &CompiledRule| rule.from.is_match(from) || rule.from.is_match(file);
+        let matches_to = |rule: &CompiledRule| rule.to.is_match(to) || rule.to.is_match(&import.source);
+
+        let mut allows = self.rules.iter()
+            .filter(|r| r.rule.kind == DependencyRuleKind::Allow && matches_from(r))
+            .peekable();
+        let first_allow = allows.peek().copied();
+        if allows.any(matches_to) {
+            return None;
+        }
+        self.rules.iter()
+            .find(|r| r.rule.kind == DependencyRuleKind::Forbid && matches_from(r) && matches_to(r))
+            .or(first_allow)
+    }
+}
+
+/// Imports creating each module dependency edge, keyed by (from, to)
+///
+/// The target of an import outside the analyzed modules is its source
+/// (the package name for packages).
+pub(crate) fn module_edges<'a>(
+    file_graphs: &'a HashMap<String, FileGraph>,
+    module_map: &HashMap<String, Vec<String>>,
+) -> BTreeMap<(String, String), Vec<(&'a str, &'a ImportEdge)>> {
+    let mut edges: BTreeMap<(String, String), Vec<(&str, &ImportEdge)>> = BTreeMap::new();
+    for (file, graph) in file_graphs {
+        let from = module_of(file);
+        for import in &graph.imports {
+            let target = module_of(&import.source);
+            let to = if module_map.contains_key(&target) { target } else { import.source.clone() };
+            if to != from {
+                edges.entry((from.clone(), to)).or_default().push((file, import));
+            }
+        }
+    }
+    edges
+}
+
+/// Module (directory) of a file, as `CouplingAnalyzer` groups them
+pub(crate) fn module_of(file: &str) -> String {
+    Path::new(file)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// Whether `from -> to` is one of the cycle's edges
+fn closes_edge(cycle: &DependencyCycle, from: &str, to: &str) -> bool {
+    let modules = &cycle.modules;
+    (0..modules.len()).any(|i| modules[i] == from && modules[(i + 1) % modules.len()] == to)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coupling::*;
+
+    fn rule(from_glob: &str, to_glob: &str, kind: DependencyRuleKind) -> DependencyRule {
+        DependencyRule { from_glob: from_glob.to_string(), to_glob: to_glob.to_string(), kind }
+    }
+
+    #[test]
+    fn test_dependency_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for sub in ["src/ui", "src/persistence", "src/domain"] {
+            std::fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        let files = [
+            ("src/ui/page.ts", "import { save } from '../persistence/db';\nimport { Order } from '../domain/order';\nimport axios from 'axios';\n"),
+            ("src/ui/form.ts", "import { load } from '../persistence/db';\nimport axios from 'axios';\n"),
+            ("src/persistence/db.ts", "import { Order } from '../domain/order';\nexport function save() {}\nexport function load() {}\n"),
+            ("src/domain/order.ts", "import { render } from '../ui/page';\nexport class Order {}\n"),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let rules = vec![
+            rule("**/ui", "**/persistence/**", DependencyRuleKind::Forbid),
+            rule("**/domain/**", "**/domain/**", DependencyRuleKind::Allow),
+            rule("**", "axios", DependencyRuleKind::Forbid),
+            rule("**/ui/form.ts", "axios", DependencyRuleKind::Allow),
+        ];
+        let violations = CouplingAnalyzer::new().check_dependency_rules(&paths, &rules).unwrap();
+        let module = |m: &str| root.join(m).to_string_lossy().to_string();
+        let edges: Vec<(String, String, DependencyRuleKind)> = violations.iter()
+            .map(|v| (v.from_module.clone(), v.to_module.clone(), v.rule.kind))
+            .collect();
+        assert_eq!(edges, vec![
+            (module("src/domain"), module("src/ui"), DependencyRuleKind::Allow),
+            (module("src/ui"), module("src/persistence"), DependencyRuleKind::Forbid),
+            (module("src/ui"), "axios".to_string(), DependencyRuleKind::Forbid),
+        ]);
+
+        // The domain -> ui edge closes ui -> domain
+        assert!(violations[0].cycle.is_some());
+        // form.ts is allowed axios, page.ts isn't
+        assert_eq!(violations[2].imports.len(), 1);
+        assert_eq!(violations[2].imports[0].file, paths[0]);
+        let imports: Vec<(String, u32, &str)> = violations[1].imports.iter()
+            .map(|i| (i.file.clone(), i.line, i.specifier.as_str()))
+            .collect();
+        assert_eq!(imports, vec![
+            (paths[1].clone(), 0, "../persistence/db"),
+            (paths[0].clone(), 0, "../persistence/db"),
+        ]);
+
+        assert!(DependencyRules::new(&[rule("src/[ui", "**", DependencyRuleKind::Forbid)]).is_err());
+    }
+}
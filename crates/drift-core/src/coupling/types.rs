@@ -19,6 +19,28 @@ pub struct ModuleMetrics {
     pub distance: f32,
     /// Files in this module
     pub files: Vec<String>,
+    /// Types and abstractness per language, by language name
+    #[serde(default)]
+    pub languages: Vec<LanguageMetrics>,
+}
+
+/// Types of one language within a module
+///
+/// Abstract types are interfaces, traits, protocols and abstract classes
+/// (Python ABCs included); classes, structs and records are concrete.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageMetrics {
+    /// Language name (`typescript`, `csharp`, ...)
+    pub language: String,
+    /// Files in this language
+    pub files: usize,
+    /// Types declared
+    pub types: usize,
+    /// Abstract types declared
+    #[serde(alias = "abstractTypes")]
+    pub abstract_types: usize,
+    /// Abstractness: abstract types / types
+    pub abstractness: f32,
 }
 
 /// A dependency cycle
@@ -29,6 +51,7 @@ pub struct DependencyCycle {
     /// Severity based on cycle length
     pub severity: CycleSeverity,
     /// Total files affected
+    #[serde(alias = "filesAffected")]
     pub files_affected: usize,
 }
 
@@ -46,12 +69,61 @@ pub enum CycleSeverity {
 pub struct CouplingHotspot {
     /// Module path
     pub module: String,
-    /// Total coupling (Ca + Ce)
+    /// Weighted coupling: importing files summed over incoming and outgoing edges
+    #[serde(alias = "totalCoupling")]
     pub total_coupling: usize,
     /// Incoming dependencies
     pub incoming: Vec<String>,
     /// Outgoing dependencies
     pub outgoing: Vec<String>,
+    /// Weight of each edge, heaviest first
+    #[serde(default, alias = "edgeWeights")]
+    pub edge_weights: Vec<EdgeWeight>,
+    /// Symbols of this module imported by the most files in other modules
+    #[serde(default, alias = "hotSymbols")]
+    pub hot_symbols: Vec<HotSymbol>,
+}
+
+/// Direction of a module dependency edge, seen from a hotspot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeDirection {
+    Incoming,
+    Outgoing,
+}
+
+/// How heavily two modules depend on each other along one edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeWeight {
+    /// Module at the other end of the edge
+    pub module: String,
+    pub direction: EdgeDirection,
+    /// Files importing across the edge
+    #[serde(alias = "fileCount")]
+    pub file_count: usize,
+    /// Distinct symbols imported across the edge
+    #[serde(alias = "symbolCount")]
+    pub symbol_count: usize,
+}
+
+/// A symbol imported from a hotspot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotSymbol {
+    pub name: String,
+    /// Files in other modules importing it
+    #[serde(alias = "fileCount")]
+    pub file_count: usize,
+}
+
+/// Whether an export reported by unused export detection may still be used
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportUsageStatus {
+    /// Never imported, statically or dynamically
+    #[default]
+    Unused,
+    /// Only reachable through a dynamic import whose path isn't known
+    PossiblyUsed,
 }
 
 /// Unused export
@@ -64,10 +136,19 @@ pub struct UnusedExport {
     /// Line number
     pub line: u32,
     /// Export type (function, class, const, etc.)
+    #[serde(alias = "exportType")]
     pub export_type: String,
+    #[serde(default)]
+    pub status: ExportUsageStatus,
+    /// Confidence that the export is unused (0.0 - 1.0)
+    #[serde(default)]
+    pub confidence: f32,
 }
 
 /// Result of coupling analysis
+///
+/// Also reads the camelCase JSON that `analyzeCoupling` returns to
+/// JavaScript, so results saved by either side can be diffed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CouplingAnalysisResult {
     /// Module metrics
@@ -75,15 +156,99 @@ pub struct CouplingAnalysisResult {
     /// Dependency cycles found
     pub cycles: Vec<DependencyCycle>,
     /// Coupling hotspots
+    #[serde(default)]
     pub hotspots: Vec<CouplingHotspot>,
     /// Unused exports
+    #[serde(default, alias = "unusedExports")]
     pub unused_exports: Vec<UnusedExport>,
     /// Overall health score (0-100)
+    #[serde(alias = "healthScore")]
     pub health_score: f32,
     /// Files analyzed
+    #[serde(default, alias = "filesAnalyzed")]
     pub files_analyzed: usize,
     /// Duration in milliseconds
+    #[serde(default, alias = "durationMs")]
     pub duration_ms: u64,
+    /// Inferred layer of each module, top layer first
+    #[serde(default)]
+    pub layers: Vec<ModuleLayer>,
+    /// Imports going upward against the inferred layering
+    #[serde(default, alias = "layerViolations")]
+    pub layer_violations: Vec<LayerViolation>,
+}
+
+/// A module's inferred layer
+///
+/// Layer 0 modules import no other analyzed module; every other module sits
+/// one layer above the highest module it imports (entry points on top,
+/// models at the bottom).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleLayer {
+    pub module: String,
+    pub layer: usize,
+}
+
+/// A module dependency edge going upward against the inferred layering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerViolation {
+    #[serde(alias = "fromModule")]
+    pub from_module: String,
+    #[serde(alias = "toModule")]
+    pub to_module: String,
+    #[serde(alias = "fromLayer")]
+    pub from_layer: usize,
+    #[serde(alias = "toLayer")]
+    pub to_layer: usize,
+    /// Imports that create the edge
+    pub imports: Vec<ImportEvidence>,
+}
+
+/// Whether a dependency rule allows or forbids the edges it matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyRuleKind {
+    Allow,
+    Forbid,
+}
+
+/// An architectural rule on module dependency edges
+///
+/// Globs match a module path or the path of a file in it (`src/ui/**`,
+/// `**/persistence`); for imports of packages outside the analyzed files,
+/// `to_glob` matches the package name (`axios`, `@prisma/*`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyRule {
+    pub from_glob: String,
+    pub to_glob: String,
+    pub kind: DependencyRuleKind,
+}
+
+/// An import that creates a module dependency edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEvidence {
+    /// Importing file
+    pub file: String,
+    /// Line number
+    pub line: u32,
+    /// Import source as written
+    pub specifier: String,
+}
+
+/// A module dependency edge that breaks a rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyViolation {
+    /// Importing module
+    pub from_module: String,
+    /// Imported module, or package name
+    pub to_module: String,
+    /// The forbid rule matched, or for an edge missing from the allow list
+    /// of its module, the first allow rule for that module
+    pub rule: DependencyRule,
+    /// Imports that create the edge
+    pub imports: Vec<ImportEvidence>,
+    /// A dependency cycle the edge is part of
+    pub cycle: Option<DependencyCycle>,
 }
 
 /// Import/export graph for a file
@@ -91,10 +256,20 @@ pub struct CouplingAnalysisResult {
 pub struct FileGraph {
     /// File path
     pub path: String,
+    /// Language name
+    pub language: String,
+    /// Types declared
+    pub types: usize,
+    /// Abstract types declared
+    pub abstract_types: usize,
     /// Imports from other files
     pub imports: Vec<ImportEdge>,
     /// Exports from this file
     pub exports: Vec<ExportNode>,
+    /// Exports forwarded from other files (`export { a } from './x'`)
+    pub re_exports: Vec<ReExportEdge>,
+    /// `import()` and `require()` calls
+    pub dynamic_imports: Vec<DynamicImportEdge>,
 }
 
 /// An import edge
@@ -102,8 +277,37 @@ pub struct FileGraph {
 pub struct ImportEdge {
     /// Source file (where import is from)
     pub source: String,
+    /// Import source as written (`../db`, `axios`)
+    pub specifier: String,
     /// Imported symbols
     pub symbols: Vec<String>,
+    /// Imports the whole module (`import * as ns`)
+    pub namespace: bool,
+    /// Line number
+    pub line: u32,
+}
+
+/// A re-export edge
+#[derive(Debug, Clone)]
+pub struct ReExportEdge {
+    /// Source file (where the export is forwarded from)
+    pub source: String,
+    /// Exported name (`*` for `export * from`)
+    pub name: String,
+    /// Name in the source file (`*` for the whole module)
+    pub original: String,
+    /// Line number
+    pub line: u32,
+}
+
+/// A dynamic import edge
+#[derive(Debug, Clone)]
+pub struct DynamicImportEdge {
+    /// Imported file, or for computed paths the static prefix
+    /// (`./locales/${lang}` -> `src/locales`)
+    pub target: String,
+    /// The whole path is a literal
+    pub exact: bool,
     /// Line number
     pub line: u32,
 }
@@ -0,0 +1,209 @@
+//! Export usage - Which exports are imported anywhere
+//!
+//! Imports resolve to files by path without extension (`./util` ->
+//! `util.ts`, `./lib` -> `lib/index.ts`). Usage follows re-export chains
+//! through barrel files, and namespace imports and dynamic imports of a
+//! literal path use every export of the file. Exports of files that only a
+//! computed dynamic import could load (`import(`./locales/${lang}`)`) are
+//! reported as possibly used.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::types::*;
+
+/// Confidence that an export nothing imports is unused
+const UNUSED_CONFIDENCE: f32 = 0.9;
+
+/// Confidence that an export only a computed dynamic import reaches is unused
+const POSSIBLY_USED_CONFIDENCE: f32 = 0.4;
+
+/// Exports never imported, sorted by file and line
+pub(crate) fn find_unused_exports(file_graphs: &HashMap<String, FileGraph>) -> Vec<UnusedExport> {
+    let mut used = ExportUsage::new(file_graphs);
+    let mut possibly_used = ExportUsage::new(file_graphs);
+
+    for graph in file_graphs.values() {
+        for import in &graph.imports {
+            let Some(file) = used.resolve(&import.source) else {
+                continue;
+            };
+            if import.namespace {
+                used.use_module(file);
+            }
+            for symbol in &import.symbols {
+                used.use_symbol(file, symbol);
+            }
+        }
+        for dynamic in &graph.dynamic_imports {
+            if dynamic.exact {
+                if let Some(file) = used.resolve(&dynamic.target) {
+                    used.use_module(file);
+                }
+            } else {
+                for file in file_graphs.keys().filter(|f| f.starts_with(&dynamic.target)) {
+                    possibly_used.use_module(file);
+                }
+            }
+        }
+    }
+
+    let mut files: Vec<&String> = file_graphs.keys().collect();
+    files.sort();
+    let mut unused = Vec::new();
+    for file in files {
+        // Exported declarations show up both as exports and as exported functions
+        let mut seen = HashSet::new();
+        let mut exports: Vec<&ExportNode> = file_graphs[file].exports.iter()
+            .filter(|e| !e.is_default && seen.insert(e.name.as_str()))
+            .collect();
+        exports.sort_by_key(|e| e.line);
+
+        for export in exports {
+            if used.is_used(file, &export.name) {
+                continue;
+            }
+            let (status, confidence) = if possibly_used.is_used(file, &export.name) {
+                (ExportUsageStatus::PossiblyUsed, POSSIBLY_USED_CONFIDENCE)
+            } else {
+                (ExportUsageStatus::Unused, UNUSED_CONFIDENCE)
+            };
+            unused.push(UnusedExport {
+                name: export.name.clone(),
+                file: file.clone(),
+                line: export.line,
+                export_type: "unknown".to_string(),
+                status,
+                confidence,
+            });
+        }
+    }
+
+    unused
+}
+
+/// Exports used so far, propagated through re-exports
+struct ExportUsage<'a> {
+    file_graphs: &'a HashMap<String, FileGraph>,
+    /// Path without extension, and directory of index files -> file
+    files: HashMap<String, &'a str>,
+    symbols: HashSet<(&'a str, String)>,
+    modules: HashSet<&'a str>,
+}
+
+impl<'a> ExportUsage<'a> {
+    fn new(file_graphs: &'a HashMap<String, FileGraph>) -> Self {
+        let mut paths: Vec<&'a String> = file_graphs.keys().collect();
+        paths.sort();
+        let mut files: HashMap<String, &'a str> = paths.iter()
+            .map(|f| (without_extension(f), f.as_str()))
+            .collect();
+        for file in &paths {
+            let path = Path::new(file.as_str());
+            if matches!(path.file_stem().and_then(|s| s.to_str()), Some("index" | "__init__")) {
+                let dir = path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+                files.entry(dir).or_insert(file.as_str());
+            }
+        }
+        Self {
+            file_graphs,
+            files,
+            symbols: HashSet::new(),
+            modules: HashSet::new(),
+        }
+    }
+
+    /// File an import source resolves to
+    fn resolve(&self, source: &str) -> Option<&'a str> {
+        self.files.get(source)
+            .or_else(|| self.files.get(&without_extension(source)))
+            .copied()
+    }
+
+    fn is_used(&self, file: &str, name: &str) -> bool {
+        self.modules.contains(file) || self.symbols.contains(&(file, name.to_string()))
+    }
+
+    /// Mark `name` of `file` used, and whatever it re-exports
+    fn use_symbol(&mut self, file: &'a str, name: &str) {
+        if !self.symbols.insert((file, name.to_string())) {
+            return;
+        }
+        let file_graphs = self.file_graphs;
+        for re_export in &file_graphs[file].re_exports {
+            let Some(source) = self.resolve(&re_export.source) else {
+                continue;
+            };
+            if re_export.name == "*" {
+                self.use_symbol(source, name);
+            } else if re_export.name == name {
+                if re_export.original == "*" {
+                    self.use_module(source);
+                } else {
+                    self.use_symbol(source, &re_export.original);
+                }
+            }
+        }
+    }
+
+    /// Mark every export of `file` used, including everything it re-exports
+    fn use_module(&mut self, file: &'a str) {
+        if !self.modules.insert(file) {
+            return;
+        }
+        let file_graphs = self.file_graphs;
+        for re_export in &file_graphs[file].re_exports {
+            let Some(source) = self.resolve(&re_export.source) else {
+                continue;
+            };
+            if re_export.original == "*" {
+                self.use_module(source);
+            } else {
+                self.use_symbol(source, &re_export.original);
+            }
+        }
+    }
+}
+
+fn without_extension(path: &str) -> String {
+    Path::new(path).with_extension("").to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coupling::*;
+
+    #[test]
+    fn test_unused_exports_through_barrels_and_dynamic_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/lib")).unwrap();
+        std::fs::create_dir_all(root.join("src/plugins/locales")).unwrap();
+        let files = [
+            ("src/lib/format.ts", "export function formatDate() {}\nexport function formatMoney() {}\nexport function unusedHelper() {}\n"),
+            ("src/lib/index.ts", "export * from './format';\n"),
+            ("src/index.ts", "export * from './lib';\n"),
+            ("src/app.ts", "import { formatDate, formatMoney } from '.';\n"),
+            ("src/plugins/loader.ts", "export async function load(lang) {\n  const settings = require('./config');\n  return import(`./locales/${lang}`);\n}\n"),
+            ("src/plugins/config.ts", "export const settings = {};\n"),
+            ("src/plugins/locales/en.ts", "export const messages = {};\n"),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let result = CouplingAnalyzer::new().analyze(&paths);
+        let unused: Vec<(String, &str, ExportUsageStatus)> = result.unused_exports.iter()
+            .map(|u| (u.file.strip_prefix(root.to_str().unwrap()).unwrap().to_string(), u.name.as_str(), u.status))
+            .collect();
+        assert_eq!(unused, vec![
+            ("/src/lib/format.ts".to_string(), "unusedHelper", ExportUsageStatus::Unused),
+            ("/src/plugins/loader.ts".to_string(), "load", ExportUsageStatus::Unused),
+            ("/src/plugins/locales/en.ts".to_string(), "messages", ExportUsageStatus::PossiblyUsed),
+        ]);
+        assert!(result.unused_exports[2].confidence < result.unused_exports[0].confidence);
+    }
+}
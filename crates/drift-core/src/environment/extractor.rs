@@ -37,6 +37,14 @@ static RUST_ENV_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"std::env::var\(['"]([A-Z_][A-Z0-9_]*)['"]\)|env::var\(['"]([A-Z_][A-Z0-9_]*)['"]\)"#).unwrap()
 });
 
+static RUBY_ENV_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"ENV\[['"]([A-Z_][A-Z0-9_]*)['"]\]|ENV\.fetch\(['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap()
+});
+
+static SWIFT_ENV_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"ProcessInfo\.processInfo\.environment\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap()
+});
+
 static CPP_ENV_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"std::getenv\(['"]([A-Z_][A-Z0-9_]*)['"]\)|getenv\(['"]([A-Z_][A-Z0-9_]*)['"]\)"#).unwrap()
 });
@@ -54,23 +62,27 @@ impl EnvExtractor {
         let regex = match language {
             Language::TypeScript | Language::JavaScript => &*TS_ENV_REGEX,
             Language::Python => &*PY_ENV_REGEX,
-            Language::Java => &*JAVA_ENV_REGEX,
+            Language::Java | Language::Kotlin => &*JAVA_ENV_REGEX,
             Language::CSharp => &*CSHARP_ENV_REGEX,
             Language::Go => &*GO_ENV_REGEX,
             Language::Php => &*PHP_ENV_REGEX,
             Language::Rust => &*RUST_ENV_REGEX,
             Language::Cpp | Language::C => &*CPP_ENV_REGEX,
+            Language::Ruby => &*RUBY_ENV_REGEX,
+            Language::Swift => &*SWIFT_ENV_REGEX,
         };
 
         let access_pattern = match language {
             Language::TypeScript | Language::JavaScript => "process.env",
             Language::Python => "os.environ/os.getenv",
-            Language::Java => "System.getenv",
+            Language::Java | Language::Kotlin => "System.getenv",
             Language::CSharp => "Environment.GetEnvironmentVariable",
             Language::Go => "os.Getenv",
             Language::Php => "getenv/$_ENV",
             Language::Rust => "std::env::var",
             Language::Cpp | Language::C => "std::getenv",
+            Language::Ruby => "ENV",
+            Language::Swift => "ProcessInfo.environment",
         };
 
         let mut accesses = Vec::new();
@@ -130,6 +142,31 @@ impl EnvExtractor {
                     return (true, None); // Has fallback handling
                 }
             }
+            Language::Ruby => {
+                // ENV.fetch('X', 'default') or ENV['X'] || 'default'
+                if line.contains("ENV.fetch") && line.contains(',') {
+                    if let Some(default) = self.extract_second_arg(line) {
+                        return (true, Some(default));
+                    }
+                }
+                if line.contains("||") {
+                    if let Some(default) = self.extract_string_after_operator(line, &["||"]) {
+                        return (true, Some(default));
+                    }
+                }
+            }
+            Language::Kotlin => {
+                // System.getenv("X") ?: "default"
+                if let Some(default) = self.extract_string_after_operator(line, &["?:"]) {
+                    return (true, Some(default));
+                }
+            }
+            Language::Swift => {
+                // ProcessInfo.processInfo.environment["X"] ?? "default"
+                if let Some(default) = self.extract_string_after_operator(line, &["??"]) {
+                    return (true, Some(default));
+                }
+            }
             Language::Rust => {
                 // env::var("X").unwrap_or("default")
                 if line.contains("unwrap_or") || line.contains("unwrap_or_else") {
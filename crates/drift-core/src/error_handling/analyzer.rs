@@ -3,64 +3,148 @@
 //! Uses tree-sitter parsed data to detect error handling patterns.
 //! Analyzes try/catch blocks, error boundaries, and unhandled error paths.
 
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::hierarchy::{construction_sites, error_hierarchy};
+use super::middleware::{find_node_error_handling, ErrorMiddleware, Route};
+use super::promises::find_dangling_promises;
 use super::types::*;
+use super::reach::ReachGraph;
+use super::rust::find_rust_panics;
+use crate::call_graph::CallGraphDb;
 use crate::parsers::{ParserManager, ParseResult, FunctionInfo};
 
+/// Logging calls: `console.warn()`, `logger.error()`, `log.Printf()`, `print()`
+static LOGGING_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"\b(?:console|_?logger|_?log|logging|Log|LOG)\.\w+\s*\(|\b(?:print|println!|eprintln!|captureException|reportError)\s*\("
+).unwrap());
+/// Rethrowing, usually wrapped: `throw new AppError(e)`, `raise ... from e`
+static RETHROW: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:throw|raise|rethrow)\b|\bpanic!?\s*\(").unwrap());
+/// Passing the error on to the next middleware: `next(err)`
+static NEXT_ERROR: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bnext\s*\(\s*\w").unwrap());
+
 /// Error handling analyzer - AST-first
 pub struct ErrorHandlingAnalyzer {
     parser: ParserManager,
+    /// Writes reachable through the call graph, when analyzing with one
+    call_graph: Option<Arc<ReachGraph>>,
 }
 
 impl ErrorHandlingAnalyzer {
     pub fn new() -> Self {
         Self {
             parser: ParserManager::new(),
+            call_graph: None,
         }
     }
     
+    /// Grade gaps by the call graph, whose paths are relative to `root`
+    ///
+    /// A swallowing catch around data writes or external calls is critical;
+    /// one around nothing the call graph knows to write is low. Rust panics
+    /// escalate a level where an entry point reaches them.
+    pub fn with_call_graph(self, root: &Path, db: &CallGraphDb) -> Result<Self, String> {
+        Ok(self.with_reach_graph(Arc::new(ReachGraph::load(root, db)?)))
+    }
+    
+    pub(super) fn with_reach_graph(mut self, graph: Arc<ReachGraph>) -> Self {
+        self.call_graph = Some(graph);
+        self
+    }
+    
     /// Analyze error handling in files
     pub fn analyze(&mut self, files: &[String]) -> ErrorHandlingResult {
         let start = Instant::now();
         
         let mut all_boundaries = Vec::new();
         let mut all_gaps = Vec::new();
-        let mut all_error_types = Vec::new();
+        let mut all_classes = Vec::new();
+        let mut constructions: HashMap<String, usize> = HashMap::new();
+        // NestJS filters by boundary index, and the filters registered globally
+        let mut filters: Vec<(usize, String)> = Vec::new();
+        let mut global_filters: HashSet<String> = HashSet::new();
+        let mut routes: Vec<(String, String, Route)> = Vec::new();
         let mut files_analyzed = 0;
         
         for file in files {
             if let Some(source) = std::fs::read_to_string(file).ok() {
                 if let Some(result) = self.parser.parse_file(file, &source) {
-                    let boundaries = self.extract_boundaries_from_ast(&result, file, &source);
-                    let gaps = self.detect_gaps_from_ast(&result, file, &source);
-                    let error_types = self.extract_error_types_from_ast(&result, file);
+                    let (mut boundaries, swallowed) = self.extract_boundaries_from_ast(&result, file, &source);
+                    let node_handling = find_node_error_handling(&result, &source);
+                    for handler in node_handling.handlers {
+                        if let Some(filter) = handler.filter.clone() {
+                            filters.push((all_boundaries.len() + boundaries.len(), filter));
+                        }
+                        boundaries.push(error_middleware_boundary(file, &source, handler));
+                    }
+                    global_filters.extend(node_handling.global_filters);
+                    for route in node_handling.routes {
+                        let function = route.handler.clone().unwrap_or_else(|| enclosing_function(&result, route.line));
+                        routes.push((file.clone(), function, route));
+                    }
+                    let mut gaps = self.detect_gaps_from_ast(&result, file, &source);
+                    gaps.extend(swallowed);
+                    let classes = self.extract_classes_from_ast(&result, file);
+                    for (name, _) in construction_sites(&result, &source) {
+                        *constructions.entry(name).or_default() += 1;
+                    }
                     
                     all_boundaries.extend(boundaries);
                     all_gaps.extend(gaps);
-                    all_error_types.extend(error_types);
+                    all_classes.extend(classes);
                     files_analyzed += 1;
                 }
             }
         }
         
+        for (index, filter) in filters {
+            if global_filters.contains(&filter) {
+                all_boundaries[index].scope = Some(HandlerScope::Global);
+            }
+        }
+        let has_global_handler = !global_filters.is_empty()
+            || all_boundaries.iter().any(|b| b.scope == Some(HandlerScope::Global));
+        for (file, function, route) in routes {
+            let covered = route.guarded || has_global_handler || all_boundaries.iter().any(|b| {
+                b.file == file && matches!(&b.scope, Some(HandlerScope::Router(router)) if *router == route.router)
+            });
+            if !covered {
+                all_gaps.push(ErrorGap {
+                    file,
+                    line: route.line,
+                    function,
+                    gap_type: GapType::MissingErrorBoundary,
+                    severity: GapSeverity::Medium,
+                    description: format!("Route {} {} has no error handler or try/catch", route.method, route.path),
+                    reachable_writes: Vec::new(),
+                });
+            }
+        }
+        
         ErrorHandlingResult {
             boundaries: all_boundaries,
             gaps: all_gaps,
-            error_types: all_error_types,
+            error_types: error_hierarchy(all_classes, &constructions),
             files_analyzed,
             duration_ms: start.elapsed().as_millis() as u64,
         }
     }
     
-    /// Extract error boundaries from AST
+    /// Extract error boundaries from AST, with a gap for each swallowing catch
     fn extract_boundaries_from_ast(
         &self,
         result: &ParseResult,
         file: &str,
         source: &str,
-    ) -> Vec<ErrorBoundary> {
+    ) -> (Vec<ErrorBoundary>, Vec<ErrorGap>) {
         let mut boundaries = Vec::new();
+        let mut swallowed = Vec::new();
         let lines: Vec<&str> = source.lines().collect();
         
         // First, scan the entire file for try/catch blocks
@@ -85,14 +169,18 @@ impl ErrorHandlingAnalyzer {
             
             // Catch block
             if in_try && (trimmed.starts_with("catch") || trimmed.starts_with("} catch") || trimmed.starts_with("except")) {
-                let is_swallowed = self.is_empty_catch(&lines, line_num);
-                let logs_error = self.check_logs_error(&lines, line_num);
-                let rethrows = self.check_rethrows(&lines, line_num);
+                let (body, end_line) = catch_body(&lines, line_num);
+                let logs_error = LOGGING_CALL.is_match(&body);
+                let rethrows = RETHROW.is_match(&body);
+                let is_swallowed = !logs_error && !rethrows;
+                if is_swallowed {
+                    swallowed.push(self.swallowed_error_gap(result, file, try_start, line_num));
+                }
                 
                 boundaries.push(ErrorBoundary {
                     file: file.to_string(),
                     start_line: try_start,
-                    end_line: line_num + 5, // Approximate end
+                    end_line,
                     boundary_type: if trimmed.starts_with("except") {
                         BoundaryType::TryExcept
                     } else {
@@ -102,6 +190,8 @@ impl ErrorHandlingAnalyzer {
                     rethrows,
                     logs_error,
                     is_swallowed,
+                    framework: None,
+                    scope: None,
                 });
                 in_try = false;
             }
@@ -119,11 +209,39 @@ impl ErrorHandlingAnalyzer {
                     rethrows: false,
                     logs_error: false,
                     is_swallowed: false,
+                    framework: None,
+                    scope: None,
                 });
             }
         }
         
-        boundaries
+        (boundaries, swallowed)
+    }
+    
+    /// Gap for a catch on `catch_line` that neither logs nor rethrows what
+    /// the try block from `try_start` throws
+    fn swallowed_error_gap(&self, result: &ParseResult, file: &str, try_start: u32, catch_line: u32) -> ErrorGap {
+        let reachable_writes = self.call_graph.as_ref()
+            .map(|graph| graph.reachable_writes(file, try_start, catch_line))
+            .unwrap_or_default();
+        let (severity, description) = if !reachable_writes.is_empty() {
+            let mut tables: Vec<&str> = reachable_writes.iter().map(|w| w.table.as_str()).collect();
+            tables.dedup();
+            (GapSeverity::Critical, format!("Catch block swallows errors from writes to {}", tables.join(", ")))
+        } else if self.call_graph.is_some() {
+            (GapSeverity::Low, "Catch block neither logs nor rethrows the error".to_string())
+        } else {
+            (GapSeverity::Medium, "Catch block neither logs nor rethrows the error".to_string())
+        };
+        ErrorGap {
+            file: file.to_string(),
+            line: catch_line,
+            function: enclosing_function(result, catch_line),
+            gap_type: GapType::SwallowedError,
+            severity,
+            description,
+            reachable_writes,
+        }
     }
     
     /// Detect error handling gaps from AST
@@ -160,77 +278,67 @@ impl ErrorHandlingAnalyzer {
                                 "Async function '{}' has await calls without error handling",
                                 func.name
                             ),
+                            reachable_writes: Vec::new(),
                         });
                     }
                 }
             }
         }
         
-        // Check for unhandled promise chains from AST calls
-        for call in &result.calls {
-            // Look for .then() without .catch()
-            if call.callee == "then" {
-                // Check if there's a .catch() nearby
-                let has_catch = result.calls.iter().any(|c| {
-                    c.callee == "catch" &&
-                    c.range.start.line >= call.range.start.line &&
-                    c.range.start.line <= call.range.start.line + 5
-                });
-                
-                if !has_catch {
-                    gaps.push(ErrorGap {
-                        file: file.to_string(),
-                        line: call.range.start.line,
-                        function: "unknown".to_string(),
-                        gap_type: GapType::UnhandledPromise,
-                        severity: GapSeverity::Medium,
-                        description: "Promise chain with .then() but no .catch()".to_string(),
-                    });
-                }
-            }
-            
-            // Rust: .unwrap() without error handling
-            if call.callee == "unwrap" || call.callee == "expect" {
-                gaps.push(ErrorGap {
-                    file: file.to_string(),
-                    line: call.range.start.line,
-                    function: "unknown".to_string(),
-                    gap_type: GapType::UnwrapWithoutCheck,
-                    severity: if call.callee == "unwrap" { GapSeverity::High } else { GapSeverity::Medium },
-                    description: format!("Use of .{}() can panic on error", call.callee),
-                });
-            }
+        // Promises whose result is discarded with no rejection handler
+        for promise in find_dangling_promises(result, source) {
+            gaps.push(ErrorGap {
+                file: file.to_string(),
+                line: promise.line,
+                function: enclosing_function(result, promise.line),
+                gap_type: GapType::UnhandledPromise,
+                severity: GapSeverity::Medium,
+                description: format!("Promise from '{}()' is neither awaited, returned nor caught", promise.origin),
+                reachable_writes: Vec::new(),
+            });
+        }
+        
+        // Rust: panics outside tests, worse where entry points reach
+        for panic in find_rust_panics(result, file, source) {
+            let reachable = self.call_graph.as_ref()
+                .is_some_and(|graph| graph.reachable_from_entry_point(file, panic.line));
+            gaps.push(ErrorGap {
+                file: file.to_string(),
+                line: panic.line,
+                function: panic.function.unwrap_or_else(|| "unknown".to_string()),
+                gap_type: panic.gap_type,
+                severity: if reachable { escalate(panic.severity) } else { panic.severity },
+                description: if reachable {
+                    format!("{}, reachable from an entry point", panic.description)
+                } else {
+                    panic.description
+                },
+                reachable_writes: Vec::new(),
+            });
         }
         
         gaps
     }
     
-    /// Extract custom error types from AST classes
-    fn extract_error_types_from_ast(
+    /// Every class as a candidate error type; which are errors depends on
+    /// the `extends` chains across all files
+    fn extract_classes_from_ast(
         &self,
         result: &ParseResult,
         file: &str,
     ) -> Vec<ErrorType> {
-        let mut error_types = Vec::new();
-        
-        for class in &result.classes {
-            // Check if class extends Error or Exception
-            let is_error_type = class.extends.as_ref().map_or(false, |ext| {
-                ext.contains("Error") || ext.contains("Exception") || ext.contains("Throwable")
-            }) || class.name.ends_with("Error") || class.name.ends_with("Exception");
-            
-            if is_error_type {
-                error_types.push(ErrorType {
-                    name: class.name.clone(),
-                    file: file.to_string(),
-                    line: class.range.start.line,
-                    extends: class.extends.clone(),
-                    is_exported: class.is_exported,
-                });
-            }
-        }
-        
-        error_types
+        result.classes.iter()
+            .map(|class| ErrorType {
+                name: class.name.clone(),
+                file: file.to_string(),
+                line: class.range.start.line,
+                extends: class.extends.clone(),
+                is_exported: class.is_exported,
+                children: Vec::new(),
+                throw_count: 0,
+                is_unused: false,
+            })
+            .collect()
     }
     
     // Helper methods
@@ -251,36 +359,6 @@ impl ErrorHandlingAnalyzer {
         func_source.contains("try") && (func_source.contains("catch") || func_source.contains("except"))
     }
     
-    fn is_empty_catch(&self, lines: &[&str], catch_line: u32) -> bool {
-        // Check if catch block is empty or just has a comment
-        let idx = (catch_line as usize).saturating_sub(1);
-        if idx + 2 < lines.len() {
-            let next_lines = &lines[idx..idx.saturating_add(3).min(lines.len())];
-            let content: String = next_lines.join(" ");
-            // Empty if just braces or pass statement
-            content.contains("{ }") || content.contains("{}") || 
-            content.contains("pass") || content.trim().ends_with("{ }")
-        } else {
-            false
-        }
-    }
-    
-    fn check_logs_error(&self, lines: &[&str], catch_line: u32) -> bool {
-        let idx = (catch_line as usize).saturating_sub(1);
-        let end_idx = (idx + 10).min(lines.len());
-        let content: String = lines[idx..end_idx].join(" ");
-        content.contains("console.error") || content.contains("console.log") ||
-        content.contains("logger.error") || content.contains("logging.error") ||
-        content.contains("log.error") || content.contains("print(")
-    }
-    
-    fn check_rethrows(&self, lines: &[&str], catch_line: u32) -> bool {
-        let idx = (catch_line as usize).saturating_sub(1);
-        let end_idx = (idx + 10).min(lines.len());
-        let content: String = lines[idx..end_idx].join(" ");
-        content.contains("throw ") || content.contains("raise ") || content.contains("rethrow")
-    }
-    
     fn extract_caught_types(&self, catch_line: &str) -> Vec<String> {
         let mut types = Vec::new();
         
@@ -320,6 +398,103 @@ impl ErrorHandlingAnalyzer {
     }
 }
 
+/// Innermost named function containing `line`; callbacks report as the
+/// function they sit in
+fn enclosing_function(result: &ParseResult, line: u32) -> String {
+    result.functions.iter()
+        .filter(|f| f.range.start.line <= line && line <= f.range.end.line)
+        .filter(|f| !f.name.starts_with("<anon@"))
+        .min_by_key(|f| f.range.end.line - f.range.start.line)
+        .map_or_else(|| "unknown".to_string(), |f| f.name.clone())
+}
+
+/// Boundary for a framework error handler, which rethrows when it passes
+/// the error on with `next(err)`
+fn error_middleware_boundary(file: &str, source: &str, handler: ErrorMiddleware) -> ErrorBoundary {
+    let body = source.lines()
+        .skip(handler.start_line as usize)
+        .take((handler.end_line - handler.start_line) as usize + 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let logs_error = LOGGING_CALL.is_match(&body);
+    let rethrows = RETHROW.is_match(&body) || NEXT_ERROR.is_match(&body);
+    ErrorBoundary {
+        file: file.to_string(),
+        start_line: handler.start_line,
+        end_line: handler.end_line,
+        boundary_type: BoundaryType::ErrorHandler,
+        caught_types: Vec::new(),
+        rethrows,
+        logs_error,
+        is_swallowed: !logs_error && !rethrows,
+        framework: Some(handler.framework.to_string()),
+        scope: Some(handler.scope),
+    }
+}
+
+fn escalate(severity: GapSeverity) -> GapSeverity {
+    match severity {
+        GapSeverity::Low => GapSeverity::Medium,
+        GapSeverity::Medium => GapSeverity::High,
+        GapSeverity::High | GapSeverity::Critical => GapSeverity::Critical,
+    }
+}
+
+/// Text of the catch or except block opening on `catch_line`, and the line
+/// it ends on
+fn catch_body(lines: &[&str], catch_line: u32) -> (String, u32) {
+    let start = catch_line as usize;
+    let header = lines[start];
+    let trimmed = header.trim_start();
+    let mut body = String::new();
+    
+    // Python: the rest of the except line, then every deeper-indented line
+    if trimmed.starts_with("except") {
+        let indent = header.len() - trimmed.len();
+        body.push_str(header.split_once(':').map_or("", |(_, rest)| rest));
+        let mut end = start;
+        for (i, line) in lines.iter().enumerate().skip(start + 1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.len() - line.trim_start().len() <= indent {
+                break;
+            }
+            body.push('\n');
+            body.push_str(line);
+            end = i;
+        }
+        return (body, end as u32);
+    }
+    
+    // Braces: from the first `{` after `catch` to the one closing it
+    let mut depth = 0;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let text = if i == start { &line[line.find("catch").unwrap_or(0)..] } else { line };
+        for c in text.chars() {
+            if c == '{' {
+                depth += 1;
+                if depth == 1 {
+                    continue;
+                }
+            }
+            if c == '}' && depth > 0 {
+                depth -= 1;
+                if depth == 0 {
+                    return (body, i as u32);
+                }
+            }
+            if depth > 0 {
+                body.push(c);
+            }
+        }
+        if depth > 0 {
+            body.push('\n');
+        }
+    }
+    (body, lines.len().saturating_sub(1) as u32)
+}
+
 impl Default for ErrorHandlingAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -349,4 +524,86 @@ mod tests {
         let types = analyzer.extract_caught_types("catch (IOException e) {");
         assert!(types.contains(&"IOException".to_string()));
     }
+    
+    #[test]
+    fn test_swallowed_error_severity() {
+        use crate::call_graph::fixtures::{access, accessor, function, resolved_call, resolved_call_at, sample_db, spanning};
+        use crate::call_graph::DataOperation;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let path = root.join("src/checkout.ts");
+        std::fs::write(&path, concat!(
+            "import { charge } from './payments';\n",
+            "\n",
+            "export async function checkout(order: Order) {\n",
+            "  try {\n",
+            "    await charge(order);\n",
+            "  } catch (e) {}\n",
+            "  try {\n",
+            "    metrics.increment('checkout');\n",
+            "  } catch (e) {\n",
+            "    // best effort\n",
+            "  }\n",
+            "  try {\n",
+            "    await charge(order);\n",
+            "  } catch (e) {\n",
+            "    logger.warn('charge failed', e);\n",
+            "  }\n",
+            "  try {\n",
+            "    await charge(order);\n",
+            "  } catch (e) {\n",
+            "    throw new CheckoutError(e);\n",
+            "  }\n",
+            "}\n",
+        )).unwrap();
+        let files = vec![path.to_string_lossy().to_string()];
+        
+        let charge = "src/payments.ts:charge:0";
+        let write = |table: &str| access(table, DataOperation::Write, &[]);
+        let db = sample_db(root, vec![
+            spanning("src/checkout.ts:checkout:2", 21, vec![
+                resolved_call_at(charge, 4),
+                resolved_call_at("src/metrics.ts:increment:0", 7),
+                resolved_call_at(charge, 12),
+                resolved_call_at(charge, 17),
+            ]),
+            accessor(charge, vec![resolved_call("src/stripe.ts:createCharge:0")], vec![write("payments")]),
+            accessor("src/stripe.ts:createCharge:0", vec![], vec![write("external:api.stripe.com")]),
+            function("src/metrics.ts:increment:0", vec![]),
+        ]);
+        
+        let swallowed = |result: &ErrorHandlingResult| -> Vec<(u32, GapSeverity)> {
+            result.gaps.iter()
+                .filter(|g| g.gap_type == GapType::SwallowedError)
+                .map(|g| (g.line, g.severity))
+                .collect()
+        };
+        
+        // Logging and rethrowing catches aren't swallowing
+        let result = ErrorHandlingAnalyzer::new().analyze(&files);
+        let handling: Vec<(u32, bool, bool, bool)> = result.boundaries.iter()
+            .filter(|b| b.boundary_type == BoundaryType::TryCatch)
+            .map(|b| (b.end_line, b.logs_error, b.rethrows, b.is_swallowed))
+            .collect();
+        assert_eq!(handling, vec![
+            (5, false, false, true),
+            (10, false, false, true),
+            (15, true, false, false),
+            (20, false, true, false),
+        ]);
+        assert_eq!(swallowed(&result), vec![(5, GapSeverity::Medium), (8, GapSeverity::Medium)]);
+        
+        let result = ErrorHandlingAnalyzer::new().with_call_graph(root, &db).unwrap().analyze(&files);
+        assert_eq!(swallowed(&result), vec![(5, GapSeverity::Critical), (8, GapSeverity::Low)]);
+        let gap = result.gaps.iter().find(|g| g.gap_type == GapType::SwallowedError).unwrap();
+        assert_eq!(gap.function, "checkout");
+        assert_eq!(gap.description, "Catch block swallows errors from writes to external:api.stripe.com, payments");
+        let writes: Vec<(&str, &str)> = gap.reachable_writes.iter().map(|w| (w.table.as_str(), w.function.as_str())).collect();
+        assert_eq!(writes, vec![
+            ("external:api.stripe.com", "src/stripe.ts:createCharge:0"),
+            ("payments", charge),
+        ]);
+    }
 }
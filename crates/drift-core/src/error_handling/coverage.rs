@@ -0,0 +1,198 @@
+//! Error coverage - Boundaries on the write paths from entry points
+//!
+//! A call or write is covered when it sits inside an error boundary that
+//! logs or rethrows; nothing below a covered call needs a boundary of its
+//! own. Walking from an entry point along uncovered calls only, every write
+//! reached outside a boundary is on an uncovered path.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::analyzer::ErrorHandlingAnalyzer;
+use super::reach::{reachable_write, ReachGraph};
+use super::types::*;
+use crate::call_graph::CallGraphDb;
+use crate::test_topology::is_test_path;
+
+/// Boundary coverage of the write paths from every entry point in the call
+/// graph, whose paths are relative to `root`
+pub fn analyze_error_coverage(
+    root: &Path,
+    db: &CallGraphDb,
+    options: &ErrorCoverageOptions,
+) -> Result<ErrorCoverageResult, String> {
+    let start = Instant::now();
+    let files = db.get_files().map_err(|e| format!("Failed to read call graph: {}", e))?;
+    let paths: Vec<String> = files.iter().map(|file| root.join(file).to_string_lossy().to_string()).collect();
+    let analyzed: HashMap<&str, &str> = paths.iter().map(String::as_str).zip(files.iter().map(String::as_str)).collect();
+    let graph = Arc::new(ReachGraph::load(root, db)?);
+    let result = ErrorHandlingAnalyzer::new().with_reach_graph(Arc::clone(&graph)).analyze(&paths);
+
+    // Call graph file -> line ranges of boundaries that log or rethrow
+    let mut handled: HashMap<&str, Vec<(u32, u32)>> = HashMap::new();
+    for boundary in result.boundaries.iter().filter(|b| b.logs_error || b.rethrows) {
+        if let Some(&file) = analyzed.get(boundary.file.as_str()) {
+            handled.entry(file).or_default().push((boundary.start_line, boundary.end_line));
+        }
+    }
+    let covered = |file: &str, line: u32| handled.get(file)
+        .is_some_and(|ranges| ranges.iter().any(|&(start, end)| start <= line && line <= end));
+
+    let mut entry_points: Vec<_> = graph.entry_points()
+        .filter(|f| !(options.exclude_tests && is_test_path(CallGraphDb::extract_file_from_id(&f.id))))
+        .collect();
+    entry_points.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut uncovered = Vec::new();
+    for entry in &entry_points {
+        // Breadth-first, so the first write found ends a shortest path
+        let mut parents: HashMap<&str, (&str, u32)> = HashMap::new();
+        let mut visited: HashSet<&str> = HashSet::from([entry.id.as_str()]);
+        let mut queue: VecDeque<(&str, usize)> = VecDeque::from([(entry.id.as_str(), 0)]);
+        let mut writes = Vec::new();
+        let mut first_write: Option<(&str, u32)> = None;
+        while let Some((id, depth)) = queue.pop_front() {
+            let Some(function) = graph.function(id) else { continue };
+            let file = CallGraphDb::extract_file_from_id(id);
+            for access in function.data_access.iter().filter(|a| !covered(file, a.line)) {
+                first_write.get_or_insert((id, access.line));
+                writes.push(reachable_write(function, access));
+            }
+            if options.max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            for call in function.calls.iter().filter(|c| !covered(file, c.line)) {
+                let Some(target) = call.resolved_id.as_deref() else { continue };
+                if visited.insert(target) {
+                    parents.insert(target, (id, call.line));
+                    queue.push_back((target, depth + 1));
+                }
+            }
+        }
+        let Some((writer, write_line)) = first_write else { continue };
+
+        let mut example_path = vec![PathStep { function: writer.to_string(), line: write_line }];
+        let mut step = writer;
+        while let Some(&(caller, line)) = parents.get(step) {
+            example_path.push(PathStep { function: caller.to_string(), line });
+            step = caller;
+        }
+        example_path.reverse();
+
+        let on_path: Vec<(&str, u32, u32)> = example_path.iter()
+            .filter_map(|step| graph.function(&step.function))
+            .map(|f| (CallGraphDb::extract_file_from_id(&f.id), f.start_line, f.end_line))
+            .collect();
+        let gaps = result.gaps.iter()
+            .filter(|gap| analyzed.get(gap.file.as_str()).is_some_and(|&file| on_path.iter()
+                .any(|&(path_file, start, end)| path_file == file && start <= gap.line && gap.line <= end)))
+            .cloned()
+            .collect();
+
+        writes.sort_by(|a, b| (&a.table, &a.function, a.operation as u8).cmp(&(&b.table, &b.function, b.operation as u8)));
+        writes.dedup();
+        uncovered.push(UncoveredEntryPoint {
+            entry_point: entry.id.clone(),
+            file: CallGraphDb::extract_file_from_id(&entry.id).to_string(),
+            line: entry.start_line,
+            uncovered_writes: writes,
+            example_path,
+            gaps,
+        });
+    }
+    uncovered.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    Ok(ErrorCoverageResult {
+        entry_points: entry_points.len(),
+        covered_entry_points: entry_points.len() - uncovered.len(),
+        uncovered,
+        files_analyzed: result.files_analyzed,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::call_graph::fixtures::{access, accessor, exported, resolved_call_at, sample_db};
+    use crate::call_graph::{DataAccessRef, DataOperation, FunctionEntry};
+    use crate::error_handling::*;
+
+    #[test]
+    fn test_error_coverage() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/routes.ts"), concat!(
+            "import { charge, refund, audit } from './payments';\n",
+            "\n",
+            "export async function checkout(req) {\n",
+            "  try {\n",
+            "    await charge(req.order);\n",
+            "  } catch (e) {\n",
+            "    logger.error('checkout failed', e);\n",
+            "    throw e;\n",
+            "  }\n",
+            "}\n",
+            "\n",
+            "export async function cancel(req) {\n",
+            "  try {\n",
+            "    await refund(req.order);\n",
+            "  } catch (e) {}\n",
+            "  audit(req.order);\n",
+            "}\n",
+            "\n",
+            "export function health() {\n",
+            "  return 'ok';\n",
+            "}\n",
+        )).unwrap();
+        std::fs::write(root.join("src/payments.ts"), concat!(
+            "export async function charge(order) {\n",
+            "  await db.payments.insert(order);\n",
+            "}\n",
+            "export async function refund(order) {\n",
+            "  await db.payments.update(order);\n",
+            "}\n",
+            "export function audit(order) {\n",
+            "  db.audit_log.insert(order);\n",
+            "}\n",
+        )).unwrap();
+
+        let route = |id: &str, end_line: u32, calls| FunctionEntry { end_line, ..exported(id, calls) };
+        let writer = |id: &str, end_line: u32, table: &str, line: u32| FunctionEntry {
+            end_line,
+            ..accessor(id, vec![], vec![DataAccessRef { line, ..access(table, DataOperation::Write, &[]) }])
+        };
+        let db = sample_db(root, vec![
+            route("src/routes.ts:checkout:2", 9, vec![resolved_call_at("src/payments.ts:charge:0", 4)]),
+            route("src/routes.ts:cancel:11", 16, vec![
+                resolved_call_at("src/payments.ts:refund:3", 13),
+                resolved_call_at("src/payments.ts:audit:6", 15),
+            ]),
+            route("src/routes.ts:health:18", 20, vec![]),
+            writer("src/payments.ts:charge:0", 2, "payments", 1),
+            writer("src/payments.ts:refund:3", 5, "payments", 4),
+            writer("src/payments.ts:audit:6", 8, "audit_log", 7),
+        ]);
+
+        // checkout logs and rethrows around its write; cancel swallows the
+        // refund error and never guards the audit write
+        let result = analyze_error_coverage(root, &db, &ErrorCoverageOptions::default()).unwrap();
+        assert_eq!((result.entry_points, result.covered_entry_points), (3, 2));
+        assert_eq!(result.uncovered.len(), 1);
+        let cancel = &result.uncovered[0];
+        assert_eq!((cancel.entry_point.as_str(), cancel.file.as_str(), cancel.line), ("src/routes.ts:cancel:11", "src/routes.ts", 11));
+        let writes: Vec<(&str, &str)> = cancel.uncovered_writes.iter().map(|w| (w.table.as_str(), w.function.as_str())).collect();
+        assert_eq!(writes, vec![("audit_log", "src/payments.ts:audit:6"), ("payments", "src/payments.ts:refund:3")]);
+        let path: Vec<(&str, u32)> = cancel.example_path.iter().map(|s| (s.function.as_str(), s.line)).collect();
+        assert_eq!(path, vec![("src/routes.ts:cancel:11", 13), ("src/payments.ts:refund:3", 4)]);
+        let gaps: Vec<(u32, GapType)> = cancel.gaps.iter().map(|g| (g.line, g.gap_type)).collect();
+        assert_eq!(gaps, vec![(3, GapType::UnhandledAsync), (14, GapType::SwallowedError)]);
+
+        // Entry points don't write themselves
+        let options = ErrorCoverageOptions { max_depth: Some(0), ..Default::default() };
+        let result = analyze_error_coverage(root, &db, &options).unwrap();
+        assert_eq!((result.entry_points, result.covered_entry_points), (3, 3));
+    }
+}
@@ -0,0 +1,169 @@
+//! Error hierarchy - Inheritance between error types, and where they're thrown
+//!
+//! A class is an error type when its name or base says so (`*Error`,
+//! `*Exception`, `Throwable`), or when it extends an error type from any
+//! analyzed file. Construction sites are call sites of the type (`new X()`,
+//! `X(...)`) and bare `raise X`; a type is unused when neither it nor any
+//! of its descendants is ever constructed.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::types::ErrorType;
+use crate::parsers::ParseResult;
+
+/// `new X`, `new errors.X`, `raise X` without arguments
+static CONSTRUCTION: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:new|raise)\s+(?:\w+\.)*([A-Z]\w*)").unwrap());
+
+/// (type name, line) of every place `result` may construct a class
+pub(crate) fn construction_sites(result: &ParseResult, source: &str) -> HashSet<(String, u32)> {
+    let mut sites: HashSet<(String, u32)> = result.calls.iter()
+        .filter(|c| c.callee.starts_with(|c: char| c.is_ascii_uppercase()))
+        .map(|c| (c.callee.clone(), c.range.start.line))
+        .collect();
+    for (line, text) in source.lines().enumerate() {
+        sites.extend(CONSTRUCTION.captures_iter(text).map(|c| (c[1].to_string(), line as u32)));
+    }
+    sites
+}
+
+/// The error types among `classes`, linked to their subclasses and counted
+/// against `constructions` (type name -> construction sites)
+pub(crate) fn error_hierarchy(classes: Vec<ErrorType>, constructions: &HashMap<String, usize>) -> Vec<ErrorType> {
+    let mut errors: HashSet<String> = classes.iter()
+        .filter(|c| is_error_name(&c.name) || c.extends.as_deref().is_some_and(is_error_base))
+        .map(|c| c.name.clone())
+        .collect();
+    // Subclasses of error types are error types, however they're named
+    loop {
+        let before = errors.len();
+        for class in &classes {
+            if class.extends.as_deref().map(base_name).is_some_and(|base| errors.contains(base)) {
+                errors.insert(class.name.clone());
+            }
+        }
+        if errors.len() == before {
+            break;
+        }
+    }
+
+    let mut error_types: Vec<ErrorType> = classes.into_iter().filter(|c| errors.contains(&c.name)).collect();
+    let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for error_type in &error_types {
+        if let Some(base) = error_type.extends.as_deref().map(base_name) {
+            children.entry(base.to_string()).or_default().push(error_type.name.clone());
+        }
+    }
+    for error_type in &mut error_types {
+        let mut subclasses = children.get(&error_type.name).cloned().unwrap_or_default();
+        subclasses.sort();
+        subclasses.dedup();
+        error_type.children = subclasses;
+        error_type.throw_count = constructions.get(&error_type.name).copied().unwrap_or(0);
+    }
+
+    // Unused: nothing constructs the type or anything below it
+    let thrown: HashMap<&str, usize> = error_types.iter().map(|e| (e.name.as_str(), e.throw_count)).collect();
+    let used: HashSet<String> = error_types.iter()
+        .filter(|e| subtree_thrown(&e.name, &thrown, &children, &mut HashSet::new()))
+        .map(|e| e.name.clone())
+        .collect();
+    for error_type in &mut error_types {
+        error_type.is_unused = !used.contains(&error_type.name);
+    }
+    error_types
+}
+
+fn subtree_thrown<'a>(
+    name: &'a str,
+    thrown: &HashMap<&str, usize>,
+    children: &'a BTreeMap<String, Vec<String>>,
+    visited: &mut HashSet<&'a str>,
+) -> bool {
+    if !visited.insert(name) {
+        return false;
+    }
+    thrown.get(name).is_some_and(|&count| count > 0)
+        || children.get(name).into_iter().flatten().any(|child| subtree_thrown(child, thrown, children, visited))
+}
+
+fn is_error_name(name: &str) -> bool {
+    name.ends_with("Error") || name.ends_with("Exception")
+}
+
+fn is_error_base(base: &str) -> bool {
+    base.contains("Error") || base.contains("Exception") || base.contains("Throwable")
+}
+
+/// `errors.AppError` and `AppError<T>` as `AppError`
+fn base_name(name: &str) -> &str {
+    let name = name.split('<').next().unwrap_or(name).trim();
+    name.rsplit(['.', ':', '\\']).next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error_handling::*;
+
+    #[test]
+    fn test_error_hierarchy() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            ("errors.ts", concat!(
+                "export class AppError extends Error {}\n",
+                "export class ValidationError extends AppError {}\n",
+                "export class FieldValidationError extends ValidationError {\n",
+                "  constructor(public field: string) { super(field); }\n",
+                "}\n",
+                "export class RateLimited extends AppError {}\n",
+                "export class LegacyError extends Error {}\n",
+                "export class Widget {}\n",
+            )),
+            ("service.ts", concat!(
+                "import { FieldValidationError } from './errors';\n",
+                "\n",
+                "export function validate(input) {\n",
+                "  if (!input.name) throw new FieldValidationError('name');\n",
+                "  if (!input.email) throw new FieldValidationError('email');\n",
+                "  if (input.tooMany) throw new errors.RateLimited();\n",
+                "}\n",
+            )),
+            ("payments.py", concat!(
+                "class PaymentError(Exception):\n",
+                "    pass\n",
+                "\n",
+                "class CardDeclined(PaymentError):\n",
+                "    pass\n",
+                "\n",
+                "def charge(card):\n",
+                "    if card.expired:\n",
+                "        raise CardDeclined\n",
+                "    raise PaymentError('failed')\n",
+            )),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let result = ErrorHandlingAnalyzer::new().analyze(&paths);
+        let error_types: Vec<(&str, Vec<&str>, usize, bool)> = result.error_types.iter()
+            .map(|e| (e.name.as_str(), e.children.iter().map(String::as_str).collect(), e.throw_count, e.is_unused))
+            .collect();
+        assert_eq!(error_types, vec![
+            ("AppError", vec!["RateLimited", "ValidationError"], 0, false),
+            ("ValidationError", vec!["FieldValidationError"], 0, false),
+            ("FieldValidationError", vec![], 2, false),
+            // An error type by inheritance alone
+            ("RateLimited", vec![], 1, false),
+            ("LegacyError", vec![], 0, true),
+            ("PaymentError", vec!["CardDeclined"], 1, false),
+            ("CardDeclined", vec![], 1, false),
+        ]);
+    }
+}
@@ -0,0 +1,453 @@
+//! Node error middleware - Framework error handlers and the routes they cover
+//!
+//! Express error middleware takes four parameters (`app.use((err, req, res,
+//! next) => ...)`), Koa's wraps `await next()` in a try block, Fastify's is
+//! registered with `setErrorHandler()` and NestJS's are `@Catch()` exception
+//! filters. A handler registered on the application is global; one on a
+//! router covers that router's routes. NestJS filters are global once passed
+//! to `useGlobalFilters()` or provided as `APP_FILTER`, and otherwise cover
+//! what names them in `@UseFilters()`.
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tree_sitter::Node;
+
+use super::types::HandlerScope;
+use crate::parsers::{Language, ParseResult};
+
+/// `const app = express()`, `const router = new Router()`, `const app = await NestFactory.create(...)`
+static BINDING: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"\b(?:const|let|var)\s+(\w+)\s*(?::[^=]+)?=\s*(?:await\s+)?(?:new\s+)?([\w.]+)\s*[(<]"
+).unwrap());
+/// Application constructors
+static APP_CONSTRUCTOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:express|Koa|[fF]astify|NestFactory\.create\w*)$").unwrap());
+/// Router constructors
+static ROUTER_CONSTRUCTOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\.)(?:Router|KoaRouter)$").unwrap());
+/// Receivers that register routes when nothing in the file binds them
+static ROUTER_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:app|server|fastify|\w*[rR]outer)$").unwrap());
+/// `useGlobalFilters(new AllExceptionsFilter())`, `{ provide: APP_FILTER, useClass: AllExceptionsFilter }`
+static GLOBAL_FILTER: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"useGlobalFilters\s*\(([^)]*)\)|provide\s*:\s*APP_FILTER\s*,\s*use(?:Class|Value|Existing)\s*:\s*(?:new\s+)?(\w+)"
+).unwrap());
+static CONSTRUCTED: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bnew\s+(\w+)").unwrap());
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "all", "head", "options"];
+
+/// A framework error handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ErrorMiddleware {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub framework: &'static str,
+    /// `UseFilters` for NestJS filters until the project's global filters
+    /// are known
+    pub scope: HandlerScope,
+    /// The NestJS exception filter class
+    pub filter: Option<String>,
+}
+
+/// A route and whether it handles its own errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Route {
+    pub line: u32,
+    pub method: String,
+    pub path: String,
+    /// Router or application variable the route is registered on, or the
+    /// NestJS controller
+    pub router: String,
+    /// Named handler, when the route isn't registered with an inline function
+    pub handler: Option<String>,
+    /// A try block in the handler, or a NestJS `@UseFilters()`
+    pub guarded: bool,
+}
+
+/// Error handlers, routes and global NestJS filters of a file
+#[derive(Debug, Default)]
+pub(crate) struct NodeErrorHandling {
+    pub handlers: Vec<ErrorMiddleware>,
+    pub routes: Vec<Route>,
+    pub global_filters: Vec<String>,
+}
+
+/// Error handlers and routes of a JavaScript or TypeScript file
+pub(crate) fn find_node_error_handling(result: &ParseResult, source: &str) -> NodeErrorHandling {
+    let mut found = NodeErrorHandling::default();
+    if !matches!(result.language, Language::TypeScript | Language::JavaScript) {
+        return found;
+    }
+    let Some(tree) = result.tree.as_ref() else { return found };
+    for captures in GLOBAL_FILTER.captures_iter(source) {
+        match (captures.get(1), captures.get(2)) {
+            (Some(args), _) => found.global_filters.extend(CONSTRUCTED.captures_iter(args.as_str()).map(|c| c[1].to_string())),
+            (_, Some(class)) => found.global_filters.push(class.as_str().to_string()),
+            _ => {}
+        }
+    }
+    // Variable -> whether it holds an application rather than a router
+    let bindings: HashMap<&str, bool> = BINDING.captures_iter(source)
+        .filter_map(|c| {
+            let (name, constructor) = (c.get(1)?.as_str(), c.get(2)?.as_str());
+            let is_app = APP_CONSTRUCTOR.is_match(constructor);
+            (is_app || ROUTER_CONSTRUCTOR.is_match(constructor)).then_some((name, is_app))
+        })
+        .collect();
+    let context = Context { result, source: source.as_bytes(), bindings };
+
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "call_expression" => context.registration(node, &mut found),
+            "class_declaration" | "class" | "abstract_class_declaration" => context.nest_class(node, &mut found),
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    found.handlers.sort_by_key(|h| h.start_line);
+    found.routes.sort_by_key(|r| r.line);
+    found
+}
+
+struct Context<'a> {
+    result: &'a ParseResult,
+    source: &'a [u8],
+    bindings: HashMap<&'a str, bool>,
+}
+
+impl Context<'_> {
+    /// `X.use(...)`, `X.setErrorHandler(...)` and `X.get('/path', ...)`
+    fn registration(&self, call: Node, found: &mut NodeErrorHandling) {
+        let Some(function) = call.child_by_field_name("function").filter(|f| f.kind() == "member_expression") else { return };
+        let (Some(object), Some(property)) = (function.child_by_field_name("object"), function.child_by_field_name("property")) else { return };
+        let Some(arguments) = call.child_by_field_name("arguments") else { return };
+        let mut cursor = arguments.walk();
+        let args: Vec<Node> = arguments.named_children(&mut cursor).filter(|a| a.kind() != "comment").collect();
+        // this.app.use() registers on `app`
+        let receiver = match object.kind() {
+            "member_expression" => object.child_by_field_name("property").map_or("", |p| text(p, self.source)),
+            _ => text(object, self.source),
+        };
+        let line = call.start_position().row as u32;
+
+        match text(property, self.source) {
+            "use" => {
+                for arg in &args {
+                    if let Some((framework, start_line, end_line)) = self.error_middleware(*arg, line) {
+                        found.handlers.push(ErrorMiddleware {
+                            start_line,
+                            end_line,
+                            framework,
+                            scope: self.scope(receiver),
+                            filter: None,
+                        });
+                    }
+                }
+            }
+            "setErrorHandler" => {
+                let (start_line, end_line) = args.first().map_or((line, line), |handler| self.handler_range(*handler, line));
+                found.handlers.push(ErrorMiddleware {
+                    start_line,
+                    end_line,
+                    framework: "fastify",
+                    scope: self.scope(receiver),
+                    filter: None,
+                });
+            }
+            method if HTTP_METHODS.contains(&method) && self.registers_routes(receiver) => {
+                let (Some(path), Some(handler)) = (args.first().and_then(|a| string_value(*a, self.source)), args.last()) else { return };
+                found.routes.push(Route {
+                    line,
+                    method: method.to_uppercase(),
+                    path,
+                    router: receiver.to_string(),
+                    handler: (handler.kind() == "identifier").then(|| text(*handler, self.source).to_string()),
+                    guarded: self.handles_errors(*handler),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Framework and line range of `arg` when it is error middleware
+    fn error_middleware(&self, arg: Node, line: u32) -> Option<(&'static str, u32, u32)> {
+        if is_function(arg) {
+            let body = arg.child_by_field_name("body")?;
+            return match parameter_count(arg) {
+                4 => Some(("express", arg.start_position().row as u32, arg.end_position().row as u32)),
+                // Koa: try { await next() } catch ...
+                2 if contains_kind(body, "try_statement") && text(body, self.source).contains("next()") => {
+                    Some(("koa", arg.start_position().row as u32, arg.end_position().row as u32))
+                }
+                _ => None,
+            };
+        }
+        let name = match arg.kind() {
+            "identifier" => text(arg, self.source),
+            // Factories: errorHandler({ log: true })
+            "call_expression" => arg.child_by_field_name("function").map_or("", |f| text(f, self.source)),
+            _ => return None,
+        };
+        if let Some(function) = self.result.functions.iter().find(|f| f.name == name) {
+            return (function.parameters.len() == 4)
+                .then_some(("express", function.range.start.line, function.range.end.line));
+        }
+        // Imported: recognizable by name alone
+        name.to_lowercase().contains("error").then_some(("express", line, line))
+    }
+
+    /// Lines of a handler function, or of the registration
+    fn handler_range(&self, handler: Node, line: u32) -> (u32, u32) {
+        if is_function(handler) {
+            return (handler.start_position().row as u32, handler.end_position().row as u32);
+        }
+        let name = text(handler, self.source);
+        self.result.functions.iter()
+            .find(|f| f.name == name)
+            .map_or((line, line), |f| (f.range.start.line, f.range.end.line))
+    }
+
+    /// Whether a route handler catches its own errors
+    fn handles_errors(&self, handler: Node) -> bool {
+        if handler.kind() != "identifier" {
+            // Inline, or wrapped: asyncHandler(async (req, res) => ...)
+            return contains_kind(handler, "try_statement");
+        }
+        let name = text(handler, self.source);
+        let lines: Vec<&str> = std::str::from_utf8(self.source).unwrap_or("").lines().collect();
+        self.result.functions.iter()
+            .filter(|f| f.name == name)
+            .any(|f| {
+                let end = (f.range.end.line as usize + 1).min(lines.len());
+                let body = lines.get(f.range.start.line as usize..end).unwrap_or_default().join("\n");
+                body.contains("try") && body.contains("catch")
+            })
+    }
+
+    fn registers_routes(&self, receiver: &str) -> bool {
+        self.bindings.contains_key(receiver) || ROUTER_NAME.is_match(receiver)
+    }
+
+    fn scope(&self, receiver: &str) -> HandlerScope {
+        let is_app = self.bindings.get(receiver).copied()
+            .unwrap_or(matches!(receiver, "app" | "server"));
+        if is_app {
+            HandlerScope::Global
+        } else {
+            HandlerScope::Router(receiver.to_string())
+        }
+    }
+
+    /// `@Catch()` exception filters and `@Controller()` routes
+    fn nest_class(&self, class: Node, found: &mut NodeErrorHandling) {
+        let class_decorators = decorators(class, self.source);
+        let Some(name) = class.child_by_field_name("name").map(|n| text(n, self.source)) else { return };
+        if class_decorators.iter().any(|(decorator, _)| *decorator == "Catch") {
+            // From the decorators, which `export` holds
+            let start = class.parent().filter(|p| p.kind() == "export_statement").unwrap_or(class);
+            found.handlers.push(ErrorMiddleware {
+                start_line: start.start_position().row as u32,
+                end_line: class.end_position().row as u32,
+                framework: "nestjs",
+                scope: HandlerScope::UseFilters,
+                filter: Some(name.to_string()),
+            });
+        }
+        let Some((_, prefix)) = class_decorators.iter().find(|(decorator, _)| *decorator == "Controller") else { return };
+        let filtered = class_decorators.iter().any(|(decorator, _)| *decorator == "UseFilters");
+        let Some(body) = class.child_by_field_name("body") else { return };
+        let mut cursor = body.walk();
+        for method in body.named_children(&mut cursor).filter(|m| m.kind() == "method_definition") {
+            let method_decorators = decorators(method, self.source);
+            let Some((verb, path)) = method_decorators.iter()
+                .find(|(decorator, _)| HTTP_METHODS.contains(&decorator.to_lowercase().as_str()))
+            else { continue };
+            let Some(method_name) = method.child_by_field_name("name").map(|n| text(n, self.source)) else { continue };
+            let path = [prefix.as_deref(), path.as_deref()].into_iter()
+                .flatten()
+                .map(|segment| segment.trim_matches('/'))
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join("/");
+            found.routes.push(Route {
+                line: method.start_position().row as u32,
+                method: verb.to_uppercase(),
+                path: format!("/{}", path),
+                router: name.to_string(),
+                handler: Some(format!("{}.{}", name, method_name)),
+                guarded: filtered
+                    || method_decorators.iter().any(|(decorator, _)| *decorator == "UseFilters")
+                    || contains_kind(method, "try_statement"),
+            });
+        }
+    }
+}
+
+/// (name, first string argument) of the decorators on a class or method
+fn decorators<'a>(node: Node, source: &'a [u8]) -> Vec<(&'a str, Option<String>)> {
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut cursor = node.walk();
+    nodes.extend(node.children(&mut cursor).filter(|c| c.kind() == "decorator"));
+    // Decorators before the node, or before its `export`
+    for owner in [Some(node), node.parent().filter(|p| p.kind() == "export_statement")].into_iter().flatten() {
+        let mut sibling = owner.prev_named_sibling();
+        while let Some(decorator) = sibling.filter(|s| s.kind() == "decorator") {
+            nodes.push(decorator);
+            sibling = decorator.prev_named_sibling();
+        }
+        if owner.kind() == "export_statement" {
+            let mut cursor = owner.walk();
+            nodes.extend(owner.children(&mut cursor).filter(|c| c.kind() == "decorator"));
+        }
+    }
+    nodes.iter()
+        .filter_map(|decorator| {
+            let expression = decorator.named_child(0)?;
+            let (callee, argument) = match expression.kind() {
+                "call_expression" => (
+                    expression.child_by_field_name("function")?,
+                    expression.child_by_field_name("arguments")
+                        .and_then(|args| args.named_child(0))
+                        .and_then(|arg| string_value(arg, source)),
+                ),
+                _ => (expression, None),
+            };
+            let name = text(callee, source);
+            Some((name.rsplit('.').next().unwrap_or(name), argument))
+        })
+        .collect()
+}
+
+fn is_function(node: Node) -> bool {
+    matches!(node.kind(), "arrow_function" | "function_expression" | "function")
+}
+
+fn parameter_count(function: Node) -> usize {
+    match function.child_by_field_name("parameters") {
+        Some(parameters) => parameters.named_child_count(),
+        // x => ...
+        None => usize::from(function.child_by_field_name("parameter").is_some()),
+    }
+}
+
+fn contains_kind(node: Node, kind: &str) -> bool {
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if node.kind() == kind {
+            return true;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    false
+}
+
+/// Contents of a string literal, or of a template without substitutions
+fn string_value(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "string" => Some(text(node, source).trim_matches(|c| c == '"' || c == '\'').to_string()),
+        "template_string" if node.named_child_count() == 0 => Some(text(node, source).trim_matches('`').to_string()),
+        _ => None,
+    }
+}
+
+fn text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error_handling::*;
+
+    #[test]
+    fn test_error_middleware() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            ("routes.ts", concat!(
+                "import express from 'express';\n",
+                "const router = express.Router();\n",
+                "const admin = express.Router();\n",
+                "router.get('/users', listUsers);\n",
+                "router.post('/users', async (req, res, next) => {\n",
+                "  try { await save(req.body); } catch (e) { next(e); }\n",
+                "});\n",
+                "admin.delete('/users/:id', async (req, res) => { await remove(req.params.id); });\n",
+                "cache.get('users', fallback);\n",
+                "router.use((err, req, res, next) => {\n",
+                "  logger.error(err);\n",
+                "  res.status(500).json({ error: 'internal' });\n",
+                "});\n",
+                "function listUsers(req, res) { res.json([]); }\n",
+            )),
+            ("plugin.js", concat!(
+                "export default async function (fastify) {\n",
+                "  fastify.setErrorHandler((error, request, reply) => { reply.send(error); });\n",
+                "  fastify.get('/status', async () => ({ ok: true }));\n",
+                "}\n",
+            )),
+            ("orders.controller.ts", concat!(
+                "@Controller('orders')\n",
+                "export class OrdersController {\n",
+                "  @Get(':id')\n",
+                "  find(@Param('id') id: string) { return this.orders.find(id); }\n",
+                "\n",
+                "  @Post()\n",
+                "  @UseFilters(PaymentFilter)\n",
+                "  create(@Body() body) { return this.orders.create(body); }\n",
+                "}\n",
+                "\n",
+                "@Catch(PaymentError)\n",
+                "export class PaymentFilter implements ExceptionFilter {\n",
+                "  catch(exception, host) { logger.warn(exception); }\n",
+                "}\n",
+            )),
+        ];
+        let mut paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let handlers = |result: &ErrorHandlingResult| -> Vec<(u32, String, HandlerScope, bool)> {
+            result.boundaries.iter()
+                .filter(|b| b.boundary_type == BoundaryType::ErrorHandler)
+                .map(|b| (b.start_line, b.framework.clone().unwrap(), b.scope.clone().unwrap(), b.logs_error || b.rethrows))
+                .collect()
+        };
+        let missing = |result: &ErrorHandlingResult| -> Vec<(u32, String, String)> {
+            result.gaps.iter()
+                .filter(|g| g.gap_type == GapType::MissingErrorBoundary)
+                .map(|g| (g.line, g.function.clone(), g.description.clone()))
+                .collect()
+        };
+        let result = ErrorHandlingAnalyzer::new().analyze(&paths);
+        assert_eq!(handlers(&result), vec![
+            (9, "express".to_string(), HandlerScope::Router("router".to_string()), true),
+            (1, "fastify".to_string(), HandlerScope::Router("fastify".to_string()), false),
+            (10, "nestjs".to_string(), HandlerScope::UseFilters, true),
+        ]);
+        // The admin router has no error middleware, the find route no filter
+        assert_eq!(missing(&result), vec![
+            (7, "unknown".to_string(), "Route DELETE /users/:id has no error handler or try/catch".to_string()),
+            (3, "OrdersController.find".to_string(), "Route GET /orders/:id has no error handler or try/catch".to_string()),
+        ]);
+
+        // Global: Koa middleware around next(), and the filter for every controller
+        std::fs::write(root.join("main.ts"), concat!(
+            "const app = new Koa();\n",
+            "app.use(async (ctx, next) => {\n",
+            "  try { await next(); } catch (err) { ctx.status = 500; }\n",
+            "});\n",
+            "const nest = await NestFactory.create(AppModule);\n",
+            "nest.useGlobalFilters(new PaymentFilter());\n",
+        )).unwrap();
+        paths.push(root.join("main.ts").to_string_lossy().to_string());
+        let result = ErrorHandlingAnalyzer::new().analyze(&paths);
+        assert!(handlers(&result).contains(&(1, "koa".to_string(), HandlerScope::Global, false)));
+        assert!(handlers(&result).contains(&(10, "nestjs".to_string(), HandlerScope::Global, true)));
+        assert!(missing(&result).is_empty());
+    }
+}
@@ -1,12 +1,20 @@
 //! Error handling analysis module
 //!
 //! AST-first approach: Uses tree-sitter parsed data to detect:
-//! - Error boundaries (try/catch, error handlers)
+//! - Error boundaries (try/catch, framework error handlers)
 //! - Error handling gaps (unhandled promises, missing catches)
 //! - Custom error types
+//! - Boundary coverage of the write paths from entry points
 
 mod types;
 mod analyzer;
+mod coverage;
+mod hierarchy;
+mod middleware;
+mod promises;
+mod reach;
+mod rust;
 
 pub use types::*;
 pub use analyzer::ErrorHandlingAnalyzer;
+pub use coverage::analyze_error_coverage;
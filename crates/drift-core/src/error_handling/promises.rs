@@ -0,0 +1,164 @@
+//! Dangling promises - Promises whose rejection nothing observes
+//!
+//! A promise is any call chain through `.then()`, `.catch()` or
+//! `.finally()`, or starting at `new Promise()`, `Promise.*()`, `fetch()` or
+//! a function the file declares `async`. It dangles when its value is thrown
+//! away: a bare expression statement with no `.catch()` or rejection handler
+//! anywhere in the chain. Promises that are awaited, returned, yielded,
+//! aggregated by `Promise.all()` and friends, explicitly `void`ed, assigned
+//! or passed on are left alone, as is a bare `Promise.allSettled()`, which
+//! never rejects.
+
+use std::collections::HashSet;
+
+use tree_sitter::Node;
+
+use crate::parsers::{Language, ParseResult};
+
+/// A promise-producing expression statement nothing handles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DanglingPromise {
+    pub line: u32,
+    /// The call the chain starts from: `fetch`, `this.save`, `Promise.all`
+    pub origin: String,
+}
+
+/// Dangling promises of a JavaScript or TypeScript file
+pub(crate) fn find_dangling_promises(result: &ParseResult, source: &str) -> Vec<DanglingPromise> {
+    if !matches!(result.language, Language::TypeScript | Language::JavaScript) {
+        return Vec::new();
+    }
+    let Some(tree) = result.tree.as_ref() else { return Vec::new() };
+    let async_functions: HashSet<&str> = result.functions.iter()
+        .filter(|f| f.is_async)
+        .map(|f| f.name.as_str())
+        .collect();
+
+    let mut found = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "expression_statement" {
+            if let Some(promise) = node.named_child(0).and_then(|e| dangling(e, source.as_bytes(), &async_functions)) {
+                found.push(DanglingPromise { line: node.start_position().row as u32, origin: promise });
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    found.sort_by_key(|p| p.line);
+    found
+}
+
+/// The origin of `expression` when it is an unhandled promise
+fn dangling(expression: Node, source: &[u8], async_functions: &HashSet<&str>) -> Option<String> {
+    let mut node = unwrap_expression(expression);
+    let mut chained = false;
+    // Walk the chain from its last call down to the call it starts from
+    loop {
+        match node.kind() {
+            "call_expression" => {
+                let function = unwrap_expression(node.child_by_field_name("function")?);
+                if function.kind() != "member_expression" {
+                    let name = text(function, source);
+                    let is_promise = chained || name == "fetch" || async_functions.contains(name);
+                    return is_promise.then(|| name.to_string());
+                }
+                let method = text(function.child_by_field_name("property")?, source);
+                let object = unwrap_expression(function.child_by_field_name("object")?);
+                let arg_count = node.child_by_field_name("arguments")
+                    .map(|args| args.named_child_count())
+                    .unwrap_or(0);
+                match method {
+                    "catch" => return None,
+                    // .then(onFulfilled, onRejected)
+                    "then" if arg_count >= 2 => return None,
+                    "then" | "finally" => chained = true,
+                    _ => {}
+                }
+                if !matches!(object.kind(), "call_expression" | "new_expression") {
+                    let receiver = text(object, source);
+                    // Promise.allSettled() never rejects
+                    if receiver == "Promise" && method == "allSettled" && !chained {
+                        return None;
+                    }
+                    let is_promise = chained || receiver == "Promise" || async_functions.contains(method);
+                    return is_promise.then(|| text(function, source).to_string());
+                }
+                node = object;
+            }
+            "new_expression" => {
+                let constructor = text(node.child_by_field_name("constructor")?, source);
+                return (chained || constructor == "Promise").then(|| format!("new {}", constructor));
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// `(expr)`, `expr!`, `expr as T` and `expr satisfies T` as `expr`
+fn unwrap_expression(mut node: Node) -> Node {
+    while matches!(node.kind(), "parenthesized_expression" | "non_null_expression" | "as_expression" | "satisfies_expression") {
+        match node.named_child(0) {
+            Some(inner) => node = inner,
+            None => break,
+        }
+    }
+    node
+}
+
+fn text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error_handling::*;
+
+    #[test]
+    fn test_unhandled_promises() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("promises.ts");
+        std::fs::write(&path, concat!(
+            "async function save(user: User): Promise<void> {}\n",
+            "\n",
+            "async function patterns(users: User[]) {\n",
+            "  save(users[0]);\n",
+            "  await save(users[0]);\n",
+            "  fetch('/api').then((res) => res.json());\n",
+            "  fetch('/api').then((res) => res.json()).catch(() => {});\n",
+            "  fetch('/api').then(render, reportError);\n",
+            "  void save(users[0]);\n",
+            "  await Promise.all(users.map((u) => save(u)));\n",
+            "  Promise.allSettled([save(users[0]), save(users[1])]);\n",
+            "  const pending = save(users[0]);\n",
+            "  new Promise((resolve) => setTimeout(resolve, 10));\n",
+            "  fetch('/api')\n",
+            "    .then((res) => res.json())\n",
+            "    .finally(() => done());\n",
+            "  (fetch('/api') as Promise<Response>).catch(log).finally(done);\n",
+            "  users.forEach((u) => { save(u); });\n",
+            "  queue.push(save(users[0]));\n",
+            "  this.save(users[0]);\n",
+            "  return save(users[0]).then(() => users.length);\n",
+            "}\n",
+            "\n",
+            "const handler = () => save(current);\n",
+        )).unwrap();
+
+        let result = ErrorHandlingAnalyzer::new().analyze(&[path.to_string_lossy().to_string()]);
+        let flagged: Vec<(u32, &str)> = result.gaps.iter()
+            .filter(|g| g.gap_type == GapType::UnhandledPromise)
+            .map(|g| (g.line, g.function.as_str()))
+            .collect();
+        assert_eq!(flagged, vec![
+            (3, "patterns"),
+            (5, "patterns"),
+            (12, "patterns"),
+            (13, "patterns"),
+            (17, "patterns"),
+            (19, "patterns"),
+        ]);
+        let gap = result.gaps.iter().find(|g| g.gap_type == GapType::UnhandledPromise && g.line == 19).unwrap();
+        assert_eq!(gap.description, "Promise from 'this.save()' is neither awaited, returned nor caught");
+    }
+}
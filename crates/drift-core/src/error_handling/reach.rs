@@ -0,0 +1,124 @@
+//! Call graph reach - What try blocks reach, and what entry points reach
+//!
+//! A try block reaches whatever its calls resolve to in the call graph,
+//! transitively. Writes are `write` and `delete` data accesses; external
+//! calls are accesses on `external:<host>` sinks, whatever their method.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use super::types::ReachableWrite;
+use crate::boundaries::EXTERNAL_TABLE_PREFIX;
+use crate::call_graph::{CallGraphDb, DataAccessRef, DataOperation, FunctionEntry};
+
+/// Call graph functions, the writes each performs itself, and the ones
+/// entry points reach
+pub(crate) struct ReachGraph {
+    /// Project root the call graph's paths are relative to
+    root: PathBuf,
+    functions: HashMap<String, FunctionEntry>,
+    by_file: HashMap<String, Vec<String>>,
+    from_entry_points: HashSet<String>,
+}
+
+impl ReachGraph {
+    pub fn load(root: &Path, db: &CallGraphDb) -> Result<Self, String> {
+        let mut functions: HashMap<String, FunctionEntry> = db.get_all_functions()
+            .map_err(|e| format!("Failed to read call graph: {}", e))?
+            .into_iter()
+            .map(|f| (f.id.clone(), f))
+            .collect();
+        for id in db.get_data_accessors().map_err(|e| format!("Failed to read call graph: {}", e))? {
+            let access = db.get_data_access(&id).map_err(|e| format!("Failed to read call graph: {}", e))?;
+            if let Some(function) = functions.get_mut(&id) {
+                function.data_access = access.into_iter().filter(is_write).collect();
+            }
+        }
+        let mut by_file: HashMap<String, Vec<String>> = HashMap::new();
+        for id in functions.keys() {
+            by_file.entry(CallGraphDb::extract_file_from_id(id).to_string()).or_default().push(id.clone());
+        }
+        
+        let mut from_entry_points: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<&str> = functions.values()
+            .filter(|f| f.is_entry_point)
+            .map(|f| f.id.as_str())
+            .collect();
+        while let Some(id) = queue.pop_front() {
+            if !from_entry_points.insert(id.to_string()) {
+                continue;
+            }
+            if let Some(function) = functions.get(id) {
+                queue.extend(function.calls.iter().filter_map(|c| c.resolved_id.as_deref()));
+            }
+        }
+        Ok(Self { root: root.to_path_buf(), functions, by_file, from_entry_points })
+    }
+    
+    pub fn function(&self, id: &str) -> Option<&FunctionEntry> {
+        self.functions.get(id)
+    }
+    
+    pub fn entry_points(&self) -> impl Iterator<Item = &FunctionEntry> {
+        self.functions.values().filter(|f| f.is_entry_point)
+    }
+    
+    /// Whether the innermost function around `line` of `file` is reachable
+    /// from an entry point
+    pub fn reachable_from_entry_point(&self, file: &str, line: u32) -> bool {
+        self.functions_in(file)
+            .filter(|f| f.start_line <= line && line <= f.end_line)
+            .min_by_key(|f| f.end_line - f.start_line)
+            .is_some_and(|f| self.from_entry_points.contains(&f.id))
+    }
+    
+    fn functions_in(&self, file: &str) -> impl Iterator<Item = &FunctionEntry> {
+        let file = Path::new(file).strip_prefix(&self.root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| file.to_string());
+        self.by_file.get(&file).into_iter().flatten().filter_map(|id| self.functions.get(id))
+    }
+
+    /// Writes reached from lines `start..=end` of `file`
+    pub fn reachable_writes(&self, file: &str, start: u32, end: u32) -> Vec<ReachableWrite> {
+        let in_block = |line: u32| start <= line && line <= end;
+
+        let mut writes = Vec::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        for function in self.functions_in(file) {
+            writes.extend(function.data_access.iter()
+                .filter(|access| in_block(access.line))
+                .map(|access| reachable_write(function, access)));
+            for target in function.calls.iter().filter(|c| in_block(c.line)).filter_map(|c| c.resolved_id.as_deref()) {
+                if visited.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        while let Some(id) = queue.pop_front() {
+            let Some(function) = self.functions.get(id) else { continue };
+            writes.extend(function.data_access.iter().map(|access| reachable_write(function, access)));
+            for target in function.calls.iter().filter_map(|c| c.resolved_id.as_deref()) {
+                if visited.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        writes.sort_by(|a, b| (&a.table, &a.function, a.operation as u8).cmp(&(&b.table, &b.function, b.operation as u8)));
+        writes.dedup();
+        writes
+    }
+}
+
+fn is_write(access: &DataAccessRef) -> bool {
+    access.operation != DataOperation::Read || access.table.starts_with(EXTERNAL_TABLE_PREFIX)
+}
+
+pub(crate) fn reachable_write(function: &FunctionEntry, access: &DataAccessRef) -> ReachableWrite {
+    ReachableWrite {
+        function: function.id.clone(),
+        table: access.table.clone(),
+        operation: access.operation,
+    }
+}
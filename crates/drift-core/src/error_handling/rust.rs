@@ -0,0 +1,222 @@
+//! Rust panics - Panicking calls, macros and indexing outside tests
+//!
+//! `.unwrap()` and `.expect()` (on a poisoned `Mutex::lock()` too),
+//! `panic!`, `todo!` and `unimplemented!`, and indexing, which panics out of
+//! bounds. Test files, `#[cfg(test)]` modules and `#[test]` functions are
+//! skipped.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tree_sitter::Node;
+
+use super::types::{GapSeverity, GapType};
+use crate::parsers::{Language, ParseResult};
+use crate::test_topology::is_test_path;
+
+/// `#[test]`, `#[tokio::test]`, `#[rstest]`, `#[cfg(test)]`
+static TEST_ATTRIBUTE: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"^#\[\s*(?:cfg\s*\(\s*test\s*\)|(?:\w+::)*(?:test|rstest)\b)"
+).unwrap());
+/// Integration tests, benches and examples
+static TEST_DIRECTORY: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|[/\\])(?:tests|benches|examples)[/\\]").unwrap());
+
+/// A place Rust code can panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RustPanic {
+    pub line: u32,
+    /// Enclosing function; `None` in constants and statics
+    pub function: Option<String>,
+    pub gap_type: GapType,
+    pub severity: GapSeverity,
+    pub description: String,
+}
+
+/// Panics in the non-test code of a Rust file
+pub(crate) fn find_rust_panics(result: &ParseResult, file: &str, source: &str) -> Vec<RustPanic> {
+    if result.language != Language::Rust || is_test_path(file) || TEST_DIRECTORY.is_match(file) {
+        return Vec::new();
+    }
+    let Some(tree) = result.tree.as_ref() else { return Vec::new() };
+    let source = source.as_bytes();
+
+    let mut panics = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "function_item" | "mod_item" | "impl_item") && is_test_item(node, source) {
+            continue;
+        }
+        if let Some((gap_type, severity, description)) = panic_kind(node, source) {
+            panics.push(RustPanic {
+                line: node.start_position().row as u32,
+                function: enclosing_function(node, source),
+                gap_type,
+                severity,
+                description,
+            });
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    panics.sort_by_key(|p| p.line);
+    panics
+}
+
+fn panic_kind(node: Node, source: &[u8]) -> Option<(GapType, GapSeverity, String)> {
+    match node.kind() {
+        "call_expression" => {
+            let function = node.child_by_field_name("function").filter(|f| f.kind() == "field_expression")?;
+            let method = text(function.child_by_field_name("field")?, source);
+            if !matches!(method, "unwrap" | "expect") {
+                return None;
+            }
+            // lock().unwrap() only panics when another thread panicked holding the lock
+            let on_lock = function.child_by_field_name("value")
+                .filter(|value| value.kind() == "call_expression")
+                .and_then(|value| value.child_by_field_name("function"))
+                .filter(|f| f.kind() == "field_expression")
+                .and_then(|f| f.child_by_field_name("field"))
+                .is_some_and(|field| text(field, source) == "lock");
+            Some(if on_lock {
+                (GapType::UnwrapWithoutCheck, GapSeverity::Low, format!("Use of .{}() on a Mutex lock panics if the lock is poisoned", method))
+            } else if method == "unwrap" {
+                (GapType::UnwrapWithoutCheck, GapSeverity::High, "Use of .unwrap() can panic on error".to_string())
+            } else {
+                (GapType::UnwrapWithoutCheck, GapSeverity::Medium, "Use of .expect() can panic on error".to_string())
+            })
+        }
+        "macro_invocation" => {
+            let name = text(node.child_by_field_name("macro")?, source);
+            match name.rsplit("::").next().unwrap_or(name) {
+                "panic" => Some((GapType::ExplicitPanic, GapSeverity::Medium, "panic! aborts the current thread".to_string())),
+                name @ ("todo" | "unimplemented") => {
+                    Some((GapType::ExplicitPanic, GapSeverity::High, format!("{}! panics when reached", name)))
+                }
+                _ => None,
+            }
+        }
+        // v[..] never panics
+        "index_expression" if node.named_child(1).is_some_and(|index| text(index, source) != "..") => {
+            Some((GapType::UncheckedIndex, GapSeverity::Low, "Indexing panics when out of bounds".to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a test attribute sits on `item`, past doc comments and other
+/// attributes
+fn is_test_item(item: Node, source: &[u8]) -> bool {
+    let mut sibling = item.prev_sibling();
+    while let Some(node) = sibling.filter(|s| matches!(s.kind(), "attribute_item" | "line_comment" | "block_comment")) {
+        if node.kind() == "attribute_item" && TEST_ATTRIBUTE.is_match(text(node, source)) {
+            return true;
+        }
+        sibling = node.prev_sibling();
+    }
+    false
+}
+
+fn enclosing_function(node: Node, source: &[u8]) -> Option<String> {
+    let mut parent = node.parent();
+    while let Some(node) = parent {
+        if node.kind() == "function_item" {
+            return node.child_by_field_name("name").map(|name| text(name, source).to_string());
+        }
+        parent = node.parent();
+    }
+    None
+}
+
+fn text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::call_graph::fixtures::{resolved_call_at, sample_db, spanning};
+    use crate::call_graph::FunctionEntry;
+    use crate::error_handling::*;
+
+    #[test]
+    fn test_rust_panics() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::write(root.join("src/api.rs"), concat!(
+            "use std::sync::Mutex;\n",
+            "\n",
+            "pub fn handle(req: &Request, cache: &Mutex<Cache>) -> Response {\n",
+            "    let id = req.params.get(\"id\").unwrap();\n",
+            "    let entry = cache.lock().unwrap();\n",
+            "    let first = req.items[0];\n",
+            "    let all = &req.items[..];\n",
+            "    render(id).expect(\"template renders\")\n",
+            "}\n",
+            "\n",
+            "fn migrate() {\n",
+            "    todo!()\n",
+            "}\n",
+            "\n",
+            "fn fail(reason: &str) -> ! {\n",
+            "    std::panic!(\"{}\", reason)\n",
+            "}\n",
+            "\n",
+            "static ID: Lazy<Regex> = Lazy::new(|| Regex::new(\"[0-9]+\").unwrap());\n",
+            "\n",
+            "#[cfg(test)]\n",
+            "mod tests {\n",
+            "    #[test]\n",
+            "    fn parses() {\n",
+            "        parse(\"1\").unwrap();\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "#[test]\n",
+            "/// Runs on its own\n",
+            "fn standalone() {\n",
+            "    unimplemented!()\n",
+            "}\n",
+        )).unwrap();
+        std::fs::write(root.join("tests/api.rs"), "#[test]\nfn handles() {\n    handle(&req()).unwrap();\n}\n").unwrap();
+        let files: Vec<String> = ["src/api.rs", "tests/api.rs"].iter()
+            .map(|path| root.join(path).to_string_lossy().to_string())
+            .collect();
+
+        let panics = |result: &ErrorHandlingResult| -> Vec<(u32, String, GapType, GapSeverity)> {
+            result.gaps.iter().map(|g| (g.line, g.function.clone(), g.gap_type, g.severity)).collect()
+        };
+        let gap = |line: u32, function: &str, gap_type: GapType, severity: GapSeverity| {
+            (line, function.to_string(), gap_type, severity)
+        };
+        let result = ErrorHandlingAnalyzer::new().analyze(&files);
+        assert_eq!(panics(&result), vec![
+            gap(3, "handle", GapType::UnwrapWithoutCheck, GapSeverity::High),
+            gap(4, "handle", GapType::UnwrapWithoutCheck, GapSeverity::Low),
+            gap(5, "handle", GapType::UncheckedIndex, GapSeverity::Low),
+            gap(7, "handle", GapType::UnwrapWithoutCheck, GapSeverity::Medium),
+            gap(11, "migrate", GapType::ExplicitPanic, GapSeverity::High),
+            gap(15, "fail", GapType::ExplicitPanic, GapSeverity::Medium),
+            gap(18, "unknown", GapType::UnwrapWithoutCheck, GapSeverity::High),
+        ]);
+
+        // handle is an entry point and calls fail; nothing calls migrate
+        let handle = spanning("src/api.rs:handle:2", 8, vec![resolved_call_at("src/api.rs:fail:14", 7)]);
+        let db = sample_db(root, vec![
+            FunctionEntry { is_entry_point: true, is_exported: true, ..handle },
+            spanning("src/api.rs:migrate:10", 12, vec![]),
+            spanning("src/api.rs:fail:14", 16, vec![]),
+        ]);
+
+        let result = ErrorHandlingAnalyzer::new().with_call_graph(root, &db).unwrap().analyze(&files);
+        assert_eq!(panics(&result), vec![
+            gap(3, "handle", GapType::UnwrapWithoutCheck, GapSeverity::Critical),
+            gap(4, "handle", GapType::UnwrapWithoutCheck, GapSeverity::Medium),
+            gap(5, "handle", GapType::UncheckedIndex, GapSeverity::Medium),
+            gap(7, "handle", GapType::UnwrapWithoutCheck, GapSeverity::High),
+            gap(11, "migrate", GapType::ExplicitPanic, GapSeverity::High),
+            gap(15, "fail", GapType::ExplicitPanic, GapSeverity::High),
+            gap(18, "unknown", GapType::UnwrapWithoutCheck, GapSeverity::High),
+        ]);
+        assert_eq!(result.gaps[0].description, "Use of .unwrap() can panic on error, reachable from an entry point");
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::call_graph::DataOperation;
+
 /// An error boundary (try/catch, error handler)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorBoundary {
@@ -19,8 +21,27 @@ pub struct ErrorBoundary {
     pub rethrows: bool,
     /// Whether error is logged
     pub logs_error: bool,
-    /// Whether error is swallowed (empty catch)
+    /// Whether error is swallowed: neither logged nor rethrown
     pub is_swallowed: bool,
+    /// Framework an error handler is registered with: express, koa,
+    /// fastify or nestjs
+    #[serde(default)]
+    pub framework: Option<String>,
+    /// Routes an error handler covers
+    #[serde(default)]
+    pub scope: Option<HandlerScope>,
+}
+
+/// Routes a framework error handler covers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "name", rename_all = "snake_case")]
+pub enum HandlerScope {
+    /// Every route of the application
+    Global,
+    /// Routes registered on the named router in the same file
+    Router(String),
+    /// Controllers and handlers naming the NestJS filter in `@UseFilters()`
+    UseFilters,
 }
 
 /// Type of error boundary
@@ -52,6 +73,20 @@ pub struct ErrorGap {
     pub severity: GapSeverity,
     /// Description
     pub description: String,
+    /// Data writes and external calls a swallowing catch's try block
+    /// reaches, which make the swallow critical
+    #[serde(default)]
+    pub reachable_writes: Vec<ReachableWrite>,
+}
+
+/// A data write or external call reachable through the call graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachableWrite {
+    /// Call graph function performing the write
+    pub function: String,
+    /// Table written, or `external:<host>` for an outbound request
+    pub table: String,
+    pub operation: DataOperation,
 }
 
 /// Type of error handling gap
@@ -63,6 +98,10 @@ pub enum GapType {
     MissingCatch,
     SwallowedError,
     UnwrapWithoutCheck,
+    /// `panic!`, `todo!`, `unimplemented!`
+    ExplicitPanic,
+    /// Indexing that panics out of bounds
+    UncheckedIndex,
     UncheckedResult,
     MissingErrorBoundary,
 }
@@ -90,6 +129,15 @@ pub struct ErrorType {
     pub extends: Option<String>,
     /// Is exported
     pub is_exported: bool,
+    /// Error types directly extending this one
+    #[serde(default)]
+    pub children: Vec<String>,
+    /// Places constructing or raising the type
+    #[serde(default)]
+    pub throw_count: usize,
+    /// Neither the type nor any type below it is ever constructed
+    #[serde(default)]
+    pub is_unused: bool,
 }
 
 /// Error handling analysis result
@@ -106,3 +154,52 @@ pub struct ErrorHandlingResult {
     /// Duration in milliseconds
     pub duration_ms: u64,
 }
+
+/// What error coverage analysis walks
+#[derive(Debug, Clone, Default)]
+pub struct ErrorCoverageOptions {
+    /// Deepest call chain followed from an entry point (unlimited when `None`)
+    pub max_depth: Option<usize>,
+    /// Skip entry points in test files
+    pub exclude_tests: bool,
+}
+
+/// A step of a call path: a call made, or the write at the end
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathStep {
+    /// Call graph function id
+    pub function: String,
+    /// Line of the call to the next step, or of the write on the last
+    pub line: u32,
+}
+
+/// An entry point reaching data writes with no boundary that logs or
+/// rethrows on the way
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncoveredEntryPoint {
+    /// Call graph function id
+    pub entry_point: String,
+    pub file: String,
+    pub line: u32,
+    /// Writes reached outside every logging or rethrowing boundary
+    pub uncovered_writes: Vec<ReachableWrite>,
+    /// Shortest uncovered path, from the entry point to the first write
+    pub example_path: Vec<PathStep>,
+    /// Gaps in the functions along the example path
+    pub gaps: Vec<ErrorGap>,
+}
+
+/// Error boundary coverage of the write paths from each entry point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorCoverageResult {
+    /// Entry points walked
+    pub entry_points: usize,
+    /// Entry points whose every write path passes a boundary
+    pub covered_entry_points: usize,
+    /// Entry points with uncovered write paths, by file and line
+    pub uncovered: Vec<UncoveredEntryPoint>,
+    /// Files analyzed for boundaries
+    pub files_analyzed: usize,
+    /// Duration in milliseconds
+    pub duration_ms: u64,
+}
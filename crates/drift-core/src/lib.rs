@@ -39,7 +39,7 @@ pub use call_graph::{
 };
 pub use boundaries::{
     BoundaryScanner, BoundaryScanResult, DataAccessPoint, DataOperation,
-    SensitiveField, SensitivityType, ORMModel,
+    SensitiveField, SensitivityType, ORMModel, UnmigratedField,
 };
 pub use coupling::{
     CouplingAnalyzer, CouplingAnalysisResult, ModuleMetrics,
@@ -50,8 +50,8 @@ pub use test_topology::{
     TestFramework, TestType, MockUsage, MockType, TestCoverage, RiskLevel,
 };
 pub use error_handling::{
-    ErrorHandlingAnalyzer, ErrorHandlingResult, ErrorBoundary, BoundaryType,
-    ErrorGap, GapType, GapSeverity, ErrorType,
+    ErrorHandlingAnalyzer, ErrorHandlingResult, ErrorBoundary, BoundaryType, HandlerScope,
+    ErrorGap, GapType, GapSeverity, ErrorType, ReachableWrite,
 };
 pub use reachability::{
     ReachabilityEngine, ReachabilityResult, ReachabilityOptions,
@@ -65,7 +65,7 @@ pub use reachability::{
 pub use unified::{
     UnifiedAnalyzer, UnifiedOptions, UnifiedResult, FilePatterns,
     DetectedPattern, DetectionMethod, PatternCategory, Language as UnifiedLanguage,
-    Violation, ViolationSeverity, ResolutionStats, CallGraphSummary, AnalysisMetrics,
+    Violation, ViolationSeverity, ResolutionStats, StrategyCount, CallGraphSummary, AnalysisMetrics,
 };
 pub use constants::{
     ConstantsAnalyzer, ConstantsResult, ConstantInfo, ConstantCategory,
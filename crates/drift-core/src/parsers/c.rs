@@ -127,6 +127,7 @@ impl CParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -137,6 +138,8 @@ impl CParser {
         
         let mut result = ParseResult::with_tree(Language::C, tree.clone());
         
+        result.record_syntax_errors(&root, source_bytes);
+        
         self.extract_functions(&root, source_bytes, &mut result);
         self.extract_structs(&root, source_bytes, &mut result);
         self.extract_includes(&root, source_bytes, &mut result);
@@ -193,6 +196,9 @@ impl CParser {
                     is_exported: true,
                     is_async: false,
                     is_generator: false,
+                    visibility: Visibility::Public,
+                    is_static: false,
+                    type_parameters: Vec::new(),
                     range,
                     decorators: Vec::new(),
                     doc_comment,
@@ -401,6 +407,9 @@ impl CParser {
                     properties,
                     range,
                     decorators: Vec::new(),
+                    namespace: None,
+                    is_partial: false,
+                    type_parameters: Vec::new(),
                 });
             }
         }
@@ -496,6 +505,9 @@ impl CParser {
             is_readonly: false,
             visibility: Visibility::Public, // C doesn't have visibility modifiers
             tags: None,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
 
@@ -530,6 +542,7 @@ impl CParser {
                     default: None,
                     namespace: None,
                     is_type_only: false,
+                    relative_level: 0,
                     range,
                 });
             }
@@ -573,6 +586,7 @@ impl CParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg: None,
                 });
             }
         }
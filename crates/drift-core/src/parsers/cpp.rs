@@ -3,10 +3,20 @@
 //! Extracts functions, classes, includes, and call sites from C++ code.
 //! Supports Boost, Qt, and other framework patterns.
 
+use std::collections::HashMap;
 use std::time::Instant;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 use super::types::*;
+use super::generics::template_type_parameters;
+
+/// Access and modifiers of a member function, from its in-class declaration
+struct MemberTraits {
+    /// `virtual`, `override`, `final` and `pure_virtual`
+    markers: Vec<String>,
+    visibility: Visibility,
+    is_static: bool,
+}
 
 /// C++ parser
 pub struct CppParser {
@@ -37,9 +47,7 @@ impl CppParser {
             
             (function_definition
                 declarator: (function_declarator
-                    declarator: (qualified_identifier
-                        name: (identifier) @name
-                    )
+                    declarator: (qualified_identifier) @qualified
                     parameters: (parameter_list) @params
                 )
             ) @method
@@ -71,7 +79,6 @@ impl CppParser {
             r#"
             (class_specifier
                 name: (type_identifier) @name
-                (base_class_clause (type_identifier) @base)*
             ) @class
             
             (struct_specifier
@@ -131,6 +138,7 @@ impl CppParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -141,8 +149,37 @@ impl CppParser {
         
         let mut result = ParseResult::with_tree(Language::Cpp, tree.clone());
         
+        result.record_syntax_errors(&root, source_bytes);
+        
         self.extract_functions(&root, source_bytes, &mut result);
-        self.extract_classes(&root, source_bytes, &mut result);
+        let member_traits = self.extract_classes(&root, source_bytes, &mut result);
+        
+        // Link inline and out-of-line method definitions to classes declared in this file,
+        // carrying over access, static and virtual/override markers from the in-class declaration
+        for func in &mut result.functions {
+            let owner = func.qualified_name.as_deref()
+                .and_then(|q| q.rsplit_once("::"))
+                .map(|(owner, _)| owner.to_string());
+            if let Some(owner) = owner {
+                if let Some(traits) = member_traits.get(&owner).and_then(|m| m.get(&func.name)) {
+                    for marker in &traits.markers {
+                        if !func.decorators.contains(marker) {
+                            func.decorators.push(marker.clone());
+                        }
+                    }
+                    func.visibility = traits.visibility;
+                    func.is_static = traits.is_static;
+                }
+                let class = result.classes.iter_mut().find(|c| match &c.namespace {
+                    Some(ns) => owner == format!("{}::{}", ns, c.name),
+                    None => owner == c.name,
+                });
+                if let Some(class) = class {
+                    class.methods.push(func.clone());
+                }
+            }
+        }
+        
         self.extract_includes(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         
@@ -160,6 +197,7 @@ impl CppParser {
             let mut parameters = Vec::new();
             let mut return_type: Option<String> = None;
             let mut function_node: Option<Node> = None;
+            let mut qualified: Option<String> = None;
             
             for capture in m.captures {
                 let node = capture.node;
@@ -169,6 +207,15 @@ impl CppParser {
                     "name" => {
                         name = node.utf8_text(source).unwrap_or("").to_string();
                     }
+                    "qualified" => {
+                        // `void ns::MyClass::process()` - the name is the innermost segment
+                        let mut innermost = node;
+                        while let Some(inner) = innermost.child_by_field_name("name") {
+                            innermost = inner;
+                        }
+                        name = innermost.utf8_text(source).unwrap_or("").to_string();
+                        qualified = Some(strip_template_args(node.utf8_text(source).unwrap_or("")));
+                    }
                     "params" => {
                         parameters = self.extract_parameters(&node, source);
                     }
@@ -189,14 +236,33 @@ impl CppParser {
             if !name.is_empty() {
                 let doc_comment = function_node.and_then(|n| self.extract_doc_comment(&n, source));
                 
+                // Qualify with the enclosing namespaces and classes
+                let (namespace, is_anonymous) = function_node
+                    .map(|n| self.enclosing_namespace(&n, source))
+                    .unwrap_or((None, false));
+                let mut scope: Vec<String> = namespace.into_iter().collect();
+                scope.extend(function_node.map(|n| enclosing_classes(&n, source)).unwrap_or_default());
+                scope.push(qualified.unwrap_or_else(|| name.clone()));
+                let qualified_name = if scope.len() > 1 || scope[0] != name {
+                    Some(scope.join("::"))
+                } else {
+                    None
+                };
+                
                 result.functions.push(FunctionInfo {
                     name,
-                    qualified_name: None,
+                    qualified_name,
                     parameters,
                     return_type,
-                    is_exported: true, // C++ doesn't have export in same sense
+                    // C++ doesn't have export in same sense - only anonymous namespaces hide symbols
+                    is_exported: !is_anonymous,
                     is_async: false,
                     is_generator: false,
+                    visibility: Visibility::Public,
+                    is_static: false,
+                    type_parameters: function_node
+                        .map(|n| template_type_parameters(&n, source))
+                        .unwrap_or_default(),
                     range,
                     decorators: Vec::new(),
                     doc_comment,
@@ -381,13 +447,15 @@ impl CppParser {
         }
     }
     
-    fn extract_classes(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    /// Extract classes, returning the traits of their member functions
+    /// keyed by qualified class name, then member name
+    fn extract_classes(&self, root: &Node, source: &[u8], result: &mut ParseResult) -> HashMap<String, HashMap<String, MemberTraits>> {
         let mut cursor = QueryCursor::new();
         let matches = cursor.matches(&self.class_query, *root, source);
+        let mut member_traits = HashMap::new();
         
         for m in matches {
             let mut name = String::new();
-            let mut bases = Vec::new();
             let mut range = Range::new(0, 0, 0, 0);
             let mut class_node: Option<Node> = None;
             let mut is_struct = false;
@@ -400,9 +468,6 @@ impl CppParser {
                     "name" => {
                         name = node.utf8_text(source).unwrap_or("").to_string();
                     }
-                    "base" => {
-                        bases.push(node.utf8_text(source).unwrap_or("").to_string());
-                    }
                     "class" => {
                         range = node_range(&node);
                         class_node = Some(node);
@@ -422,20 +487,141 @@ impl CppParser {
                     .unwrap_or_default();
                 
                 let doc_comment = class_node.and_then(|n| self.extract_doc_comment(&n, source));
+                let (namespace, is_anonymous) = class_node
+                    .map(|n| self.enclosing_namespace(&n, source))
+                    .unwrap_or((None, false));
+                let bases = class_node
+                    .map(|n| self.extract_base_classes(&n, source))
+                    .unwrap_or_default();
+                let traits = class_node
+                    .map(|n| self.extract_member_traits(&n, source, is_struct))
+                    .unwrap_or_default();
+                // A class with any pure virtual member (`= 0`) cannot be instantiated
+                let is_abstract = traits.values().any(|t| t.markers.iter().any(|marker| marker == "pure_virtual"));
+                
+                let qualified = match &namespace {
+                    Some(ns) => format!("{}::{}", ns, name),
+                    None => name.clone(),
+                };
+                member_traits.insert(qualified, traits);
                 
                 result.classes.push(ClassInfo {
                     name,
                     extends: bases.first().cloned(),
                     implements: bases.into_iter().skip(1).collect(),
-                    is_exported: true,
-                    is_abstract: false,
+                    is_exported: !is_anonymous,
+                    is_abstract,
                     methods: Vec::new(),
                     properties,
                     range,
                     decorators: if doc_comment.is_some() { vec![] } else { vec![] },
+                    namespace,
+                    is_partial: false,
+                    type_parameters: class_node
+                        .map(|n| template_type_parameters(&n, source))
+                        .unwrap_or_default(),
                 });
             }
         }
+        
+        member_traits
+    }
+    
+    /// Base classes from the `base_class_clause`, in declaration order (`: public A, protected ns::B`)
+    fn extract_base_classes(&self, class_node: &Node, source: &[u8]) -> Vec<String> {
+        let mut bases = Vec::new();
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
+            if child.kind() != "base_class_clause" {
+                continue;
+            }
+            let mut base_cursor = child.walk();
+            for base in child.named_children(&mut base_cursor) {
+                if matches!(base.kind(), "type_identifier" | "qualified_identifier" | "template_type") {
+                    bases.push(base.utf8_text(source).unwrap_or("").to_string());
+                }
+            }
+        }
+        bases
+    }
+    
+    /// Access, `static` and `virtual`/`override`/`final`/`pure_virtual` (`= 0`)
+    /// markers of each member function
+    fn extract_member_traits(&self, class_node: &Node, source: &[u8], is_struct: bool) -> HashMap<String, MemberTraits> {
+        let mut traits: HashMap<String, MemberTraits> = HashMap::new();
+        let body = match class_node.child_by_field_name("body") {
+            Some(body) => body,
+            None => return traits,
+        };
+        
+        let mut visibility = if is_struct { Visibility::Public } else { Visibility::Private };
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            if member.kind() == "access_specifier" {
+                visibility = match member.utf8_text(source).unwrap_or("").trim_end_matches(':') {
+                    "public" => Visibility::Public,
+                    "protected" => Visibility::Protected,
+                    "private" => Visibility::Private,
+                    _ => visibility,
+                };
+                continue;
+            }
+            if member.kind() != "field_declaration" && member.kind() != "function_definition" {
+                continue;
+            }
+            let declarator = match member.child_by_field_name("declarator") {
+                Some(d) if d.kind() == "function_declarator" => d,
+                _ => continue,
+            };
+            let name = match declarator.child_by_field_name("declarator") {
+                Some(n) => n.utf8_text(source).unwrap_or("").to_string(),
+                None => continue,
+            };
+            
+            let mut member_markers = Vec::new();
+            let mut member_cursor = member.walk();
+            if member.children(&mut member_cursor).any(|c| c.kind() == "virtual") {
+                member_markers.push("virtual".to_string());
+            }
+            let mut decl_cursor = declarator.walk();
+            for specifier in declarator.children(&mut decl_cursor).filter(|c| c.kind() == "virtual_specifier") {
+                member_markers.push(specifier.utf8_text(source).unwrap_or("").to_string());
+            }
+            let is_pure = member.child_by_field_name("default_value")
+                .is_some_and(|v| v.utf8_text(source).unwrap_or("") == "0");
+            if is_pure {
+                member_markers.push("pure_virtual".to_string());
+            }
+            
+            let mut member_cursor = member.walk();
+            let is_static = member.children(&mut member_cursor)
+                .any(|c| c.kind() == "storage_class_specifier" && c.utf8_text(source).unwrap_or("") == "static");
+            traits.insert(name, MemberTraits { markers: member_markers, visibility, is_static });
+        }
+        traits
+    }
+    
+    /// Enclosing named namespaces joined with "::", and whether any enclosing namespace is anonymous
+    fn enclosing_namespace(&self, node: &Node, source: &[u8]) -> (Option<String>, bool) {
+        let mut parts = Vec::new();
+        let mut is_anonymous = false;
+        let mut current = node.parent();
+        while let Some(parent) = current {
+            if parent.kind() == "namespace_definition" {
+                match parent.child_by_field_name("name") {
+                    // `namespace a::b { }` is a nested_namespace_specifier
+                    Some(name) => parts.push(name.utf8_text(source).unwrap_or("").to_string()),
+                    None => is_anonymous = true,
+                }
+            }
+            current = parent.parent();
+        }
+        
+        if parts.is_empty() {
+            return (None, is_anonymous);
+        }
+        parts.reverse();
+        (Some(parts.join("::")), is_anonymous)
     }
     
     /// Extract class/struct members (fields)
@@ -542,6 +728,9 @@ impl CppParser {
             is_readonly: is_const,
             visibility: Visibility::Private, // Will be overwritten by caller
             tags: None,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
 
@@ -577,6 +766,7 @@ impl CppParser {
                     default: None,
                     namespace: None,
                     is_type_only: false,
+                    relative_level: 0,
                     range,
                 });
             }
@@ -620,6 +810,7 @@ impl CppParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg: None,
                 });
             }
         }
@@ -632,6 +823,34 @@ impl Default for CppParser {
     }
 }
 
+/// Names of the classes/structs enclosing an inline method definition, outermost first
+fn enclosing_classes(node: &Node, source: &[u8]) -> Vec<String> {
+    let mut classes = Vec::new();
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "class_specifier" || parent.kind() == "struct_specifier" {
+            if let Some(name) = parent.child_by_field_name("name") {
+                classes.push(name.utf8_text(source).unwrap_or("").to_string());
+            }
+        }
+        current = parent.parent();
+    }
+    classes.reverse();
+    classes
+}
+
+/// Drop template arguments and whitespace: "Repo<T>::find" gives "Repo::find"
+fn strip_template_args(qualified: &str) -> String {
+    let mut depth = 0;
+    qualified.chars()
+        .filter(|c| match c {
+            '<' => { depth += 1; false }
+            '>' => { depth = (depth - 1).max(0); false }
+            c => depth == 0 && !c.is_whitespace(),
+        })
+        .collect()
+}
+
 fn node_range(node: &Node) -> Range {
     Range {
         start: Position {
@@ -701,7 +920,56 @@ mod tests {
         let source = "class IRepository { public: virtual void save() = 0; virtual void load() = 0; };";
         let result = parser.parse(source);
         assert_eq!(result.classes.len(), 1);
-        // Note: is_abstract detection requires body capture which may not work for inline classes
+        assert!(result.classes[0].is_abstract);
+        
+        let concrete = parser.parse("class Repository { public: virtual void save() {} };");
+        assert!(!concrete.classes[0].is_abstract);
+    }
+    
+    #[test]
+    fn test_parse_multiple_inheritance() {
+        let mut parser = CppParser::new().unwrap();
+        let source = "class UserRepo : public Base, public IReadable, protected io::IWritable, public Mixin<UserRepo> { };";
+        let result = parser.parse(source);
+        
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].extends.as_deref(), Some("Base"));
+        assert_eq!(result.classes[0].implements, vec!["IReadable", "io::IWritable", "Mixin<UserRepo>"]);
+    }
+    
+    #[test]
+    fn test_virtual_and_override_markers() {
+        let mut parser = CppParser::new().unwrap();
+        let source = r#"
+            class Shape {
+            public:
+                virtual double area() const = 0;
+                virtual void draw() { }
+            };
+            
+            class Circle : public Shape {
+            public:
+                double area() const override;
+                void draw() override final { }
+                void resize() { }
+            };
+            
+            double Circle::area() const { return 3.14; }
+        "#;
+        let result = parser.parse(source);
+        
+        assert!(result.classes.iter().find(|c| c.name == "Shape").unwrap().is_abstract);
+        assert!(!result.classes.iter().find(|c| c.name == "Circle").unwrap().is_abstract);
+        
+        let decorators = |qualified: &str| result.functions.iter()
+            .find(|f| f.qualified_name.as_deref() == Some(qualified))
+            .map(|f| f.decorators.clone())
+            .unwrap();
+        assert_eq!(decorators("Shape::draw"), vec!["virtual"]);
+        assert_eq!(decorators("Circle::draw"), vec!["override", "final"]);
+        // Out-of-line definition picks up the marker from the in-class declaration
+        assert_eq!(decorators("Circle::area"), vec!["override"]);
+        assert!(decorators("Circle::resize").is_empty());
     }
 
     #[test]
@@ -744,6 +1012,74 @@ mod tests {
         assert!(result.functions.iter().any(|f| f.name == "process"));
     }
 
+    #[test]
+    fn test_qualified_names_in_nested_namespaces() {
+        let mut parser = CppParser::new().unwrap();
+        let source = r#"
+            namespace acme {
+            namespace net {
+                class Server {
+                public:
+                    void start() { listen(); }
+                    void process();
+                };
+                
+                void Server::process() { handle(); }
+            }
+            }
+            
+            void acme::net::Server::stop() { }
+            
+            namespace acme::util {
+                int clamp(int v) { return v; }
+            }
+        "#;
+        let result = parser.parse(source);
+        
+        let qualified = |name: &str| result.functions.iter()
+            .find(|f| f.name == name)
+            .and_then(|f| f.qualified_name.clone());
+        assert_eq!(qualified("start").as_deref(), Some("acme::net::Server::start"));
+        assert_eq!(qualified("process").as_deref(), Some("acme::net::Server::process"));
+        assert_eq!(qualified("stop").as_deref(), Some("acme::net::Server::stop"));
+        assert_eq!(qualified("clamp").as_deref(), Some("acme::util::clamp"));
+        
+        let server = result.classes.iter().find(|c| c.name == "Server").unwrap();
+        assert_eq!(server.namespace.as_deref(), Some("acme::net"));
+        let mut methods: Vec<&str> = server.methods.iter().map(|m| m.name.as_str()).collect();
+        methods.sort();
+        assert_eq!(methods, vec!["process", "start", "stop"]);
+    }
+    
+    #[test]
+    fn test_anonymous_namespace_not_exported() {
+        let mut parser = CppParser::new().unwrap();
+        let source = r#"
+            namespace {
+                struct Cache { int size; };
+                int helper() { return 1; }
+            }
+            
+            namespace app {
+                namespace {
+                    void detail() { }
+                }
+                void run() { detail(); }
+            }
+        "#;
+        let result = parser.parse(source);
+        
+        let helper = result.functions.iter().find(|f| f.name == "helper").unwrap();
+        assert!(!helper.is_exported);
+        assert_eq!(helper.qualified_name, None);
+        let detail = result.functions.iter().find(|f| f.name == "detail").unwrap();
+        assert!(!detail.is_exported);
+        assert_eq!(detail.qualified_name.as_deref(), Some("app::detail"));
+        let run = result.functions.iter().find(|f| f.name == "run").unwrap();
+        assert!(run.is_exported);
+        assert!(!result.classes.iter().find(|c| c.name == "Cache").unwrap().is_exported);
+    }
+
     #[test]
     fn test_parse_template_function() {
         let mut parser = CppParser::new().unwrap();
@@ -17,12 +17,14 @@ use std::time::Instant;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 use super::types::*;
+use super::generics::declared_type_parameters;
 
 /// C# parser with enterprise attribute support
 pub struct CSharpParser {
     parser: Parser,
     method_query: Query,
     class_query: Query,
+    enum_query: Query,
     using_query: Query,
     call_query: Query,
 }
@@ -78,12 +80,28 @@ impl CSharpParser {
             "#,
         ).map_err(|e| format!("Failed to create class query: {}", e))?;
         
+        let enum_query = Query::new(
+            &language.into(),
+            r#"
+            (enum_declaration
+                (modifier)* @modifier
+                name: (identifier) @name
+                body: (enum_member_declaration_list) @members
+            ) @enum
+            "#,
+        ).map_err(|e| format!("Failed to create enum query: {}", e))?;
+        
         let using_query = Query::new(
             &language.into(),
             r#"
             (using_directive
                 (qualified_name) @namespace
             ) @using
+            
+            (using_directive
+                !name
+                (identifier) @namespace
+            ) @using
             "#,
         ).map_err(|e| format!("Failed to create using query: {}", e))?;
 
@@ -106,6 +124,8 @@ impl CSharpParser {
                 type: [(identifier) @callee (qualified_name (identifier) @callee) (generic_name (identifier) @callee)]
                 arguments: (argument_list)? @args
             ) @new_call
+            
+            (query_expression) @query
             "#,
         ).map_err(|e| format!("Failed to create call query: {}", e))?;
         
@@ -113,6 +133,7 @@ impl CSharpParser {
             parser,
             method_query,
             class_query,
+            enum_query,
             using_query,
             call_query,
         })
@@ -128,6 +149,7 @@ impl CSharpParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -138,8 +160,12 @@ impl CSharpParser {
         
         let mut result = ParseResult::with_tree(Language::CSharp, tree.clone());
         
+        result.record_syntax_errors(&root, source_bytes);
+        
         self.extract_methods(&root, source_bytes, &mut result);
         self.extract_classes(&root, source_bytes, &mut result);
+        result.attach_methods();
+        self.extract_enums(&root, source_bytes, &mut result);
         self.extract_usings(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         
@@ -155,6 +181,8 @@ impl CSharpParser {
             let mut name = String::new();
             let mut range = Range::new(0, 0, 0, 0);
             let mut is_public = false;
+            let mut visibility = Visibility::Private; // C# members default to private
+            let mut is_static = false;
             let mut is_async = false;
             let mut method_node: Option<Node> = None;
             let mut params_node: Option<Node> = None;
@@ -171,7 +199,12 @@ impl CSharpParser {
                     "modifier" => {
                         let modifier = node.utf8_text(source).unwrap_or("");
                         match modifier {
-                            "public" => is_public = true,
+                            "public" => {
+                                is_public = true;
+                                visibility = Visibility::Public;
+                            }
+                            "protected" => visibility = Visibility::Protected,
+                            "static" => is_static = true,
                             "async" => is_async = true,
                             _ => {}
                         }
@@ -208,14 +241,22 @@ impl CSharpParser {
                     .map(|n| self.extract_parameters(&n, source))
                     .unwrap_or_default();
                 
+                let qualified_name = method_node
+                    .and_then(|n| self.qualify_member(&n, &name, source));
+                
                 result.functions.push(FunctionInfo {
                     name,
-                    qualified_name: None,
+                    qualified_name,
                     parameters,
                     return_type,
                     is_exported: is_public,
                     is_async,
                     is_generator: false,
+                    visibility,
+                    is_static,
+                    type_parameters: method_node
+                        .map(|n| declared_type_parameters(&n, source))
+                        .unwrap_or_default(),
                     range,
                     decorators,
                     doc_comment,
@@ -519,6 +560,7 @@ impl CSharpParser {
             let mut range = Range::new(0, 0, 0, 0);
             let mut is_public = false;
             let mut is_abstract = false;
+            let mut is_partial = false;
             let mut class_node: Option<Node> = None;
             
             for capture in m.captures {
@@ -534,6 +576,7 @@ impl CSharpParser {
                         match modifier {
                             "public" => is_public = true,
                             "abstract" => is_abstract = true,
+                            "partial" => is_partial = true,
                             _ => {}
                         }
                     }
@@ -576,12 +619,62 @@ impl CSharpParser {
                     properties,
                     range,
                     decorators,
+                    namespace: class_node.and_then(|n| self.extract_enclosing_namespace(&n, source)),
+                    is_partial,
+                    type_parameters: class_node
+                        .map(|n| declared_type_parameters(&n, source))
+                        .unwrap_or_default(),
                 });
             }
         }
     }
     
     /// Extract base types (extends and implements) from a class declaration
+    fn extract_enums(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(&self.enum_query, *root, source);
+        
+        for m in matches {
+            let mut name = String::new();
+            let mut is_public = false;
+            let mut variants = Vec::new();
+            let mut enum_node: Option<Node> = None;
+            
+            for capture in m.captures {
+                let node = capture.node;
+                match self.enum_query.capture_names()[capture.index as usize] {
+                    "name" => name = node.utf8_text(source).unwrap_or("").to_string(),
+                    "modifier" => is_public |= node.utf8_text(source).unwrap_or("") == "public",
+                    "members" => {
+                        let mut member_cursor = node.walk();
+                        variants = node.named_children(&mut member_cursor)
+                            .filter(|n| n.kind() == "enum_member_declaration")
+                            .filter_map(|n| Some(EnumVariant {
+                                name: n.child_by_field_name("name")?.utf8_text(source).ok()?.to_string(),
+                                value: n.child_by_field_name("value")
+                                    .and_then(|v| v.utf8_text(source).ok())
+                                    .map(|v| v.to_string()),
+                                range: node_range(&n),
+                            }))
+                            .collect();
+                    }
+                    "enum" => enum_node = Some(node),
+                    _ => {}
+                }
+            }
+            
+            if let (false, Some(node)) = (name.is_empty(), enum_node) {
+                result.enums.push(EnumInfo {
+                    name,
+                    variants,
+                    is_exported: is_public,
+                    range: node_range(&node),
+                    decorators: self.extract_attributes_for_declaration(&node, source),
+                });
+            }
+        }
+    }
+    
     fn extract_base_types(&self, class_node: &Node, source: &[u8]) -> (Option<String>, Vec<String>) {
         let mut extends = None;
         let mut implements = Vec::new();
@@ -733,6 +826,9 @@ impl CSharpParser {
             is_readonly,
             visibility: if is_public { Visibility::Public } else { Visibility::Private },
             tags,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
     
@@ -832,47 +928,71 @@ impl CSharpParser {
             is_readonly,
             visibility: if is_public { Visibility::Public } else { Visibility::Private },
             tags,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
     
-    /// Extract the enclosing namespace (kept for potential future use)
-    #[allow(dead_code)]
+    /// Extract the enclosing namespace
+    ///
+    /// Nested block-scoped namespaces are joined (`namespace A { namespace B {`
+    /// gives "A.B"). A file-scoped `namespace Foo;` is a sibling of the
+    /// declarations it applies to, so it is looked up on the compilation unit.
     fn extract_enclosing_namespace(&self, node: &Node, source: &[u8]) -> Option<String> {
+        let mut parts = Vec::new();
+        let mut root = *node;
         let mut current = node.parent();
         while let Some(parent) = current {
             if parent.kind() == "namespace_declaration" {
-                // Find the namespace name
-                let mut cursor = parent.walk();
-                if cursor.goto_first_child() {
-                    loop {
-                        let child = cursor.node();
-                        if child.kind() == "qualified_name" || child.kind() == "identifier" {
-                            return Some(child.utf8_text(source).unwrap_or("").to_string());
-                        }
-                        if !cursor.goto_next_sibling() {
-                            break;
-                        }
-                    }
+                if let Some(name) = parent.child_by_field_name("name") {
+                    parts.push(name.utf8_text(source).unwrap_or("").to_string());
                 }
             }
-            // Also check for file-scoped namespace
-            if parent.kind() == "file_scoped_namespace_declaration" {
-                let mut cursor = parent.walk();
-                if cursor.goto_first_child() {
-                    loop {
-                        let child = cursor.node();
-                        if child.kind() == "qualified_name" || child.kind() == "identifier" {
-                            return Some(child.utf8_text(source).unwrap_or("").to_string());
-                        }
-                        if !cursor.goto_next_sibling() {
-                            break;
-                        }
-                    }
+            root = parent;
+            current = parent.parent();
+        }
+        
+        let mut cursor = root.walk();
+        let file_scoped = root.children(&mut cursor)
+            .find(|c| c.kind() == "file_scoped_namespace_declaration")
+            .and_then(|ns| ns.child_by_field_name("name"))
+            .and_then(|name| name.utf8_text(source).ok());
+        if let Some(name) = file_scoped {
+            parts.push(name.to_string());
+        }
+        
+        if parts.is_empty() {
+            return None;
+        }
+        parts.reverse();
+        Some(parts.join("."))
+    }
+    
+    /// Qualify a member with its namespace and enclosing types ("Ns.Outer.Inner.Method")
+    fn qualify_member(&self, member_node: &Node, name: &str, source: &[u8]) -> Option<String> {
+        let mut parts = vec![name.to_string()];
+        let mut current = member_node.parent();
+        while let Some(parent) = current {
+            if matches!(parent.kind(),
+                "class_declaration" | "struct_declaration" | "record_declaration" | "interface_declaration"
+            ) {
+                if let Some(type_name) = parent.child_by_field_name("name") {
+                    parts.push(type_name.utf8_text(source).unwrap_or("").to_string());
                 }
             }
             current = parent.parent();
         }
-        None
+        
+        if let Some(namespace) = self.extract_enclosing_namespace(member_node, source) {
+            parts.push(namespace);
+        }
+        
+        if parts.len() == 1 {
+            return None;
+        }
+        parts.reverse();
+        Some(parts.join("."))
     }
     
     fn extract_usings(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -905,6 +1025,7 @@ impl CSharpParser {
                     default: None,
                     namespace: None,
                     is_type_only: false,
+                    relative_level: 0,
                     range,
                 });
             }
@@ -919,6 +1040,7 @@ impl CSharpParser {
             let mut callee = String::new();
             let mut receiver = None;
             let mut arg_count = 0;
+            let mut string_arg = None;
             let mut range = Range::new(0, 0, 0, 0);
             
             for capture in m.captures {
@@ -934,10 +1056,14 @@ impl CSharpParser {
                     }
                     "args" => {
                         arg_count = node.named_child_count();
+                        string_arg = first_string_arg(&node, source);
                     }
                     "call" | "new_call" => {
                         range = node_range(&node);
                     }
+                    "query" => {
+                        self.extract_query_expression_calls(&node, source, result);
+                    }
                     _ => {}
                 }
             }
@@ -948,10 +1074,77 @@ impl CSharpParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg,
                 });
             }
         }
     }
+    
+    /// Emit synthetic call sites for LINQ query syntax
+    ///
+    /// `from u in _context.Users where u.Active select u` becomes the
+    /// method-syntax equivalent `_context.Users.Where(..).Select(..)`, so
+    /// data access detection treats both forms alike. Each clause uses the
+    /// queried source as receiver; `join` and nested `from` clauses use the
+    /// source they bring in.
+    fn extract_query_expression_calls(&self, node: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut receiver: Option<String> = None;
+        let mut cursor = node.walk();
+        
+        for clause in node.named_children(&mut cursor) {
+            let callee = match clause.kind() {
+                "from_clause" if receiver.is_none() => {
+                    receiver = query_clause_source(&clause, source);
+                    continue;
+                }
+                "from_clause" => "SelectMany",
+                "join_clause" => "Join",
+                "where_clause" => "Where",
+                "order_by_clause" => "OrderBy",
+                "select_clause" => "Select",
+                "group_clause" => "GroupBy",
+                _ => continue,
+            };
+            
+            let clause_receiver = match clause.kind() {
+                "from_clause" | "join_clause" => query_clause_source(&clause, source),
+                _ => receiver.clone(),
+            };
+            
+            result.calls.push(CallSite {
+                callee: callee.to_string(),
+                receiver: clause_receiver,
+                arg_count: 1,
+                range: node_range(&clause),
+                string_arg: None,
+            });
+        }
+    }
+}
+
+/// Contents of the first argument when it is a string literal; interpolation
+/// holes are kept as written (`users/{id}`)
+fn first_string_arg(args: &Node, source: &[u8]) -> Option<String> {
+    let first = args.named_child(0).filter(|arg| arg.kind() == "argument")?.named_child(0)?;
+    let text = first.utf8_text(source).ok()?;
+    match first.kind() {
+        "string_literal" | "interpolated_string_expression" | "verbatim_string_literal" | "raw_string_literal" => {
+            let text = text.trim_start_matches(['$', '@']).trim_matches('"');
+            Some(text.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Source expression of a `from x in <source>` / `join x in <source>` clause
+fn query_clause_source(clause: &Node, source: &[u8]) -> Option<String> {
+    let mut cursor = clause.walk();
+    let mut children = clause.children(&mut cursor);
+    children.find(|c| c.kind() == "in")?;
+    children
+        .find(|c| c.is_named())
+        .and_then(|n| n.utf8_text(source).ok())
+        .map(|s| s.to_string())
 }
 
 impl Default for CSharpParser {
@@ -1071,6 +1264,34 @@ public class UsersController : ControllerBase
             "Missing Authorize, got: {:?}", create.decorators);
     }
     
+    #[test]
+    fn test_route_annotations_aspnet() {
+        let mut parser = CSharpParser::new().unwrap();
+        let source = r#"
+[ApiController]
+[Route("api/orders")]
+public class OrdersController : ControllerBase
+{
+    [HttpGet("{id}")]
+    public IActionResult Get(int id) { return Ok(); }
+
+    [HttpPost]
+    [Route("bulk")]
+    public IActionResult CreateMany() { return Ok(); }
+
+    [HttpDelete]
+    public IActionResult Clear() { return Ok(); }
+}
+        "#;
+        let result = parser.parse(source);
+
+        assert_eq!(result.route_annotations(), vec![
+            ("GET".to_string(), "api/orders/{id}".to_string()),
+            ("POST".to_string(), "api/orders/bulk".to_string()),
+            ("DELETE".to_string(), "api/orders".to_string()),
+        ]);
+    }
+
     #[test]
     fn test_parse_entity_framework_attributes() {
         let mut parser = CSharpParser::new().unwrap();
@@ -1368,4 +1589,86 @@ public static class UserEndpoints
         assert!(method.decorators.iter().any(|d| d.contains("Authorize")));
         assert!(method.decorators.iter().any(|d| d.contains("Tags")));
     }
+    
+    #[test]
+    fn test_parse_linq_query_syntax() {
+        let mut parser = CSharpParser::new().unwrap();
+        let source = r#"
+public class UserService
+{
+    public List<User> GetActive()
+    {
+        var users = from u in _context.Users
+                    join o in _context.Orders on u.Id equals o.UserId
+                    where u.Active
+                    orderby u.Name
+                    select u;
+        return users.ToList();
+    }
+}
+"#;
+        let result = parser.parse(source);
+        
+        let linq: Vec<(&str, Option<&str>)> = result.calls.iter()
+            .filter(|c| matches!(c.callee.as_str(), "Join" | "Where" | "OrderBy" | "Select"))
+            .map(|c| (c.callee.as_str(), c.receiver.as_deref()))
+            .collect();
+        assert_eq!(linq, vec![
+            ("Join", Some("_context.Orders")),
+            ("Where", Some("_context.Users")),
+            ("OrderBy", Some("_context.Users")),
+            ("Select", Some("_context.Users")),
+        ]);
+    }
+    
+    #[test]
+    fn test_block_scoped_namespace() {
+        let mut parser = CSharpParser::new().unwrap();
+        let source = r#"
+namespace Acme.Billing
+{
+    namespace Invoices
+    {
+        public class InvoiceService
+        {
+            public void Send() { }
+        }
+    }
+}
+"#;
+        let result = parser.parse(source);
+        
+        let class = result.classes.iter().find(|c| c.name == "InvoiceService").unwrap();
+        assert_eq!(class.namespace.as_deref(), Some("Acme.Billing.Invoices"));
+        assert!(!class.is_partial);
+        let method = result.functions.iter().find(|f| f.name == "Send").unwrap();
+        assert_eq!(method.qualified_name.as_deref(), Some("Acme.Billing.Invoices.InvoiceService.Send"));
+    }
+    
+    #[test]
+    fn test_file_scoped_namespace_and_partial_class() {
+        let mut parser = CSharpParser::new().unwrap();
+        let source = r#"
+namespace Acme.Users;
+
+public partial class UserService
+{
+    public void Create() { }
+    
+    private class Validator
+    {
+        public bool Validate() { return true; }
+    }
+}
+"#;
+        let result = parser.parse(source);
+        
+        let class = result.classes.iter().find(|c| c.name == "UserService").unwrap();
+        assert_eq!(class.namespace.as_deref(), Some("Acme.Users"));
+        assert!(class.is_partial);
+        let create = result.functions.iter().find(|f| f.name == "Create").unwrap();
+        assert_eq!(create.qualified_name.as_deref(), Some("Acme.Users.UserService.Create"));
+        let validate = result.functions.iter().find(|f| f.name == "Validate").unwrap();
+        assert_eq!(validate.qualified_name.as_deref(), Some("Acme.Users.UserService.Validator.Validate"));
+    }
 }
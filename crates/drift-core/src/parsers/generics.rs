@@ -0,0 +1,55 @@
+//! Generic / template type parameter extraction shared by the parsers
+//!
+//! Grammars disagree on where the parameter list hangs off a declaration
+//! (a `type_parameters` field in TS/Java/Rust, a bare `type_parameter_list`
+//! child on C# classes, the enclosing `template_declaration` in C++), but the
+//! entries themselves always carry the parameter name either as a `name`
+//! field or as their first identifier child.
+
+use tree_sitter::Node;
+
+/// Type parameter names declared directly on `decl`
+pub(crate) fn declared_type_parameters(decl: &Node, source: &[u8]) -> Vec<String> {
+    let list = decl.child_by_field_name("type_parameters").or_else(|| {
+        let mut cursor = decl.walk();
+        let found = decl.children(&mut cursor).find(|c| c.kind() == "type_parameter_list");
+        found
+    });
+    list.map(|l| type_parameter_names(&l, source)).unwrap_or_default()
+}
+
+/// Type parameter names of a C++ `template <...>` wrapping `decl`
+pub(crate) fn template_type_parameters(decl: &Node, source: &[u8]) -> Vec<String> {
+    decl.parent()
+        .filter(|p| p.kind() == "template_declaration")
+        .and_then(|p| p.child_by_field_name("parameters"))
+        .map(|l| type_parameter_names(&l, source))
+        .unwrap_or_default()
+}
+
+/// Names from a type parameter list; lifetimes, const and non-type template
+/// parameters are skipped
+pub(crate) fn type_parameter_names(list: &Node, source: &[u8]) -> Vec<String> {
+    let mut cursor = list.walk();
+    list.named_children(&mut cursor)
+        .filter(|p| matches!(
+            p.kind(),
+            "type_parameter"
+                | "constrained_type_parameter"
+                | "optional_type_parameter"
+                | "type_parameter_declaration"
+                | "optional_type_parameter_declaration"
+                | "variadic_type_parameter_declaration"
+        ))
+        .filter_map(|p| {
+            let name = p.child_by_field_name("name").or_else(|| {
+                let mut c = p.walk();
+                let found = p
+                    .named_children(&mut c)
+                    .find(|n| matches!(n.kind(), "type_identifier" | "identifier"));
+                found
+            })?;
+            name.utf8_text(source).ok().map(|s| s.to_string())
+        })
+        .collect()
+}
@@ -117,6 +117,7 @@ impl GoParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -127,8 +128,17 @@ impl GoParser {
         
         let mut result = ParseResult::with_tree(Language::Go, tree.clone());
         
-        self.extract_functions(&root, source_bytes, &mut result);
+        result.record_syntax_errors(&root, source_bytes);
+        
+        let receiver_methods = self.extract_functions(&root, source_bytes, &mut result);
         self.extract_structs(&root, source_bytes, &mut result);
+        
+        // Attach receiver methods to the type declared in this file
+        for (owner, method) in receiver_methods {
+            if let Some(class) = result.classes.iter_mut().find(|c| c.name == owner) {
+                class.methods.push(method);
+            }
+        }
         self.extract_imports(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         
@@ -136,9 +146,11 @@ impl GoParser {
         result
     }
     
-    fn extract_functions(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    /// Extract functions, returning the methods declared with a receiver along with the receiver type
+    fn extract_functions(&self, root: &Node, source: &[u8], result: &mut ParseResult) -> Vec<(String, FunctionInfo)> {
         let mut cursor = QueryCursor::new();
         let matches = cursor.matches(&self.function_query, *root, source);
+        let mut receiver_methods = Vec::new();
         
         for m in matches {
             let mut name = String::new();
@@ -146,6 +158,7 @@ impl GoParser {
             let mut is_exported = false;
             let mut parameters = Vec::new();
             let mut return_type: Option<String> = None;
+            let mut receiver_type: Option<String> = None;
             let mut function_node: Option<Node> = None;
             
             for capture in m.captures {
@@ -158,6 +171,9 @@ impl GoParser {
                         // Go exports start with uppercase
                         is_exported = name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false);
                     }
+                    "receiver" => {
+                        receiver_type = receiver_type_name(&node, source);
+                    }
                     "params" => {
                         parameters = self.extract_parameters(&node, source);
                     }
@@ -178,20 +194,29 @@ impl GoParser {
             if !name.is_empty() {
                 let doc_comment = function_node.and_then(|n| self.extract_doc_comment(&n, source));
                 
-                result.functions.push(FunctionInfo {
+                let func = FunctionInfo {
+                    qualified_name: receiver_type.as_ref().map(|ty| format!("{}.{}", ty, name)),
                     name,
-                    qualified_name: None,
                     parameters,
                     return_type,
                     is_exported,
                     is_async: false, // Go uses goroutines, not async
                     is_generator: false,
+                    visibility: if is_exported { Visibility::Public } else { Visibility::Private },
+                    is_static: false,
+                    type_parameters: Vec::new(),
                     range,
                     decorators: Vec::new(),
                     doc_comment,
-                });
+                };
+                if let Some(ty) = receiver_type {
+                    receiver_methods.push((ty, func.clone()));
+                }
+                result.functions.push(func);
             }
         }
+        
+        receiver_methods
     }
     
     /// Extract parameters from a parameter_list node
@@ -320,6 +345,7 @@ impl GoParser {
             let mut is_exported = false;
             let mut is_interface = false;
             let mut struct_body: Option<Node> = None;
+            let mut interface_body: Option<Node> = None;
             let mut struct_node: Option<Node> = None;
             
             for capture in m.captures {
@@ -336,6 +362,7 @@ impl GoParser {
                     }
                     "interface_body" => {
                         is_interface = true;
+                        interface_body = Some(node);
                     }
                     "struct" => {
                         range = node_range(&node);
@@ -355,6 +382,10 @@ impl GoParser {
                     .map(|n| self.extract_struct_fields(&n, source))
                     .unwrap_or_default();
                 
+                let methods = interface_body
+                    .map(|n| self.extract_interface_methods(&n, &name, source))
+                    .unwrap_or_default();
+                
                 let doc_comment = struct_node.and_then(|n| self.extract_doc_comment(&n, source));
                 
                 result.classes.push(ClassInfo {
@@ -363,15 +394,59 @@ impl GoParser {
                     implements: Vec::new(), // Go uses implicit interfaces
                     is_exported,
                     is_abstract: is_interface,
-                    methods: Vec::new(),
+                    methods,
                     properties,
                     range,
                     decorators: if doc_comment.is_some() { vec![] } else { vec![] },
+                    namespace: None,
+                    is_partial: false,
+                    type_parameters: Vec::new(),
                 });
             }
         }
     }
     
+    /// Extract the method set declared by an interface type
+    fn extract_interface_methods(&self, interface_body: &Node, interface_name: &str, source: &[u8]) -> Vec<FunctionInfo> {
+        let mut methods = Vec::new();
+        let mut cursor = interface_body.walk();
+        
+        for child in interface_body.children(&mut cursor) {
+            if child.kind() != "method_elem" {
+                continue;
+            }
+            let Some(name_node) = child.child_by_field_name("name") else {
+                continue;
+            };
+            let name = name_node.utf8_text(source).unwrap_or("").to_string();
+            let parameters = child.child_by_field_name("parameters")
+                .map(|n| self.extract_parameters(&n, source))
+                .unwrap_or_default();
+            let return_type = child.child_by_field_name("result")
+                .and_then(|n| n.utf8_text(source).ok())
+                .map(|rt| rt.trim().to_string())
+                .filter(|rt| !rt.is_empty());
+            
+            methods.push(FunctionInfo {
+                qualified_name: Some(format!("{}.{}", interface_name, name)),
+                is_exported: name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false),
+                name,
+                parameters,
+                return_type,
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                is_static: false,
+                type_parameters: Vec::new(),
+                range: node_range(&child),
+                decorators: Vec::new(),
+                doc_comment: self.extract_doc_comment(&child, source),
+            });
+        }
+        
+        methods
+    }
+    
     /// Extract struct fields with their tags
     fn extract_struct_fields(&self, struct_body: &Node, source: &[u8]) -> Vec<PropertyInfo> {
         let mut properties = Vec::new();
@@ -454,6 +529,9 @@ impl GoParser {
                     is_readonly: false,
                     visibility: if is_exported { Visibility::Public } else { Visibility::Private },
                     tags: tags.clone(),
+                    value: None,
+                    is_computed: false,
+                    decorators: Vec::new(),
                 });
             }
         }
@@ -464,7 +542,12 @@ impl GoParser {
     /// Parse Go struct tags like `json:"name" db:"user_name" validate:"required"`
     fn parse_struct_tags(&self, tag_text: &str) -> Vec<StructTag> {
         let mut tags = Vec::new();
-        let text = tag_text.trim_matches('`').trim_matches('"');
+        // Raw string tags are used as-is; interpreted string tags need their quotes unescaped
+        let text = match tag_text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+            Some(inner) => inner.replace("\\\"", "\""),
+            None => tag_text.trim_matches('`').to_string(),
+        };
+        let text = text.as_str();
         
         // Parse key:"value" pairs
         let mut remaining = text;
@@ -536,6 +619,7 @@ impl GoParser {
                     default: None,
                     namespace: None,
                     is_type_only: false,
+                    relative_level: 0,
                     range,
                 });
             }
@@ -579,6 +663,7 @@ impl GoParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg: None,
                 });
             }
         }
@@ -591,6 +676,22 @@ impl Default for GoParser {
     }
 }
 
+/// Type named by a method receiver, without pointer or type parameters
+///
+/// `(s *UserService)` and `(r Repo[T])` give "UserService" and "Repo".
+fn receiver_type_name(receiver: &Node, source: &[u8]) -> Option<String> {
+    let mut cursor = receiver.walk();
+    let param = receiver.named_children(&mut cursor).find(|c| c.kind() == "parameter_declaration")?;
+    let ty = param.child_by_field_name("type")?.utf8_text(source).ok()?;
+    let ty = ty.trim_start_matches('*').trim();
+    let ty = ty.split('[').next().unwrap_or(ty).trim();
+    if ty.is_empty() {
+        None
+    } else {
+        Some(ty.to_string())
+    }
+}
+
 fn node_range(node: &Node) -> Range {
     Range {
         start: Position {
@@ -627,6 +728,32 @@ mod tests {
         assert_eq!(result.classes[0].name, "User");
     }
 
+    #[test]
+    fn test_struct_tags_round_trip() {
+        let mut parser = GoParser::new().unwrap();
+        let source = r#"
+            package main
+            
+            type User struct {
+                Name string `json:"name" gorm:"column:user_name"`
+            }
+        "#;
+        let result = parser.parse(source);
+        
+        let name = &result.classes[0].properties[0];
+        let tags = name.tags.as_ref().unwrap();
+        let pairs: Vec<(&str, &str)> = tags.iter().map(|t| (t.key.as_str(), t.value.as_str())).collect();
+        assert_eq!(pairs, vec![("json", "name"), ("gorm", "column:user_name")]);
+        
+        // Tags survive serialization unchanged (the NAPI layer maps them key-for-key)
+        let json = serde_json::to_string(&result.classes[0]).unwrap();
+        let restored: ClassInfo = serde_json::from_str(&json).unwrap();
+        let restored_tags = restored.properties[0].tags.as_ref().unwrap();
+        assert_eq!(restored_tags.len(), 2);
+        assert_eq!(restored_tags[1].key, "gorm");
+        assert_eq!(restored_tags[1].value, "column:user_name");
+    }
+
     #[test]
     fn test_parse_imports() {
         let mut parser = GoParser::new().unwrap();
@@ -663,6 +790,40 @@ mod tests {
         let result = parser.parse(source);
         
         assert!(result.functions.iter().any(|f| f.name == "GetName"));
+        
+        let get_name = result.functions.iter().find(|f| f.name == "GetName").unwrap();
+        assert_eq!(get_name.qualified_name.as_deref(), Some("User.GetName"));
+        let user = result.classes.iter().find(|c| c.name == "User").unwrap();
+        assert_eq!(user.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["GetName"]);
+    }
+
+    #[test]
+    fn test_parse_receiver_type_variants() {
+        let mut parser = GoParser::new().unwrap();
+        let source = r#"
+            package main
+            
+            type Repo[T any] struct { items []T }
+            
+            func (r Repo[T]) Count() int { return len(r.items) }
+            func (r *Repo[T]) Add(item T) { r.items = append(r.items, item) }
+            func (Service) Name() string { return "svc" }
+            func helper() {}
+        "#;
+        let result = parser.parse(source);
+        
+        let qualified = |name: &str| {
+            result.functions.iter().find(|f| f.name == name).unwrap().qualified_name.clone()
+        };
+        assert_eq!(qualified("Count").as_deref(), Some("Repo.Count"));
+        assert_eq!(qualified("Add").as_deref(), Some("Repo.Add"));
+        assert_eq!(qualified("Name").as_deref(), Some("Service.Name"));
+        assert_eq!(qualified("helper"), None);
+        
+        // Methods on a type declared in another file stay top-level functions only
+        let repo = result.classes.iter().find(|c| c.name == "Repo").unwrap();
+        assert_eq!(repo.methods.len(), 2);
+        assert!(!result.classes.iter().any(|c| c.name == "Service"));
     }
 
     #[test]
@@ -681,6 +842,16 @@ mod tests {
         // Interfaces are parsed as classes in Go
         // The interface may or may not be detected depending on query
         assert!(result.errors.is_empty());
+        
+        let repo = result.classes.iter().find(|c| c.name == "Repository").unwrap();
+        assert!(repo.is_abstract);
+        assert_eq!(repo.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["Save", "Find"]);
+        let save = &repo.methods[0];
+        assert_eq!(save.qualified_name.as_deref(), Some("Repository.Save"));
+        assert_eq!(save.parameters.len(), 1);
+        assert_eq!(save.return_type.as_deref(), Some("error"));
+        // Interface method specs are not standalone functions
+        assert!(result.functions.is_empty());
     }
 
     #[test]
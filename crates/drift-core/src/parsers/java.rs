@@ -16,12 +16,14 @@ use std::time::Instant;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 use super::types::*;
+use super::generics::declared_type_parameters;
 
 /// Java parser
 pub struct JavaParser {
     parser: Parser,
     method_query: Query,
     class_query: Query,
+    enum_query: Query,
     import_query: Query,
     call_query: Query,
 }
@@ -70,6 +72,17 @@ impl JavaParser {
             "#,
         ).map_err(|e| format!("Failed to create class query: {}", e))?;
         
+        let enum_query = Query::new(
+            &language.into(),
+            r#"
+            (enum_declaration
+                (modifiers)? @modifiers
+                name: (identifier) @name
+                body: (enum_body) @body
+            ) @enum
+            "#,
+        ).map_err(|e| format!("Failed to create enum query: {}", e))?;
+        
         let import_query = Query::new(
             &language.into(),
             r#"
@@ -99,6 +112,7 @@ impl JavaParser {
             parser,
             method_query,
             class_query,
+            enum_query,
             import_query,
             call_query,
         })
@@ -114,6 +128,7 @@ impl JavaParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -124,8 +139,12 @@ impl JavaParser {
         
         let mut result = ParseResult::with_tree(Language::Java, tree.clone());
         
+        result.record_syntax_errors(&root, source_bytes);
+        
         self.extract_methods(&root, source_bytes, &mut result);
         self.extract_classes(&root, source_bytes, &mut result);
+        result.attach_methods();
+        self.extract_enums(&root, source_bytes, &mut result);
         self.extract_imports(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         
@@ -143,10 +162,8 @@ impl JavaParser {
                 let child = cursor.node();
                 match child.kind() {
                     "marker_annotation" | "annotation" => {
-                        // Extract the full annotation text
-                        let ann_text = child.utf8_text(source).unwrap_or("");
-                        if !ann_text.is_empty() {
-                            annotations.push(ann_text.to_string());
+                        if let Some(annotation) = self.format_annotation(&child, source) {
+                            annotations.push(annotation);
                         }
                     }
                     _ => {}
@@ -159,7 +176,20 @@ impl JavaParser {
         
         annotations
     }
-    
+
+    /// Format an annotation as @Name or @Name(args), keeping the full argument list
+    fn format_annotation(&self, node: &Node, source: &[u8]) -> Option<String> {
+        let name = node.child_by_field_name("name")?.utf8_text(source).unwrap_or("");
+        if name.is_empty() {
+            return None;
+        }
+
+        match node.child_by_field_name("arguments") {
+            Some(args) => Some(format!("@{}{}", name, args.utf8_text(source).unwrap_or(""))),
+            None => Some(format!("@{}", name)),
+        }
+    }
+
     /// Extract parameters from a formal_parameters node
     fn extract_parameters(&self, params_node: &Node, source: &[u8]) -> Vec<ParameterInfo> {
         let mut parameters = Vec::new();
@@ -283,7 +313,8 @@ impl JavaParser {
             let mut name = String::new();
             let mut range = Range::new(0, 0, 0, 0);
             let mut is_public = false;
-            let mut _is_static = false;
+            let mut visibility = Visibility::Private; // package-private, as for fields
+            let mut is_static = false;
             let mut is_async = false;
             let mut return_type: Option<String> = None;
             let mut annotations = Vec::new();
@@ -301,7 +332,16 @@ impl JavaParser {
                     "modifiers" => {
                         let mods = node.utf8_text(source).unwrap_or("");
                         is_public = mods.contains("public");
-                        _is_static = mods.contains("static");
+                        is_static = mods.contains("static");
+                        let mut mod_cursor = node.walk();
+                        for keyword in node.children(&mut mod_cursor) {
+                            match keyword.kind() {
+                                "public" => visibility = Visibility::Public,
+                                "protected" => visibility = Visibility::Protected,
+                                "private" => visibility = Visibility::Private,
+                                _ => {}
+                            }
+                        }
                         // Check for CompletableFuture or async-related annotations
                         is_async = mods.contains("@Async");
                         
@@ -329,14 +369,26 @@ impl JavaParser {
                 // Extract Javadoc
                 let doc_comment = method_node.and_then(|n| self.extract_javadoc(&n, source));
                 
+                // Qualified by the declaring class or interface
+                let owner = method_node
+                    .and_then(|n| n.parent())
+                    .and_then(|body| body.parent())
+                    .and_then(|decl| decl.child_by_field_name("name"))
+                    .and_then(|n| n.utf8_text(source).ok());
+                
                 result.functions.push(FunctionInfo {
+                    qualified_name: owner.map(|owner| format!("{}.{}", owner, name)),
                     name,
-                    qualified_name: None,
                     parameters,
                     return_type,
                     is_exported: is_public,
                     is_async,
                     is_generator: false,
+                    visibility,
+                    is_static,
+                    type_parameters: method_node
+                        .map(|n| declared_type_parameters(&n, source))
+                        .unwrap_or_default(),
                     range,
                     decorators: annotations,
                     doc_comment,
@@ -417,6 +469,12 @@ impl JavaParser {
                     properties,
                     range,
                     decorators: annotations,
+                    namespace: None,
+                    is_partial: false,
+                    type_parameters: class_body
+                        .and_then(|body| body.parent())
+                        .map(|decl| declared_type_parameters(&decl, source))
+                        .unwrap_or_default(),
                 });
             }
         }
@@ -508,9 +566,63 @@ impl JavaParser {
             is_readonly: is_final,
             visibility,
             tags: None,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
     
+    fn extract_enums(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(&self.enum_query, *root, source);
+        
+        for m in matches {
+            let mut name = String::new();
+            let mut is_public = false;
+            let mut annotations = Vec::new();
+            let mut variants = Vec::new();
+            let mut enum_node: Option<Node> = None;
+            
+            for capture in m.captures {
+                let node = capture.node;
+                match self.enum_query.capture_names()[capture.index as usize] {
+                    "name" => name = node.utf8_text(source).unwrap_or("").to_string(),
+                    "modifiers" => {
+                        is_public = node.utf8_text(source).unwrap_or("").contains("public");
+                        annotations = self.extract_annotations(&node, source);
+                    }
+                    "body" => {
+                        // `ACTIVE("active")` - constructor arguments stand in for the value
+                        let mut body_cursor = node.walk();
+                        variants = node.named_children(&mut body_cursor)
+                            .filter(|n| n.kind() == "enum_constant")
+                            .filter_map(|n| Some(EnumVariant {
+                                name: n.child_by_field_name("name")?.utf8_text(source).ok()?.to_string(),
+                                value: n.child_by_field_name("arguments")
+                                    .and_then(|a| a.utf8_text(source).ok())
+                                    .map(|a| a.trim_start_matches('(').trim_end_matches(')').trim().to_string())
+                                    .filter(|a| !a.is_empty()),
+                                range: node_range(&n),
+                            }))
+                            .collect();
+                    }
+                    "enum" => enum_node = Some(node),
+                    _ => {}
+                }
+            }
+            
+            if let (false, Some(node)) = (name.is_empty(), enum_node) {
+                result.enums.push(EnumInfo {
+                    name,
+                    variants,
+                    is_exported: is_public,
+                    range: node_range(&node),
+                    decorators: annotations,
+                });
+            }
+        }
+    }
+    
     fn extract_imports(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
         let matches = cursor.matches(&self.import_query, *root, source);
@@ -543,6 +655,7 @@ impl JavaParser {
                     default: None,
                     namespace: None,
                     is_type_only: false,
+                    relative_level: 0,
                     range,
                 });
             }
@@ -586,6 +699,7 @@ impl JavaParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg: None,
                 });
             }
         }
@@ -770,6 +884,46 @@ public class UserController {
             "Expected @PostMapping, got: {:?}", create_user.decorators);
     }
 
+    #[test]
+    fn test_route_annotations_with_class_prefix() {
+        let mut parser = JavaParser::new().unwrap();
+        let source = r#"
+@RestController
+@RequestMapping(value = "/api/users")
+public class UserController {
+    @GetMapping("/{id}")
+    public User getUser(@PathVariable Long id) { return null; }
+
+    @PostMapping
+    public User createUser(@RequestBody UserDTO dto) { return null; }
+
+    @RequestMapping(
+        value = "/search",
+        method = RequestMethod.POST
+    )
+    public List<User> search() { return null; }
+
+    @DeleteMapping(path = "/{id}", produces = "application/json")
+    public void deleteUser(@PathVariable Long id) { }
+
+    public void helper() { }
+}
+        "#;
+        let result = parser.parse(source);
+
+        let get_user = result.functions.iter().find(|f| f.name == "getUser").unwrap();
+        assert!(get_user.decorators.contains(&"@GetMapping(\"/{id}\")".to_string()),
+            "Expected argument list to be kept, got: {:?}", get_user.decorators);
+
+        let routes = result.route_annotations();
+        assert_eq!(routes, vec![
+            ("GET".to_string(), "/api/users/{id}".to_string()),
+            ("POST".to_string(), "/api/users".to_string()),
+            ("POST".to_string(), "/api/users/search".to_string()),
+            ("DELETE".to_string(), "/api/users/{id}".to_string()),
+        ]);
+    }
+
     #[test]
     fn test_parse_method_parameters() {
         let mut parser = JavaParser::new().unwrap();
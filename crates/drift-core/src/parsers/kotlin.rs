@@ -0,0 +1,769 @@
+//! Kotlin parser using native tree-sitter
+//!
+//! Extracts functions, classes/objects, imports, and call sites from Kotlin code.
+//! Supports Spring Boot, Ktor, Exposed, and Room patterns.
+//!
+//! Enterprise features:
+//! - Annotation extraction with arguments (@GetMapping("/users"), @Entity)
+//! - `suspend` functions reported as async
+//! - Data class / primary constructor properties (`val`/`var`)
+//! - Superclass and interface extraction from delegation specifiers
+//! - Companion object members qualified by their owning class
+//! - KDoc comment extraction
+
+use std::time::Instant;
+use tree_sitter::{Node, Parser};
+
+use super::types::*;
+
+/// Kotlin parser
+pub struct KotlinParser {
+    parser: Parser,
+}
+
+impl KotlinParser {
+    pub fn new() -> Result<Self, String> {
+        let mut parser = Parser::new();
+        let language = tree_sitter_kotlin_ng::LANGUAGE;
+        parser.set_language(&language.into())
+            .map_err(|e| format!("Failed to set language: {}", e))?;
+
+        Ok(Self { parser })
+    }
+
+    pub fn parse(&mut self, source: &str) -> ParseResult {
+        let start = Instant::now();
+
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => {
+                let mut result = ParseResult::new(Language::Kotlin);
+                result.errors.push(ParseError {
+                    message: "Failed to parse source".to_string(),
+                    range: Range::new(0, 0, 0, 0),
+                    snippet: None,
+                });
+                return result;
+            }
+        };
+
+        let root = tree.root_node();
+        let source_bytes = source.as_bytes();
+
+        let mut result = ParseResult::with_tree(Language::Kotlin, tree.clone());
+
+        result.record_syntax_errors(&root, source_bytes);
+
+        let package = self.extract_package(&root, source_bytes);
+        self.extract_imports(&root, source_bytes, &mut result);
+        self.extract_declarations(&root, source_bytes, None, package.as_deref(), &mut result);
+        self.extract_calls(&root, source_bytes, &mut result);
+
+        result.parse_time_us = start.elapsed().as_micros() as u64;
+        result
+    }
+
+    fn extract_package(&self, root: &Node, source: &[u8]) -> Option<String> {
+        let mut cursor = root.walk();
+        let header = root.named_children(&mut cursor).find(|c| c.kind() == "package_header")?;
+        let name = find_child(&header, "qualified_identifier")?;
+        name.utf8_text(source).ok().map(|s| s.to_string())
+    }
+
+    /// Extract classes, objects and functions declared in `body`
+    ///
+    /// Returns the functions declared directly in the body so the caller can
+    /// attach them to the enclosing class.
+    fn extract_declarations(
+        &self,
+        body: &Node,
+        source: &[u8],
+        owner: Option<&str>,
+        package: Option<&str>,
+        result: &mut ParseResult,
+    ) -> Vec<FunctionInfo> {
+        let mut methods = Vec::new();
+
+        let mut cursor = body.walk();
+        for child in body.named_children(&mut cursor) {
+            match child.kind() {
+                "function_declaration" => {
+                    if let Some(func) = self.extract_function(&child, source, owner) {
+                        result.functions.push(func.clone());
+                        methods.push(func);
+                    }
+                }
+                "class_declaration" | "object_declaration" => {
+                    self.extract_class(&child, source, package, result);
+                }
+                // Companion members are called through the class name (`User.create()`)
+                "companion_object" => {
+                    if let Some(class_body) = find_child(&child, "class_body") {
+                        methods.extend(self.extract_declarations(&class_body, source, owner, package, result));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        methods
+    }
+
+    /// Extract a class, interface or object declaration
+    fn extract_class(&self, node: &Node, source: &[u8], package: Option<&str>, result: &mut ParseResult) {
+        let Some(name) = node.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+            .map(|s| s.to_string()) else {
+            return;
+        };
+
+        let modifiers = find_child(node, "modifiers");
+        let mut decorators = leading_annotations(node, source);
+        decorators.extend(modifiers.map(|m| self.extract_annotations(&m, source)).unwrap_or_default());
+
+        let is_interface = has_token(node, "interface");
+        let is_abstract = is_interface || modifiers.is_some_and(|m| has_modifier(&m, source, "abstract"));
+        let is_exported = modifiers.map(|m| is_visible(&m, source)).unwrap_or(true);
+
+        // `: Base(), Iface` - the constructor invocation is the superclass
+        let mut extends = None;
+        let mut implements = Vec::new();
+        if let Some(specifiers) = find_child(node, "delegation_specifiers") {
+            let mut spec_cursor = specifiers.walk();
+            for spec in specifiers.named_children(&mut spec_cursor) {
+                let Some(target) = spec.named_child(0) else {
+                    continue;
+                };
+                let type_name = match target.kind() {
+                    "constructor_invocation" => find_child(&target, "user_type"),
+                    "explicit_delegation" => target.named_child(0),
+                    _ => Some(target),
+                };
+                let Some(type_name) = type_name.and_then(|t| t.utf8_text(source).ok()) else {
+                    continue;
+                };
+                if target.kind() == "constructor_invocation" && extends.is_none() && !is_interface {
+                    extends = Some(type_name.to_string());
+                } else {
+                    implements.push(type_name.to_string());
+                }
+            }
+        }
+
+        let mut properties = find_child(node, "primary_constructor")
+            .and_then(|c| find_child(&c, "class_parameters"))
+            .map(|p| self.extract_constructor_properties(&p, source))
+            .unwrap_or_default();
+
+        let class_body = find_child(node, "class_body");
+        if let Some(body) = class_body {
+            let mut body_cursor = body.walk();
+            for member in body.named_children(&mut body_cursor) {
+                if member.kind() == "property_declaration" {
+                    properties.extend(self.extract_property(&member, source));
+                }
+            }
+        }
+
+        let methods = class_body
+            .map(|b| self.extract_declarations(&b, source, Some(&name), package, result))
+            .unwrap_or_default();
+
+        result.classes.push(ClassInfo {
+            name,
+            extends,
+            implements,
+            is_exported,
+            is_abstract,
+            methods,
+            properties,
+            range: node_range(node),
+            decorators,
+            namespace: package.map(|p| p.to_string()),
+            is_partial: false,
+            type_parameters: Vec::new(),
+        });
+    }
+
+    /// Extract a function declaration
+    fn extract_function(&self, node: &Node, source: &[u8], owner: Option<&str>) -> Option<FunctionInfo> {
+        let name = node.child_by_field_name("name")?.utf8_text(source).ok()?.to_string();
+
+        let modifiers = find_child(node, "modifiers");
+        let mut decorators = leading_annotations(node, source);
+        decorators.extend(modifiers.map(|m| self.extract_annotations(&m, source)).unwrap_or_default());
+        let is_async = modifiers.is_some_and(|m| has_modifier(&m, source, "suspend"));
+        let is_exported = modifiers.map(|m| is_visible(&m, source)).unwrap_or(true);
+        let visibility = modifiers.map(|m| visibility(&m, source)).unwrap_or(Visibility::Public);
+        // Companion object members are called on the class itself
+        let is_static = node.parent().and_then(|body| body.parent()).is_some_and(|o| o.kind() == "companion_object");
+
+        let params_node = find_child(node, "function_value_parameters");
+        let parameters = params_node
+            .map(|p| self.extract_parameters(&p, source))
+            .unwrap_or_default();
+
+        // The return type is the type following the parameter list
+        let return_type = params_node.and_then(|params| {
+            let mut sibling = params.next_named_sibling();
+            while let Some(s) = sibling {
+                match s.kind() {
+                    "function_body" | "type_constraints" => return None,
+                    kind if kind.ends_with("_type") => {
+                        return s.utf8_text(source).ok().map(|t| t.to_string());
+                    }
+                    _ => sibling = s.next_named_sibling(),
+                }
+            }
+            None
+        });
+
+        Some(FunctionInfo {
+            qualified_name: owner.map(|o| format!("{}.{}", o, name)),
+            name,
+            parameters,
+            return_type,
+            is_exported,
+            is_async,
+            is_generator: false,
+            visibility,
+            is_static,
+            type_parameters: Vec::new(),
+            range: node_range(node),
+            decorators,
+            doc_comment: self.extract_kdoc(node, source),
+        })
+    }
+
+    /// Extract parameters from function_value_parameters
+    ///
+    /// Modifiers (`vararg`, annotations) and default values are siblings of the
+    /// `parameter` node they belong to.
+    fn extract_parameters(&self, params_node: &Node, source: &[u8]) -> Vec<ParameterInfo> {
+        let mut parameters: Vec<ParameterInfo> = Vec::new();
+        let mut pending_vararg = false;
+
+        let mut cursor = params_node.walk();
+        for child in params_node.named_children(&mut cursor) {
+            match child.kind() {
+                "parameter_modifiers" => {
+                    pending_vararg = has_modifier(&child, source, "vararg");
+                }
+                "parameter" => {
+                    let name = find_child(&child, "identifier")
+                        .and_then(|n| n.utf8_text(source).ok())
+                        .unwrap_or("")
+                        .to_string();
+                    let type_annotation = child.named_child(1)
+                        .and_then(|t| t.utf8_text(source).ok())
+                        .map(|t| t.to_string());
+                    parameters.push(ParameterInfo {
+                        name,
+                        type_annotation,
+                        default_value: None,
+                        is_rest: pending_vararg,
+                    });
+                    pending_vararg = false;
+                }
+                // `= default` follows its parameter
+                _ => {
+                    if let Some(last) = parameters.last_mut() {
+                        last.default_value = child.utf8_text(source).ok().map(|t| t.to_string());
+                    }
+                }
+            }
+        }
+
+        parameters
+    }
+
+    /// Properties declared with `val`/`var` in a primary constructor
+    fn extract_constructor_properties(&self, params: &Node, source: &[u8]) -> Vec<PropertyInfo> {
+        let mut properties = Vec::new();
+
+        let mut cursor = params.walk();
+        for param in params.named_children(&mut cursor) {
+            if param.kind() != "class_parameter" {
+                continue;
+            }
+            let is_val = has_token(&param, "val");
+            if !is_val && !has_token(&param, "var") {
+                continue; // plain constructor parameter, not a property
+            }
+            let Some(name) = find_child(&param, "identifier").and_then(|n| n.utf8_text(source).ok()) else {
+                continue;
+            };
+
+            let modifiers = find_child(&param, "modifiers");
+            // The type follows the name; a default value follows `=`
+            let mut type_annotation = None;
+            let mut value = None;
+            let mut seen_equals = false;
+            let mut child_cursor = param.walk();
+            for child in param.children(&mut child_cursor) {
+                match child.kind() {
+                    "=" => seen_equals = true,
+                    "modifiers" | "identifier" | "val" | "var" | ":" => {}
+                    _ if seen_equals => value = child.utf8_text(source).ok().map(|t| t.to_string()),
+                    _ if child.is_named() => type_annotation = child.utf8_text(source).ok().map(|t| t.to_string()),
+                    _ => {}
+                }
+            }
+
+            properties.push(PropertyInfo {
+                name: name.to_string(),
+                type_annotation,
+                is_static: false,
+                is_readonly: is_val,
+                visibility: modifiers.map(|m| visibility(&m, source)).unwrap_or(Visibility::Public),
+                tags: None,
+                value,
+                is_computed: false,
+                decorators: Vec::new(),
+            });
+        }
+
+        properties
+    }
+
+    /// Property declared in a class body (`val name: Column<String> = varchar("name", 50)`)
+    fn extract_property(&self, node: &Node, source: &[u8]) -> Option<PropertyInfo> {
+        let declaration = find_child(node, "variable_declaration")?;
+        let name = find_child(&declaration, "identifier")?.utf8_text(source).ok()?.to_string();
+        let type_annotation = declaration.named_child(1)
+            .and_then(|t| t.utf8_text(source).ok())
+            .map(|t| t.to_string());
+
+        // The initializer is the node following `=`
+        let mut value = None;
+        let mut cursor = node.walk();
+        let mut seen_equals = false;
+        for child in node.children(&mut cursor) {
+            if child.kind() == "=" {
+                seen_equals = true;
+            } else if seen_equals && child.is_named() {
+                value = child.utf8_text(source).ok().map(|t| t.to_string());
+                break;
+            }
+        }
+
+        let modifiers = find_child(node, "modifiers");
+        Some(PropertyInfo {
+            name,
+            type_annotation,
+            is_static: false,
+            is_readonly: has_token(node, "val"),
+            visibility: modifiers.map(|m| visibility(&m, source)).unwrap_or(Visibility::Public),
+            tags: None,
+            value,
+            is_computed: false,
+            decorators: Vec::new(),
+        })
+    }
+
+    /// Extract annotations from a modifiers node as @Name or @Name(args)
+    fn extract_annotations(&self, modifiers: &Node, source: &[u8]) -> Vec<String> {
+        let mut annotations = Vec::new();
+        let mut cursor = modifiers.walk();
+        for child in modifiers.named_children(&mut cursor) {
+            if child.kind() == "annotation" {
+                if let Some(annotation) = format_annotation(&child, source) {
+                    annotations.push(annotation);
+                }
+            }
+        }
+        annotations
+    }
+
+    /// Extract KDoc (`/** ... */`) directly above a declaration
+    fn extract_kdoc(&self, node: &Node, source: &[u8]) -> Option<String> {
+        let mut sibling = node.prev_sibling();
+        // Skip annotations the grammar split off the declaration
+        while let Some(s) = sibling.filter(|s| s.kind() == "annotated_expression") {
+            sibling = s.prev_sibling();
+        }
+        let comment = sibling.filter(|s| s.kind() == "block_comment")?;
+        let text = comment.utf8_text(source).ok()?;
+        if !text.starts_with("/**") {
+            return None;
+        }
+
+        let cleaned: Vec<&str> = text.trim_start_matches("/**")
+            .trim_end_matches("*/")
+            .lines()
+            .map(|l| l.trim().trim_start_matches('*').trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        if cleaned.is_empty() {
+            None
+        } else {
+            Some(cleaned.join("\n"))
+        }
+    }
+
+    fn extract_imports(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = root.walk();
+        for import in root.named_children(&mut cursor) {
+            if import.kind() != "import" {
+                continue;
+            }
+            let Some(path) = find_child(&import, "qualified_identifier")
+                .or_else(|| find_child(&import, "identifier"))
+                .and_then(|n| n.utf8_text(source).ok()) else {
+                continue;
+            };
+            let is_wildcard = has_token(&import, "*");
+            // `import a.b.User as U` - the alias is the last identifier after `as`
+            let alias = if has_token(&import, "as") {
+                let mut import_cursor = import.walk();
+                let last = import.named_children(&mut import_cursor).last();
+                last.filter(|n| n.kind() == "identifier")
+                    .and_then(|n| n.utf8_text(source).ok())
+                    .map(|s| s.to_string())
+            } else {
+                None
+            };
+
+            let named = if is_wildcard {
+                Vec::new()
+            } else {
+                vec![alias.unwrap_or_else(|| path.rsplit('.').next().unwrap_or(path).to_string())]
+            };
+            result.imports.push(ImportInfo {
+                source: path.to_string(),
+                named,
+                default: None,
+                namespace: if is_wildcard { Some("*".to_string()) } else { None },
+                is_type_only: false,
+                relative_level: 0,
+                range: node_range(&import),
+            });
+        }
+    }
+
+    fn extract_calls(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut stack = vec![*root];
+        while let Some(node) = stack.pop() {
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+
+            if node.kind() != "call_expression" {
+                continue;
+            }
+            let Some(function) = node.named_child(0) else {
+                continue;
+            };
+            let (callee, receiver) = match function.kind() {
+                "identifier" => (function.utf8_text(source).unwrap_or("").to_string(), None),
+                // `service.findById(id)` / `repo?.save(u)`
+                "navigation_expression" => {
+                    let mut nav_cursor = function.walk();
+                    let parts: Vec<Node> = function.named_children(&mut nav_cursor).collect();
+                    let (Some(first), Some(last)) = (parts.first(), parts.last()) else {
+                        continue;
+                    };
+                    if parts.len() < 2 || last.kind() != "identifier" {
+                        continue;
+                    }
+                    (
+                        last.utf8_text(source).unwrap_or("").to_string(),
+                        first.utf8_text(source).ok().map(|s| s.to_string()),
+                    )
+                }
+                _ => continue,
+            };
+            if callee.is_empty() {
+                continue;
+            }
+
+            let mut arg_count = 0;
+            let mut string_arg = None;
+            let mut call_cursor = node.walk();
+            for part in node.named_children(&mut call_cursor) {
+                match part.kind() {
+                    "value_arguments" => {
+                        let mut args_cursor = part.walk();
+                        let args: Vec<Node> = part.named_children(&mut args_cursor).collect();
+                        arg_count += args.len();
+                        string_arg = args.first()
+                            .and_then(|a| a.named_child(0))
+                            .and_then(|a| plain_string(&a, source));
+                    }
+                    // Trailing lambda: `launch { ... }`
+                    "annotated_lambda" => arg_count += 1,
+                    _ => {}
+                }
+            }
+
+            result.calls.push(CallSite {
+                callee,
+                receiver,
+                arg_count,
+                range: node_range(&node),
+                string_arg,
+            });
+        }
+        result.calls.sort_by_key(|c| (c.range.start.line, c.range.start.column));
+    }
+}
+
+impl Default for KotlinParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create Kotlin parser")
+    }
+}
+
+fn find_child<'a>(node: &Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).find(|c| c.kind() == kind);
+    found
+}
+
+/// Whether `node` has a direct (possibly anonymous) child of the given kind
+fn has_token(node: &Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+/// Whether a modifiers node contains the given keyword (`suspend`, `abstract`, `vararg`)
+fn has_modifier(modifiers: &Node, source: &[u8], keyword: &str) -> bool {
+    let mut cursor = modifiers.walk();
+    let found = modifiers.named_children(&mut cursor)
+        .any(|m| m.kind() != "annotation" && m.utf8_text(source).unwrap_or("") == keyword);
+    found
+}
+
+fn visibility(modifiers: &Node, source: &[u8]) -> Visibility {
+    if has_modifier(modifiers, source, "private") {
+        Visibility::Private
+    } else if has_modifier(modifiers, source, "protected") {
+        Visibility::Protected
+    } else {
+        Visibility::Public
+    }
+}
+
+/// Kotlin declarations are public unless marked private or protected
+fn is_visible(modifiers: &Node, source: &[u8]) -> bool {
+    visibility(modifiers, source) == Visibility::Public
+}
+
+/// Format an annotation node as @Name or @Name(args)
+fn format_annotation(node: &Node, source: &[u8]) -> Option<String> {
+    let target = node.named_children(&mut node.walk())
+        .find(|c| matches!(c.kind(), "user_type" | "constructor_invocation"))?;
+    match target.kind() {
+        "constructor_invocation" => {
+            let name = find_child(&target, "user_type")?.utf8_text(source).ok()?;
+            let args = find_child(&target, "value_arguments")
+                .and_then(|a| a.utf8_text(source).ok())
+                .unwrap_or("");
+            Some(format!("@{}{}", name, args))
+        }
+        _ => Some(format!("@{}", target.utf8_text(source).ok()?)),
+    }
+}
+
+/// Annotations the grammar attaches to a preceding expression instead of the declaration
+///
+/// At the top level `@RequestMapping("/api")` followed by `class X` parses as an
+/// annotated parenthesized expression; the parenthesized part is the argument list.
+fn leading_annotations(node: &Node, source: &[u8]) -> Vec<String> {
+    let mut groups = Vec::new();
+    let mut sibling = node.prev_sibling();
+    while let Some(s) = sibling.filter(|s| s.kind() == "annotated_expression") {
+        let mut annotations = Vec::new();
+        let mut current = Some(s);
+        while let Some(expr) = current {
+            current = None;
+            let mut cursor = expr.walk();
+            for child in expr.named_children(&mut cursor) {
+                match child.kind() {
+                    "annotation" => {
+                        if let Some(annotation) = format_annotation(&child, source) {
+                            annotations.push(annotation);
+                        }
+                    }
+                    "annotated_expression" => current = Some(child),
+                    "parenthesized_expression" => {
+                        if let (Some(last), Ok(args)) = (annotations.last_mut(), child.utf8_text(source)) {
+                            last.push_str(args);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        groups.push(annotations);
+        sibling = s.prev_sibling();
+    }
+    groups.into_iter().rev().flatten().collect()
+}
+
+/// Content of a string literal without templates (`"users"`)
+fn plain_string(node: &Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "string_literal" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let mut content = String::new();
+    for part in node.named_children(&mut cursor) {
+        match part.kind() {
+            "string_content" | "escape_sequence" => content.push_str(part.utf8_text(source).ok()?),
+            _ => return None,
+        }
+    }
+    Some(content)
+}
+
+fn node_range(node: &Node) -> Range {
+    Range {
+        start: Position {
+            line: node.start_position().row as u32,
+            column: node.start_position().column as u32,
+        },
+        end: Position {
+            line: node.end_position().row as u32,
+            column: node.end_position().column as u32,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function() {
+        let mut parser = KotlinParser::new().unwrap();
+        let result = parser.parse("fun greet(name: String, vararg tags: String, loud: Boolean = false): String = name\nprivate fun helper() {}");
+
+        assert_eq!(result.functions.len(), 2);
+        let greet = &result.functions[0];
+        assert_eq!(greet.name, "greet");
+        assert!(greet.is_exported);
+        assert_eq!(greet.return_type.as_deref(), Some("String"));
+        let params: Vec<(&str, Option<&str>, bool)> = greet.parameters.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref(), p.is_rest))
+            .collect();
+        assert_eq!(params, vec![("name", Some("String"), false), ("tags", Some("String"), true), ("loud", Some("Boolean"), false)]);
+        assert_eq!(greet.parameters[2].default_value.as_deref(), Some("false"));
+        assert!(!result.functions[1].is_exported);
+    }
+
+    #[test]
+    fn test_parse_spring_boot_controller() {
+        let mut parser = KotlinParser::new().unwrap();
+        let source = r#"
+package com.example.api
+
+import org.springframework.web.bind.annotation.*
+import com.example.model.User as AppUser
+
+/** Users API */
+@RestController
+@RequestMapping("/api/users")
+class UserController(private val service: UserService) : BaseController(), Auditable {
+    /** Load a single user */
+    @GetMapping("/{id}")
+    suspend fun getUser(@PathVariable id: Long): AppUser? {
+        return service.findById(id)
+    }
+
+    @PostMapping
+    fun create(@RequestBody user: AppUser) = service.save(user)
+
+    companion object {
+        fun build(): UserController = UserController(UserService())
+    }
+}
+"#;
+        let result = parser.parse(source);
+
+        let controller = result.classes.iter().find(|c| c.name == "UserController").unwrap();
+        assert_eq!(controller.decorators, vec!["@RestController", "@RequestMapping(\"/api/users\")"]);
+        assert_eq!(controller.extends.as_deref(), Some("BaseController"));
+        assert_eq!(controller.implements, vec!["Auditable"]);
+        assert_eq!(controller.namespace.as_deref(), Some("com.example.api"));
+        assert_eq!(controller.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["getUser", "create", "build"]);
+        // A private constructor property is still a property
+        assert_eq!(controller.properties[0].name, "service");
+        assert_eq!(controller.properties[0].visibility, Visibility::Private);
+
+        let get_user = result.functions.iter().find(|f| f.name == "getUser").unwrap();
+        assert!(get_user.is_async);
+        assert_eq!(get_user.qualified_name.as_deref(), Some("UserController.getUser"));
+        assert_eq!(get_user.return_type.as_deref(), Some("AppUser?"));
+        assert_eq!(get_user.doc_comment.as_deref(), Some("Load a single user"));
+        assert_eq!(result.functions.iter().find(|f| f.name == "build").unwrap().qualified_name.as_deref(), Some("UserController.build"));
+
+        assert_eq!(result.route_annotations(), vec![
+            ("GET".to_string(), "/api/users/{id}".to_string()),
+            ("POST".to_string(), "/api/users".to_string()),
+        ]);
+
+        let imports: Vec<(&str, Vec<&str>)> = result.imports.iter()
+            .map(|i| (i.source.as_str(), i.named.iter().map(|n| n.as_str()).collect()))
+            .collect();
+        assert_eq!(imports, vec![
+            ("org.springframework.web.bind.annotation", vec![]),
+            ("com.example.model.User", vec!["AppUser"]),
+        ]);
+
+        let call = result.calls.iter().find(|c| c.callee == "findById").unwrap();
+        assert_eq!(call.receiver.as_deref(), Some("service"));
+        assert_eq!(call.arg_count, 1);
+    }
+
+    #[test]
+    fn test_parse_room_and_exposed_entities() {
+        let mut parser = KotlinParser::new().unwrap();
+        let source = r#"
+@Entity(tableName = "users")
+data class User(
+    @PrimaryKey(autoGenerate = true) val id: Long = 0,
+    @ColumnInfo(name = "user_name") var name: String,
+    email: String
+)
+
+object Orders : Table("orders") {
+    val id = integer("id").autoIncrement()
+    val total: Column<Double> = double("total")
+}
+"#;
+        let result = parser.parse(source);
+
+        let user = result.classes.iter().find(|c| c.name == "User").unwrap();
+        assert_eq!(user.decorators, vec!["@Entity(tableName = \"users\")"]);
+        let props: Vec<(&str, Option<&str>, bool, Option<&str>)> = user.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref(), p.is_readonly, p.value.as_deref()))
+            .collect();
+        assert_eq!(props, vec![("id", Some("Long"), true, Some("0")), ("name", Some("String"), false, None)]);
+
+        let orders = result.classes.iter().find(|c| c.name == "Orders").unwrap();
+        assert_eq!(orders.extends.as_deref(), Some("Table"));
+        assert_eq!(orders.properties.len(), 2);
+        assert_eq!(orders.properties[1].type_annotation.as_deref(), Some("Column<Double>"));
+        assert_eq!(orders.properties[1].value.as_deref(), Some("double(\"total\")"));
+
+        let column_call = result.calls.iter().find(|c| c.callee == "integer").unwrap();
+        assert_eq!(column_call.string_arg.as_deref(), Some("id"));
+        assert!(result.calls.iter().any(|c| c.callee == "autoIncrement" && c.receiver.as_deref() == Some("integer(\"id\")")));
+    }
+
+    #[test]
+    fn test_parse_interface_and_abstract_class() {
+        let mut parser = KotlinParser::new().unwrap();
+        let result = parser.parse("interface Repo : Closeable { fun save(u: User) }\nabstract class Base\nclass Impl : Base(), Repo");
+
+        let repo = result.classes.iter().find(|c| c.name == "Repo").unwrap();
+        assert!(repo.is_abstract);
+        assert_eq!(repo.implements, vec!["Closeable"]);
+        assert!(result.classes.iter().find(|c| c.name == "Base").unwrap().is_abstract);
+        let implementation = result.classes.iter().find(|c| c.name == "Impl").unwrap();
+        assert!(!implementation.is_abstract);
+        assert_eq!(implementation.extends.as_deref(), Some("Base"));
+        assert_eq!(implementation.implements, vec!["Repo"]);
+    }
+}
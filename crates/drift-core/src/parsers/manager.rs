@@ -1,7 +1,12 @@
 //! Parser manager - Unified interface for all language parsers
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::Path;
 
+use rayon::prelude::*;
+
+use crate::scanner::detect_language_from_content;
 use super::types::*;
 use super::typescript::TypeScriptParser;
 use super::python::PythonParser;
@@ -12,6 +17,20 @@ use super::go::GoParser;
 use super::rust_lang::RustParser;
 use super::cpp::CppParser;
 use super::c::CParser;
+use super::ruby::RubyParser;
+use super::kotlin::KotlinParser;
+use super::swift::SwiftParser;
+use super::sfc;
+
+/// Outcome of parsing one file with [`ParserManager::parse_files`]
+pub struct FileParseResult {
+    /// Path as given by the caller
+    pub path: String,
+    /// None when the file could not be read or its language is unsupported
+    pub result: Option<ParseResult>,
+    /// Why `result` is None
+    pub error: Option<String>,
+}
 
 /// Manages parsers for all supported languages
 pub struct ParserManager {
@@ -24,6 +43,9 @@ pub struct ParserManager {
     rust_parser: Option<RustParser>,
     cpp_parser: Option<CppParser>,
     c_parser: Option<CParser>,
+    ruby_parser: Option<RubyParser>,
+    kotlin_parser: Option<KotlinParser>,
+    swift_parser: Option<SwiftParser>,
 }
 
 impl ParserManager {
@@ -39,6 +61,9 @@ impl ParserManager {
             rust_parser: RustParser::new().ok(),
             cpp_parser: CppParser::new().ok(),
             c_parser: CParser::new().ok(),
+            ruby_parser: RubyParser::new().ok(),
+            kotlin_parser: KotlinParser::new().ok(),
+            swift_parser: SwiftParser::new().ok(),
         }
     }
     
@@ -54,6 +79,9 @@ impl ParserManager {
             Language::Rust => self.rust_parser.is_some(),
             Language::Cpp => self.cpp_parser.is_some(),
             Language::C => self.c_parser.is_some(),
+            Language::Ruby => self.ruby_parser.is_some(),
+            Language::Kotlin => self.kotlin_parser.is_some(),
+            Language::Swift => self.swift_parser.is_some(),
         }
     }
 
@@ -88,18 +116,67 @@ impl ParserManager {
         if self.c_parser.is_some() {
             langs.push(Language::C);
         }
+        if self.ruby_parser.is_some() {
+            langs.push(Language::Ruby);
+        }
+        if self.kotlin_parser.is_some() {
+            langs.push(Language::Kotlin);
+        }
+        if self.swift_parser.is_some() {
+            langs.push(Language::Swift);
+        }
         langs
     }
     
     /// Parse a file by path
+    ///
+    /// Extensionless files (e.g. `bin/deploy`) fall back to shebang detection.
     pub fn parse_file(&mut self, path: &str, source: &str) -> Option<ParseResult> {
-        let language = Language::from_path(path)?;
+        self.parse_file_with_language(path, source, None)
+    }
+    
+    /// Parse a file by path, with an optional explicit language override
+    pub fn parse_file_with_language(
+        &mut self,
+        path: &str,
+        source: &str,
+        language: Option<Language>,
+    ) -> Option<ParseResult> {
+        if sfc::is_component(path) {
+            return self.parse_component(path, source, language);
+        }
+        
+        let language = language
+            .or_else(|| Language::from_path(path))
+            .or_else(|| detect_language_from_content(source).and_then(Language::from_name))?;
+        
+        // `.tsx` needs the JSX-aware grammar; plain `.ts` keeps `<T>expr` casts working
+        if language == Language::TypeScript && path.to_lowercase().ends_with(".tsx") {
+            let mut result = self.typescript_parser.as_mut().map(|p| p.parse_tsx(source))?;
+            result.collect_argument_refs(source);
+            return Some(result);
+        }
         self.parse(source, language)
     }
     
+    /// Parse a Vue/Svelte single-file component through its `<script>` blocks
+    ///
+    /// Ranges refer to the component file itself, not to the extracted script.
+    fn parse_component(&mut self, path: &str, source: &str, language: Option<Language>) -> Option<ParseResult> {
+        let script = sfc::extract_script(source);
+        let is_typescript = match language {
+            Some(language) => language == Language::TypeScript,
+            None => script.is_typescript,
+        };
+        let mut result = self.typescript_parser.as_mut()?.parse(&script.source, is_typescript);
+        let component = sfc::component_class(&result, &script.source, path);
+        result.classes.push(component);
+        Some(result)
+    }
+    
     /// Parse source code with explicit language
     pub fn parse(&mut self, source: &str, language: Language) -> Option<ParseResult> {
-        match language {
+        let mut result = match language {
             Language::TypeScript => {
                 self.typescript_parser.as_mut().map(|p| p.parse(source, true))
             }
@@ -130,7 +207,18 @@ impl ParserManager {
             Language::C => {
                 self.c_parser.as_mut().map(|p| p.parse(source))
             }
-        }
+            Language::Ruby => {
+                self.ruby_parser.as_mut().map(|p| p.parse(source))
+            }
+            Language::Kotlin => {
+                self.kotlin_parser.as_mut().map(|p| p.parse(source))
+            }
+            Language::Swift => {
+                self.swift_parser.as_mut().map(|p| p.parse(source))
+            }
+        }?;
+        result.collect_argument_refs(source);
+        Some(result)
     }
 
     /// Parse multiple files in batch
@@ -145,6 +233,33 @@ impl ParserManager {
         
         results
     }
+    
+    /// Read and parse files in parallel, one parser manager per worker thread
+    ///
+    /// Relative paths are resolved against `root` when given. Results come
+    /// back in the order of `paths`.
+    pub fn parse_files(paths: &[String], root: Option<&Path>, language: Option<Language>) -> Vec<FileParseResult> {
+        thread_local! {
+            static PARSER: RefCell<ParserManager> = RefCell::new(ParserManager::new());
+        }
+        
+        paths.par_iter().map(|path| {
+            let full_path = match root {
+                Some(root) => root.join(path),
+                None => Path::new(path).to_path_buf(),
+            };
+            let parsed = std::fs::read_to_string(&full_path)
+                .map_err(|e| format!("Failed to read file: {}", e))
+                .and_then(|source| {
+                    PARSER.with(|parser| parser.borrow_mut().parse_file_with_language(path, &source, language))
+                        .ok_or_else(|| "Unsupported language".to_string())
+                });
+            match parsed {
+                Ok(result) => FileParseResult { path: path.clone(), result: Some(result), error: None },
+                Err(e) => FileParseResult { path: path.clone(), result: None, error: Some(e) },
+            }
+        }).collect()
+    }
 }
 
 impl Default for ParserManager {
@@ -278,6 +393,40 @@ mod tests {
         assert_eq!(result.language, Language::C);
     }
 
+    #[test]
+    fn test_parse_ruby_file() {
+        let mut manager = ParserManager::new();
+        
+        for path in ["app/models/user.rb", "lib/tasks/db.rake", "Gemfile"] {
+            let result = manager.parse_file(path, "source 'https://rubygems.org'\ndef hello; end");
+            let result = result.unwrap();
+            assert_eq!(result.language, Language::Ruby);
+            assert_eq!(result.functions.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_parse_kotlin_file() {
+        let mut manager = ParserManager::new();
+        
+        for path in ["src/main/kotlin/App.kt", "build.gradle.kts"] {
+            let result = manager.parse_file(path, "suspend fun main() { println(\"hi\") }");
+            let result = result.unwrap();
+            assert_eq!(result.language, Language::Kotlin);
+            assert!(result.functions[0].is_async);
+        }
+    }
+
+    #[test]
+    fn test_parse_swift_file() {
+        let mut manager = ParserManager::new();
+        let result = manager.parse_file("Sources/App/ContentView.swift", "struct ContentView { func load() async {} }");
+        
+        let result = result.unwrap();
+        assert_eq!(result.language, Language::Swift);
+        assert!(result.functions[0].is_async);
+    }
+
     #[test]
     fn test_parse_php_file() {
         let mut manager = ParserManager::new();
@@ -296,14 +445,171 @@ mod tests {
         let manager = ParserManager::new();
         let langs = manager.supported_languages();
         
-        // Should support all 10 languages (TS, JS, Python, Java, C#, PHP, Go, Rust, C++, C)
-        assert!(langs.len() >= 10);
+        // Should support all 13 languages (TS, JS, Python, Java, C#, PHP, Go, Rust, C++, C, Ruby, Kotlin, Swift)
+        assert!(langs.len() >= 13);
+        assert!(langs.contains(&Language::Ruby));
+        assert!(langs.contains(&Language::Kotlin));
+        assert!(langs.contains(&Language::Swift));
+    }
+
+    #[test]
+    fn test_parse_shebang_and_override() {
+        let mut manager = ParserManager::new();
+        
+        let result = manager.parse_file("bin/deploy", "#!/usr/bin/env python3\ndef main():\n    pass\n");
+        assert_eq!(result.unwrap().language, Language::Python);
+        
+        let result = manager.parse_file_with_language(
+            "scripts/build",
+            "export function build() {}",
+            Some(Language::TypeScript),
+        );
+        assert_eq!(result.unwrap().functions.len(), 1);
+    }
+
+    #[test]
+    fn test_syntax_errors_reported() {
+        let mut manager = ParserManager::new();
+        
+        let clean = manager.parse_file("ok.py", "def ok():\n    return 1\n").unwrap();
+        assert_eq!(clean.error_node_count, 0);
+        assert!(clean.errors.is_empty());
+        
+        let source = "def ok():\n    return 1\n\ndef broken(:\n    pass\n";
+        let result = manager.parse_file("broken.py", source).unwrap();
+        assert!(result.error_node_count > 0);
+        assert_eq!(result.errors.len(), result.error_node_count);
+        assert_eq!(result.errors[0].range.start.line, 3);
+        assert!(result.functions.iter().any(|f| f.name == "ok"));
+        
+        let result = manager.parse_file("broken.ts", "function f() {\n  const x = ;\n}\n").unwrap();
+        assert!(result.error_node_count > 0);
+        assert!(result.errors.iter().all(|e| e.range.start.line >= 1));
+    }
+
+    #[test]
+    fn test_class_methods_match_flat_functions() {
+        let mut manager = ParserManager::new();
+        let cases = [
+            ("svc.ts", "export class UserService {\n  static create() { return new UserService(); }\n  protected async find(id: string) { return id; }\n}\n", Visibility::Protected),
+            ("svc.py", "class UserService:\n    @staticmethod\n    def create():\n        pass\n\n    async def _find(self, id):\n        pass\n", Visibility::Protected),
+            ("UserService.java", "public class UserService {\n    public static UserService create() { return null; }\n    private String find(String id) { return id; }\n}\n", Visibility::Private),
+            ("UserService.cs", "public class UserService {\n    public static UserService Create() => null;\n    protected async Task<string> Find(string id) { return id; }\n}\n", Visibility::Protected),
+            ("svc.rs", "pub struct UserService;\nimpl UserService {\n    pub fn create() -> Self { UserService }\n    async fn find(&self, id: &str) {}\n}\n", Visibility::Private),
+            ("svc.cpp", "class UserService {\npublic:\n    static UserService create();\nprivate:\n    void find(int id) {}\n};\nUserService UserService::create() { return UserService(); }\n", Visibility::Private),
+        ];
+        
+        for (file, source, find_visibility) in cases {
+            let result = manager.parse_file(file, source).unwrap();
+            let class = result.classes.iter().find(|c| c.name == "UserService").unwrap();
+            assert_eq!(class.methods.len(), 2, "{}", file);
+            for method in &class.methods {
+                let listed = result.functions.iter().any(|f| f.name == method.name && f.range.start == method.range.start);
+                assert!(listed, "{}: {} missing from functions", file, method.name);
+            }
+            
+            let method = |name: &str| class.methods.iter()
+                .find(|m| m.name.trim_start_matches('_').eq_ignore_ascii_case(name))
+                .unwrap();
+            assert!(method("create").is_static, "{}", file);
+            assert_eq!(method("create").visibility, Visibility::Public, "{}", file);
+            assert!(!method("find").is_static, "{}", file);
+            assert_eq!(method("find").visibility, find_visibility, "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_generic_type_parameters() {
+        let mut manager = ParserManager::new();
+        let cases = [
+            ("repo.ts", "export class Repository<T, K extends string = string> {\n  find<R>(id: K): R { return null as any; }\n}\n"),
+            ("Repository.java", "public class Repository<T, K extends Comparable<K>> {\n    public <R> R find(K id) { return null; }\n}\n"),
+            ("Repository.cs", "public class Repository<T, K> where T : class {\n    public R Find<R>(K id) { return default; }\n}\n"),
+            ("repo.rs", "pub struct Repository<'a, T, K: Ord> { items: &'a [T], key: K }\npub fn find<R: Default, const N: usize>() -> R { R::default() }\n"),
+            ("repo.cpp", "template <typename T, class K = int>\nclass Repository {};\ntemplate <typename R, int N>\nR find(int id) { return R(); }\n"),
+        ];
+
+        for (file, source) in cases {
+            let result = manager.parse_file(file, source).unwrap();
+            let class = result.classes.iter().find(|c| c.name == "Repository").unwrap();
+            assert_eq!(class.type_parameters, vec!["T", "K"], "{}", file);
+            let find = result.functions.iter().find(|f| f.name.eq_ignore_ascii_case("find")).unwrap();
+            assert_eq!(find.type_parameters, vec!["R"], "{}", file);
+        }
+
+        let plain = manager.parse_file("plain.ts", "function find(id: string) {}").unwrap();
+        assert!(plain.functions[0].type_parameters.is_empty());
+    }
+
+    #[test]
+    fn test_enums_extracted() {
+        let mut manager = ParserManager::new();
+        let cases = [
+            ("status.ts", "export enum Status {\n  Active = 'active',\n  Inactive,\n}\n"),
+            ("status.rs", "#[derive(Debug)]\npub enum Status {\n    Active = 1,\n    Inactive,\n}\n"),
+            ("Status.cs", "[Flags]\npublic enum Status {\n    Active = 1,\n    Inactive\n}\n"),
+            ("Status.java", "public enum Status {\n    ACTIVE(\"active\"),\n    INACTIVE;\n    Status() {}\n    Status(String v) {}\n}\n"),
+        ];
+
+        for (file, source) in cases {
+            let result = manager.parse_file(file, source).unwrap();
+            assert_eq!(result.enums.len(), 1, "{}", file);
+            let status = &result.enums[0];
+            assert_eq!(status.name, "Status", "{}", file);
+            assert!(status.is_exported, "{}", file);
+            assert_eq!(status.variants.len(), 2, "{}", file);
+            assert!(status.variants[0].name.eq_ignore_ascii_case("active"), "{}", file);
+            assert!(status.variants[0].value.is_some(), "{}", file);
+            assert_eq!(status.variants[1].value, None, "{}", file);
+            assert!(!result.classes.iter().any(|c| c.name == "Status"), "{}", file);
+        }
+
+        let result = manager.parse_file("role.ts", "type Role = 'admin' | 'member' | 'guest';\ntype Id = string | number;\n").unwrap();
+        assert_eq!(result.enums.len(), 1);
+        let names: Vec<&str> = result.enums[0].variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["admin", "member", "guest"]);
+        assert_eq!(result.enums[0].variants[0].value.as_deref(), Some("'admin'"));
+        assert!(!result.enums[0].is_exported);
+    }
+
+    #[test]
+    fn test_ad_hoc_query() {
+        let mut manager = ParserManager::new();
+        let source = "async function sync(items) {\n  for (const item of items) {\n    await save(item);\n  }\n  await done();\n}\n";
+        let result = manager.parse_file("sync.js", source).unwrap();
+        
+        let captures = result.query("(for_in_statement body: (_ (expression_statement (await_expression) @awaited)))", source).unwrap();
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].name, "awaited");
+        assert_eq!(captures[0].text, "await save(item)");
+        assert_eq!(captures[0].range.start.line, 2);
+        
+        let err = result.query("(for_in_statement body: (", source).unwrap_err();
+        assert!(err.starts_with("Invalid query at offset"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_files_parallel() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "def a():\n    pass\n").unwrap();
+        std::fs::write(dir.path().join("b.ts"), "export function b() {}").unwrap();
+        std::fs::write(dir.path().join("c.scala"), "object C").unwrap();
+        
+        let paths: Vec<String> = ["a.py", "b.ts", "c.scala", "missing.go"].iter().map(|p| p.to_string()).collect();
+        let results = ParserManager::parse_files(&paths, Some(dir.path()), None);
+        
+        let order: Vec<&str> = results.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(order, vec!["a.py", "b.ts", "c.scala", "missing.go"]);
+        assert_eq!(results[0].result.as_ref().unwrap().functions[0].name, "a");
+        assert_eq!(results[1].result.as_ref().unwrap().language, Language::TypeScript);
+        assert_eq!(results[2].error.as_deref(), Some("Unsupported language"));
+        assert!(results[3].error.as_deref().unwrap().starts_with("Failed to read file"));
     }
 
     #[test]
     fn test_unsupported_language() {
         let mut manager = ParserManager::new();
-        let result = manager.parse_file("test.rb", "def hello; end");
+        let result = manager.parse_file("test.scala", "func hello() {}");
         
         assert!(result.is_none());
     }
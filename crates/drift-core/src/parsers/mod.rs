@@ -4,7 +4,7 @@
 //! No WASM overhead - grammars are linked at compile time.
 //!
 //! Supported languages:
-//! - TypeScript/JavaScript
+//! - TypeScript/JavaScript (including Vue/Svelte `<script>` blocks)
 //! - Python
 //! - Java
 //! - C#
@@ -13,6 +13,9 @@
 //! - Rust
 //! - C++
 //! - C
+//! - Ruby
+//! - Kotlin
+//! - Swift
 
 mod types;
 mod manager;
@@ -25,9 +28,14 @@ mod go;
 mod rust_lang;
 mod cpp;
 mod c;
+mod ruby;
+mod kotlin;
+mod swift;
+mod sfc;
+mod generics;
 
 pub use types::*;
-pub use manager::ParserManager;
+pub use manager::{ParserManager, FileParseResult};
 pub use typescript::TypeScriptParser;
 pub use python::PythonParser;
 pub use java::JavaParser;
@@ -37,3 +45,6 @@ pub use go::GoParser;
 pub use rust_lang::RustParser;
 pub use cpp::CppParser;
 pub use c::CParser;
+pub use ruby::RubyParser;
+pub use kotlin::KotlinParser;
+pub use swift::SwiftParser;
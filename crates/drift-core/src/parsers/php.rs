@@ -64,15 +64,17 @@ impl PhpParser {
             Some(t) => t,
             None => {
                 let mut result = ParseResult::new(Language::Php);
-                result.errors.push(ParseError { message: "Failed to parse source".to_string(), range: Range::new(0, 0, 0, 0) });
+                result.errors.push(ParseError { message: "Failed to parse source".to_string(), range: Range::new(0, 0, 0, 0), snippet: None });
                 return result;
             }
         };
         let root = tree.root_node();
         let source_bytes = source.as_bytes();
         let mut result = ParseResult::with_tree(Language::Php, tree.clone());
+        result.record_syntax_errors(&root, source_bytes);
         self.extract_functions(&root, source_bytes, &mut result);
         self.extract_classes(&root, source_bytes, &mut result);
+        result.attach_methods();
         self.extract_uses(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         result.parse_time_us = start.elapsed().as_micros() as u64;
@@ -172,6 +174,7 @@ impl PhpParser {
             let mut name = String::new();
             let mut range = Range::new(0, 0, 0, 0);
             let mut is_public = true;
+            let mut visibility = Visibility::Public;
             let mut return_type: Option<String> = None;
             let mut parameters = Vec::new();
             let mut decorators = Vec::new();
@@ -181,7 +184,10 @@ impl PhpParser {
                 let node = capture.node;
                 match self.function_query.capture_names()[capture.index as usize] {
                     "name" => { name = node.utf8_text(source).unwrap_or("").to_string(); }
-                    "visibility" => { is_public = node.utf8_text(source).unwrap_or("") == "public"; }
+                    "visibility" => {
+                        visibility = match node.utf8_text(source).unwrap_or("") { "private" => Visibility::Private, "protected" => Visibility::Protected, _ => Visibility::Public };
+                        is_public = visibility == Visibility::Public;
+                    }
                     "return_type" => { let rt = node.utf8_text(source).unwrap_or("").trim_start_matches(':').trim(); if !rt.is_empty() { return_type = Some(rt.to_string()); } }
                     "params" => { parameters = self.extract_parameters(&node, source); }
                     "method_attrs" => { decorators = self.extract_attributes(&node, source); }
@@ -191,7 +197,16 @@ impl PhpParser {
             }
             if !name.is_empty() {
                 let doc_comment = method_node.as_ref().and_then(|n| self.extract_doc_comment(n, source));
-                result.functions.push(FunctionInfo { name, qualified_name: None, parameters, return_type, is_exported: is_public, is_async: false, is_generator: false, range, decorators, doc_comment });
+                let is_static = method_node.is_some_and(|n| (0..n.child_count()).filter_map(|i| n.child(i)).any(|c| c.kind() == "static_modifier"));
+                // Methods are qualified by their class, interface or trait
+                let qualified_name = method_node
+                    .filter(|n| n.kind() == "method_declaration")
+                    .and_then(|n| n.parent())
+                    .and_then(|body| body.parent())
+                    .and_then(|decl| decl.child_by_field_name("name"))
+                    .and_then(|n| n.utf8_text(source).ok())
+                    .map(|owner| format!("{}.{}", owner, name));
+                result.functions.push(FunctionInfo { name, qualified_name, parameters, return_type, is_exported: is_public, is_async: false, is_generator: false, visibility, is_static, type_parameters: Vec::new(), range, decorators, doc_comment });
             }
         }
     }
@@ -227,7 +242,7 @@ impl PhpParser {
                     .map(|body| self.extract_class_properties(&body, source))
                     .unwrap_or_default();
                 
-                result.classes.push(ClassInfo { name, extends, implements, is_exported: true, is_abstract, methods: Vec::new(), properties, range, decorators: attributes });
+                result.classes.push(ClassInfo { name, extends, implements, is_exported: true, is_abstract, methods: Vec::new(), properties, range, decorators: attributes, namespace: None, is_partial: false, type_parameters: Vec::new() });
             }
         }
     }
@@ -284,28 +299,26 @@ impl PhpParser {
                         type_annotation = Some(child.utf8_text(source).unwrap_or("").to_string());
                     }
                     "property_element" => {
-                        // Get the variable name
-                        let mut var_cursor = child.walk();
-                        if var_cursor.goto_first_child() {
-                            loop {
-                                let var_child = var_cursor.node();
-                                if var_child.kind() == "variable_name" {
-                                    let name = var_child.utf8_text(source).unwrap_or("")
-                                        .trim_start_matches('$')
-                                        .to_string();
-                                    if !name.is_empty() {
-                                        props.push(PropertyInfo {
-                                            name,
-                                            type_annotation: type_annotation.clone(),
-                                            is_static,
-                                            is_readonly,
-                                            visibility,
-                                            tags: None,
-                                        });
-                                    }
-                                }
-                                if !var_cursor.goto_next_sibling() { break; }
-                            }
+                        let name = child.child_by_field_name("name")
+                            .or_else(|| find_child(&child, "variable_name"))
+                            .map(|n| n.utf8_text(source).unwrap_or("").trim_start_matches('$').to_string())
+                            .unwrap_or_default();
+                        // `= value` is a default_value field in newer grammars, a property_initializer node in older ones
+                        let value = child.child_by_field_name("default_value")
+                            .or_else(|| find_child(&child, "property_initializer").and_then(|n| n.named_child(0)))
+                            .map(|n| n.utf8_text(source).unwrap_or("").to_string());
+                        if !name.is_empty() {
+                            props.push(PropertyInfo {
+                                name,
+                                type_annotation: type_annotation.clone(),
+                                is_static,
+                                is_readonly,
+                                visibility,
+                                tags: None,
+                                value,
+                                is_computed: false,
+                                decorators: Vec::new(),
+                            });
                         }
                     }
                     _ => {}
@@ -334,7 +347,7 @@ impl PhpParser {
             }
             if !namespace.is_empty() {
                 let class_name = namespace.rsplit('\\').next().unwrap_or(&namespace).to_string();
-                result.imports.push(ImportInfo { source: namespace, named: vec![class_name], default: None, namespace: None, is_type_only: false, range });
+                result.imports.push(ImportInfo { source: namespace, named: vec![class_name], default: None, namespace: None, is_type_only: false, relative_level: 0, range });
             }
         }
     }
@@ -359,13 +372,19 @@ impl PhpParser {
                     _ => {}
                 }
             }
-            if !callee.is_empty() { result.calls.push(CallSite { callee, receiver, arg_count, range }); }
+            if !callee.is_empty() { result.calls.push(CallSite { callee, receiver, arg_count, range, string_arg: None }); }
         }
     }
 }
 
 impl Default for PhpParser { fn default() -> Self { Self::new().expect("Failed to create PHP parser") } }
 
+fn find_child<'a>(node: &Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).find(|c| c.kind() == kind);
+    found
+}
+
 fn node_range(node: &Node) -> Range {
     Range { start: Position { line: node.start_position().row as u32, column: node.start_position().column as u32 }, end: Position { line: node.end_position().row as u32, column: node.end_position().column as u32 } }
 }
@@ -470,4 +489,28 @@ mod tests {
         assert!(method.decorators.iter().any(|d| d.contains("Route")), "Expected Route, got: {:?}", method.decorators);
         assert!(method.decorators.iter().any(|d| d.contains("IsGranted")), "Expected IsGranted, got: {:?}", method.decorators);
     }
+
+    #[test]
+    fn test_parse_property_values() {
+        let mut parser = PhpParser::new().unwrap();
+        let source = "<?php\nclass User extends Model {\n    protected $table = 'app_users';\n    protected $fillable = ['name', 'email'];\n    private $cache;\n}";
+        let result = parser.parse(source);
+        let user = &result.classes[0];
+        let value = |name: &str| user.properties.iter().find(|p| p.name == name).unwrap().value.clone();
+        assert_eq!(value("table"), Some("'app_users'".to_string()));
+        assert_eq!(value("fillable"), Some("['name', 'email']".to_string()));
+        assert_eq!(value("cache"), None);
+    }
+
+    #[test]
+    fn test_symfony_route_annotations() {
+        let mut parser = PhpParser::new().unwrap();
+        let source = "<?php\n#[Route('/api/users')]\nclass UserController {\n    #[Route('/{id}', name: 'user_show', methods: ['GET'])]\n    public function show(): void {}\n    #[Route(path: '/', methods: ['POST', 'PUT'])]\n    public function save(): void {}\n}";
+        let result = parser.parse(source);
+        assert_eq!(result.route_annotations(), vec![
+            ("GET".to_string(), "/api/users/{id}".to_string()),
+            ("POST".to_string(), "/api/users/".to_string()),
+            ("PUT".to_string(), "/api/users/".to_string()),
+        ]);
+    }
 }
@@ -10,7 +10,7 @@
 //! - Docstring extraction ("""...""")
 //! - Base class extraction (multiple inheritance)
 //! - Generator detection (yield)
-//! - Class property extraction
+//! - Class property extraction (class-level and typed `self.x` attributes)
 
 use std::time::Instant;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
@@ -45,13 +45,10 @@ impl PythonParser {
                 body: (block) @body
             ) @function
             
-            (decorated_definition
-                (decorator) @decorator
-                definition: (function_definition
-                    name: (identifier) @name
-                    parameters: (parameters) @params
-                )
-            ) @decorated_function
+            (assignment
+                left: (identifier) @name
+                right: (lambda) @lambda
+            )
             "#,
         ).map_err(|e| format!("Failed to create function query: {}", e))?;
         
@@ -76,17 +73,9 @@ impl PythonParser {
         let import_query = Query::new(
             &language.into(),
             r#"
-            (import_statement
-                name: (dotted_name) @module
-            ) @import
+            (import_statement) @import
             
-            (import_from_statement
-                module_name: (dotted_name) @module
-                name: [
-                    (dotted_name) @name
-                    (aliased_import name: (dotted_name) @name)
-                ]*
-            ) @from_import
+            (import_from_statement) @from_import
             "#,
         ).map_err(|e| format!("Failed to create import query: {}", e))?;
         
@@ -127,6 +116,7 @@ impl PythonParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -137,8 +127,11 @@ impl PythonParser {
         
         let mut result = ParseResult::with_tree(Language::Python, tree.clone());
         
+        result.record_syntax_errors(&root, source_bytes);
+        
         self.extract_functions(&root, source_bytes, &mut result);
         self.extract_classes(&root, source_bytes, &mut result);
+        result.attach_methods();
         self.extract_imports(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         
@@ -147,170 +140,70 @@ impl PythonParser {
     }
     
     fn extract_functions(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
-        // First pass: collect all decorated function lines to avoid duplicates
-        let mut decorated_lines = std::collections::HashSet::new();
-        {
-            let mut cursor = QueryCursor::new();
-            for m in cursor.matches(&self.function_query, *root, source) {
-                for capture in m.captures {
-                    let capture_name = self.function_query.capture_names()[capture.index as usize];
-                    if capture_name == "decorated_function" {
-                        // Find the inner function_definition to get its line
-                        let mut inner_cursor = capture.node.walk();
-                        if inner_cursor.goto_first_child() {
-                            loop {
-                                let child = inner_cursor.node();
-                                if child.kind() == "function_definition" {
-                                    decorated_lines.insert(child.start_position().row);
-                                    break;
-                                }
-                                if !inner_cursor.goto_next_sibling() {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Second pass: extract functions
-        // Track which functions we've already processed to avoid duplicates
-        let mut processed = std::collections::HashSet::new();
         let mut cursor = QueryCursor::new();
         
         for m in cursor.matches(&self.function_query, *root, source) {
-            let mut name = String::new();
-            let mut decorators = Vec::new();
-            let mut range = Range::new(0, 0, 0, 0);
-            let mut is_async = false;
-            let mut is_decorated = false;
-            let mut inner_func_line: Option<u32> = None;
-            let mut func_node: Option<Node> = None;
-            let mut params_node: Option<Node> = None;
-            let mut return_type: Option<String> = None;
-            
             for capture in m.captures {
-                let node = capture.node;
                 let capture_name = self.function_query.capture_names()[capture.index as usize];
-                
-                match capture_name {
-                    "name" => {
-                        name = node.utf8_text(source).unwrap_or("").to_string();
-                    }
-                    "decorator" => {
-                        decorators.push(node.utf8_text(source).unwrap_or("").to_string());
-                    }
-                    "params" => {
-                        params_node = Some(node);
-                    }
-                    "return_type" => {
-                        return_type = Some(node.utf8_text(source).unwrap_or("").to_string());
-                    }
-                    "function" => {
-                        range = node_range(&node);
-                        inner_func_line = Some(node.start_position().row as u32);
-                        func_node = Some(node);
-                        // Check if async
-                        if let Some(first_child) = node.child(0) {
-                            if first_child.kind() == "async" {
-                                is_async = true;
-                            }
-                        }
-                    }
-                    "decorated_function" => {
-                        range = node_range(&node);
-                        is_decorated = true;
-                        
-                        // Extract ALL decorators from the decorated_definition
-                        decorators = self.extract_decorators(&node, source);
-                        
-                        // Find the inner function_definition and extract parameters immediately
-                        let mut inner_cursor = node.walk();
-                        if inner_cursor.goto_first_child() {
-                            loop {
-                                let child = inner_cursor.node();
-                                if child.kind() == "function_definition" {
-                                    inner_func_line = Some(child.start_position().row as u32);
-                                    func_node = Some(child);
-                                    
-                                    // Check if async
-                                    if let Some(first_child) = child.child(0) {
-                                        if first_child.kind() == "async" {
-                                            is_async = true;
-                                        }
-                                    }
-                                    
-                                    // Extract return type from the function_definition
-                                    return_type = self.extract_return_type(&child, source);
-                                    
-                                    // Find and store parameters node from the function_definition
-                                    // We need to find it within the child's children
-                                    let mut func_cursor = child.walk();
-                                    if func_cursor.goto_first_child() {
-                                        loop {
-                                            let func_child = func_cursor.node();
-                                            if func_child.kind() == "parameters" {
-                                                params_node = Some(func_child);
-                                                break;
-                                            }
-                                            if !func_cursor.goto_next_sibling() {
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    
-                                    break;
-                                }
-                                if !inner_cursor.goto_next_sibling() {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ => {}
+                if capture_name == "lambda" {
+                    self.extract_lambda(&capture.node, source, result);
+                    continue;
                 }
-            }
-            
-            if !name.is_empty() {
-                // Skip plain function matches if they're part of a decorated_definition
-                if !is_decorated {
-                    if let Some(line) = inner_func_line {
-                        if decorated_lines.contains(&(line as usize)) {
-                            continue;
-                        }
-                    }
+                if capture_name != "function" {
+                    continue;
                 }
                 
-                // Create a key based on name and start line to deduplicate
-                let key = (name.clone(), range.start.line);
-                if processed.contains(&key) {
+                let func_node = capture.node;
+                let name = func_node.child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source).ok())
+                    .unwrap_or("")
+                    .to_string();
+                if name.is_empty() {
                     continue;
                 }
-                processed.insert(key);
+                
+                // Decorators live on the wrapping decorated_definition, which also
+                // defines the range (so it starts at the first decorator)
+                let decorated = func_node.parent().filter(|p| p.kind() == "decorated_definition");
+                let decorators = decorated
+                    .map(|n| self.extract_decorators(&n, source))
+                    .unwrap_or_default();
+                let range = node_range(&decorated.unwrap_or(func_node));
+                
+                // Methods are qualified by their (possibly nested) class: `Outer.Inner.method`
+                let qualified_name = self.enclosing_class_path(&func_node, source)
+                    .map(|class_path| format!("{}.{}", class_path, name));
+                
+                // Methods follow the underscore naming convention for visibility
+                let visibility = if qualified_name.is_some() { name_visibility(&name) } else { Visibility::Public };
+                let is_static = decorators.iter().any(|d| d == "@staticmethod" || d == "@classmethod");
+                
+                let is_async = func_node.child(0).map(|c| c.kind() == "async").unwrap_or(false);
                 
                 // Extract parameters
-                let parameters = params_node
+                let parameters = self.find_parameters_node(&func_node)
                     .map(|n| self.extract_parameters(&n, source))
                     .unwrap_or_default();
                 
+                let return_type = self.extract_return_type(&func_node, source);
+                
                 // Extract docstring
-                let doc_comment = func_node
-                    .and_then(|n| self.extract_docstring(&n, source));
+                let doc_comment = self.extract_docstring(&func_node, source);
                 
                 // Check if generator (contains yield)
-                let is_generator = func_node
-                    .map(|n| self.contains_yield(&n, source))
-                    .unwrap_or(false);
+                let is_generator = self.contains_yield(&func_node, source);
                 
                 result.functions.push(FunctionInfo {
                     name,
-                    qualified_name: None,
+                    qualified_name,
                     parameters,
                     return_type,
                     is_exported: true, // Python functions are "exported" by default
                     is_async,
                     is_generator,
+                    visibility,
+                    is_static,
+                    type_parameters: Vec::new(),
                     range,
                     decorators,
                     doc_comment,
@@ -319,6 +212,69 @@ impl PythonParser {
         }
     }
     
+    /// A lambda bound to a name (`handler = lambda req: ...`), extracted as a
+    /// function under that name
+    fn extract_lambda(&self, lambda_node: &Node, source: &[u8], result: &mut ParseResult) {
+        let Some(assignment) = lambda_node.parent() else { return };
+        let name = assignment.child_by_field_name("left")
+            .and_then(|n| n.utf8_text(source).ok())
+            .unwrap_or("")
+            .to_string();
+        if name.is_empty() {
+            return;
+        }
+        
+        let qualified_name = self.enclosing_class_path(lambda_node, source)
+            .map(|class_path| format!("{}.{}", class_path, name));
+        let parameters = lambda_node.child_by_field_name("parameters")
+            .map(|n| self.extract_parameters(&n, source))
+            .unwrap_or_default();
+        
+        result.functions.push(FunctionInfo {
+            name,
+            qualified_name,
+            parameters,
+            return_type: None,
+            is_exported: true,
+            is_async: false,
+            is_generator: false,
+            visibility: Visibility::Public,
+            is_static: false,
+            type_parameters: Vec::new(),
+            range: node_range(&assignment),
+            decorators: Vec::new(),
+            doc_comment: None,
+        });
+    }
+    
+    /// Dotted path of the classes a method is defined in, innermost last
+    ///
+    /// Returns None for module-level functions and functions nested inside
+    /// other functions.
+    fn enclosing_class_path(&self, func_node: &Node, source: &[u8]) -> Option<String> {
+        let mut classes = Vec::new();
+        let mut current = func_node.parent();
+        
+        while let Some(node) = current {
+            match node.kind() {
+                "class_definition" => {
+                    if let Some(name) = node.child_by_field_name("name").and_then(|n| n.utf8_text(source).ok()) {
+                        classes.push(name);
+                    }
+                }
+                "function_definition" => break,
+                _ => {}
+            }
+            current = node.parent();
+        }
+        
+        if classes.is_empty() {
+            return None;
+        }
+        classes.reverse();
+        Some(classes.join("."))
+    }
+    
     /// Extract parameters from a parameters node
     fn extract_parameters(&self, params_node: &Node, source: &[u8]) -> Vec<ParameterInfo> {
         let mut parameters = Vec::new();
@@ -705,18 +661,27 @@ impl PythonParser {
                     .map(|n| self.extract_decorators(&n, source))
                     .unwrap_or_default();
                 
+                // ABCs, protocols, and classes declaring abstract methods
+                let class_text = class_node.and_then(|n| n.utf8_text(source).ok()).unwrap_or("");
+                let is_abstract = decorators.iter().any(|d| d.contains("abstract"))
+                    || bases.iter().any(|b| matches!(b.rsplit('.').next(), Some("ABC" | "Protocol")))
+                    || class_text.contains("metaclass=ABCMeta")
+                    || class_text.contains("abstractmethod");
+                
                 // Python uses multiple inheritance - first base is "extends", rest are "implements"
                 let extends = bases.first().cloned();
                 let implements: Vec<String> = bases.into_iter().skip(1).collect();
                 
-                // Extract class properties (class-level assignments with type annotations)
-                let properties = class_node
+                // Extract class properties (class-level assignments with type annotations),
+                // then instance attributes whose type `__init__` makes known
+                let mut properties = class_node
                     .map(|n| self.extract_class_properties(&n, source))
                     .unwrap_or_default();
-                
-                // Check if abstract (has ABC in bases or @abstractmethod decorators)
-                let is_abstract = decorators.iter().any(|d| d.contains("abstract")) ||
-                    extends.as_ref().map(|e| e.contains("ABC")).unwrap_or(false);
+                for attribute in class_node.map(|n| self.extract_instance_attributes(&n, source)).unwrap_or_default() {
+                    if !properties.iter().any(|p| p.name == attribute.name) {
+                        properties.push(attribute);
+                    }
+                }
                 
                 result.classes.push(ClassInfo {
                     name,
@@ -728,6 +693,9 @@ impl PythonParser {
                     properties,
                     range,
                     decorators,
+                    namespace: None,
+                    is_partial: false,
+                    type_parameters: Vec::new(),
                 });
             }
         }
@@ -781,6 +749,106 @@ impl PythonParser {
         properties
     }
     
+    /// Extract `self.<name> = ...` attributes assigned in `__init__` whose type
+    /// is known: an annotation on the assignment, an annotated `__init__`
+    /// parameter assigned as-is (`self.repo = repo`), or a constructor call
+    /// (`self.cache = FileCache()`)
+    fn extract_instance_attributes(&self, class_node: &Node, source: &[u8]) -> Vec<PropertyInfo> {
+        let Some(body) = class_node.child_by_field_name("body") else { return Vec::new() };
+        let mut cursor = body.walk();
+        let init = body.named_children(&mut cursor)
+            .map(|n| if n.kind() == "decorated_definition" { n.child_by_field_name("definition").unwrap_or(n) } else { n })
+            .find(|n| {
+                n.kind() == "function_definition"
+                    && n.child_by_field_name("name").and_then(|name| name.utf8_text(source).ok()) == Some("__init__")
+            });
+        let Some(init) = init else { return Vec::new() };
+        
+        // Annotated parameters: name -> type
+        let mut param_types: Vec<(String, String)> = Vec::new();
+        if let Some(params) = init.child_by_field_name("parameters") {
+            let mut cursor = params.walk();
+            for param in params.named_children(&mut cursor) {
+                let name = match param.kind() {
+                    "typed_parameter" => {
+                        let mut c = param.walk();
+                        let found = param.named_children(&mut c).find(|n| n.kind() == "identifier");
+                        found
+                    }
+                    "typed_default_parameter" => param.child_by_field_name("name"),
+                    _ => None,
+                };
+                let ty = param.child_by_field_name("type");
+                if let (Some(name), Some(ty)) = (name, ty) {
+                    param_types.push((
+                        name.utf8_text(source).unwrap_or("").to_string(),
+                        ty.utf8_text(source).unwrap_or("").to_string(),
+                    ));
+                }
+            }
+        }
+        
+        let mut attributes: Vec<PropertyInfo> = Vec::new();
+        let mut stack: Vec<Node> = init.child_by_field_name("body").into_iter().collect();
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                // Assignments in nested scopes don't run as part of `__init__`
+                "function_definition" | "class_definition" | "lambda" => continue,
+                "assignment" => {
+                    if let Some(attribute) = self.typed_self_assignment(&node, &param_types, source) {
+                        if !attributes.iter().any(|a| a.name == attribute.name) {
+                            attributes.push(attribute);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        attributes.sort_by_key(|a| a.name.clone());
+        attributes
+    }
+    
+    /// `self.<name> = <value>` as a property, if the attribute's type is known
+    fn typed_self_assignment(&self, assignment: &Node, param_types: &[(String, String)], source: &[u8]) -> Option<PropertyInfo> {
+        let left = assignment.child_by_field_name("left").filter(|l| l.kind() == "attribute")?;
+        if left.child_by_field_name("object")?.utf8_text(source).ok()? != "self" {
+            return None;
+        }
+        let name = left.child_by_field_name("attribute")?.utf8_text(source).ok()?.to_string();
+        
+        let annotated = assignment.child_by_field_name("type")
+            .and_then(|t| t.utf8_text(source).ok())
+            .map(|t| t.to_string());
+        let inferred = || {
+            let right = assignment.child_by_field_name("right")?;
+            let text = right.utf8_text(source).ok()?;
+            match right.kind() {
+                "identifier" => param_types.iter().find(|(p, _)| p == text).map(|(_, t)| t.clone()),
+                "call" => {
+                    let callee = right.child_by_field_name("function")?.utf8_text(source).ok()?;
+                    let class_name = callee.rsplit('.').next().unwrap_or(callee);
+                    class_name.starts_with(|c: char| c.is_ascii_uppercase()).then(|| class_name.to_string())
+                }
+                _ => None,
+            }
+        };
+        let type_annotation = annotated.or_else(inferred)?;
+        
+        Some(PropertyInfo {
+            visibility: if name.starts_with('_') { Visibility::Private } else { Visibility::Public },
+            name,
+            type_annotation: Some(type_annotation),
+            is_static: false,
+            is_readonly: false,
+            tags: None,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
+        })
+    }
+    
     /// Extract a property from an assignment expression
     fn extract_assignment_property(&self, node: &Node, source: &[u8]) -> Option<PropertyInfo> {
         let mut name = String::new();
@@ -819,6 +887,9 @@ impl PythonParser {
             is_readonly: false,
             visibility: Visibility::Public,
             tags: None,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
     
@@ -898,46 +969,105 @@ impl PythonParser {
         let matches = cursor.matches(&self.import_query, *root, source);
         
         for m in matches {
-            let mut module = String::new();
-            let mut names = Vec::new();
-            let mut range = Range::new(0, 0, 0, 0);
-            let mut is_from_import = false;
-            
             for capture in m.captures {
                 let node = capture.node;
                 let capture_name = self.import_query.capture_names()[capture.index as usize];
                 
                 match capture_name {
-                    "module" => {
-                        module = node.utf8_text(source).unwrap_or("").to_string();
-                    }
-                    "name" => {
-                        names.push(node.utf8_text(source).unwrap_or("").to_string());
-                    }
-                    "import" => {
-                        range = node_range(&node);
-                    }
-                    "from_import" => {
-                        range = node_range(&node);
-                        is_from_import = true;
-                    }
+                    "import" => self.extract_import_statement(&node, source, result),
+                    "from_import" => self.extract_from_import(&node, source, result),
                     _ => {}
                 }
             }
+        }
+    }
+    
+    /// `import a.b` / `import numpy as np` - one ImportInfo per imported module
+    fn extract_import_statement(&self, node: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = node.walk();
+        for name_node in node.children_by_field_name("name", &mut cursor) {
+            let (module, alias) = match name_node.kind() {
+                "aliased_import" => (
+                    name_node.child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(source).ok())
+                        .unwrap_or(""),
+                    name_node.child_by_field_name("alias")
+                        .and_then(|n| n.utf8_text(source).ok())
+                        .map(|s| s.to_string()),
+                ),
+                _ => (name_node.utf8_text(source).unwrap_or(""), None),
+            };
             
             if !module.is_empty() {
                 result.imports.push(ImportInfo {
-                    source: module,
-                    named: if is_from_import { names.clone() } else { Vec::new() },
-                    default: if !is_from_import { names.first().cloned() } else { None },
+                    source: module.to_string(),
+                    named: Vec::new(),
+                    default: alias,
                     namespace: None,
                     is_type_only: false,
-                    range,
+                    relative_level: 0,
+                    range: node_range(node),
                 });
             }
         }
     }
     
+    /// `from pkg import a, b as c`, `from ..models import User`, `from . import views`
+    fn extract_from_import(&self, node: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut module = String::new();
+        let mut relative_level = 0;
+        
+        if let Some(module_node) = node.child_by_field_name("module_name") {
+            if module_node.kind() == "relative_import" {
+                // relative_import: import_prefix ("." / "..") followed by an optional dotted_name
+                let mut cursor = module_node.walk();
+                for child in module_node.children(&mut cursor) {
+                    match child.kind() {
+                        "import_prefix" => {
+                            relative_level = child.utf8_text(source).unwrap_or("")
+                                .chars()
+                                .filter(|c| *c == '.')
+                                .count();
+                        }
+                        "dotted_name" => {
+                            module = child.utf8_text(source).unwrap_or("").to_string();
+                        }
+                        _ => {}
+                    }
+                }
+            } else {
+                module = module_node.utf8_text(source).unwrap_or("").to_string();
+            }
+        }
+        
+        if module.is_empty() && relative_level == 0 {
+            return;
+        }
+        
+        let mut named = Vec::new();
+        let mut cursor = node.walk();
+        for name_node in node.children_by_field_name("name", &mut cursor) {
+            let name = match name_node.kind() {
+                "aliased_import" => name_node.child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source).ok()),
+                _ => name_node.utf8_text(source).ok(),
+            };
+            if let Some(name) = name {
+                named.push(name.to_string());
+            }
+        }
+        
+        result.imports.push(ImportInfo {
+            source: module,
+            named,
+            default: None,
+            namespace: None,
+            is_type_only: false,
+            relative_level,
+            range: node_range(node),
+        });
+    }
+    
     fn extract_calls(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
         let matches = cursor.matches(&self.call_query, *root, source);
@@ -947,6 +1077,7 @@ impl PythonParser {
             let mut receiver = None;
             let mut arg_count = 0;
             let mut range = Range::new(0, 0, 0, 0);
+            let mut string_arg = None;
             
             for capture in m.captures {
                 let node = capture.node;
@@ -961,6 +1092,7 @@ impl PythonParser {
                     }
                     "args" => {
                         arg_count = node.named_child_count();
+                        string_arg = first_string_arg(&node, source);
                     }
                     "call" => {
                         range = node_range(&node);
@@ -975,18 +1107,39 @@ impl PythonParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg,
                 });
             }
         }
     }
 }
 
+/// Contents of the first argument when it is a string literal; f-string
+/// replacement fields are kept as written (`user:{id}`)
+fn first_string_arg(args: &Node, source: &[u8]) -> Option<String> {
+    let first = args.named_child(0).filter(|arg| arg.kind() == "string")?;
+    let text = first.utf8_text(source).ok()?.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let quotes = if text.starts_with("\"\"\"") || text.starts_with("'''") { 3 } else { 1 };
+    text.get(quotes..text.len().checked_sub(quotes)?).map(str::to_string)
+}
+
 impl Default for PythonParser {
     fn default() -> Self {
         Self::new().expect("Failed to create Python parser")
     }
 }
 
+/// `__name` is private (name-mangled), `_name` protected; dunders are public
+fn name_visibility(name: &str) -> Visibility {
+    if name.starts_with("__") && !name.ends_with("__") {
+        Visibility::Private
+    } else if name.starts_with('_') && !name.starts_with("__") {
+        Visibility::Protected
+    } else {
+        Visibility::Public
+    }
+}
+
 fn node_range(node: &Node) -> Range {
     Range {
         start: Position {
@@ -1031,6 +1184,34 @@ mod tests {
         assert_eq!(result.imports[0].source, "typing");
     }
 
+    #[test]
+    fn test_parse_aliased_import() {
+        let mut parser = PythonParser::new().unwrap();
+        let result = parser.parse("import numpy as np\nimport os, pandas as pd");
+        
+        let sources: Vec<(&str, Option<&str>)> = result.imports.iter()
+            .map(|i| (i.source.as_str(), i.default.as_deref()))
+            .collect();
+        assert_eq!(sources, vec![("numpy", Some("np")), ("os", None), ("pandas", Some("pd"))]);
+        assert!(result.imports.iter().all(|i| i.relative_level == 0));
+    }
+    
+    #[test]
+    fn test_parse_relative_imports() {
+        let mut parser = PythonParser::new().unwrap();
+        let result = parser.parse("from ..models import User, Group as G\nfrom . import views\nfrom .utils.db import session");
+        
+        assert_eq!(result.imports.len(), 3);
+        assert_eq!(result.imports[0].source, "models");
+        assert_eq!(result.imports[0].relative_level, 2);
+        assert_eq!(result.imports[0].named, vec!["User", "Group"]);
+        assert_eq!(result.imports[1].source, "");
+        assert_eq!(result.imports[1].relative_level, 1);
+        assert_eq!(result.imports[1].named, vec!["views"]);
+        assert_eq!(result.imports[2].source, "utils.db");
+        assert_eq!(result.imports[2].relative_level, 1);
+    }
+    
     #[test]
     fn test_parse_fastapi_class() {
         let mut parser = PythonParser::new().unwrap();
@@ -1285,6 +1466,10 @@ class Repository(ABC):
         
         let repo = result.classes.iter().find(|c| c.name == "Repository").unwrap();
         assert!(repo.is_abstract, "Repository should be abstract");
+        
+        let others = parser.parse("class Clock(typing.Protocol):\n    def now(self): ...\n\nclass Store(metaclass=ABCMeta):\n    pass\n\nclass SqlStore(Store):\n    pass\n");
+        let abstract_classes: Vec<(&str, bool)> = others.classes.iter().map(|c| (c.name.as_str(), c.is_abstract)).collect();
+        assert_eq!(abstract_classes, vec![("Clock", true), ("Store", true), ("SqlStore", false)]);
     }
     
     #[test]
@@ -1326,4 +1511,143 @@ def users():
         assert!(func.decorators.iter().any(|d| d.contains("login_required")));
         assert!(func.doc_comment.is_some());
     }
+
+    #[test]
+    fn test_route_annotations_flask_and_fastapi() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"
+@app.route('/api/users', methods=['GET', 'POST'])
+def users():
+    pass
+
+@router.get("/items/{item_id}", response_model=Item)
+async def read_item(item_id: int):
+    pass
+
+@app.route("/health")
+def health():
+    pass
+"#;
+        let result = parser.parse(source);
+        
+        assert_eq!(result.route_annotations(), vec![
+            ("GET".to_string(), "/api/users".to_string()),
+            ("POST".to_string(), "/api/users".to_string()),
+            ("GET".to_string(), "/items/{item_id}".to_string()),
+            ("GET".to_string(), "/health".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_decorated_methods() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"
+class User:
+    @property
+    def full_name(self) -> str:
+        return self.first + self.last
+
+    @classmethod
+    def create(cls, name):
+        return cls(name)
+
+    @staticmethod
+    @cache
+    def normalize(name):
+        return name.lower()
+
+    def save(self):
+        pass
+"#;
+        let result = parser.parse(source);
+        assert_eq!(result.functions.len(), 4, "methods should not be duplicated");
+        
+        let method = |name: &str| result.functions.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(method("full_name").decorators, vec!["@property"]);
+        assert_eq!(method("full_name").qualified_name.as_deref(), Some("User.full_name"));
+        assert_eq!(method("full_name").return_type.as_deref(), Some("str"));
+        assert_eq!(method("create").decorators, vec!["@classmethod"]);
+        assert_eq!(method("normalize").decorators, vec!["@staticmethod", "@cache"]);
+        assert!(method("save").decorators.is_empty());
+        assert_eq!(method("save").qualified_name.as_deref(), Some("User.save"));
+    }
+
+    #[test]
+    fn test_parse_fastapi_class_based_routes() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"
+router = APIRouter()
+
+@cbv(router)
+class ItemView:
+    class Config:
+        def schema(self):
+            pass
+
+    @router.get("/items")
+    async def list_items(self, limit: int = 10):
+        return []
+
+    @router.post("/items")
+    async def create_item(self, item: Item):
+        return item
+
+def helper():
+    def inner():
+        pass
+"#;
+        let result = parser.parse(source);
+        let func = |name: &str| result.functions.iter().find(|f| f.name == name).unwrap();
+        
+        let list = func("list_items");
+        assert!(list.is_async);
+        assert_eq!(list.decorators, vec!["@router.get(\"/items\")"]);
+        assert_eq!(list.qualified_name.as_deref(), Some("ItemView.list_items"));
+        assert_eq!(list.parameters.len(), 1);
+        assert_eq!(list.parameters[0].name, "limit");
+        assert_eq!(func("create_item").decorators, vec!["@router.post(\"/items\")"]);
+        assert_eq!(func("schema").qualified_name.as_deref(), Some("ItemView.Config.schema"));
+        assert_eq!(func("helper").qualified_name, None);
+        assert_eq!(func("inner").qualified_name, None);
+    }
+
+    #[test]
+    fn test_parse_assigned_lambdas() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = "normalize = lambda value, strip=True: value.lower()\n\nclass Rules:\n    check = lambda self, x: x > 0\n";
+        let result = parser.parse(source);
+        
+        let normalize = result.functions.iter().find(|f| f.name == "normalize").unwrap();
+        let params: Vec<&str> = normalize.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(params, vec!["value", "strip"]);
+        assert_eq!(normalize.qualified_name, None);
+        
+        let check = result.functions.iter().find(|f| f.name == "check").unwrap();
+        assert_eq!(check.qualified_name.as_deref(), Some("Rules.check"));
+    }
+
+    #[test]
+    fn test_parse_typed_instance_attributes() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"
+class UserService:
+    def __init__(self, repo: UserRepository, name):
+        self.repo = repo
+        self._cache = FileCache()
+        self.db: Database = connect()
+        self.name = name
+        self.count = 0
+"#;
+        let result = parser.parse(source);
+        
+        let class = &result.classes[0];
+        let typed: Vec<(&str, Option<&str>)> = class.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(typed, vec![
+            ("_cache", Some("FileCache")),
+            ("db", Some("Database")),
+            ("repo", Some("UserRepository")),
+        ]);
+    }
 }
@@ -0,0 +1,536 @@
+//! Ruby parser using native tree-sitter
+//!
+//! Extracts methods, classes/modules, requires, and call sites from Ruby code.
+//! Supports Rails (ActiveRecord models, controllers) and Rake patterns.
+//!
+//! Enterprise features:
+//! - Instance and `self.` class methods, qualified as `User#save` / `User.find`
+//! - Visibility sections (`private`, `protected`)
+//! - Superclass and `include`/`extend` mixin extraction
+//! - `attr_accessor`/`attr_reader`/`attr_writer` properties
+//! - `require`/`require_relative` imports
+//! - Doc comment extraction (leading `#` comments)
+
+use std::time::Instant;
+use tree_sitter::{Node, Parser, Query, QueryCursor};
+
+use super::types::*;
+
+/// Ruby parser
+pub struct RubyParser {
+    parser: Parser,
+    call_query: Query,
+}
+
+impl RubyParser {
+    pub fn new() -> Result<Self, String> {
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into())
+            .map_err(|e| format!("Failed to set language: {}", e))?;
+
+        let call_query = Query::new(
+            &language.into(),
+            r#"
+            (call
+                receiver: (_)? @receiver
+                method: (_) @callee
+                arguments: (argument_list)? @args
+            ) @call
+            "#,
+        ).map_err(|e| format!("Failed to create call query: {}", e))?;
+
+        Ok(Self {
+            parser,
+            call_query,
+        })
+    }
+
+    pub fn parse(&mut self, source: &str) -> ParseResult {
+        let start = Instant::now();
+
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => {
+                let mut result = ParseResult::new(Language::Ruby);
+                result.errors.push(ParseError {
+                    message: "Failed to parse source".to_string(),
+                    range: Range::new(0, 0, 0, 0),
+                    snippet: None,
+                });
+                return result;
+            }
+        };
+
+        let root = tree.root_node();
+        let source_bytes = source.as_bytes();
+
+        let mut result = ParseResult::with_tree(Language::Ruby, tree.clone());
+
+        result.record_syntax_errors(&root, source_bytes);
+
+        // Classes, modules and methods are nested arbitrarily, so walk the tree
+        // keeping track of the enclosing namespace
+        self.extract_definitions(&root, source_bytes, &[], &mut result);
+        self.extract_calls(&root, source_bytes, &mut result);
+
+        result.parse_time_us = start.elapsed().as_micros() as u64;
+        result
+    }
+
+    /// Extract classes, modules and methods declared in `body`
+    ///
+    /// `namespace` holds the enclosing class/module names, outermost first.
+    /// Returns the methods declared directly in the body so the caller can
+    /// attach them to the enclosing class.
+    fn extract_definitions(
+        &self,
+        body: &Node,
+        source: &[u8],
+        namespace: &[String],
+        result: &mut ParseResult,
+    ) -> Vec<FunctionInfo> {
+        let mut methods = Vec::new();
+        let mut is_public = true;
+        let owner = namespace.last().map(|s| s.as_str());
+
+        let mut cursor = body.walk();
+        for child in body.named_children(&mut cursor) {
+            match child.kind() {
+                "method" | "singleton_method" => {
+                    if let Some(method) = self.extract_method(&child, source, owner, is_public) {
+                        result.functions.push(method.clone());
+                        methods.push(method);
+                    }
+                }
+                "class" | "module" => {
+                    self.extract_class(&child, source, namespace, result);
+                }
+                // `private` / `protected` / `public` on their own line switch the visibility
+                // of the methods declared after them
+                "identifier" => {
+                    match child.utf8_text(source).unwrap_or("") {
+                        "private" | "protected" => is_public = false,
+                        "public" => is_public = true,
+                        _ => {}
+                    }
+                }
+                // `private def helper` declares a single non-public method
+                "call" => {
+                    let method_name = child.child_by_field_name("method")
+                        .and_then(|n| n.utf8_text(source).ok())
+                        .unwrap_or("");
+                    if matches!(method_name, "private" | "protected" | "public") && child.child_by_field_name("receiver").is_none() {
+                        let defs = child.child_by_field_name("arguments")
+                            .map(|args| {
+                                let mut args_cursor = args.walk();
+                                args.named_children(&mut args_cursor)
+                                    .filter(|a| matches!(a.kind(), "method" | "singleton_method"))
+                                    .collect::<Vec<_>>()
+                            })
+                            .unwrap_or_default();
+                        for def in defs {
+                            if let Some(method) = self.extract_method(&def, source, owner, method_name == "public") {
+                                result.functions.push(method.clone());
+                                methods.push(method);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        methods
+    }
+
+    /// Extract a class or module declaration (and everything declared inside it)
+    fn extract_class(&self, node: &Node, source: &[u8], namespace: &[String], result: &mut ParseResult) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+        // `class Admin::User` declares User inside Admin
+        let full_name = name_node.utf8_text(source).unwrap_or("").to_string();
+        let mut path: Vec<String> = namespace.to_vec();
+        path.extend(full_name.split("::").filter(|s| !s.is_empty()).map(|s| s.to_string()));
+        let Some(name) = path.pop() else {
+            return;
+        };
+
+        let extends = node.child_by_field_name("superclass")
+            .and_then(|s| s.named_child(0))
+            .and_then(|s| s.utf8_text(source).ok())
+            .map(|s| s.to_string());
+
+        let body = node.child_by_field_name("body");
+        let (implements, properties) = body
+            .map(|b| self.extract_class_body_macros(&b, source))
+            .unwrap_or_default();
+
+        let mut inner_namespace = path.clone();
+        inner_namespace.push(name.clone());
+        let methods = body
+            .map(|b| self.extract_definitions(&b, source, &inner_namespace, result))
+            .unwrap_or_default();
+
+        result.classes.push(ClassInfo {
+            name,
+            extends,
+            implements,
+            is_exported: true,
+            // Modules cannot be instantiated
+            is_abstract: node.kind() == "module",
+            methods,
+            properties,
+            range: node_range(node),
+            decorators: Vec::new(),
+            namespace: if path.is_empty() { None } else { Some(path.join("::")) },
+            is_partial: false,
+            type_parameters: Vec::new(),
+        });
+    }
+
+    /// Extract a method definition
+    fn extract_method(&self, node: &Node, source: &[u8], owner: Option<&str>, is_public: bool) -> Option<FunctionInfo> {
+        let name = node.child_by_field_name("name")?.utf8_text(source).ok()?.to_string();
+        let is_singleton = node.kind() == "singleton_method";
+
+        let parameters = node.child_by_field_name("parameters")
+            .map(|p| self.extract_parameters(&p, source))
+            .unwrap_or_default();
+
+        // `def self.find` is a class method (`User.find`), `def save` an instance method (`User#save`)
+        let qualified_name = owner.map(|o| {
+            if is_singleton {
+                format!("{}.{}", o, name)
+            } else {
+                format!("{}#{}", o, name)
+            }
+        });
+
+        Some(FunctionInfo {
+            name,
+            qualified_name,
+            parameters,
+            return_type: None,
+            is_exported: is_public,
+            is_async: false,
+            is_generator: false,
+            visibility: if is_public { Visibility::Public } else { Visibility::Private },
+            is_static: is_singleton,
+            type_parameters: Vec::new(),
+            range: node_range(node),
+            decorators: Vec::new(),
+            doc_comment: self.extract_doc_comment(node, source),
+        })
+    }
+
+    /// Extract parameters from a method_parameters node
+    fn extract_parameters(&self, params_node: &Node, source: &[u8]) -> Vec<ParameterInfo> {
+        let mut parameters = Vec::new();
+        let mut cursor = params_node.walk();
+
+        for param in params_node.named_children(&mut cursor) {
+            let text = |n: Option<Node>| n.and_then(|n| n.utf8_text(source).ok()).map(|s| s.to_string());
+            let (name, default_value, is_rest) = match param.kind() {
+                "identifier" => (text(Some(param)), None, false),
+                "optional_parameter" | "keyword_parameter" => (
+                    text(param.child_by_field_name("name")),
+                    text(param.child_by_field_name("value")),
+                    false,
+                ),
+                "splat_parameter" | "hash_splat_parameter" | "block_parameter" => {
+                    (text(param.child_by_field_name("name")), None, true)
+                }
+                _ => (None, None, false),
+            };
+
+            if let Some(name) = name {
+                parameters.push(ParameterInfo {
+                    name,
+                    type_annotation: None,
+                    default_value,
+                    is_rest,
+                });
+            }
+        }
+
+        parameters
+    }
+
+    /// Extract `include`/`extend` mixins and `attr_*` properties from a class body
+    fn extract_class_body_macros(&self, body: &Node, source: &[u8]) -> (Vec<String>, Vec<PropertyInfo>) {
+        let mut mixins = Vec::new();
+        let mut properties = Vec::new();
+
+        let mut cursor = body.walk();
+        for child in body.named_children(&mut cursor) {
+            if child.kind() != "call" || child.child_by_field_name("receiver").is_some() {
+                continue;
+            }
+            let method = child.child_by_field_name("method")
+                .and_then(|n| n.utf8_text(source).ok())
+                .unwrap_or("");
+            let Some(args) = child.child_by_field_name("arguments") else {
+                continue;
+            };
+            let mut args_cursor = args.walk();
+            let arg_texts: Vec<&str> = args.named_children(&mut args_cursor)
+                .filter_map(|a| a.utf8_text(source).ok())
+                .collect();
+
+            match method {
+                "include" | "extend" | "prepend" => {
+                    mixins.extend(arg_texts.iter().map(|a| a.to_string()));
+                }
+                "attr_accessor" | "attr_reader" | "attr_writer" => {
+                    for arg in arg_texts {
+                        let name = arg.trim_start_matches(':').trim_matches(|c| c == '"' || c == '\'');
+                        if name.is_empty() {
+                            continue;
+                        }
+                        properties.push(PropertyInfo {
+                            name: name.to_string(),
+                            type_annotation: None,
+                            is_static: false,
+                            is_readonly: method == "attr_reader",
+                            visibility: Visibility::Public,
+                            tags: None,
+                            value: None,
+                            is_computed: false,
+                            decorators: Vec::new(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (mixins, properties)
+    }
+
+    /// Extract doc comment (consecutive `#` comments right above the node)
+    fn extract_doc_comment(&self, node: &Node, source: &[u8]) -> Option<String> {
+        let mut doc_lines = Vec::new();
+        let mut sibling = node.prev_sibling();
+
+        while let Some(sib) = sibling {
+            if sib.kind() != "comment" {
+                break;
+            }
+            let comment = sib.utf8_text(source).unwrap_or("");
+            doc_lines.push(comment.trim_start_matches('#').trim().to_string());
+            sibling = sib.prev_sibling();
+        }
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            doc_lines.reverse();
+            Some(doc_lines.join("\n"))
+        }
+    }
+
+    fn extract_calls(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(&self.call_query, *root, source);
+
+        for m in matches {
+            let mut callee = String::new();
+            let mut receiver = None;
+            let mut args_node: Option<Node> = None;
+            let mut range = Range::new(0, 0, 0, 0);
+
+            for capture in m.captures {
+                let node = capture.node;
+                let capture_name = self.call_query.capture_names()[capture.index as usize];
+
+                match capture_name {
+                    "callee" => {
+                        callee = node.utf8_text(source).unwrap_or("").to_string();
+                    }
+                    "receiver" => {
+                        receiver = Some(node.utf8_text(source).unwrap_or("").to_string());
+                    }
+                    "args" => {
+                        args_node = Some(node);
+                    }
+                    "call" => {
+                        range = node_range(&node);
+                    }
+                    _ => {}
+                }
+            }
+
+            if callee.is_empty() {
+                continue;
+            }
+
+            let first_arg = args_node.and_then(|a| a.named_child(0));
+            let string_arg = first_arg.and_then(|a| plain_string(&a, source));
+
+            // `require 'json'` is an import, not a call
+            if receiver.is_none() && matches!(callee.as_str(), "require" | "require_relative") {
+                if let Some(path) = string_arg {
+                    let source_path = if callee == "require_relative" && !path.starts_with('.') {
+                        format!("./{}", path)
+                    } else {
+                        path
+                    };
+                    result.imports.push(ImportInfo {
+                        source: source_path,
+                        named: Vec::new(),
+                        default: None,
+                        namespace: None,
+                        is_type_only: false,
+                        relative_level: 0,
+                        range,
+                    });
+                }
+                continue;
+            }
+
+            result.calls.push(CallSite {
+                callee,
+                receiver,
+                arg_count: args_node.map(|a| a.named_child_count()).unwrap_or(0),
+                range,
+                string_arg,
+            });
+        }
+    }
+}
+
+impl Default for RubyParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create Ruby parser")
+    }
+}
+
+/// Content of a string literal without interpolation (`'users'`, `"users"`)
+fn plain_string(node: &Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "string" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let mut content = String::new();
+    for part in node.named_children(&mut cursor) {
+        match part.kind() {
+            "string_content" => content.push_str(part.utf8_text(source).ok()?),
+            "escape_sequence" => content.push_str(part.utf8_text(source).ok()?),
+            _ => return None,
+        }
+    }
+    Some(content)
+}
+
+fn node_range(node: &Node) -> Range {
+    Range {
+        start: Position {
+            line: node.start_position().row as u32,
+            column: node.start_position().column as u32,
+        },
+        end: Position {
+            line: node.end_position().row as u32,
+            column: node.end_position().column as u32,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_method() {
+        let mut parser = RubyParser::new().unwrap();
+        let result = parser.parse("def hello(name, greeting = 'hi', *rest, key:, &block)\n  puts name\nend");
+
+        assert_eq!(result.functions.len(), 1);
+        let hello = &result.functions[0];
+        assert_eq!(hello.name, "hello");
+        assert!(hello.qualified_name.is_none());
+        let names: Vec<&str> = hello.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["name", "greeting", "rest", "key", "block"]);
+        assert_eq!(hello.parameters[1].default_value.as_deref(), Some("'hi'"));
+        assert!(hello.parameters[2].is_rest);
+    }
+
+    #[test]
+    fn test_parse_class_with_methods() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+module Admin
+  # A registered user
+  class User < ApplicationRecord
+    include Auditable
+    attr_reader :token
+    attr_accessor :nickname
+
+    def self.find_active
+      where(active: true)
+    end
+
+    def save
+      super
+    end
+
+    private
+
+    def normalize
+    end
+  end
+end
+"#;
+        let result = parser.parse(source);
+
+        let user = result.classes.iter().find(|c| c.name == "User").unwrap();
+        assert_eq!(user.extends.as_deref(), Some("ApplicationRecord"));
+        assert_eq!(user.implements, vec!["Auditable"]);
+        assert_eq!(user.namespace.as_deref(), Some("Admin"));
+        assert_eq!(user.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["find_active", "save", "normalize"]);
+        assert_eq!(user.properties.len(), 2);
+        assert!(user.properties[0].is_readonly);
+
+        let admin = result.classes.iter().find(|c| c.name == "Admin").unwrap();
+        assert!(admin.is_abstract);
+
+        let qualified = |name: &str| result.functions.iter().find(|f| f.name == name).unwrap().qualified_name.clone();
+        assert_eq!(qualified("find_active").as_deref(), Some("User.find_active"));
+        assert_eq!(qualified("save").as_deref(), Some("User#save"));
+
+        let normalize = result.functions.iter().find(|f| f.name == "normalize").unwrap();
+        assert!(!normalize.is_exported);
+        assert!(result.functions.iter().find(|f| f.name == "save").unwrap().is_exported);
+    }
+
+    #[test]
+    fn test_parse_requires() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = "require 'json'\nrequire_relative 'lib/helpers'\nrequire \"#{dir}/dynamic\"\n";
+        let result = parser.parse(source);
+
+        let sources: Vec<&str> = result.imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["json", "./lib/helpers"]);
+        assert!(!result.calls.iter().any(|c| c.callee == "require_relative"));
+    }
+
+    #[test]
+    fn test_parse_calls_with_receivers() {
+        let mut parser = RubyParser::new().unwrap();
+        let source = r#"
+users = User.where(active: true).order(:name)
+user.update!(name: "x")
+Rails.logger.info "saved"
+ActiveRecord::Base.connection.execute("DELETE FROM sessions")
+"#;
+        let result = parser.parse(source);
+
+        let call = |callee: &str| result.calls.iter().find(|c| c.callee == callee).unwrap();
+        assert_eq!(call("where").receiver.as_deref(), Some("User"));
+        assert_eq!(call("where").arg_count, 1);
+        assert_eq!(call("update!").receiver.as_deref(), Some("user"));
+        assert_eq!(call("info").receiver.as_deref(), Some("Rails.logger"));
+        assert_eq!(call("execute").string_arg.as_deref(), Some("DELETE FROM sessions"));
+    }
+}
@@ -17,6 +17,7 @@ use std::time::Instant;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 use super::types::*;
+use super::generics::declared_type_parameters;
 
 /// Rust parser
 pub struct RustParser {
@@ -46,6 +47,14 @@ impl RustParser {
                 return_type: (_)? @return_type
                 body: (block)? @body
             ) @function
+            
+            (let_declaration
+                pattern: (identifier) @name
+                value: (closure_expression
+                    parameters: (closure_parameters) @params
+                    return_type: (_)? @return_type
+                ) @closure
+            )
             "#,
         ).map_err(|e| format!("Failed to create function query: {}", e))?;
         
@@ -103,6 +112,11 @@ impl RustParser {
                 ]
                 arguments: (arguments) @args
             ) @call
+            
+            (macro_invocation
+                macro: [(identifier) (scoped_identifier)] @macro_path
+                (token_tree) @macro_args
+            ) @macro
             "#,
         ).map_err(|e| format!("Failed to create call query: {}", e))?;
         
@@ -139,6 +153,7 @@ impl RustParser {
                 result.errors.push(ParseError {
                     message: "Failed to parse source".to_string(),
                     range: Range::new(0, 0, 0, 0),
+                    snippet: None,
                 });
                 return result;
             }
@@ -149,8 +164,17 @@ impl RustParser {
         
         let mut result = ParseResult::with_tree(Language::Rust, tree.clone());
         
-        self.extract_functions(&root, source_bytes, &mut result);
+        result.record_syntax_errors(&root, source_bytes);
+        
+        let impl_methods = self.extract_functions(&root, source_bytes, &mut result);
         self.extract_structs(&root, source_bytes, &mut result);
+        
+        // Attach impl/trait methods to the type declared in this file
+        for (owner, method) in impl_methods {
+            if let Some(class) = result.classes.iter_mut().find(|c| c.name == owner) {
+                class.methods.push(method);
+            }
+        }
         self.extract_uses(&root, source_bytes, &mut result);
         self.extract_calls(&root, source_bytes, &mut result);
         
@@ -158,9 +182,11 @@ impl RustParser {
         result
     }
 
-    fn extract_functions(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    /// Extract functions, returning the methods found in `impl`/`trait` blocks with their owning type
+    fn extract_functions(&self, root: &Node, source: &[u8], result: &mut ParseResult) -> Vec<(String, FunctionInfo)> {
         let mut cursor = QueryCursor::new();
         let matches = cursor.matches(&self.function_query, *root, source);
+        let mut impl_methods = Vec::new();
         
         for m in matches {
             let mut name = String::new();
@@ -201,6 +227,11 @@ impl RustParser {
                         let text = node.utf8_text(source).unwrap_or("");
                         is_async = text.contains("async fn");
                     }
+                    // Closures bound with `let` are callable by their binding name
+                    "closure" => {
+                        range = node_range(&node);
+                        is_async = node.utf8_text(source).unwrap_or("").starts_with("async");
+                    }
                     _ => {}
                 }
             }
@@ -211,20 +242,69 @@ impl RustParser {
                     .map(|n| self.extract_attributes_and_docs(&n, source))
                     .unwrap_or((Vec::new(), None));
                 
-                result.functions.push(FunctionInfo {
+                let owner = function_node.and_then(|n| self.impl_owner(&n, source));
+                let qualified_name = owner.as_ref().map(|(ty, trait_name)| match trait_name {
+                    Some(tr) => format!("<{} as {}>::{}", ty, tr, name),
+                    None => format!("{}::{}", ty, name),
+                });
+                
+                // Associated functions (`User::new`) take no `self`
+                let is_static = owner.is_some() && !parameters.iter().any(|p| p.name == "self");
+                
+                let func = FunctionInfo {
                     name,
-                    qualified_name: None,
+                    qualified_name,
                     parameters,
                     return_type,
                     is_exported: is_pub,
                     is_async,
                     is_generator: false,
+                    visibility: if is_pub { Visibility::Public } else { Visibility::Private },
+                    is_static,
+                    type_parameters: function_node
+                        .map(|n| declared_type_parameters(&n, source))
+                        .unwrap_or_default(),
                     range,
                     decorators,
                     doc_comment,
-                });
+                };
+                if let Some((ty, _)) = owner {
+                    impl_methods.push((ty, func.clone()));
+                }
+                result.functions.push(func);
             }
         }
+        
+        impl_methods
+    }
+    
+    /// Type (and implemented trait) owning a function declared in an `impl` or `trait` block
+    ///
+    /// Generic arguments are dropped from the type (`impl<T> Repo<T>` gives "Repo").
+    fn impl_owner(&self, func_node: &Node, source: &[u8]) -> Option<(String, Option<String>)> {
+        let block = func_node.parent().filter(|p| p.kind() == "declaration_list")?;
+        let owner = block.parent()?;
+        
+        match owner.kind() {
+            "impl_item" => {
+                let type_node = owner.child_by_field_name("type")?;
+                let type_node = if type_node.kind() == "generic_type" {
+                    type_node.child_by_field_name("type").unwrap_or(type_node)
+                } else {
+                    type_node
+                };
+                let ty = type_node.utf8_text(source).ok()?.to_string();
+                let trait_name = owner.child_by_field_name("trait")
+                    .and_then(|t| t.utf8_text(source).ok())
+                    .map(|t| t.to_string());
+                Some((ty, trait_name))
+            }
+            "trait_item" => {
+                let name = owner.child_by_field_name("name")?;
+                Some((name.utf8_text(source).ok()?.to_string(), None))
+            }
+            _ => None,
+        }
     }
 
     fn extract_structs(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -239,6 +319,8 @@ impl RustParser {
             let mut properties = Vec::new();
             let mut is_trait = false;
             let mut trait_name: Option<String> = None;
+            let mut enum_node: Option<Node> = None;
+            let mut variants = Vec::new();
             
             for capture in m.captures {
                 let node = capture.node;
@@ -258,10 +340,17 @@ impl RustParser {
                     "trait_name" => {
                         trait_name = Some(node.utf8_text(source).unwrap_or("").to_string());
                     }
-                    "struct" | "enum" => {
+                    "struct" => {
                         range = node_range(&node);
                         struct_node = Some(node);
                     }
+                    "enum" => {
+                        range = node_range(&node);
+                        enum_node = Some(node);
+                    }
+                    "variants" => {
+                        variants = self.extract_enum_variants(&node, source);
+                    }
                     "trait" => {
                         range = node_range(&node);
                         struct_node = Some(node);
@@ -275,6 +364,12 @@ impl RustParser {
                 }
             }
             
+            if let (false, Some(node)) = (name.is_empty(), enum_node) {
+                let (decorators, _doc_comment) = self.extract_attributes_and_docs(&node, source);
+                result.enums.push(EnumInfo { name, variants, is_exported: is_pub, range, decorators });
+                continue;
+            }
+            
             if !name.is_empty() && struct_node.is_some() {
                 // Extract attributes and doc comments
                 let (decorators, _doc_comment) = struct_node
@@ -291,11 +386,30 @@ impl RustParser {
                     properties,
                     range,
                     decorators,
+                    namespace: None,
+                    is_partial: false,
+                    type_parameters: struct_node
+                        .map(|n| declared_type_parameters(&n, source))
+                        .unwrap_or_default(),
                 });
             }
         }
     }
     
+    fn extract_enum_variants(&self, list: &Node, source: &[u8]) -> Vec<EnumVariant> {
+        let mut cursor = list.walk();
+        list.named_children(&mut cursor)
+            .filter(|v| v.kind() == "enum_variant")
+            .filter_map(|v| Some(EnumVariant {
+                name: v.child_by_field_name("name")?.utf8_text(source).ok()?.to_string(),
+                value: v.child_by_field_name("value")
+                    .and_then(|n| n.utf8_text(source).ok())
+                    .map(|t| t.to_string()),
+                range: node_range(&v),
+            }))
+            .collect()
+    }
+    
     fn extract_uses(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
         let matches = cursor.matches(&self.use_query, *root, source);
@@ -326,6 +440,7 @@ impl RustParser {
                     default: None,
                     namespace: None,
                     is_type_only: false,
+                    relative_level: 0,
                     range,
                 });
             }
@@ -341,6 +456,7 @@ impl RustParser {
             let mut receiver = None;
             let mut arg_count = 0;
             let mut range = Range::new(0, 0, 0, 0);
+            let mut string_arg = None;
             
             for capture in m.captures {
                 let node = capture.node;
@@ -355,8 +471,17 @@ impl RustParser {
                     }
                     "args" => {
                         arg_count = node.named_child_count();
+                        string_arg = first_string_literal(&node, source);
                     }
-                    "call" => {
+                    "macro_path" => {
+                        // Macros keep their full path as callee: "sqlx::query!", "println!"
+                        callee = format!("{}!", node.utf8_text(source).unwrap_or(""));
+                    }
+                    "macro_args" => {
+                        arg_count = macro_arg_count(&node);
+                        string_arg = first_string_literal(&node, source);
+                    }
+                    "call" | "macro" => {
                         range = node_range(&node);
                     }
                     _ => {}
@@ -369,6 +494,7 @@ impl RustParser {
                     receiver,
                     arg_count,
                     range,
+                    string_arg,
                 });
             }
         }
@@ -390,6 +516,15 @@ impl RustParser {
                             parameters.push(param);
                         }
                     }
+                    // Untyped closure parameter (`|x| ...`)
+                    "identifier" => {
+                        parameters.push(ParameterInfo {
+                            name: child.utf8_text(source).unwrap_or("").to_string(),
+                            type_annotation: None,
+                            default_value: None,
+                            is_rest: false,
+                        });
+                    }
                     "self_parameter" => {
                         // Handle self, &self, &mut self
                         let self_text = child.utf8_text(source).unwrap_or("self");
@@ -593,6 +728,9 @@ impl RustParser {
             is_readonly: false, // Rust fields are mutable by default unless the struct is immutable
             visibility,
             tags: if serde_tags.is_empty() { None } else { Some(serde_tags) },
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
         })
     }
     
@@ -661,6 +799,31 @@ impl Default for RustParser {
     }
 }
 
+/// Value of the first string literal among a node's direct children (`"..."`, `r#"..."#`)
+fn first_string_literal(node: &Node, source: &[u8]) -> Option<String> {
+    let mut cursor = node.walk();
+    let literal = node.children(&mut cursor)
+        .find(|c| c.kind() == "string_literal" || c.kind() == "raw_string_literal")?;
+    let text = literal.utf8_text(source).ok()?;
+    let value = text
+        .trim_start_matches(['b', 'r'])
+        .trim_start_matches('#')
+        .trim_end_matches('#');
+    Some(value.strip_prefix('"')?.strip_suffix('"')?.to_string())
+}
+
+/// Number of comma-separated arguments in a macro token tree
+fn macro_arg_count(token_tree: &Node) -> usize {
+    // Children include the opening and closing delimiters
+    let inner = token_tree.child_count().saturating_sub(2);
+    if inner == 0 {
+        return 0;
+    }
+    let mut cursor = token_tree.walk();
+    let commas = token_tree.children(&mut cursor).filter(|c| c.kind() == ",").count();
+    commas + 1
+}
+
 fn node_range(node: &Node) -> Range {
     Range {
         start: Position {
@@ -754,6 +917,42 @@ pub struct User {
         assert!(result.functions.iter().any(|f| f.name == "get_name"));
     }
 
+    #[test]
+    fn test_impl_methods_qualified_and_attached() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+            pub struct UserRepository { pool: Pool }
+            pub struct User { name: String }
+            
+            impl UserRepository {
+                pub fn save(&self, user: &User) {}
+            }
+            
+            impl std::fmt::Display for User {
+                fn fmt(&self, f: &mut Formatter) -> fmt::Result { Ok(()) }
+            }
+            
+            impl<T> Cache<T> {
+                fn get(&self) {}
+            }
+            
+            fn save() {}
+        "#;
+        let result = parser.parse(source);
+        
+        let qualified = |name: &str| -> Vec<Option<String>> {
+            result.functions.iter().filter(|f| f.name == name).map(|f| f.qualified_name.clone()).collect()
+        };
+        assert_eq!(qualified("save"), vec![Some("UserRepository::save".to_string()), None]);
+        assert_eq!(qualified("fmt"), vec![Some("<User as std::fmt::Display>::fmt".to_string())]);
+        assert_eq!(qualified("get"), vec![Some("Cache::get".to_string())]);
+        
+        let repo = result.classes.iter().find(|c| c.name == "UserRepository").unwrap();
+        assert_eq!(repo.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["save"]);
+        let user = result.classes.iter().find(|c| c.name == "User").unwrap();
+        assert_eq!(user.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["fmt"]);
+    }
+
     #[test]
     fn test_parse_trait() {
         let mut parser = RustParser::new().unwrap();
@@ -795,6 +994,32 @@ pub struct User {
         assert!(result.calls.iter().any(|c| c.callee == "query"));
     }
 
+    #[test]
+    fn test_parse_macro_invocations() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r##"
+            async fn load(pool: &PgPool) {
+                println!("loading");
+                let users = sqlx::query_as!(User, r#"
+                    SELECT id, name
+                    FROM users
+                "#).fetch_all(pool).await;
+                tokio::spawn(async move { work().await });
+            }
+        "##;
+        let result = parser.parse(source);
+        
+        let println = result.calls.iter().find(|c| c.callee == "println!").unwrap();
+        assert_eq!(println.string_arg.as_deref(), Some("loading"));
+        
+        let query = result.calls.iter().find(|c| c.callee == "sqlx::query_as!").unwrap();
+        assert_eq!(query.arg_count, 2);
+        assert!(query.string_arg.as_deref().unwrap().contains("FROM users"));
+        assert_eq!(query.range.start.line, 3);
+        
+        assert!(result.calls.iter().any(|c| c.callee == "spawn" && c.receiver.as_deref() == Some("tokio")));
+    }
+
     #[test]
     fn test_parse_enum() {
         let mut parser = RustParser::new().unwrap();
@@ -808,9 +1033,13 @@ pub struct User {
         "#;
         let result = parser.parse(source);
         
-        // Enums are parsed as classes
-        assert!(!result.classes.is_empty());
-        assert!(result.classes.iter().any(|c| c.name == "Status"));
+        // Enums are first-class symbols, not classes
+        assert!(result.classes.is_empty());
+        let status = result.enums.iter().find(|e| e.name == "Status").unwrap();
+        assert!(status.is_exported);
+        assert!(status.decorators.iter().any(|d| d.contains("derive")));
+        let names: Vec<&str> = status.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Active", "Inactive", "Pending"]);
     }
 
     #[test]
@@ -1101,4 +1330,18 @@ pub fn create(user: Json<User>) -> Status {
         assert!(create.decorators.iter().any(|d| d.contains("post")),
             "Expected #[post] attribute, got: {:?}", create.decorators);
     }
+
+    #[test]
+    fn test_parse_bound_closures() {
+        let mut parser = RustParser::new().unwrap();
+        let source = "fn run() {\n    let add = |a: i32, b| -> i32 { a + b };\n    let fetch = async move || load().await;\n    add(1, 2);\n}\n";
+        let result = parser.parse(source);
+        
+        let add = result.functions.iter().find(|f| f.name == "add").unwrap();
+        let params: Vec<&str> = add.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(params, vec!["a", "b"]);
+        assert_eq!(add.return_type.as_deref(), Some("i32"));
+        assert!(!add.is_exported);
+        assert!(result.functions.iter().find(|f| f.name == "fetch").unwrap().is_async);
+    }
 }
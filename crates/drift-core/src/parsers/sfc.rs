@@ -0,0 +1,477 @@
+//! Single-file components - Vue (`.vue`) and Svelte (`.svelte`)
+//!
+//! The `<script>` / `<script setup>` blocks are parsed with the TypeScript/JavaScript
+//! parser. Everything outside them is blanked to spaces (newlines kept) so byte
+//! offsets, lines and columns in the AST match the original file exactly.
+//!
+//! Component props and emits become properties of a synthetic `ClassInfo`:
+//! - Vue: `defineProps` / `withDefaults` / `defineEmits` and Options API `props` / `emits`
+//! - Svelte: `export let` (Svelte 4) and `$props()` (Svelte 5)
+
+use tree_sitter::Node;
+
+use super::types::*;
+
+/// Whether a path is a Vue or Svelte single-file component
+pub fn is_component(path: &str) -> bool {
+    let ext = path.rsplit('.').next().unwrap_or("").to_lowercase();
+    path.contains('.') && matches!(ext.as_str(), "vue" | "svelte")
+}
+
+/// Script blocks of a component, with the rest of the file blanked out
+pub struct ComponentScript {
+    /// Same length as the original source; only `<script>` contents are kept
+    pub source: String,
+    /// Any block declared `lang="ts"`
+    pub is_typescript: bool,
+}
+
+/// Extract every `<script>` block, keeping it at its original offsets
+pub fn extract_script(source: &str) -> ComponentScript {
+    let lower = source.to_ascii_lowercase();
+    let mut keep = vec![false; source.len()];
+    let mut is_typescript = false;
+
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find("<script").map(|i| pos + i) {
+        let Some(tag_end) = lower[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        let attributes = &lower[start + "<script".len()..tag_end];
+        // `<scripts>` or similar is not a script tag
+        if !attributes.is_empty() && !attributes.starts_with(|c: char| c.is_whitespace() || c == '/') {
+            pos = tag_end;
+            continue;
+        }
+        if attributes.contains("lang=\"ts\"") || attributes.contains("lang='ts'")
+            || attributes.contains("lang=\"typescript\"") || attributes.contains("lang='typescript'") {
+            is_typescript = true;
+        }
+
+        let body_start = tag_end + 1;
+        let body_end = lower[body_start..].find("</script").map(|i| body_start + i).unwrap_or(source.len());
+        keep[body_start..body_end].iter_mut().for_each(|k| *k = true);
+        pos = body_end;
+    }
+
+    // Replace bytes (not chars) so multi-byte template text keeps its width
+    let bytes: Vec<u8> = source.bytes()
+        .zip(keep)
+        .map(|(b, keep)| if keep || b == b'\n' || b == b'\r' { b } else { b' ' })
+        .collect();
+
+    ComponentScript {
+        source: String::from_utf8(bytes).unwrap_or_default(),
+        is_typescript,
+    }
+}
+
+/// Build the synthetic component class for a parsed script
+///
+/// `script` must be the blanked source the result was parsed from.
+pub fn component_class(result: &ParseResult, script: &str, path: &str) -> ClassInfo {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let name = file_name.split('.').next().unwrap_or(file_name).to_string();
+
+    let mut properties = Vec::new();
+    if let Some(tree) = &result.tree {
+        let root = tree.root_node();
+        collect_component_members(&root, &root, script.as_bytes(), &mut properties);
+    }
+
+    let last_line = script.lines().count().saturating_sub(1) as u32;
+    ClassInfo {
+        name,
+        extends: None,
+        implements: Vec::new(),
+        is_exported: true,
+        is_abstract: false,
+        methods: Vec::new(),
+        properties,
+        range: Range::new(0, 0, last_line, 0),
+        decorators: Vec::new(),
+        namespace: None,
+        is_partial: false,
+        type_parameters: Vec::new(),
+    }
+}
+
+fn collect_component_members(node: &Node, root: &Node, source: &[u8], properties: &mut Vec<PropertyInfo>) {
+    match node.kind() {
+        "call_expression" => {
+            let callee = node.child_by_field_name("function")
+                .and_then(|f| f.utf8_text(source).ok())
+                .unwrap_or("");
+            match callee {
+                "defineProps" => properties.extend(declared_members(node, root, source, "@prop")),
+                "defineEmits" => properties.extend(declared_members(node, root, source, "@emit")),
+                // withDefaults(defineProps<Props>(), { count: 0 })
+                "withDefaults" => {
+                    let args = call_arguments(node);
+                    if let Some(inner) = args.first().filter(|a| a.kind() == "call_expression") {
+                        let mut props = declared_members(inner, root, source, "@prop");
+                        if let Some(defaults) = args.get(1).filter(|a| a.kind() == "object") {
+                            for (key, value) in object_pairs(defaults, source) {
+                                if let Some(prop) = props.iter_mut().find(|p| p.name == key) {
+                                    prop.value = value.utf8_text(source).ok().map(|v| v.to_string());
+                                }
+                            }
+                        }
+                        properties.extend(props);
+                    }
+                    return;
+                }
+                // Options API: defineComponent({ props: [...], emits: [...] })
+                "defineComponent" => {
+                    if let Some(options) = call_arguments(node).first().filter(|a| a.kind() == "object") {
+                        properties.extend(options_members(options, source));
+                    }
+                }
+                _ => {}
+            }
+        }
+        "export_statement" => {
+            // export default { props: [...], emits: [...] }
+            if let Some(options) = node.child_by_field_name("value").filter(|v| v.kind() == "object") {
+                properties.extend(options_members(&options, source));
+            }
+            // Svelte 4: export let title: string = 'x'
+            if let Some(declaration) = node.child_by_field_name("declaration")
+                .filter(|d| d.kind() == "lexical_declaration" && d.child_by_field_name("kind").is_some_and(|k| k.kind() == "let")) {
+                let mut cursor = declaration.walk();
+                for declarator in declaration.named_children(&mut cursor).filter(|d| d.kind() == "variable_declarator") {
+                    let Some(name) = declarator.child_by_field_name("name").filter(|n| n.kind() == "identifier") else {
+                        continue;
+                    };
+                    let mut prop = member(name.utf8_text(source).unwrap_or("").to_string(), "@prop");
+                    prop.type_annotation = type_text(declarator.child_by_field_name("type"), source);
+                    prop.value = declarator.child_by_field_name("value").and_then(|v| v.utf8_text(source).ok()).map(|v| v.to_string());
+                    properties.push(prop);
+                }
+            }
+        }
+        // Svelte 5: let { title, count = 0 }: Props = $props()
+        "variable_declarator" => {
+            let is_props_rune = node.child_by_field_name("value")
+                .and_then(|v| v.child_by_field_name("function"))
+                .and_then(|f| f.utf8_text(source).ok())
+                == Some("$props");
+            if let (true, Some(pattern)) = (is_props_rune, node.child_by_field_name("name").filter(|n| n.kind() == "object_pattern")) {
+                let types = node.child_by_field_name("type")
+                    .and_then(|t| t.named_child(0))
+                    .map(|t| type_members(&t, root, source, "@prop"))
+                    .unwrap_or_default();
+                properties.extend(pattern_members(&pattern, source, &types));
+                return;
+            }
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_component_members(&child, root, source, properties);
+    }
+}
+
+/// Members declared by `defineProps` / `defineEmits`, from type arguments or the runtime argument
+fn declared_members(call: &Node, root: &Node, source: &[u8], decorator: &str) -> Vec<PropertyInfo> {
+    if let Some(type_arg) = call.child_by_field_name("type_arguments").and_then(|t| t.named_child(0)) {
+        return type_members(&type_arg, root, source, decorator);
+    }
+    call_arguments(call).first()
+        .map(|arg| runtime_members(arg, source, decorator))
+        .unwrap_or_default()
+}
+
+/// `['a', 'b']` or `{ a: String, b: { type: Number, default: 0 } }`
+fn runtime_members(node: &Node, source: &[u8], decorator: &str) -> Vec<PropertyInfo> {
+    match node.kind() {
+        "array" => {
+            let mut cursor = node.walk();
+            let members = node.named_children(&mut cursor)
+                .filter_map(|element| string_value(&element, source))
+                .map(|name| member(name, decorator))
+                .collect();
+            members
+        }
+        "object" => object_pairs(node, source).into_iter()
+            .map(|(key, value)| {
+                let mut prop = member(key, decorator);
+                if value.kind() == "object" {
+                    for (option, option_value) in object_pairs(&value, source) {
+                        let text = option_value.utf8_text(source).ok().map(|t| t.to_string());
+                        match option.as_str() {
+                            "type" => prop.type_annotation = text,
+                            "default" => prop.value = text,
+                            _ => {}
+                        }
+                    }
+                } else if matches!(value.kind(), "identifier" | "array") && decorator == "@prop" {
+                    prop.type_annotation = value.utf8_text(source).ok().map(|t| t.to_string());
+                }
+                prop
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Members of an object type, or of the interface / type alias it names
+fn type_members(node: &Node, root: &Node, source: &[u8], decorator: &str) -> Vec<PropertyInfo> {
+    let body = match node.kind() {
+        "object_type" => Some(*node),
+        "type_identifier" => {
+            let name = node.utf8_text(source).unwrap_or("");
+            find_type_body(root, name, source)
+        }
+        _ => None,
+    };
+    let Some(body) = body else {
+        return Vec::new();
+    };
+
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+    for signature in body.named_children(&mut cursor) {
+        match signature.kind() {
+            "property_signature" => {
+                let Some(name) = signature.child_by_field_name("name").and_then(|n| n.utf8_text(source).ok()) else {
+                    continue;
+                };
+                let mut prop = member(name.trim_matches(['\'', '"']).to_string(), decorator);
+                prop.type_annotation = type_text(signature.child_by_field_name("type"), source);
+                members.push(prop);
+            }
+            // (e: 'change', id: number): void
+            "call_signature" => {
+                let event = signature.child_by_field_name("parameters")
+                    .and_then(|p| p.named_child(0))
+                    .and_then(|p| p.child_by_field_name("type"))
+                    .and_then(|t| t.named_child(0))
+                    .filter(|t| t.kind() == "literal_type")
+                    .and_then(|t| t.named_child(0))
+                    .and_then(|s| string_value(&s, source));
+                if let Some(event) = event {
+                    members.push(member(event, decorator));
+                }
+            }
+            _ => {}
+        }
+    }
+    members
+}
+
+/// Body of `interface Name { ... }` or `type Name = { ... }` declared in the script
+fn find_type_body<'a>(root: &Node<'a>, name: &str, source: &[u8]) -> Option<Node<'a>> {
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        // `export interface Props` wraps the declaration
+        let declaration = if child.kind() == "export_statement" {
+            match child.child_by_field_name("declaration") {
+                Some(d) => d,
+                None => continue,
+            }
+        } else {
+            child
+        };
+        if declaration.child_by_field_name("name").and_then(|n| n.utf8_text(source).ok()) != Some(name) {
+            continue;
+        }
+        match declaration.kind() {
+            "interface_declaration" => return declaration.child_by_field_name("body"),
+            "type_alias_declaration" => return declaration.child_by_field_name("value").filter(|v| v.kind() == "object_type"),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `props` / `emits` entries of an Options API component object
+fn options_members(options: &Node, source: &[u8]) -> Vec<PropertyInfo> {
+    let mut members = Vec::new();
+    for (key, value) in object_pairs(options, source) {
+        match key.as_str() {
+            "props" => members.extend(runtime_members(&value, source, "@prop")),
+            "emits" => members.extend(runtime_members(&value, source, "@emit")),
+            _ => {}
+        }
+    }
+    members
+}
+
+/// `{ a, b = 1, c: renamed }` destructured from `$props()`
+fn pattern_members(pattern: &Node, source: &[u8], types: &[PropertyInfo]) -> Vec<PropertyInfo> {
+    let mut members = Vec::new();
+    let mut cursor = pattern.walk();
+    for child in pattern.named_children(&mut cursor) {
+        let (name, default) = match child.kind() {
+            "shorthand_property_identifier_pattern" => (child.utf8_text(source).ok(), None),
+            "object_assignment_pattern" => (
+                child.child_by_field_name("left").and_then(|l| l.utf8_text(source).ok()),
+                child.child_by_field_name("right"),
+            ),
+            "pair_pattern" => (child.child_by_field_name("key").and_then(|k| k.utf8_text(source).ok()), None),
+            _ => continue,
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        let mut prop = member(name.to_string(), "@prop");
+        prop.type_annotation = types.iter().find(|t| t.name == name).and_then(|t| t.type_annotation.clone());
+        prop.value = default.and_then(|d| d.utf8_text(source).ok()).map(|d| d.to_string());
+        members.push(prop);
+    }
+    members
+}
+
+fn member(name: String, decorator: &str) -> PropertyInfo {
+    PropertyInfo {
+        name,
+        type_annotation: None,
+        is_static: false,
+        // Props are read-only inside the component
+        is_readonly: true,
+        visibility: Visibility::Public,
+        tags: None,
+        value: None,
+        is_computed: false,
+        decorators: vec![decorator.to_string()],
+    }
+}
+
+fn call_arguments<'a>(call: &Node<'a>) -> Vec<Node<'a>> {
+    let Some(args) = call.child_by_field_name("arguments") else {
+        return Vec::new();
+    };
+    let mut cursor = args.walk();
+    let nodes = args.named_children(&mut cursor).collect();
+    nodes
+}
+
+fn object_pairs<'a>(object: &Node<'a>, source: &[u8]) -> Vec<(String, Node<'a>)> {
+    let mut pairs = Vec::new();
+    let mut cursor = object.walk();
+    for pair in object.named_children(&mut cursor).filter(|p| p.kind() == "pair") {
+        let (Some(key), Some(value)) = (pair.child_by_field_name("key"), pair.child_by_field_name("value")) else {
+            continue;
+        };
+        let key = string_value(&key, source)
+            .or_else(|| key.utf8_text(source).ok().map(|k| k.to_string()))
+            .unwrap_or_default();
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+/// Text of a string literal node without quotes
+fn string_value(node: &Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "string" {
+        return None;
+    }
+    node.utf8_text(source).ok().map(|s| s.trim_matches(['\'', '"']).to_string())
+}
+
+/// Type text from a `type_annotation` node (without the leading colon)
+fn type_text(annotation: Option<Node>, source: &[u8]) -> Option<String> {
+    annotation?.named_child(0)?.utf8_text(source).ok().map(|t| t.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ParserManager;
+
+    #[test]
+    fn test_extract_script_keeps_offsets() {
+        let source = "<template>\n  <p>héllo</p>\n</template>\n<script setup lang=\"ts\">\nconst a = 1\n</script>\n";
+        let script = extract_script(source);
+
+        assert!(script.is_typescript);
+        assert_eq!(script.source.len(), source.len());
+        assert_eq!(script.source.lines().nth(4), Some("const a = 1"));
+        assert!(script.source.lines().take(4).all(|l| l.trim().is_empty()));
+    }
+
+    #[test]
+    fn test_parse_vue_script_setup() {
+        let source = r#"<template>
+  <button @click="emit('change', count)">{{ title }}</button>
+</template>
+
+<script setup lang="ts">
+import { ref } from 'vue'
+import { api } from '@/api'
+
+interface Props {
+  title: string
+  count?: number
+}
+const props = withDefaults(defineProps<Props>(), { count: 0 })
+const emit = defineEmits<{ (e: 'change', id: number): void; update: [value: string] }>()
+
+async function save() {
+  await api.post('/items', { title: props.title })
+}
+</script>
+"#;
+        let mut manager = ParserManager::new();
+        let result = manager.parse_file("src/components/ItemCard.vue", source).unwrap();
+
+        assert_eq!(result.language, Language::TypeScript);
+        assert_eq!(result.imports.len(), 2);
+        let save = result.functions.iter().find(|f| f.name == "save").unwrap();
+        assert_eq!(save.range.start.line, 15);
+        let post = result.calls.iter().find(|c| c.callee == "post").unwrap();
+        assert_eq!(post.range.start.line, 16);
+        assert_eq!(post.range.start.column, 8);
+
+        let component = result.classes.iter().find(|c| c.name == "ItemCard").unwrap();
+        let members: Vec<(&str, Option<&str>, Option<&str>, &str)> = component.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref(), p.value.as_deref(), p.decorators[0].as_str()))
+            .collect();
+        assert_eq!(members, vec![
+            ("title", Some("string"), None, "@prop"),
+            ("count", Some("number"), Some("0"), "@prop"),
+            ("change", None, None, "@emit"),
+            ("update", Some("[value: string]"), None, "@emit"),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_vue_options_api() {
+        let source = "<script>\nexport default {\n  props: { title: String, size: { type: Number, default: 1 } },\n  emits: ['close'],\n}\n</script>\n<template><div/></template>\n";
+        let mut manager = ParserManager::new();
+        let result = manager.parse_file("Modal.vue", source).unwrap();
+
+        assert_eq!(result.language, Language::JavaScript);
+        let component = result.classes.iter().find(|c| c.name == "Modal").unwrap();
+        let members: Vec<(&str, Option<&str>, Option<&str>)> = component.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref(), p.value.as_deref()))
+            .collect();
+        assert_eq!(members, vec![("title", Some("String"), None), ("size", Some("Number"), Some("1")), ("close", None, None)]);
+    }
+
+    #[test]
+    fn test_parse_svelte_props() {
+        let mut manager = ParserManager::new();
+
+        let svelte4 = "<script lang=\"ts\">\n  export let name: string = 'world';\n  export const version = 1;\n  fetch('/api/user');\n</script>\n\n<h1>Hello {name}!</h1>\n";
+        let result = manager.parse_file("Hello.svelte", svelte4).unwrap();
+        let component = result.classes.iter().find(|c| c.name == "Hello").unwrap();
+        assert_eq!(component.properties.len(), 1);
+        assert_eq!(component.properties[0].name, "name");
+        assert_eq!(component.properties[0].type_annotation.as_deref(), Some("string"));
+        assert_eq!(component.properties[0].value.as_deref(), Some("'world'"));
+        let fetch = result.calls.iter().find(|c| c.callee == "fetch").unwrap();
+        assert_eq!(fetch.range.start.line, 3);
+
+        let svelte5 = "<script lang=\"ts\">\n  type Props = { title: string; count?: number };\n  let { title, count = 0 }: Props = $props();\n</script>\n";
+        let result = manager.parse_file("Counter.svelte", svelte5).unwrap();
+        let component = result.classes.iter().find(|c| c.name == "Counter").unwrap();
+        let members: Vec<(&str, Option<&str>, Option<&str>)> = component.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref(), p.value.as_deref()))
+            .collect();
+        assert_eq!(members, vec![("title", Some("string"), None), ("count", Some("number"), Some("0"))]);
+    }
+}
@@ -0,0 +1,663 @@
+//! Swift parser using native tree-sitter
+//!
+//! Extracts functions, types, imports, and call sites from Swift code.
+//! Supports SwiftUI, UIKit, and URLSession patterns.
+//!
+//! Enterprise features:
+//! - Attribute / property wrapper extraction (@State, @objc, @MainActor)
+//! - `async` functions and initializers
+//! - Classes, structs, enums, actors, extensions and protocols (protocols are abstract)
+//! - Stored vs computed property detection
+//! - Doc comment (`///`) extraction
+
+use std::time::Instant;
+use tree_sitter::{Node, Parser};
+
+use super::types::*;
+
+/// Swift parser
+pub struct SwiftParser {
+    parser: Parser,
+}
+
+impl SwiftParser {
+    pub fn new() -> Result<Self, String> {
+        let mut parser = Parser::new();
+        let language = tree_sitter_swift::LANGUAGE;
+        parser.set_language(&language.into())
+            .map_err(|e| format!("Failed to set language: {}", e))?;
+
+        Ok(Self { parser })
+    }
+
+    pub fn parse(&mut self, source: &str) -> ParseResult {
+        let start = Instant::now();
+
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => {
+                let mut result = ParseResult::new(Language::Swift);
+                result.errors.push(ParseError {
+                    message: "Failed to parse source".to_string(),
+                    range: Range::new(0, 0, 0, 0),
+                    snippet: None,
+                });
+                return result;
+            }
+        };
+
+        let root = tree.root_node();
+        let source_bytes = source.as_bytes();
+
+        let mut result = ParseResult::with_tree(Language::Swift, tree.clone());
+
+        result.record_syntax_errors(&root, source_bytes);
+
+        self.extract_imports(&root, source_bytes, &mut result);
+        self.extract_declarations(&root, source_bytes, None, &mut result);
+        self.extract_calls(&root, source_bytes, &mut result);
+
+        result.parse_time_us = start.elapsed().as_micros() as u64;
+        result
+    }
+
+    /// Extract types and functions declared in `body`
+    ///
+    /// Returns the functions declared directly in the body so the caller can
+    /// attach them to the enclosing type.
+    fn extract_declarations(
+        &self,
+        body: &Node,
+        source: &[u8],
+        owner: Option<&str>,
+        result: &mut ParseResult,
+    ) -> Vec<FunctionInfo> {
+        let mut methods = Vec::new();
+
+        let mut cursor = body.walk();
+        for child in body.named_children(&mut cursor) {
+            match child.kind() {
+                "function_declaration" | "init_declaration" | "protocol_function_declaration" => {
+                    if let Some(func) = self.extract_function(&child, source, owner) {
+                        result.functions.push(func.clone());
+                        methods.push(func);
+                    }
+                }
+                "class_declaration" | "protocol_declaration" => {
+                    self.extract_type(&child, source, result);
+                }
+                _ => {}
+            }
+        }
+
+        methods
+    }
+
+    /// Extract a class, struct, enum, actor, extension or protocol
+    fn extract_type(&self, node: &Node, source: &[u8], result: &mut ParseResult) {
+        let Some(name) = node.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+            .map(|s| s.to_string()) else {
+            return;
+        };
+        let kind = node.child_by_field_name("declaration_kind").map(|k| k.kind()).unwrap_or("class");
+
+        let modifiers = find_child(node, "modifiers");
+        let decorators = modifiers.map(|m| extract_attributes(&m, source)).unwrap_or_default();
+        let is_exported = modifiers.map(|m| is_visible(&m, source)).unwrap_or(true);
+
+        // Only a class can have a superclass, and it must be listed first
+        let mut inherited = Vec::new();
+        let mut cursor = node.walk();
+        for spec in node.named_children(&mut cursor) {
+            if spec.kind() == "inheritance_specifier" {
+                if let Some(type_name) = spec.child_by_field_name("inherits_from").and_then(|t| t.utf8_text(source).ok()) {
+                    inherited.push(type_name.to_string());
+                }
+            }
+        }
+        let extends = if kind == "class" && !inherited.is_empty() {
+            Some(inherited.remove(0))
+        } else {
+            None
+        };
+
+        let mut properties = Vec::new();
+        let mut methods = Vec::new();
+        if let Some(body) = node.child_by_field_name("body") {
+            let mut body_cursor = body.walk();
+            for member in body.named_children(&mut body_cursor) {
+                match member.kind() {
+                    "property_declaration" => properties.extend(self.extract_property(&member, source)),
+                    "protocol_property_declaration" => properties.extend(self.extract_protocol_property(&member, source)),
+                    _ => {}
+                }
+            }
+            methods = self.extract_declarations(&body, source, Some(&name), result);
+        }
+
+        result.classes.push(ClassInfo {
+            name,
+            extends,
+            implements: inherited,
+            is_exported,
+            is_abstract: kind == "protocol",
+            methods,
+            properties,
+            range: node_range(node),
+            decorators,
+            namespace: None,
+            // Extensions add members to a type declared elsewhere
+            is_partial: kind == "extension",
+            type_parameters: Vec::new(),
+        });
+    }
+
+    /// Extract a function, initializer or protocol requirement
+    fn extract_function(&self, node: &Node, source: &[u8], owner: Option<&str>) -> Option<FunctionInfo> {
+        let name = node.child_by_field_name("name")?.utf8_text(source).ok()?.to_string();
+
+        let modifiers = find_child(node, "modifiers");
+        let decorators = modifiers.map(|m| extract_attributes(&m, source)).unwrap_or_default();
+        let is_exported = modifiers.map(|m| is_visible(&m, source)).unwrap_or(true);
+        let visibility = modifiers.map(|m| visibility(&m, source)).unwrap_or(Visibility::Public);
+        let is_static = modifiers.is_some_and(|m| has_modifier(&m, source, "static") || has_modifier(&m, source, "class"));
+
+        let mut parameters: Vec<ParameterInfo> = Vec::new();
+        let mut is_async = false;
+        let mut return_type = None;
+        let mut after_arrow = false;
+
+        let mut cursor = node.walk();
+        for (i, child) in node.children(&mut cursor).enumerate() {
+            match child.kind() {
+                "parameter" => parameters.push(self.extract_parameter(&child, source)),
+                "async" => is_async = true,
+                "->" => after_arrow = true,
+                _ if node.field_name_for_child(i as u32) == Some("default_value") => {
+                    if let Some(last) = parameters.last_mut() {
+                        last.default_value = child.utf8_text(source).ok().map(|t| t.to_string());
+                    }
+                }
+                _ if after_arrow && child.is_named() => {
+                    return_type = child.utf8_text(source).ok().map(|t| t.to_string());
+                    after_arrow = false;
+                }
+                _ => {}
+            }
+        }
+
+        Some(FunctionInfo {
+            qualified_name: owner.map(|o| format!("{}.{}", o, name)),
+            name,
+            parameters,
+            return_type,
+            is_exported,
+            is_async,
+            is_generator: false,
+            visibility,
+            is_static,
+            type_parameters: Vec::new(),
+            range: node_range(node),
+            decorators,
+            doc_comment: self.extract_doc_comment(node, source),
+        })
+    }
+
+    /// Extract a parameter (`_ label: String = "a"`, `items: String...`)
+    fn extract_parameter(&self, node: &Node, source: &[u8]) -> ParameterInfo {
+        let name = node.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+            .unwrap_or("")
+            .to_string();
+
+        let mut type_annotation = None;
+        let mut after_colon = false;
+        let mut is_rest = false;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                ":" => after_colon = true,
+                "..." => is_rest = true,
+                _ if after_colon && child.is_named() && type_annotation.is_none() => {
+                    type_annotation = child.utf8_text(source).ok().map(|t| t.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        ParameterInfo {
+            name,
+            type_annotation,
+            default_value: None,
+            is_rest,
+        }
+    }
+
+    /// Stored or computed property declared in a type body
+    fn extract_property(&self, node: &Node, source: &[u8]) -> Option<PropertyInfo> {
+        let name = node.child_by_field_name("name")
+            .and_then(|p| p.child_by_field_name("bound_identifier"))?
+            .utf8_text(source).ok()?
+            .to_string();
+
+        let type_annotation = find_child(node, "type_annotation")
+            .and_then(|t| t.child_by_field_name("name"))
+            .and_then(|t| t.utf8_text(source).ok())
+            .map(|t| t.to_string());
+        let value = node.child_by_field_name("value")
+            .and_then(|v| v.utf8_text(source).ok())
+            .map(|v| v.to_string());
+
+        let computed = node.child_by_field_name("computed_value");
+        let is_let = find_child(node, "value_binding_pattern")
+            .and_then(|b| b.child_by_field_name("mutability"))
+            .is_some_and(|m| m.kind() == "let");
+        // A computed property without a setter is read-only
+        let is_readonly = is_let || computed.is_some_and(|c| find_child(&c, "computed_setter").is_none());
+
+        let modifiers = find_child(node, "modifiers");
+        Some(PropertyInfo {
+            name,
+            type_annotation,
+            is_static: modifiers.is_some_and(|m| has_modifier(&m, source, "static") || has_modifier(&m, source, "class")),
+            is_readonly,
+            visibility: modifiers.map(|m| visibility(&m, source)).unwrap_or(Visibility::Public),
+            tags: None,
+            value,
+            is_computed: computed.is_some(),
+            decorators: modifiers.map(|m| extract_attributes(&m, source)).unwrap_or_default(),
+        })
+    }
+
+    /// Protocol property requirement (`var base: URL { get }`)
+    fn extract_protocol_property(&self, node: &Node, source: &[u8]) -> Option<PropertyInfo> {
+        let name = node.child_by_field_name("name")
+            .and_then(|p| p.child_by_field_name("bound_identifier"))?
+            .utf8_text(source).ok()?
+            .to_string();
+        let type_annotation = find_child(node, "type_annotation")
+            .and_then(|t| t.child_by_field_name("name"))
+            .and_then(|t| t.utf8_text(source).ok())
+            .map(|t| t.to_string());
+        let is_readonly = find_child(node, "protocol_property_requirements")
+            .is_none_or(|r| find_child(&r, "setter_specifier").is_none());
+
+        let modifiers = find_child(node, "modifiers");
+        Some(PropertyInfo {
+            name,
+            type_annotation,
+            is_static: modifiers.is_some_and(|m| has_modifier(&m, source, "static")),
+            is_readonly,
+            visibility: Visibility::Public,
+            tags: None,
+            value: None,
+            is_computed: false,
+            decorators: Vec::new(),
+        })
+    }
+
+    /// Extract `///` or `/** */` doc comments directly above a declaration
+    fn extract_doc_comment(&self, node: &Node, source: &[u8]) -> Option<String> {
+        let mut lines = Vec::new();
+        let mut sibling = node.prev_sibling();
+        while let Some(s) = sibling {
+            let text = match s.kind() {
+                "comment" | "multiline_comment" => s.utf8_text(source).ok()?,
+                _ => break,
+            };
+            if let Some(line) = text.strip_prefix("///") {
+                lines.push(line.trim().to_string());
+            } else if text.starts_with("/**") {
+                let block: Vec<&str> = text.trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .lines()
+                    .map(|l| l.trim().trim_start_matches('*').trim())
+                    .filter(|l| !l.is_empty())
+                    .collect();
+                lines.extend(block.into_iter().rev().map(|l| l.to_string()));
+                break;
+            } else {
+                break;
+            }
+            sibling = s.prev_sibling();
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            lines.reverse();
+            Some(lines.join("\n"))
+        }
+    }
+
+    fn extract_imports(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = root.walk();
+        for import in root.named_children(&mut cursor) {
+            if import.kind() != "import_declaration" {
+                continue;
+            }
+            let Some(path) = find_child(&import, "identifier").and_then(|n| n.utf8_text(source).ok()) else {
+                continue;
+            };
+            // `import struct Foundation.URL` imports a single declaration
+            let is_declaration_import = import.children(&mut import.walk())
+                .any(|c| matches!(c.kind(), "struct" | "class" | "enum" | "protocol" | "typealias" | "func" | "var" | "let"));
+            let named = if is_declaration_import {
+                path.rsplit('.').next().map(|n| vec![n.to_string()]).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            result.imports.push(ImportInfo {
+                source: path.to_string(),
+                named,
+                default: None,
+                namespace: None,
+                is_type_only: false,
+                relative_level: 0,
+                range: node_range(&import),
+            });
+        }
+    }
+
+    fn extract_calls(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut stack = vec![*root];
+        while let Some(node) = stack.pop() {
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+
+            if node.kind() != "call_expression" {
+                continue;
+            }
+            let Some(function) = node.named_child(0) else {
+                continue;
+            };
+            let (callee, receiver) = match function.kind() {
+                "simple_identifier" => (function.utf8_text(source).unwrap_or("").to_string(), None),
+                // `URLSession.shared.data(from: url)`
+                "navigation_expression" => {
+                    let Some(suffix) = function.child_by_field_name("suffix")
+                        .and_then(|s| s.child_by_field_name("suffix")) else {
+                        continue;
+                    };
+                    (
+                        suffix.utf8_text(source).unwrap_or("").to_string(),
+                        function.child_by_field_name("target")
+                            .and_then(|t| t.utf8_text(source).ok())
+                            .map(|t| t.to_string()),
+                    )
+                }
+                _ => continue,
+            };
+            if callee.is_empty() {
+                continue;
+            }
+
+            let mut arg_count = 0;
+            let mut string_arg = None;
+            if let Some(call_suffix) = find_child(&node, "call_suffix") {
+                let mut suffix_cursor = call_suffix.walk();
+                for part in call_suffix.named_children(&mut suffix_cursor) {
+                    match part.kind() {
+                        "value_arguments" => {
+                            let mut args_cursor = part.walk();
+                            let args: Vec<Node> = part.named_children(&mut args_cursor).collect();
+                            arg_count += args.len();
+                            string_arg = args.first()
+                                .and_then(|a| a.child_by_field_name("value"))
+                                .and_then(|v| plain_string(&v, source));
+                        }
+                        // Trailing closure: `dataTask(with: url) { data, _, _ in }`
+                        "lambda_literal" => arg_count += 1,
+                        _ => {}
+                    }
+                }
+            }
+
+            result.calls.push(CallSite {
+                callee,
+                receiver,
+                arg_count,
+                range: node_range(&node),
+                string_arg,
+            });
+        }
+        result.calls.sort_by_key(|c| (c.range.start.line, c.range.start.column));
+    }
+}
+
+impl Default for SwiftParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create Swift parser")
+    }
+}
+
+fn find_child<'a>(node: &Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).find(|c| c.kind() == kind);
+    found
+}
+
+/// Whether a modifiers node contains the given keyword (`static`, `private`)
+fn has_modifier(modifiers: &Node, source: &[u8], keyword: &str) -> bool {
+    let mut cursor = modifiers.walk();
+    let found = modifiers.named_children(&mut cursor)
+        .any(|m| m.kind() != "attribute" && m.utf8_text(source).unwrap_or("").starts_with(keyword));
+    found
+}
+
+/// `private(set)` only restricts the setter, so it is still readable from outside
+fn visibility(modifiers: &Node, source: &[u8]) -> Visibility {
+    let mut cursor = modifiers.walk();
+    let restricted = modifiers.named_children(&mut cursor)
+        .filter(|m| m.kind() == "visibility_modifier")
+        .filter_map(|m| m.utf8_text(source).ok())
+        .any(|m| m == "private" || m == "fileprivate");
+    if restricted {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    }
+}
+
+/// Swift declarations are visible to the module unless private or fileprivate
+fn is_visible(modifiers: &Node, source: &[u8]) -> bool {
+    visibility(modifiers, source) == Visibility::Public
+}
+
+/// Format attributes as @Name or @Name(args)
+fn extract_attributes(modifiers: &Node, source: &[u8]) -> Vec<String> {
+    let mut attributes = Vec::new();
+    let mut cursor = modifiers.walk();
+    for attribute in modifiers.named_children(&mut cursor) {
+        if attribute.kind() != "attribute" {
+            continue;
+        }
+        let Some(name) = find_child(&attribute, "user_type").and_then(|n| n.utf8_text(source).ok()) else {
+            continue;
+        };
+        // Arguments run from `(` to the end of the attribute
+        let args = attribute.children(&mut attribute.walk())
+            .find(|c| c.kind() == "(")
+            .and_then(|open| source.get(open.start_byte()..attribute.end_byte()))
+            .and_then(|a| std::str::from_utf8(a).ok())
+            .unwrap_or("");
+        attributes.push(format!("@{}{}", name, args));
+    }
+    attributes
+}
+
+/// Content of a string literal without interpolation (`"https://api"`)
+fn plain_string(node: &Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "line_string_literal" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let mut content = String::new();
+    for part in node.named_children(&mut cursor) {
+        match part.kind() {
+            "line_str_text" | "str_escaped_char" => content.push_str(part.utf8_text(source).ok()?),
+            _ => return None,
+        }
+    }
+    Some(content)
+}
+
+fn node_range(node: &Node) -> Range {
+    Range {
+        start: Position {
+            line: node.start_position().row as u32,
+            column: node.start_position().column as u32,
+        },
+        end: Position {
+            line: node.end_position().row as u32,
+            column: node.end_position().column as u32,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function() {
+        let mut parser = SwiftParser::new().unwrap();
+        let result = parser.parse("func greet(_ name: String, times: Int = 1, tags: String...) -> String { name }\nprivate func helper() {}");
+
+        assert_eq!(result.functions.len(), 2);
+        let greet = &result.functions[0];
+        assert_eq!(greet.name, "greet");
+        assert!(greet.is_exported);
+        assert!(!greet.is_async);
+        assert_eq!(greet.return_type.as_deref(), Some("String"));
+        let params: Vec<(&str, Option<&str>, bool)> = greet.parameters.iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref(), p.is_rest))
+            .collect();
+        assert_eq!(params, vec![("name", Some("String"), false), ("times", Some("Int"), false), ("tags", Some("String"), true)]);
+        assert_eq!(greet.parameters[1].default_value.as_deref(), Some("1"));
+        assert!(!result.functions[1].is_exported);
+    }
+
+    #[test]
+    fn test_parse_swiftui_view() {
+        let mut parser = SwiftParser::new().unwrap();
+        let source = r#"
+import SwiftUI
+
+/// Shows a user's profile
+@MainActor
+struct ProfileView: View {
+    @State private var name: String = ""
+    @AppStorage("theme") var theme = "light"
+    static let preview = ProfileView()
+
+    var body: some View {
+        Text(name).padding()
+    }
+
+    var initials: String {
+        get { String(name.prefix(1)) }
+        set { name = newValue }
+    }
+}
+"#;
+        let result = parser.parse(source);
+
+        assert_eq!(result.imports[0].source, "SwiftUI");
+        let view = result.classes.iter().find(|c| c.name == "ProfileView").unwrap();
+        assert_eq!(view.decorators, vec!["@MainActor"]);
+        assert_eq!(view.extends, None);
+        assert_eq!(view.implements, vec!["View"]);
+
+        let props: Vec<(&str, bool, bool, bool)> = view.properties.iter()
+            .map(|p| (p.name.as_str(), p.is_computed, p.is_readonly, p.is_static))
+            .collect();
+        assert_eq!(props, vec![
+            ("name", false, false, false),
+            ("theme", false, false, false),
+            ("preview", false, true, true),
+            ("body", true, true, false),
+            ("initials", true, false, false),
+        ]);
+        assert_eq!(view.properties[0].decorators, vec!["@State"]);
+        assert_eq!(view.properties[0].visibility, Visibility::Private);
+        assert_eq!(view.properties[1].decorators, vec!["@AppStorage(\"theme\")"]);
+        assert_eq!(view.properties[1].value.as_deref(), Some("\"light\""));
+        assert_eq!(view.properties[3].type_annotation.as_deref(), Some("some View"));
+
+        let padding = result.calls.iter().find(|c| c.callee == "padding").unwrap();
+        assert_eq!(padding.receiver.as_deref(), Some("Text(name)"));
+        assert!(result.calls.iter().any(|c| c.callee == "Text" && c.receiver.is_none()));
+    }
+
+    #[test]
+    fn test_parse_urlsession_client() {
+        let mut parser = SwiftParser::new().unwrap();
+        let source = r#"
+import Foundation
+import struct Foundation.URL
+
+protocol UserService {
+    func fetchUser(id: Int) async throws -> User
+    var baseURL: URL { get }
+}
+
+final class APIClient: NSObject, UserService {
+    let baseURL: URL
+
+    init(baseURL: URL) {
+        self.baseURL = baseURL
+    }
+
+    /// Loads a user from the API
+    @objc public func fetchUser(id: Int) async throws -> User {
+        let (data, _) = try await URLSession.shared.data(from: baseURL.appendingPathComponent("users"))
+        return try JSONDecoder().decode(User.self, from: data)
+    }
+
+    private func legacy(url: URL) {
+        URLSession.shared.dataTask(with: url) { data, _, _ in }.resume()
+    }
+}
+
+extension APIClient: CustomStringConvertible {
+    var description: String { "APIClient" }
+}
+"#;
+        let result = parser.parse(source);
+
+        assert_eq!(result.imports[1].named, vec!["URL"]);
+
+        let service = result.classes.iter().find(|c| c.name == "UserService").unwrap();
+        assert!(service.is_abstract);
+        assert_eq!(service.methods[0].name, "fetchUser");
+        assert!(service.methods[0].is_async);
+        assert!(service.properties[0].is_readonly);
+
+        let client = result.classes.iter().find(|c| c.name == "APIClient" && !c.is_partial).unwrap();
+        assert_eq!(client.extends.as_deref(), Some("NSObject"));
+        assert_eq!(client.implements, vec!["UserService"]);
+        assert_eq!(client.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["init", "fetchUser", "legacy"]);
+
+        let fetch = client.methods.iter().find(|m| m.name == "fetchUser").unwrap();
+        assert!(fetch.is_async);
+        assert_eq!(fetch.decorators, vec!["@objc"]);
+        assert_eq!(fetch.qualified_name.as_deref(), Some("APIClient.fetchUser"));
+        assert_eq!(fetch.return_type.as_deref(), Some("User"));
+        assert_eq!(fetch.doc_comment.as_deref(), Some("Loads a user from the API"));
+        assert!(!client.methods.iter().find(|m| m.name == "legacy").unwrap().is_exported);
+
+        let extension = result.classes.iter().find(|c| c.name == "APIClient" && c.is_partial).unwrap();
+        assert_eq!(extension.implements, vec!["CustomStringConvertible"]);
+        assert!(extension.properties[0].is_computed);
+
+        let data_call = result.calls.iter().find(|c| c.callee == "data").unwrap();
+        assert_eq!(data_call.receiver.as_deref(), Some("URLSession.shared"));
+        let task = result.calls.iter().find(|c| c.callee == "dataTask").unwrap();
+        assert_eq!(task.arg_count, 2);
+        let path = result.calls.iter().find(|c| c.callee == "appendingPathComponent").unwrap();
+        assert_eq!(path.string_arg.as_deref(), Some("users"));
+    }
+}
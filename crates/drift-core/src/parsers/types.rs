@@ -16,6 +16,9 @@ pub enum Language {
     Rust,
     Cpp,
     C,
+    Ruby,
+    Kotlin,
+    Swift,
 }
 
 impl Language {
@@ -32,19 +35,50 @@ impl Language {
             "rs" => Some(Language::Rust),
             "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Some(Language::Cpp),
             "c" | "h" => Some(Language::C),
+            "rb" | "rake" | "gemspec" => Some(Language::Ruby),
+            "kt" | "kts" => Some(Language::Kotlin),
+            "swift" => Some(Language::Swift),
             _ => None,
         }
     }
     
     /// Get language from file path
     pub fn from_path(path: &str) -> Option<Self> {
+        let file_name = path.rsplit(['/', '\\']).next()?;
+        if matches!(file_name, "Gemfile" | "Rakefile" | "Guardfile" | "Podfile") {
+            return Some(Language::Ruby);
+        }
         let ext = path.rsplit('.').next()?;
+        // Single-file components are parsed through their <script> block
+        if ext.eq_ignore_ascii_case("vue") || ext.eq_ignore_ascii_case("svelte") {
+            return Some(Language::JavaScript);
+        }
         Self::from_extension(ext)
     }
+    
+    /// Get language from its name (as reported by the scanner or NAPI layer)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "typescript" | "ts" => Some(Language::TypeScript),
+            "javascript" | "js" => Some(Language::JavaScript),
+            "python" | "py" => Some(Language::Python),
+            "java" => Some(Language::Java),
+            "csharp" | "c#" | "cs" => Some(Language::CSharp),
+            "php" => Some(Language::Php),
+            "go" | "golang" => Some(Language::Go),
+            "rust" | "rs" => Some(Language::Rust),
+            "cpp" | "c++" => Some(Language::Cpp),
+            "c" => Some(Language::C),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "kotlin" | "kt" => Some(Language::Kotlin),
+            "swift" => Some(Language::Swift),
+            _ => None,
+        }
+    }
 }
 
 /// Position in source code
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Position {
     pub line: u32,
     pub column: u32,
@@ -64,6 +98,11 @@ impl Range {
             end: Position { line: end_line, column: end_col },
         }
     }
+    
+    /// Whether `other` lies entirely within this range
+    pub fn contains(&self, other: &Range) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
 }
 
 /// A function/method extracted from source code
@@ -83,6 +122,15 @@ pub struct FunctionInfo {
     pub is_async: bool,
     /// Is this a generator?
     pub is_generator: bool,
+    /// Member visibility (free functions are Public)
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Static / class-level method (no instance receiver)
+    #[serde(default)]
+    pub is_static: bool,
+    /// Generic / template type parameter names (e.g. `["T", "K"]`)
+    #[serde(default)]
+    pub type_parameters: Vec<String>,
     /// Source location
     pub range: Range,
     /// Decorators/attributes
@@ -112,6 +160,34 @@ pub struct ClassInfo {
     pub properties: Vec<PropertyInfo>,
     pub range: Range,
     pub decorators: Vec<String>,
+    /// Enclosing namespace (C# `namespace Foo.Bar` / `namespace Foo.Bar;`)
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Declared with `partial` - the same class may be split across several files
+    #[serde(default)]
+    pub is_partial: bool,
+    /// Generic / template type parameter names (e.g. `["T"]` for `Repository<T>`)
+    #[serde(default)]
+    pub type_parameters: Vec<String>,
+}
+
+/// An enum, or a TypeScript union of literal types, extracted from source code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumInfo {
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+    pub is_exported: bool,
+    pub range: Range,
+    pub decorators: Vec<String>,
+}
+
+/// A single enum member / union literal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub name: String,
+    /// Explicit value or discriminant as written (`= 3`, `"active"`)
+    pub value: Option<String>,
+    pub range: Range,
 }
 
 /// Property information
@@ -124,6 +200,15 @@ pub struct PropertyInfo {
     pub visibility: Visibility,
     /// Struct tags (for Go struct field tags like `json:"name"`)
     pub tags: Option<Vec<StructTag>>,
+    /// Initializer source text (e.g. PHP `protected $table = 'users'` gives "'users'")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Computed property (Swift `var x: Int { ... }`) rather than stored state
+    #[serde(default)]
+    pub is_computed: bool,
+    /// Attributes / property wrappers (e.g. Swift `@State`, `@Published`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
 }
 
 /// Struct tag (for Go struct field tags)
@@ -134,9 +219,10 @@ pub struct StructTag {
 }
 
 /// Visibility modifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Visibility {
+    #[default]
     Public,
     Private,
     Protected,
@@ -155,6 +241,9 @@ pub struct ImportInfo {
     pub namespace: Option<String>,
     /// Is this a type-only import?
     pub is_type_only: bool,
+    /// Leading dots of a Python relative import (0 = absolute)
+    #[serde(default)]
+    pub relative_level: usize,
     pub range: Range,
 }
 
@@ -185,6 +274,28 @@ pub struct CallSite {
     pub arg_count: usize,
     /// Location of the call
     pub range: Range,
+    /// First string-literal argument (e.g. the SQL passed to `sqlx::query!`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub string_arg: Option<String>,
+}
+
+/// An identifier passed as a call argument (`handler` in
+/// `app.get('/x', handler)`), which the callee may call back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArgumentRef {
+    pub name: String,
+    /// Location of the call it is passed to
+    pub range: Range,
+}
+
+/// A node captured by an ad-hoc tree-sitter query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryCapture {
+    /// Capture name without the `@`
+    pub name: String,
+    /// Source text of the captured node
+    pub text: String,
+    pub range: Range,
 }
 
 /// Result of parsing a file
@@ -197,14 +308,21 @@ pub struct ParseResult {
     pub functions: Vec<FunctionInfo>,
     /// Classes found
     pub classes: Vec<ClassInfo>,
+    /// Enums and literal unions found
+    pub enums: Vec<EnumInfo>,
     /// Imports found
     pub imports: Vec<ImportInfo>,
     /// Exports found
     pub exports: Vec<ExportInfo>,
     /// Call sites found
     pub calls: Vec<CallSite>,
+    /// Identifiers passed as call arguments
+    pub argument_refs: Vec<ArgumentRef>,
     /// Parse errors (non-fatal)
     pub errors: Vec<ParseError>,
+    /// ERROR/MISSING nodes tree-sitter recovered from; non-zero means the
+    /// file was only partially analyzed
+    pub error_node_count: usize,
     /// Parse duration in microseconds
     pub parse_time_us: u64,
 }
@@ -215,10 +333,16 @@ pub struct ParseResultSerialized {
     pub language: Language,
     pub functions: Vec<FunctionInfo>,
     pub classes: Vec<ClassInfo>,
+    #[serde(default)]
+    pub enums: Vec<EnumInfo>,
     pub imports: Vec<ImportInfo>,
     pub exports: Vec<ExportInfo>,
     pub calls: Vec<CallSite>,
+    #[serde(default)]
+    pub argument_refs: Vec<ArgumentRef>,
     pub errors: Vec<ParseError>,
+    #[serde(default)]
+    pub error_node_count: usize,
     pub parse_time_us: u64,
 }
 
@@ -228,10 +352,13 @@ impl From<ParseResult> for ParseResultSerialized {
             language: r.language,
             functions: r.functions,
             classes: r.classes,
+            enums: r.enums,
             imports: r.imports,
             exports: r.exports,
             calls: r.calls,
+            argument_refs: r.argument_refs,
             errors: r.errors,
+            error_node_count: r.error_node_count,
             parse_time_us: r.parse_time_us,
         }
     }
@@ -242,6 +369,9 @@ impl From<ParseResult> for ParseResultSerialized {
 pub struct ParseError {
     pub message: String,
     pub range: Range,
+    /// Source text of the region that failed to parse
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
 }
 
 impl ParseResult {
@@ -251,10 +381,13 @@ impl ParseResult {
             tree: None,
             functions: Vec::new(),
             classes: Vec::new(),
+            enums: Vec::new(),
             imports: Vec::new(),
             exports: Vec::new(),
             calls: Vec::new(),
+            argument_refs: Vec::new(),
             errors: Vec::new(),
+            error_node_count: 0,
             parse_time_us: 0,
         }
     }
@@ -266,11 +399,371 @@ impl ParseResult {
             tree: Some(tree),
             functions: Vec::new(),
             classes: Vec::new(),
+            enums: Vec::new(),
             imports: Vec::new(),
             exports: Vec::new(),
             calls: Vec::new(),
+            argument_refs: Vec::new(),
             errors: Vec::new(),
+            error_node_count: 0,
             parse_time_us: 0,
         }
     }
+    
+    /// Copy methods from the flat `functions` list onto their classes
+    ///
+    /// A function belongs to a class when the owner part of its qualified name
+    /// (`Owner.method`, innermost segment) is the class name and it is declared
+    /// inside the class body. The flat list is left untouched.
+    pub(crate) fn attach_methods(&mut self) {
+        for class in &mut self.classes {
+            let owned = self.functions.iter().filter(|f| {
+                let owner = f.qualified_name.as_deref()
+                    .and_then(|q| q.strip_suffix(f.name.as_str()))
+                    .and_then(|q| q.strip_suffix('.'))
+                    .map(|q| q.rsplit('.').next().unwrap_or(q));
+                owner == Some(class.name.as_str()) && class.range.contains(&f.range)
+            });
+            for method in owned {
+                if !class.methods.iter().any(|m| m.name == method.name && m.range.start == method.range.start) {
+                    class.methods.push(method.clone());
+                }
+            }
+        }
+    }
+    
+    /// Run a tree-sitter query (S-expression syntax) against the parsed tree
+    ///
+    /// `source` must be the text this result was parsed from. Captures are
+    /// returned in match order.
+    pub fn query(&self, query: &str, source: &str) -> Result<Vec<QueryCapture>, String> {
+        let tree = self.tree.as_ref().ok_or_else(|| "No syntax tree available".to_string())?;
+        let query = tree_sitter::Query::new(&tree.language(), query)
+            .map_err(|e| format!("Invalid query at offset {}: {}", e.offset, e))?;
+        
+        let names = query.capture_names();
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut captures = Vec::new();
+        for m in cursor.matches(&query, tree.root_node(), source.as_bytes()) {
+            for capture in m.captures {
+                let node = capture.node;
+                captures.push(QueryCapture {
+                    name: names[capture.index as usize].to_string(),
+                    text: node.utf8_text(source.as_bytes()).unwrap_or("").to_string(),
+                    range: Range::new(
+                        node.start_position().row as u32,
+                        node.start_position().column as u32,
+                        node.end_position().row as u32,
+                        node.end_position().column as u32,
+                    ),
+                });
+            }
+        }
+        Ok(captures)
+    }
+    
+    /// Record a `ParseError` for every ERROR or MISSING region in the tree
+    ///
+    /// tree-sitter recovers from syntax errors by wrapping the unparseable
+    /// span, so extraction still runs on the rest of the file. Nested error
+    /// nodes count once, as part of their outermost region.
+    pub fn record_syntax_errors(&mut self, root: &tree_sitter::Node, source: &[u8]) {
+        if !root.has_error() {
+            return;
+        }
+        let mut stack = vec![*root];
+        while let Some(node) = stack.pop() {
+            if node.is_error() || node.is_missing() {
+                let text = node.utf8_text(source).unwrap_or("");
+                let snippet: String = text.lines().next().unwrap_or("").trim().chars().take(80).collect();
+                let message = if node.is_missing() {
+                    format!("Missing {}", node.kind())
+                } else {
+                    "Syntax error".to_string()
+                };
+                self.errors.push(ParseError {
+                    message,
+                    range: Range::new(
+                        node.start_position().row as u32,
+                        node.start_position().column as u32,
+                        node.end_position().row as u32,
+                        node.end_position().column as u32,
+                    ),
+                    snippet: (!snippet.is_empty()).then_some(snippet),
+                });
+                self.error_node_count += 1;
+                continue;
+            }
+            if node.has_error() {
+                let mut cursor = node.walk();
+                let children: Vec<_> = node.children(&mut cursor).collect();
+                stack.extend(children.into_iter().rev());
+            }
+        }
+    }
+    
+    /// Fill `argument_refs` with the bare identifiers passed to calls
+    ///
+    /// Works on any grammar whose call nodes (`call_expression`, `call`,
+    /// `method_invocation`, `invocation_expression`, ...) carry an
+    /// `arguments` field; C#/PHP/Kotlin argument wrappers are looked through.
+    pub fn collect_argument_refs(&mut self, source: &str) {
+        let Some(tree) = self.tree.as_ref() else { return };
+        let mut refs = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            let kind = node.kind();
+            let arguments = (kind.contains("call") || kind.contains("invocation"))
+                .then(|| node.child_by_field_name("arguments"))
+                .flatten();
+            if let Some(arguments) = arguments {
+                let mut cursor = arguments.walk();
+                for arg in arguments.named_children(&mut cursor) {
+                    let arg = match arg.kind() {
+                        "argument" | "value_argument" if arg.named_child_count() == 1 => arg.named_child(0).unwrap_or(arg),
+                        _ => arg,
+                    };
+                    if arg.kind() != "identifier" {
+                        continue;
+                    }
+                    if let Ok(name) = arg.utf8_text(source.as_bytes()) {
+                        refs.push(ArgumentRef {
+                            name: name.to_string(),
+                            range: Range::new(
+                                node.start_position().row as u32,
+                                node.start_position().column as u32,
+                                node.end_position().row as u32,
+                                node.end_position().column as u32,
+                            ),
+                        });
+                    }
+                }
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        refs.sort_by_key(|r| r.range.start);
+        self.argument_refs = refs;
+    }
+    
+    /// HTTP `(method, path)` pairs declared by well-known route annotations
+    ///
+    /// Understands Spring (`@GetMapping`, `@RequestMapping`), ASP.NET
+    /// (`[HttpGet]`, `[Route]`), Symfony/PHP 8 attributes (`#[Route]`) and
+    /// Flask/FastAPI (`@app.get`, `@app.route`). A class-level
+    /// `@RequestMapping`/`[Route]`/`#[Route]` path prefixes the routes of
+    /// the methods declared inside the class. Mappings that accept any verb
+    /// are reported as "ANY".
+    pub fn route_annotations(&self) -> Vec<(String, String)> {
+        let mut routes = Vec::new();
+        
+        for func in &self.functions {
+            let prefix = self.classes.iter()
+                .filter(|c| range_contains(&c.range, &func.range))
+                .min_by_key(|c| c.range.end.line - c.range.start.line)
+                .and_then(|c| c.decorators.iter().find_map(|d| route_prefix(d)))
+                .unwrap_or_default();
+            
+            let annotations: Vec<(String, String)> = func.decorators.iter()
+                .filter_map(|d| route_annotation(d))
+                .flat_map(|(verbs, path)| verbs.into_iter().map(move |verb| (verb, path.clone())))
+                .collect();
+            // ASP.NET pairs a verb attribute with a method-level [Route("...")]
+            let has_verb = annotations.iter().any(|(method, _)| method != "ANY");
+            let method_route = func.decorators.iter()
+                .map(|d| split_annotation(d))
+                .find(|(name, _)| *name == "Route")
+                .filter(|_| has_verb)
+                .map(|(_, args)| route_path(args));
+            
+            for (method, path) in annotations {
+                let path = match &method_route {
+                    Some(_) if method == "ANY" => continue,
+                    Some(route) if path.is_empty() => route.clone(),
+                    _ => path,
+                };
+                routes.push((method, join_route(&prefix, &path)));
+            }
+        }
+        
+        routes
+    }
+}
+
+fn range_contains(outer: &Range, inner: &Range) -> bool {
+    (outer.start.line, outer.start.column) <= (inner.start.line, inner.start.column)
+        && (inner.end.line, inner.end.column) <= (outer.end.line, outer.end.column)
+}
+
+/// Split an annotation/decorator into its bare name and argument text
+///
+/// `@GetMapping("/{id}")` gives ("GetMapping", "\"/{id}\""), `[HttpPost]`
+/// gives ("HttpPost", ""), `@app.get("/")` gives ("app.get", "\"/\""),
+/// `#[Route('/users')]` gives ("Route", "'/users'").
+fn split_annotation(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    let text = text.strip_prefix("#[").or_else(|| text.strip_prefix('['))
+        .and_then(|t| t.strip_suffix(']'))
+        .unwrap_or_else(|| text.trim_start_matches('@'));
+    match text.find('(') {
+        Some(open) => {
+            let args = text[open + 1..].trim_end();
+            (text[..open].trim(), args.strip_suffix(')').unwrap_or(args).trim())
+        }
+        None => (text.trim(), ""),
+    }
+}
+
+/// Top-level arguments as (keyword, value) pairs (keyword is None for positional arguments)
+///
+/// Keywords are `name = value` (Java, C#, Python) or `name: value` (PHP 8, C#).
+fn annotation_args(args: &str) -> Vec<(Option<&str>, &str)> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    
+    for (i, ch) in args.char_indices() {
+        match (quote, ch) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    
+    parts.into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let key_end = p.find(['=', ':'])
+                .filter(|&sep| {
+                    let key = p[..sep].trim();
+                    !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_')
+                })
+                .filter(|&sep| !p[sep + 1..].starts_with(':'));
+            match key_end {
+                Some(sep) => (Some(p[..sep].trim()), p[sep + 1..].trim()),
+                None => (None, p),
+            }
+        })
+        .collect()
+}
+
+/// String literals in an argument value (`"/a"`, `{"/a", "/b"}`, `['GET']`)
+fn string_literals(value: &str) -> Vec<&str> {
+    let mut literals = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(['"', '\'']) {
+        let quote = &rest[start..start + 1];
+        let after = &rest[start + 1..];
+        match after.find(quote) {
+            Some(end) => {
+                literals.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    literals
+}
+
+/// Path given by the positional or `value`/`path`/`Template` argument
+fn route_path(args: &str) -> String {
+    annotation_args(args).into_iter()
+        .find(|(key, _)| matches!(key, None | Some("value" | "path" | "Template")))
+        .and_then(|(_, value)| string_literals(value).first().map(|s| s.to_string()))
+        .unwrap_or_default()
+}
+
+/// Verbs of a `RequestMethod.POST` / `{RequestMethod.GET, RequestMethod.POST}` / `['GET', 'POST']` argument
+fn route_verbs(args: &str) -> Vec<String> {
+    let Some((_, value)) = annotation_args(args).into_iter()
+        .find(|(key, _)| matches!(key, Some("method" | "methods"))) else {
+        return Vec::new();
+    };
+    let literals = string_literals(value);
+    let verbs: Vec<&str> = if literals.is_empty() {
+        value.trim_matches(['{', '}', '[', ']'])
+            .split(',')
+            .map(|v| v.rsplit('.').next().unwrap_or("").trim())
+            .collect()
+    } else {
+        literals
+    };
+    verbs.into_iter()
+        .filter(|v| !v.is_empty())
+        .map(str::to_uppercase)
+        .collect()
+}
+
+/// Verbs or the given default when none are listed
+fn verbs_or(verbs: Vec<String>, default: &str) -> Vec<String> {
+    if verbs.is_empty() {
+        vec![default.to_string()]
+    } else {
+        verbs
+    }
+}
+
+/// Path prefix declared by a class-level route annotation
+fn route_prefix(decorator: &str) -> Option<String> {
+    let (name, args) = split_annotation(decorator);
+    matches!(name, "RequestMapping" | "Route").then(|| route_path(args))
+}
+
+/// Does this decorator/annotation register its function as an HTTP route handler?
+pub fn is_route_decorator(decorator: &str) -> bool {
+    route_annotation(decorator).is_some()
+}
+
+/// Verbs and path declared by a single function-level annotation
+fn route_annotation(decorator: &str) -> Option<(Vec<String>, String)> {
+    let (name, args) = split_annotation(decorator);
+    let (receiver, short_name) = match name.rsplit_once('.') {
+        Some((receiver, short)) => (Some(receiver), short),
+        None => (None, name),
+    };
+    
+    let verbs = match (receiver, short_name) {
+        // Spring
+        (None, "GetMapping") => vec!["GET".to_string()],
+        (None, "PostMapping") => vec!["POST".to_string()],
+        (None, "PutMapping") => vec!["PUT".to_string()],
+        (None, "DeleteMapping") => vec!["DELETE".to_string()],
+        (None, "PatchMapping") => vec!["PATCH".to_string()],
+        (None, "RequestMapping") => verbs_or(route_verbs(args), "ANY"),
+        // ASP.NET
+        (None, "HttpGet") => vec!["GET".to_string()],
+        (None, "HttpPost") => vec!["POST".to_string()],
+        (None, "HttpPut") => vec!["PUT".to_string()],
+        (None, "HttpDelete") => vec!["DELETE".to_string()],
+        (None, "HttpPatch") => vec!["PATCH".to_string()],
+        // ASP.NET and Symfony (`#[Route('/users', methods: ['GET'])]`)
+        (None, "Route") => verbs_or(route_verbs(args), "ANY"),
+        // Flask / FastAPI
+        (Some(_), "get" | "post" | "put" | "delete" | "patch") => vec![short_name.to_uppercase()],
+        (Some(_), "route") => verbs_or(route_verbs(args), "GET"),
+        _ => return None,
+    };
+    
+    Some((verbs, route_path(args)))
+}
+
+/// Join a class-level prefix and a method-level path with a single separator
+fn join_route(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else if path.is_empty() {
+        prefix.to_string()
+    } else {
+        format!("{}/{}", prefix.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
 }
@@ -8,18 +8,41 @@
 //! - Property extraction with visibility modifiers
 //! - Abstract class detection
 //! - Type-only import detection
+//! - React function component and hook detection (JSX via the TSX grammar)
 
 use std::time::Instant;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 use super::types::*;
+use super::generics::declared_type_parameters;
 
-pub struct TypeScriptParser {
-    parser: Parser,
+/// Wrappers whose argument is a React component (`forwardRef(...)`, `memo(...)`)
+const REACT_COMPONENT_WRAPPERS: &[&str] = &["forwardRef", "React.forwardRef", "memo", "React.memo"];
+
+/// Type annotations that mark a variable as a React function component
+const REACT_COMPONENT_TYPES: &[&str] = &["FC", "React.FC", "FunctionComponent", "React.FunctionComponent", "VFC", "React.VFC"];
+
+/// Decorator-style marker added to React function components
+pub const REACT_COMPONENT_MARKER: &str = "@react:component";
+
+/// Receiver recorded on React hook calls (`useState`, `useEffect`, custom `useX`)
+pub const REACT_HOOK_RECEIVER: &str = "react-hook";
+
+/// Queries compiled against one grammar (plain TypeScript or TSX)
+struct Queries {
     function_query: Query,
     class_query: Query,
     import_query: Query,
     export_query: Query,
     call_query: Query,
+    enum_query: Query,
+}
+
+pub struct TypeScriptParser {
+    parser: Parser,
+    queries: Queries,
+    /// TSX grammar, used for JavaScript and `.tsx` sources so JSX parses
+    tsx_parser: Parser,
+    tsx_queries: Queries,
 }
 
 impl TypeScriptParser {
@@ -28,57 +51,97 @@ impl TypeScriptParser {
         let language = tree_sitter_typescript::LANGUAGE_TYPESCRIPT;
         parser.set_language(&language.into()).map_err(|e| format!("Failed to set language: {}", e))?;
         
-        let function_query = Query::new(&language.into(), r#"
+        let mut tsx_parser = Parser::new();
+        let tsx_language = tree_sitter_typescript::LANGUAGE_TSX;
+        tsx_parser.set_language(&tsx_language.into()).map_err(|e| format!("Failed to set language: {}", e))?;
+        
+        Ok(Self {
+            parser,
+            queries: Self::compile_queries(&language.into())?,
+            tsx_parser,
+            tsx_queries: Self::compile_queries(&tsx_language.into())?,
+        })
+    }
+    
+    fn compile_queries(language: &tree_sitter::Language) -> Result<Queries, String> {
+        let function_query = Query::new(language, r#"
             (function_declaration name: (identifier) @name parameters: (formal_parameters) @params return_type: (type_annotation)? @return_type) @function
+            (function_expression name: (identifier)? @name parameters: (formal_parameters) @params return_type: (type_annotation)? @return_type) @function_expr
             (method_definition name: (property_identifier) @name parameters: (formal_parameters) @params return_type: (type_annotation)? @return_type) @method
             (arrow_function parameters: [(formal_parameters) (identifier)] @params return_type: (type_annotation)? @return_type) @arrow
         "#).map_err(|e| format!("Failed to create function query: {}", e))?;
         
-        let class_query = Query::new(&language.into(), r#"
+        let class_query = Query::new(language, r#"
             (class_declaration name: (type_identifier) @name (class_heritage (extends_clause (identifier) @extends)? (implements_clause (type_identifier) @implements)*)?) @class
+            (abstract_class_declaration name: (type_identifier) @name (class_heritage (extends_clause (identifier) @extends)? (implements_clause (type_identifier) @implements)*)?) @class
+            (interface_declaration name: (type_identifier) @name) @class
         "#).map_err(|e| format!("Failed to create class query: {}", e))?;
         
-        let import_query = Query::new(&language.into(), r#"
+        let import_query = Query::new(language, r#"
             (import_statement (import_clause (identifier)? @default (named_imports (import_specifier (identifier) @named)*)? (namespace_import (identifier) @namespace)?)? source: (string) @source) @import
         "#).map_err(|e| format!("Failed to create import query: {}", e))?;
         
-        let export_query = Query::new(&language.into(), r#"
+        let export_query = Query::new(language, r#"
             (export_statement (export_clause (export_specifier name: (identifier) @name)*)? source: (string)? @source declaration: [(function_declaration name: (identifier) @decl_name) (class_declaration name: (type_identifier) @decl_name) (lexical_declaration (variable_declarator name: (identifier) @decl_name))]?) @export
+            (export_statement value: [(identifier) @decl_name (function_expression name: (identifier) @decl_name) (class name: (type_identifier) @decl_name)]) @export
         "#).map_err(|e| format!("Failed to create export query: {}", e))?;
         
-        let call_query = Query::new(&language.into(), r#"
+        let call_query = Query::new(language, r#"
             (call_expression function: [(identifier) @callee (member_expression object: (_) @receiver property: (property_identifier) @callee)] arguments: (arguments) @args) @call
             (new_expression constructor: [(identifier) @callee (member_expression object: (_) @receiver property: (property_identifier) @callee)] arguments: (arguments) @args) @new_call
+            (call_expression function: (import) @callee arguments: (arguments) @args) @call
         "#).map_err(|e| format!("Failed to create call query: {}", e))?;
         
-        Ok(Self { parser, function_query, class_query, import_query, export_query, call_query })
+        let enum_query = Query::new(language, r#"
+            (enum_declaration name: (identifier) @name body: (enum_body) @body) @enum
+            (type_alias_declaration name: (type_identifier) @name value: (union_type) @union) @enum
+        "#).map_err(|e| format!("Failed to create enum query: {}", e))?;
+        
+        Ok(Queries { function_query, class_query, import_query, export_query, call_query, enum_query })
     }
 
+    /// Parse TypeScript (`is_typescript`) or JavaScript source
+    ///
+    /// JavaScript is parsed with the TSX grammar so JSX is understood.
     pub fn parse(&mut self, source: &str, is_typescript: bool) -> ParseResult {
+        self.parse_with_grammar(source, is_typescript, !is_typescript)
+    }
+    
+    /// Parse TypeScript source containing JSX (`.tsx` files)
+    pub fn parse_tsx(&mut self, source: &str) -> ParseResult {
+        self.parse_with_grammar(source, true, true)
+    }
+
+    fn parse_with_grammar(&mut self, source: &str, is_typescript: bool, jsx: bool) -> ParseResult {
         let start = Instant::now();
-        let tree = match self.parser.parse(source, None) {
+        let parser = if jsx { &mut self.tsx_parser } else { &mut self.parser };
+        let tree = match parser.parse(source, None) {
             Some(t) => t,
             None => {
                 let mut result = ParseResult::new(if is_typescript { Language::TypeScript } else { Language::JavaScript });
-                result.errors.push(ParseError { message: "Failed to parse source".to_string(), range: Range::new(0, 0, 0, 0) });
+                result.errors.push(ParseError { message: "Failed to parse source".to_string(), range: Range::new(0, 0, 0, 0), snippet: None });
                 return result;
             }
         };
         let root = tree.root_node();
         let source_bytes = source.as_bytes();
         let mut result = ParseResult::with_tree(if is_typescript { Language::TypeScript } else { Language::JavaScript }, tree.clone());
-        self.extract_functions(&root, source_bytes, &mut result);
-        self.extract_classes(&root, source_bytes, &mut result);
-        self.extract_imports(&root, source_bytes, &mut result);
-        self.extract_exports(&root, source_bytes, &mut result);
-        self.extract_calls(&root, source_bytes, &mut result);
+        result.record_syntax_errors(&root, source_bytes);
+        let queries = if jsx { &self.tsx_queries } else { &self.queries };
+        self.extract_functions(queries, &root, source_bytes, &mut result);
+        self.extract_classes(queries, &root, source_bytes, &mut result);
+        result.attach_methods();
+        self.extract_enums(queries, &root, source_bytes, &mut result);
+        self.extract_imports(queries, &root, source_bytes, &mut result);
+        self.extract_exports(queries, &root, source_bytes, &mut result);
+        self.extract_calls(queries, &root, source_bytes, &mut result);
         result.parse_time_us = start.elapsed().as_micros() as u64;
         result
     }
 
-    fn extract_functions(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    fn extract_functions(&self, queries: &Queries, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
-        for m in cursor.matches(&self.function_query, *root, source) {
+        for m in cursor.matches(&queries.function_query, *root, source) {
             let mut name = String::new();
             let mut range = Range::new(0, 0, 0, 0);
             let mut is_async = false;
@@ -88,7 +151,7 @@ impl TypeScriptParser {
             let mut return_type: Option<String> = None;
             for capture in m.captures {
                 let node = capture.node;
-                let capture_name = self.function_query.capture_names()[capture.index as usize];
+                let capture_name = queries.function_query.capture_names()[capture.index as usize];
                 match capture_name {
                     "name" => name = node.utf8_text(source).unwrap_or("").to_string(),
                     "params" => params_node = Some(node),
@@ -96,7 +159,7 @@ impl TypeScriptParser {
                         let t = node.utf8_text(source).unwrap_or("").trim_start_matches(':').trim();
                         if !t.is_empty() { return_type = Some(t.to_string()); }
                     }
-                    "function" | "method" | "arrow" => {
+                    "function" | "function_expr" | "method" | "arrow" => {
                         range = node_range(&node);
                         function_node = Some(node);
                         let text = node.utf8_text(source).unwrap_or("");
@@ -106,16 +169,144 @@ impl TypeScriptParser {
                     _ => {}
                 }
             }
+            
+            // Arrow functions and function expressions take the name of the variable they're bound to
+            let binding = function_node
+                .filter(|n| n.kind() == "arrow_function" || n.kind() == "function_expression")
+                .and_then(|n| self.variable_binding(&n, source));
+            if let Some((declarator, _)) = binding {
+                if name.is_empty() {
+                    name = declarator.child_by_field_name("name")
+                        .filter(|n| n.kind() == "identifier")
+                        .and_then(|n| n.utf8_text(source).ok())
+                        .unwrap_or("")
+                        .to_string();
+                }
+            }
+            
+            // Inline callbacks (`app.get("/", (req, res) => ...)`) get a synthetic
+            // "<anon@line>" name, qualified by the function they are defined in
+            let mut qualified_name = None;
+            let inline_callback = name.is_empty()
+                && function_node.is_some_and(|n| n.parent().is_some_and(|p| p.kind() == "arguments"));
+            if inline_callback {
+                name = format!("<anon@{}>", range.start.line);
+                qualified_name = function_node
+                    .and_then(|n| self.enclosing_function_name(&n, source))
+                    .map(|outer| format!("{}:{}", outer, name));
+            }
+            
+            // Methods are qualified by their class and carry member modifiers
+            let mut visibility = Visibility::Public;
+            let mut is_static = false;
+            if let Some(n) = function_node.filter(|n| n.kind() == "method_definition") {
+                if let Some(class) = self.enclosing_class_name(&n, source) {
+                    qualified_name = Some(format!("{}.{}", class, name));
+                }
+                let mut cursor = n.walk();
+                for child in n.children(&mut cursor) {
+                    match child.kind() {
+                        "accessibility_modifier" => visibility = match child.utf8_text(source).unwrap_or("") { "private" => Visibility::Private, "protected" => Visibility::Protected, _ => Visibility::Public },
+                        "static" => is_static = true,
+                        _ => {}
+                    }
+                }
+            }
+            
             if !name.is_empty() {
-                let decorators = function_node.map(|n| self.extract_decorators(&n, source)).unwrap_or_default();
+                let mut decorators = function_node.map(|n| self.extract_decorators(&n, source)).unwrap_or_default();
+                if let Some(n) = function_node {
+                    if self.is_react_component(&name, &n, binding, source) {
+                        decorators.push(REACT_COMPONENT_MARKER.to_string());
+                    }
+                }
                 let doc_comment = function_node.and_then(|n| self.extract_jsdoc(&n, source));
                 let parameters = params_node.map(|n| self.extract_parameters(&n, source)).unwrap_or_default();
-                let is_exported = function_node.map(|n| self.check_visibility(&n, source)).unwrap_or(true);
-                result.functions.push(FunctionInfo { name, qualified_name: None, parameters, return_type, is_exported, is_async, is_generator, range, decorators, doc_comment });
+                let is_exported = !inline_callback && function_node.map(|n| self.check_visibility(&n, source)).unwrap_or(true);
+                let type_parameters = function_node.map(|n| declared_type_parameters(&n, source)).unwrap_or_default();
+                result.functions.push(FunctionInfo { name, qualified_name, parameters, return_type, is_exported, is_async, is_generator, visibility, is_static, type_parameters, range, decorators, doc_comment });
+            }
+        }
+    }
+
+    /// Variable declarator a function expression is assigned to
+    ///
+    /// Looks through component wrappers (`forwardRef`, `memo`) and parentheses.
+    /// The flag reports whether such a wrapper was crossed.
+    fn variable_binding<'a>(&self, node: &Node<'a>, source: &[u8]) -> Option<(Node<'a>, bool)> {
+        let mut current = *node;
+        let mut wrapped = false;
+        loop {
+            let parent = current.parent()?;
+            match parent.kind() {
+                "variable_declarator" => return Some((parent, wrapped)),
+                "parenthesized_expression" => current = parent,
+                "arguments" => {
+                    let call = parent.parent()?;
+                    let callee = call.child_by_field_name("function")?.utf8_text(source).unwrap_or("");
+                    if !REACT_COMPONENT_WRAPPERS.contains(&callee) { return None; }
+                    wrapped = true;
+                    current = call;
+                }
+                _ => return None,
             }
         }
     }
 
+    /// Name of the nearest named function enclosing a node ("Class.method" for methods)
+    fn enclosing_function_name(&self, node: &Node, source: &[u8]) -> Option<String> {
+        let text = |n: Node| n.utf8_text(source).ok().map(str::to_string);
+        let mut current = node.parent();
+        while let Some(n) = current {
+            let name = match n.kind() {
+                "function_declaration" | "generator_function_declaration" => n.child_by_field_name("name").and_then(text),
+                "method_definition" => n.child_by_field_name("name").and_then(text).map(|method| {
+                    match self.enclosing_class_name(&n, source) {
+                        Some(class) => format!("{}.{}", class, method),
+                        None => method,
+                    }
+                }),
+                "arrow_function" | "function_expression" => n.child_by_field_name("name").and_then(text)
+                    .or_else(|| self.variable_binding(&n, source)
+                        .and_then(|(declarator, _)| declarator.child_by_field_name("name"))
+                        .filter(|name| name.kind() == "identifier")
+                        .and_then(text)),
+                _ => None,
+            };
+            if name.is_some() {
+                return name;
+            }
+            current = n.parent();
+        }
+        None
+    }
+
+    /// Name of the class declaring a method
+    fn enclosing_class_name(&self, node: &Node, source: &[u8]) -> Option<String> {
+        std::iter::successors(node.parent(), |p| p.parent())
+            .find(|p| p.kind() == "class_declaration" || p.kind() == "class")
+            .and_then(|c| c.child_by_field_name("name"))
+            .and_then(|n| n.utf8_text(source).ok())
+            .map(str::to_string)
+    }
+
+    /// React function component: a PascalCase function that returns JSX, is typed
+    /// as `React.FC`, or is wrapped in `forwardRef`/`memo`
+    fn is_react_component(&self, name: &str, function_node: &Node, binding: Option<(Node, bool)>, source: &[u8]) -> bool {
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) || function_node.kind() == "method_definition" {
+            return false;
+        }
+        if let Some((declarator, wrapped)) = binding {
+            if wrapped { return true; }
+            let annotated = declarator.child_by_field_name("type")
+                .and_then(|t| t.utf8_text(source).ok())
+                .map(|t| t.trim_start_matches(':').trim().split('<').next().unwrap_or("").trim())
+                .is_some_and(|t| REACT_COMPONENT_TYPES.contains(&t));
+            if annotated { return true; }
+        }
+        contains_jsx(function_node)
+    }
+
     fn extract_parameters(&self, params_node: &Node, source: &[u8]) -> Vec<ParameterInfo> {
         let mut params = Vec::new();
         let mut cursor = params_node.walk();
@@ -226,9 +417,74 @@ impl TypeScriptParser {
         None
     }
 
-    fn extract_classes(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    /// `enum` declarations and type aliases whose union members are all literals
+    /// (`type Status = 'active' | 'inactive'`)
+    fn extract_enums(&self, queries: &Queries, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
-        for m in cursor.matches(&self.class_query, *root, source) {
+        for m in cursor.matches(&queries.enum_query, *root, source) {
+            let mut name = String::new();
+            let mut variants = Some(Vec::new());
+            let mut enum_node: Option<Node> = None;
+            for capture in m.captures {
+                let node = capture.node;
+                match queries.enum_query.capture_names()[capture.index as usize] {
+                    "name" => name = node.utf8_text(source).unwrap_or("").to_string(),
+                    "body" => variants = Some(self.extract_enum_members(&node, source)),
+                    "union" => {
+                        let mut literals = Vec::new();
+                        variants = self.collect_literal_union(&node, source, &mut literals).then_some(literals);
+                    }
+                    "enum" => enum_node = Some(node),
+                    _ => {}
+                }
+            }
+            if let (false, Some(variants), Some(node)) = (name.is_empty(), variants, enum_node) {
+                let is_exported = node.parent().is_some_and(|p| p.kind() == "export_statement");
+                result.enums.push(EnumInfo { name, variants, is_exported, range: node_range(&node), decorators: Vec::new() });
+            }
+        }
+    }
+
+    fn extract_enum_members(&self, body: &Node, source: &[u8]) -> Vec<EnumVariant> {
+        let mut cursor = body.walk();
+        body.named_children(&mut cursor)
+            .filter(|member| member.kind() != "comment")
+            .filter_map(|member| {
+                let (name, value) = if member.kind() == "enum_assignment" {
+                    (member.child_by_field_name("name")?, member.child_by_field_name("value"))
+                } else {
+                    (member, None)
+                };
+                let name = name.utf8_text(source).ok()?.trim_matches(|c| c == '"' || c == '\'').to_string();
+                let value = value.and_then(|v| v.utf8_text(source).ok()).map(|v| v.to_string());
+                Some(EnumVariant { name, value, range: node_range(&member) })
+            })
+            .collect()
+    }
+
+    /// Flatten a union into its literal members; false if any member is not a literal type
+    fn collect_literal_union(&self, union: &Node, source: &[u8], variants: &mut Vec<EnumVariant>) -> bool {
+        let mut cursor = union.walk();
+        for member in union.named_children(&mut cursor) {
+            match member.kind() {
+                "union_type" => if !self.collect_literal_union(&member, source, variants) { return false; },
+                "literal_type" => {
+                    let text = member.utf8_text(source).unwrap_or("");
+                    variants.push(EnumVariant {
+                        name: text.trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string(),
+                        value: Some(text.to_string()),
+                        range: node_range(&member),
+                    });
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn extract_classes(&self, queries: &Queries, root: &Node, source: &[u8], result: &mut ParseResult) {
+        let mut cursor = QueryCursor::new();
+        for m in cursor.matches(&queries.class_query, *root, source) {
             let mut name = String::new();
             let mut extends = None;
             let mut implements = Vec::new();
@@ -236,7 +492,7 @@ impl TypeScriptParser {
             let mut class_node: Option<Node> = None;
             for capture in m.captures {
                 let node = capture.node;
-                let capture_name = self.class_query.capture_names()[capture.index as usize];
+                let capture_name = queries.class_query.capture_names()[capture.index as usize];
                 match capture_name {
                     "name" => name = node.utf8_text(source).unwrap_or("").to_string(),
                     "extends" => extends = Some(node.utf8_text(source).unwrap_or("").to_string()),
@@ -247,9 +503,10 @@ impl TypeScriptParser {
             }
             if !name.is_empty() {
                 let decorators = class_node.map(|n| self.extract_decorators(&n, source)).unwrap_or_default();
-                let is_abstract = class_node.map(|n| n.utf8_text(source).unwrap_or("").trim_start().starts_with("abstract ")).unwrap_or(false);
+                let is_abstract = class_node.is_some_and(|n| matches!(n.kind(), "abstract_class_declaration" | "interface_declaration"));
                 let properties = class_node.map(|n| self.extract_class_properties(&n, source)).unwrap_or_default();
-                result.classes.push(ClassInfo { name, extends, implements, is_exported: false, is_abstract, methods: Vec::new(), properties, range, decorators });
+                let type_parameters = class_node.map(|n| declared_type_parameters(&n, source)).unwrap_or_default();
+                result.classes.push(ClassInfo { name, extends, implements, is_exported: false, is_abstract, methods: Vec::new(), properties, range, decorators, namespace: None, is_partial: false, type_parameters });
             }
         }
     }
@@ -304,7 +561,7 @@ impl TypeScriptParser {
         }
         if name.is_empty() { return None; }
         let tags = if !decorators.is_empty() { Some(decorators.iter().map(|d| StructTag { key: "decorator".to_string(), value: d.clone() }).collect()) } else { None };
-        Some(PropertyInfo { name, type_annotation, is_static, is_readonly, visibility, tags })
+        Some(PropertyInfo { name, type_annotation, is_static, is_readonly, visibility, tags, value: None, is_computed: false, decorators: Vec::new() })
     }
 
     fn extract_ctor_properties(&self, ctor_node: &Node, source: &[u8]) -> Vec<PropertyInfo> {
@@ -353,12 +610,12 @@ impl TypeScriptParser {
         let visibility = visibility?;
         if name.is_empty() { return None; }
         let tags = if !decorators.is_empty() { Some(decorators.iter().map(|d| StructTag { key: "decorator".to_string(), value: d.clone() }).collect()) } else { None };
-        Some(PropertyInfo { name, type_annotation, is_static: false, is_readonly, visibility, tags })
+        Some(PropertyInfo { name, type_annotation, is_static: false, is_readonly, visibility, tags, value: None, is_computed: false, decorators: Vec::new() })
     }
 
-    fn extract_imports(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    fn extract_imports(&self, queries: &Queries, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
-        for m in cursor.matches(&self.import_query, *root, source) {
+        for m in cursor.matches(&queries.import_query, *root, source) {
             let mut import_source = String::new();
             let mut named = Vec::new();
             let mut default = None;
@@ -368,7 +625,7 @@ impl TypeScriptParser {
             let mut import_node: Option<Node> = None;
             for capture in m.captures {
                 let node = capture.node;
-                let capture_name = self.import_query.capture_names()[capture.index as usize];
+                let capture_name = queries.import_query.capture_names()[capture.index as usize];
                 match capture_name {
                     "source" => import_source = node.utf8_text(source).unwrap_or("").trim_matches(|c| c == '"' || c == '\'').to_string(),
                     "named" => named.push(node.utf8_text(source).unwrap_or("").to_string()),
@@ -378,53 +635,72 @@ impl TypeScriptParser {
                     _ => {}
                 }
             }
-            if let Some(n) = import_node { is_type_only = n.utf8_text(source).unwrap_or("").contains("import type "); }
-            if !import_source.is_empty() { result.imports.push(ImportInfo { source: import_source, named, default, namespace, is_type_only, range }); }
+            if let Some(n) = import_node {
+                is_type_only = n.utf8_text(source).unwrap_or("").contains("import type ");
+                // The quantified @named capture only keeps one specifier per match
+                named = import_specifiers(&n, source);
+            }
+            if !import_source.is_empty() { result.imports.push(ImportInfo { source: import_source, named, default, namespace, is_type_only, relative_level: 0, range }); }
         }
     }
 
-    fn extract_exports(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    fn extract_exports(&self, queries: &Queries, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
-        for m in cursor.matches(&self.export_query, *root, source) {
-            let mut names = Vec::new();
+        for m in cursor.matches(&queries.export_query, *root, source) {
+            let mut names: Vec<(String, Option<String>)> = Vec::new();
             let mut from_source = None;
             let mut range = Range::new(0, 0, 0, 0);
             let mut is_type_only = false;
+            let mut is_default = false;
             let mut export_node: Option<Node> = None;
             for capture in m.captures {
                 let node = capture.node;
-                let capture_name = self.export_query.capture_names()[capture.index as usize];
+                let capture_name = queries.export_query.capture_names()[capture.index as usize];
                 match capture_name {
-                    "name" | "decl_name" => names.push(node.utf8_text(source).unwrap_or("").to_string()),
+                    "name" | "decl_name" => names.push((node.utf8_text(source).unwrap_or("").to_string(), None)),
                     "source" => from_source = Some(node.utf8_text(source).unwrap_or("").trim_matches(|c| c == '"' || c == '\'').to_string()),
                     "export" => { range = node_range(&node); export_node = Some(node); }
                     _ => {}
                 }
             }
-            if let Some(n) = export_node { is_type_only = n.utf8_text(source).unwrap_or("").contains("export type "); }
-            for name in names { if !name.is_empty() { result.exports.push(ExportInfo { name, original_name: None, from_source: from_source.clone(), is_type_only, is_default: false, range }); } }
+            if let Some(n) = export_node {
+                is_type_only = n.utf8_text(source).unwrap_or("").contains("export type ");
+                is_default = (0..n.child_count()).filter_map(|i| n.child(i)).any(|c| c.kind() == "default");
+                // The quantified @name capture only keeps one specifier per match
+                let clause = export_clause_names(&n, source);
+                if !clause.is_empty() { names = clause; }
+            }
+            for (name, original_name) in names { if !name.is_empty() { result.exports.push(ExportInfo { name, original_name, from_source: from_source.clone(), is_type_only, is_default, range }); } }
         }
     }
 
-    fn extract_calls(&self, root: &Node, source: &[u8], result: &mut ParseResult) {
+    fn extract_calls(&self, queries: &Queries, root: &Node, source: &[u8], result: &mut ParseResult) {
         let mut cursor = QueryCursor::new();
-        for m in cursor.matches(&self.call_query, *root, source) {
+        for m in cursor.matches(&queries.call_query, *root, source) {
             let mut callee = String::new();
             let mut receiver = None;
             let mut arg_count = 0;
             let mut range = Range::new(0, 0, 0, 0);
+            let mut string_arg = None;
             for capture in m.captures {
                 let node = capture.node;
-                let capture_name = self.call_query.capture_names()[capture.index as usize];
+                let capture_name = queries.call_query.capture_names()[capture.index as usize];
                 match capture_name {
                     "callee" => callee = node.utf8_text(source).unwrap_or("").to_string(),
                     "receiver" => receiver = Some(node.utf8_text(source).unwrap_or("").to_string()),
-                    "args" => arg_count = node.named_child_count(),
+                    "args" => {
+                        arg_count = node.named_child_count();
+                        string_arg = first_string_arg(&node, source);
+                    }
                     "call" | "new_call" => range = node_range(&node),
                     _ => {}
                 }
             }
-            if !callee.is_empty() { result.calls.push(CallSite { callee, receiver, arg_count, range }); }
+            // Hooks are tagged so React usage can be audited without resolving them
+            if is_react_hook(&callee) && receiver.as_deref().is_none_or(|r| r == "React") {
+                receiver = Some(REACT_HOOK_RECEIVER.to_string());
+            }
+            if !callee.is_empty() { result.calls.push(CallSite { callee, receiver, arg_count, range, string_arg }); }
         }
     }
 }
@@ -433,6 +709,95 @@ impl Default for TypeScriptParser {
     fn default() -> Self { Self::new().expect("Failed to create TypeScript parser") }
 }
 
+/// Hook naming convention: `use` or `useX`
+fn is_react_hook(name: &str) -> bool {
+    name == "use" || name.strip_prefix("use").is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+/// Whether any JSX element appears below this node
+fn contains_jsx(node: &Node) -> bool {
+    let mut cursor = node.walk();
+    let mut stack = vec![*node];
+    while let Some(current) = stack.pop() {
+        if matches!(current.kind(), "jsx_element" | "jsx_self_closing_element") {
+            return true;
+        }
+        stack.extend(current.children(&mut cursor));
+    }
+    false
+}
+
+/// Contents of the first argument when it is a string or template literal
+/// (substitutions kept as written: `user:${id}`), or of the `TableName` of
+/// an object-literal first argument (DynamoDB params)
+fn first_string_arg(args: &Node, source: &[u8]) -> Option<String> {
+    let literal = |node: Node| -> Option<String> {
+        if node.kind() != "string" && node.kind() != "template_string" {
+            return None;
+        }
+        let text = node.utf8_text(source).ok()?;
+        text.get(1..text.len().checked_sub(1)?).map(str::to_string)
+    };
+    let first = args.named_child(0)?;
+    if first.kind() != "object" {
+        return literal(first);
+    }
+    let mut cursor = first.walk();
+    let table_name = first.named_children(&mut cursor).find(|pair| {
+        pair.kind() == "pair"
+            && pair.child_by_field_name("key")
+                .and_then(|key| key.utf8_text(source).ok())
+                .is_some_and(|key| key.trim_matches(['\'', '"']) == "TableName")
+    })?;
+    literal(table_name.child_by_field_name("value")?)
+}
+
+/// Names exported by `export { a, b as c }` as (exported, original if renamed):
+/// `(a, None)`, `(c, Some(b))`. Star re-exports export `*`, or with
+/// `export * as ns` the namespace: `(ns, Some(*))`.
+fn export_clause_names(export: &Node, source: &[u8]) -> Vec<(String, Option<String>)> {
+    let text = |node: Node| node.utf8_text(source).unwrap_or("").to_string();
+    let mut cursor = export.walk();
+    let children: Vec<Node> = export.children(&mut cursor).collect();
+    if let Some(clause) = children.iter().find(|n| n.kind() == "export_clause") {
+        let mut cursor = clause.walk();
+        return clause.named_children(&mut cursor)
+            .filter(|n| n.kind() == "export_specifier")
+            .filter_map(|spec| {
+                let name = text(spec.child_by_field_name("name")?);
+                Some(match spec.child_by_field_name("alias") {
+                    Some(alias) => (text(alias), Some(name)),
+                    None => (name, None),
+                })
+            })
+            .collect();
+    }
+    if let Some(namespace) = children.iter().find(|n| n.kind() == "namespace_export") {
+        return namespace.named_child(0).map(|ns| vec![(text(ns), Some("*".to_string()))]).unwrap_or_default();
+    }
+    if children.iter().any(|n| n.kind() == "*") {
+        return vec![("*".to_string(), None)];
+    }
+    Vec::new()
+}
+
+/// Imported names of `import { a, b as c } from '...'` (`a`, `b`)
+fn import_specifiers(import: &Node, source: &[u8]) -> Vec<String> {
+    let mut cursor = import.walk();
+    let Some(clause) = import.named_children(&mut cursor).find(|n| n.kind() == "import_clause") else {
+        return Vec::new();
+    };
+    let mut cursor = clause.walk();
+    let Some(named_imports) = clause.named_children(&mut cursor).find(|n| n.kind() == "named_imports") else {
+        return Vec::new();
+    };
+    let mut cursor = named_imports.walk();
+    named_imports.named_children(&mut cursor)
+        .filter(|n| n.kind() == "import_specifier")
+        .filter_map(|n| n.child_by_field_name("name")?.utf8_text(source).ok().map(str::to_string))
+        .collect()
+}
+
 fn node_range(node: &Node) -> Range {
     Range { start: Position { line: node.start_position().row as u32, column: node.start_position().column as u32 }, end: Position { line: node.end_position().row as u32, column: node.end_position().column as u32 } }
 }
@@ -511,12 +876,10 @@ mod tests {
     #[test]
     fn test_parse_abstract_class() {
         let mut parser = TypeScriptParser::new().unwrap();
-        // Note: tree-sitter-typescript may not parse abstract classes the same way
-        // This test verifies basic class parsing works
-        let source = "class BaseRepository { findById(id: string): any { return null; } }";
+        let source = "abstract class BaseRepository { abstract findById(id: string): any; }\ninterface Clock { now(): Date }\nclass UserRepository extends BaseRepository { findById(id: string) { return null; } }";
         let result = parser.parse(source, true);
-        assert!(!result.classes.is_empty(), "Expected at least one class");
-        assert_eq!(result.classes[0].name, "BaseRepository");
+        let classes: Vec<(&str, bool)> = result.classes.iter().map(|c| (c.name.as_str(), c.is_abstract)).collect();
+        assert_eq!(classes, vec![("BaseRepository", true), ("Clock", true), ("UserRepository", false)]);
     }
 
     #[test]
@@ -527,4 +890,111 @@ mod tests {
         let type_import = result.imports.iter().find(|i| i.source == "./types").unwrap();
         assert!(type_import.is_type_only);
     }
+
+    #[test]
+    fn test_parse_reexports_and_dynamic_imports() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let source = "export * from './format';\nexport * as money from './money';\nexport { a, b as c } from './x';\nconst m = await import(`./locales/${lang}`);\n";
+        let result = parser.parse(source, true);
+        let exports: Vec<(&str, Option<&str>, Option<&str>)> = result.exports.iter()
+            .map(|e| (e.name.as_str(), e.original_name.as_deref(), e.from_source.as_deref()))
+            .collect();
+        assert_eq!(exports, vec![
+            ("*", None, Some("./format")),
+            ("money", Some("*"), Some("./money")),
+            ("a", None, Some("./x")),
+            ("c", Some("b"), Some("./x")),
+        ]);
+        let import = result.calls.iter().find(|c| c.callee == "import").unwrap();
+        assert_eq!(import.string_arg.as_deref(), Some("./locales/${lang}"));
+    }
+
+    #[test]
+    fn test_parse_named_imports() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let result = parser.parse("import Api, { format, parse as parseDate } from '../core/util';", true);
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.imports[0].named, vec!["format", "parse"]);
+        assert_eq!(result.imports[0].default.as_deref(), Some("Api"));
+    }
+
+    #[test]
+    fn test_react_function_components() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let source = r#"
+            export function UserCard({ user }: Props) {
+                const [open, setOpen] = useState(false);
+                useEffect(() => { track(user.id); }, [user]);
+                return <div onClick={() => setOpen(!open)}>{user.name}</div>;
+            }
+            function formatName(user: User) { return user.name; }
+        "#;
+        let result = parser.parse_tsx(source);
+        let card = result.functions.iter().find(|f| f.name == "UserCard").unwrap();
+        assert!(card.decorators.contains(&REACT_COMPONENT_MARKER.to_string()));
+        let helper = result.functions.iter().find(|f| f.name == "formatName").unwrap();
+        assert!(helper.decorators.is_empty());
+        
+        let hooks: Vec<&str> = result.calls.iter()
+            .filter(|c| c.receiver.as_deref() == Some(REACT_HOOK_RECEIVER))
+            .map(|c| c.callee.as_str())
+            .collect();
+        assert_eq!(hooks, vec!["useState", "useEffect"]);
+    }
+
+    #[test]
+    fn test_react_arrow_components() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let source = r#"
+            const Badge = ({ label }) => <span>{label}</span>;
+            const Panel: React.FC<PanelProps> = (props) => null;
+            const useAuth = () => React.useContext(AuthContext);
+        "#;
+        let result = parser.parse(source, false);
+        let is_component = |name: &str| result.functions.iter()
+            .find(|f| f.name == name)
+            .map(|f| f.decorators.contains(&REACT_COMPONENT_MARKER.to_string()))
+            .unwrap();
+        assert!(is_component("Badge"));
+        assert!(is_component("Panel"));
+        assert!(!is_component("useAuth"));
+        
+        let use_context = result.calls.iter().find(|c| c.callee == "useContext").unwrap();
+        assert_eq!(use_context.receiver.as_deref(), Some(REACT_HOOK_RECEIVER));
+    }
+
+    #[test]
+    fn test_react_forward_ref_components() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let source = r#"
+            export const Input = forwardRef<HTMLInputElement, Props>((props, ref) => <input ref={ref} {...props} />);
+            export const Select = React.memo(React.forwardRef(function Select(props, ref) { return null; }));
+        "#;
+        let result = parser.parse_tsx(source);
+        for name in ["Input", "Select"] {
+            let func = result.functions.iter().find(|f| f.name == name).unwrap();
+            assert!(func.decorators.contains(&REACT_COMPONENT_MARKER.to_string()), "{} should be a component", name);
+        }
+    }
+
+    #[test]
+    fn test_inline_callbacks_get_synthetic_names() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let source = r#"
+const handler = async (req) => { return save(req.body); };
+export function register(app) {
+  app.get("/users", (req, res) => res.json(list()));
+}
+class Jobs {
+  start() { queue.on("done", function () { notify(); }); }
+}
+"#;
+        let result = parser.parse(source, true);
+        let func = |name: &str| result.functions.iter().find(|f| f.name == name).unwrap();
+        
+        assert!(func("handler").is_async);
+        assert_eq!(func("<anon@3>").qualified_name.as_deref(), Some("register:<anon@3>"));
+        assert!(!func("<anon@3>").is_exported);
+        assert_eq!(func("<anon@6>").qualified_name.as_deref(), Some("Jobs.start:<anon@6>"));
+    }
 }
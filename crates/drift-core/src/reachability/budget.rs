@@ -0,0 +1,36 @@
+//! Traversal Budget
+//!
+//! Bounds the work a single reachability query may do, so a dense graph
+//! yields a partial result instead of pinning a CPU.
+
+use std::time::{Duration, Instant};
+
+use super::types::{ReachabilityOptions, TruncationReason};
+
+/// `ReachabilityOptions::max_functions` and `timeout_ms`, with the clock
+/// started
+pub(crate) struct TraversalBudget {
+    max_functions: Option<u32>,
+    deadline: Option<Instant>,
+}
+
+impl TraversalBudget {
+    pub(crate) fn new(options: &ReachabilityOptions) -> Self {
+        Self {
+            max_functions: options.max_functions,
+            deadline: options.timeout_ms.map(|ms| Instant::now() + Duration::from_millis(ms as u64)),
+        }
+    }
+
+    /// Why the traversal must stop instead of visiting another function,
+    /// having visited `visited` already
+    pub(crate) fn exceeded(&self, visited: usize) -> Option<TruncationReason> {
+        if self.max_functions.is_some_and(|max| visited >= max as usize) {
+            Some(TruncationReason::MaxFunctions)
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(TruncationReason::Timeout)
+        } else {
+            None
+        }
+    }
+}
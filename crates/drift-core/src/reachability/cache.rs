@@ -0,0 +1,103 @@
+//! Reachability Result Cache
+//!
+//! LRU cache of `SqliteReachabilityEngine` results, keyed by origin function,
+//! options and the call graph's content version. Every write to the graph
+//! gives it a new version, so stale results are never served - they just age
+//! out. Editors that re-query on every hover reopen the engine each time, so
+//! `shared` keeps one cache per database for the whole process.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+
+use super::types::{ReachabilityOptions, ReachabilityResult};
+
+/// Results kept per database by `shared` caches
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+static SHARED: Lazy<Mutex<HashMap<PathBuf, Arc<ReachabilityCache>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// (function ID, options, content version)
+type CacheKey = (String, ReachabilityOptions, String);
+
+/// Bounded cache of reachability results
+pub struct ReachabilityCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    results: FxHashMap<CacheKey, (ReachabilityResult, u64)>,
+    /// Incremented per lookup; each entry keeps the tick it was last used at
+    tick: u64,
+}
+
+impl ReachabilityCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// The process-wide cache for the database at `db_path`
+    pub fn shared(db_path: &Path) -> Arc<ReachabilityCache> {
+        SHARED
+            .lock()
+            .unwrap()
+            .entry(db_path.to_path_buf())
+            .or_insert_with(|| Arc::new(ReachabilityCache::new(DEFAULT_CACHE_CAPACITY)))
+            .clone()
+    }
+
+    /// Empty the process-wide cache for the database at `db_path`, returning
+    /// how many results it held
+    pub fn clear_shared(db_path: &Path) -> usize {
+        let cache = SHARED.lock().unwrap().get(db_path).cloned();
+        cache.map_or(0, |cache| cache.clear())
+    }
+
+    pub(crate) fn get(&self, function_id: &str, options: &ReachabilityOptions, version: &str) -> Option<ReachabilityResult> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.tick += 1;
+        let tick = entries.tick;
+        let key = (function_id.to_string(), options.clone(), version.to_string());
+        let (result, used) = entries.results.get_mut(&key)?;
+        *used = tick;
+        Some(result.clone())
+    }
+
+    pub(crate) fn insert(&self, function_id: &str, options: &ReachabilityOptions, version: &str, result: ReachabilityResult) {
+        let key = (function_id.to_string(), options.clone(), version.to_string());
+        let mut entries = self.entries.lock().unwrap();
+        entries.tick += 1;
+        let tick = entries.tick;
+        if !entries.results.contains_key(&key) && entries.results.len() >= self.capacity {
+            let oldest = entries.results.iter().min_by_key(|(_, (_, used))| *used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.results.remove(&oldest);
+            }
+        }
+        entries.results.insert(key, (result, tick));
+    }
+
+    /// Drop every result, returning how many there were
+    pub fn clear(&self) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let count = entries.results.len();
+        entries.results.clear();
+        count
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -6,9 +6,14 @@
 //! - Reference-counted paths to avoid copies
 
 use std::collections::{HashSet, VecDeque};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use super::types::*;
+use super::budget::TraversalBudget;
+use super::inverse::InverseCollector;
+use super::relations::with_eager_relations;
+use super::paths::{call_path_confidence, expand_paths, function_reachability, score_paths, DEFAULT_MAX_PATHS};
+use crate::call_graph::method_name;
 
 /// Sensitive field patterns for classification
 const CREDENTIAL_PATTERNS: &[&str] = &[
@@ -27,12 +32,21 @@ const PII_PATTERNS: &[&str] = &[
 /// Reachability Analysis Engine
 pub struct ReachabilityEngine {
     graph: CallGraph,
+    /// Function IDs by bare method name, for following unresolved calls
+    by_name: FxHashMap<String, Vec<String>>,
 }
 
 impl ReachabilityEngine {
     /// Create a new reachability engine
     pub fn new(graph: CallGraph) -> Self {
-        Self { graph }
+        let mut by_name: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        for func in graph.functions.values() {
+            by_name.entry(method_name(&func.name).to_string()).or_default().push(func.id.clone());
+        }
+        for ids in by_name.values_mut() {
+            ids.sort();
+        }
+        Self { graph, by_name }
     }
     
     /// Get all data reachable from a specific code location
@@ -76,6 +90,13 @@ impl ReachabilityEngine {
         let max_depth = options.max_depth.unwrap_or(u32::MAX);
         let mut visited: FxHashSet<String> = FxHashSet::default();
         let mut reachable_access: Vec<ReachableDataAccess> = Vec::new();
+        // Calls and path nodes seen, for enumerating more than one path per access
+        let max_paths = options.max_paths_per_access.unwrap_or(1).max(1) as usize;
+        let mut edges: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        let mut nodes: FxHashMap<String, CallPathNode> = FxHashMap::default();
+        let budget = TraversalBudget::new(options);
+        let mut truncation: Option<TruncationReason> = None;
+        let mut unresolved_edges: u32 = 0;
         
         // BFS queue: (function_id, path, depth)
         let mut queue: VecDeque<(String, Vec<CallPathNode>, u32)> = VecDeque::new();
@@ -87,6 +108,10 @@ impl ReachabilityEngine {
             if visited.contains(&func_id) || depth > max_depth {
                 continue;
             }
+            if let Some(reason) = budget.exceeded(visited.len()) {
+                truncation = Some(reason);
+                break;
+            }
             visited.insert(func_id.clone());
             
             let current_func = match self.graph.functions.get(&func_id) {
@@ -102,28 +127,44 @@ impl ReachabilityEngine {
                 file: current_func.file.clone(),
                 line: current_func.start_line,
             });
+            if max_paths > 1 {
+                nodes.insert(func_id.clone(), current_path.last().unwrap().clone());
+            }
             
             // Collect data access from this function
             for access in &current_func.data_access {
-                // Filter by tables if specified
-                if !options.tables.is_empty() && !options.tables.contains(&access.table) {
-                    continue;
+                for (access, loaded_via) in with_eager_relations(access.clone(), &options.eager_relations) {
+                    // Filter by tables and operations if specified
+                    if !options.includes(&access) {
+                        continue;
+                    }
+                    
+                    reachable_access.push(ReachableDataAccess {
+                        access,
+                        path: current_path.clone(),
+                        paths: vec![current_path.clone()],
+                        depth,
+                        path_confidence: 0.0,
+                        crosses_async: false,
+                        loaded_via,
+                    });
                 }
-                
-                reachable_access.push(ReachableDataAccess {
-                    access: access.clone(),
-                    path: current_path.clone(),
-                    depth,
-                });
             }
             
             // Follow calls to other functions
             for call in &current_func.calls {
-                if !call.resolved && !options.include_unresolved {
-                    continue;
-                }
+                let candidates = if call.resolved {
+                    &call.resolved_candidates[..]
+                } else {
+                    let followed = options.unresolved_mode.select(self.unresolved_candidates(call));
+                    unresolved_edges += followed.len() as u32;
+                    followed
+                };
                 
-                for candidate_id in &call.resolved_candidates {
+                for candidate_id in candidates {
+                    if max_paths > 1 {
+                        edges.entry(func_id.clone()).or_default().push(candidate_id.clone());
+                    }
                     if !visited.contains(candidate_id) {
                         queue.push_back((
                             candidate_id.clone(),
@@ -135,8 +176,13 @@ impl ReachabilityEngine {
             }
         }
         
+        score_paths(&mut reachable_access, options.min_confidence, |caller, callee| self.call_confidence(caller, callee));
+        if max_paths > 1 {
+            expand_paths(&mut reachable_access, &edges, &nodes, function_id, max_depth, max_paths);
+        }
+        
         // Build result
-        self.build_result(
+        let mut result = self.build_result(
             CodeLocation {
                 file: func.file.clone(),
                 line: func.start_line,
@@ -146,7 +192,67 @@ impl ReachabilityEngine {
             reachable_access,
             options.sensitive_only,
             visited.len() as u32,
-        )
+        );
+        result.truncated = truncation.is_some();
+        result.truncation_reason = truncation;
+        result.unresolved_edges_traversed = unresolved_edges;
+        result
+    }
+    
+    /// Functions an unresolved call may target: the candidates it was
+    /// stored with, else every function with the callee's method name
+    fn unresolved_candidates<'a>(&'a self, call: &'a CallSite) -> &'a [String] {
+        if !call.resolved_candidates.is_empty() {
+            return &call.resolved_candidates;
+        }
+        self.by_name.get(method_name(&call.callee_name)).map_or(&[], |ids| &ids[..])
+    }
+    
+    /// Confidence of the most certain resolved call from `caller_id` to
+    /// `callee_id`, or `UNRESOLVED_CALL_CONFIDENCE` when only an unresolved
+    /// call leads there
+    fn call_confidence(&self, caller_id: &str, callee_id: &str) -> f64 {
+        self.graph.functions
+            .get(caller_id)
+            .into_iter()
+            .flat_map(|func| &func.calls)
+            .filter(|call| call.resolved && call.resolved_candidates.iter().any(|id| id == callee_id))
+            .map(|call| call.confidence as f64)
+            .reduce(f64::max)
+            .unwrap_or(UNRESOLVED_CALL_CONFIDENCE)
+    }
+    
+    /// Whether `to_id` can be reached from `from_id` through calls, with the
+    /// shortest path and how many distinct paths there are (up to a limit)
+    pub fn find_paths(&self, from_id: &str, to_id: &str, options: &FunctionPathOptions) -> FunctionReachability {
+        let max_depth = options.max_depth.unwrap_or(u32::MAX);
+        let mut edges: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        let mut nodes: FxHashMap<String, CallPathNode> = FxHashMap::default();
+        
+        let mut queue: VecDeque<(String, u32)> = VecDeque::from([(from_id.to_string(), 0)]);
+        while let Some((func_id, depth)) = queue.pop_front() {
+            if nodes.contains_key(&func_id) || depth > max_depth {
+                continue;
+            }
+            let Some(func) = self.graph.functions.get(&func_id) else { continue };
+            nodes.insert(func_id.clone(), CallPathNode {
+                function_id: func_id.clone(),
+                function_name: func.qualified_name.clone(),
+                file: func.file.clone(),
+                line: func.start_line,
+            });
+            
+            for call in func.calls.iter().filter(|c| c.resolved || options.include_unresolved) {
+                for candidate_id in &call.resolved_candidates {
+                    edges.entry(func_id.clone()).or_default().push(candidate_id.clone());
+                    if !nodes.contains_key(candidate_id) {
+                        queue.push_back((candidate_id.clone(), depth + 1));
+                    }
+                }
+            }
+        }
+        
+        function_reachability(&edges, &nodes, from_id, to_id, max_depth, options.max_paths.unwrap_or(DEFAULT_MAX_PATHS))
     }
     
     /// Get the call path from a location to a specific data access point
@@ -185,54 +291,37 @@ impl ReachabilityEngine {
         
         for func_id in &self.graph.data_accessors {
             if let Some(func) = self.graph.functions.get(func_id) {
-                for access in &func.data_access {
-                    if access.table == options.table {
-                        if options.field.as_ref().map_or(true, |f| access.fields.contains(f)) {
-                            direct_accessors.push(func_id.clone());
-                            break;
-                        }
-                    }
+                if func.data_access.iter().any(|access| options.matches(access)) {
+                    direct_accessors.push(func_id.clone());
                 }
             }
         }
         
         // For each direct accessor, find all paths from entry points
-        let mut access_paths: Vec<InverseAccessPath> = Vec::new();
-        let mut reaching_entry_points: HashSet<String> = HashSet::new();
+        let mut collector = InverseCollector::new(options);
         
         for accessor_id in &direct_accessors {
             if let Some(accessor) = self.graph.functions.get(accessor_id) {
-                // Find the specific access point
-                let access_point = accessor.data_access.iter().find(|a| {
-                    a.table == options.table
-                        && options.field.as_ref().map_or(true, |f| a.fields.contains(f))
-                });
+                // Find the specific access points
+                let access_points: Vec<DataAccessPoint> = accessor.data_access
+                    .iter()
+                    .filter(|a| options.matches(a))
+                    .cloned()
+                    .collect();
                 
-                if let Some(access_point) = access_point {
+                if !access_points.is_empty() {
                     // Find paths from entry points to this accessor
                     let paths = self.find_paths_to_function(accessor_id, max_depth);
                     
                     for (entry_point, path) in paths {
-                        reaching_entry_points.insert(entry_point.clone());
-                        access_paths.push(InverseAccessPath {
-                            entry_point,
-                            path,
-                            access_point: access_point.clone(),
-                        });
+                        let confidence = call_path_confidence(&path, |caller, callee| self.call_confidence(caller, callee));
+                        collector.add(&entry_point, path, confidence, &access_points);
                     }
                 }
             }
         }
         
-        InverseReachabilityResult {
-            target: InverseTarget {
-                table: options.table.clone(),
-                field: options.field.clone(),
-            },
-            access_paths,
-            entry_points: reaching_entry_points.into_iter().collect(),
-            total_accessors: direct_accessors.len() as u32,
-        }
+        collector.finish(direct_accessors.len() as u32)
     }
     
     /// Find all paths from entry points to a specific function
@@ -416,6 +505,10 @@ impl ReachabilityEngine {
             sensitive_fields: sensitive_fields_map.into_values().collect(),
             max_depth,
             functions_traversed,
+            unresolved_edges_traversed: 0,
+            cache_hit: false,
+            truncated: false,
+            truncation_reason: None,
         }
     }
     
@@ -448,6 +541,10 @@ impl ReachabilityEngine {
             sensitive_fields: Vec::new(),
             max_depth: 0,
             functions_traversed: 0,
+            unresolved_edges_traversed: 0,
+            cache_hit: false,
+            truncated: false,
+            truncation_reason: None,
         }
     }
 }
@@ -474,6 +571,7 @@ mod tests {
                     resolved: true,
                     resolved_candidates: vec!["service.ts:getUsers:1".to_string()],
                     line: 5,
+                    confidence: 0.95,
                 }],
                 data_access: Vec::new(),
                 is_entry_point: true,
@@ -494,6 +592,7 @@ mod tests {
                     resolved: true,
                     resolved_candidates: vec!["repo.ts:findAll:1".to_string()],
                     line: 5,
+                    confidence: 0.95,
                 }],
                 data_access: Vec::new(),
                 is_entry_point: false,
@@ -545,6 +644,207 @@ mod tests {
         assert_eq!(result.max_depth, 2);
     }
     
+    #[test]
+    fn test_traversal_budget() {
+        let engine = ReachabilityEngine::new(create_test_graph());
+        let reach = |options: ReachabilityOptions| engine.get_reachable_data_from_function("main.ts:main:1", &options);
+        
+        // findAll is the third function visited
+        let capped = reach(ReachabilityOptions { max_functions: Some(2), ..Default::default() });
+        assert!(capped.truncated);
+        assert_eq!(capped.truncation_reason, Some(TruncationReason::MaxFunctions));
+        assert_eq!(capped.functions_traversed, 2);
+        assert!(capped.reachable_access.is_empty());
+        
+        let enough = reach(ReachabilityOptions { max_functions: Some(3), timeout_ms: Some(60_000), ..Default::default() });
+        assert!(!enough.truncated && enough.truncation_reason.is_none());
+        assert_eq!(enough.reachable_access.len(), 1);
+        
+        let expired = reach(ReachabilityOptions { timeout_ms: Some(0), ..Default::default() });
+        assert_eq!(expired.truncation_reason, Some(TruncationReason::Timeout));
+        assert_eq!(expired.functions_traversed, 0);
+    }
+    
+    #[test]
+    fn test_unresolved_modes() {
+        let mut graph = create_test_graph();
+        let accessor = |id: &str, name: &str, table: &str| FunctionNode {
+            id: id.to_string(),
+            name: name.rsplit('.').next().unwrap().to_string(),
+            qualified_name: name.to_string(),
+            file: id.split(':').next().unwrap().to_string(),
+            start_line: 1,
+            end_line: 10,
+            calls: Vec::new(),
+            data_access: vec![DataAccessPoint {
+                table: table.to_string(),
+                operation: DataOperation::Write,
+                fields: Vec::new(),
+                file: id.split(':').next().unwrap().to_string(),
+                line: 5,
+                confidence: 0.9,
+                framework: None,
+            }],
+            is_entry_point: false,
+        };
+        for (id, name, table) in [
+            ("audit.ts:save:1", "AuditLog.save", "audit_log"),
+            ("cache.ts:save:1", "Cache.save", "cache"),
+            ("queue.ts:flush:1", "flush", "jobs"),
+        ] {
+            graph.functions.insert(id.to_string(), accessor(id, name, table));
+        }
+        let unresolved = |callee: &str, candidates: &[&str]| CallSite {
+            callee_name: callee.to_string(),
+            resolved: false,
+            resolved_candidates: candidates.iter().map(|c| c.to_string()).collect(),
+            line: 7,
+            confidence: 0.0,
+        };
+        // store.save could be either save; flush has one candidate by name;
+        // emit's stored candidate wins over name lookup
+        graph.functions.get_mut("main.ts:main:1").unwrap().calls.extend([
+            unresolved("store.save", &[]),
+            unresolved("flush", &[]),
+            unresolved("emit", &["cache.ts:save:1"]),
+        ]);
+        let engine = ReachabilityEngine::new(graph);
+        let reach = |unresolved_mode| {
+            let result = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+                unresolved_mode,
+                ..Default::default()
+            });
+            let mut tables = result.tables;
+            tables.sort();
+            (tables.join(","), result.unresolved_edges_traversed)
+        };
+        
+        assert_eq!(reach(UnresolvedMode::Ignore), ("users".to_string(), 0));
+        assert_eq!(reach(UnresolvedMode::Pessimistic), ("audit_log,cache,jobs,users".to_string(), 4));
+        assert_eq!(reach(UnresolvedMode::UniqueCandidate), ("cache,jobs,users".to_string(), 2));
+        
+        assert_eq!(UnresolvedMode::parse("unique-candidate"), Ok(UnresolvedMode::UniqueCandidate));
+        assert!(UnresolvedMode::parse("optimistic").is_err());
+    }
+    
+    #[test]
+    fn test_multiple_paths_per_access() {
+        let mut graph = create_test_graph();
+        graph.functions.get_mut("main.ts:main:1").unwrap().calls.push(CallSite {
+            callee_name: "findAll".to_string(),
+            resolved: true,
+            resolved_candidates: vec!["repo.ts:findAll:1".to_string()],
+            line: 6,
+            confidence: 0.95,
+        });
+        let engine = ReachabilityEngine::new(graph);
+        let ids = |path: &Vec<CallPathNode>| path.iter().map(|n| n.function_id.clone()).collect::<Vec<_>>();
+        
+        let result = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            max_paths_per_access: Some(5),
+            ..Default::default()
+        });
+        let access = &result.reachable_access[0];
+        assert_eq!(ids(&access.path), vec!["main.ts:main:1", "repo.ts:findAll:1"]);
+        assert_eq!(access.paths.iter().map(ids).collect::<Vec<_>>(), vec![
+            vec!["main.ts:main:1", "repo.ts:findAll:1"],
+            vec!["main.ts:main:1", "service.ts:getUsers:1", "repo.ts:findAll:1"],
+        ]);
+        
+        let default = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions::default());
+        assert_eq!(default.reachable_access[0].paths.iter().map(ids).collect::<Vec<_>>(), vec![ids(&default.reachable_access[0].path)]);
+    }
+    
+    #[test]
+    fn test_find_paths_between_functions() {
+        let mut graph = create_test_graph();
+        // findAll calls back into main, closing a cycle
+        graph.functions.get_mut("repo.ts:findAll:1").unwrap().calls.push(CallSite {
+            callee_name: "main".to_string(),
+            resolved: true,
+            resolved_candidates: vec!["main.ts:main:1".to_string()],
+            line: 7,
+            confidence: 0.95,
+        });
+        graph.functions.get_mut("main.ts:main:1").unwrap().calls.push(CallSite {
+            callee_name: "findAll".to_string(),
+            resolved: true,
+            resolved_candidates: vec!["repo.ts:findAll:1".to_string()],
+            line: 6,
+            confidence: 0.95,
+        });
+        let engine = ReachabilityEngine::new(graph);
+        let options = FunctionPathOptions::default();
+        
+        let found = engine.find_paths("main.ts:main:1", "repo.ts:findAll:1", &options);
+        assert!(found.reachable);
+        assert_eq!(found.path_count, 2);
+        assert_eq!(found.min_depth, Some(1));
+        let ids: Vec<&str> = found.shortest_path.iter().map(|n| n.function_id.as_str()).collect();
+        assert_eq!(ids, vec!["main.ts:main:1", "repo.ts:findAll:1"]);
+        
+        let limited = engine.find_paths("main.ts:main:1", "repo.ts:findAll:1", &FunctionPathOptions {
+            max_paths: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(limited.path_count, 1);
+        
+        let itself = engine.find_paths("service.ts:getUsers:1", "service.ts:getUsers:1", &options);
+        assert!(itself.reachable);
+        assert_eq!((itself.path_count, itself.min_depth, itself.shortest_path.len()), (1, Some(0), 1));
+        
+        let too_deep = engine.find_paths("service.ts:getUsers:1", "main.ts:main:1", &FunctionPathOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        });
+        assert!(!too_deep.reachable);
+        assert!(too_deep.shortest_path.is_empty());
+        assert_eq!((too_deep.path_count, too_deep.min_depth), (0, None));
+        
+        assert!(!engine.find_paths("main.ts:main:1", "missing.ts:nope:1", &options).reachable);
+    }
+    
+    #[test]
+    fn test_operation_filters() {
+        let mut graph = create_test_graph();
+        graph.functions.get_mut("service.ts:getUsers:1").unwrap().data_access.push(DataAccessPoint {
+            table: "users".to_string(),
+            operation: DataOperation::Delete,
+            fields: vec![],
+            file: "service.ts".to_string(),
+            line: 4,
+            confidence: 0.9,
+            framework: None,
+        });
+        graph.data_accessors.push("service.ts:getUsers:1".to_string());
+        let engine = ReachabilityEngine::new(graph);
+        
+        let mutations = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            operations: Some(vec![DataOperation::Write, DataOperation::Delete]),
+            ..Default::default()
+        });
+        assert_eq!(mutations.reachable_access.len(), 1);
+        assert_eq!(mutations.reachable_access[0].access.operation, DataOperation::Delete);
+        
+        let deleters = engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: Some(vec![DataOperation::Delete]),
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        });
+        assert_eq!(deleters.total_accessors, 1);
+        assert_eq!(deleters.access_paths.len(), 1);
+        assert_eq!(deleters.access_paths[0].access_point.operation, DataOperation::Delete);
+        assert_eq!(deleters.access_paths[0].path.last().unwrap().function_id, "service.ts:getUsers:1");
+        
+        assert_eq!(DataOperation::parse(" Delete").unwrap(), DataOperation::Delete);
+        assert!(DataOperation::parse("upsert").is_err());
+    }
+    
     #[test]
     fn test_sensitive_field_detection() {
         let graph = create_test_graph();
@@ -577,12 +877,84 @@ mod tests {
         let engine = ReachabilityEngine::new(graph);
         
         let result = engine.get_code_paths_to_data(&InverseReachabilityOptions {
-            table: "users".to_string(),
+            table: Some("users".to_string()),
             field: None,
             max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
         });
         
         assert_eq!(result.total_accessors, 1);
         assert!(!result.entry_points.is_empty());
     }
+    
+    #[test]
+    fn test_inverse_by_field() {
+        let mut graph = create_test_graph();
+        let mut audit = graph.functions["repo.ts:findAll:1"].clone();
+        audit.id = "audit.ts:record:1".to_string();
+        audit.data_access[0].table = "audit_log".to_string();
+        audit.data_access[0].fields = vec!["actor_email".to_string()];
+        graph.functions.insert(audit.id.clone(), audit);
+        graph.data_accessors.push("audit.ts:record:1".to_string());
+        graph.functions.get_mut("main.ts:main:1").unwrap().calls[0].resolved_candidates.push("audit.ts:record:1".to_string());
+        let engine = ReachabilityEngine::new(graph);
+        
+        let result = engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: None,
+            field: Some("*email".to_string()),
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        });
+        let tables: Vec<(&str, &[String])> = result.tables.iter().map(|t| (t.table.as_str(), &t.fields[..])).collect();
+        assert_eq!(tables, vec![
+            ("audit_log", &["actor_email".to_string()][..]),
+            ("users", &["email".to_string()][..]),
+        ]);
+        assert_eq!(result.total_accessors, 2);
+        
+        assert!(field_matches("*_token", "api_token"));
+        assert!(field_matches("pass*hash", "password_hash"));
+        assert!(!field_matches("*_token", "token"));
+        assert!(!field_matches("ssn", "ssn_last4"));
+    }
+    
+    #[test]
+    fn test_path_confidence() {
+        let mut graph = create_test_graph();
+        // getUsers -> findAll was only matched by a fuzzy global lookup
+        graph.functions.get_mut("service.ts:getUsers:1").unwrap().calls[0].confidence = 0.4;
+        let engine = ReachabilityEngine::new(graph);
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        
+        let forward = |min_confidence| engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            min_confidence,
+            ..Default::default()
+        });
+        let all = forward(None);
+        assert!(close(all.reachable_access[0].path_confidence, 0.95 * 0.4 * 0.9), "{}", all.reachable_access[0].path_confidence);
+        let trimmed = forward(Some(0.5));
+        assert!(trimmed.reachable_access.is_empty() && trimmed.tables.is_empty());
+        
+        let inverse = |min_confidence| engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence,
+        });
+        let paths = inverse(None).access_paths;
+        assert!(close(paths[0].path_confidence, 0.95 * 0.4 * 0.9));
+        assert!(inverse(Some(0.5)).access_paths.is_empty());
+    }
 }
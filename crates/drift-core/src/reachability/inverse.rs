@@ -0,0 +1,248 @@
+//! Inverse Path Collection
+//!
+//! Gathers the entry point -> accessor paths both engines find for
+//! `get_code_paths_to_data`. Paths can be capped per entry point so a hot
+//! table can't blow up the result, which grouped paths are by default; with
+//! `group_by_entry_point` each entry point is summarized instead, from every
+//! path found, not just the kept ones. A path
+//! to a function accessing several matching tables counts once per table.
+//! A collector with a sink hands kept paths to it instead of holding them,
+//! so a streamed result carries only the summaries; streamed paths are only
+//! capped when the caller asks.
+
+use std::collections::{BTreeMap, BTreeSet};
+use rustc_hash::FxHashSet;
+
+use super::types::*;
+
+/// Paths kept per group when grouping by entry point without a limit
+const DEFAULT_MAX_PATHS_PER_ENTRY_POINT: u32 = 100;
+
+/// An entry point's group and the access sites counted for it
+struct GroupState {
+    group: EntryPointAccessGroup,
+    sites: FxHashSet<(String, u32)>,
+}
+
+/// A table's summary as paths into it are added
+#[derive(Default)]
+struct TableState {
+    fields: BTreeSet<String>,
+    accessors: FxHashSet<String>,
+    entry_points: BTreeSet<String>,
+    path_count: u32,
+}
+
+pub(crate) struct InverseCollector<'a> {
+    options: &'a InverseReachabilityOptions,
+    limit: Option<u32>,
+    access_paths: Vec<InverseAccessPath>,
+    groups: BTreeMap<String, GroupState>,
+    tables: BTreeMap<String, TableState>,
+    truncated: bool,
+    sink: Option<Box<dyn FnMut(InverseAccessPath) + 'a>>,
+}
+
+impl<'a> InverseCollector<'a> {
+    pub(crate) fn new(options: &'a InverseReachabilityOptions) -> Self {
+        Self {
+            options,
+            limit: options.max_paths_per_entry_point
+                .or(options.group_by_entry_point.then_some(DEFAULT_MAX_PATHS_PER_ENTRY_POINT)),
+            access_paths: Vec::new(),
+            groups: BTreeMap::new(),
+            tables: BTreeMap::new(),
+            truncated: false,
+            sink: None,
+        }
+    }
+
+    /// A collector passing each kept path to `sink` as it is added, keeping
+    /// every path unless `max_paths_per_entry_point` is set
+    pub(crate) fn with_sink(options: &'a InverseReachabilityOptions, sink: impl FnMut(InverseAccessPath) + 'a) -> Self {
+        Self {
+            limit: options.max_paths_per_entry_point,
+            sink: Some(Box::new(sink)),
+            ..Self::new(options)
+        }
+    }
+
+    /// Record a path from `entry_point` to a function making the matching
+    /// `accesses`, whose calls multiply to `call_confidence`. Accesses below
+    /// `min_confidence` are dropped; the first left in each table is the
+    /// path's access point for that table.
+    pub(crate) fn add(&mut self, entry_point: &str, path: Vec<CallPathNode>, call_confidence: f64, accesses: &[DataAccessPoint]) {
+        let mut by_table: BTreeMap<&str, Vec<&DataAccessPoint>> = BTreeMap::new();
+        for access in accesses {
+            if self.options.min_confidence.is_none_or(|min| call_confidence * access.confidence as f64 >= min) {
+                by_table.entry(access.table.as_str()).or_default().push(access);
+            }
+        }
+        for (table, accesses) in by_table {
+            self.record_table(table, entry_point, &path, &accesses);
+            self.add_path(entry_point, path.clone(), call_confidence, &accesses);
+        }
+    }
+
+    fn record_table(&mut self, table: &str, entry_point: &str, path: &[CallPathNode], accesses: &[&DataAccessPoint]) {
+        let state = self.tables.entry(table.to_string()).or_default();
+        state.path_count += 1;
+        state.entry_points.insert(entry_point.to_string());
+        if let Some(accessor) = path.last() {
+            state.accessors.insert(accessor.function_id.clone());
+        }
+        if let Some(pattern) = &self.options.field {
+            let fields = accesses.iter().flat_map(|a| &a.fields).filter(|f| field_matches(pattern, f));
+            state.fields.extend(fields.cloned());
+        }
+    }
+
+    fn add_path(&mut self, entry_point: &str, path: Vec<CallPathNode>, call_confidence: f64, accesses: &[&DataAccessPoint]) {
+        let (Some(&access_point), Some(accessor)) = (accesses.first(), path.last()) else { return };
+        let accessor = accessor.function_id.clone();
+        let depth = path.len() as u32 - 1;
+
+        let state = self.groups.entry(entry_point.to_string()).or_insert_with(|| GroupState {
+            group: EntryPointAccessGroup {
+                entry_point: entry_point.to_string(),
+                operations: Vec::new(),
+                access_sites: 0,
+                path_count: 0,
+                min_depth: depth,
+                max_depth: depth,
+                paths: Vec::new(),
+                truncated: false,
+            },
+            sites: FxHashSet::default(),
+        });
+        let group = &mut state.group;
+        group.path_count += 1;
+        group.min_depth = group.min_depth.min(depth);
+        group.max_depth = group.max_depth.max(depth);
+        for access in accesses {
+            if !group.operations.contains(&access.operation) {
+                group.operations.push(access.operation);
+            }
+            state.sites.insert((accessor.clone(), access.line));
+        }
+
+        if self.options.group_by_entry_point && !self.options.include_paths {
+            return;
+        }
+        if self.limit.is_some_and(|limit| group.path_count > limit) {
+            group.truncated = true;
+            self.truncated = true;
+            return;
+        }
+        let path = InverseAccessPath {
+            entry_point: entry_point.to_string(),
+            path,
+            access_point: access_point.clone(),
+            path_confidence: call_confidence * access_point.confidence as f64,
+        };
+        if let Some(sink) = &mut self.sink {
+            sink(path);
+        } else if self.options.group_by_entry_point {
+            group.paths.push(path);
+        } else {
+            self.access_paths.push(path);
+        }
+    }
+
+    pub(crate) fn finish(self, total_accessors: u32) -> InverseReachabilityResult {
+        let entry_points = self.groups.keys().cloned().collect();
+        let groups = if self.options.group_by_entry_point {
+            self.groups
+                .into_values()
+                .map(|mut state| {
+                    state.group.operations.sort();
+                    state.group.access_sites = state.sites.len() as u32;
+                    state.group
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        InverseReachabilityResult {
+            target: InverseTarget {
+                table: self.options.table.clone(),
+                field: self.options.field.clone(),
+            },
+            access_paths: self.access_paths,
+            groups,
+            tables: self.tables
+                .into_iter()
+                .map(|(table, state)| InverseTableGroup {
+                    table,
+                    fields: state.fields.into_iter().collect(),
+                    accessors: state.accessors.len() as u32,
+                    entry_points: state.entry_points.into_iter().collect(),
+                    path_count: state.path_count,
+                })
+                .collect(),
+            entry_points,
+            total_accessors,
+            truncated: self.truncated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str) -> CallPathNode {
+        CallPathNode {
+            function_id: id.to_string(),
+            function_name: id.to_string(),
+            file: "api.ts".to_string(),
+            line: 1,
+        }
+    }
+
+    /// 150 paths from one entry point into the users table
+    fn collect(group_by_entry_point: bool, max_paths_per_entry_point: Option<u32>) -> InverseReachabilityResult {
+        let options = InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: None,
+            group_by_entry_point,
+            include_paths: true,
+            max_paths_per_entry_point,
+            min_confidence: None,
+        };
+        let access = DataAccessPoint {
+            table: "users".to_string(),
+            operation: DataOperation::Read,
+            fields: vec![],
+            file: "repo.ts".to_string(),
+            line: 3,
+            confidence: 1.0,
+            framework: None,
+        };
+        let mut collector = InverseCollector::new(&options);
+        for i in 0..150 {
+            let path = vec![node("handler"), node(&format!("step{}", i)), node("findAll")];
+            collector.add("handler", path, 1.0, std::slice::from_ref(&access));
+        }
+        collector.finish(1)
+    }
+
+    #[test]
+    fn test_flat_paths_uncapped_by_default() {
+        let flat = collect(false, None);
+        assert_eq!(flat.access_paths.len(), 150);
+        assert!(!flat.truncated);
+
+        let capped = collect(false, Some(20));
+        assert_eq!(capped.access_paths.len(), 20);
+        assert!(capped.truncated);
+
+        let grouped = collect(true, None);
+        assert_eq!(grouped.groups[0].paths.len(), DEFAULT_MAX_PATHS_PER_ENTRY_POINT as usize);
+        assert_eq!(grouped.groups[0].path_count, 150);
+        assert!(grouped.groups[0].truncated && grouped.truncated);
+    }
+}
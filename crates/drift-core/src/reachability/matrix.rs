@@ -0,0 +1,90 @@
+//! Sensitivity Matrix
+//!
+//! Entry point x sensitive field table for compliance reports: rescans the
+//! call graph's files with `BoundaryScanner` to learn which fields are
+//! sensitive, then lists every sensitive field each entry point reaches.
+//! With `expand_eager_relations`, the rescan also collects ORM models, and
+//! reading a model's table reaches the fields of the models it loads eagerly.
+
+use std::path::Path;
+
+use crate::boundaries::BoundaryScanner;
+use super::relations::eager_relations;
+use super::sqlite_engine::SqliteReachabilityEngine;
+use super::types::{SensitivityMatrixOptions, SensitivityMatrixRow};
+
+/// The sensitivity matrix for the call graph built under `root`
+pub fn generate_sensitivity_matrix(
+    root: &Path,
+    options: &SensitivityMatrixOptions,
+) -> Result<Vec<SensitivityMatrixRow>, String> {
+    let engine = SqliteReachabilityEngine::from_project_root(root)
+        .map_err(|e| format!("Failed to open call graph database: {}", e))?;
+    if !engine.is_available() {
+        return Err("Call graph database is empty".to_string());
+    }
+
+    let mut scanner = BoundaryScanner::new();
+    let mut detections = Vec::new();
+    let mut models = Vec::new();
+    for file in engine.get_files() {
+        let Some(result) = scanner.scan_file(&root.join(&file)) else { continue };
+        if options.expand_eager_relations {
+            models.extend(result.models);
+        }
+        // Call graph files are relative to the root
+        detections.extend(result.sensitive_fields.into_iter().map(|mut field| {
+            field.file = file.clone();
+            field
+        }));
+    }
+
+    Ok(engine.sensitivity_matrix(&detections, &eager_relations(&models), options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::{BuilderConfig, StreamingBuilder};
+    use crate::reachability::{DataOperation, SensitivityType};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generate_sensitivity_matrix() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("api.ts"), "export function login() {\n  checkUser();\n  return loadUser();\n}\n\nexport function signup() {\n  return saveUser();\n}\n\nfunction checkUser() {\n  return findUser();\n}\n\nfunction loadUser() {\n  return findUser();\n}\n").unwrap();
+        fs::write(src.join("users.ts"), "export function findUser() {\n  return db.query('SELECT id, email, password FROM users');\n}\n\nexport function saveUser() {\n  return db.query('INSERT INTO users (email, nickname) VALUES (?, ?)');\n}\n").unwrap();
+
+        let builder = StreamingBuilder::new(BuilderConfig {
+            root_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let build = builder.build_sqlite(&["**/*.ts"]);
+        assert!(build.errors.is_empty(), "{:?}", build.errors);
+
+        let rows = generate_sensitivity_matrix(dir.path(), &SensitivityMatrixOptions::default()).unwrap();
+        let summary: Vec<(&str, SensitivityType, &str, DataOperation, u64, u32)> = rows
+            .iter()
+            .map(|r| (r.entry_point.rsplit(':').nth(1).unwrap(), r.sensitivity_type, r.field.as_str(), r.operation, r.path_count, r.min_depth))
+            .filter(|r| r.0 == "login" || r.0 == "signup")
+            .collect();
+        // login reaches findUser through both checkUser and loadUser
+        assert_eq!(summary, vec![
+            ("login", SensitivityType::Pii, "email", DataOperation::Read, 2, 2),
+            ("login", SensitivityType::Credentials, "password", DataOperation::Read, 2, 2),
+            ("signup", SensitivityType::Pii, "email", DataOperation::Write, 1, 1),
+        ]);
+
+        let credentials = generate_sensitivity_matrix(dir.path(), &SensitivityMatrixOptions {
+            sensitivity_types: Some(vec![SensitivityType::Credentials]),
+            ..Default::default()
+        }).unwrap();
+        assert!(credentials.iter().all(|r| r.sensitivity_type == SensitivityType::Credentials && r.table == "users"));
+        assert!(credentials.iter().any(|r| r.entry_point.contains(":login:")));
+
+        assert!(generate_sensitivity_matrix(&dir.path().join("missing"), &SensitivityMatrixOptions::default()).is_err());
+    }
+}
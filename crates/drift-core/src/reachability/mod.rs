@@ -10,7 +10,16 @@
 mod types;
 mod engine;
 mod sqlite_engine;
+mod paths;
+mod inverse;
+mod matrix;
+mod cache;
+mod budget;
+mod relations;
 
 pub use types::*;
 pub use engine::ReachabilityEngine;
 pub use sqlite_engine::SqliteReachabilityEngine;
+pub use matrix::generate_sensitivity_matrix;
+pub use relations::{eager_relations, scan_eager_relations};
+pub use cache::{ReachabilityCache, DEFAULT_CACHE_CAPACITY};
@@ -0,0 +1,219 @@
+//! Path Enumeration
+//!
+//! Bounded DFS listing the distinct simple call paths between two functions,
+//! over the edges a reachability traversal recorded. Both engines use it
+//! when `max_paths_per_access` asks for more than the one BFS path.
+
+use std::collections::VecDeque;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::types::{CallPathNode, FunctionReachability, ReachableDataAccess};
+
+/// Paths counted by `function_reachability` when no limit is given
+pub(crate) const DEFAULT_MAX_PATHS: u32 = 100;
+
+/// Functions visited per enumeration before giving up on finding more paths
+const MAX_EXPANSIONS: usize = 100_000;
+
+/// Up to `limit` distinct simple paths (function IDs) from `from` to `to`,
+/// each at most `max_depth` calls long, shortest first
+pub(crate) fn simple_paths(
+    edges: &FxHashMap<String, Vec<String>>,
+    from: &str,
+    to: &str,
+    max_depth: u32,
+    limit: usize,
+) -> Vec<Vec<String>> {
+    if from == to {
+        return vec![vec![from.to_string()]];
+    }
+
+    // Calls left to reach `to` from each function that can
+    let mut callers: FxHashMap<&str, Vec<&str>> = FxHashMap::default();
+    for (caller, callees) in edges {
+        for callee in callees {
+            callers.entry(callee.as_str()).or_default().push(caller.as_str());
+        }
+    }
+    let mut remaining: FxHashMap<&str, u32> = FxHashMap::default();
+    let mut queue: VecDeque<&str> = VecDeque::new();
+    remaining.insert(to, 0);
+    queue.push_back(to);
+    while let Some(node) = queue.pop_front() {
+        let next = remaining[node] + 1;
+        for &caller in callers.get(node).into_iter().flatten() {
+            if !remaining.contains_key(caller) {
+                remaining.insert(caller, next);
+                queue.push_back(caller);
+            }
+        }
+    }
+    if remaining.get(from).is_none_or(|&d| d > max_depth) {
+        return Vec::new();
+    }
+
+    let mut paths: Vec<Vec<String>> = Vec::new();
+    let mut path: Vec<&str> = vec![from];
+    let mut on_path: FxHashSet<&str> = FxHashSet::from_iter([from]);
+    // Callees still to try below each path position, nearest to `to` on top
+    // so the first path found is a shortest one
+    let mut pending: Vec<Vec<&str>> = vec![next_steps(edges, &remaining, from)];
+    let mut expansions = 0;
+
+    while let Some(steps) = pending.last_mut() {
+        match steps.pop() {
+            Some(next) if on_path.contains(next) || path.len() as u32 + remaining[next] > max_depth => {}
+            Some(next) if next == to => {
+                paths.push(path.iter().chain([&next]).map(|s| s.to_string()).collect());
+                if paths.len() >= limit {
+                    break;
+                }
+            }
+            Some(next) => {
+                expansions += 1;
+                if expansions > MAX_EXPANSIONS {
+                    break;
+                }
+                path.push(next);
+                on_path.insert(next);
+                pending.push(next_steps(edges, &remaining, next));
+            }
+            None => {
+                pending.pop();
+                if let Some(done) = path.pop() {
+                    on_path.remove(done);
+                }
+            }
+        }
+    }
+
+    paths.sort_by_key(|p| p.len());
+    paths
+}
+
+/// Whether `to` is reachable from `from` over the `edges` a traversal from
+/// `from` recorded, with the shortest path and a bounded path count
+pub(crate) fn function_reachability(
+    edges: &FxHashMap<String, Vec<String>>,
+    nodes: &FxHashMap<String, CallPathNode>,
+    from: &str,
+    to: &str,
+    max_depth: u32,
+    max_paths: u32,
+) -> FunctionReachability {
+    let paths = if nodes.contains_key(to) {
+        simple_paths(edges, from, to, max_depth, max_paths.max(1) as usize)
+    } else {
+        Vec::new()
+    };
+    let shortest = paths.first();
+    FunctionReachability {
+        from_id: from.to_string(),
+        to_id: to.to_string(),
+        reachable: shortest.is_some(),
+        shortest_path: shortest
+            .into_iter()
+            .flatten()
+            .filter_map(|id| nodes.get(id).cloned())
+            .collect(),
+        path_count: paths.len() as u32,
+        min_depth: shortest.map(|p| p.len() as u32 - 1),
+    }
+}
+
+/// Fill each access's `paths` with its BFS `path` followed by other paths
+/// from `origin` to its function, up to `limit` in all
+pub(crate) fn expand_paths(
+    reachable_access: &mut [ReachableDataAccess],
+    edges: &FxHashMap<String, Vec<String>>,
+    nodes: &FxHashMap<String, CallPathNode>,
+    origin: &str,
+    max_depth: u32,
+    limit: usize,
+) {
+    // Accesses in one function share its enumeration
+    let mut by_function: FxHashMap<String, Vec<Vec<String>>> = FxHashMap::default();
+    for access in reachable_access {
+        let Some(target) = access.path.last().map(|n| n.function_id.clone()) else { continue };
+        let found = by_function
+            .entry(target)
+            .or_insert_with_key(|target| simple_paths(edges, origin, target, max_depth, limit));
+        let shortest: Vec<&str> = access.path.iter().map(|n| n.function_id.as_str()).collect();
+        let others = found
+            .iter()
+            .filter(|ids| **ids != shortest)
+            .map(|ids| ids.iter().filter_map(|id| nodes.get(id).cloned()).collect());
+        access.paths = std::iter::once(access.path.clone()).chain(others).take(limit).collect();
+    }
+}
+
+/// Product of the confidences of the calls along `path`, as given by
+/// `call_confidence(caller, callee)`
+pub(crate) fn call_path_confidence(path: &[CallPathNode], call_confidence: impl Fn(&str, &str) -> f64) -> f64 {
+    path.windows(2)
+        .map(|pair| call_confidence(&pair[0].function_id, &pair[1].function_id))
+        .product()
+}
+
+/// Set each access's `path_confidence` from its `path`, then drop those
+/// below `min_confidence`
+pub(crate) fn score_paths(
+    reachable_access: &mut Vec<ReachableDataAccess>,
+    min_confidence: Option<f64>,
+    call_confidence: impl Fn(&str, &str) -> f64,
+) {
+    for access in reachable_access.iter_mut() {
+        access.path_confidence = call_path_confidence(&access.path, &call_confidence) * access.access.confidence as f64;
+    }
+    if let Some(min) = min_confidence {
+        reachable_access.retain(|access| access.path_confidence >= min);
+    }
+}
+
+/// Set each access's `crosses_async` from its `path`, as given by
+/// `is_async(caller, callee)` for the calls along it
+pub(crate) fn mark_async_crossings(reachable_access: &mut [ReachableDataAccess], is_async: impl Fn(&str, &str) -> bool) {
+    for access in reachable_access {
+        access.crosses_async = access.path.windows(2).any(|pair| is_async(&pair[0].function_id, &pair[1].function_id));
+    }
+}
+
+/// Callees of `node` that can reach the target, as a stack: nearest on top
+fn next_steps<'a>(edges: &'a FxHashMap<String, Vec<String>>, remaining: &FxHashMap<&str, u32>, node: &str) -> Vec<&'a str> {
+    let mut steps: Vec<&str> = edges
+        .get(node)
+        .into_iter()
+        .flatten()
+        .map(|s| s.as_str())
+        .filter(|s| remaining.contains_key(s))
+        .collect();
+    steps.sort_by(|a, b| remaining[b].cmp(&remaining[a]).then_with(|| b.cmp(a)));
+    steps.dedup();
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> FxHashMap<String, Vec<String>> {
+        let mut graph: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        for (from, to) in edges {
+            graph.entry(from.to_string()).or_default().push(to.to_string());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_simple_paths() {
+        // a -> b -> d, a -> c -> d, a -> d, and a cycle b <-> c
+        let edges = graph(&[("a", "b"), ("a", "c"), ("a", "d"), ("b", "d"), ("c", "d"), ("b", "c"), ("c", "b"), ("b", "d")]);
+        let joined = |paths: Vec<Vec<String>>| paths.into_iter().map(|p| p.join(">")).collect::<Vec<_>>();
+
+        assert_eq!(joined(simple_paths(&edges, "a", "d", 10, 10)), vec!["a>d", "a>b>d", "a>c>d", "a>b>c>d", "a>c>b>d"]);
+        assert_eq!(joined(simple_paths(&edges, "a", "d", 10, 1)), vec!["a>d"]);
+        assert_eq!(joined(simple_paths(&edges, "a", "d", 2, 10)), vec!["a>d", "a>b>d", "a>c>d"]);
+        assert!(simple_paths(&edges, "d", "a", 10, 10).is_empty());
+        assert_eq!(joined(simple_paths(&edges, "a", "a", 10, 10)), vec!["a"]);
+    }
+}
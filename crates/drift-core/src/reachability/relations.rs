@@ -0,0 +1,138 @@
+//! Eager relations - Tables loaded along with the tables a query reads
+//!
+//! An ORM relation marked eager (TypeORM `eager: true`, SQLAlchemy
+//! `lazy="joined"`) loads the related rows with every read of its model, so
+//! code reaching the read reaches the related table too. Expansion is one
+//! hop: the related table's own eager relations are not followed.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::boundaries::{BoundaryScanner, ORMModel};
+use super::types::{DataAccessPoint, DataOperation, EagerRelation};
+
+/// The eager relations among `models`, whose targets are models too
+pub fn eager_relations(models: &[ORMModel]) -> Vec<EagerRelation> {
+    let by_name: HashMap<&str, &ORMModel> = models.iter().map(|m| (m.name.as_str(), m)).collect();
+    let mut relations: Vec<EagerRelation> = models.iter()
+        .flat_map(|model| model.relations.iter().filter(|r| r.eager).map(move |r| (model, r)))
+        .filter_map(|(model, relation)| {
+            let target = by_name.get(relation.target.as_str())?;
+            Some(EagerRelation {
+                table: model.table_name.clone(),
+                related_table: target.table_name.clone(),
+                fields: target.fields.clone(),
+                via: format!("{}.{}", model.name, relation.field),
+            })
+        })
+        .collect();
+    relations.sort_by(|a, b| a.via.cmp(&b.via));
+    relations.dedup();
+    relations
+}
+
+/// The eager relations of the models declared in `files` under `root`
+pub fn scan_eager_relations(root: &Path, files: &[String]) -> Vec<EagerRelation> {
+    let mut scanner = BoundaryScanner::new();
+    let models: Vec<ORMModel> = files.iter()
+        .filter_map(|file| scanner.scan_file(&root.join(file)))
+        .flat_map(|result| result.models)
+        .collect();
+    eager_relations(&models)
+}
+
+/// Relations `table` loads eagerly
+pub(super) fn eager_loads<'a>(table: &'a str, relations: &'a [EagerRelation]) -> impl Iterator<Item = &'a EagerRelation> {
+    relations.iter().filter(move |r| r.table.eq_ignore_ascii_case(table))
+}
+
+/// `access`, then the reads of the related tables it loads with the
+/// relation loading each
+pub(super) fn with_eager_relations(access: DataAccessPoint, relations: &[EagerRelation]) -> Vec<(DataAccessPoint, Option<String>)> {
+    let loaded: Vec<(DataAccessPoint, Option<String>)> = match access.operation {
+        DataOperation::Read => eager_loads(&access.table, relations)
+            .map(|relation| {
+                let related = DataAccessPoint {
+                    table: relation.related_table.clone(),
+                    fields: relation.fields.clone(),
+                    ..access.clone()
+                };
+                (related, Some(relation.via.clone()))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    std::iter::once((access, None)).chain(loaded).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_graph::{BuilderConfig, StreamingBuilder};
+    use crate::reachability::{generate_sensitivity_matrix, ReachabilityOptions, SensitivityMatrixOptions, SqliteReachabilityEngine};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_eager_relations_expand_reads() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("entities.ts"), concat!(
+            "@Entity('users')\n",
+            "export class User {\n",
+            "  @PrimaryGeneratedColumn() id: number;\n",
+            "  @Column() email: string;\n",
+            "}\n",
+            "\n",
+            "@Entity('posts')\n",
+            "export class Post {\n",
+            "  @Column() title: string;\n",
+            "  @ManyToOne(() => User, { eager: true }) author: User;\n",
+            "  @ManyToOne(() => Post) parent: Post;\n",
+            "}\n",
+        )).unwrap();
+        fs::write(src.join("api.ts"), "export function listPosts() {\n  return db.query('SELECT id, title FROM posts');\n}\n\nexport function deletePosts() {\n  return db.query('DELETE FROM posts');\n}\n").unwrap();
+
+        let builder = StreamingBuilder::new(BuilderConfig {
+            root_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let build = builder.build_sqlite(&["**/*.ts"]);
+        assert!(build.errors.is_empty(), "{:?}", build.errors);
+
+        let engine = SqliteReachabilityEngine::from_project_root(dir.path()).unwrap();
+        let relations = scan_eager_relations(dir.path(), &engine.get_files());
+        assert_eq!(relations, vec![EagerRelation {
+            table: "posts".to_string(),
+            related_table: "users".to_string(),
+            fields: vec!["id".to_string(), "email".to_string()],
+            via: "Post.author".to_string(),
+        }]);
+
+        // Reading posts loads their authors; deleting them doesn't
+        let tables = |function: &str| {
+            let result = engine.get_reachable_data_from_function(function, &ReachabilityOptions {
+                tables: vec!["posts".to_string(), "users".to_string()],
+                eager_relations: relations.clone(),
+                ..Default::default()
+            });
+            result.reachable_access.into_iter().map(|a| (a.access.table, a.loaded_via)).collect::<Vec<_>>()
+        };
+        assert_eq!(tables("src/api.ts:listPosts:0"), vec![
+            ("posts".to_string(), None),
+            ("users".to_string(), Some("Post.author".to_string())),
+        ]);
+        assert_eq!(tables("src/api.ts:deletePosts:4"), vec![("posts".to_string(), None)]);
+
+        let matrix = |expand_eager_relations| {
+            let rows = generate_sensitivity_matrix(dir.path(), &SensitivityMatrixOptions {
+                expand_eager_relations,
+                ..Default::default()
+            }).unwrap();
+            rows.into_iter().map(|r| (r.table, r.field)).collect::<Vec<_>>()
+        };
+        assert!(matrix(false).is_empty());
+        assert_eq!(matrix(true), vec![("users".to_string(), "email".to_string())]);
+    }
+}
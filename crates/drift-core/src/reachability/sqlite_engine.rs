@@ -7,13 +7,24 @@
 //! - O(1) memory usage regardless of codebase size
 //! - Fast indexed queries for caller/callee lookups
 //! - Supports incremental updates without full rebuild
+//! - Path searches step over recursion cycles via the SCC condensation
 
-use std::collections::{HashSet, VecDeque};
-use std::path::Path;
-use rustc_hash::FxHashSet;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use rustc_hash::{FxHashMap, FxHashSet};
+use rayon::prelude::*;
 use rusqlite::{params, Connection, Result as SqliteResult};
 
 use super::types::*;
+use super::cache::ReachabilityCache;
+use super::relations::{eager_loads, with_eager_relations};
+use super::budget::TraversalBudget;
+use super::inverse::InverseCollector;
+use super::paths::{call_path_confidence, expand_paths, function_reachability, mark_async_crossings, score_paths, DEFAULT_MAX_PATHS};
+use crate::boundaries::{SensitiveField as DetectedSensitiveField, EXTERNAL_TABLE_PREFIX};
+use crate::call_graph::{method_name, Condensation, CALL_KIND, CONTENT_VERSION_KEY};
 
 /// Sensitive field patterns for classification
 const CREDENTIAL_PATTERNS: &[&str] = &[
@@ -29,9 +40,20 @@ const PII_PATTERNS: &[&str] = &[
     "ssn", "social_security", "email", "phone", "address", "dob", "name", "birth",
 ];
 
+/// A function as loaded for whole-graph traversals: (id, name, file, is_entry_point)
+type FunctionRow = (String, String, String, bool);
+
 /// SQLite-backed Reachability Engine
 pub struct SqliteReachabilityEngine {
     conn: Connection,
+    db_path: PathBuf,
+    /// Resolved call graph condensed into SCCs, loaded on the first path search
+    condensation: OnceCell<Condensation>,
+    /// Function IDs by bare method name, loaded on the first traversal that
+    /// follows unresolved calls
+    by_name: OnceCell<FxHashMap<String, Vec<String>>>,
+    /// Results of earlier forward queries, when enabled
+    cache: Option<Arc<ReachabilityCache>>,
 }
 
 impl SqliteReachabilityEngine {
@@ -41,7 +63,13 @@ impl SqliteReachabilityEngine {
             db_path,
             rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
         )?;
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            db_path: db_path.to_path_buf(),
+            condensation: OnceCell::new(),
+            by_name: OnceCell::new(),
+            cache: None,
+        })
     }
     
     /// Create from project root (looks for .drift/lake/callgraph/callgraph.db)
@@ -54,6 +82,19 @@ impl SqliteReachabilityEngine {
         Self::open(&db_path)
     }
 
+    /// Serve repeated `get_reachable_data*` queries from `cache` while the
+    /// graph is unchanged
+    pub fn with_cache(mut self, cache: Arc<ReachabilityCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    
+    /// Use the process-wide cache for this database; see `ReachabilityCache::shared`
+    pub fn with_shared_cache(self) -> Self {
+        let cache = ReachabilityCache::shared(&self.db_path);
+        self.with_cache(cache)
+    }
+    
     /// Check if the database exists and has data
     pub fn is_available(&self) -> bool {
         self.conn
@@ -87,6 +128,28 @@ impl SqliteReachabilityEngine {
         &self,
         function_id: &str,
         options: &ReachabilityOptions,
+    ) -> ReachabilityResult {
+        // Databases written before content versions were recorded can't be
+        // cached safely
+        let Some((cache, version)) = self.cache.as_ref().zip(self.content_version()) else {
+            return self.traverse_from_function(function_id, options);
+        };
+        if let Some(mut result) = cache.get(function_id, options, &version) {
+            result.cache_hit = true;
+            return result;
+        }
+        let result = self.traverse_from_function(function_id, options);
+        // A timed-out traversal could get further next time
+        if !result.truncated {
+            cache.insert(function_id, options, &version, result.clone());
+        }
+        result
+    }
+    
+    fn traverse_from_function(
+        &self,
+        function_id: &str,
+        options: &ReachabilityOptions,
     ) -> ReachabilityResult {
         // Get function info
         let func = match self.get_function_info(function_id) {
@@ -104,6 +167,13 @@ impl SqliteReachabilityEngine {
         let max_depth = options.max_depth.unwrap_or(100);
         let mut visited: FxHashSet<String> = FxHashSet::default();
         let mut reachable_access: Vec<ReachableDataAccess> = Vec::new();
+        // Calls and path nodes seen, for enumerating more than one path per access
+        let max_paths = options.max_paths_per_access.unwrap_or(1).max(1) as usize;
+        let mut edges: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        let mut nodes: FxHashMap<String, CallPathNode> = FxHashMap::default();
+        let budget = TraversalBudget::new(options);
+        let mut truncation: Option<TruncationReason> = None;
+        let mut unresolved_edges: u32 = 0;
         
         // BFS queue: (function_id, path, depth)
         let mut queue: VecDeque<(String, Vec<CallPathNode>, u32)> = VecDeque::new();
@@ -113,6 +183,10 @@ impl SqliteReachabilityEngine {
             if visited.contains(&func_id) || depth > max_depth {
                 continue;
             }
+            if let Some(reason) = budget.exceeded(visited.len()) {
+                truncation = Some(reason);
+                break;
+            }
             visited.insert(func_id.clone());
             
             // Get function info from SQLite
@@ -129,33 +203,62 @@ impl SqliteReachabilityEngine {
                 file: current_func.file.clone(),
                 line: current_func.start_line,
             });
+            if max_paths > 1 {
+                nodes.insert(func_id.clone(), current_path.last().unwrap().clone());
+            }
             
             // Get data access from SQLite
             let data_access = self.get_data_access(&func_id);
             for access in data_access {
-                // Filter by tables if specified
-                if !options.tables.is_empty() && !options.tables.contains(&access.table) {
-                    continue;
+                for (access, loaded_via) in with_eager_relations(access, &options.eager_relations) {
+                    // Filter by tables and operations if specified
+                    if !options.includes(&access) {
+                        continue;
+                    }
+                    
+                    reachable_access.push(ReachableDataAccess {
+                        access,
+                        path: current_path.clone(),
+                        paths: vec![current_path.clone()],
+                        depth,
+                        path_confidence: 0.0,
+                        crosses_async: false,
+                        loaded_via,
+                    });
                 }
-                
-                reachable_access.push(ReachableDataAccess {
-                    access,
-                    path: current_path.clone(),
-                    depth,
-                });
             }
             
-            // Get resolved calls from SQLite and add to queue
-            let calls = self.get_resolved_calls(&func_id);
+            // Get resolved calls from SQLite, plus the unresolved ones the
+            // mode follows, and add to queue
+            let kinds = options.edge_kinds.as_deref();
+            let mut calls = self.get_resolved_calls(&func_id, kinds, options.include_async_edges);
+            if options.unresolved_mode != UnresolvedMode::Ignore {
+                for target in self.get_unresolved_targets(&func_id, kinds, options.include_async_edges) {
+                    let followed = options.unresolved_mode.select(self.functions_named(method_name(&target)));
+                    unresolved_edges += followed.len() as u32;
+                    calls.extend(followed.iter().cloned());
+                }
+            }
             for resolved_id in calls {
+                if max_paths > 1 {
+                    edges.entry(func_id.clone()).or_default().push(resolved_id.clone());
+                }
                 if !visited.contains(&resolved_id) {
                     queue.push_back((resolved_id, current_path.clone(), depth + 1));
                 }
             }
         }
         
+        score_paths(&mut reachable_access, options.min_confidence, |caller, callee| self.call_confidence(caller, callee));
+        if follows_call_kind("async", options.edge_kinds.as_deref(), options.include_async_edges) {
+            mark_async_crossings(&mut reachable_access, |caller, callee| self.is_async_call(caller, callee));
+        }
+        if max_paths > 1 {
+            expand_paths(&mut reachable_access, &edges, &nodes, function_id, max_depth, max_paths);
+        }
+        
         // Build result
-        self.build_result(
+        let mut result = self.build_result(
             CodeLocation {
                 file: func.file.clone(),
                 line: func.start_line,
@@ -165,65 +268,413 @@ impl SqliteReachabilityEngine {
             reachable_access,
             options.sensitive_only,
             visited.len() as u32,
-        )
+        );
+        result.truncated = truncation.is_some();
+        result.truncation_reason = truncation;
+        result.unresolved_edges_traversed = unresolved_edges;
+        result
+    }
+
+    /// Summarized reachability from every entry point, keyed by entry point ID
+    ///
+    /// Reads the resolved calls and data access once, and works out each
+    /// function's filtered access - tables and sensitive fields - once for
+    /// every traversal that reaches it, then walks from the entry points in
+    /// parallel. Far cheaper than `get_reachable_data_from_function` per entry
+    /// point on large graphs.
+    pub fn analyze_all_entry_points(&self, options: &ReachabilityOptions) -> BTreeMap<String, EntryPointReachability> {
+        let max_depth = options.max_depth.unwrap_or(100);
+        
+        let (functions, callees) = self.load_call_graph(options.edge_kinds.as_deref(), options.include_async_edges);
+        let index: FxHashMap<&str, usize> = functions.iter().enumerate().map(|(i, f)| (f.0.as_str(), i)).collect();
+        
+        // Each function's access that passes the filters: tables, sensitive
+        // fields, and whether any of it counts towards depth
+        let mut tables: Vec<Vec<String>> = vec![Vec::new(); functions.len()];
+        let mut sensitive: Vec<Vec<(String, SensitivityType)>> = vec![Vec::new(); functions.len()];
+        let mut counted: Vec<bool> = vec![false; functions.len()];
+        for (function_id, table, operation, fields_json, _) in self.load_data_access() {
+            let Some(&i) = index.get(function_id.as_str()) else { continue };
+            let point = DataAccessPoint {
+                table,
+                operation: DataOperation::parse(&operation).unwrap_or(DataOperation::Read),
+                fields: serde_json::from_str(&fields_json).unwrap_or_default(),
+                file: functions[i].2.clone(),
+                line: 0,
+                confidence: 0.9,
+                framework: None,
+            };
+            if !options.includes(&point) {
+                continue;
+            }
+            let fields: Vec<(String, SensitivityType)> = point.fields
+                .iter()
+                .map(|f| (f, Self::classify_sensitivity(f)))
+                .filter(|(_, sensitivity)| *sensitivity != SensitivityType::Unknown)
+                .map(|(f, sensitivity)| (format!("{}.{}", point.table, f), sensitivity))
+                .collect();
+            counted[i] |= !options.sensitive_only || !fields.is_empty();
+            sensitive[i].extend(fields);
+            tables[i].push(point.table);
+        }
+        
+        functions
+            .par_iter()
+            .enumerate()
+            .filter(|(_, f)| f.3)
+            .map(|(start, (id, name, file, _))| {
+                let mut reached_tables: BTreeSet<&str> = BTreeSet::new();
+                let mut reached_sensitive: FxHashMap<&str, SensitivityType> = FxHashMap::default();
+                let mut deepest = 0;
+                
+                let mut visited: FxHashSet<usize> = FxHashSet::from_iter([start]);
+                let mut queue: VecDeque<(usize, u32)> = VecDeque::from([(start, 0)]);
+                while let Some((func, depth)) = queue.pop_front() {
+                    reached_tables.extend(tables[func].iter().map(|t| t.as_str()));
+                    reached_sensitive.extend(sensitive[func].iter().map(|(key, sensitivity)| (key.as_str(), *sensitivity)));
+                    if counted[func] {
+                        deepest = deepest.max(depth);
+                    }
+                    if depth < max_depth {
+                        for &callee in &callees[func] {
+                            if visited.insert(callee) {
+                                queue.push_back((callee, depth + 1));
+                            }
+                        }
+                    }
+                }
+                
+                let mut sensitive_field_counts: BTreeMap<String, u32> = BTreeMap::new();
+                for sensitivity in reached_sensitive.values() {
+                    *sensitive_field_counts.entry(sensitivity.as_str().to_string()).or_default() += 1;
+                }
+                (id.clone(), EntryPointReachability {
+                    entry_point: id.clone(),
+                    name: name.clone(),
+                    file: file.clone(),
+                    tables: reached_tables.into_iter().map(String::from).collect(),
+                    sensitive_field_counts,
+                    max_depth: deepest,
+                    functions_traversed: visited.len() as u32,
+                })
+            })
+            .collect()
+    }
+    
+    /// Every sensitive field each entry point reaches, per table and
+    /// operation, sorted by entry point, sensitivity type, table, field and
+    /// operation
+    ///
+    /// `detections` decide what is sensitive. An access touches a sensitive
+    /// field when a detection names one of its fields, or was found on the
+    /// access's own line (embedded SQL rarely yields field lists); a
+    /// detection that knows its table only applies to that table. The most
+    /// confident detection gives the sensitivity type.
+    pub fn sensitivity_matrix(
+        &self,
+        detections: &[DetectedSensitiveField],
+        relations: &[EagerRelation],
+        options: &SensitivityMatrixOptions,
+    ) -> Vec<SensitivityMatrixRow> {
+        let max_depth = options.max_depth.unwrap_or(100);
+        
+        let mut by_field: FxHashMap<String, Vec<&DetectedSensitiveField>> = FxHashMap::default();
+        let mut by_site: FxHashMap<(&str, u32), Vec<&DetectedSensitiveField>> = FxHashMap::default();
+        for detection in detections {
+            by_field.entry(detection.field.to_lowercase()).or_default().push(detection);
+            by_site.entry((detection.file.as_str(), detection.line)).or_default().push(detection);
+        }
+        let applies = |detection: &&&DetectedSensitiveField, table: &str| {
+            detection.table.as_ref().is_none_or(|t| t.eq_ignore_ascii_case(table))
+        };
+        let classify = |table: &str, field: &str| {
+            by_field
+                .get(&field.to_lowercase())?
+                .iter()
+                .filter(|d| applies(d, table))
+                .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+                .map(|d| SensitivityType::from(d.sensitivity_type))
+        };
+        
+        let (functions, callees) = self.load_call_graph(options.edge_kinds.as_deref(), false);
+        let index: FxHashMap<&str, usize> = functions.iter().enumerate().map(|(i, f)| (f.0.as_str(), i)).collect();
+        
+        // Each function's sensitive access: (sensitivity, table, field, operation)
+        let mut sensitive: Vec<Vec<(SensitivityType, String, String, DataOperation)>> = vec![Vec::new(); functions.len()];
+        for (function_id, table, operation, fields_json, line) in self.load_data_access() {
+            let Some(&i) = index.get(function_id.as_str()) else { continue };
+            let operation = DataOperation::parse(&operation).unwrap_or(DataOperation::Read);
+            let fields: Vec<String> = serde_json::from_str(&fields_json).unwrap_or_default();
+            let named = fields
+                .into_iter()
+                .filter_map(|field| Some((classify(&table, &field)?, field)));
+            let on_line = by_site
+                .get(&(functions[i].2.as_str(), line))
+                .into_iter()
+                .flatten()
+                .filter(|d| applies(d, &table))
+                .filter_map(|d| Some((classify(&table, &d.field)?, d.field.to_lowercase())));
+            let mut found: Vec<(SensitivityType, String, String)> = named
+                .chain(on_line)
+                .map(|(sensitivity, field)| (sensitivity, table.clone(), field))
+                .collect();
+            // Reads also reach the related tables eager relations load
+            if operation == DataOperation::Read {
+                for relation in eager_loads(&table, relations) {
+                    found.extend(relation.fields.iter().filter_map(|field| {
+                        Some((classify(&relation.related_table, field)?, relation.related_table.clone(), field.clone()))
+                    }));
+                }
+            }
+            for (sensitivity, table, field) in found {
+                if options.sensitivity_types.as_ref().is_none_or(|types| types.contains(&sensitivity)) {
+                    sensitive[i].push((sensitivity, table, field, operation));
+                }
+            }
+        }
+        for list in &mut sensitive {
+            list.sort_unstable();
+            list.dedup();
+        }
+        
+        let mut rows: Vec<SensitivityMatrixRow> = functions
+            .par_iter()
+            .enumerate()
+            .filter(|(_, f)| f.3)
+            .flat_map_iter(|(start, (id, ..))| {
+                // BFS counting the shortest paths to each function
+                let mut depth: FxHashMap<usize, u32> = FxHashMap::from_iter([(start, 0)]);
+                let mut path_count: FxHashMap<usize, u64> = FxHashMap::from_iter([(start, 1)]);
+                let mut order: Vec<usize> = Vec::new();
+                let mut queue: VecDeque<usize> = VecDeque::from([start]);
+                while let Some(func) = queue.pop_front() {
+                    order.push(func);
+                    let (d, count) = (depth[&func], path_count[&func]);
+                    if d >= max_depth {
+                        continue;
+                    }
+                    for &callee in &callees[func] {
+                        match depth.get(&callee) {
+                            None => {
+                                depth.insert(callee, d + 1);
+                                path_count.insert(callee, count);
+                                queue.push_back(callee);
+                            }
+                            Some(&callee_depth) if callee_depth == d + 1 => {
+                                let total = path_count.get_mut(&callee).unwrap();
+                                *total = total.saturating_add(count);
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                }
+                
+                let mut cells: BTreeMap<&(SensitivityType, String, String, DataOperation), (u64, u32)> = BTreeMap::new();
+                for func in order {
+                    for key in &sensitive[func] {
+                        let cell = cells.entry(key).or_insert((0, depth[&func]));
+                        cell.0 = cell.0.saturating_add(path_count[&func]);
+                    }
+                }
+                cells
+                    .into_iter()
+                    .map(|((sensitivity_type, table, field, operation), (path_count, min_depth))| SensitivityMatrixRow {
+                        entry_point: id.clone(),
+                        sensitivity_type: *sensitivity_type,
+                        table: table.clone(),
+                        field: field.clone(),
+                        operation: *operation,
+                        path_count,
+                        min_depth,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        rows.sort_by(|a, b| a.entry_point.cmp(&b.entry_point));
+        rows
+    }
+    
+    /// Whether `to_id` can be reached from `from_id` through resolved calls,
+    /// with the shortest path and how many distinct paths there are (up to a
+    /// limit)
+    pub fn find_paths(&self, from_id: &str, to_id: &str, options: &FunctionPathOptions) -> FunctionReachability {
+        let max_depth = options.max_depth.unwrap_or(100);
+        let mut edges: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        let mut nodes: FxHashMap<String, CallPathNode> = FxHashMap::default();
+        
+        let mut queue: VecDeque<(String, u32)> = VecDeque::from([(from_id.to_string(), 0)]);
+        while let Some((func_id, depth)) = queue.pop_front() {
+            if nodes.contains_key(&func_id) || depth > max_depth {
+                continue;
+            }
+            let Some(func) = self.get_function_info(&func_id) else { continue };
+            nodes.insert(func_id.clone(), CallPathNode {
+                function_id: func_id.clone(),
+                function_name: func.name,
+                file: func.file,
+                line: func.start_line,
+            });
+            
+            for resolved_id in self.get_resolved_calls(&func_id, options.edge_kinds.as_deref(), false) {
+                if !nodes.contains_key(&resolved_id) {
+                    queue.push_back((resolved_id.clone(), depth + 1));
+                }
+                edges.entry(func_id.clone()).or_default().push(resolved_id);
+            }
+        }
+        
+        function_reachability(&edges, &nodes, from_id, to_id, max_depth, options.max_paths.unwrap_or(DEFAULT_MAX_PATHS))
     }
 
     /// Inverse query: "Who can reach this data?"
     pub fn get_code_paths_to_data(
         &self,
         options: &InverseReachabilityOptions,
+    ) -> InverseReachabilityResult {
+        self.collect_code_paths_to_data(options, InverseCollector::new(options))
+    }
+    
+    /// Inverse query handing each path to `on_path` as soon as it is found
+    ///
+    /// Paths are found one entry point and accessor at a time, so the first
+    /// arrive before the rest are searched. The result holds the same
+    /// summaries as `get_code_paths_to_data`, without the paths. Only
+    /// `max_paths_per_entry_point` caps the paths streamed.
+    pub fn get_code_paths_to_data_with(
+        &self,
+        options: &InverseReachabilityOptions,
+        on_path: impl FnMut(InverseAccessPath),
+    ) -> InverseReachabilityResult {
+        self.collect_code_paths_to_data(options, InverseCollector::with_sink(options, on_path))
+    }
+    
+    fn collect_code_paths_to_data(
+        &self,
+        options: &InverseReachabilityOptions,
+        mut collector: InverseCollector,
     ) -> InverseReachabilityResult {
         let max_depth = options.max_depth.unwrap_or(100);
         
         // Find all functions that directly access this table
-        let direct_accessors = self.get_table_accessors(&options.table, options.field.as_deref());
+        let direct_accessors = self.get_table_accessors(options);
         
         // For each direct accessor, find all paths from entry points
-        let mut access_paths: Vec<InverseAccessPath> = Vec::new();
-        let mut reaching_entry_points: HashSet<String> = HashSet::new();
         
         // Get all entry points
         let entry_points = self.get_entry_points();
         
         for accessor_id in &direct_accessors {
             // Get the access point info
-            let access_points = self.get_data_access(accessor_id);
-            let access_point = access_points.into_iter().find(|a| {
-                a.table == options.table
-                    && options.field.as_ref().map_or(true, |f| a.fields.contains(f))
-            });
+            let access_points: Vec<DataAccessPoint> = self.get_data_access(accessor_id)
+                .into_iter()
+                .filter(|a| options.matches(a))
+                .collect();
             
-            if let Some(access_point) = access_point {
+            if !access_points.is_empty() {
                 // Find paths from entry points to this accessor
                 for entry_point in &entry_points {
                     let paths = self.find_paths_bfs(entry_point, accessor_id, max_depth);
                     for path in paths {
-                        reaching_entry_points.insert(entry_point.clone());
-                        access_paths.push(InverseAccessPath {
-                            entry_point: entry_point.clone(),
-                            path,
-                            access_point: access_point.clone(),
-                        });
+                        let confidence = call_path_confidence(&path, |caller, callee| self.call_confidence(caller, callee));
+                        collector.add(entry_point, path, confidence, &access_points);
                     }
                 }
             }
         }
         
-        InverseReachabilityResult {
-            target: InverseTarget {
-                table: options.table.clone(),
-                field: options.field.clone(),
-            },
-            access_paths,
-            entry_points: reaching_entry_points.into_iter().collect(),
-            total_accessors: direct_accessors.len() as u32,
-        }
+        collector.finish(direct_accessors.len() as u32)
+    }
+    
+    /// Entry points that reach both the target data and an outbound HTTP call
+    ///
+    /// "Which entry points can send user.email to a third-party host": the
+    /// entry points of `get_code_paths_to_data(options)` that also reach a
+    /// function accessing an `external:` sink, with the hosts they reach,
+    /// limited to `host` when given. Both must be reachable from the entry
+    /// point; whether the data actually flows into the request isn't tracked.
+    pub fn get_external_exposure(
+        &self,
+        options: &InverseReachabilityOptions,
+        host: Option<&str>,
+    ) -> Vec<ExternalExposure> {
+        let max_depth = options.max_depth.unwrap_or(100);
+        let data_entry_points = self.get_code_paths_to_data(&InverseReachabilityOptions {
+            group_by_entry_point: true,
+            include_paths: false,
+            ..options.clone()
+        }).entry_points;
+        
+        let (sql, key) = match host {
+            Some(host) => ("SELECT DISTINCT function_id, table_name FROM data_access WHERE table_name = ?1", format!("{}{}", EXTERNAL_TABLE_PREFIX, host)),
+            None => ("SELECT DISTINCT function_id, table_name FROM data_access WHERE table_name GLOB ?1", format!("{}*", EXTERNAL_TABLE_PREFIX)),
+        };
+        let mut stmt = self.conn.prepare_cached(sql).unwrap();
+        let sinks: Vec<(String, String)> = stmt
+            .query_map(params![key], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        
+        data_entry_points.into_iter()
+            .filter_map(|entry_point| {
+                let hosts: BTreeSet<String> = sinks.iter()
+                    .filter(|(accessor, _)| !self.find_paths_bfs(&entry_point, accessor, max_depth).is_empty())
+                    .map(|(_, table)| table[EXTERNAL_TABLE_PREFIX.len()..].to_string())
+                    .collect();
+                (!hosts.is_empty()).then(|| ExternalExposure { entry_point, hosts: hosts.into_iter().collect() })
+            })
+            .collect()
     }
     
     // ========================================================================
     // SQL Query Methods
     // ========================================================================
     
+    /// Every function and the distinct functions each one calls, following
+    /// only the calls `follows_call_kind` lets through
+    fn load_call_graph(&self, edge_kinds: Option<&[String]>, include_async: bool) -> (Vec<FunctionRow>, Vec<Vec<usize>>) {
+        let functions: Vec<FunctionRow> = self.conn
+            .prepare("SELECT id, name, file, is_entry_point FROM functions")
+            .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?.collect())
+            .unwrap_or_default();
+        let index: FxHashMap<&str, usize> = functions.iter().enumerate().map(|(i, f)| (f.0.as_str(), i)).collect();
+        
+        let mut callees: Vec<Vec<usize>> = vec![Vec::new(); functions.len()];
+        let calls: Vec<(String, String, String)> = self.conn
+            .prepare(&format!("SELECT caller_id, resolved_id, {} FROM calls WHERE resolved_id IS NOT NULL", CALL_KIND))
+            .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?.collect())
+            .unwrap_or_default();
+        for (caller, callee, kind) in &calls {
+            if !follows_call_kind(kind, edge_kinds, include_async) {
+                continue;
+            }
+            if let (Some(&from), Some(&to)) = (index.get(caller.as_str()), index.get(callee.as_str())) {
+                callees[from].push(to);
+            }
+        }
+        for list in &mut callees {
+            list.sort_unstable();
+            list.dedup();
+        }
+        
+        (functions, callees)
+    }
+    
+    /// Every data access row: (function_id, table, operation, fields JSON, line)
+    fn load_data_access(&self) -> Vec<(String, String, String, String, u32)> {
+        self.conn
+            .prepare("SELECT function_id, table_name, operation, fields, line FROM data_access")
+            .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))?.collect())
+            .unwrap_or_default()
+    }
+    
+    /// The graph's current content version
+    fn content_version(&self) -> Option<String> {
+        self.conn
+            .query_row("SELECT value FROM metadata WHERE key = ?1", params![CONTENT_VERSION_KEY], |row| row.get(0))
+            .ok()
+    }
+    
     /// Get function info by ID
     fn get_function_info(&self, id: &str) -> Option<FunctionInfo> {
         self.conn
@@ -245,18 +696,82 @@ impl SqliteReachabilityEngine {
             .ok()
     }
     
-    /// Get resolved calls from a function
-    fn get_resolved_calls(&self, caller_id: &str) -> Vec<String> {
+    /// Get resolved calls from a function that `follows_call_kind` lets through
+    fn get_resolved_calls(&self, caller_id: &str, kinds: Option<&[String]>, include_async: bool) -> Vec<String> {
         let mut stmt = self.conn
-            .prepare_cached("SELECT resolved_id FROM calls WHERE caller_id = ?1 AND resolved_id IS NOT NULL")
+            .prepare_cached(&format!(
+                "SELECT resolved_id, {} FROM calls WHERE caller_id = ?1 AND resolved_id IS NOT NULL",
+                CALL_KIND
+            ))
             .unwrap();
         
-        stmt.query_map(params![caller_id], |row| row.get(0))
+        stmt.query_map(params![caller_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
             .unwrap()
             .filter_map(|r| r.ok())
+            .filter(|(_, kind)| follows_call_kind(kind, kinds, include_async))
+            .map(|(resolved_id, _)| resolved_id)
             .collect()
     }
     
+    /// Confidence of the most certain resolved call from `caller_id` to
+    /// `callee_id`, or `UNRESOLVED_CALL_CONFIDENCE` when only an unresolved
+    /// call leads there
+    fn call_confidence(&self, caller_id: &str, callee_id: &str) -> f64 {
+        self.conn
+            .prepare_cached("SELECT MAX(confidence) FROM calls WHERE caller_id = ?1 AND resolved_id = ?2")
+            .and_then(|mut stmt| stmt.query_row(params![caller_id, callee_id], |row| row.get::<_, Option<f64>>(0)))
+            .ok()
+            .flatten()
+            .unwrap_or(UNRESOLVED_CALL_CONFIDENCE)
+    }
+    
+    /// Whether every resolved call from `caller_id` to `callee_id` is async
+    fn is_async_call(&self, caller_id: &str, callee_id: &str) -> bool {
+        self.conn
+            .prepare_cached(&format!(
+                "SELECT MIN({} = 'async') FROM calls WHERE caller_id = ?1 AND resolved_id = ?2",
+                CALL_KIND
+            ))
+            .and_then(|mut stmt| stmt.query_row(params![caller_id, callee_id], |row| row.get::<_, Option<bool>>(0)))
+            .ok()
+            .flatten()
+            .unwrap_or(false)
+    }
+    
+    /// Callee names of a function's unresolved calls that `follows_call_kind`
+    /// lets through
+    fn get_unresolved_targets(&self, caller_id: &str, kinds: Option<&[String]>, include_async: bool) -> Vec<String> {
+        let mut stmt = self.conn
+            .prepare_cached(&format!(
+                "SELECT target, {} FROM calls WHERE caller_id = ?1 AND resolved_id IS NULL",
+                CALL_KIND
+            ))
+            .unwrap();
+        
+        stmt.query_map(params![caller_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .filter(|(_, kind)| follows_call_kind(kind, kinds, include_async))
+            .map(|(target, _)| target)
+            .collect()
+    }
+    
+    /// IDs of functions whose bare method name is `name`
+    fn functions_named(&self, name: &str) -> &[String] {
+        let by_name = self.by_name.get_or_init(|| {
+            let functions: Vec<(String, String)> = self.conn
+                .prepare("SELECT id, name FROM functions ORDER BY id")
+                .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect())
+                .unwrap_or_default();
+            let mut by_name: FxHashMap<String, Vec<String>> = FxHashMap::default();
+            for (id, name) in functions {
+                by_name.entry(method_name(&name).to_string()).or_default().push(id);
+            }
+            by_name
+        });
+        by_name.get(name).map_or(&[], |ids| &ids[..])
+    }
+    
     /// Get data access points for a function
     fn get_data_access(&self, function_id: &str) -> Vec<DataAccessPoint> {
         let mut stmt = self.conn
@@ -290,33 +805,49 @@ impl SqliteReachabilityEngine {
     }
 
     /// Get functions that access a specific table
-    fn get_table_accessors(&self, table: &str, field: Option<&str>) -> Vec<String> {
-        let mut stmt = self.conn
-            .prepare_cached(
-                "SELECT DISTINCT function_id FROM data_access WHERE table_name = ?1"
-            )
-            .unwrap();
+    fn get_table_accessors(&self, options: &InverseReachabilityOptions) -> Vec<String> {
+        // Without a table, a field pattern narrows the scan through its index
+        let (sql, key) = match (&options.table, &options.field) {
+            (Some(table), _) => ("SELECT DISTINCT function_id FROM data_access WHERE table_name = ?1", table.clone()),
+            (None, Some(field)) => (
+                "SELECT DISTINCT data_access.function_id FROM data_access_fields
+                 JOIN data_access ON data_access.id = data_access_fields.access_id
+                 WHERE data_access_fields.field GLOB ?1",
+                field_glob(field),
+            ),
+            (None, None) => ("SELECT DISTINCT function_id FROM data_access WHERE ?1 = ''", String::new()),
+        };
+        let mut stmt = self.conn.prepare_cached(sql).unwrap();
         
         let accessors: Vec<String> = stmt
-            .query_map(params![table], |row| row.get(0))
+            .query_map(params![key], |row| row.get(0))
             .unwrap()
             .filter_map(|r| r.ok())
             .collect();
         
-        // Filter by field if specified
-        if let Some(field) = field {
+        // Filter by field and operation if specified
+        if options.field.is_some() || options.operations.is_some() {
             accessors
                 .into_iter()
-                .filter(|func_id| {
-                    let access = self.get_data_access(func_id);
-                    access.iter().any(|a| a.fields.contains(&field.to_string()))
-                })
+                .filter(|func_id| self.get_data_access(func_id).iter().any(|a| options.matches(a)))
                 .collect()
         } else {
             accessors
         }
     }
     
+    /// Get all files with functions
+    pub fn get_files(&self) -> Vec<String> {
+        let mut stmt = self.conn
+            .prepare_cached("SELECT DISTINCT file FROM functions ORDER BY file")
+            .unwrap();
+        
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect()
+    }
+    
     /// Get all entry points
     fn get_entry_points(&self) -> Vec<String> {
         let mut stmt = self.conn
@@ -343,7 +874,30 @@ impl SqliteReachabilityEngine {
             .ok()
     }
     
+    /// Resolved call graph with its strongly connected components
+    fn condensation(&self) -> &Condensation {
+        self.condensation.get_or_init(|| {
+            let ids: Vec<String> = self.conn
+                .prepare("SELECT id FROM functions")
+                .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+                .unwrap_or_default();
+            let edges: Vec<(String, String)> = self.conn
+                .prepare(&format!(
+                    "SELECT caller_id, resolved_id FROM calls WHERE resolved_id IS NOT NULL AND {} != 'async'",
+                    CALL_KIND
+                ))
+                .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect())
+                .unwrap_or_default();
+            Condensation::new(ids, edges)
+        })
+    }
+    
     /// BFS to find paths between two functions
+    ///
+    /// Walks the SCC condensation rather than raw calls: a path crosses each
+    /// recursion cycle once, along the shortest route between where it enters
+    /// and where it leaves, so cycles can't multiply the paths. Only calls
+    /// into functions that can still reach the target are followed.
     fn find_paths_bfs(
         &self,
         from_id: &str,
@@ -351,62 +905,70 @@ impl SqliteReachabilityEngine {
         max_depth: u32,
     ) -> Vec<Vec<CallPathNode>> {
         let mut paths: Vec<Vec<CallPathNode>> = Vec::new();
-        let mut queue: VecDeque<(String, Vec<CallPathNode>, u32)> = VecDeque::new();
-        let mut visited: FxHashSet<(String, u32)> = FxHashSet::default();
+        let graph = self.condensation();
         
-        let from_func = match self.get_function_info(from_id) {
-            Some(f) => f,
-            None => return paths,
+        let (from, to) = match (graph.node(from_id), graph.node(to_id)) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return paths,
         };
+        let reaches_target = graph.reaching(to);
+        if !reaches_target[from] {
+            return paths;
+        }
+        let target_component = graph.component(to);
         
-        queue.push_back((
-            from_id.to_string(),
-            vec![CallPathNode {
-                function_id: from_id.to_string(),
-                function_name: from_func.name.clone(),
-                file: from_func.file.clone(),
-                line: from_func.start_line,
-            }],
-            0,
-        ));
+        // Queue of node paths, each ending where it entered a component
+        let mut queue: VecDeque<Vec<usize>> = VecDeque::new();
+        let mut visited: FxHashSet<(usize, usize)> = FxHashSet::default();
+        queue.push_back(vec![from]);
         
-        while let Some((func_id, path, depth)) = queue.pop_front() {
-            if depth > max_depth {
-                continue;
-            }
+        while let Some(path) = queue.pop_front() {
+            let entry = *path.last().unwrap();
+            let depth = path.len() - 1;
+            let component = graph.component(entry);
             
-            // Check if we've reached the target
-            if func_id == to_id {
-                paths.push(path);
+            // Check if we've reached the target's component
+            if component == target_component {
+                let mut full = path;
+                full.extend(graph.internal_path(entry, to).unwrap_or_default().into_iter().skip(1));
+                if full.len() - 1 <= max_depth as usize {
+                    paths.push(full.into_iter().filter_map(|n| self.path_node(graph.id(n))).collect());
+                }
                 continue;
             }
             
-            // Skip if we've visited this node at this or lower depth
-            let visit_key = (func_id.clone(), depth);
-            if visited.contains(&visit_key) {
+            // Skip if we've entered this node at this depth already
+            if !visited.insert((entry, depth)) {
                 continue;
             }
-            visited.insert(visit_key);
             
-            // Get resolved calls and follow them
-            let calls = self.get_resolved_calls(&func_id);
-            for resolved_id in calls {
-                if let Some(candidate) = self.get_function_info(&resolved_id) {
-                    let mut new_path = path.clone();
-                    new_path.push(CallPathNode {
-                        function_id: resolved_id.clone(),
-                        function_name: candidate.name.clone(),
-                        file: candidate.file.clone(),
-                        line: candidate.start_line,
-                    });
-                    
-                    queue.push_back((resolved_id, new_path, depth + 1));
+            // Leave the component through every call that can still reach the target
+            for (exit, next) in graph.exits(component) {
+                if !reaches_target[next] {
+                    continue;
                 }
+                let inner = graph.internal_path(entry, exit).unwrap_or_default();
+                if depth + inner.len() > max_depth as usize {
+                    continue;
+                }
+                let mut new_path = path.clone();
+                new_path.extend(inner.into_iter().skip(1));
+                new_path.push(next);
+                queue.push_back(new_path);
             }
         }
         
         paths
     }
+    
+    fn path_node(&self, id: &str) -> Option<CallPathNode> {
+        self.get_function_info(id).map(|f| CallPathNode {
+            function_id: f.id,
+            function_name: f.name,
+            file: f.file,
+            line: f.start_line,
+        })
+    }
 
     // ========================================================================
     // Result Building
@@ -482,11 +1044,15 @@ impl SqliteReachabilityEngine {
             sensitive_fields: sensitive_fields_map.into_values().collect(),
             max_depth,
             functions_traversed,
+            unresolved_edges_traversed: 0,
+            cache_hit: false,
+            truncated: false,
+            truncation_reason: None,
         }
     }
     
     /// Classify sensitivity type based on field name
-    fn classify_sensitivity(field: &str) -> SensitivityType {
+    pub(crate) fn classify_sensitivity(field: &str) -> SensitivityType {
         let field_lower = field.to_lowercase();
         
         if CREDENTIAL_PATTERNS.iter().any(|p| field_lower.contains(p)) {
@@ -514,6 +1080,10 @@ impl SqliteReachabilityEngine {
             sensitive_fields: Vec::new(),
             max_depth: 0,
             functions_traversed: 0,
+            unresolved_edges_traversed: 0,
+            cache_hit: false,
+            truncated: false,
+            truncation_reason: None,
         }
     }
 }
@@ -529,11 +1099,22 @@ struct FunctionInfo {
     is_entry_point: bool,
 }
 
+/// A `field_matches` pattern as a SQLite GLOB: `*` stays a wildcard,
+/// GLOB's other metacharacters match literally
+fn field_glob(pattern: &str) -> String {
+    pattern.chars().map(|c| match c {
+        '?' => "[?]".to_string(),
+        '[' => "[[]".to_string(),
+        c => c.to_string(),
+    }).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
-    use crate::call_graph::{CallGraphDb, FunctionBatch, FunctionEntry, CallEntry, DataAccessRef, DataOperation as CgDataOperation};
+    use crate::call_graph::{CallGraphDb, FunctionBatch, FunctionEntry, CallEntry, CallKind, DataAccessRef, DataOperation as CgDataOperation};
+    use crate::call_graph::fixtures;
     
     fn create_test_db() -> (tempfile::TempDir, std::path::PathBuf) {
         let dir = tempdir().unwrap();
@@ -541,13 +1122,15 @@ mod tests {
         
         let mut db = CallGraphDb::open(&db_path).unwrap();
         
-        // Create test data: main -> service -> repository
+        // Create test data: main -(callback)-> service -> repository
         let main_func = FunctionEntry {
             id: "main.ts:main:1".to_string(),
             name: "main".to_string(),
             start_line: 1,
             end_line: 10,
             is_entry_point: true,
+            is_exported: true,
+            entry_kind: None,
             is_data_accessor: false,
             calls: vec![CallEntry {
                 target: "getUsers".to_string(),
@@ -555,6 +1138,9 @@ mod tests {
                 resolved: true,
                 confidence: 0.95,
                 line: 5,
+                receiver: None,
+                receiver_type: None,
+                kind: CallKind::Callback,
             }],
             called_by: vec![],
             data_access: vec![],
@@ -566,6 +1152,8 @@ mod tests {
             start_line: 1,
             end_line: 10,
             is_entry_point: false,
+            is_exported: false,
+            entry_kind: None,
             is_data_accessor: false,
             calls: vec![CallEntry {
                 target: "findAll".to_string(),
@@ -573,6 +1161,9 @@ mod tests {
                 resolved: true,
                 confidence: 0.95,
                 line: 5,
+                receiver: None,
+                receiver_type: None,
+                kind: CallKind::Direct,
             }],
             called_by: vec![],
             data_access: vec![],
@@ -584,6 +1175,8 @@ mod tests {
             start_line: 1,
             end_line: 10,
             is_entry_point: false,
+            is_exported: false,
+            entry_kind: None,
             is_data_accessor: true,
             calls: vec![],
             called_by: vec![],
@@ -598,16 +1191,28 @@ mod tests {
         db.insert_batch(&FunctionBatch {
             file: "main.ts".to_string(),
             functions: vec![main_func],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
         }).unwrap();
         
         db.insert_batch(&FunctionBatch {
             file: "service.ts".to_string(),
             functions: vec![service_func],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
         }).unwrap();
         
         db.insert_batch(&FunctionBatch {
             file: "repo.ts".to_string(),
             functions: vec![repo_func],
+            imports: vec![],
+            types: vec![],
+            di_bindings: vec![],
+            async_handlers: vec![],
         }).unwrap();
         
         (dir, db_path)
@@ -631,6 +1236,430 @@ mod tests {
         assert_eq!(result.max_depth, 2);
     }
     
+    #[test]
+    fn test_sqlite_reachability_edge_kinds() {
+        let (_dir, db_path) = create_test_db();
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap();
+        
+        let without_callbacks = engine.get_reachable_data_from_function(
+            "main.ts:main:1",
+            &ReachabilityOptions {
+                edge_kinds: Some(vec!["direct".to_string(), "method".to_string()]),
+                ..Default::default()
+            },
+        );
+        assert!(without_callbacks.reachable_access.is_empty());
+        
+        let with_callbacks = engine.get_reachable_data_from_function(
+            "main.ts:main:1",
+            &ReachabilityOptions {
+                edge_kinds: Some(vec!["direct".to_string(), "callback".to_string()]),
+                ..Default::default()
+            },
+        );
+        assert_eq!(with_callbacks.reachable_access.len(), 1);
+    }
+    
+    #[test]
+    fn test_sqlite_find_paths() {
+        let (_dir, db_path) = create_test_db();
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap();
+        
+        let found = engine.find_paths("main.ts:main:1", "repo.ts:findAll:1", &FunctionPathOptions::default());
+        assert!(found.reachable);
+        assert_eq!((found.path_count, found.min_depth), (1, Some(2)));
+        let names: Vec<&str> = found.shortest_path.iter().map(|n| n.function_name.as_str()).collect();
+        assert_eq!(names, vec!["main", "getUsers", "findAll"]);
+        
+        // The only route starts with a callback
+        let direct_only = engine.find_paths("main.ts:main:1", "repo.ts:findAll:1", &FunctionPathOptions {
+            edge_kinds: Some(vec!["direct".to_string()]),
+            ..Default::default()
+        });
+        assert!(!direct_only.reachable);
+        
+        assert!(!engine.find_paths("repo.ts:findAll:1", "main.ts:main:1", &FunctionPathOptions::default()).reachable);
+        assert_eq!(engine.find_paths("main.ts:main:1", "main.ts:main:1", &FunctionPathOptions::default()).min_depth, Some(0));
+    }
+    
+    #[test]
+    fn test_sqlite_multiple_paths_per_access() {
+        use fixtures::{access, accessor, function, resolved_call};
+        let dir = tempdir().unwrap();
+        
+        // main -> a -> findAll and main -> b -> findAll, with a and b calling each other
+        drop(fixtures::sample_db(dir.path(), vec![
+            function("main.ts:main:1", vec![resolved_call("svc.ts:a:1"), resolved_call("svc.ts:b:1")]),
+            function("svc.ts:a:1", vec![resolved_call("repo.ts:findAll:1"), resolved_call("svc.ts:b:1")]),
+            function("svc.ts:b:1", vec![resolved_call("svc.ts:a:1"), resolved_call("repo.ts:findAll:1")]),
+            accessor("repo.ts:findAll:1", vec![], vec![access("users", CgDataOperation::Read, &["id"])]),
+        ]));
+        
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        let names = |path: &Vec<CallPathNode>| path.iter().map(|n| n.function_name.as_str()).collect::<Vec<_>>().join(">");
+        
+        let single = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions::default());
+        assert_eq!(single.reachable_access[0].paths.len(), 1);
+        assert_eq!(names(&single.reachable_access[0].path), "main>a>findAll");
+        
+        let all = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            max_paths_per_access: Some(10),
+            ..Default::default()
+        });
+        let access = &all.reachable_access[0];
+        assert_eq!(names(&access.path), "main>a>findAll");
+        assert_eq!(
+            access.paths.iter().map(names).collect::<Vec<_>>(),
+            vec!["main>a>findAll", "main>b>findAll", "main>a>b>findAll", "main>b>a>findAll"],
+        );
+        
+        let bounded = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            max_paths_per_access: Some(3),
+            max_depth: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(bounded.reachable_access[0].paths.iter().map(names).collect::<Vec<_>>(), vec!["main>a>findAll", "main>b>findAll"]);
+    }
+    
+    #[test]
+    fn test_reachability_cache() {
+        let (_dir, db_path) = create_test_db();
+        let cache = Arc::new(ReachabilityCache::new(2));
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap().with_cache(cache.clone());
+        let options = ReachabilityOptions::default();
+        let sensitive = ReachabilityOptions { sensitive_only: true, ..Default::default() };
+        
+        let first = engine.get_reachable_data_from_function("main.ts:main:1", &options);
+        let second = engine.get_reachable_data_from_function("main.ts:main:1", &options);
+        assert!(!first.cache_hit && second.cache_hit);
+        assert_eq!(second.tables, first.tables);
+        assert!(!engine.get_reachable_data_from_function("main.ts:main:1", &sensitive).cache_hit);
+        assert_eq!(cache.len(), 2);
+        
+        // The least recently used result makes room
+        assert!(engine.get_reachable_data_from_function("main.ts:main:1", &options).cache_hit);
+        engine.get_reachable_data_from_function("service.ts:getUsers:1", &options);
+        assert_eq!(cache.len(), 2);
+        assert!(engine.get_reachable_data_from_function("main.ts:main:1", &options).cache_hit);
+        assert!(!engine.get_reachable_data_from_function("main.ts:main:1", &sensitive).cache_hit);
+        
+        // Partial results aren't cached
+        let budget = ReachabilityOptions { max_functions: Some(1), ..Default::default() };
+        let capped = engine.get_reachable_data_from_function("main.ts:main:1", &budget);
+        assert_eq!(capped.truncation_reason, Some(TruncationReason::MaxFunctions));
+        assert!(!engine.get_reachable_data_from_function("main.ts:main:1", &budget).cache_hit);
+        
+        // Writing to the graph retires every cached result
+        CallGraphDb::open(&db_path).unwrap().delete_files(&["repo.ts".to_string()]).unwrap();
+        let after = engine.get_reachable_data_from_function("main.ts:main:1", &options);
+        assert!(!after.cache_hit);
+        assert!(after.tables.is_empty());
+        
+        assert_eq!(cache.clear(), 2);
+        assert!(cache.is_empty());
+    }
+    
+    #[test]
+    fn test_sqlite_unresolved_modes() {
+        use fixtures::{access, accessor, function, unresolved_call};
+        let dir = tempdir().unwrap();
+        let writer = |id: &str, table: &str| accessor(id, vec![], vec![access(table, CgDataOperation::Write, &[])]);
+        
+        // main calls store.save, which two classes define, and flush, which one function defines
+        let save = CallEntry {
+            receiver: Some("store".to_string()),
+            kind: CallKind::Method,
+            ..unresolved_call("store.save")
+        };
+        drop(fixtures::sample_db(dir.path(), vec![
+            function("main.ts:main:1", vec![save, unresolved_call("flush")]),
+            writer("audit.ts:AuditLog.save:1", "audit_log"),
+            writer("cache.ts:Cache.save:1", "cache"),
+            writer("queue.ts:flush:1", "jobs"),
+        ]));
+        
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        let reach = |unresolved_mode, edge_kinds: Option<&[&str]>| {
+            let result = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+                unresolved_mode,
+                edge_kinds: edge_kinds.map(|kinds| kinds.iter().map(|k| k.to_string()).collect()),
+                ..Default::default()
+            });
+            let mut tables = result.tables;
+            tables.sort();
+            (tables, result.unresolved_edges_traversed)
+        };
+        let tables = |names: &[&str]| names.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        
+        assert_eq!(reach(UnresolvedMode::Ignore, None), (tables(&[]), 0));
+        assert_eq!(reach(UnresolvedMode::Pessimistic, None), (tables(&["audit_log", "cache", "jobs"]), 3));
+        assert_eq!(reach(UnresolvedMode::UniqueCandidate, None), (tables(&["jobs"]), 1));
+        assert_eq!(reach(UnresolvedMode::Pessimistic, Some(&["method"])), (tables(&["audit_log", "cache"]), 2));
+    }
+    
+    #[test]
+    fn test_sqlite_path_confidence() {
+        let (_dir, db_path) = create_test_db();
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        
+        let result = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions::default());
+        assert!(close(result.reachable_access[0].path_confidence, 0.95 * 0.95 * 0.9));
+        let trimmed = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            min_confidence: Some(0.85),
+            ..Default::default()
+        });
+        assert!(trimmed.reachable_access.is_empty());
+        // From one call closer the path clears the bar
+        let nearer = engine.get_reachable_data_from_function("service.ts:getUsers:1", &ReachabilityOptions {
+            min_confidence: Some(0.85),
+            ..Default::default()
+        });
+        assert!(close(nearer.reachable_access[0].path_confidence, 0.95 * 0.9));
+        
+        let inverse = |min_confidence| engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence,
+        });
+        let paths = inverse(Some(0.8)).access_paths;
+        assert_eq!(paths.len(), 1);
+        assert!(close(paths[0].path_confidence, 0.95 * 0.95 * 0.9));
+        assert!(inverse(Some(0.85)).access_paths.is_empty());
+    }
+    
+    #[test]
+    fn test_sqlite_async_edges() {
+        let (dir, db_path) = create_test_db();
+        // A route that enqueues a job handled by getUsers
+        let enqueue = CallEntry {
+            target: "load-users".to_string(),
+            confidence: 0.8,
+            kind: CallKind::Async,
+            ..fixtures::resolved_call("service.ts:getUsers:1")
+        };
+        drop(fixtures::sample_db(dir.path(), vec![fixtures::exported("signup.ts:signup:1", vec![enqueue])]));
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap();
+        let reach = |options: ReachabilityOptions| engine.get_reachable_data_from_function("signup.ts:signup:1", &options);
+        
+        assert!(reach(ReachabilityOptions::default()).reachable_access.is_empty());
+        let crossed = reach(ReachabilityOptions { include_async_edges: true, ..Default::default() });
+        assert_eq!(crossed.tables, vec!["users".to_string()]);
+        assert!(crossed.reachable_access[0].crosses_async);
+        let listed = reach(ReachabilityOptions {
+            edge_kinds: Some(vec!["async".to_string(), "direct".to_string()]),
+            ..Default::default()
+        });
+        assert_eq!(listed.reachable_access.len(), 1);
+        
+        // Paths without an async call don't cross a boundary
+        let sync = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            include_async_edges: true,
+            ..Default::default()
+        });
+        assert!(!sync.reachable_access[0].crosses_async);
+        
+        let all = |include_async_edges| engine.analyze_all_entry_points(&ReachabilityOptions {
+            include_async_edges,
+            ..Default::default()
+        });
+        assert!(all(false)["signup.ts:signup:1"].tables.is_empty());
+        assert_eq!(all(true)["signup.ts:signup:1"].tables, vec!["users".to_string()]);
+    }
+    
+    #[test]
+    fn test_sqlite_inverse_by_field() {
+        use fixtures::{access, accessor, exported, resolved_call};
+        let dir = tempdir().unwrap();
+        let reader = |id: &str, table: &str, fields: &[&str]| accessor(id, vec![], vec![access(table, CgDataOperation::Read, fields)]);
+        let mut db = fixtures::sample_db(dir.path(), vec![
+            exported("api.ts:profile:1", vec![resolved_call("repo.ts:loadUser:1"), resolved_call("hr.ts:loadEmployee:1")]),
+            exported("api.ts:login:20", vec![resolved_call("auth.ts:issue:1")]),
+            reader("repo.ts:loadUser:1", "users", &["id", "ssn", "api_token"]),
+            reader("hr.ts:loadEmployee:1", "employees", &["ssn"]),
+            reader("auth.ts:issue:1", "sessions", &["refresh_token"]),
+        ]);
+        
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        let by_field = |field: &str| engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: None,
+            field: Some(field.to_string()),
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        });
+        let summary = |result: &InverseReachabilityResult| result.tables
+            .iter()
+            .map(|t| format!("{}:{}:{}", t.table, t.fields.join(","), t.entry_points.join(",")))
+            .collect::<Vec<_>>();
+        
+        let ssn = by_field("ssn");
+        assert_eq!(summary(&ssn), vec!["employees:ssn:api.ts:profile:1", "users:ssn:api.ts:profile:1"]);
+        assert_eq!(ssn.total_accessors, 2);
+        assert_eq!(ssn.access_paths.len(), 2);
+        
+        let tokens = by_field("*_token");
+        assert_eq!(summary(&tokens), vec!["sessions:refresh_token:api.ts:login:20", "users:api_token:api.ts:profile:1"]);
+        assert!(by_field("token").tables.is_empty());
+        
+        // The field index follows deletes
+        db.delete_files(&["hr.ts".to_string()]).unwrap();
+        assert_eq!(summary(&by_field("ssn")), vec!["users:ssn:api.ts:profile:1"]);
+    }
+    
+    #[test]
+    fn test_external_exposure() {
+        use fixtures::{access, accessor, exported, resolved_call};
+        let dir = tempdir().unwrap();
+        drop(fixtures::sample_db(dir.path(), vec![
+            exported("api.ts:signup:1", vec![resolved_call("repo.ts:loadUser:1"), resolved_call("crm.ts:track:1")]),
+            exported("api.ts:profile:20", vec![resolved_call("repo.ts:loadUser:1")]),
+            exported("api.ts:health:40", vec![resolved_call("crm.ts:track:1")]),
+            accessor("repo.ts:loadUser:1", vec![], vec![access("users", CgDataOperation::Read, &["email"])]),
+            accessor("crm.ts:track:1", vec![], vec![
+                access("external:api.segment.io", CgDataOperation::Write, &[]),
+                access("external:hooks.slack.com", CgDataOperation::Write, &[]),
+            ]),
+        ]));
+        
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        let options = InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: Some("email".to_string()),
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        };
+        
+        let exposure = engine.get_external_exposure(&options, None);
+        assert_eq!(exposure.len(), 1);
+        assert_eq!(exposure[0].entry_point, "api.ts:signup:1");
+        assert_eq!(exposure[0].hosts, vec!["api.segment.io", "hooks.slack.com"]);
+        
+        let slack = engine.get_external_exposure(&options, Some("hooks.slack.com"));
+        assert_eq!(slack[0].hosts, vec!["hooks.slack.com"]);
+        assert!(engine.get_external_exposure(&options, Some("api.stripe.com")).is_empty());
+    }
+    
+    #[test]
+    fn test_field_index_backfill() {
+        let (_dir, db_path) = create_test_db();
+        Connection::open(&db_path).unwrap().execute_batch("DROP TABLE data_access_fields").unwrap();
+        drop(CallGraphDb::open(&db_path).unwrap());
+        
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap();
+        let result = engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: None,
+            field: Some("email".to_string()),
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        });
+        assert_eq!(result.total_accessors, 1);
+        assert_eq!(result.tables[0].table, "users");
+    }
+    
+    #[test]
+    fn test_sqlite_operation_filters() {
+        let (_dir, db_path) = create_test_db();
+        let engine = SqliteReachabilityEngine::open(&db_path).unwrap();
+        
+        // findAll only reads
+        let writes = engine.get_reachable_data_from_function("main.ts:main:1", &ReachabilityOptions {
+            operations: Some(vec![DataOperation::Write, DataOperation::Delete]),
+            ..Default::default()
+        });
+        assert!(writes.reachable_access.is_empty());
+        
+        let inverse = |operation| engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: Some(vec![operation]),
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        });
+        assert_eq!(inverse(DataOperation::Delete).total_accessors, 0);
+        let readers = inverse(DataOperation::Read);
+        assert_eq!(readers.total_accessors, 1);
+        assert_eq!(readers.access_paths.len(), 1);
+    }
+    
+    #[test]
+    fn test_analyze_all_entry_points_matches_single_queries() {
+        use fixtures::{access, accessor, exported, resolved_call};
+        let dir = tempdir().unwrap();
+        
+        // Two handlers sharing a recursive service layer
+        drop(fixtures::sample_db(dir.path(), vec![
+            exported("api.ts:listUsers:1", vec![resolved_call("svc.ts:load:1")]),
+            exported("api.ts:deleteUser:10", vec![resolved_call("svc.ts:remove:10"), resolved_call("svc.ts:load:1")]),
+            exported("api.ts:health:20", vec![]),
+            accessor(
+                "svc.ts:load:1",
+                vec![resolved_call("svc.ts:audit:20"), resolved_call("svc.ts:load:1")],
+                vec![access("users", CgDataOperation::Read, &["email", "password_hash"])],
+            ),
+            accessor(
+                "svc.ts:remove:10",
+                vec![resolved_call("svc.ts:audit:20")],
+                vec![access("users", CgDataOperation::Delete, &["id"])],
+            ),
+            accessor(
+                "svc.ts:audit:20",
+                vec![resolved_call("svc.ts:load:1")],
+                vec![access("audit_log", CgDataOperation::Write, &["ssn", "event"])],
+            ),
+        ]));
+        
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        let option_sets = [
+            ReachabilityOptions::default(),
+            ReachabilityOptions { sensitive_only: true, max_depth: Some(1), ..Default::default() },
+            ReachabilityOptions { operations: Some(vec![DataOperation::Delete, DataOperation::Write]), ..Default::default() },
+        ];
+        for options in &option_sets {
+            let all = engine.analyze_all_entry_points(options);
+            assert_eq!(all.keys().collect::<Vec<_>>(), vec!["api.ts:deleteUser:10", "api.ts:health:20", "api.ts:listUsers:1"]);
+            for (id, summary) in &all {
+                let single = engine.get_reachable_data_from_function(id, options);
+                let mut tables = single.tables.clone();
+                tables.sort();
+                let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+                for field in &single.sensitive_fields {
+                    *counts.entry(field.field.sensitivity_type.as_str().to_string()).or_default() += 1;
+                }
+                assert_eq!(summary.tables, tables, "{} {:?}", id, options);
+                assert_eq!(summary.sensitive_field_counts, counts, "{} {:?}", id, options);
+                assert_eq!(summary.max_depth, single.max_depth, "{} {:?}", id, options);
+                assert_eq!(summary.functions_traversed, single.functions_traversed, "{} {:?}", id, options);
+            }
+        }
+        
+        let all = engine.analyze_all_entry_points(&ReachabilityOptions::default());
+        let delete_user = &all["api.ts:deleteUser:10"];
+        assert_eq!(delete_user.tables, vec!["audit_log", "users"]);
+        assert_eq!(delete_user.sensitive_field_counts.get("credentials"), Some(&1));
+        assert_eq!(delete_user.sensitive_field_counts.get("pii"), Some(&2));
+        assert!(all["api.ts:health:20"].tables.is_empty());
+    }
+    
     #[test]
     fn test_sqlite_sensitive_detection() {
         let (_dir, db_path) = create_test_db();
@@ -648,4 +1677,123 @@ mod tests {
             .find(|s| s.field.field == "password_hash");
         assert!(password_field.is_some());
     }
+    
+    #[test]
+    fn test_paths_through_recursion_cycle() {
+        let dir = tempdir().unwrap();
+        
+        // handler -> a clique of 8 mutually recursive functions -> findAll
+        let knot: Vec<String> = (0..8).map(|i| format!("knot.ts:step{}:1", i)).collect();
+        let mut functions = vec![fixtures::exported("api.ts:handler:1", vec![fixtures::resolved_call(&knot[0])])];
+        for id in &knot {
+            let mut calls: Vec<CallEntry> = knot.iter().filter(|other| *other != id).map(|other| fixtures::resolved_call(other)).collect();
+            calls.push(fixtures::resolved_call("repo.ts:findAll:1"));
+            functions.push(fixtures::function(id, calls));
+        }
+        functions.push(fixtures::accessor(
+            "repo.ts:findAll:1",
+            vec![],
+            vec![fixtures::access("users", CgDataOperation::Read, &["id"])],
+        ));
+        drop(fixtures::sample_db(dir.path(), functions));
+        
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        let result = engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: false,
+            include_paths: false,
+            max_paths_per_entry_point: None,
+            min_confidence: None,
+        });
+        
+        // One path per way out of the cycle, none looping inside it
+        assert_eq!(result.access_paths.len(), 8);
+        assert_eq!(result.entry_points, vec!["api.ts:handler:1".to_string()]);
+        for access in &result.access_paths {
+            let ids: HashSet<&str> = access.path.iter().map(|n| n.function_id.as_str()).collect();
+            assert_eq!(ids.len(), access.path.len());
+            assert_eq!(access.path.last().unwrap().function_id, "repo.ts:findAll:1");
+        }
+        
+        let capped = |group_by_entry_point, include_paths| engine.get_code_paths_to_data(&InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: None,
+            group_by_entry_point,
+            include_paths,
+            max_paths_per_entry_point: Some(3),
+            min_confidence: None,
+        });
+        let flat = capped(false, false);
+        assert_eq!(flat.access_paths.len(), 3);
+        assert!(flat.truncated && flat.groups.is_empty());
+        
+        // Summaries count every path, not just the kept ones
+        let grouped = capped(true, false);
+        assert!(grouped.access_paths.is_empty());
+        let group = &grouped.groups[0];
+        assert_eq!(group.entry_point, "api.ts:handler:1");
+        assert_eq!(group.operations, vec![DataOperation::Read]);
+        assert_eq!((group.access_sites, group.path_count, group.min_depth, group.max_depth), (1, 8, 2, 3));
+        assert!(group.paths.is_empty() && !group.truncated && !grouped.truncated);
+        
+        let with_paths = capped(true, true);
+        assert_eq!(with_paths.groups[0].paths.len(), 3);
+        assert!(with_paths.groups[0].truncated && with_paths.truncated);
+    }
+    
+    #[test]
+    fn test_stream_code_paths_to_data() {
+        let dir = tempdir().unwrap();
+        
+        // handler -> 150 steps -> findAll, one path through each step
+        let steps: Vec<String> = (0..150).map(|i| format!("steps.ts:step{}:1", i)).collect();
+        let mut functions = vec![fixtures::exported("api.ts:handler:1", steps.iter().map(|id| fixtures::resolved_call(id)).collect())];
+        functions.extend(steps.iter().map(|id| fixtures::function(id, vec![fixtures::resolved_call("repo.ts:findAll:1")])));
+        functions.push(fixtures::accessor(
+            "repo.ts:findAll:1",
+            vec![],
+            vec![fixtures::access("users", CgDataOperation::Read, &["id"])],
+        ));
+        drop(fixtures::sample_db(dir.path(), functions));
+        let engine = SqliteReachabilityEngine::open(&dir.path().join("test.db")).unwrap();
+        
+        let options = |max_paths_per_entry_point| InverseReachabilityOptions {
+            table: Some("users".to_string()),
+            field: None,
+            max_depth: None,
+            operations: None,
+            group_by_entry_point: true,
+            include_paths: true,
+            max_paths_per_entry_point,
+            min_confidence: None,
+        };
+        let stream = |options: &InverseReachabilityOptions| {
+            let mut streamed: Vec<Vec<String>> = Vec::new();
+            let summary = engine.get_code_paths_to_data_with(options, |access| {
+                streamed.push(access.path.into_iter().map(|n| n.function_id).collect());
+            });
+            (streamed, summary)
+        };
+        
+        // Every path is streamed unless the caller sets a limit
+        let (streamed, summary) = stream(&options(None));
+        assert_eq!(streamed.len(), 150);
+        assert!(summary.groups[0].paths.is_empty() && summary.access_paths.is_empty());
+        assert_eq!((summary.groups[0].path_count, summary.truncated), (150, false));
+        
+        // Capped streams hand over the paths a collected result would keep
+        let (streamed, summary) = stream(&options(Some(3)));
+        let collected = engine.get_code_paths_to_data(&options(Some(3)));
+        let kept: Vec<Vec<String>> = collected.groups[0].paths
+            .iter()
+            .map(|access| access.path.iter().map(|n| n.function_id.clone()).collect())
+            .collect();
+        assert_eq!(streamed, kept);
+        assert_eq!((summary.groups[0].path_count, summary.truncated), (150, true));
+    }
 }
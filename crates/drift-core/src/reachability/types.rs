@@ -1,7 +1,8 @@
 //! Reachability types
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 
 /// Code location
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,7 +35,7 @@ pub struct DataAccessPoint {
 }
 
 /// Data operation type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DataOperation {
     Read,
@@ -42,8 +43,27 @@ pub enum DataOperation {
     Delete,
 }
 
+impl DataOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataOperation::Read => "read",
+            DataOperation::Write => "write",
+            DataOperation::Delete => "delete",
+        }
+    }
+
+    pub fn parse(operation: &str) -> Result<Self, String> {
+        match operation.trim().to_lowercase().as_str() {
+            "read" => Ok(DataOperation::Read),
+            "write" => Ok(DataOperation::Write),
+            "delete" => Ok(DataOperation::Delete),
+            other => Err(format!("Unknown data operation '{}' (expected read, write or delete)", other)),
+        }
+    }
+}
+
 /// Sensitivity type for fields
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SensitivityType {
     Pii,
@@ -53,6 +73,41 @@ pub enum SensitivityType {
     Unknown,
 }
 
+impl SensitivityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SensitivityType::Pii => "pii",
+            SensitivityType::Credentials => "credentials",
+            SensitivityType::Financial => "financial",
+            SensitivityType::Health => "health",
+            SensitivityType::Unknown => "unknown",
+        }
+    }
+
+    pub fn parse(sensitivity: &str) -> Result<Self, String> {
+        match sensitivity.trim().to_lowercase().as_str() {
+            "pii" => Ok(SensitivityType::Pii),
+            "credentials" => Ok(SensitivityType::Credentials),
+            "financial" => Ok(SensitivityType::Financial),
+            "health" => Ok(SensitivityType::Health),
+            other => Err(format!("Unknown sensitivity type '{}' (expected pii, credentials, financial or health)", other)),
+        }
+    }
+}
+
+impl From<crate::boundaries::SensitivityType> for SensitivityType {
+    fn from(sensitivity: crate::boundaries::SensitivityType) -> Self {
+        match sensitivity {
+            crate::boundaries::SensitivityType::Pii => SensitivityType::Pii,
+            crate::boundaries::SensitivityType::Credentials => SensitivityType::Credentials,
+            crate::boundaries::SensitivityType::Financial => SensitivityType::Financial,
+            crate::boundaries::SensitivityType::Health => SensitivityType::Health,
+            // Configured taxonomies fall outside reachability's fixed types
+            crate::boundaries::SensitivityType::Custom => SensitivityType::Unknown,
+        }
+    }
+}
+
 /// Sensitive field information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SensitiveField {
@@ -68,8 +123,23 @@ pub struct SensitiveField {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReachableDataAccess {
     pub access: DataAccessPoint,
+    /// A shortest path from the origin to the accessing function
     pub path: Vec<CallPathNode>,
+    /// Distinct simple paths to the accessing function, shortest first, up
+    /// to `max_paths_per_access` (`path` is the first)
+    pub paths: Vec<Vec<CallPathNode>>,
     pub depth: u32,
+    /// Confidence of the calls along `path` and of the access, multiplied
+    #[serde(default)]
+    pub path_confidence: f64,
+    /// Whether `path` follows an async call from a job enqueue site to its
+    /// handler
+    #[serde(default)]
+    pub crosses_async: bool,
+    /// For reads of a table an eager relation loads, the relation
+    /// (`Post.author`) and not a query of the accessing function
+    #[serde(default)]
+    pub loaded_via: Option<String>,
 }
 
 /// Sensitive field access info
@@ -80,8 +150,66 @@ pub struct SensitiveFieldAccess {
     pub access_count: u32,
 }
 
+/// Confidence of a call followed by `UnresolvedMode` rather than resolved;
+/// below every resolution strategy's
+pub const UNRESOLVED_CALL_CONFIDENCE: f64 = 0.2;
+
+/// How reachability treats calls resolution left without a target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnresolvedMode {
+    /// Don't follow them
+    #[default]
+    Ignore,
+    /// Follow them to every function they could name, over-reporting rather
+    /// than missing access
+    Pessimistic,
+    /// Follow them only when a single function has the callee's name
+    UniqueCandidate,
+}
+
+impl UnresolvedMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnresolvedMode::Ignore => "ignore",
+            UnresolvedMode::Pessimistic => "pessimistic",
+            UnresolvedMode::UniqueCandidate => "unique-candidate",
+        }
+    }
+
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode.trim().to_lowercase().as_str() {
+            "ignore" => Ok(UnresolvedMode::Ignore),
+            "pessimistic" => Ok(UnresolvedMode::Pessimistic),
+            "unique-candidate" | "unique_candidate" => Ok(UnresolvedMode::UniqueCandidate),
+            other => Err(format!("Unknown unresolved mode '{}' (expected ignore, pessimistic or unique-candidate)", other)),
+        }
+    }
+
+    /// The candidates an unresolved call is followed to
+    pub fn select<'a>(&self, candidates: &'a [String]) -> &'a [String] {
+        match self {
+            UnresolvedMode::Pessimistic => candidates,
+            UnresolvedMode::UniqueCandidate if candidates.len() == 1 => candidates,
+            _ => &[],
+        }
+    }
+}
+
+/// Whether a traversal follows calls of `kind`: async calls only when
+/// `include_async` is set or `kinds` lists them, other calls when `kinds` is
+/// unset or lists them
+pub(crate) fn follows_call_kind(kind: &str, kinds: Option<&[String]>, include_async: bool) -> bool {
+    let listed = |kinds: &[String]| kinds.iter().any(|k| k == kind);
+    if kind == "async" {
+        include_async || kinds.is_some_and(listed)
+    } else {
+        kinds.is_none_or(listed)
+    }
+}
+
 /// Reachability query options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ReachabilityOptions {
     /// Maximum depth to traverse
     pub max_depth: Option<u32>,
@@ -89,8 +217,69 @@ pub struct ReachabilityOptions {
     pub sensitive_only: bool,
     /// Filter by table names
     pub tables: Vec<String>,
-    /// Include unresolved calls in traversal
-    pub include_unresolved: bool,
+    /// How to treat calls resolution left without a target
+    pub unresolved_mode: UnresolvedMode,
+    /// Only follow calls of these kinds (`direct`, `method`, `callback`,
+    /// `dispatch`, `async`); all but `async` when unset. Only the SQLite
+    /// engine records kinds.
+    pub edge_kinds: Option<Vec<String>>,
+    /// Also follow `async` calls from job enqueue sites to their handlers
+    pub include_async_edges: bool,
+    /// Paths to list per reachable access (default 1)
+    pub max_paths_per_access: Option<u32>,
+    /// Only include access with these operations; all when unset
+    pub operations: Option<Vec<DataOperation>>,
+    /// Stop after visiting this many functions
+    pub max_functions: Option<u32>,
+    /// Stop after this many milliseconds
+    pub timeout_ms: Option<u32>,
+    /// Only include access whose `path_confidence` is at least this
+    pub min_confidence: Option<f64>,
+    /// Relations loaded with their model; reads of a model's table also
+    /// reach the related table, one hop
+    pub eager_relations: Vec<EagerRelation>,
+}
+
+impl Eq for ReachabilityOptions {}
+
+/// Options key `ReachabilityCache` entries, so `min_confidence` hashes by bits
+impl Hash for ReachabilityOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let ReachabilityOptions {
+            max_depth,
+            sensitive_only,
+            tables,
+            unresolved_mode,
+            edge_kinds,
+            include_async_edges,
+            max_paths_per_access,
+            operations,
+            max_functions,
+            timeout_ms,
+            min_confidence,
+            eager_relations,
+        } = self;
+        max_depth.hash(state);
+        sensitive_only.hash(state);
+        tables.hash(state);
+        unresolved_mode.hash(state);
+        edge_kinds.hash(state);
+        include_async_edges.hash(state);
+        max_paths_per_access.hash(state);
+        operations.hash(state);
+        max_functions.hash(state);
+        timeout_ms.hash(state);
+        min_confidence.map(f64::to_bits).hash(state);
+        eager_relations.hash(state);
+    }
+}
+
+impl ReachabilityOptions {
+    /// Whether the table and operation filters let `access` through
+    pub fn includes(&self, access: &DataAccessPoint) -> bool {
+        (self.tables.is_empty() || self.tables.contains(&access.table))
+            && self.operations.as_ref().is_none_or(|ops| ops.contains(&access.operation))
+    }
 }
 
 /// Result of reachability analysis
@@ -102,14 +291,165 @@ pub struct ReachabilityResult {
     pub sensitive_fields: Vec<SensitiveFieldAccess>,
     pub max_depth: u32,
     pub functions_traversed: u32,
+    /// Unresolved calls followed, per the options' `unresolved_mode`, to
+    /// a candidate
+    #[serde(default)]
+    pub unresolved_edges_traversed: u32,
+    /// Whether the result came from a `ReachabilityCache`
+    #[serde(default)]
+    pub cache_hit: bool,
+    /// Whether a budget stopped the traversal early, leaving the result partial
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(default)]
+    pub truncation_reason: Option<TruncationReason>,
+}
+
+/// Which `ReachabilityOptions` budget cut a traversal short
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TruncationReason {
+    MaxFunctions,
+    Timeout,
+}
+
+impl TruncationReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TruncationReason::MaxFunctions => "max-functions",
+            TruncationReason::Timeout => "timeout",
+        }
+    }
+}
+
+/// Reachability from one entry point, summarized
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryPointReachability {
+    pub entry_point: String,
+    pub name: String,
+    pub file: String,
+    /// Reachable tables, sorted
+    pub tables: Vec<String>,
+    /// Distinct reachable sensitive fields (`table.field`) per sensitivity type
+    pub sensitive_field_counts: BTreeMap<String, u32>,
+    pub max_depth: u32,
+    pub functions_traversed: u32,
+}
+
+/// Sensitivity matrix options
+#[derive(Debug, Clone, Default)]
+pub struct SensitivityMatrixOptions {
+    /// Maximum calls from an entry point
+    pub max_depth: Option<u32>,
+    /// Only follow calls of these kinds; see `ReachabilityOptions::edge_kinds`
+    pub edge_kinds: Option<Vec<String>>,
+    /// Only these sensitivity types; all when unset
+    pub sensitivity_types: Option<Vec<SensitivityType>>,
+    /// Also count the related tables eager relations load with the tables
+    /// an entry point reads
+    pub expand_eager_relations: bool,
+}
+
+/// An ORM relation loaded whenever its model is: reading `table` reads
+/// `related_table` too
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EagerRelation {
+    pub table: String,
+    pub related_table: String,
+    /// Fields of the related model
+    pub fields: Vec<String>,
+    /// The relation, as `Model.field`
+    pub via: String,
+}
+
+/// One entry point reaching one sensitive field with one operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SensitivityMatrixRow {
+    pub entry_point: String,
+    pub sensitivity_type: SensitivityType,
+    pub table: String,
+    pub field: String,
+    pub operation: DataOperation,
+    /// Shortest call paths from the entry point to the functions making the access
+    pub path_count: u64,
+    /// Fewest calls from the entry point to the access
+    pub min_depth: u32,
+}
+
+/// Function-to-function reachability options
+#[derive(Debug, Clone, Default)]
+pub struct FunctionPathOptions {
+    /// Maximum calls along a path
+    pub max_depth: Option<u32>,
+    /// Stop counting paths at this many (default 100)
+    pub max_paths: Option<u32>,
+    /// Include unresolved calls in traversal
+    pub include_unresolved: bool,
+    /// Only follow calls of these kinds; see `ReachabilityOptions::edge_kinds`
+    pub edge_kinds: Option<Vec<String>>,
+}
+
+/// Whether one function can reach another through calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionReachability {
+    pub from_id: String,
+    pub to_id: String,
+    pub reachable: bool,
+    /// Empty when unreachable; just the function when `from_id == to_id`
+    pub shortest_path: Vec<CallPathNode>,
+    /// Distinct simple paths, counted up to `max_paths`
+    pub path_count: u32,
+    /// Calls along the shortest path
+    pub min_depth: Option<u32>,
 }
 
 /// Inverse reachability options
 #[derive(Debug, Clone)]
 pub struct InverseReachabilityOptions {
-    pub table: String,
+    /// Only access to this table; every table when unset
+    pub table: Option<String>,
+    /// Only access naming this field; `*` matches any run of characters
+    /// (`*_token`)
     pub field: Option<String>,
     pub max_depth: Option<u32>,
+    /// Only access with these operations; all when unset
+    pub operations: Option<Vec<DataOperation>>,
+    /// Summarize paths per entry point in `groups` instead of listing them
+    /// in `access_paths`
+    pub group_by_entry_point: bool,
+    /// Keep each group's paths when grouping
+    pub include_paths: bool,
+    /// Paths kept per entry point; the rest are only counted. Grouped paths
+    /// default to 100, the flat list to no limit
+    pub max_paths_per_entry_point: Option<u32>,
+    /// Only include paths whose `path_confidence` is at least this
+    pub min_confidence: Option<f64>,
+}
+
+impl InverseReachabilityOptions {
+    /// Whether `access` is to the target table (and field) with an allowed operation
+    pub fn matches(&self, access: &DataAccessPoint) -> bool {
+        self.table.as_ref().is_none_or(|t| access.table == *t)
+            && self.field.as_ref().is_none_or(|f| access.fields.iter().any(|field| field_matches(f, field)))
+            && self.operations.as_ref().is_none_or(|ops| ops.contains(&access.operation))
+    }
+}
+
+/// Whether `field` matches `pattern`, where `*` matches any run of characters
+pub fn field_matches(pattern: &str, field: &str) -> bool {
+    let mut parts: Vec<&str> = pattern.split('*').collect();
+    let Some(last) = parts.pop() else { return false };
+    if parts.is_empty() {
+        return field == last;
+    }
+    let Some(mut rest) = field.strip_prefix(parts[0]) else { return false };
+    for part in &parts[1..] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
 }
 
 /// Inverse access path
@@ -118,21 +458,69 @@ pub struct InverseAccessPath {
     pub entry_point: String,
     pub path: Vec<CallPathNode>,
     pub access_point: DataAccessPoint,
+    /// Confidence of the calls along `path` and of the access, multiplied
+    #[serde(default)]
+    pub path_confidence: f64,
+}
+
+/// Inverse access paths from one entry point, summarized
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryPointAccessGroup {
+    pub entry_point: String,
+    /// Distinct operations on the target, in read, write, delete order
+    pub operations: Vec<DataOperation>,
+    /// Distinct access sites (function and line) reached
+    pub access_sites: u32,
+    pub path_count: u32,
+    pub min_depth: u32,
+    pub max_depth: u32,
+    /// Paths, up to `max_paths_per_entry_point`, when `include_paths` is set
+    pub paths: Vec<InverseAccessPath>,
+    /// Whether the cap dropped some of the paths
+    pub truncated: bool,
+}
+
+/// An entry point reaching both queried data and outbound HTTP calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalExposure {
+    pub entry_point: String,
+    /// Hosts of the `external:` sinks it reaches, sorted
+    pub hosts: Vec<String>,
+}
+
+/// Inverse access paths into one table, summarized
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InverseTableGroup {
+    pub table: String,
+    /// Fields matching the target field, sorted; empty without one
+    pub fields: Vec<String>,
+    /// Functions making the access
+    pub accessors: u32,
+    /// Entry points reaching them, sorted
+    pub entry_points: Vec<String>,
+    pub path_count: u32,
 }
 
 /// Result of inverse reachability query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InverseReachabilityResult {
     pub target: InverseTarget,
+    /// Empty when grouping by entry point
     pub access_paths: Vec<InverseAccessPath>,
+    /// Per entry point, sorted by ID, when grouping by entry point
+    pub groups: Vec<EntryPointAccessGroup>,
+    /// Per table reached, sorted by name
+    pub tables: Vec<InverseTableGroup>,
     pub entry_points: Vec<String>,
     pub total_accessors: u32,
+    /// Whether any entry point had more paths than `max_paths_per_entry_point`
+    pub truncated: bool,
 }
 
 /// Target for inverse query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InverseTarget {
-    pub table: String,
+    pub table: Option<String>,
     pub field: Option<String>,
 }
 
@@ -157,6 +545,8 @@ pub struct CallSite {
     pub resolved: bool,
     pub resolved_candidates: Vec<String>,
     pub line: u32,
+    /// How sure resolution is of `resolved_candidates`
+    pub confidence: f32,
 }
 
 /// Call graph for reachability analysis
@@ -186,54 +186,148 @@ pub const DEFAULT_IGNORE_EXTENSIONS: &[&str] = &[
     "*.designer.cs",
 ];
 
+/// Where an ignore rule came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IgnoreSource {
+    /// Built-in directory and extension ignores
+    Default,
+    /// `extra_ignores` passed in the scan config
+    Config,
+    /// `.driftignore` at the scan root
+    DriftIgnore,
+    /// `.gitignore` at the root or in any subdirectory
+    GitIgnore,
+}
+
+impl IgnoreSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IgnoreSource::Default => "default",
+            IgnoreSource::Config => "config",
+            IgnoreSource::DriftIgnore => "driftignore",
+            IgnoreSource::GitIgnore => "gitignore",
+        }
+    }
+}
+
 /// Patterns for the ignore crate
+///
+/// Each source is kept separately so skips can be attributed. When several
+/// sources match, the most specific wins: nested `.gitignore` files (deepest
+/// first), then the root `.gitignore`, `.driftignore`, config patterns, and
+/// finally the defaults. A negation (`!keep.js`) in a higher-precedence source
+/// re-includes a path ignored by a lower one.
 pub struct IgnorePatterns {
+    defaults: Gitignore,
+    config: Gitignore,
+    driftignore: Gitignore,
     gitignore: Gitignore,
+    respect_gitignore: bool,
 }
 
 impl IgnorePatterns {
     /// Create ignore patterns from defaults + custom patterns
     pub fn new(root: &Path, extra_patterns: &[String]) -> Self {
-        let mut builder = GitignoreBuilder::new(root);
-        
-        // Add default directory ignores
-        for pattern in DEFAULT_IGNORE_DIRS {
-            let _ = builder.add_line(None, pattern);
-        }
-        
-        // Add default extension ignores
-        for pattern in DEFAULT_IGNORE_EXTENSIONS {
-            let _ = builder.add_line(None, pattern);
-        }
+        Self::with_options(root, extra_patterns, true)
+    }
+    
+    /// Create ignore patterns, optionally skipping `.gitignore` files
+    pub fn with_options(root: &Path, extra_patterns: &[String], respect_gitignore: bool) -> Self {
+        // Default directory and extension ignores
+        let defaults = build_gitignore(
+            root,
+            DEFAULT_IGNORE_DIRS.iter().chain(DEFAULT_IGNORE_EXTENSIONS.iter()).copied(),
+        );
         
-        // Add custom patterns
-        for pattern in extra_patterns {
-            let _ = builder.add_line(None, pattern);
-        }
+        // Custom patterns
+        let config = build_gitignore(root, extra_patterns.iter().map(|s| s.as_str()));
         
-        // Try to load .driftignore if it exists
-        let driftignore = root.join(".driftignore");
-        if driftignore.exists() {
-            let _ = builder.add(&driftignore);
-        }
+        // .driftignore is always honored when present
+        let driftignore = load_gitignore(root, ".driftignore")
+            .unwrap_or_else(Gitignore::empty);
         
-        // Try to load .gitignore if it exists
-        let gitignore = root.join(".gitignore");
-        if gitignore.exists() {
-            let _ = builder.add(&gitignore);
-        }
+        let gitignore = if respect_gitignore {
+            load_gitignore(root, ".gitignore").unwrap_or_else(Gitignore::empty)
+        } else {
+            Gitignore::empty()
+        };
         
         Self {
-            gitignore: builder.build().unwrap_or_else(|_| {
-                GitignoreBuilder::new(root).build().unwrap()
-            }),
+            defaults,
+            config,
+            driftignore,
+            gitignore,
+            respect_gitignore,
         }
     }
     
+    /// Whether nested `.gitignore` files should be loaded while walking
+    pub fn respects_gitignore(&self) -> bool {
+        self.respect_gitignore
+    }
+    
+    /// Load the `.gitignore` of a subdirectory, if present and enabled
+    pub fn load_nested(&self, dir: &Path) -> Option<Gitignore> {
+        if !self.respect_gitignore {
+            return None;
+        }
+        load_gitignore(dir, ".gitignore")
+    }
+    
     /// Check if a path should be ignored
     pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
-        self.gitignore.matched(path, is_dir).is_ignore()
+        self.ignore_source(path, is_dir, &[]).is_some()
+    }
+    
+    /// Determine which source (if any) ignores a path
+    ///
+    /// `nested` holds the `.gitignore` files of the directories between the
+    /// root and `path`, outermost first.
+    pub fn ignore_source(&self, path: &Path, is_dir: bool, nested: &[Gitignore]) -> Option<IgnoreSource> {
+        let layers = nested
+            .iter()
+            .rev()
+            .map(|gi| (gi, IgnoreSource::GitIgnore))
+            .chain([
+                (&self.gitignore, IgnoreSource::GitIgnore),
+                (&self.driftignore, IgnoreSource::DriftIgnore),
+                (&self.config, IgnoreSource::Config),
+                (&self.defaults, IgnoreSource::Default),
+            ]);
+        
+        for (gitignore, source) in layers {
+            let matched = gitignore.matched(path, is_dir);
+            if matched.is_ignore() {
+                return Some(source);
+            }
+            if matched.is_whitelist() {
+                return None;
+            }
+        }
+        
+        None
+    }
+}
+
+/// Build a gitignore matcher from in-memory patterns
+fn build_gitignore<'a>(root: &Path, patterns: impl Iterator<Item = &'a str>) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        let _ = builder.add_line(None, pattern);
     }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Load an ignore file from a directory, if it exists
+fn load_gitignore(dir: &Path, name: &str) -> Option<Gitignore> {
+    let path = dir.join(name);
+    if !path.is_file() {
+        return None;
+    }
+    
+    let mut builder = GitignoreBuilder::new(dir);
+    builder.add(&path);
+    builder.build().ok()
 }
 
 /// Lazy static default ignores
@@ -275,4 +369,30 @@ mod tests {
         assert!(!patterns.is_ignored(Path::new("src/main.ts"), false));
         assert!(!patterns.is_ignored(Path::new("lib/utils.py"), false));
     }
+
+    #[test]
+    fn test_nested_gitignore_negation() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("apps/web");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "*.gen.ts\n").unwrap();
+        std::fs::write(pkg.join(".gitignore"), "!keep.gen.ts\nfixtures/\n").unwrap();
+        
+        let patterns = IgnorePatterns::new(dir.path(), &[]);
+        let nested = vec![patterns.load_nested(&pkg).unwrap()];
+        
+        assert_eq!(
+            patterns.ignore_source(&dir.path().join("other.gen.ts"), false, &[]),
+            Some(IgnoreSource::GitIgnore)
+        );
+        assert_eq!(patterns.ignore_source(&pkg.join("keep.gen.ts"), false, &nested), None);
+        assert_eq!(
+            patterns.ignore_source(&pkg.join("fixtures"), true, &nested),
+            Some(IgnoreSource::GitIgnore)
+        );
+        assert_eq!(
+            patterns.ignore_source(&pkg.join("node_modules"), true, &nested),
+            Some(IgnoreSource::Default)
+        );
+    }
 }
@@ -3,12 +3,15 @@
 //! This module provides high-performance file system scanning using:
 //! - `ignore` crate for gitignore-style pattern matching
 //! - `rayon` for parallel directory traversal
-//! - `xxhash` for fast file hashing
+//! - `xxhash` for fast file hashing (SHA-256 and BLAKE3 on request)
 
 mod ignores;
 mod types;
 mod walker;
 
-pub use ignores::{IgnorePatterns, DEFAULT_IGNORES};
-pub use types::{FileInfo, ScanConfig, ScanResult, ScanStats};
-pub use walker::Scanner;
+pub use ignores::{IgnorePatterns, IgnoreSource, DEFAULT_IGNORES};
+pub use types::{
+    ChangeStatus, FileInfo, HashAlgorithm, IgnoreSourceStats, LanguageStats, ScanConfig,
+    ScanProgress, ScanResult, ScanStats,
+};
+pub use walker::{detect_language_from_content, is_generated, is_minified, Scanner};
@@ -1,11 +1,13 @@
 //! Scanner types - Core data structures for file scanning
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Configuration for the scanner
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ScanConfig {
     /// Root directory to scan
     pub root: PathBuf,
@@ -15,10 +17,24 @@ pub struct ScanConfig {
     pub extra_ignores: Vec<String>,
     /// Whether to compute file hashes
     pub compute_hashes: bool,
+    /// Algorithm used when computing file hashes
+    pub hash_algorithm: HashAlgorithm,
     /// Maximum file size to process (bytes)
     pub max_file_size: u64,
     /// Number of threads (0 = auto)
     pub threads: usize,
+    /// Whether to follow symbolic links (cycles are detected and reported)
+    pub follow_symlinks: bool,
+    /// Whether to apply `.gitignore` files (root and nested)
+    pub respect_gitignore: bool,
+    /// Progress callback, invoked from worker threads
+    pub on_progress: Option<Arc<dyn Fn(ScanProgress) + Send + Sync>>,
+    /// Number of files between progress callbacks
+    pub progress_interval: usize,
+    /// Maximum directory depth below the root to descend into (None = unlimited)
+    pub max_depth: Option<usize>,
+    /// Skip directories containing more files than this (None = unlimited)
+    pub max_files_per_dir: Option<usize>,
 }
 
 impl Default for ScanConfig {
@@ -28,12 +44,91 @@ impl Default for ScanConfig {
             patterns: vec!["**/*".to_string()],
             extra_ignores: vec![],
             compute_hashes: true,
+            hash_algorithm: HashAlgorithm::default(),
             max_file_size: 10 * 1024 * 1024, // 10MB
             threads: 0,
+            follow_symlinks: false,
+            respect_gitignore: true,
+            on_progress: None,
+            progress_interval: 100,
+            max_depth: None,
+            max_files_per_dir: None,
         }
     }
 }
 
+impl fmt::Debug for ScanConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The progress callback has no useful representation
+        f.debug_struct("ScanConfig")
+            .field("root", &self.root)
+            .field("patterns", &self.patterns)
+            .field("extra_ignores", &self.extra_ignores)
+            .field("compute_hashes", &self.compute_hashes)
+            .field("hash_algorithm", &self.hash_algorithm)
+            .field("max_file_size", &self.max_file_size)
+            .field("threads", &self.threads)
+            .field("follow_symlinks", &self.follow_symlinks)
+            .field("respect_gitignore", &self.respect_gitignore)
+            .field("progress_interval", &self.progress_interval)
+            .field("max_depth", &self.max_depth)
+            .field("max_files_per_dir", &self.max_files_per_dir)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Content hash algorithm
+///
+/// xxHash is the default - it is by far the fastest and is only used for
+/// change detection. SHA-256 and BLAKE3 are available when hashes are
+/// compared against external tooling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    #[serde(rename = "xxhash64")]
+    XxHash64,
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Parse an algorithm name ("xxhash64", "sha256", "blake3")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "xxhash64" | "xxhash" | "xxh3" => Some(HashAlgorithm::XxHash64),
+            "sha256" | "sha-256" => Some(HashAlgorithm::Sha256),
+            "blake3" => Some(HashAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::XxHash64 => "xxhash64",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+}
+
+/// Progress event emitted while scanning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanProgress {
+    /// Files processed so far
+    pub files_seen: usize,
+    /// Files queued for processing (after ignores)
+    pub total_files: usize,
+    /// Bytes of file content processed so far
+    pub bytes_read: u64,
+    /// Directory of the most recently processed file (relative to root)
+    pub current_dir: String,
+    /// Directories skipped by ignore rules or depth/file-count limits
+    pub dirs_skipped: usize,
+    /// Files skipped by ignore rules or size/file-count limits
+    pub files_skipped: usize,
+}
+
 /// Information about a scanned file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileInfo {
@@ -41,10 +136,35 @@ pub struct FileInfo {
     pub path: String,
     /// File size in bytes
     pub size: u64,
-    /// xxHash of file contents (if computed)
+    /// Hash of file contents (if computed), see `ScanResult::hash_algorithm`
     pub hash: Option<String>,
     /// Detected language
     pub language: Option<String>,
+    /// Last modification time in milliseconds since the Unix epoch
+    #[serde(default)]
+    pub mtime: Option<u64>,
+    /// Change relative to the previous scan (incremental scans only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_status: Option<ChangeStatus>,
+    /// Number of lines (counted for files with a detected language)
+    #[serde(default)]
+    pub lines: usize,
+    /// Minified bundle (`.min.js`, very long lines)
+    #[serde(default)]
+    pub is_minified: bool,
+    /// Generated code (auto-generated header, compiled output with a source map)
+    #[serde(default)]
+    pub is_generated: bool,
+}
+
+/// How a file changed since the previous scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeStatus {
+    Added,
+    Modified,
+    Unchanged,
+    Deleted,
 }
 
 /// Statistics about the scan
@@ -52,19 +172,40 @@ pub struct FileInfo {
 pub struct ScanStats {
     /// Total files found
     pub total_files: usize,
-    /// Files by language
-    pub by_language: std::collections::HashMap<String, usize>,
+    /// File count, bytes and lines by language
+    pub by_language: std::collections::HashMap<String, LanguageStats>,
     /// Total bytes scanned
     pub total_bytes: u64,
-    /// Directories skipped (ignored)
+    /// Directories skipped (ignored, too deep, too many files)
     pub dirs_skipped: usize,
-    /// Files skipped (too large, binary, etc.)
+    /// Files skipped (ignored, too large, binary, in an over-limit directory, etc.)
     pub files_skipped: usize,
+    /// Files added, modified, or deleted since the previous scan (incremental scans only)
+    #[serde(default)]
+    pub changed_files: usize,
+    /// Files/directories skipped per ignore source ("default", "config", "driftignore", "gitignore")
+    #[serde(default)]
+    pub skipped_by_source: std::collections::HashMap<String, IgnoreSourceStats>,
     /// Scan duration
     #[serde(with = "duration_millis")]
     pub duration: Duration,
 }
 
+/// Size of a single language within the scanned files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageStats {
+    pub files: usize,
+    pub bytes: u64,
+    pub lines: u64,
+}
+
+/// Files and directories skipped by a single ignore source
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IgnoreSourceStats {
+    pub files_skipped: usize,
+    pub dirs_skipped: usize,
+}
+
 /// Result of a scan operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanResult {
@@ -72,12 +213,40 @@ pub struct ScanResult {
     pub root: String,
     /// All files found
     pub files: Vec<FileInfo>,
+    /// Files present in the previous scan but no longer on disk (incremental scans only)
+    #[serde(default)]
+    pub deleted_files: Vec<FileInfo>,
+    /// Algorithm used for `FileInfo::hash` (None when hashes were not computed)
+    #[serde(default)]
+    pub hash_algorithm: Option<HashAlgorithm>,
     /// Scan statistics
     pub stats: ScanStats,
     /// Any errors encountered (non-fatal)
     pub errors: Vec<String>,
 }
 
+impl ScanResult {
+    /// Load a previously persisted scan result (e.g. `.drift/lake/scan-cache.json`)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read scan cache: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse scan cache: {}", e))
+    }
+
+    /// Persist this scan result so a later incremental scan can reuse it
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write scan cache: {}", e))
+    }
+}
+
 // Custom serialization for Duration as milliseconds
 mod duration_millis {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
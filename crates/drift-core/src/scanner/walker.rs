@@ -3,20 +3,29 @@
 //! This is the core scanner that walks the filesystem in parallel,
 //! respecting ignore patterns and computing file hashes.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Mutex;
-use std::time::Instant;
+use std::time::{Instant, UNIX_EPOCH};
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use rayon::prelude::*;
+use sha2::{Digest, Sha256};
 use xxhash_rust::xxh3::xxh3_64;
 
-use super::ignores::IgnorePatterns;
-use super::types::{FileInfo, ScanConfig, ScanResult, ScanStats};
+use ignore::gitignore::Gitignore;
+
+use super::ignores::{IgnorePatterns, IgnoreSource};
+use super::types::{
+    ChangeStatus, FileInfo, HashAlgorithm, IgnoreSourceStats, LanguageStats, ScanConfig, ScanProgress,
+    ScanResult, ScanStats,
+};
+
+/// Number of files processed in parallel before handing them to the visitor
+const PROCESS_CHUNK_SIZE: usize = 4096;
 
 /// High-performance file scanner
 pub struct Scanner {
@@ -28,7 +37,11 @@ pub struct Scanner {
 impl Scanner {
     /// Create a new scanner with the given configuration
     pub fn new(config: ScanConfig) -> Self {
-        let ignores = IgnorePatterns::new(&config.root, &config.extra_ignores);
+        let ignores = IgnorePatterns::with_options(
+            &config.root,
+            &config.extra_ignores,
+            config.respect_gitignore,
+        );
         
         // Build glob set for include patterns
         let mut builder = GlobSetBuilder::new();
@@ -56,101 +69,352 @@ impl Scanner {
     
     /// Scan the filesystem and return results
     pub fn scan(&self) -> ScanResult {
+        self.scan_with_previous(None)
+    }
+    
+    /// Scan incrementally against a previous result
+    ///
+    /// Files whose size and mtime match the previous scan reuse the cached hash
+    /// instead of being re-read. Every file is annotated with a `change_status`,
+    /// and files that no longer exist are reported in `deleted_files`.
+    ///
+    /// When the previous result was hashed with a different algorithm its
+    /// hashes can't be compared: every file is rehashed and reported as
+    /// modified, while files missing from disk are still reported as deleted.
+    pub fn scan_incremental(&self, previous: &ScanResult) -> ScanResult {
+        let index = self.index_previous(previous);
+        self.scan_with_previous(Some(&index))
+    }
+    
+    /// Scan incrementally against the persisted cache, then update the cache
+    ///
+    /// When no cache exists yet every file is reported as added.
+    pub fn scan_cached(&self) -> ScanResult {
+        let cache_path = self.cache_path();
+        let previous = ScanResult::load(&cache_path).ok();
+        let index = previous.as_ref().map(|p| self.index_previous(p)).unwrap_or_default();
+        
+        let mut result = self.scan_with_previous(Some(&index));
+        if let Err(e) = result.save(&cache_path) {
+            result.errors.push(e);
+        }
+        result
+    }
+    
+    /// Index a previous scan's files by relative path
+    fn index_previous<'a>(&self, previous: &'a ScanResult) -> PreviousIndex<'a> {
+        PreviousIndex {
+            files: previous.files.iter().map(|f| (f.path.as_str(), f)).collect(),
+            stale_hashes: self.config.compute_hashes
+                && previous.hash_algorithm != Some(self.config.hash_algorithm),
+        }
+    }
+    
+    /// Location of the persisted scan cache for this root
+    pub fn cache_path(&self) -> PathBuf {
+        self.config.root
+            .join(".drift")
+            .join("lake")
+            .join("scan-cache.json")
+    }
+    
+    /// Scan the filesystem, handing each file to `visit` instead of collecting them
+    ///
+    /// Files are processed in parallel chunks and visited in walk order on the
+    /// calling thread, so memory stays bounded on very large repositories. The
+    /// returned result carries stats and errors but an empty `files` list.
+    pub fn scan_with<F: FnMut(FileInfo)>(&self, visit: F) -> ScanResult {
+        self.visit_files(None, visit)
+    }
+    
+    /// Run a scan, optionally diffing against the files of a previous scan
+    fn scan_with_previous(&self, previous: Option<&PreviousIndex>) -> ScanResult {
+        let mut files = Vec::new();
+        let mut result = self.visit_files(previous, |info| files.push(info));
+        result.files = files;
+        result
+    }
+    
+    /// Core scan loop shared by the collecting and visitor APIs
+    fn visit_files<F: FnMut(FileInfo)>(&self, previous: Option<&PreviousIndex>, mut visit: F) -> ScanResult {
         let start = Instant::now();
         
         // Collect all files first (single-threaded walk for correctness)
-        let files_to_process = self.collect_files();
+        let walk = self.collect_files();
         
         // Counters for stats
-        let dirs_skipped = AtomicUsize::new(0);
-        let files_skipped = AtomicUsize::new(0);
-        let errors: Mutex<Vec<String>> = Mutex::new(Vec::new());
-        
-        // Process files in parallel
-        let files: Vec<FileInfo> = files_to_process
-            .par_iter()
-            .filter_map(|path| {
-                match self.process_file(path) {
-                    Ok(Some(info)) => Some(info),
-                    Ok(None) => {
-                        files_skipped.fetch_add(1, Ordering::Relaxed);
-                        None
-                    }
-                    Err(e) => {
-                        if let Ok(mut errs) = errors.lock() {
-                            errs.push(format!("{}: {}", path.display(), e));
-                        }
-                        None
-                    }
-                }
-            })
-            .collect();
+        let total_files = walk.files.len();
+        let dirs_skipped = walk.dirs_skipped;
+        let files_skipped = AtomicUsize::new(walk.files_skipped);
+        let files_seen = AtomicUsize::new(0);
+        let bytes_read = AtomicU64::new(0);
+        let errors: Mutex<Vec<String>> = Mutex::new(walk.errors);
         
-        // Compute stats
-        let mut by_language: HashMap<String, usize> = HashMap::new();
+        let mut by_language: HashMap<String, LanguageStats> = HashMap::new();
         let mut total_bytes = 0u64;
+        let mut visited = 0usize;
+        let mut changed_files = 0usize;
+        let mut seen: HashSet<String> = HashSet::new();
         
-        for file in &files {
-            total_bytes += file.size;
-            if let Some(ref lang) = file.language {
-                *by_language.entry(lang.clone()).or_insert(0) += 1;
+        // Process files in parallel, one chunk at a time
+        for chunk in walk.files.chunks(PROCESS_CHUNK_SIZE) {
+            let infos: Vec<FileInfo> = chunk
+                .par_iter()
+                .filter_map(|path| {
+                    let info = match self.process_file(path, previous) {
+                        Ok(Some(info)) => {
+                            bytes_read.fetch_add(info.size, Ordering::Relaxed);
+                            Some(info)
+                        }
+                        Ok(None) => {
+                            files_skipped.fetch_add(1, Ordering::Relaxed);
+                            None
+                        }
+                        Err(e) => {
+                            if let Ok(mut errs) = errors.lock() {
+                                errs.push(format!("{}: {}", path.display(), e));
+                            }
+                            None
+                        }
+                    };
+                    
+                    let seen = files_seen.fetch_add(1, Ordering::Relaxed) + 1;
+                    self.report_progress(
+                        seen,
+                        total_files,
+                        bytes_read.load(Ordering::Relaxed),
+                        path,
+                        (dirs_skipped, files_skipped.load(Ordering::Relaxed)),
+                    );
+                    
+                    info
+                })
+                .collect();
+            
+            for info in infos {
+                visited += 1;
+                total_bytes += info.size;
+                if let Some(ref lang) = info.language {
+                    let entry = by_language.entry(lang.clone()).or_default();
+                    entry.files += 1;
+                    entry.bytes += info.size;
+                    entry.lines += info.lines as u64;
+                }
+                if matches!(info.change_status, Some(ChangeStatus::Added | ChangeStatus::Modified)) {
+                    changed_files += 1;
+                }
+                if previous.is_some() {
+                    seen.insert(info.path.clone());
+                }
+                visit(info);
             }
         }
         
+        // Anything in the previous scan that we did not see again was deleted
+        let deleted_files: Vec<FileInfo> = match previous {
+            Some(prev) => {
+                let mut deleted: Vec<FileInfo> = prev.files
+                    .iter()
+                    .filter(|(path, _)| !seen.contains(**path))
+                    .map(|(_, info)| FileInfo {
+                        change_status: Some(ChangeStatus::Deleted),
+                        ..(*info).clone()
+                    })
+                    .collect();
+                deleted.sort_by(|a, b| a.path.cmp(&b.path));
+                deleted
+            }
+            None => Vec::new(),
+        };
+        changed_files += deleted_files.len();
+        
         let stats = ScanStats {
-            total_files: files.len(),
+            total_files: visited,
             by_language,
             total_bytes,
-            dirs_skipped: dirs_skipped.load(Ordering::Relaxed),
+            dirs_skipped,
             files_skipped: files_skipped.load(Ordering::Relaxed),
+            changed_files,
+            skipped_by_source: walk.skipped_by_source
+                .into_iter()
+                .map(|(source, counts)| (source.as_str().to_string(), counts))
+                .collect(),
             duration: start.elapsed(),
         };
         
         ScanResult {
             root: self.config.root.display().to_string(),
-            files,
+            files: Vec::new(),
+            deleted_files,
+            hash_algorithm: self.config.compute_hashes.then_some(self.config.hash_algorithm),
             stats,
             errors: errors.into_inner().unwrap_or_default(),
         }
     }
     
     /// Collect all files to process (respecting ignores)
-    fn collect_files(&self) -> Vec<PathBuf> {
-        let mut files = Vec::new();
-        self.walk_dir(&self.config.root, &mut files);
-        files
+    fn collect_files(&self) -> WalkState {
+        let mut state = WalkState::default();
+        
+        if self.config.follow_symlinks {
+            if let Ok(root) = fs::canonicalize(&self.config.root) {
+                state.visited.insert(root.clone());
+                state.ancestors.push(root);
+            }
+        }
+        
+        self.walk_dir(&self.config.root, 0, &mut state);
+        state
     }
     
     /// Recursively walk a directory
-    fn walk_dir(&self, dir: &Path, files: &mut Vec<PathBuf>) {
-        let entries = match fs::read_dir(dir) {
-            Ok(e) => e,
+    ///
+    /// When following symlinks, `state.ancestors` holds the canonicalized
+    /// directories above `dir`, so a symlink pointing back up the tree is
+    /// reported as a cycle instead of looping forever, and `state.visited`
+    /// holds every directory walked, so one reached again through another
+    /// path is skipped. `depth` is the number of directories between `dir`
+    /// and the scan root.
+    fn walk_dir(&self, dir: &Path, depth: usize, state: &mut WalkState) {
+        let entries: Vec<fs::DirEntry> = match fs::read_dir(dir) {
+            Ok(e) => e.flatten().collect(),
             Err(_) => return,
         };
         
-        for entry in entries.flatten() {
+        // Oversized directories (generated code, state mirrors) are skipped wholesale
+        if let Some(max_files) = self.config.max_files_per_dir {
+            let file_count = entries.iter()
+                .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+                .count();
+            if file_count > max_files {
+                state.dirs_skipped += 1;
+                state.files_skipped += file_count;
+                state.errors.push(format!(
+                    "{}: skipped, {} files exceeds max_files_per_dir ({})",
+                    dir.display(), file_count, max_files
+                ));
+                return;
+            }
+        }
+        
+        // Nested .gitignore applies to everything below this directory
+        let nested = if dir != self.config.root {
+            self.ignores.load_nested(dir)
+        } else {
+            None
+        };
+        let pushed = nested.is_some();
+        state.nested.extend(nested);
+        
+        for entry in entries {
             let path = entry.path();
             let relative = path.strip_prefix(&self.config.root).unwrap_or(&path);
             
-            if path.is_dir() {
+            let file_type = match entry.file_type() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            
+            let is_symlink = file_type.is_symlink();
+            if is_symlink && !self.config.follow_symlinks {
+                continue;
+            }
+            
+            // `Path::is_dir` / `is_file` resolve symlinks, plain entries use the cheap file type
+            let (is_dir, is_file) = if is_symlink {
+                (path.is_dir(), path.is_file())
+            } else {
+                (file_type.is_dir(), file_type.is_file())
+            };
+            
+            if is_dir {
                 // Check if directory should be ignored
-                if !self.ignores.is_ignored(relative, true) {
-                    self.walk_dir(&path, files);
+                if let Some(source) = self.ignores.ignore_source(&path, true, &state.nested) {
+                    state.dirs_skipped += 1;
+                    state.skipped_by_source.entry(source).or_default().dirs_skipped += 1;
+                    continue;
                 }
-            } else if path.is_file() {
-                // Check if file should be ignored
-                if !self.ignores.is_ignored(relative, false) {
-                    // Check if file matches include patterns
-                    if self.include_globs.is_empty() || self.include_globs.is_match(relative) {
-                        files.push(path);
+                
+                if self.config.max_depth.is_some_and(|max| depth >= max) {
+                    state.dirs_skipped += 1;
+                    continue;
+                }
+                
+                if !self.config.follow_symlinks {
+                    self.walk_dir(&path, depth + 1, state);
+                    continue;
+                }
+                
+                let target = match fs::canonicalize(&path) {
+                    Ok(target) => target,
+                    Err(e) => {
+                        state.errors.push(format!("{}: {}", path.display(), e));
+                        continue;
                     }
+                };
+                if state.ancestors.contains(&target) {
+                    state.errors.push(format!("{}: symlink cycle detected", path.display()));
+                    continue;
+                }
+                // Already walked through another path
+                if !state.visited.insert(target.clone()) {
+                    continue;
+                }
+                
+                state.ancestors.push(target);
+                self.walk_dir(&path, depth + 1, state);
+                state.ancestors.pop();
+            } else if is_file {
+                // Check if file should be ignored
+                if let Some(source) = self.ignores.ignore_source(&path, false, &state.nested) {
+                    state.files_skipped += 1;
+                    state.skipped_by_source.entry(source).or_default().files_skipped += 1;
+                } else if self.include_globs.is_empty() || self.include_globs.is_match(relative) {
+                    // File matches include patterns
+                    state.files.push(path);
                 }
             }
         }
+        
+        if pushed {
+            state.nested.pop();
+        }
+    }
+    
+    /// Report progress to the configured callback every `progress_interval` files
+    fn report_progress(&self, files_seen: usize, total_files: usize, bytes_read: u64, path: &Path, skipped: (usize, usize)) {
+        let Some(ref on_progress) = self.config.on_progress else {
+            return;
+        };
+        
+        let interval = self.config.progress_interval.max(1);
+        if !files_seen.is_multiple_of(interval) {
+            return;
+        }
+        
+        let current_dir = path
+            .parent()
+            .map(|p| p.strip_prefix(&self.config.root).unwrap_or(p))
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        
+        let (dirs_skipped, files_skipped) = skipped;
+        on_progress(ScanProgress {
+            files_seen,
+            total_files,
+            bytes_read,
+            current_dir,
+            dirs_skipped,
+            files_skipped,
+        });
     }
     
     /// Process a single file
-    fn process_file(&self, path: &Path) -> Result<Option<FileInfo>, std::io::Error> {
+    fn process_file(
+        &self,
+        path: &Path,
+        previous: Option<&PreviousIndex>,
+    ) -> Result<Option<FileInfo>, std::io::Error> {
         let metadata = fs::metadata(path)?;
         let size = metadata.len();
         
@@ -165,36 +429,171 @@ impl Scanner {
             .to_string_lossy()
             .to_string();
         
-        // Detect language from extension
-        let language = detect_language(path);
+        // Detect language from extension, sniffing the shebang of extensionless scripts
+        let language = match detect_language(path) {
+            Some(lang) => Some(lang),
+            None if path.extension().is_none() => read_head(path)
+                .ok()
+                .and_then(|head| detect_language_from_content(&head))
+                .map(|lang| lang.to_string()),
+            None => None,
+        };
+        
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64);
+        
+        // Size and mtime match the previous scan - trust the cached hash, flags and line count
+        let prior = previous.and_then(|p| p.files.get(relative.as_str()).copied());
+        let stale_hashes = previous.is_some_and(|p| p.stale_hashes);
+        let untouched = prior
+            .map(|p| p.size == size && p.mtime.is_some() && p.mtime == mtime)
+            .unwrap_or(false);
+        let cached = prior.filter(|_| untouched);
+        let cached_hash = cached.filter(|_| !stale_hashes).and_then(|p| p.hash.clone());
+        
+        // Hashing reads the whole file, which the heuristics and line count
+        // then reuse; otherwise they read only what they need
+        let content = if self.config.compute_hashes && cached_hash.is_none() {
+            Some(fs::read(path)?)
+        } else {
+            None
+        };
         
         // Compute hash if requested
         let hash = if self.config.compute_hashes {
-            Some(compute_file_hash(path)?)
+            cached_hash.or_else(|| content.as_deref().map(|c| compute_hash(self.config.hash_algorithm, c)))
         } else {
             None
         };
         
+        let (is_minified, is_generated, lines) = match (cached, &content) {
+            (Some(p), _) => (p.is_minified, p.is_generated, p.lines),
+            (None, _) if language.is_none() => (false, false, 0),
+            (None, Some(bytes)) => {
+                let text = String::from_utf8_lossy(bytes);
+                (is_minified(&relative, &text), is_generated(&text), count_lines(bytes))
+            }
+            (None, None) => {
+                let sample = read_sample(path, size)?;
+                (is_minified(&relative, &sample), is_generated(&sample), count_file_lines(path)?)
+            }
+        };
+        
+        let change_status = previous.map(|_| match prior {
+            None => ChangeStatus::Added,
+            // Rehashed with another algorithm, so the new hash differs regardless
+            Some(_) if stale_hashes => ChangeStatus::Modified,
+            Some(_) if untouched => ChangeStatus::Unchanged,
+            // Touched but identical content
+            Some(p) if hash.is_some() && p.hash == hash => ChangeStatus::Unchanged,
+            Some(_) => ChangeStatus::Modified,
+        });
+        
         Ok(Some(FileInfo {
             path: relative,
             size,
             hash,
             language,
+            mtime,
+            change_status,
+            lines,
+            is_minified,
+            is_generated,
         }))
     }
 }
 
-/// Compute xxHash of a file
-fn compute_file_hash(path: &Path) -> Result<String, std::io::Error> {
-    let mut file = fs::File::open(path)?;
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)?;
-    let hash = xxh3_64(&buffer);
-    Ok(format!("{:016x}", hash))
+/// Accumulated state for the single-threaded directory walk
+#[derive(Default)]
+struct WalkState {
+    files: Vec<PathBuf>,
+    /// Canonicalized directories walked so far, when following symlinks
+    visited: HashSet<PathBuf>,
+    /// Canonicalized directories from the root down to the one being walked
+    ancestors: Vec<PathBuf>,
+    errors: Vec<String>,
+    dirs_skipped: usize,
+    files_skipped: usize,
+    /// `.gitignore` files of the directories currently being walked, outermost first
+    nested: Vec<Gitignore>,
+    skipped_by_source: HashMap<IgnoreSource, IgnoreSourceStats>,
+}
+
+/// Files from a previous scan, keyed by relative path
+#[derive(Default)]
+struct PreviousIndex<'a> {
+    files: HashMap<&'a str, &'a FileInfo>,
+    /// The previous scan hashed with a different algorithm
+    stale_hashes: bool,
+}
+
+/// Compute the hex-encoded hash of file contents
+fn compute_hash(algorithm: HashAlgorithm, content: &[u8]) -> String {
+    match algorithm {
+        HashAlgorithm::XxHash64 => format!("{:016x}", xxh3_64(content)),
+        HashAlgorithm::Sha256 => format!("{:x}", Sha256::digest(content)),
+        HashAlgorithm::Blake3 => blake3::hash(content).to_hex().to_string(),
+    }
+}
+
+/// Count lines in file contents (a trailing line without a newline counts)
+fn count_lines(content: &[u8]) -> usize {
+    let newlines = content.iter().filter(|&&b| b == b'\n').count();
+    match content.last() {
+        Some(&b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    }
+}
+
+/// Average line length above which a file is considered minified
+const MINIFIED_LINE_LENGTH: usize = 500;
+
+/// Whether a file looks like a minified bundle
+///
+/// Flags `.min.js` files and files whose average line length exceeds
+/// `MINIFIED_LINE_LENGTH`.
+pub fn is_minified(path: &str, source: &str) -> bool {
+    if path.ends_with(".min.js") {
+        return true;
+    }
+    
+    let lines = source.lines().count();
+    lines > 0 && source.len() / lines > MINIFIED_LINE_LENGTH
+}
+
+/// Whether a file looks like generated code
+///
+/// Looks for generator headers (`// <auto-generated>`, `@generated`,
+/// `DO NOT EDIT`) near the top, and for compiled output whose only trailer is
+/// a `sourceMappingURL` comment.
+pub fn is_generated(source: &str) -> bool {
+    let header = source.lines().take(5).any(|line| {
+        let line = line.trim();
+        line.contains("<auto-generated") || line.contains("@generated") || line.contains("DO NOT EDIT")
+    });
+    if header {
+        return true;
+    }
+    
+    source.lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| {
+            let line = line.trim();
+            line.starts_with("//# sourceMappingURL=") || line.starts_with("/*# sourceMappingURL=")
+        })
+        .unwrap_or(false)
 }
 
-/// Detect language from file extension
+/// Detect language from file extension (or well-known extensionless file names)
 fn detect_language(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    if matches!(file_name, "Gemfile" | "Rakefile" | "Guardfile" | "Podfile") {
+        return Some("ruby".to_string());
+    }
     let ext = path.extension()?.to_str()?;
     
     let lang = match ext.to_lowercase().as_str() {
@@ -261,6 +660,89 @@ fn detect_language(path: &Path) -> Option<String> {
     Some(lang.to_string())
 }
 
+/// Read the first few hundred bytes of a file (enough for a shebang line)
+/// Bytes read from each end of a file for the minified/generated heuristics
+const SAMPLE_SIZE: u64 = 8 * 1024;
+
+/// The head and tail of a file, which the minified/generated heuristics
+/// look at: generator headers, long lines and a `sourceMappingURL` trailer
+fn read_sample(path: &Path, size: u64) -> Result<String, std::io::Error> {
+    let mut file = fs::File::open(path)?;
+    let mut sample = Vec::new();
+    if size <= 2 * SAMPLE_SIZE {
+        file.read_to_end(&mut sample)?;
+    } else {
+        sample.resize(SAMPLE_SIZE as usize, 0);
+        file.read_exact(&mut sample)?;
+        let mut tail = vec![0u8; SAMPLE_SIZE as usize];
+        file.seek(SeekFrom::End(-(SAMPLE_SIZE as i64)))?;
+        file.read_exact(&mut tail)?;
+        sample.push(b'\n');
+        sample.extend(tail);
+    }
+    Ok(String::from_utf8_lossy(&sample).into_owned())
+}
+
+/// `count_lines` over a file, read in chunks
+fn count_file_lines(path: &Path) -> Result<usize, std::io::Error> {
+    let mut file = fs::File::open(path)?;
+    let mut buffer = [0u8; 64 * 1024];
+    let (mut newlines, mut last) = (0, None);
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        newlines += buffer[..n].iter().filter(|&&b| b == b'\n').count();
+        last = Some(buffer[n - 1]);
+    }
+    Ok(match last {
+        Some(b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    })
+}
+
+fn read_head(path: &Path) -> Result<String, std::io::Error> {
+    let mut buffer = [0u8; 512];
+    let n = fs::File::open(path)?.read(&mut buffer)?;
+    Ok(String::from_utf8_lossy(&buffer[..n]).into_owned())
+}
+
+/// Detect language from file content (shebang line or well-known headers)
+///
+/// Used as a fallback for extensionless files such as `bin/deploy`.
+pub fn detect_language_from_content(source: &str) -> Option<&'static str> {
+    let first_line = source.lines().next()?.trim();
+    
+    if let Some(shebang) = first_line.strip_prefix("#!") {
+        let mut parts = shebang.split_whitespace();
+        let mut interpreter = parts.next()?.rsplit('/').next()?;
+        
+        // `#!/usr/bin/env [-S] python3`
+        if interpreter == "env" {
+            interpreter = parts.find(|p| !p.starts_with('-'))?;
+        }
+        
+        let lang = match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+            "python" | "pypy" => "python",
+            "node" | "nodejs" | "bun" => "javascript",
+            "deno" | "ts-node" | "tsx" => "typescript",
+            "sh" | "bash" | "zsh" | "dash" | "ksh" | "ash" => "shell",
+            "ruby" => "ruby",
+            "php" => "php",
+            "pwsh" | "powershell" => "powershell",
+            _ => return None,
+        };
+        return Some(lang);
+    }
+    
+    if first_line.starts_with("<?php") {
+        return Some("php");
+    }
+    
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,4 +754,286 @@ mod tests {
         assert_eq!(detect_language(Path::new("file.rs")), Some("rust".to_string()));
         assert_eq!(detect_language(Path::new("file.unknown")), None);
     }
+
+    #[test]
+    fn test_detect_language_from_content() {
+        assert_eq!(detect_language_from_content("#!/usr/bin/env python3\nprint(1)"), Some("python"));
+        assert_eq!(detect_language_from_content("#!/bin/bash\nset -e"), Some("shell"));
+        assert_eq!(detect_language_from_content("#!/usr/bin/env -S node --no-warnings\n"), Some("javascript"));
+        assert_eq!(detect_language_from_content("<?php\necho 1;"), Some("php"));
+        assert_eq!(detect_language_from_content("just some text"), None);
+    }
+
+    #[test]
+    fn test_scan_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "export const a = 1;").unwrap();
+        fs::write(dir.path().join("b.ts"), "export const b = 2;").unwrap();
+        fs::write(dir.path().join("c.ts"), "export const c = 3;").unwrap();
+        
+        let scanner = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let first = scanner.scan();
+        assert_eq!(first.files.len(), 3);
+        assert!(first.files.iter().all(|f| f.change_status.is_none()));
+        
+        fs::write(dir.path().join("a.ts"), "export const a = 100;").unwrap();
+        fs::remove_file(dir.path().join("b.ts")).unwrap();
+        fs::write(dir.path().join("d.ts"), "export const d = 4;").unwrap();
+        
+        let second = scanner.scan_incremental(&first);
+        let status = |path: &str| {
+            second.files.iter().find(|f| f.path == path).and_then(|f| f.change_status)
+        };
+        
+        assert_eq!(status("a.ts"), Some(ChangeStatus::Modified));
+        assert_eq!(status("c.ts"), Some(ChangeStatus::Unchanged));
+        assert_eq!(status("d.ts"), Some(ChangeStatus::Added));
+        assert_eq!(second.deleted_files.len(), 1);
+        assert_eq!(second.deleted_files[0].path, "b.ts");
+        assert_eq!(second.stats.changed_files, 3);
+    }
+
+    #[test]
+    fn test_hash_algorithm_mismatch_rehashes_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "export const a = 1;").unwrap();
+        
+        let first = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        }).scan();
+        assert_eq!(first.hash_algorithm, Some(HashAlgorithm::XxHash64));
+        assert_eq!(first.files[0].hash.as_ref().unwrap().len(), 16);
+        
+        let second = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            hash_algorithm: HashAlgorithm::Blake3,
+            ..Default::default()
+        }).scan_incremental(&first);
+        assert_eq!(second.hash_algorithm, Some(HashAlgorithm::Blake3));
+        assert_eq!(second.files[0].hash.as_ref().unwrap().len(), 64);
+        assert_eq!(second.files[0].change_status, Some(ChangeStatus::Modified));
+        assert!(second.deleted_files.is_empty());
+        
+        // Deletions are still reported against a result hashed differently
+        fs::remove_file(dir.path().join("a.ts")).unwrap();
+        fs::write(dir.path().join("b.ts"), "export const b = 2;").unwrap();
+        let third = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            hash_algorithm: HashAlgorithm::Sha256,
+            ..Default::default()
+        }).scan_incremental(&second);
+        assert_eq!(third.files.len(), 1);
+        assert_eq!(third.files[0].change_status, Some(ChangeStatus::Added));
+        assert_eq!(third.deleted_files.len(), 1);
+        assert_eq!(third.deleted_files[0].path, "a.ts");
+    }
+
+    #[test]
+    fn test_language_breakdown() {
+        assert_eq!(count_lines(b""), 0);
+        assert_eq!(count_lines(b"a\nb\n"), 2);
+        assert_eq!(count_lines(b"a\nb"), 2);
+        
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "const a = 1;\nconst b = 2;\n").unwrap();
+        fs::write(dir.path().join("b.ts"), "export {};").unwrap();
+        fs::write(dir.path().join("main.py"), "print('hi')\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not source\n").unwrap();
+        
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        }).scan();
+        let ts = &result.stats.by_language["typescript"];
+        assert_eq!(ts.files, 2);
+        assert_eq!(ts.lines, 3);
+        assert_eq!(ts.bytes, 36);
+        assert_eq!(result.stats.by_language["python"].lines, 1);
+        assert_eq!(result.stats.by_language.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_with_visitor() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            fs::write(dir.path().join(format!("file{}.ts", i)), "export {};").unwrap();
+        }
+        
+        let scanner = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let mut paths = Vec::new();
+        let result = scanner.scan_with(|info| paths.push(info.path));
+        
+        paths.sort();
+        assert_eq!(paths, vec!["file0.ts", "file1.ts", "file2.ts", "file3.ts", "file4.ts"]);
+        assert!(result.files.is_empty());
+        assert_eq!(result.stats.total_files, 5);
+        assert_eq!(result.stats.by_language["typescript"].files, 5);
+    }
+
+    #[test]
+    fn test_progress_callback() {
+        use std::sync::Arc;
+        
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("node_modules")).unwrap();
+        for i in 0..5 {
+            fs::write(dir.path().join(format!("file{}.ts", i)), "export {};").unwrap();
+        }
+        
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let scanner = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            on_progress: Some(Arc::new(move |p| sink.lock().unwrap().push(p))),
+            progress_interval: 2,
+            ..Default::default()
+        });
+        scanner.scan();
+        
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.total_files == 5 && e.dirs_skipped == 1));
+        assert!(events.iter().any(|e| e.files_seen == 4));
+    }
+
+    #[test]
+    fn test_nested_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("apps/web");
+        fs::create_dir_all(pkg.join("generated")).unwrap();
+        fs::write(pkg.join(".gitignore"), "generated/\n").unwrap();
+        fs::write(pkg.join("index.ts"), "export {};").unwrap();
+        fs::write(pkg.join("generated/api.ts"), "export {};").unwrap();
+        
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            patterns: vec!["**/*.ts".to_string()],
+            ..Default::default()
+        }).scan();
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.stats.skipped_by_source["gitignore"].dirs_skipped, 1);
+        
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            patterns: vec!["**/*.ts".to_string()],
+            respect_gitignore: false,
+            ..Default::default()
+        }).scan();
+        assert_eq!(result.files.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/a/b")).unwrap();
+        fs::create_dir_all(dir.path().join("proto")).unwrap();
+        fs::write(dir.path().join("src/main.ts"), "export {};").unwrap();
+        fs::write(dir.path().join("src/a/b/deep.ts"), "export {};").unwrap();
+        for i in 0..5 {
+            fs::write(dir.path().join(format!("proto/gen{}.ts", i)), "export {};").unwrap();
+        }
+        
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            max_depth: Some(2),
+            max_files_per_dir: Some(3),
+            ..Default::default()
+        }).scan();
+        
+        let paths: Vec<&str> = result.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/main.ts"]);
+        // src/a/b is too deep, proto has too many files
+        assert_eq!(result.stats.dirs_skipped, 2);
+        assert_eq!(result.stats.files_skipped, 5);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].contains("max_files_per_dir"));
+    }
+
+    #[test]
+    fn test_minified_and_generated_detection() {
+        assert!(is_minified("dist/app.min.js", "var a=1;"));
+        assert!(is_minified("dist/app.js", &"x".repeat(2000)));
+        assert!(!is_minified("src/app.js", "const a = 1;\nconst b = 2;\n"));
+        
+        assert!(is_generated("// <auto-generated>\n// This file was generated\nclass A {}"));
+        assert!(is_generated("// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb"));
+        assert!(is_generated("\"use strict\";\nexports.a = 1;\n//# sourceMappingURL=index.js.map\n"));
+        assert!(!is_generated("export const a = 1;\n"));
+        
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("app.ts"), "export const a = 1;\n").unwrap();
+        fs::write(dir.path().join("bundle.js"), "var a=1;".repeat(100)).unwrap();
+        
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        }).scan();
+        let minified: Vec<&str> = result.files.iter()
+            .filter(|f| f.is_minified)
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(minified, vec!["bundle.js"]);        
+        // Without hashing, large files are judged by their head and tail
+        fs::write(dir.path().join("compiled.js"), format!("{}//# sourceMappingURL=compiled.js.map\n", "exports.a = 1;\n".repeat(4000))).unwrap();
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            compute_hashes: false,
+            ..Default::default()
+        }).scan();
+        let compiled = result.files.iter().find(|f| f.path == "compiled.js").unwrap();
+        assert!(compiled.is_generated && !compiled.is_minified);
+        assert_eq!(compiled.lines, 4001);
+        assert!(result.files.iter().find(|f| f.path == "bundle.js").unwrap().is_minified);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cycle_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.ts"), "export {};").unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("src/loop")).unwrap();
+        
+        // Symlinks are skipped by default
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        }).scan();
+        assert_eq!(result.files.len(), 1);
+        assert!(result.errors.is_empty());
+        
+        // Following symlinks reports the cycle instead of hanging
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            follow_symlinks: true,
+            ..Default::default()
+        }).scan();
+        assert_eq!(result.files.len(), 1);
+        assert!(result.errors.iter().any(|e| e.contains("symlink cycle")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_alias_walked_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.ts"), "export {};").unwrap();
+        // A sibling alias of `src`, which is no cycle
+        std::os::unix::fs::symlink(dir.path().join("src"), dir.path().join("lib")).unwrap();
+        
+        let result = Scanner::new(ScanConfig {
+            root: dir.path().to_path_buf(),
+            follow_symlinks: true,
+            ..Default::default()
+        }).scan();
+        assert_eq!(result.files.len(), 1);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
 }
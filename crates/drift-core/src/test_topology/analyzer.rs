@@ -4,51 +4,115 @@
 //! Regex is only used as fallback for framework-specific patterns that
 //! can't be captured via AST (like decorator strings, test names in strings).
 
+use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use std::time::Instant;
 
+use super::assertions::count_assertions;
+use super::coverage::{match_report_path, parse_coverage_report, LineCoverage};
+use super::flaky::detect_flaky_signals;
+use super::risk::{assess_risk, call_graph_signals};
 use super::types::*;
-use crate::parsers::{ParserManager, ParseResult, FunctionInfo};
+use crate::call_graph::{CallGraphDb, ModuleIndex};
+use crate::parsers::{ParserManager, ParseResult, FunctionInfo, CallSite, ImportInfo, Position, Range};
+
+/// Test file path patterns (these are path-based, not code-based)
+static TEST_PATH_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| vec![
+    Regex::new(r"\.(?:test|spec)\.[jt]sx?$").unwrap(),
+    Regex::new(r"__tests__/.*\.[jt]sx?$").unwrap(),
+    Regex::new(r"test_.*\.py$").unwrap(),
+    Regex::new(r".*_test\.py$").unwrap(),
+    Regex::new(r".*Test\.java$").unwrap(),
+    Regex::new(r".*Tests?\.cs$").unwrap(),
+    Regex::new(r".*_test\.go$").unwrap(),
+    Regex::new(r".*_test\.rs$").unwrap(),
+    Regex::new(r".*Test\.php$").unwrap(),
+]);
+
+/// Slice or map of structs a table-driven Go test iterates (`[]struct {`)
+static GO_TEST_TABLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:\[\]|map\[[^\]]+\])struct\s*\{").unwrap());
+
+/// Class argument of a class mock: `Mock<IUserRepository>`,
+/// `Substitute.For<IUserRepository>`, `mock(UserRepository.class)`,
+/// `patch.object(EmailClient, ...)`, `Mock(spec=EmailClient)`
+static MOCKED_CLASS: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"(?:Mock|For)\s*<\s*([\w.]+)\s*>|(?:mock|spy)\s*\(\s*([\w.]+)\.class\b|object\s*\(\s*([\w.]+)|spec(?:_set)?\s*=\s*([\w.]+)"
+).unwrap());
+
+/// Does `path` follow a test file naming convention?
+pub fn is_test_path(path: &str) -> bool {
+    TEST_PATH_PATTERNS.iter().any(|p| p.is_match(path))
+}
 
 /// Test topology analyzer - AST-first with regex fallbacks
 pub struct TestTopologyAnalyzer {
     parser: ParserManager,
-    // Test file path patterns (these are path-based, not code-based)
-    test_path_patterns: Vec<Regex>,
+    coverage_prefixes: Vec<CoveragePathPrefix>,
+    /// Analyzed file -> line hits, from `load_coverage`
+    line_coverage: HashMap<String, LineCoverage>,
+    suppressed_flaky_signals: HashSet<FlakySignalKind>,
 }
 
 impl TestTopologyAnalyzer {
     pub fn new() -> Self {
         Self {
             parser: ParserManager::new(),
-            // Path patterns for identifying test files
-            test_path_patterns: vec![
-                Regex::new(r"\.(?:test|spec)\.[jt]sx?$").unwrap(),
-                Regex::new(r"__tests__/.*\.[jt]sx?$").unwrap(),
-                Regex::new(r"test_.*\.py$").unwrap(),
-                Regex::new(r".*_test\.py$").unwrap(),
-                Regex::new(r".*Test\.java$").unwrap(),
-                Regex::new(r".*Tests?\.cs$").unwrap(),
-                Regex::new(r".*_test\.go$").unwrap(),
-                Regex::new(r".*_test\.rs$").unwrap(),
-                Regex::new(r".*Test\.php$").unwrap(),
-            ],
+            coverage_prefixes: Vec::new(),
+            line_coverage: HashMap::new(),
+            suppressed_flaky_signals: HashSet::new(),
         }
     }
     
+    /// Rewrite coverage report paths with the first matching prefix before
+    /// matching them to analyzed files
+    pub fn with_coverage_path_prefixes(mut self, prefixes: Vec<CoveragePathPrefix>) -> Self {
+        self.coverage_prefixes = prefixes;
+        self
+    }
+    
+    /// Leave out flaky signals of these kinds, for teams retrying on purpose
+    pub fn with_suppressed_flaky_signals(mut self, kinds: Vec<FlakySignalKind>) -> Self {
+        self.suppressed_flaky_signals = kinds.into_iter().collect();
+        self
+    }
+    
+    /// Load line coverage from lcov, Cobertura or coverage.py reports onto
+    /// `files`, for the coverage percent, uncovered lines and risk level of
+    /// source files in later analyses
+    pub fn load_coverage(&mut self, files: &[String], coverage_paths: &[String]) -> Result<CoverageLoadResult, String> {
+        let mut unmatched_paths = Vec::new();
+        for report in coverage_paths {
+            for (path, coverage) in parse_coverage_report(Path::new(report))? {
+                match match_report_path(&path, &self.coverage_prefixes, files) {
+                    Some(file) => self.line_coverage.entry(file.clone()).or_default().merge(coverage),
+                    None => unmatched_paths.push(path),
+                }
+            }
+        }
+        unmatched_paths.sort();
+        unmatched_paths.dedup();
+        Ok(CoverageLoadResult {
+            matched_files: self.line_coverage.len(),
+            unmatched_paths,
+        })
+    }
+    
     /// Analyze test topology using AST-parsed data
-    pub fn analyze(&mut self, files: &[String]) -> TestTopologyResult {
+    ///
+    /// Without a call graph, risk levels only weigh coverage.
+    pub fn analyze(&mut self, files: &[String], options: &RiskOptions) -> TestTopologyResult {
         let start = Instant::now();
         
+        let index = ModuleIndex::new(files.iter().map(String::as_str));
         let mut test_files = Vec::new();
         let mut source_files: HashSet<String> = HashSet::new();
         let mut tested_files: HashSet<String> = HashSet::new();
         
         for file in files {
             if self.is_test_file(file) {
-                if let Some(test_file) = self.analyze_test_file_ast(file) {
+                if let Some(test_file) = self.analyze_test_file_ast(file, &index) {
                     if let Some(ref tested) = test_file.tests_file {
                         tested_files.insert(tested.clone());
                     }
@@ -59,12 +123,6 @@ impl TestTopologyAnalyzer {
             }
         }
         
-        // Find uncovered files
-        let uncovered_files: Vec<String> = source_files
-            .difference(&tested_files)
-            .cloned()
-            .collect();
-        
         // Build coverage mappings
         let mut coverage_map: HashMap<String, Vec<String>> = HashMap::new();
         for test_file in &test_files {
@@ -75,14 +133,25 @@ impl TestTopologyAnalyzer {
             }
         }
         
-        let coverage: Vec<TestCoverage> = coverage_map.into_iter()
+        let test_counts: HashMap<&str, usize> = test_files.iter()
+            .map(|t| (t.path.as_str(), t.test_cases.len()))
+            .collect();
+        let mut coverage: Vec<TestCoverage> = coverage_map.into_iter()
             .map(|(source, tests)| TestCoverage {
+                test_function_count: tests.iter().map(|t| test_counts[t.as_str()]).sum(),
                 source_file: source,
                 test_files: tests,
                 coverage_percent: None,
+                uncovered_line_ranges: Vec::new(),
                 risk_level: RiskLevel::Low,
+                risk_factors: Vec::new(),
+                mapping_method: MappingMethod::Convention,
             })
+            .chain(source_files.difference(&tested_files).map(|file| untested(file, MappingMethod::Convention)))
             .collect();
+        coverage.sort_by(|a, b| a.source_file.cmp(&b.source_file));
+        let uncovered_files = self.join_line_coverage(&mut coverage);
+        assess_risk(&mut coverage, &HashMap::new(), options);
         
         let total_tests: usize = test_files.iter()
             .map(|f| f.test_cases.len())
@@ -93,6 +162,8 @@ impl TestTopologyAnalyzer {
             .count();
         
         TestTopologyResult {
+            mock_overlaps: mock_overlaps(&test_files, &coverage),
+            flaky_signal_count: test_files.iter().map(|f| f.flaky_signals.len()).sum(),
             test_files,
             coverage,
             uncovered_files,
@@ -103,12 +174,141 @@ impl TestTopologyAnalyzer {
         }
     }
     
+    /// Analyze test topology, mapping tests through the call graph
+    ///
+    /// A source file is covered when a call from a test file resolves to one
+    /// of its functions; each call counts toward the test case it appears in.
+    /// The call graph stores paths relative to `root`, which `files` may be
+    /// absolute under. Risk levels weigh fan-in and reachable sensitive data
+    /// besides coverage.
+    pub fn analyze_with_call_graph(
+        &mut self,
+        root: &Path,
+        files: &[String],
+        db: &CallGraphDb,
+        options: &RiskOptions,
+    ) -> Result<TestTopologyResult, String> {
+        let start = Instant::now();
+        let relative = |file: &str| -> String {
+            Path::new(file).strip_prefix(root)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| file.to_string())
+        };
+        
+        let index = ModuleIndex::new(files.iter().map(String::as_str));
+        let mut test_files = Vec::new();
+        let mut source_files: BTreeMap<String, String> = BTreeMap::new();
+        for file in files {
+            if self.is_test_file(file) {
+                if let Some(test_file) = self.analyze_test_file_ast(file, &index) {
+                    test_files.push(test_file);
+                }
+            } else {
+                source_files.insert(relative(file), file.clone());
+            }
+        }
+        let tests_by_file: HashMap<String, &TestFile> = test_files.iter()
+            .map(|t| (relative(&t.path), t))
+            .collect();
+        
+        let functions = db.get_all_functions()
+            .map_err(|e| format!("Failed to read call graph: {}", e))?;
+        
+        // Source file -> (test files, (test file, test case line))
+        type Touches<'a> = (BTreeSet<&'a str>, HashSet<(&'a str, u32)>);
+        let mut touched: BTreeMap<&str, Touches> = BTreeMap::new();
+        for function in &functions {
+            let Some(test_file) = tests_by_file.get(CallGraphDb::extract_file_from_id(&function.id)) else {
+                continue;
+            };
+            for call in &function.calls {
+                let Some(target) = call.resolved_id.as_deref().map(CallGraphDb::extract_file_from_id) else {
+                    continue;
+                };
+                if self.is_test_file(target) {
+                    continue;
+                }
+                let (tests, cases) = touched.entry(target).or_default();
+                tests.insert(&test_file.path);
+                if let Some(case) = enclosing_test_case(test_file, call.line) {
+                    cases.insert((&test_file.path, case.line));
+                }
+            }
+        }
+        
+        let analyzed_file = |source: &str| source_files.get(source)
+            .cloned()
+            .unwrap_or_else(|| root.join(source).to_string_lossy().to_string());
+        let mut coverage: Vec<TestCoverage> = touched.iter()
+            .map(|(&source, (tests, cases))| TestCoverage {
+                source_file: analyzed_file(source),
+                test_files: tests.iter().map(|t| t.to_string()).collect(),
+                coverage_percent: None,
+                uncovered_line_ranges: Vec::new(),
+                risk_level: RiskLevel::Low,
+                risk_factors: Vec::new(),
+                mapping_method: MappingMethod::CallGraph,
+                test_function_count: cases.len(),
+            })
+            .chain(source_files.iter()
+                .filter(|(source, _)| !touched.contains_key(source.as_str()))
+                .map(|(_, file)| untested(file, MappingMethod::CallGraph)))
+            .collect();
+        coverage.sort_by(|a, b| a.source_file.cmp(&b.source_file));
+        let uncovered_files = self.join_line_coverage(&mut coverage);
+        let signals = call_graph_signals(db, &functions)?.into_iter()
+            .map(|(source, signals)| (analyzed_file(source), signals))
+            .collect();
+        assess_risk(&mut coverage, &signals, options);
+        
+        let total_tests: usize = test_files.iter()
+            .map(|f| f.test_cases.len())
+            .sum();
+        let skipped_tests: usize = test_files.iter()
+            .flat_map(|f| &f.test_cases)
+            .filter(|t| t.is_skipped)
+            .count();
+        
+        Ok(TestTopologyResult {
+            mock_overlaps: mock_overlaps(&test_files, &coverage),
+            flaky_signal_count: test_files.iter().map(|f| f.flaky_signals.len()).sum(),
+            test_files,
+            coverage,
+            uncovered_files,
+            total_tests,
+            skipped_tests,
+            files_analyzed: files.len(),
+            duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+    
+    /// Fill in loaded line coverage: coverage percent and uncovered lines.
+    /// Files no test maps to that the reports show executed are mapped by
+    /// coverage; the rest are returned as uncovered.
+    fn join_line_coverage(&self, coverage: &mut [TestCoverage]) -> Vec<String> {
+        let mut uncovered_files = Vec::new();
+        for entry in coverage.iter_mut() {
+            if let Some(lines) = self.line_coverage.get(&entry.source_file) {
+                entry.coverage_percent = lines.percent();
+                entry.uncovered_line_ranges = lines.uncovered_ranges();
+            }
+            if entry.test_files.is_empty() {
+                if entry.coverage_percent.is_some_and(|percent| percent > 0.0) {
+                    entry.mapping_method = MappingMethod::Coverage;
+                } else {
+                    uncovered_files.push(entry.source_file.clone());
+                }
+            }
+        }
+        uncovered_files
+    }
+    
     fn is_test_file(&self, path: &str) -> bool {
-        self.test_path_patterns.iter().any(|p| p.is_match(path))
+        is_test_path(path)
     }
     
     /// Analyze test file using AST-first approach
-    fn analyze_test_file_ast(&mut self, path: &str) -> Option<TestFile> {
+    fn analyze_test_file_ast(&mut self, path: &str, index: &ModuleIndex) -> Option<TestFile> {
         let source = std::fs::read_to_string(path).ok()?;
         
         // Parse via tree-sitter AST
@@ -116,8 +316,15 @@ impl TestTopologyAnalyzer {
         
         let framework = self.detect_framework_from_ast(&parse_result, path);
         let test_cases = self.extract_test_cases_from_ast(&parse_result, &source, framework);
-        let mocks = self.extract_mocks_from_ast(&parse_result, &source, framework);
+        let mocks = self.extract_mocks_from_ast(&parse_result, &source, framework, path, index);
+        let flaky_signals = detect_flaky_signals(&parse_result, &source, framework, &mocks, &self.suppressed_flaky_signals);
         let tests_file = self.infer_source_file(path);
+        let run: Vec<&TestCase> = test_cases.iter().filter(|c| !c.is_skipped).collect();
+        let assertion_density = if run.is_empty() {
+            0.0
+        } else {
+            run.iter().map(|c| c.assertion_count).sum::<usize>() as f32 / run.len() as f32
+        };
         
         Some(TestFile {
             path: path.to_string(),
@@ -125,6 +332,8 @@ impl TestTopologyAnalyzer {
             framework,
             test_cases,
             mocks,
+            flaky_signals,
+            assertion_density,
         })
     }
     
@@ -208,57 +417,71 @@ impl TestTopologyAnalyzer {
         
         // Use AST-parsed functions as primary source
         for func in &result.functions {
-            let is_test = self.is_test_function(&func, framework);
+            let is_test = self.is_test_function(func, framework);
             
             if is_test {
-                let is_skipped = self.is_skipped_test(&func, source, framework);
+                let is_skipped = self.is_skipped_test(func, result, source, framework);
+                let assertions = count_assertions(result, source, framework, func.range.start.line, func.range.end.line);
                 
                 cases.push(TestCase {
                     name: func.name.clone(),
                     test_type: self.infer_test_type(&func.name),
                     line: func.range.start.line,
                     is_skipped,
+                    is_only: false,
+                    parameter_sets: self.parameter_sets(func, result, source, framework),
+                    assertion_count: assertions.assertions,
+                    is_snapshot_only: assertions.is_snapshot_only(),
                 });
             }
         }
         
         // For JS/TS: Also look for it()/test() calls from AST call sites
         if matches!(framework, TestFramework::Jest | TestFramework::Vitest | TestFramework::Mocha) {
-            for call in &result.calls {
-                // Handle regular it()/test() calls
-                if call.callee == "it" || call.callee == "test" {
-                    // Extract test name from source at this line (regex fallback for string extraction)
-                    if let Some(name) = self.extract_test_name_at_line(source, call.range.start.line) {
-                        // Avoid duplicates
-                        if !cases.iter().any(|c| c.line == call.range.start.line) {
-                            cases.push(TestCase {
-                                name,
-                                test_type: TestType::Unit,
-                                line: call.range.start.line,
-                                is_skipped: false,
-                            });
-                        }
-                    }
+            let calls: Vec<(&CallSite, JsTestCall)> = result.calls.iter()
+                .filter_map(|call| Some((call, JsTestCall::of(call)?)))
+                .collect();
+            // describe.skip()/describe.only() apply to every test inside the suite
+            let in_suite = |line: u32, modifier: JsModifier| calls.iter().any(|(call, test)| {
+                test.is_suite && test.modifier == modifier
+                    && call.range.start.line <= line && line <= call.range.end.line
+            });
+            
+            for (call, test) in &calls {
+                let line = call.range.start.line;
+                // Avoid duplicates
+                if test.is_suite || cases.iter().any(|c| c.line == line) {
+                    continue;
                 }
-                // Handle it.skip()/test.skip() - callee is "skip", receiver is "it" or "test"
-                else if call.callee == "skip" {
-                    if let Some(ref recv) = call.receiver {
-                        if recv == "it" || recv == "test" {
-                            // Extract test name from source at this line
-                            if let Some(name) = self.extract_skipped_test_name_at_line(source, call.range.start.line) {
-                                // Avoid duplicates
-                                if !cases.iter().any(|c| c.line == call.range.start.line) {
-                                    cases.push(TestCase {
-                                        name,
-                                        test_type: TestType::Unit,
-                                        line: call.range.start.line,
-                                        is_skipped: true,
-                                    });
-                                }
-                            }
-                        }
+                // it.each(table)('name', fn) - the name follows the table call
+                let (name, parameter_sets) = if test.modifier == JsModifier::Each {
+                    match each_table(source, call) {
+                        Some((name, sets)) => (Some(name), sets),
+                        None => continue,
                     }
-                }
+                } else {
+                    // Regex fallback for names the AST has no string literal for
+                    let name = call.string_arg.clone()
+                        .or_else(|| self.extract_test_name_at_line(source, line));
+                    (name, None)
+                };
+                let Some(name) = name else { continue };
+                let end = match test.modifier {
+                    JsModifier::Each => each_call_end_line(source, call).unwrap_or(call.range.end.line),
+                    _ => call.range.end.line,
+                };
+                let assertions = count_assertions(result, source, framework, line, end);
+                
+                cases.push(TestCase {
+                    name,
+                    test_type: TestType::Unit,
+                    line,
+                    is_skipped: test.modifier == JsModifier::Skip || in_suite(line, JsModifier::Skip),
+                    is_only: test.modifier == JsModifier::Only || in_suite(line, JsModifier::Only),
+                    parameter_sets,
+                    assertion_count: assertions.assertions,
+                    is_snapshot_only: assertions.is_snapshot_only(),
+                });
             }
         }
         
@@ -267,13 +490,14 @@ impl TestTopologyAnalyzer {
     
     /// Check if function is a test based on AST data
     fn is_test_function(&self, func: &FunctionInfo, framework: TestFramework) -> bool {
+        let mut names = func.decorators.iter().map(|d| decorator_name(d));
         match framework {
             TestFramework::Pytest => func.name.starts_with("test_"),
             TestFramework::GoTest => func.name.starts_with("Test"),
-            TestFramework::RustTest => func.decorators.iter().any(|d| d == "test"),
+            TestFramework::RustTest => names.any(|n| n == "test" || n.ends_with("::test") || n == "rstest"),
             TestFramework::JUnit => func.decorators.iter().any(|d| d.contains("Test")),
             TestFramework::NUnit | TestFramework::XUnit => {
-                func.decorators.iter().any(|d| d == "Test" || d == "Fact" || d == "Theory")
+                names.any(|n| matches!(n, "Test" | "TestCase" | "TestCaseSource" | "Fact" | "Theory"))
             }
             TestFramework::PHPUnit => {
                 func.name.starts_with("test") || 
@@ -283,9 +507,10 @@ impl TestTopologyAnalyzer {
         }
     }
     
-    /// Check if test is skipped (uses decorators from AST, with source fallback)
-    fn is_skipped_test(&self, func: &FunctionInfo, source: &str, framework: TestFramework) -> bool {
-        // Check decorators from AST first
+    /// Check if test is skipped (uses decorators and skip calls from AST, with source fallback)
+    fn is_skipped_test(&self, func: &FunctionInfo, result: &ParseResult, source: &str, framework: TestFramework) -> bool {
+        // Check decorators from AST first: @pytest.mark.skip, [Fact(Skip = "...")],
+        // [Ignore], @Disabled, #[ignore]
         for decorator in &func.decorators {
             let d = decorator.to_lowercase();
             if d.contains("skip") || d.contains("ignore") || d.contains("disabled") {
@@ -293,6 +518,18 @@ impl TestTopologyAnalyzer {
             }
         }
         
+        // Skip calls in the test body - t.Skip() also skips the subtests of
+        // table-driven Go tests when made inside t.Run()
+        let is_skip_call = |call: &CallSite| match framework {
+            TestFramework::GoTest => call.receiver.is_some() && matches!(call.callee.as_str(), "Skip" | "Skipf" | "SkipNow"),
+            TestFramework::Pytest => call.callee == "skip" && call.receiver.as_deref() == Some("pytest"),
+            TestFramework::PHPUnit => call.callee == "markTestSkipped",
+            _ => false,
+        };
+        if calls_within(result, func).any(is_skip_call) {
+            return true;
+        }
+        
         // Fallback: check source line for skip patterns
         // line is 0-indexed from tree-sitter
         let lines: Vec<&str> = source.lines().collect();
@@ -311,45 +548,114 @@ impl TestTopologyAnalyzer {
         false
     }
     
-    /// Extract mocks from AST call sites
+    /// Parameter sets of a parametrized test function, when listed literally
+    fn parameter_sets(&self, func: &FunctionInfo, result: &ParseResult, source: &str, framework: TestFramework) -> Option<usize> {
+        let decorators = || func.decorators.iter().map(|d| (decorator_name(d), d.as_str()));
+        match framework {
+            // @pytest.mark.parametrize("a,b", [(1, 2), (3, 4)]); stacked
+            // decorators run every combination
+            TestFramework::Pytest => decorators()
+                .filter(|(name, _)| name.ends_with("parametrize"))
+                .map(|(_, d)| {
+                    let (args, _) = top_level_items(&d[d.find('(')?..])?;
+                    let values = args.iter()
+                        .find_map(|a| a.strip_prefix("argvalues="))
+                        .or_else(|| args.get(1).copied())?;
+                    top_level_items(values.trim()).map(|(values, _)| values.len())
+                })
+                .reduce(|a, b| Some(a? * b?))?,
+            // [InlineData(1, 2)] / [TestCase(1, 2)], one per parameter set
+            TestFramework::NUnit | TestFramework::XUnit => {
+                let count = decorators().filter(|(name, _)| matches!(*name, "InlineData" | "TestCase")).count();
+                (count > 0).then_some(count)
+            }
+            // @ValueSource(ints = {1, 2, 3}) / @CsvSource({"a, 1", "b, 2"})
+            TestFramework::JUnit => decorators()
+                .find(|(name, _)| matches!(*name, "ValueSource" | "CsvSource"))
+                .and_then(|(_, d)| top_level_items(&d[d.find('{')?..]))
+                .map(|(values, _)| values.len()),
+            // rstest: #[case(1, 2)], one per parameter set
+            TestFramework::RustTest => {
+                let count = decorators().filter(|(name, _)| *name == "case").count();
+                (count > 0).then_some(count)
+            }
+            // Table-driven tests: a slice or map of structs, run with t.Run()
+            TestFramework::GoTest => {
+                if !calls_within(result, func).any(|c| c.callee == "Run") {
+                    return None;
+                }
+                let body: String = source.lines()
+                    .skip(func.range.start.line as usize)
+                    .take((func.range.end.line - func.range.start.line + 1) as usize)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let table = GO_TEST_TABLE.find(&body)?;
+                let (_, struct_len) = top_level_items(&body[table.end() - 1..])?;
+                let rows = body[table.end() - 1 + struct_len..].trim_start();
+                top_level_items(rows).map(|(rows, _)| rows.len())
+            }
+            _ => None,
+        }
+    }
+    
+    /// Extract mocks from AST call sites, resolving what they mock to a file
     fn extract_mocks_from_ast(
         &self,
         result: &ParseResult,
         source: &str,
         framework: TestFramework,
+        path: &str,
+        index: &ModuleIndex,
     ) -> Vec<MockUsage> {
         let mut mocks = Vec::new();
         
         // Use AST call sites to find mock calls
         for call in &result.calls {
             let mock_type = match (framework, call.callee.as_str(), call.receiver.as_deref()) {
-                // Jest: jest.mock(), jest.spyOn(), jest.fn()
-                (TestFramework::Jest, "mock", Some("jest")) => Some(MockType::Module),
+                // Jest: jest.mock(), jest.doMock(), jest.spyOn(), jest.fn()
+                (TestFramework::Jest, "mock" | "doMock", Some("jest")) => Some(MockType::Module),
                 (TestFramework::Jest, "spyOn", Some("jest")) => Some(MockType::Function),
                 (TestFramework::Jest, "fn", Some("jest")) => Some(MockType::Function),
                 
-                // Vitest: vi.mock(), vi.spyOn(), vi.fn()
-                (TestFramework::Vitest, "mock", Some("vi")) => Some(MockType::Module),
+                // Vitest: vi.mock(), vi.doMock(), vi.spyOn(), vi.fn()
+                (TestFramework::Vitest, "mock" | "doMock", Some("vi")) => Some(MockType::Module),
                 (TestFramework::Vitest, "spyOn", Some("vi")) => Some(MockType::Function),
                 (TestFramework::Vitest, "fn", Some("vi")) => Some(MockType::Function),
                 
-                // Python: Mock(), MagicMock(), patch()
+                // Python: Mock(), MagicMock(), patch(), patch.object()
                 (TestFramework::Pytest, "Mock", _) => Some(MockType::Class),
                 (TestFramework::Pytest, "MagicMock", _) => Some(MockType::Class),
                 (TestFramework::Pytest, "patch", _) => Some(MockType::Function),
+                (TestFramework::Pytest, "object", Some(recv)) if recv.ends_with("patch") => Some(MockType::Function),
+                
+                // C#: new Mock<T>() (Moq), Substitute.For<T>() (NSubstitute)
+                (TestFramework::NUnit | TestFramework::XUnit, "Mock", None) => Some(MockType::Class),
+                (TestFramework::NUnit | TestFramework::XUnit, "For", Some("Substitute")) => Some(MockType::Class),
+                
+                // Java: mock(T.class), spy(T.class) (Mockito)
+                (TestFramework::JUnit, "mock" | "spy", None | Some("Mockito")) => Some(MockType::Class),
                 
                 _ => None,
             };
             
             if let Some(mt) = mock_type {
-                // Extract target from source (regex fallback for string argument)
-                let target = self.extract_mock_target_at_line(source, call.range.start.line)
-                    .unwrap_or_else(|| call.callee.clone());
+                let line = call.range.start.line;
+                // Module path or patch target from the AST string argument,
+                // the class of class mocks, else the regex fallback
+                let argument = match (mt, call.callee.as_str()) {
+                    (MockType::Module, _) | (_, "patch") => call.string_arg.clone(),
+                    (MockType::Class, _) | (_, "object") => self.extract_mocked_class_at_line(source, line),
+                    _ => None,
+                }
+                .or_else(|| self.extract_mock_target_at_line(source, line));
+                let resolved_file = argument.as_deref()
+                    .and_then(|target| resolve_mock_target(path, result, index, target));
                 
                 mocks.push(MockUsage {
-                    target,
+                    target: argument.unwrap_or_else(|| call.callee.clone()),
                     mock_type: mt,
-                    line: call.range.start.line,
+                    line,
+                    resolved_file,
                 });
             }
         }
@@ -363,21 +669,8 @@ impl TestTopologyAnalyzer {
         // line is 0-indexed from tree-sitter
         let line_content = lines.get(line as usize)?;
         
-        // Match: it('name', ...) or test('name', ...)
-        let re = Regex::new(r#"(?:it|test)\s*\(\s*['"`]([^'"`]+)['"`]"#).ok()?;
-        re.captures(line_content)
-            .and_then(|c| c.get(1))
-            .map(|m| m.as_str().to_string())
-    }
-    
-    /// Regex fallback: extract test name from skipped test (it.skip/test.skip)
-    fn extract_skipped_test_name_at_line(&self, source: &str, line: u32) -> Option<String> {
-        let lines: Vec<&str> = source.lines().collect();
-        // line is 0-indexed from tree-sitter
-        let line_content = lines.get(line as usize)?;
-        
-        // Match: it.skip('name', ...) or test.skip('name', ...)
-        let re = Regex::new(r#"(?:it|test)\.skip\s*\(\s*['"`]([^'"`]+)['"`]"#).ok()?;
+        // Match: it('name', ...), test.skip('name', ...), xit('name', ...), fit('name', ...)
+        let re = Regex::new(r#"\b[xf]?(?:it|test)(?:\.(?:skip|only))?\s*\(\s*['"`]([^'"`]+)['"`]"#).ok()?;
         re.captures(line_content)
             .and_then(|c| c.get(1))
             .map(|m| m.as_str().to_string())
@@ -396,6 +689,17 @@ impl TestTopologyAnalyzer {
             .map(|m| m.as_str().trim().to_string())
     }
     
+    /// Regex fallback: extract the class a class mock is created for at line
+    fn extract_mocked_class_at_line(&self, source: &str, line: u32) -> Option<String> {
+        let line_content = source.lines().nth(line as usize)?;
+        MOCKED_CLASS.captures(line_content)?
+            .iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .map(|m| m.as_str().to_string())
+    }
+    
     fn infer_source_file(&self, test_path: &str) -> Option<String> {
         let path = Path::new(test_path);
         let file_name = path.file_name()?.to_str()?;
@@ -428,6 +732,228 @@ impl TestTopologyAnalyzer {
     }
 }
 
+/// Modifier of a JS test or suite call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsModifier {
+    None,
+    Skip,
+    Only,
+    Each,
+}
+
+/// A JS test framework call: a test (`it`, `test`) or a suite (`describe`)
+struct JsTestCall {
+    is_suite: bool,
+    modifier: JsModifier,
+}
+
+impl JsTestCall {
+    fn of(call: &CallSite) -> Option<Self> {
+        let (base, modifier) = match (call.receiver.as_deref(), call.callee.as_str()) {
+            (None, "it" | "test") => ("it", JsModifier::None),
+            (None, "xit" | "xtest") => ("it", JsModifier::Skip),
+            (None, "fit") => ("it", JsModifier::Only),
+            (None, "xdescribe") => ("describe", JsModifier::Skip),
+            (None, "fdescribe") => ("describe", JsModifier::Only),
+            (Some(base @ ("it" | "test" | "describe")), "skip") => (base, JsModifier::Skip),
+            (Some(base @ ("it" | "test" | "describe")), "only") => (base, JsModifier::Only),
+            (Some(base @ ("it" | "test")), "each") => (base, JsModifier::Each),
+            _ => return None,
+        };
+        Some(Self { is_suite: base == "describe", modifier })
+    }
+}
+
+/// Name and parameter-set count of `it.each(table)('name', fn)`, from the
+/// `it.each(table)` call
+fn each_table(source: &str, call: &CallSite) -> Option<(String, Option<usize>)> {
+    let start = byte_offset(source, call.range.start)?;
+    let end = byte_offset(source, call.range.end)?;
+    let call_text = source.get(start..end)?;
+    let table = call_text[call_text.find("each")? + "each".len()..]
+        .trim_start()
+        .strip_prefix('(')?
+        .trim_start();
+    // A table held in a variable can't be counted
+    let sets = top_level_items(table).map(|(rows, _)| rows.len());
+    
+    let name = source[end..].trim_start().strip_prefix('(')?.trim_start();
+    let quote = name.chars().next().filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let name = &name[1..];
+    Some((name[..name.find(quote)?].to_string(), sets))
+}
+
+/// Last line of `it.each(table)('name', fn)`, from the `it.each(table)` call
+fn each_call_end_line(source: &str, call: &CallSite) -> Option<u32> {
+    let end = byte_offset(source, call.range.end)?;
+    let rest = &source[end..];
+    let args = rest.trim_start();
+    let (_, len) = top_level_items(args)?;
+    let offset = end + (rest.len() - args.len()) + len;
+    Some(source[..offset].matches('\n').count() as u32)
+}
+
+/// Items of the bracketed list `text` starts with, and the length of the list
+///
+/// Commas inside nested brackets, strings and `//` comments don't separate items.
+fn top_level_items(text: &str) -> Option<(Vec<&str>, usize)> {
+    if !text.starts_with(['(', '[', '{']) {
+        return None;
+    }
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut comment = false;
+    let mut items = Vec::new();
+    let mut item_start = 1;
+    for (i, c) in text.char_indices() {
+        if comment {
+            comment = c != '\n';
+            continue;
+        }
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '/' if text[i..].starts_with("//") => comment = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    items.push(&text[item_start..i]);
+                    let items = items.into_iter()
+                        .map(strip_line_comments)
+                        .filter(|item| !item.is_empty())
+                        .collect();
+                    return Some((items, i + 1));
+                }
+            }
+            ',' if depth == 1 => {
+                items.push(&text[item_start..i]);
+                item_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// An item of a list without the whole-line `//` comments around it
+fn strip_line_comments(item: &str) -> &str {
+    let mut item = item.trim();
+    while item.starts_with("//") {
+        item = item.find('\n').map_or("", |i| item[i..].trim());
+    }
+    while let Some(i) = item.rfind('\n').filter(|&i| item[i..].trim_start().starts_with("//")) {
+        item = item[..i].trim();
+    }
+    item
+}
+
+/// Byte offset of a tree-sitter position (0-indexed line, byte column)
+fn byte_offset(source: &str, position: Position) -> Option<usize> {
+    let line_start: usize = source.split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum();
+    let offset = line_start + position.column as usize;
+    (offset <= source.len()).then_some(offset)
+}
+
+/// Bare name of a decorator, attribute or annotation: `@pytest.mark.skip(...)`
+/// -> `pytest.mark.skip`, `[Fact(Skip = "...")]` -> `Fact`, `#[ignore]` -> `ignore`
+pub(super) fn decorator_name(decorator: &str) -> &str {
+    let name = decorator.strip_prefix("#[").unwrap_or(decorator).trim_start_matches(['@', '[']);
+    let end = name.find(|c: char| matches!(c, '(' | ']' | '=') || c.is_whitespace()).unwrap_or(name.len());
+    &name[..end]
+}
+
+/// Calls made inside a function
+fn calls_within<'a>(result: &'a ParseResult, func: &'a FunctionInfo) -> impl Iterator<Item = &'a CallSite> {
+    result.calls.iter().filter(|c| func.range.start.line <= c.range.start.line && c.range.end.line <= func.range.end.line)
+}
+
+/// File a mock target refers to: a module path (`../db`), a dotted module
+/// (`app.services.email`), or a class, through the test file's import of
+/// it or else the file named after it
+fn resolve_mock_target(path: &str, result: &ParseResult, index: &ModuleIndex, target: &str) -> Option<String> {
+    let head = target.split('.').next().unwrap_or(target);
+    let import = result.imports.iter()
+        .filter(|_| !target.contains('/'))
+        .find(|i| {
+            i.named.iter().any(|n| n == head)
+                || i.default.as_deref() == Some(head)
+                || i.namespace.as_deref() == Some(head)
+        });
+    let files = match import {
+        Some(import) => index.resolve(path, import),
+        None => index.resolve(path, &ImportInfo {
+            source: target.to_string(),
+            named: Vec::new(),
+            default: None,
+            namespace: None,
+            is_type_only: false,
+            relative_level: 0,
+            range: Range::new(0, 0, 0, 0),
+        }),
+    };
+    // A package or namespace spanning several files names no single target
+    match files.as_slice() {
+        [file] if file != path => Some(file.clone()),
+        _ => None,
+    }
+}
+
+/// Mocks targeting a source file the mocking test file is mapped to
+fn mock_overlaps(test_files: &[TestFile], coverage: &[TestCoverage]) -> Vec<MockOverlap> {
+    let by_path: HashMap<&str, &TestFile> = test_files.iter().map(|t| (t.path.as_str(), t)).collect();
+    let mut overlaps: Vec<MockOverlap> = coverage.iter()
+        .flat_map(|c| c.test_files.iter().map(move |test| (c, test)))
+        .filter_map(|(c, test)| Some((c, by_path.get(test.as_str())?)))
+        .flat_map(|(c, test)| {
+            test.mocks.iter()
+                .filter(|mock| mock.resolved_file.as_deref() == Some(c.source_file.as_str()))
+                .map(|mock| MockOverlap {
+                    test_file: test.path.clone(),
+                    source_file: c.source_file.clone(),
+                    target: mock.target.clone(),
+                    line: mock.line,
+                })
+        })
+        .collect();
+    overlaps.sort_by(|a, b| (&a.test_file, a.line).cmp(&(&b.test_file, b.line)));
+    overlaps
+}
+
+/// Coverage entry of a source file no test maps to
+fn untested(file: &str, mapping_method: MappingMethod) -> TestCoverage {
+    TestCoverage {
+        source_file: file.to_string(),
+        test_files: Vec::new(),
+        coverage_percent: None,
+        uncovered_line_ranges: Vec::new(),
+        risk_level: RiskLevel::Low,
+        risk_factors: Vec::new(),
+        mapping_method,
+        test_function_count: 0,
+    }
+}
+
+/// Test case a call on `line` belongs to: the last one starting at or before it
+fn enclosing_test_case(test_file: &TestFile, line: u32) -> Option<&TestCase> {
+    test_file.test_cases.iter()
+        .filter(|case| case.line <= line)
+        .max_by_key(|case| case.line)
+}
+
 impl Default for TestTopologyAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -452,4 +978,348 @@ mod tests {
         assert!(!analyzer.is_test_file("src/utils.ts"));
         assert!(!analyzer.is_test_file("main.py"));
     }
+    
+    #[test]
+    fn test_case_modifiers_and_parameters() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("cart.test.ts", concat!(
+                "import { describe, it, test, expect } from 'vitest';\n",
+                "\n",
+                "describe('cart', () => {\n",
+                "  it('adds items', () => {});\n",
+                "  it.skip('removes items', () => {});\n",
+                "  it.only('totals', () => {});\n",
+                "  test.each([\n",
+                "    [1, 1, 2],\n",
+                "    [1, 2, 3],\n",
+                "  ])('add(%i, %i)', (a, b, expected) => {});\n",
+                "});\n",
+                "describe.skip('legacy', () => {\n",
+                "  xit('migrates', () => {});\n",
+                "  it('exports', () => {});\n",
+                "});\n",
+            )),
+            ("test_math.py", concat!(
+                "import pytest\n",
+                "\n",
+                "@pytest.mark.parametrize(\"a,b\", [(1, 2), (3, 4), (5, 6)])\n",
+                "@pytest.mark.parametrize(\"c\", [True, False])\n",
+                "def test_add(a, b, c):\n",
+                "    assert a < b\n",
+                "\n",
+                "@pytest.mark.skip(reason=\"flaky\")\n",
+                "def test_sub():\n",
+                "    pass\n",
+                "\n",
+                "def test_div():\n",
+                "    pytest.skip(\"not on this platform\")\n",
+            )),
+            ("math_test.go", concat!(
+                "package math\n",
+                "\n",
+                "import \"testing\"\n",
+                "\n",
+                "func TestAdd(t *testing.T) {\n",
+                "\ttests := []struct {\n",
+                "\t\tname string\n",
+                "\t\ta, b int\n",
+                "\t}{\n",
+                "\t\t// small numbers\n",
+                "\t\t{\"one\", 1, 1},\n",
+                "\t\t{\"two\", 2, 2},\n",
+                "\t}\n",
+                "\tfor _, tt := range tests {\n",
+                "\t\tt.Run(tt.name, func(t *testing.T) {\n",
+                "\t\t\tif testing.Short() {\n",
+                "\t\t\t\tt.Skip(\"slow\")\n",
+                "\t\t\t}\n",
+                "\t\t})\n",
+                "\t}\n",
+                "}\n",
+                "\n",
+                "func TestSub(t *testing.T) {}\n",
+            )),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(dir.path().join(path), source).unwrap();
+                dir.path().join(path).to_string_lossy().to_string()
+            })
+            .collect();
+        
+        let result = TestTopologyAnalyzer::new().analyze(&paths, &RiskOptions::default());
+        let cases = |path: &str| -> Vec<(String, bool, bool, Option<usize>)> {
+            let file = result.test_files.iter().find(|f| f.path == path).unwrap();
+            let mut cases: Vec<&TestCase> = file.test_cases.iter().collect();
+            cases.sort_by_key(|c| c.line);
+            cases.iter().map(|c| (c.name.clone(), c.is_skipped, c.is_only, c.parameter_sets)).collect()
+        };
+        let case = |name: &str, skipped: bool, only: bool, sets: Option<usize>| (name.to_string(), skipped, only, sets);
+        
+        assert_eq!(cases(&paths[0]), vec![
+            case("adds items", false, false, None),
+            case("removes items", true, false, None),
+            case("totals", false, true, None),
+            case("add(%i, %i)", false, false, Some(2)),
+            case("migrates", true, false, None),
+            case("exports", true, false, None),
+        ]);
+        assert_eq!(cases(&paths[1]), vec![
+            case("test_add", false, false, Some(6)),
+            case("test_sub", true, false, None),
+            case("test_div", true, false, None),
+        ]);
+        assert_eq!(cases(&paths[2]), vec![
+            case("TestAdd", true, false, Some(2)),
+            case("TestSub", false, false, None),
+        ]);
+        assert_eq!(result.skipped_tests, 6);
+        
+        assert_eq!(decorator_name("[Fact(Skip = \"flaky\")]"), "Fact");
+        assert_eq!(decorator_name("#[ignore = \"slow\"]"), "ignore");
+        assert_eq!(decorator_name("#[tokio::test]"), "tokio::test");
+        assert_eq!(decorator_name("@pytest.mark.parametrize(\"a\", [1])"), "pytest.mark.parametrize");
+    }
+    
+    #[test]
+    fn test_mock_targets_and_overlaps() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for sub in ["src", "app/services", "tests", "Repositories", "Tests"] {
+            std::fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        let files = [
+            ("src/db.ts", "export function save() {}\n"),
+            ("src/db.test.ts", concat!(
+                "import { vi, it } from 'vitest';\n",
+                "import { save } from './db';\n",
+                "vi.mock('./db');\n",
+                "it('saves', () => { save(); });\n",
+            )),
+            ("app/services/email.py", "def send():\n    pass\n"),
+            ("tests/test_notify.py", concat!(
+                "import pytest\n",
+                "from unittest.mock import patch\n",
+                "\n",
+                "@patch('app.services.email.send')\n",
+                "def test_notify(send):\n",
+                "    pass\n",
+            )),
+            ("Repositories/IUserRepository.cs", "public interface IUserRepository {}\n"),
+            ("Tests/UserServiceTests.cs", concat!(
+                "using Xunit;\n",
+                "using Moq;\n",
+                "\n",
+                "public class UserServiceTests {\n",
+                "    [Fact]\n",
+                "    public void Saves() {\n",
+                "        var repo = new Mock<IUserRepository>();\n",
+                "    }\n",
+                "}\n",
+            )),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+        
+        let result = TestTopologyAnalyzer::new().analyze(&paths, &RiskOptions::default());
+        let mock = |path: &str| -> (String, MockType, Option<String>) {
+            let file = result.test_files.iter().find(|f| f.path == path).unwrap();
+            let mock = &file.mocks[0];
+            (mock.target.clone(), mock.mock_type, mock.resolved_file.clone())
+        };
+        assert_eq!(mock(&paths[1]), ("./db".to_string(), MockType::Module, Some(paths[0].clone())));
+        assert_eq!(mock(&paths[3]), ("app.services.email.send".to_string(), MockType::Function, Some(paths[2].clone())));
+        assert_eq!(mock(&paths[5]), ("IUserRepository".to_string(), MockType::Class, Some(paths[4].clone())));
+        
+        // db.test.ts covers db.ts by convention, and mocks it away
+        let overlaps: Vec<(&str, &str, u32)> = result.mock_overlaps.iter()
+            .map(|o| (o.test_file.as_str(), o.source_file.as_str(), o.line))
+            .collect();
+        assert_eq!(overlaps, vec![(paths[1].as_str(), paths[0].as_str(), 2)]);
+    }
+    
+    #[test]
+    fn test_load_coverage() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let files = [
+            ("src/cart.ts", "export function total() {}\n"),
+            ("src/cart.test.ts", "import { it } from 'vitest';\nit('totals', () => {});\n"),
+            ("src/tax.ts", "export function vat() {}\n"),
+            ("src/legacy.ts", "export function old() {}\n"),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+        let report = root.join("lcov.info");
+        std::fs::write(&report, concat!(
+            "SF:/ci/src/cart.ts\nDA:1,1\nDA:2,0\nDA:3,0\nDA:4,0\nend_of_record\n",
+            "SF:/ci/src/tax.ts\nDA:1,4\nend_of_record\n",
+            "SF:/ci/src/legacy.ts\nDA:1,0\nend_of_record\n",
+            "SF:/ci/vendor/lib.ts\nDA:1,1\nend_of_record\n",
+        )).unwrap();
+        
+        let prefixes = vec![CoveragePathPrefix { from: "/ci/".to_string(), to: format!("{}/", root.display()) }];
+        let mut analyzer = TestTopologyAnalyzer::new().with_coverage_path_prefixes(prefixes);
+        let loaded = analyzer.load_coverage(&paths, &[report.to_string_lossy().to_string()]).unwrap();
+        assert_eq!(loaded.matched_files, 3);
+        assert_eq!(loaded.unmatched_paths, vec!["/ci/vendor/lib.ts"]);
+        
+        let result = analyzer.analyze(&paths, &RiskOptions::default());
+        let coverage: Vec<(&str, Option<f32>, MappingMethod)> = result.coverage.iter()
+            .map(|c| (c.source_file.as_str(), c.coverage_percent, c.mapping_method))
+            .collect();
+        assert_eq!(coverage, vec![
+            (paths[0].as_str(), Some(25.0), MappingMethod::Convention),
+            (paths[3].as_str(), Some(0.0), MappingMethod::Convention),
+            (paths[2].as_str(), Some(100.0), MappingMethod::Coverage),
+        ]);
+        let cart = result.coverage.iter().find(|c| c.source_file == paths[0]).unwrap();
+        assert_eq!(cart.uncovered_line_ranges, vec![LineRange { start: 2, end: 4 }]);
+        assert_eq!(result.uncovered_files, vec![paths[3].clone()]);
+        
+        assert!(analyzer.load_coverage(&paths, &[paths[0].clone()]).is_err());
+    }
+    
+    #[test]
+    fn test_call_graph_mapping() {
+        use crate::call_graph::{BuilderConfig, StreamingBuilder};
+        
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("test")).unwrap();
+        let files = [
+            ("src/cart.ts", "export function addItem(item) {}\nexport function total() { return 1; }\n"),
+            ("src/tax.ts", "export function vat(amount) { return amount * 2; }\n"),
+            ("src/unused.ts", "export function nothing() {}\n"),
+            ("test/checkout.test.ts", concat!(
+                "import { describe, it, expect } from 'vitest';\n",
+                "import { addItem, total } from '../src/cart';\n",
+                "import { vat } from '../src/tax';\n",
+                "\n",
+                "describe('checkout', () => {\n",
+                "  it('adds items', () => {\n",
+                "    addItem('a');\n",
+                "    expect(total()).toBe(1);\n",
+                "  });\n",
+                "  it('applies tax', () => {\n",
+                "    expect(vat(total())).toBe(2);\n",
+                "  });\n",
+                "});\n",
+            )),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+        let builder = StreamingBuilder::new(BuilderConfig {
+            root_dir: root.to_path_buf(),
+            ..Default::default()
+        });
+        builder.build_sqlite(&["**/*.ts"]);
+        let db = CallGraphDb::open_readonly(&root.join(".drift/lake/callgraph/callgraph.db")).unwrap();
+        
+        let mut analyzer = TestTopologyAnalyzer::new();
+        let result = analyzer.analyze_with_call_graph(root, &paths, &db, &RiskOptions::default()).unwrap();
+        let coverage: Vec<(&str, usize, MappingMethod)> = result.coverage.iter()
+            .map(|c| (c.source_file.as_str(), c.test_function_count, c.mapping_method))
+            .collect();
+        assert_eq!(coverage, vec![
+            (paths[0].as_str(), 2, MappingMethod::CallGraph),
+            (paths[1].as_str(), 1, MappingMethod::CallGraph),
+            (paths[2].as_str(), 0, MappingMethod::CallGraph),
+        ]);
+        assert_eq!(result.coverage[0].test_files, vec![paths[3].clone()]);
+        assert_eq!(result.uncovered_files, vec![paths[2].clone()]);
+        
+        // By convention the integration test covers a nonexistent test/checkout.ts
+        let convention = analyzer.analyze(&paths, &RiskOptions::default());
+        assert_eq!(convention.uncovered_files.len(), 3);
+        assert_eq!(convention.coverage[0].mapping_method, MappingMethod::Convention);
+    }    
+    #[test]
+    fn test_risk_factors() {
+        use crate::call_graph::fixtures::{access, accessor, function, resolved_call, sample_db};
+        use crate::call_graph::DataOperation;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let files = ["src/util.ts", "src/format.ts", "src/account.ts", "src/auth.ts", "src/auth.test.ts"];
+        let paths: Vec<String> = files.iter()
+            .map(|path| {
+                let source = match *path {
+                    "src/auth.test.ts" => "import { it } from 'vitest';\nit('logs in', () => {});\n",
+                    _ => "export function f() {}\n",
+                };
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+        
+        // format and auth are called from three handlers; account and auth
+        // reach users.password_hash through repo
+        let mut functions = vec![
+            function("src/util.ts:pad:1", vec![]),
+            function("src/format.ts:formatDate:1", vec![]),
+            function("src/account.ts:load:1", vec![resolved_call("src/repo.ts:findUser:1")]),
+            function("src/auth.ts:login:1", vec![resolved_call("src/repo.ts:findUser:1")]),
+            function("src/auth.test.ts:<anon@2>:2", vec![resolved_call("src/util.ts:pad:1")]),
+            accessor("src/repo.ts:findUser:1", vec![], vec![access("users", DataOperation::Read, &["id", "password_hash"])]),
+        ];
+        for handler in ["a", "b", "c"] {
+            functions.push(function(
+                &format!("src/routes/{}.ts:handle:1", handler),
+                vec![resolved_call("src/format.ts:formatDate:1"), resolved_call("src/auth.ts:login:1")],
+            ));
+        }
+        let db = sample_db(root, functions);
+        
+        let options = RiskOptions { high_fan_in: 3, ..Default::default() };
+        let mut analyzer = TestTopologyAnalyzer::new();
+        let result = analyzer.analyze_with_call_graph(root, &paths, &db, &options).unwrap();
+        let risk = |result: &TestTopologyResult, file: &str| -> (RiskLevel, Vec<String>) {
+            let entry = result.coverage.iter().find(|c| c.source_file == root.join(file).to_string_lossy()).unwrap();
+            (entry.risk_level, entry.risk_factors.clone())
+        };
+        let sensitive = "reaches sensitive data (users.password_hash)".to_string();
+        
+        // Tested leaf, untested leaf, then each call graph factor on top
+        assert_eq!(risk(&result, "src/util.ts"), (RiskLevel::Low, vec![]));
+        assert_eq!(risk(&result, "src/format.ts"), (RiskLevel::High, vec!["untested".to_string(), "fan-in of 3".to_string()]));
+        assert_eq!(risk(&result, "src/account.ts"), (RiskLevel::High, vec!["untested".to_string(), sensitive.clone()]));
+        assert_eq!(risk(&result, "src/auth.ts"), (RiskLevel::Critical, vec!["untested".to_string(), "fan-in of 3".to_string(), sensitive.clone()]));
+        
+        // Fan-in below the threshold scores partially
+        let result = analyzer.analyze_with_call_graph(root, &paths, &db, &RiskOptions::default()).unwrap();
+        assert_eq!(risk(&result, "src/format.ts").0, RiskLevel::Medium);
+        assert_eq!(risk(&result, "src/auth.ts").0, RiskLevel::High);
+        
+        // Line coverage lowers an untested file's risk
+        let report = root.join("lcov.info");
+        std::fs::write(&report, "SF:src/auth.ts\nDA:1,1\nDA:2,1\nDA:3,1\nDA:4,0\nend_of_record\n").unwrap();
+        analyzer.load_coverage(&paths, &[report.to_string_lossy().to_string()]).unwrap();
+        let result = analyzer.analyze_with_call_graph(root, &paths, &db, &options).unwrap();
+        assert_eq!(risk(&result, "src/auth.ts"), (RiskLevel::High, vec!["75% line coverage".to_string(), "fan-in of 3".to_string(), sensitive.clone()]));
+        
+        // Weights are relative: ignoring coverage leaves fan-in and sensitive data
+        let weights = RiskOptions { coverage_weight: 0.0, ..options.clone() };
+        let result = analyzer.analyze(&paths, &weights);
+        assert_eq!(risk(&result, "src/auth.ts"), (RiskLevel::Low, vec![]));
+        let result = analyzer.analyze_with_call_graph(root, &paths, &db, &weights).unwrap();
+        assert_eq!(risk(&result, "src/auth.ts"), (RiskLevel::Critical, vec!["fan-in of 3".to_string(), sensitive]));
+        assert_eq!(risk(&result, "src/format.ts").0, RiskLevel::Medium);
+    }
 }
@@ -0,0 +1,190 @@
+//! Assertions - Assertion calls per test case
+//!
+//! Assertions are the calls each framework asserts with: `expect()`,
+//! `assert*()`, `Assert.*` and `require.*` everywhere, plus `t.Error*` and
+//! `t.Fatal*` (Go), `pytest.raises` and `assert` statements (Python),
+//! `verify()` (Mockito), `.Should()` and `.Verify()` (FluentAssertions, Moq)
+//! and `expectException*` (PHPUnit). A test asserting only through snapshot
+//! matchers is snapshot-only.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::types::*;
+use crate::parsers::{CallSite, ParseResult};
+
+/// Python `assert` statement
+static ASSERT_STATEMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*assert\b").unwrap());
+/// syrupy snapshot comparison: `assert result == snapshot`
+static SNAPSHOT_COMPARISON: Lazy<Regex> = Lazy::new(|| Regex::new(r"==\s*snapshot\b").unwrap());
+
+/// Assertions made on lines `start..=end`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct AssertionCount {
+    pub assertions: usize,
+    /// Assertions through snapshot matchers
+    pub snapshots: usize,
+}
+
+impl AssertionCount {
+    pub fn is_snapshot_only(&self) -> bool {
+        self.snapshots > 0 && self.snapshots >= self.assertions
+    }
+}
+
+pub(crate) fn count_assertions(
+    result: &ParseResult,
+    source: &str,
+    framework: TestFramework,
+    start: u32,
+    end: u32,
+) -> AssertionCount {
+    let mut count = AssertionCount::default();
+    for call in result.calls.iter().filter(|c| start <= c.range.start.line && c.range.start.line <= end) {
+        if is_assertion_call(call, framework) {
+            count.assertions += 1;
+        }
+        if is_snapshot_matcher(call) {
+            count.snapshots += 1;
+        }
+    }
+    if framework == TestFramework::Pytest {
+        for line in source.lines().skip(start as usize).take((end - start) as usize + 1) {
+            if is_assert_statement(line) {
+                count.assertions += 1;
+                if SNAPSHOT_COMPARISON.is_match(line) {
+                    count.snapshots += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+/// A call asserting in `framework`'s style
+pub(crate) fn is_assertion_call(call: &CallSite, framework: TestFramework) -> bool {
+    let callee = call.callee.as_str();
+    let receiver = call.receiver.as_deref();
+    // expect.assertions(n), expect.any(Number)
+    if receiver == Some("expect") {
+        return false;
+    }
+    if callee == "expect" || callee.starts_with("assert") || callee.starts_with("Assert") {
+        return true;
+    }
+    // assert.equal, Assert.That, CollectionAssert.Contains, require.NoError, sinon.assert.called
+    if receiver.is_some_and(|r| r == "assert" || r == "require" || r.ends_with("Assert") || r.ends_with(".assert")) {
+        return true;
+    }
+    match framework {
+        TestFramework::GoTest => {
+            receiver.is_some() && matches!(callee, "Error" | "Errorf" | "Fatal" | "Fatalf" | "Fail" | "FailNow")
+        }
+        TestFramework::Pytest => callee == "raises" && receiver == Some("pytest"),
+        TestFramework::JUnit => callee == "verify",
+        TestFramework::NUnit | TestFramework::XUnit => matches!(callee, "Should" | "Verify"),
+        TestFramework::PHPUnit => callee.starts_with("expectException"),
+        _ => false,
+    }
+}
+
+/// Python `assert` statement line
+pub(crate) fn is_assert_statement(line: &str) -> bool {
+    ASSERT_STATEMENT.is_match(line)
+}
+
+/// `toMatchSnapshot()` and friends (Jest, Vitest), `snapshot.assert_match()`
+/// (snapshottest)
+fn is_snapshot_matcher(call: &CallSite) -> bool {
+    matches!(
+        call.callee.as_str(),
+        "toMatchSnapshot" | "toMatchInlineSnapshot" | "toMatchFileSnapshot"
+            | "toThrowErrorMatchingSnapshot" | "toThrowErrorMatchingInlineSnapshot"
+    ) || (call.callee == "assert_match" && call.receiver.as_deref() == Some("snapshot"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_topology::*;
+
+    #[test]
+    fn test_assertion_density() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            ("math.test.ts", concat!(
+                "import { it, expect } from '@jest/globals';\n",
+                "\n",
+                "it('adds', () => {\n",
+                "  expect(add(1, 2)).toBe(3);\n",
+                "  expect(add(0, 0)).toBe(0);\n",
+                "});\n",
+                "it('renders', () => {\n",
+                "  expect(render()).toMatchSnapshot();\n",
+                "});\n",
+                "it('does nothing', () => {\n",
+                "  add(1, 2);\n",
+                "});\n",
+                "it.skip('later', () => {});\n",
+                "it.each([[1], [2]])('counts %i', (n) => {\n",
+                "  expect.assertions(1);\n",
+                "  expect(n).toBeGreaterThan(0);\n",
+                "});\n",
+            )),
+            ("test_math.py", concat!(
+                "import pytest\n",
+                "\n",
+                "def test_add():\n",
+                "    assert add(1, 2) == 3\n",
+                "    with pytest.raises(ValueError):\n",
+                "        add(None, 1)\n",
+                "\n",
+                "def test_render(snapshot):\n",
+                "    assert render() == snapshot\n",
+                "\n",
+                "def test_nothing():\n",
+                "    add(1, 2)\n",
+            )),
+            ("math_test.go", concat!(
+                "package math\n",
+                "\n",
+                "import \"testing\"\n",
+                "\n",
+                "func TestAdd(t *testing.T) {\n",
+                "\tif Add(1, 2) != 3 {\n",
+                "\t\tt.Errorf(\"want 3\")\n",
+                "\t}\n",
+                "}\n",
+            )),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let result = TestTopologyAnalyzer::new().analyze(&paths, &RiskOptions::default());
+        let file = |path: &str| result.test_files.iter().find(|f| f.path == path).unwrap();
+        let cases = |path: &str| -> Vec<(String, usize, bool)> {
+            file(path).test_cases.iter().map(|c| (c.name.clone(), c.assertion_count, c.is_snapshot_only)).collect()
+        };
+
+        assert_eq!(cases(&paths[0]), vec![
+            ("adds".to_string(), 2, false),
+            ("renders".to_string(), 1, true),
+            ("does nothing".to_string(), 0, false),
+            ("later".to_string(), 0, false),
+            ("counts %i".to_string(), 1, false),
+        ]);
+        // The skipped test doesn't count towards density
+        assert_eq!(file(&paths[0]).assertion_density, 1.0);
+
+        assert_eq!(cases(&paths[1]), vec![
+            ("test_add".to_string(), 2, false),
+            ("test_render".to_string(), 1, true),
+            ("test_nothing".to_string(), 0, false),
+        ]);
+        assert_eq!(cases(&paths[2]), vec![("TestAdd".to_string(), 1, false)]);
+    }
+}
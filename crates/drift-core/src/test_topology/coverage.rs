@@ -0,0 +1,235 @@
+//! Coverage reports - Line coverage from lcov, Cobertura XML and coverage.py JSON
+//!
+//! Each report is read into per-file line hits keyed by the path the report
+//! names. Reports from CI often name absolute build paths; `CoveragePathPrefix`
+//! rewrites and suffix matching join them onto the analyzed files.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::types::*;
+
+static COBERTURA_SOURCE: Lazy<Regex> = Lazy::new(|| Regex::new(r"<source>\s*([^<]*?)\s*</source>").unwrap());
+static COBERTURA_CLASS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?s)<class\b[^>]*\bfilename="([^"]*)"[^>]*>(.*?)</class>"#).unwrap());
+static COBERTURA_LINE: Lazy<Regex> = Lazy::new(|| Regex::new(r"<line\b([^>]*)>").unwrap());
+static XML_ATTRIBUTE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(\w+)="([^"]*)""#).unwrap());
+
+/// Line hits of one file in a coverage report
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineCoverage {
+    /// Executable line (1-based) -> hits
+    pub lines: BTreeMap<u32, u64>,
+}
+
+impl LineCoverage {
+    /// Share of executable lines hit, in percent; None without executable lines
+    pub fn percent(&self) -> Option<f32> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        let covered = self.lines.values().filter(|&&hits| hits > 0).count();
+        Some(covered as f32 * 100.0 / self.lines.len() as f32)
+    }
+
+    /// Runs of unhit lines, not interrupted by a hit line
+    pub fn uncovered_ranges(&self) -> Vec<LineRange> {
+        let mut ranges: Vec<LineRange> = Vec::new();
+        let mut extend = false;
+        for (&line, &hits) in &self.lines {
+            if hits > 0 {
+                extend = false;
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(range) if extend => range.end = line,
+                _ => ranges.push(LineRange { start: line, end: line }),
+            }
+            extend = true;
+        }
+        ranges
+    }
+
+    pub(crate) fn merge(&mut self, other: LineCoverage) {
+        for (line, hits) in other.lines {
+            let entry = self.lines.entry(line).or_default();
+            *entry = (*entry).max(hits);
+        }
+    }
+}
+
+/// Read a coverage report: lcov (`.info`), Cobertura XML or coverage.py JSON
+pub fn parse_coverage_report(path: &Path) -> Result<HashMap<String, LineCoverage>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read coverage report {}: {}", path.display(), e))?;
+    let trimmed = content.trim_start();
+    let files = if trimmed.starts_with('<') {
+        parse_cobertura(&content)
+    } else if trimmed.starts_with('{') {
+        parse_coverage_py(&content)
+    } else if content.lines().any(|l| l.starts_with("SF:")) {
+        Ok(parse_lcov(&content))
+    } else {
+        Err("unknown format, expected lcov, Cobertura XML or coverage.py JSON".to_string())
+    };
+    files.map_err(|e| format!("Failed to parse coverage report {}: {}", path.display(), e))
+}
+
+/// lcov tracefile: `SF:<path>`, then `DA:<line>,<hits>` records until `end_of_record`
+fn parse_lcov(content: &str) -> HashMap<String, LineCoverage> {
+    let mut files: HashMap<String, LineCoverage> = HashMap::new();
+    let mut current: Option<(String, LineCoverage)> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(file) = line.strip_prefix("SF:") {
+            current = Some((file.to_string(), LineCoverage::default()));
+        } else if let Some(record) = line.strip_prefix("DA:") {
+            let mut fields = record.split(',');
+            let line = fields.next().and_then(|l| l.parse().ok());
+            let hits = fields.next().and_then(|h| h.parse().ok());
+            if let (Some((_, coverage)), Some(line), Some(hits)) = (current.as_mut(), line, hits) {
+                coverage.lines.insert(line, hits);
+            }
+        } else if line == "end_of_record" {
+            if let Some((file, coverage)) = current.take() {
+                files.entry(file).or_default().merge(coverage);
+            }
+        }
+    }
+    files
+}
+
+/// Cobertura XML: `<class filename="...">` elements with `<line number=".." hits=".."/>`,
+/// their filenames relative to the first `<source>`
+fn parse_cobertura(content: &str) -> Result<HashMap<String, LineCoverage>, String> {
+    if !content.contains("<coverage") {
+        return Err("no <coverage> element".to_string());
+    }
+    let source = COBERTURA_SOURCE.captures(content).map(|c| unescape_xml(&c[1]));
+    let mut files: HashMap<String, LineCoverage> = HashMap::new();
+    for class in COBERTURA_CLASS.captures_iter(content) {
+        let filename = unescape_xml(&class[1]);
+        let file = match &source {
+            Some(source) if !Path::new(&filename).is_absolute() && !source.is_empty() => {
+                format!("{}/{}", source.trim_end_matches('/'), filename)
+            }
+            _ => filename,
+        };
+        let mut coverage = LineCoverage::default();
+        for line in COBERTURA_LINE.captures_iter(&class[2]) {
+            let attributes: HashMap<&str, &str> = XML_ATTRIBUTE.captures_iter(line.get(1).map_or("", |m| m.as_str()))
+                .filter_map(|a| Some((a.get(1)?.as_str(), a.get(2)?.as_str())))
+                .collect();
+            let number = attributes.get("number").and_then(|n| n.parse().ok());
+            let hits = attributes.get("hits").and_then(|h| h.parse().ok());
+            if let (Some(number), Some(hits)) = (number, hits) {
+                coverage.merge(LineCoverage { lines: BTreeMap::from([(number, hits)]) });
+            }
+        }
+        files.entry(file).or_default().merge(coverage);
+    }
+    Ok(files)
+}
+
+/// coverage.py JSON (`coverage json`): `executed_lines` and `missing_lines` per file
+fn parse_coverage_py(content: &str) -> Result<HashMap<String, LineCoverage>, String> {
+    #[derive(serde::Deserialize)]
+    struct Report {
+        files: HashMap<String, FileReport>,
+    }
+    #[derive(serde::Deserialize)]
+    struct FileReport {
+        #[serde(default)]
+        executed_lines: Vec<u32>,
+        #[serde(default)]
+        missing_lines: Vec<u32>,
+    }
+
+    let report: Report = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    Ok(report.files.into_iter()
+        .map(|(file, report)| {
+            let lines = report.executed_lines.into_iter().map(|l| (l, 1))
+                .chain(report.missing_lines.into_iter().map(|l| (l, 0)))
+                .collect();
+            (file, LineCoverage { lines })
+        })
+        .collect())
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Analyzed file a report path refers to
+///
+/// The first matching prefix is rewritten, then the path is matched exactly,
+/// then by the longest trailing run of path segments only one analyzed file
+/// ends with (`/ci/build/src/db.ts` -> `src/db.ts`).
+pub(crate) fn match_report_path<'a>(
+    report_path: &str,
+    prefixes: &[CoveragePathPrefix],
+    files: &'a [String],
+) -> Option<&'a String> {
+    let report_path = report_path.replace('\\', "/");
+    let path = prefixes.iter()
+        .find_map(|p| report_path.strip_prefix(p.from.as_str()).map(|rest| format!("{}{}", p.to, rest)))
+        .unwrap_or(report_path);
+    if let Some(file) = files.iter().find(|f| **f == path) {
+        return Some(file);
+    }
+
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+    for start in 0..segments.len() {
+        let suffix = segments[start..].join("/");
+        let mut matching = files.iter().filter(|f| *f == &suffix || f.ends_with(&format!("/{}", suffix)));
+        match (matching.next(), matching.next()) {
+            (Some(file), None) => return Some(file),
+            (Some(_), Some(_)) => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coverage_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let lcov = dir.path().join("lcov.info");
+        std::fs::write(&lcov, "TN:\nSF:/ci/build/src/db.ts\nDA:1,3\nDA:2,0\nDA:3,0\nDA:5,0\nDA:6,1\nDA:7,0\nend_of_record\n").unwrap();
+        let cobertura = dir.path().join("coverage.xml");
+        std::fs::write(&cobertura, concat!(
+            "<?xml version=\"1.0\" ?>\n<coverage line-rate=\"0.5\">\n",
+            "<sources><source>/ci/build</source></sources>\n<packages><package name=\"app\"><classes>\n",
+            "<class name=\"Db\" filename=\"app/db.py\" line-rate=\"0.5\"><methods/><lines>\n",
+            "<line number=\"1\" hits=\"1\"/>\n<line hits=\"0\" number=\"2\"/>\n",
+            "</lines></class>\n</classes></package></packages>\n</coverage>\n",
+        )).unwrap();
+        let coverage_py = dir.path().join("coverage.json");
+        std::fs::write(&coverage_py, r#"{"meta": {}, "files": {"app/db.py": {"executed_lines": [1, 2], "missing_lines": [4], "summary": {}}}}"#).unwrap();
+
+        let lcov = parse_coverage_report(&lcov).unwrap();
+        let db = &lcov["/ci/build/src/db.ts"];
+        assert_eq!(db.percent(), Some(2.0 * 100.0 / 6.0));
+        assert_eq!(db.uncovered_ranges(), vec![LineRange { start: 2, end: 5 }, LineRange { start: 7, end: 7 }]);
+
+        let cobertura = parse_coverage_report(&cobertura).unwrap();
+        assert_eq!(cobertura["/ci/build/app/db.py"].lines, BTreeMap::from([(1, 1), (2, 0)]));
+
+        let coverage_py = parse_coverage_report(&coverage_py).unwrap();
+        assert_eq!(coverage_py["app/db.py"].lines, BTreeMap::from([(1, 1), (2, 1), (4, 0)]));
+
+        let files = vec!["/home/me/repo/src/db.ts".to_string(), "/home/me/repo/lib/db.ts".to_string()];
+        assert_eq!(match_report_path("/ci/build/src/db.ts", &[], &files), Some(&files[0]));
+        assert_eq!(match_report_path("db.ts", &[], &files), None);
+        let prefixes = [CoveragePathPrefix { from: "/ci/build/".to_string(), to: "/home/me/repo/".to_string() }];
+        assert_eq!(match_report_path("/ci/build/lib/db.ts", &prefixes, &files), Some(&files[1]));
+    }
+}
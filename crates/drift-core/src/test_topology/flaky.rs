@@ -0,0 +1,256 @@
+//! Flaky signals - Structural signs of flaky tests
+//!
+//! Retries, sleeps, clock reads in assertions and network calls come from
+//! call sites and decorators. Regex covers what isn't a call: test options
+//! (`{ retry: 3 }`), assert statements, and the clock reads on an
+//! assertion's line. Clock-dependent signals are dropped in files that fake
+//! the clock, network calls in files mocking an HTTP client.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeSet, HashSet};
+
+use super::analyzer::decorator_name;
+use super::assertions::{is_assert_statement, is_assertion_call};
+use super::types::*;
+use crate::parsers::{CallSite, ParseResult};
+
+/// Reads of the current time
+static CLOCK_READ: Lazy<Regex> = Lazy::new(|| Regex::new(concat!(
+    r"\b(?:Date\.now\s*\(|new\s+Date\s*\(\s*\)|performance\.now\s*\(",
+    r"|DateTime(?:Offset)?\.(?:Now|UtcNow|Today)\b|datetime\.(?:now|utcnow|today)\s*\(|date\.today\s*\(",
+    r"|time\.(?:time|monotonic|Now)\s*\(|System\.(?:currentTimeMillis|nanoTime)\s*\(",
+    r"|(?:Instant|LocalDate|LocalDateTime|ZonedDateTime)\.now\s*\(|(?:Instant|SystemTime)::now\s*\()",
+)).unwrap());
+/// Retry option of a Vitest or Playwright test: `it('x', { retry: 3 }, ...)`
+static RETRY_OPTION: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bretr(?:y|ies)\s*:\s*[1-9]").unwrap());
+/// HTTP clients, on the line of a mock
+static HTTP_CLIENT: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"\b(?:fetch|axios|got|superagent|requests|httpx|urllib\d?|aiohttp|HttpClient|HttpMessageHandler|RestTemplate|WebClient|http)\b"
+).unwrap());
+
+/// Imported modules that mock HTTP, matched against import path segments
+const HTTP_MOCK_LIBRARIES: &[&str] = &[
+    "nock", "msw", "fetch-mock", "jest-fetch-mock", "axios-mock-adapter",
+    "responses", "requests_mock", "httpretty", "respx", "pytest_httpx", "aioresponses",
+    "wiremock", "mockwebserver", "MockHttp", "httptest", "httpmock",
+];
+/// Imported modules that fake the clock
+const FAKE_CLOCK_LIBRARIES: &[&str] = &["freezegun", "time_machine", "fake-timers", "timecop"];
+
+/// Flaky signals of a test file, leaving out the `suppressed` kinds
+pub(crate) fn detect_flaky_signals(
+    result: &ParseResult,
+    source: &str,
+    framework: TestFramework,
+    mocks: &[MockUsage],
+    suppressed: &HashSet<FlakySignalKind>,
+) -> Vec<FlakySignal> {
+    let lines: Vec<&str> = source.lines().collect();
+    let imports = |libraries: &[&str]| result.imports.iter()
+        .any(|i| i.source.split(['/', '.', ':', '\\']).any(|segment| libraries.contains(&segment)));
+    let fake_clock = imports(FAKE_CLOCK_LIBRARIES)
+        || result.calls.iter().any(|c| matches!(c.callee.as_str(), "useFakeTimers" | "setSystemTime" | "freeze_time" | "travel"));
+    let mocks_http = imports(HTTP_MOCK_LIBRARIES)
+        || mocks.iter().any(|m| lines.get(m.line as usize).is_some_and(|line| HTTP_CLIENT.is_match(line)));
+
+    let mut found: BTreeSet<(u32, FlakySignalKind)> = BTreeSet::new();
+    for call in &result.calls {
+        let line = call.range.start.line;
+        let text = lines.get(line as usize).copied().unwrap_or_default();
+        if is_retry_call(call) || (is_test_call(call) && RETRY_OPTION.is_match(text)) {
+            found.insert((line, FlakySignalKind::Retry));
+        }
+        if is_sleep_call(call) && !(fake_clock && call.callee == "setTimeout") {
+            found.insert((line, FlakySignalKind::Sleep));
+        }
+        if is_assertion_call(call, framework) && !fake_clock && CLOCK_READ.is_match(text) {
+            found.insert((line, FlakySignalKind::TimeAssertion));
+        }
+        if is_network_call(call) && !mocks_http {
+            found.insert((line, FlakySignalKind::UnmockedNetwork));
+        }
+    }
+    if !fake_clock {
+        for (line, text) in lines.iter().enumerate() {
+            if is_assert_statement(text) && CLOCK_READ.is_match(text) {
+                found.insert((line as u32, FlakySignalKind::TimeAssertion));
+            }
+        }
+    }
+
+    // @flaky, @pytest.mark.flaky(reruns=3), [Retry(3)], @RetryingTest(3)
+    let mut signals: Vec<FlakySignal> = Vec::new();
+    for func in &result.functions {
+        for decorator in &func.decorators {
+            let name = decorator_name(decorator);
+            let name = name.rsplit('.').next().unwrap_or(name);
+            if matches!(name, "flaky" | "Retry" | "RetryingTest" | "RetryFact" | "RetryTheory") {
+                signals.push(FlakySignal {
+                    kind: FlakySignalKind::Retry,
+                    line: func.range.start.line,
+                    snippet: snippet(decorator),
+                });
+            }
+        }
+    }
+
+    signals.extend(found.into_iter().map(|(line, kind)| FlakySignal {
+        kind,
+        line,
+        snippet: snippet(lines.get(line as usize).copied().unwrap_or_default()),
+    }));
+    signals.retain(|s| !suppressed.contains(&s.kind));
+    signals.sort_by_key(|s| s.line);
+    signals
+}
+
+/// `jest.retryTimes(3)`, `this.retries(3)` (Mocha), `retry(3)`
+fn is_retry_call(call: &CallSite) -> bool {
+    matches!(
+        (call.receiver.as_deref(), call.callee.as_str()),
+        (Some("jest"), "retryTimes") | (Some("this"), "retries") | (None, "retry")
+    )
+}
+
+fn is_test_call(call: &CallSite) -> bool {
+    matches!(call.receiver.as_deref(), None | Some("it" | "test" | "describe"))
+        && matches!(call.callee.as_str(), "it" | "test" | "describe" | "only" | "concurrent")
+}
+
+/// `sleep()`, `time.sleep`, `Thread.sleep`, `time.Sleep`, `Task.Delay`,
+/// `setTimeout`, `page.waitForTimeout`
+fn is_sleep_call(call: &CallSite) -> bool {
+    matches!(
+        (call.receiver.as_deref(), call.callee.as_str()),
+        (_, "sleep" | "usleep")
+            | (Some("time" | "Thread"), "Sleep")
+            | (Some("Task"), "Delay")
+            | (None | Some("window" | "global" | "globalThis"), "setTimeout")
+            | (Some(_), "waitForTimeout")
+    )
+}
+
+/// `fetch()`, `axios.get()`, `requests.post()`, `http.Get()`, `urlopen()`,
+/// `client.GetAsync()`, `restTemplate.getForObject()`
+fn is_network_call(call: &CallSite) -> bool {
+    let callee = call.callee.as_str();
+    match call.receiver.as_deref() {
+        None | Some("window" | "global" | "globalThis") => matches!(callee, "fetch" | "urlopen"),
+        Some("axios" | "requests" | "httpx" | "got" | "superagent" | "http" | "https") => matches!(
+            callee.to_lowercase().as_str(),
+            "get" | "post" | "put" | "patch" | "delete" | "head" | "options" | "request"
+        ),
+        Some(receiver) if receiver.ends_with("request") => callee == "urlopen",
+        Some(_) => matches!(
+            callee,
+            "GetAsync" | "PostAsync" | "PutAsync" | "DeleteAsync" | "SendAsync" | "GetStringAsync"
+                | "getForObject" | "getForEntity" | "postForObject" | "postForEntity"
+        ),
+    }
+}
+
+/// A line trimmed, and cut to 120 characters
+fn snippet(line: &str) -> String {
+    let line = line.trim();
+    match line.char_indices().nth(120) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_topology::*;
+
+    #[test]
+    fn test_flaky_signals() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            ("api.test.ts", concat!(
+                "import { describe, it, expect } from '@jest/globals';\n",
+                "import axios from 'axios';\n",
+                "\n",
+                "jest.retryTimes(3);\n",
+                "\n",
+                "describe('api', () => {\n",
+                "  it('fetches users', async () => {\n",
+                "    const res = await axios.get('https://example.com/users');\n",
+                "    await new Promise((r) => setTimeout(r, 500));\n",
+                "    expect(res.headers.date).toBe(new Date().toString());\n",
+                "  });\n",
+                "  it('is fast', { retry: 2 }, () => {\n",
+                "    expect(Date.now()).toBeGreaterThan(0);\n",
+                "  });\n",
+                "});\n",
+            )),
+            ("test_sync.py", concat!(
+                "import time\n",
+                "import pytest\n",
+                "import requests\n",
+                "import responses\n",
+                "\n",
+                "@pytest.mark.flaky(reruns=3)\n",
+                "def test_sync():\n",
+                "    time.sleep(0.1)\n",
+                "    assert time.time() > 0\n",
+                "    requests.get('https://example.com')\n",
+            )),
+            ("clock.test.ts", concat!(
+                "import { it, expect, vi } from 'vitest';\n",
+                "\n",
+                "vi.useFakeTimers();\n",
+                "it('ticks', () => {\n",
+                "  setTimeout(() => {}, 100);\n",
+                "  expect(Date.now()).toBe(0);\n",
+                "});\n",
+            )),
+        ];
+        let paths: Vec<String> = files.iter()
+            .map(|(path, source)| {
+                std::fs::write(root.join(path), source).unwrap();
+                root.join(path).to_string_lossy().to_string()
+            })
+            .collect();
+
+        let signals = |result: &TestTopologyResult, path: &str| -> Vec<(u32, FlakySignalKind)> {
+            let file = result.test_files.iter().find(|f| f.path == path).unwrap();
+            file.flaky_signals.iter().map(|s| (s.line, s.kind)).collect()
+        };
+        let result = TestTopologyAnalyzer::new().analyze(&paths, &RiskOptions::default());
+        assert_eq!(signals(&result, &paths[0]), vec![
+            (3, FlakySignalKind::Retry),
+            (7, FlakySignalKind::UnmockedNetwork),
+            (8, FlakySignalKind::Sleep),
+            (9, FlakySignalKind::TimeAssertion),
+            (11, FlakySignalKind::Retry),
+            (12, FlakySignalKind::TimeAssertion),
+        ]);
+        let file = result.test_files.iter().find(|f| f.path == paths[0]).unwrap();
+        assert_eq!(file.flaky_signals[1].snippet, "const res = await axios.get('https://example.com/users');");
+
+        // The responses import mocks requests
+        let python = result.test_files.iter().find(|f| f.path == paths[1]).unwrap();
+        let python: Vec<(FlakySignalKind, &str)> = python.flaky_signals.iter().map(|s| (s.kind, s.snippet.as_str())).collect();
+        assert_eq!(python, vec![
+            (FlakySignalKind::Retry, "@pytest.mark.flaky(reruns=3)"),
+            (FlakySignalKind::Sleep, "time.sleep(0.1)"),
+            (FlakySignalKind::TimeAssertion, "assert time.time() > 0"),
+        ]);
+
+        // Fake timers make setTimeout and Date.now() deterministic
+        assert!(signals(&result, &paths[2]).is_empty());
+        assert_eq!(result.flaky_signal_count, 9);
+
+        let result = TestTopologyAnalyzer::new()
+            .with_suppressed_flaky_signals(vec![FlakySignalKind::Retry, FlakySignalKind::UnmockedNetwork])
+            .analyze(&paths, &RiskOptions::default());
+        assert_eq!(signals(&result, &paths[0]), vec![
+            (8, FlakySignalKind::Sleep),
+            (9, FlakySignalKind::TimeAssertion),
+            (12, FlakySignalKind::TimeAssertion),
+        ]);
+        assert_eq!(result.flaky_signal_count, 5);
+    }
+}
@@ -5,6 +5,11 @@
 
 mod types;
 mod analyzer;
+mod assertions;
+mod coverage;
+mod flaky;
+mod risk;
 
 pub use types::*;
-pub use analyzer::TestTopologyAnalyzer;
+pub use analyzer::{is_test_path, TestTopologyAnalyzer};
+pub use coverage::{parse_coverage_report, LineCoverage};
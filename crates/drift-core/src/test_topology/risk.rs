@@ -0,0 +1,120 @@
+//! Risk scoring - Risk level of source files from weighted factors
+//!
+//! Coverage comes from the topology itself. Fan-in and reachable sensitive
+//! data come from the call graph, when analyzing through one: fan-in counts
+//! the functions in other non-test files calling into a file, and sensitive
+//! data is any sensitive field accessed by a function the file's functions
+//! reach through resolved calls.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use super::analyzer::is_test_path;
+use super::types::*;
+use crate::call_graph::{CallGraphDb, FunctionEntry};
+use crate::reachability::{SensitivityType, SqliteReachabilityEngine};
+
+const CRITICAL_SCORE: f32 = 0.85;
+const HIGH_SCORE: f32 = 0.65;
+const MEDIUM_SCORE: f32 = 0.45;
+
+/// Call graph signals of one source file
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RiskSignals {
+    /// Functions in other non-test files calling into the file
+    pub fan_in: usize,
+    /// Sensitive fields (`table.field`) the file's functions reach
+    pub sensitive_data: BTreeSet<String>,
+}
+
+/// Risk signals keyed by call graph file
+pub(crate) fn call_graph_signals<'a>(
+    db: &CallGraphDb,
+    functions: &'a [FunctionEntry],
+) -> Result<HashMap<&'a str, RiskSignals>, String> {
+    let mut signals: HashMap<&str, RiskSignals> = HashMap::new();
+    let mut callers: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut reverse: HashMap<&str, Vec<&str>> = HashMap::new();
+    for function in functions {
+        let file = CallGraphDb::extract_file_from_id(&function.id);
+        for target in function.calls.iter().filter_map(|c| c.resolved_id.as_deref()) {
+            reverse.entry(target).or_default().push(&function.id);
+            let target_file = CallGraphDb::extract_file_from_id(target);
+            if target_file != file && !is_test_path(file) {
+                callers.entry(target_file).or_default().insert(&function.id);
+            }
+        }
+    }
+    for (file, callers) in callers {
+        signals.entry(file).or_default().fan_in = callers.len();
+    }
+
+    // Walk back from each sensitive accessor to every function reaching it
+    let accessors: HashSet<String> = db.get_data_accessors()
+        .map_err(|e| format!("Failed to read call graph: {}", e))?
+        .into_iter()
+        .collect();
+    for function in functions.iter().filter(|f| accessors.contains(&f.id)) {
+        let access = db.get_data_access(&function.id)
+            .map_err(|e| format!("Failed to read call graph: {}", e))?;
+        let fields: Vec<String> = access.iter()
+            .flat_map(|access| access.fields.iter()
+                .filter(|f| SqliteReachabilityEngine::classify_sensitivity(f) != SensitivityType::Unknown)
+                .map(move |f| format!("{}.{}", access.table, f)))
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let mut visited: HashSet<&str> = HashSet::from([function.id.as_str()]);
+        let mut queue: VecDeque<&str> = VecDeque::from([function.id.as_str()]);
+        while let Some(id) = queue.pop_front() {
+            signals.entry(CallGraphDb::extract_file_from_id(id)).or_default()
+                .sensitive_data.extend(fields.iter().cloned());
+            for &caller in reverse.get(id).into_iter().flatten() {
+                if visited.insert(caller) {
+                    queue.push_back(caller);
+                }
+            }
+        }
+    }
+    Ok(signals)
+}
+
+/// Set the risk level and contributing factors of every coverage entry
+pub(crate) fn assess_risk(coverage: &mut [TestCoverage], signals: &HashMap<String, RiskSignals>, options: &RiskOptions) {
+    let total_weight = options.coverage_weight + options.fan_in_weight + options.sensitive_data_weight;
+    let no_signals = RiskSignals::default();
+    for entry in coverage {
+        let signals = signals.get(&entry.source_file).unwrap_or(&no_signals);
+        let mut factors = Vec::new();
+        let mut score = 0.0;
+
+        let tested = !entry.test_files.is_empty();
+        let (gap, factor) = match entry.coverage_percent {
+            Some(percent) if percent > 0.0 || tested => (1.0 - percent / 100.0, format!("{:.0}% line coverage", percent)),
+            None if tested => (0.0, String::new()),
+            _ => (1.0, "untested".to_string()),
+        };
+        if gap > 0.0 && options.coverage_weight > 0.0 {
+            score += options.coverage_weight * gap;
+            factors.push(factor);
+        }
+        if signals.fan_in > 0 && options.fan_in_weight > 0.0 {
+            score += options.fan_in_weight * (signals.fan_in as f32 / options.high_fan_in.max(1) as f32).min(1.0);
+            factors.push(format!("fan-in of {}", signals.fan_in));
+        }
+        if !signals.sensitive_data.is_empty() && options.sensitive_data_weight > 0.0 {
+            score += options.sensitive_data_weight;
+            let fields: Vec<&str> = signals.sensitive_data.iter().map(String::as_str).collect();
+            factors.push(format!("reaches sensitive data ({})", fields.join(", ")));
+        }
+
+        let score = if total_weight > 0.0 { score / total_weight } else { 0.0 };
+        entry.risk_level = match score {
+            s if s >= CRITICAL_SCORE => RiskLevel::Critical,
+            s if s >= HIGH_SCORE => RiskLevel::High,
+            s if s >= MEDIUM_SCORE => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        };
+        entry.risk_factors = factors;
+    }
+}
@@ -15,6 +15,12 @@ pub struct TestFile {
     pub test_cases: Vec<TestCase>,
     /// Mocks used in this file
     pub mocks: Vec<MockUsage>,
+    /// Structural signs of flakiness
+    #[serde(default)]
+    pub flaky_signals: Vec<FlakySignal>,
+    /// Mean assertions per test case that isn't skipped
+    #[serde(default)]
+    pub assertion_density: f32,
 }
 
 /// Test framework
@@ -47,6 +53,20 @@ pub struct TestCase {
     pub line: u32,
     /// Is skipped
     pub is_skipped: bool,
+    /// Focused with an `only` marker (`it.only`, `fit`, `describe.only`)
+    #[serde(default)]
+    pub is_only: bool,
+    /// Parameter sets of a parametrized test (`test.each`,
+    /// `@pytest.mark.parametrize`, `[InlineData]`, Go test tables), when
+    /// they are listed literally
+    #[serde(default)]
+    pub parameter_sets: Option<usize>,
+    /// Assertion calls (and Python `assert` statements) in the test
+    #[serde(default)]
+    pub assertion_count: usize,
+    /// Asserts only through snapshot matchers (`toMatchSnapshot`)
+    #[serde(default)]
+    pub is_snapshot_only: bool,
 }
 
 /// Test type
@@ -68,6 +88,70 @@ pub struct MockUsage {
     pub mock_type: MockType,
     /// Line number
     pub line: u32,
+    /// File the mocked module or class resolves to
+    #[serde(default)]
+    pub resolved_file: Option<String>,
+}
+
+/// A structural sign of a flaky test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakySignal {
+    pub kind: FlakySignalKind,
+    /// Line number
+    pub line: u32,
+    /// The line, trimmed
+    pub snippet: String,
+}
+
+/// Kind of flaky test signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlakySignalKind {
+    /// Retried on failure: `jest.retryTimes(3)`, `@flaky`, `[Retry(3)]`
+    Retry,
+    /// Waits on the clock: `sleep()`, `time.sleep`, `Thread.sleep`
+    Sleep,
+    /// Asserts on the current time: `Date.now()`, `DateTime.Now`
+    TimeAssertion,
+    /// Calls the network in a file mocking no HTTP client
+    UnmockedNetwork,
+}
+
+impl FlakySignalKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlakySignalKind::Retry => "retry",
+            FlakySignalKind::Sleep => "sleep",
+            FlakySignalKind::TimeAssertion => "time_assertion",
+            FlakySignalKind::UnmockedNetwork => "unmocked_network",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Result<Self, String> {
+        match kind.trim().to_lowercase().as_str() {
+            "retry" => Ok(FlakySignalKind::Retry),
+            "sleep" => Ok(FlakySignalKind::Sleep),
+            "time_assertion" => Ok(FlakySignalKind::TimeAssertion),
+            "unmocked_network" => Ok(FlakySignalKind::UnmockedNetwork),
+            other => Err(format!(
+                "Unknown flaky signal '{}' (expected retry, sleep, time_assertion or unmocked_network)",
+                other
+            )),
+        }
+    }
+}
+
+/// A test mocking the source file it is mapped to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockOverlap {
+    /// Test file
+    pub test_file: String,
+    /// Source file the test covers and mocks
+    pub source_file: String,
+    /// Mock target as written
+    pub target: String,
+    /// Line of the mock
+    pub line: u32,
 }
 
 /// Mock type
@@ -89,10 +173,60 @@ pub struct TestCoverage {
     pub source_file: String,
     /// Test files that cover this source
     pub test_files: Vec<String>,
-    /// Coverage percentage (if known)
+    /// Line coverage percentage, from loaded coverage reports (if known)
     pub coverage_percent: Option<f32>,
-    /// Risk level for uncovered code
+    /// Lines the coverage reports saw no test execute
+    #[serde(default)]
+    pub uncovered_line_ranges: Vec<LineRange>,
+    /// Risk level from the weighted risk factors
     pub risk_level: RiskLevel,
+    /// Factors raising the risk level ("untested", "fan-in of 12 callers")
+    #[serde(default)]
+    pub risk_factors: Vec<String>,
+    /// How the test files were mapped to the source
+    #[serde(default)]
+    pub mapping_method: MappingMethod,
+    /// Distinct test functions touching the source
+    #[serde(default)]
+    pub test_function_count: usize,
+}
+
+/// How tests are mapped to source files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingMethod {
+    /// Test file naming convention (`foo.test.ts` -> `foo.ts`)
+    #[default]
+    Convention,
+    /// Calls from test functions resolved in the call graph
+    CallGraph,
+    /// No test maps to the source, but coverage reports show it executed
+    Coverage,
+}
+
+/// Lines `start..=end` (1-based, as in coverage reports)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Rewrite of coverage report paths starting with `from` to start with
+/// `to` instead (`/home/runner/work/app/` -> ``), for reports produced
+/// where the repository lives elsewhere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoveragePathPrefix {
+    pub from: String,
+    pub to: String,
+}
+
+/// Outcome of loading coverage reports
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoverageLoadResult {
+    /// Analyzed files coverage was found for
+    pub matched_files: usize,
+    /// Report paths no analyzed file matched
+    pub unmatched_paths: Vec<String>,
 }
 
 /// Risk level for uncovered code
@@ -105,12 +239,41 @@ pub enum RiskLevel {
     Critical,
 }
 
+/// Weights of the risk factors, relative to each other
+///
+/// Each factor scores 0 to 1: the share of lines left uncovered (all of
+/// them for untested files), fan-in up to `high_fan_in` callers, and
+/// reaching sensitive data. The weighted mean sets the risk level: Critical
+/// from 0.85, High from 0.65, Medium from 0.45. With the default weights an
+/// untested leaf stays Low; untested, widely called and reaching sensitive
+/// data is Critical. Fan-in and sensitive data need the call graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskOptions {
+    pub coverage_weight: f32,
+    pub fan_in_weight: f32,
+    pub sensitive_data_weight: f32,
+    /// Callers from other source files at which fan-in scores fully
+    pub high_fan_in: usize,
+}
+
+impl Default for RiskOptions {
+    fn default() -> Self {
+        Self {
+            coverage_weight: 0.4,
+            fan_in_weight: 0.3,
+            sensitive_data_weight: 0.3,
+            high_fan_in: 10,
+        }
+    }
+}
+
 /// Test topology analysis result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestTopologyResult {
     /// Test files found
     pub test_files: Vec<TestFile>,
-    /// Coverage mappings
+    /// Coverage mappings, one per source file - untested ones with no test files
     pub coverage: Vec<TestCoverage>,
     /// Uncovered source files
     pub uncovered_files: Vec<String>,
@@ -118,6 +281,12 @@ pub struct TestTopologyResult {
     pub total_tests: usize,
     /// Skipped test count
     pub skipped_tests: usize,
+    /// Tests mocking the source files they cover
+    #[serde(default)]
+    pub mock_overlaps: Vec<MockOverlap>,
+    /// Flaky signals across test files
+    #[serde(default)]
+    pub flaky_signal_count: usize,
     /// Files analyzed
     pub files_analyzed: usize,
     /// Duration in milliseconds
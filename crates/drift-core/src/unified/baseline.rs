@@ -0,0 +1,176 @@
+//! Baselines - Gate on new violations only
+//!
+//! `generate_baseline` records the fingerprint of every violation a result
+//! reports; analyses given that baseline mark the violations it knows as
+//! `baselined` and list the recorded ones that are gone, so the baseline
+//! can shrink as they are fixed.
+//!
+//! A fingerprint hashes the file, rule and whitespace-normalized matched
+//! code, then counts identical matches in file order: a third copy of a
+//! baselined call is new even though its hash is known. SARIF partial
+//! fingerprints use the same scheme.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::types::{Baseline, BaselineEntry, BaselineReport, FilePatterns, UnifiedResult};
+
+/// Version of the baseline file format and fingerprint scheme
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Line-independent fingerprints, numbering identical matches
+#[derive(Default)]
+pub(super) struct Fingerprinter {
+    occurrences: HashMap<(String, String, String), u32>,
+}
+
+impl Fingerprinter {
+    /// Fingerprint of the next match of `rule` in `file`; matches must be
+    /// fingerprinted in file order
+    pub fn fingerprint(&mut self, rule: &str, file: &str, snippet: &str) -> String {
+        let file = file.replace('\\', "/");
+        // Whitespace-insensitive, so reformatting keeps the fingerprint
+        let code = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+        let hash = xxh3_64(format!("{}\0{}\0{}", rule, file, code).as_bytes());
+        let occurrence = self.occurrences.entry((rule.to_string(), file, code)).or_default();
+        *occurrence += 1;
+        format!("{:016x}:{}", hash, occurrence)
+    }
+}
+
+/// A baseline of every violation `result` reports
+pub fn generate_baseline(result: &UnifiedResult) -> Baseline {
+    let mut fingerprinter = Fingerprinter::default();
+    let mut violations = Vec::new();
+    for file in &result.file_patterns {
+        for i in in_file_order(file) {
+            let violation = &file.violations[i];
+            violations.push(BaselineEntry {
+                fingerprint: fingerprinter.fingerprint(&violation.rule_id, &violation.file, &violation.actual),
+                rule_id: violation.rule_id.clone(),
+                file: violation.file.clone(),
+                line: violation.line,
+            });
+        }
+    }
+    Baseline { version: BASELINE_VERSION, violations }
+}
+
+impl Baseline {
+    /// Read a baseline `save` wrote
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read baseline {}: {}", path.display(), e))?;
+        let baseline: Baseline = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse baseline {}: {}", path.display(), e))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "Baseline {} has version {}, expected {}; regenerate it",
+                path.display(), baseline.version, BASELINE_VERSION,
+            ));
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline as indented JSON, for readable diffs
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(path, json)
+            .map_err(|e| format!("Failed to write baseline: {}", e))
+    }
+
+    /// Mark the violations of `file_patterns` the baseline knows as
+    /// `baselined`
+    pub fn apply(&self, file_patterns: &mut [FilePatterns]) -> BaselineReport {
+        let known: HashSet<&str> = self.violations.iter().map(|v| v.fingerprint.as_str()).collect();
+        let mut seen = HashSet::new();
+        let mut report = BaselineReport::default();
+        let mut fingerprinter = Fingerprinter::default();
+        for file in file_patterns.iter_mut() {
+            for i in in_file_order(file) {
+                let violation = &mut file.violations[i];
+                let fingerprint = fingerprinter.fingerprint(&violation.rule_id, &violation.file, &violation.actual);
+                violation.baselined = known.contains(fingerprint.as_str());
+                if violation.baselined {
+                    report.baselined += 1;
+                    seen.insert(fingerprint);
+                } else {
+                    report.new_violations += 1;
+                }
+            }
+        }
+        report.fixed = self.violations.iter()
+            .filter(|v| !seen.contains(&v.fingerprint))
+            .cloned()
+            .collect();
+        report
+    }
+}
+
+/// Indices of `file`'s violations by position
+pub(super) fn in_file_order(file: &FilePatterns) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..file.violations.len()).collect();
+    indices.sort_by_key(|&i| (file.violations[i].line, file.violations[i].column));
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unified::*;
+
+    #[test]
+    fn test_baseline_survives_line_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let api = dir.path().join("api.ts");
+        std::fs::write(&api, "process.exit(1);\nprocess.exit(1);\nprocess.exit(2);\n").unwrap();
+        let analyze = |baseline: Option<Baseline>| {
+            let options = UnifiedOptions {
+                include_violations: true,
+                custom_rules: vec![CustomRule {
+                    id: "no-process-exit".to_string(),
+                    category: PatternCategory::Errors,
+                    matcher: RuleMatcher::Regex(r"process\.exit\(\d\)".to_string()),
+                    severity: ViolationSeverity::Error,
+                    message: "Throw instead of exiting".to_string(),
+                    languages: vec![],
+                }],
+                force: true,
+                baseline,
+                ..Default::default()
+            };
+            UnifiedAnalyzer::new().unwrap().analyze(dir.path(), options)
+        };
+
+        let path = dir.path().join(".drift/baseline.json");
+        generate_baseline(&analyze(None)).save(&path).unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+        assert_eq!(baseline.violations.len(), 3);
+
+        // Shifted down, one copy fixed and a new copy added
+        std::fs::write(&api, "// legacy\n\nprocess.exit(1);\nprocess.exit(2);\nprocess.exit(2);\n").unwrap();
+        let result = analyze(Some(baseline));
+        let mut violations: Vec<(u32, bool)> = result.file_patterns.iter()
+            .flat_map(|f| f.violations.iter())
+            .map(|v| (v.line, v.baselined))
+            .collect();
+        violations.sort();
+        assert_eq!(violations, vec![(3, true), (4, true), (5, false)]);
+        let report = result.baseline.unwrap();
+        assert_eq!((report.baselined, report.new_violations), (2, 1));
+        // The second `process.exit(1)` is gone
+        assert_eq!(report.fixed.len(), 1);
+        assert_eq!((report.fixed[0].file.as_str(), report.fixed[0].line), ("api.ts", 2));
+
+        std::fs::write(&path, r#"{"version": 0, "violations": []}"#).unwrap();
+        assert!(Baseline::load(&path).unwrap_err().contains("regenerate"));
+    }
+}
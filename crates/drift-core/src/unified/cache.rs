@@ -0,0 +1,104 @@
+//! Unified cache - Per-file analysis persisted between runs
+//!
+//! One JSON entry per analyzed file under `.drift/lake/unified/`, named by a
+//! hash of the file path. Besides the file's patterns and violations, an
+//! entry keeps the functions it defines and the calls it makes, so the
+//! resolution index can be rebuilt over cached and fresh files alike. An
+//! entry is reused only while the file's content hash and the rules key
+//! (detector version plus the options that shape per-file output) both match.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::types::{FilePatterns, UnifiedOptions};
+
+/// Version of the unified detectors; bump whenever per-file output changes
+/// so results cached by older versions are reanalyzed
+pub const UNIFIED_RULES_VERSION: u32 = 1;
+
+/// Everything analysis keeps of one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAnalysis {
+    pub patterns: FilePatterns,
+    pub functions: Vec<IndexedFunction>,
+    /// Names of the functions the file calls
+    pub calls: Vec<String>,
+}
+
+/// A function definition, as the resolution index takes it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedFunction {
+    pub name: String,
+    pub qualified_name: Option<String>,
+    pub line: u32,
+    pub is_exported: bool,
+    pub is_async: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    file: String,
+    hash: String,
+    rules: String,
+    analysis: FileAnalysis,
+}
+
+/// On-disk cache of per-file unified analysis
+pub struct UnifiedCache {
+    dir: PathBuf,
+}
+
+impl UnifiedCache {
+    /// Cache for the project at `root` (`.drift/lake/unified/`)
+    pub fn new(root: &Path) -> Self {
+        Self {
+            dir: root.join(".drift").join("lake").join("unified"),
+        }
+    }
+
+    /// Cached analysis of `file` when it was analyzed with this content and rules
+    pub fn get(&self, file: &str, hash: &str, rules: &str) -> Option<FileAnalysis> {
+        let content = fs::read_to_string(self.entry_path(file)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        (entry.file == file && entry.hash == hash && entry.rules == rules).then_some(entry.analysis)
+    }
+
+    /// Store the analysis of `file`
+    pub fn put(&self, file: &str, hash: &str, rules: &str, analysis: &FileAnalysis) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+        let entry = CacheEntry {
+            file: file.to_string(),
+            hash: hash.to_string(),
+            rules: rules.to_string(),
+            analysis: analysis.clone(),
+        };
+        let json = serde_json::to_string(&entry)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(self.entry_path(file), json)
+            .map_err(|e| format!("Failed to write unified cache: {}", e))
+    }
+
+    fn entry_path(&self, file: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", xxh3_64(file.as_bytes())))
+    }
+}
+
+/// Identifies the rules per-file results are produced with: the detector
+/// version, category filter, custom rules and whether violations are raised
+pub fn rules_key(options: &UnifiedOptions) -> String {
+    let shaping = serde_json::json!({
+        "categories": options.categories,
+        "custom_rules": options.custom_rules,
+        "include_violations": options.include_violations,
+    });
+    format!("{}:{:016x}", UNIFIED_RULES_VERSION, xxh3_64(shaping.to_string().as_bytes()))
+}
+
+/// Hash of file contents used to key cache entries
+pub fn content_hash(source: &str) -> String {
+    format!("{:016x}", xxh3_64(source.as_bytes()))
+}
@@ -0,0 +1,223 @@
+//! Custom Rules - User-defined patterns from declarative config
+//!
+//! Rules are compiled once per analysis. A query is compiled against the
+//! grammar of every language the rule names, or every grammar when it names
+//! none; a rule that fails to compile is reported as a `RuleError` and
+//! skipped, without stopping the analysis. A query naming no languages is
+//! only an error when no grammar accepts it.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use tree_sitter::{Query, QueryCursor, Tree};
+
+use super::types::{
+    CustomRule, DetectedPattern, DetectionMethod, Language, RuleError, RuleMatcher, Violation,
+};
+
+/// Custom rules, compiled
+#[derive(Default)]
+pub struct CustomRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+struct CompiledRule {
+    rule: CustomRule,
+    matcher: CompiledMatcher,
+}
+
+enum CompiledMatcher {
+    /// The query compiled per grammar, with the index of the reported capture
+    Query(Vec<(tree_sitter::Language, Query, u32)>),
+    Regex(Regex),
+}
+
+impl CustomRuleSet {
+    /// Compile `rules`, with an error for each rule or grammar that rejects it
+    pub fn compile(rules: &[CustomRule]) -> (Self, Vec<RuleError>) {
+        let mut compiled = Vec::new();
+        let mut errors = Vec::new();
+        for rule in rules {
+            let error = |language: Option<Language>, message: String| RuleError {
+                rule: rule.id.clone(),
+                language,
+                message,
+            };
+            let matcher = match &rule.matcher {
+                RuleMatcher::Regex(pattern) => match Regex::new(pattern) {
+                    Ok(regex) => CompiledMatcher::Regex(regex),
+                    Err(e) => {
+                        errors.push(error(None, format!("Invalid regex: {}", e)));
+                        continue;
+                    }
+                },
+                RuleMatcher::Query(source) if !source.contains('@') => {
+                    errors.push(error(None, "Query has no captures to report".to_string()));
+                    continue;
+                }
+                RuleMatcher::Query(source) => {
+                    let languages = if rule.languages.is_empty() { Language::all() } else { &rule.languages[..] };
+                    let mut queries = Vec::new();
+                    let mut rejected = Vec::new();
+                    for &language in languages {
+                        for (i, grammar) in grammars(language).into_iter().enumerate() {
+                            match Query::new(&grammar, source) {
+                                Ok(query) => {
+                                    let capture = query.capture_index_for_name("match").unwrap_or(0);
+                                    queries.push((grammar, query, capture));
+                                }
+                                // TSX extends TypeScript, so its rejection alone is no error
+                                Err(_) if i > 0 => {}
+                                Err(e) => rejected.push(error(Some(language), format!("Invalid query: {}", e))),
+                            }
+                        }
+                    }
+                    if !rule.languages.is_empty() {
+                        errors.extend(rejected);
+                    } else if queries.is_empty() {
+                        let reason = rejected.first().map_or(String::new(), |e| format!(": {}", e.message));
+                        errors.push(error(None, format!("Query is invalid for every language{}", reason)));
+                    }
+                    if queries.is_empty() {
+                        continue;
+                    }
+                    CompiledMatcher::Query(queries)
+                }
+            };
+            compiled.push(CompiledRule { rule: rule.clone(), matcher });
+        }
+        (Self { rules: compiled }, errors)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The violation a custom rule's match raises
+    pub fn violation(&self, pattern: &DetectedPattern) -> Option<Violation> {
+        if !matches!(pattern.detection_method, DetectionMethod::CustomQuery | DetectionMethod::CustomRegex) {
+            return None;
+        }
+        let rule = &self.rules.iter().find(|r| r.rule.id == pattern.pattern_type)?.rule;
+        Some(Violation {
+            id: format!("{}:{}:{}:{}", rule.id, pattern.file, pattern.line, pattern.column),
+            pattern_id: pattern.pattern_type.clone(),
+            rule_id: rule.id.clone(),
+            severity: rule.severity,
+            file: pattern.file.clone(),
+            line: pattern.line,
+            column: pattern.column,
+            message: rule.message.clone(),
+            expected: format!("No match for rule '{}'", rule.id),
+            actual: pattern.matched_text.clone(),
+            suggested_fix: None,
+            baselined: false,
+        })
+    }
+
+    /// Matches of every rule applying to `language` in one file
+    pub fn detect(
+        &self,
+        tree: &Tree,
+        source: &[u8],
+        language: Language,
+        file: &str,
+    ) -> Vec<DetectedPattern> {
+        let mut patterns = Vec::new();
+        for compiled in &self.rules {
+            let rule = &compiled.rule;
+            if !rule.languages.is_empty() && !rule.languages.contains(&language) {
+                continue;
+            }
+            match &compiled.matcher {
+                CompiledMatcher::Query(queries) => {
+                    let Some((_, query, capture)) = queries.iter().find(|(grammar, _, _)| *grammar == *tree.language()) else { continue };
+                    let mut cursor = QueryCursor::new();
+                    for m in cursor.matches(query, tree.root_node(), source) {
+                        let Some(node) = m.captures.iter().find(|c| c.index == *capture).map(|c| c.node) else { continue };
+                        let start = node.start_position();
+                        let end = node.end_position();
+                        patterns.push(rule_match(
+                            rule,
+                            file,
+                            (start.row as u32 + 1, start.column as u32 + 1),
+                            (end.row as u32 + 1, end.column as u32 + 1),
+                            node.utf8_text(source).unwrap_or(""),
+                            DetectionMethod::CustomQuery,
+                        ));
+                    }
+                }
+                CompiledMatcher::Regex(regex) => {
+                    let Ok(text) = std::str::from_utf8(source) else { continue };
+                    for m in regex.find_iter(text).filter(|m| !m.as_str().is_empty()) {
+                        patterns.push(rule_match(
+                            rule,
+                            file,
+                            position(text, m.start()),
+                            position(text, m.end()),
+                            m.as_str(),
+                            DetectionMethod::CustomRegex,
+                        ));
+                    }
+                }
+            }
+        }
+        patterns
+    }
+}
+
+fn rule_match(
+    rule: &CustomRule,
+    file: &str,
+    (line, column): (u32, u32),
+    (end_line, end_column): (u32, u32),
+    text: &str,
+    detection_method: DetectionMethod,
+) -> DetectedPattern {
+    let metadata = HashMap::from([
+        ("rule".to_string(), serde_json::json!(rule.id)),
+        ("severity".to_string(), serde_json::json!(rule.severity)),
+        ("message".to_string(), serde_json::json!(rule.message)),
+    ]);
+    DetectedPattern {
+        category: rule.category,
+        pattern_type: rule.id.clone(),
+        subcategory: None,
+        file: file.to_string(),
+        line,
+        column,
+        end_line,
+        end_column,
+        matched_text: text.to_string(),
+        confidence: 1.0,
+        detection_method,
+        metadata: Some(metadata),
+    }
+}
+
+/// 1-indexed (line, column) of a byte offset
+fn position(text: &str, offset: usize) -> (u32, u32) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() as u32 + 1, (offset - line_start) as u32 + 1)
+}
+
+/// Grammars files of `language` may be parsed with; the TypeScript parser
+/// reads JavaScript as TSX
+fn grammars(language: Language) -> Vec<tree_sitter::Language> {
+    match language {
+        Language::TypeScript => vec![
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            tree_sitter_typescript::LANGUAGE_TSX.into(),
+        ],
+        Language::JavaScript => vec![tree_sitter_typescript::LANGUAGE_TSX.into()],
+        Language::Python => vec![tree_sitter_python::LANGUAGE.into()],
+        Language::Java => vec![tree_sitter_java::LANGUAGE.into()],
+        Language::CSharp => vec![tree_sitter_c_sharp::LANGUAGE.into()],
+        Language::Php => vec![tree_sitter_php::LANGUAGE_PHP.into()],
+        Language::Go => vec![tree_sitter_go::LANGUAGE.into()],
+        Language::Rust => vec![tree_sitter_rust::LANGUAGE.into()],
+        Language::Cpp => vec![tree_sitter_cpp::LANGUAGE.into()],
+        Language::C => vec![tree_sitter_c::LANGUAGE.into()],
+    }
+}
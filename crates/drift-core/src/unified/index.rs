@@ -218,6 +218,11 @@ impl ResolutionIndex {
         Resolution::Ambiguous(resolved)
     }
     
+    /// Whether `function` is defined in `file`
+    pub fn is_defined_in(&self, function: &ResolvedFunction, file: &str) -> bool {
+        self.get_path_symbol(file) == Some(function.file)
+    }
+    
     /// Get a function entry by ID
     pub fn get(&self, id: FunctionId) -> Option<&FunctionEntry> {
         self.entries.get(&id)
@@ -267,27 +272,12 @@ impl ResolutionIndex {
     
     // Helper to get name symbol without mutating
     fn get_name_symbol(&self, name: &str) -> Option<Symbol> {
-        // This is a workaround - in production we'd use a concurrent interner
-        for (sym, _) in &self.name_index {
-            if let Some(resolved) = self.func_interner.resolve(*sym) {
-                if resolved == name {
-                    return Some(*sym);
-                }
-            }
-        }
-        None
+        self.func_interner.get(name)
     }
     
     // Helper to get path symbol without mutating
     fn get_path_symbol(&self, path: &str) -> Option<Symbol> {
-        for (sym, _) in &self.file_index {
-            if let Some(resolved) = self.path_interner.resolve(*sym) {
-                if resolved == path || resolved == path.replace('\\', "/") {
-                    return Some(*sym);
-                }
-            }
-        }
-        None
+        self.path_interner.get(path)
     }
 }
 
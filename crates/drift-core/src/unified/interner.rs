@@ -150,10 +150,16 @@ impl PathInterner {
     }
     
     /// Resolve a path symbol
+    #[allow(dead_code)]
     pub fn resolve(&self, sym: Symbol) -> Option<&str> {
         self.interner.resolve(sym)
     }
     
+    /// Get the symbol for a path if it was interned
+    pub fn get(&self, path: &str) -> Option<Symbol> {
+        self.interner.get(&path.replace('\\', "/"))
+    }
+    
     /// Get the number of interned paths
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
@@ -198,10 +204,16 @@ impl FunctionInterner {
     }
     
     /// Resolve a function symbol
+    #[allow(dead_code)]
     pub fn resolve(&self, sym: Symbol) -> Option<&str> {
         self.interner.resolve(sym)
     }
     
+    /// Get the symbol for a function name if it was interned
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.interner.get(name)
+    }
+    
     /// Get the number of interned function names
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
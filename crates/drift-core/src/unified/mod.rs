@@ -19,17 +19,30 @@
 //! - Parallel file processing with work stealing
 //! - String interning for memory efficiency
 //! - Streaming pattern output
+//! - User-defined rules as tree-sitter queries or regexes, raising
+//!   violations unless a `drift-ignore` comment suppresses them
+//! - SARIF 2.1.0 export for code scanning
+//! - Per-file results cached by content hash, so unchanged files are
+//!   neither parsed nor detected again
+//! - Baselines of known violations, so gates fail on new ones only
 
 mod types;
 mod interner;
 mod index;
 mod analyzer;
+mod baseline;
+mod cache;
 mod ast_patterns;
+mod custom_rules;
 mod string_analyzer;
+mod suppression;
+mod sarif;
 
 pub use types::*;
 pub use interner::StringInterner;
 pub use index::ResolutionIndex;
 pub use analyzer::UnifiedAnalyzer;
+pub use baseline::{generate_baseline, BASELINE_VERSION};
 pub use ast_patterns::AstPatternDetector;
 pub use string_analyzer::StringLiteralAnalyzer;
+pub use sarif::{unified_to_sarif, SarifOptions};
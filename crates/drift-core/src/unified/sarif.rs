@@ -0,0 +1,320 @@
+//! SARIF Export - Unified results as SARIF 2.1.0
+//!
+//! Violations become results at their rule's severity; detected patterns,
+//! when included, become `note` results. Every rule is described once in
+//! the driver's `rules`, and every result carries a partial fingerprint
+//! (rule, file, matched code and its occurrence among identical matches)
+//! that survives lines moving between runs. When the analysis had a
+//! baseline, violations carry their `baselineState`.
+//!
+//! SARIF regions are 1-based, with an exclusive end column. Unified
+//! positions are already 1-based (tree-sitter rows and columns plus one);
+//! anything at 0 is clamped so no region is invalid.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{json, Value};
+
+use super::baseline::{in_file_order, Fingerprinter};
+use super::types::{DetectedPattern, DetectionMethod, UnifiedResult, ViolationSeverity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const FINGERPRINT_KEY: &str = "driftFingerprint/v1";
+
+/// SARIF export options
+#[derive(Debug, Clone, Default)]
+pub struct SarifOptions {
+    /// Report detected patterns as `note` results, not only violations
+    pub include_patterns: bool,
+    /// Absolute URI of the analyzed root, which file paths are relative to
+    pub src_root: Option<String>,
+    /// Indent the JSON
+    pub pretty: bool,
+}
+
+/// A SARIF result before serialization
+struct Finding<'a> {
+    rule_id: &'a str,
+    rule_index: usize,
+    level: &'static str,
+    message: String,
+    file: &'a str,
+    /// Start line and column, end line and exclusive end column; an end
+    /// column of 0 is unknown
+    region: (u32, u32, u32, u32),
+    snippet: &'a str,
+    /// `unchanged` or `new`, against a baseline
+    baseline_state: Option<&'static str>,
+}
+
+/// A SARIF rule, in order of first use
+struct Rule {
+    id: String,
+    description: String,
+    level: &'static str,
+    category: Option<String>,
+}
+
+/// `result` as a SARIF 2.1.0 log
+pub fn unified_to_sarif(result: &UnifiedResult, options: &SarifOptions) -> Result<String, String> {
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut rule_index: HashMap<String, usize> = HashMap::new();
+    let mut results = Vec::new();
+    let mut fingerprinter = Fingerprinter::default();
+
+    let mut index_of = |rule: Rule| -> usize {
+        *rule_index.entry(rule.id.clone()).or_insert_with(|| {
+            rules.push(rule);
+            rules.len() - 1
+        })
+    };
+
+    for file in &result.file_patterns {
+        // Where each pattern ends, for the violations it raised
+        let patterns: HashMap<(&str, u32, u32), &DetectedPattern> = file.patterns.iter()
+            .map(|p| ((p.pattern_type.as_str(), p.line, p.column), p))
+            .collect();
+
+        for violation in in_file_order(file).into_iter().map(|i| &file.violations[i]) {
+            let pattern = patterns.get(&(violation.pattern_id.as_str(), violation.line, violation.column));
+            let index = index_of(Rule {
+                id: violation.rule_id.clone(),
+                description: violation.message.clone(),
+                level: level(violation.severity),
+                category: pattern.map(|p| category_name(p)),
+            });
+            let (end_line, end_column) = pattern.map_or((violation.line, 0), |p| (p.end_line, p.end_column));
+            results.push(sarif_result(Finding {
+                rule_id: &violation.rule_id,
+                rule_index: index,
+                level: level(violation.severity),
+                message: violation.message.clone(),
+                file: &violation.file,
+                region: (violation.line, violation.column, end_line, end_column),
+                snippet: &violation.actual,
+                baseline_state: result.baseline.as_ref()
+                    .map(|_| if violation.baselined { "unchanged" } else { "new" }),
+            }, &mut fingerprinter));
+        }
+
+        if !options.include_patterns {
+            continue;
+        }
+        let mut patterns: Vec<&DetectedPattern> = file.patterns.iter()
+            // Custom rule matches are reported as their violations
+            .filter(|p| !matches!(p.detection_method, DetectionMethod::CustomQuery | DetectionMethod::CustomRegex))
+            .collect();
+        patterns.sort_by_key(|p| (p.line, p.column));
+        for pattern in patterns {
+            let category = category_name(pattern);
+            let index = index_of(Rule {
+                id: pattern.pattern_type.clone(),
+                description: format!("{} pattern '{}'", category, pattern.pattern_type),
+                level: "note",
+                category: Some(category.clone()),
+            });
+            results.push(sarif_result(Finding {
+                rule_id: &pattern.pattern_type,
+                rule_index: index,
+                level: "note",
+                message: format!("Detected {} pattern '{}'", category, pattern.pattern_type),
+                file: &pattern.file,
+                region: (pattern.line, pattern.column, pattern.end_line, pattern.end_column),
+                snippet: &pattern.matched_text,
+                baseline_state: None,
+            }, &mut fingerprinter));
+        }
+    }
+
+    let rules: Vec<Value> = rules.iter()
+        .map(|rule| {
+            let mut value = json!({
+                "id": rule.id,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": rule.level },
+            });
+            if let Some(category) = &rule.category {
+                value["properties"] = json!({ "tags": [category] });
+            }
+            value
+        })
+        .collect();
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "drift",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }
+        },
+        "results": results,
+    });
+    if let Some(root) = &options.src_root {
+        let uri = if root.ends_with('/') { root.clone() } else { format!("{}/", root) };
+        run["originalUriBaseIds"] = json!({ "SRCROOT": { "uri": uri } });
+    }
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    });
+
+    if options.pretty {
+        serde_json::to_string_pretty(&log)
+    } else {
+        serde_json::to_string(&log)
+    }
+    .map_err(|e| format!("Failed to serialize SARIF: {}", e))
+}
+
+fn sarif_result(finding: Finding, fingerprinter: &mut Fingerprinter) -> Value {
+    let Finding {
+        rule_id, rule_index, level, message, file, region: (line, column, end_line, end_column), snippet, baseline_state,
+    } = finding;
+    let mut fingerprints = serde_json::Map::new();
+    fingerprints.insert(FINGERPRINT_KEY.to_string(), json!(fingerprinter.fingerprint(rule_id, file, snippet)));
+
+    let mut region = BTreeMap::from([
+        ("startLine", json!(line.max(1))),
+        ("startColumn", json!(column.max(1))),
+    ]);
+    if end_column > 0 {
+        region.insert("endLine", json!(end_line.max(line).max(1)));
+        region.insert("endColumn", json!(end_column));
+    }
+    if !snippet.is_empty() {
+        region.insert("snippet", json!({ "text": snippet }));
+    }
+    let mut result = json!({
+        "ruleId": rule_id,
+        "ruleIndex": rule_index,
+        "level": level,
+        "message": { "text": message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": file.replace('\\', "/"), "uriBaseId": "SRCROOT" },
+                "region": region,
+            }
+        }],
+        "partialFingerprints": fingerprints,
+    });
+    if let Some(state) = baseline_state {
+        result["baselineState"] = json!(state);
+    }
+    result
+}
+
+fn level(severity: ViolationSeverity) -> &'static str {
+    match severity {
+        ViolationSeverity::Error => "error",
+        ViolationSeverity::Warning => "warning",
+        ViolationSeverity::Info | ViolationSeverity::Hint => "note",
+    }
+}
+
+/// The category as the unified types serialize it: `data-access`
+fn category_name(pattern: &DetectedPattern) -> String {
+    serde_json::to_value(pattern.category)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unified::*;
+
+    #[test]
+    fn test_sarif_regions_are_one_based() {
+        let dir = tempfile::tempdir().unwrap();
+        // The match starts at tree-sitter row 0, column 0
+        std::fs::write(dir.path().join("exit.ts"), "process.exit(1);\nprocess.exit(1);\n").unwrap();
+        let options = UnifiedOptions {
+            include_violations: true,
+            custom_rules: vec![CustomRule {
+                id: "no-process-exit".to_string(),
+                category: PatternCategory::Errors,
+                matcher: RuleMatcher::Query(
+                    r#"(call_expression function: (member_expression property: (property_identifier) @p (#eq? @p "exit"))) @match"#.to_string(),
+                ),
+                severity: ViolationSeverity::Error,
+                message: "Throw instead of exiting".to_string(),
+                languages: vec![],
+            }],
+            ..Default::default()
+        };
+        let result = UnifiedAnalyzer::new().unwrap().analyze(dir.path(), options);
+        let sarif: Value = serde_json::from_str(&unified_to_sarif(&result, &SarifOptions::default()).unwrap()).unwrap();
+
+        let run = &sarif["runs"][0];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "no-process-exit");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["defaultConfiguration"]["level"], "error");
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "exit.ts");
+        // `process.exit(1)` spans columns 1 to 16, the end exclusive
+        assert_eq!(location["region"]["startLine"], 1);
+        assert_eq!(location["region"]["startColumn"], 1);
+        assert_eq!(location["region"]["endLine"], 1);
+        assert_eq!(location["region"]["endColumn"], 16);
+        assert_eq!(results[1]["locations"][0]["physicalLocation"]["region"]["startLine"], 2);
+
+        // Identical matches stay apart by occurrence, and keep their
+        // fingerprints when lines shift
+        let fingerprints = |results: &[Value]| -> Vec<String> {
+            results.iter().map(|r| r["partialFingerprints"][FINGERPRINT_KEY].as_str().unwrap().to_string()).collect()
+        };
+        let before = fingerprints(results);
+        assert_ne!(before[0], before[1]);
+        let mut shifted = result.clone();
+        for violation in &mut shifted.file_patterns[0].violations {
+            violation.line += 10;
+        }
+        let sarif: Value = serde_json::from_str(&unified_to_sarif(&shifted, &SarifOptions::default()).unwrap()).unwrap();
+        assert_eq!(fingerprints(sarif["runs"][0]["results"].as_array().unwrap()), before);
+    }
+
+    #[test]
+    fn test_sarif_clamps_zero_positions() {
+        let pattern = DetectedPattern {
+            category: PatternCategory::DataAccess,
+            pattern_type: "sql-query".to_string(),
+            file: "src\\db.ts".to_string(),
+            ..Default::default()
+        };
+        let result = UnifiedResult {
+            file_patterns: vec![FilePatterns {
+                file: "src\\db.ts".to_string(),
+                language: Language::TypeScript,
+                patterns: vec![pattern],
+                violations: vec![],
+                suppressed_inline: 0,
+                suppressed_by_file: 0,
+                parse_time_us: 0,
+                detect_time_us: 0,
+            }],
+            resolution: ResolutionStats::default(),
+            call_graph: CallGraphSummary::default(),
+            metrics: AnalysisMetrics::default(),
+            total_patterns: 1,
+            total_violations: 0,
+            suppressed: SuppressionStats::default(),
+            rule_errors: vec![],
+            baseline: None,
+        };
+        let options = SarifOptions { include_patterns: true, src_root: Some("file:///repo".to_string()), pretty: false };
+        let sarif: Value = serde_json::from_str(&unified_to_sarif(&result, &options).unwrap()).unwrap();
+
+        let run = &sarif["runs"][0];
+        assert_eq!(run["originalUriBaseIds"]["SRCROOT"]["uri"], "file:///repo/");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["properties"]["tags"][0], "data-access");
+        let location = &run["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(run["results"][0]["level"], "note");
+        assert_eq!(location["artifactLocation"]["uri"], "src/db.ts");
+        assert_eq!((location["region"]["startLine"].as_u64(), location["region"]["startColumn"].as_u64()), (Some(1), Some(1)));
+        assert!(location["region"].get("endColumn").is_none());
+    }
+}
@@ -0,0 +1,80 @@
+//! Suppression Comments - `drift-ignore` and `drift-ignore-file`
+//!
+//! Read from the AST's comment nodes, so strings that mention them don't
+//! count:
+//! - `// drift-ignore rule-id` hides violations on its own line and the next
+//! - `// drift-ignore-file rule-id` hides violations anywhere in the file
+//!
+//! Several rule ids may follow, separated by commas or spaces, then an
+//! optional `-- reason`; without any, the comment hides every rule.
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tree_sitter::Tree;
+
+static DIRECTIVE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bdrift-ignore(-file)?\b([\w\-, \t]*)").unwrap());
+
+/// Rule ids a comment suppresses; empty for every rule
+type Rules = Vec<String>;
+
+/// Suppression comments of one file
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    file: Vec<Rules>,
+    /// 1-indexed line -> comments on it
+    lines: HashMap<u32, Vec<Rules>>,
+}
+
+/// Why a violation isn't reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suppressed {
+    Inline,
+    File,
+}
+
+impl Suppressions {
+    /// Suppression comments in `tree`
+    pub fn parse(tree: &Tree, source: &[u8]) -> Self {
+        let mut suppressions = Self::default();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if node.kind().ends_with("comment") {
+                let text = node.utf8_text(source).unwrap_or("");
+                for captures in DIRECTIVE.captures_iter(text) {
+                    // `-- reason` ends the ids
+                    let ids = captures[2].split("--").next().unwrap_or("");
+                    let rules: Rules = ids
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|id| !id.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    if captures.get(1).is_some() {
+                        suppressions.file.push(rules);
+                    } else {
+                        let line = node.end_position().row as u32 + 1;
+                        suppressions.lines.entry(line).or_default().push(rules);
+                    }
+                }
+                continue;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        suppressions
+    }
+
+    /// Whether a violation of `rule` on `line` (1-indexed) is suppressed
+    pub fn check(&self, rule: &str, line: u32) -> Option<Suppressed> {
+        let covers = |rules: &Rules| rules.is_empty() || rules.iter().any(|id| id == rule);
+        if self.file.iter().any(covers) {
+            return Some(Suppressed::File);
+        }
+        [line, line.saturating_sub(1)].iter()
+            .filter_map(|line| self.lines.get(line))
+            .flatten()
+            .any(covers)
+            .then_some(Suppressed::Inline)
+    }
+}
@@ -107,6 +107,10 @@ pub enum DetectionMethod {
     RegexFallback,
     /// Structural analysis (file/directory patterns)
     Structural,
+    /// A custom rule's tree-sitter query
+    CustomQuery,
+    /// A custom rule's regex, run on the whole source
+    CustomRegex,
 }
 
 /// A detected pattern
@@ -164,6 +168,9 @@ pub struct Violation {
     pub id: String,
     /// Related pattern ID
     pub pattern_id: String,
+    /// Rule that raised the violation
+    #[serde(default)]
+    pub rule_id: String,
     /// Severity level
     pub severity: ViolationSeverity,
     /// File location
@@ -180,6 +187,9 @@ pub struct Violation {
     pub actual: String,
     /// Suggested fix
     pub suggested_fix: Option<String>,
+    /// Already recorded in the baseline, so not new
+    #[serde(default)]
+    pub baselined: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -198,6 +208,12 @@ pub struct FilePatterns {
     pub language: Language,
     pub patterns: Vec<DetectedPattern>,
     pub violations: Vec<Violation>,
+    /// Violations hidden by `drift-ignore` comments on or above their line
+    #[serde(default)]
+    pub suppressed_inline: u64,
+    /// Violations hidden by a `drift-ignore-file` comment
+    #[serde(default)]
+    pub suppressed_by_file: u64,
     pub parse_time_us: u64,
     pub detect_time_us: u64,
 }
@@ -217,6 +233,61 @@ pub struct UnifiedOptions {
     pub threads: usize,
     /// Include violations in output
     pub include_violations: bool,
+    /// Include files the scanner flagged as minified or generated
+    pub include_generated: bool,
+    /// House patterns to detect on top of the built-in ones
+    pub custom_rules: Vec<CustomRule>,
+    /// Reanalyze every file instead of reusing results cached under
+    /// `.drift/lake/unified/`; the fresh results are still cached
+    pub force: bool,
+    /// Violations known before, marked `baselined` rather than new
+    pub baseline: Option<Baseline>,
+}
+
+/// A user-defined pattern rule
+///
+/// Deserializes from `{ "id": "no-process-exit", "category": "errors",
+/// "type": "regex", "pattern": "process\\.exit\\(", "severity": "error",
+/// "message": "..." }`. Matches are reported as patterns of type `id`, with
+/// the rule, severity and message in their metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomRule {
+    /// Rule id, reported as the pattern type
+    pub id: String,
+    pub category: PatternCategory,
+    #[serde(flatten)]
+    pub matcher: RuleMatcher,
+    #[serde(default = "default_rule_severity")]
+    pub severity: ViolationSeverity,
+    pub message: String,
+    /// Languages the rule applies to (empty = all)
+    #[serde(default)]
+    pub languages: Vec<Language>,
+}
+
+fn default_rule_severity() -> ViolationSeverity {
+    ViolationSeverity::Warning
+}
+
+/// How a custom rule matches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "pattern", rename_all = "lowercase")]
+pub enum RuleMatcher {
+    /// Tree-sitter query; each match reports its `@match` capture, or its
+    /// first capture
+    Query(String),
+    /// Regex over the whole source
+    Regex(String),
+}
+
+/// A custom rule that couldn't be compiled, and was skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleError {
+    pub rule: String,
+    /// Language whose grammar rejected the query; `None` when the rule
+    /// failed as a whole
+    pub language: Option<Language>,
+    pub message: String,
 }
 
 /// Resolution statistics
@@ -228,6 +299,16 @@ pub struct ResolutionStats {
     pub same_file_resolutions: u64,
     pub cross_file_resolutions: u64,
     pub unresolved_calls: u64,
+    /// Resolved calls per resolution strategy ("receiver-type", "import", ...)
+    #[serde(default)]
+    pub by_strategy: Vec<StrategyCount>,
+}
+
+/// Number of calls resolved by one resolution strategy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyCount {
+    pub strategy: String,
+    pub count: u64,
 }
 
 /// Call graph summary
@@ -248,6 +329,15 @@ pub struct AnalysisMetrics {
     pub detect_time_ms: u64,
     pub resolve_time_ms: u64,
     pub total_time_ms: u64,
+    /// Files whose cached analysis was reused
+    #[serde(default)]
+    pub cache_hits: u64,
+    /// Files analyzed afresh
+    #[serde(default)]
+    pub cache_misses: u64,
+    /// Hits over analyzed files, 0 when there were none
+    #[serde(default)]
+    pub cache_hit_ratio: f32,
 }
 
 /// Unified analysis result
@@ -263,8 +353,59 @@ pub struct UnifiedResult {
     pub metrics: AnalysisMetrics,
     /// Total patterns found
     pub total_patterns: u64,
-    /// Total violations found
+    /// Total violations found, after suppression
     pub total_violations: u64,
+    /// Violations suppressed by comments, counted apart from the total
+    #[serde(default)]
+    pub suppressed: SuppressionStats,
+    /// Custom rules skipped as invalid
+    #[serde(default)]
+    pub rule_errors: Vec<RuleError>,
+    /// How violations compared to the baseline, when one was given
+    #[serde(default)]
+    pub baseline: Option<BaselineReport>,
+}
+
+/// Fingerprints of the violations a codebase had when it adopted drift
+///
+/// Fingerprints hash the file, rule and whitespace-normalized matched code
+/// plus the occurrence among identical matches, never the line, so moving
+/// code doesn't turn known violations into new ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub violations: Vec<BaselineEntry>,
+}
+
+/// One violation recorded in a baseline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub file: String,
+    /// Where the violation was when recorded; not used for matching
+    pub line: u32,
+}
+
+/// Violations of one analysis compared to a baseline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineReport {
+    /// Violations found in the baseline
+    pub baselined: u64,
+    /// Violations the baseline doesn't know, which gates should fail on
+    pub new_violations: u64,
+    /// Baselined violations no longer reported, which can be dropped by
+    /// regenerating the baseline
+    pub fixed: Vec<BaselineEntry>,
+}
+
+/// Violations hidden by suppression comments
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SuppressionStats {
+    pub inline: u64,
+    pub file_level: u64,
+    /// Files whose `drift-ignore-file` comment hid violations
+    pub files: Vec<String>,
 }
 
 /// String literal extracted from AST for regex analysis
@@ -16,12 +16,13 @@ impl WrapperClusterer {
 
     /// Cluster wrappers by their wrapped primitives and categories
     pub fn cluster(&self, wrappers: &[WrapperInfo]) -> Vec<WrapperCluster> {
-        // Group by (category, wrapped_primitive)
+        // Group by (category, wrapped_primitive); generic instantiations such as
+        // `Repository<User>` and `Repository<Order>` share one primitive
         let mut groups: HashMap<(WrapperCategory, String), Vec<WrapperInfo>> = HashMap::new();
         
         for wrapper in wrappers {
             for wrapped in &wrapper.wraps {
-                let key = (wrapper.category, wrapped.clone());
+                let key = (wrapper.category, generic_base(wrapped).to_string());
                 groups.entry(key).or_default().push(wrapper.clone());
             }
         }
@@ -80,8 +81,44 @@ impl WrapperClusterer {
     }
 }
 
+/// Strip generic arguments: `Repository<User>` -> `Repository`
+fn generic_base(primitive: &str) -> &str {
+    primitive.split('<').next().unwrap_or(primitive).trim_end()
+}
+
 impl Default for WrapperClusterer {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrapper(name: &str, wraps: &str) -> WrapperInfo {
+        WrapperInfo {
+            name: name.to_string(),
+            file: "repo.ts".to_string(),
+            line: 1,
+            wraps: vec![wraps.to_string()],
+            category: WrapperCategory::DataFetching,
+            is_exported: true,
+            usage_count: 1,
+            confidence: 0.8,
+        }
+    }
+
+    #[test]
+    fn test_generic_instantiations_share_cluster() {
+        let wrappers = vec![
+            wrapper("userRepo", "Repository<User>"),
+            wrapper("orderRepo", "Repository<Order>"),
+            wrapper("query", "db.query"),
+        ];
+        let clusters = WrapperClusterer::new().cluster(&wrappers);
+        assert_eq!(clusters.len(), 2);
+        let repo = clusters.iter().find(|c| c.wrapped_primitive == "Repository").unwrap();
+        assert_eq!(repo.wrappers.len(), 2);
+    }
+}
@@ -7,6 +7,8 @@
 export interface JsScanResult {
   root: string
   files: Array<JsFileInfo>
+  deletedFiles: Array<JsFileInfo>
+  hashAlgorithm?: string
   stats: JsScanStats
   errors: Array<string>
 }
@@ -16,33 +18,72 @@ export interface JsFileInfo {
   size: number
   hash?: string
   language?: string
+  mtime?: number
+  changeStatus?: string
+  lines: number
+  isMinified: boolean
+  isGenerated: boolean
 }
 /** Scan stats exposed to JavaScript */
 export interface JsScanStats {
   totalFiles: number
   totalBytes: number
+  byLanguage: Array<JsLanguageBreakdown>
   dirsSkipped: number
   filesSkipped: number
+  changedFiles: number
+  skippedBySource: Array<JsIgnoreSourceStats>
   durationMs: number
 }
+/** Files, bytes and lines of one language, exposed to JavaScript */
+export interface JsLanguageBreakdown {
+  language: string
+  files: number
+  bytes: number
+  lines: number
+}
+/** Files/directories skipped by one ignore source, exposed to JavaScript */
+export interface JsIgnoreSourceStats {
+  source: string
+  filesSkipped: number
+  dirsSkipped: number
+}
 /** Scan configuration from JavaScript */
 export interface JsScanConfig {
   root: string
   patterns: Array<string>
   extraIgnores?: Array<string>
   computeHashes?: boolean
+  hashAlgorithm?: string
   maxFileSize?: number
   threads?: number
+  followSymlinks?: boolean
+  respectGitignore?: boolean
+  progressInterval?: number
+  maxDepth?: number
+  maxFilesPerDir?: number
+}
+/** Scan progress event exposed to JavaScript */
+export interface JsScanProgress {
+  filesSeen: number
+  totalFiles: number
+  bytesRead: number
+  currentDir: string
+  dirsSkipped: number
+  filesSkipped: number
 }
 /** Parse result exposed to JavaScript */
 export interface JsParseResult {
   language: string
   functions: Array<JsFunctionInfo>
   classes: Array<JsClassInfo>
+  enums: Array<JsEnumInfo>
   imports: Array<JsImportInfo>
   exports: Array<JsExportInfo>
   calls: Array<JsCallSite>
   errors: Array<JsParseError>
+  /** Syntax error regions tree-sitter recovered from (0 for a clean parse) */
+  errorNodeCount: number
   parseTimeUs: number
 }
 /** Parameter info exposed to JavaScript */
@@ -60,6 +101,9 @@ export interface JsFunctionInfo {
   returnType?: string
   isExported: boolean
   isAsync: boolean
+  visibility: string
+  isStatic: boolean
+  typeParameters: Array<string>
   startLine: number
   endLine: number
   decorators: Array<string>
@@ -75,6 +119,10 @@ export interface JsClassInfo {
   endLine: number
   decorators: Array<string>
   properties: Array<JsPropertyInfo>
+  methods: Array<JsFunctionInfo>
+  namespace?: string
+  isPartial: boolean
+  typeParameters: Array<string>
 }
 /** Property info exposed to JavaScript (for struct fields, class properties) */
 export interface JsPropertyInfo {
@@ -84,12 +132,29 @@ export interface JsPropertyInfo {
   isReadonly: boolean
   visibility: string
   tags?: Array<JsStructTag>
+  isComputed: boolean
+  decorators: Array<string>
 }
 /** Struct tag exposed to JavaScript (for Go struct field tags) */
 export interface JsStructTag {
   key: string
   value: string
 }
+/** Enum (or literal union) info exposed to JavaScript */
+export interface JsEnumInfo {
+  name: string
+  variants: Array<JsEnumVariant>
+  isExported: boolean
+  startLine: number
+  endLine: number
+  decorators: Array<string>
+}
+/** Enum member exposed to JavaScript */
+export interface JsEnumVariant {
+  name: string
+  value?: string
+  line: number
+}
 /** Import info exposed to JavaScript */
 export interface JsImportInfo {
   source: string
@@ -97,6 +162,7 @@ export interface JsImportInfo {
   default?: string
   namespace?: string
   isTypeOnly: boolean
+  relativeLevel: number
   line: number
 }
 /** Export info exposed to JavaScript */
@@ -112,16 +178,86 @@ export interface JsCallSite {
   receiver?: string
   argCount: number
   line: number
+  stringArg?: string
 }
 /** Parse error exposed to JavaScript */
 export interface JsParseError {
   message: string
   line: number
+  endLine: number
+  snippet?: string
 }
 /** Scan a directory for source files */
 export declare function scan(config: JsScanConfig): JsScanResult
-/** Parse source code and extract functions, classes, imports, exports, and calls */
-export declare function parse(source: string, filePath: string): JsParseResult | null
+/**
+ * Incrementally scan a directory using the persisted scan cache
+ *
+ * Only files whose size or mtime changed since the last cached scan are
+ * re-hashed. Each file carries a change status (added/modified/unchanged),
+ * deleted files are reported separately, and the cache is updated afterwards.
+ */
+export declare function scanIncremental(config: JsScanConfig): JsScanResult
+/**
+ * Scan a directory for source files, reporting progress via callback
+ *
+ * The scan runs on a worker thread so the callback can fire while it is
+ * in progress. It is invoked every `progressInterval` files (default 100).
+ */
+export declare function scanWithProgress(config: JsScanConfig, callback: (progress: JsScanProgress) => void): Promise<JsScanResult>
+/**
+ * Scan a directory, streaming files to a callback in batches
+ *
+ * Avoids materializing the full file list for very large repositories.
+ * The callback receives arrays of up to 1000 files; the returned promise
+ * resolves to the final scan stats.
+ */
+export declare function scanStreaming(config: JsScanConfig, onBatch: (files: Array<JsFileInfo>) => void): Promise<JsScanStats>
+/**
+ * Parse source code and extract functions, classes, imports, exports, and calls
+ *
+ * The language is inferred from the file extension (or shebang) unless
+ * `language` is given explicitly, e.g. "python" or "typescript".
+ */
+export declare function parse(source: string, filePath: string, language?: string | undefined | null): JsParseResult | null
+/** Node captured by `queryAst` */
+export interface JsAstCapture {
+  name: string
+  text: string
+  startLine: number
+  startCol: number
+  endLine: number
+  endCol: number
+}
+/**
+ * Run a tree-sitter query (S-expression syntax) against a parsed file
+ *
+ * The grammar is chosen from the file extension, as in `parse`. Invalid
+ * queries fail with the offset tree-sitter reports.
+ */
+export declare function queryAst(source: string, filePath: string, tsQuery: string): Array<JsAstCapture>
+/** Options for `parseFiles` */
+export interface JsParseFilesOptions {
+  /** Directory relative paths are resolved against */
+  rootDir?: string
+  /** Parse every file as this language instead of inferring it */
+  language?: string
+}
+/** Result for one file of a `parseFiles` batch */
+export interface JsFileParseResult {
+  path: string
+  result?: JsParseResult
+  /** Set when the file could not be read or its language is unsupported */
+  error?: string
+}
+/**
+ * Read and parse many files in parallel
+ *
+ * Files are read and parsed on Rust worker threads, so sources never cross
+ * the JavaScript boundary. Results are returned in the order of `paths`.
+ */
+export declare function parseFiles(paths: Array<string>, options?: JsParseFilesOptions | undefined | null): Array<JsFileParseResult>
+/** Read and parse many files in parallel without blocking the event loop */
+export declare function parseFilesAsync(paths: Array<string>, options?: JsParseFilesOptions | undefined | null): Promise<Array<JsFileParseResult>>
 /** Get list of supported languages */
 export declare function supportedLanguages(): Array<string>
 /** Get the version of drift-core */
@@ -137,12 +273,58 @@ export interface JsBuildResult {
   dataAccessors: number
   errors: Array<string>
   durationMs: number
+  /** Incremental builds: functions new in the changed files */
+  functionsAdded: number
+  /** Incremental builds: functions gone from the changed files */
+  functionsRemoved: number
+  /** Incremental builds: functions re-extracted from the changed files */
+  functionsUpdated: number
+}
+/** Project-specific entry point convention; every given criterion must match */
+export interface JsEntryPointRule {
+  /** Reported as the entry kind of matching functions */
+  name: string
+  /** Regexes matched against the function's decorators, or its class's */
+  decorators?: Array<string>
+  /** Names of a base class or interface of the function's class */
+  baseClasses?: Array<string>
+  /** Globs matched against the project-relative file path */
+  fileGlobs?: Array<string>
+  /** Regexes matched against the function name (without its class) */
+  functionNames?: Array<string>
+}
+/**
+ * Project-specific async job convention: regexes with a `job` group, and
+ * for handlers a `handler` group naming the function that runs the job
+ */
+export interface JsAsyncEdgeRule {
+  name: string
+  /** Patterns matching where a job is enqueued */
+  enqueue?: Array<string>
+  /** Patterns matching where a handler is registered for a job */
+  handlers?: Array<string>
 }
 /** Call graph build configuration from JavaScript */
 export interface JsBuildConfig {
   root: string
   patterns: Array<string>
   resolutionBatchSize?: number
+  /** Files written to SQLite per transaction (default: 100) */
+  writeBatchSize?: number
+  includeGenerated?: boolean
+  /** Entry point conventions checked in order, before the built-in ones */
+  entryPointRules?: Array<JsEntryPointRule>
+  /** Enqueue and job handler conventions, added to the built-in ones */
+  asyncEdgeRules?: Array<JsAsyncEdgeRule>
+}
+/** Call graph build progress event exposed to JavaScript */
+export interface JsBuildProgress {
+  /** "scanning" | "parsing" | "writing" | "resolving" */
+  phase: string
+  filesProcessed: number
+  totalFiles: number
+  elapsedMs: number
+  currentFile?: string
 }
 /**
  * Build call graph for a project using SQLite storage (recommended)
@@ -151,6 +333,27 @@ export interface JsBuildConfig {
  * for optimal performance on large codebases.
  */
 export declare function buildCallGraph(config: JsBuildConfig): JsBuildResult
+/**
+ * Build call graph on a worker thread (same as build_call_graph, without
+ * blocking the event loop)
+ */
+export declare function buildCallGraphAsync(config: JsBuildConfig): Promise<JsBuildResult>
+/**
+ * Build call graph on a worker thread, reporting progress via callback
+ *
+ * The callback receives the current phase (scanning, parsing, writing,
+ * resolving), files parsed so far, total files and elapsed milliseconds.
+ * During parsing it fires once per file.
+ */
+export declare function buildCallGraphWithProgress(config: JsBuildConfig, callback: (progress: JsBuildProgress) => void): Promise<JsBuildResult>
+/**
+ * Update the SQLite call graph for changed (or deleted) files only
+ *
+ * Rows for `changed_files` are replaced and resolution re-runs only for calls
+ * those files make or calls targeting a function they define. Requires a
+ * graph built with build_call_graph(); `patterns` in the config is ignored.
+ */
+export declare function buildCallGraphIncremental(config: JsBuildConfig, changedFiles: Array<string>): JsBuildResult
 /**
  * Build call graph using legacy JSON shard storage
  *
@@ -158,6 +361,24 @@ export declare function buildCallGraph(config: JsBuildConfig): JsBuildResult
  * Use build_call_graph() for better performance.
  */
 export declare function buildCallGraphLegacy(config: JsBuildConfig): JsBuildResult
+/** A legacy shard that could not be migrated */
+export interface JsShardError {
+  path: string
+  error: string
+}
+/** Result of migrating legacy JSON shards to SQLite */
+export interface JsMigrationResult {
+  shardsMigrated: number
+  functionsMigrated: number
+  edgesMigrated: number
+  resolvedEdges: number
+  failedShards: Array<JsShardError>
+}
+/**
+ * Migrate a call graph built with build_call_graph_legacy() into the SQLite
+ * database, replacing its contents
+ */
+export declare function migrateCallGraphToSqlite(rootDir: string): JsMigrationResult
 /** Data access point exposed to JavaScript */
 export interface JsDataAccessPoint {
   table: string
@@ -167,11 +388,15 @@ export interface JsDataAccessPoint {
   line: number
   confidence: number
   framework?: string
+  inTransaction: boolean
+  /** `file:line` where the enclosing transaction opens */
+  transactionId?: string
 }
 /** Sensitive field exposed to JavaScript */
 export interface JsSensitiveField {
   field: string
   table?: string
+  /** "pii", "credentials", "financial", "health" or a configured custom label */
   sensitivityType: string
   file: string
   line: number
@@ -182,6 +407,37 @@ export interface JsOrmModel {
   name: string
   tableName: string
   fields: Array<string>
+  relations: Array<JsModelRelation>
+  file: string
+  line: number
+  framework: string
+  confidence: number
+}
+/** Model relation exposed to JavaScript */
+export interface JsModelRelation {
+  field: string
+  target: string
+  /** "belongs-to", "has-one", "has-many" or "many-to-many" */
+  kind: string
+  isList: boolean
+  fields: Array<string>
+  references: Array<string>
+  /** Loaded whenever the model is */
+  eager: boolean
+}
+/** Model field missing from SQL migrations exposed to JavaScript */
+export interface JsUnmigratedField {
+  model: string
+  table: string
+  field: string
+  file: string
+  line: number
+}
+/** Outbound HTTP request exposed to JavaScript */
+export interface JsExternalAccessPoint {
+  urlOrHost: string
+  /** Uppercase HTTP method, or "UNKNOWN" */
+  method: string
   file: string
   line: number
   framework: string
@@ -192,16 +448,84 @@ export interface JsBoundaryScanResult {
   accessPoints: Array<JsDataAccessPoint>
   sensitiveFields: Array<JsSensitiveField>
   models: Array<JsOrmModel>
+  unmigratedFields: Array<JsUnmigratedField>
+  externalCalls: Array<JsExternalAccessPoint>
+  /**
+   * Writes outside any transaction in files that use transactions; only
+   * filled when `reportUntransactedWrites` is set
+   */
+  untransactedWrites: Array<JsDataAccessPoint>
   filesScanned: number
+  /** Files whose cached result was reused */
+  cacheHits: number
+  /** Files scanned afresh */
+  cacheMisses: number
   durationMs: number
 }
 /**
  * Scan files for data boundaries (data access points and sensitive fields)
  * Uses AST-first approach with regex fallbacks for SQL strings
  */
-export declare function scanBoundaries(files: Array<string>): JsBoundaryScanResult
+export declare function scanBoundaries(files: Array<string>, options?: JsBoundaryScanOptions | undefined | null): JsBoundaryScanResult
+/** Boundary scan options from JavaScript */
+export interface JsBoundaryScanOptions {
+  /** Report writes outside any transaction in files that use transactions */
+  reportUntransactedWrites?: boolean
+  /** Project root holding the `.drift/lake/boundaries/` cache (default: cwd) */
+  rootDir?: string
+  /** Rescan every file instead of reusing cached results */
+  noCache?: boolean
+}
+/** Tenant-scoped tables and their scoping column from JavaScript */
+export interface JsTenantScopingConfig {
+  tables: Array<string>
+  /** Scoping column ("tenant_id"); camelCase and PascalCase spellings match too */
+  column: string
+}
+/** Query on a tenant-scoped table missing the scoping column, exposed to JavaScript */
+export interface JsTenantViolation {
+  file: string
+  line: number
+  table: string
+  operation: string
+  /** The query or call chain that was checked */
+  evidence: string
+}
+/**
+ * Flag queries on tenant-scoped tables that don't filter by the scoping column
+ *
+ * Findings can be suppressed with a `drift-ignore tenant-scope` comment on
+ * the query or the line above it.
+ */
+export declare function checkTenantScoping(files: Array<string>, config: JsTenantScopingConfig): Array<JsTenantViolation>
+/**
+ * Sensitive field rule from JavaScript: a regex over source lines and field
+ * names, labelled with a built-in type or a custom one ("biometric")
+ */
+export interface JsSensitiveFieldRule {
+  pattern: string
+  sensitivity: string
+  /** Base confidence (default 0.8); matches below 0.5 are dropped */
+  confidence?: number
+  /** Drop the built-in matches this pattern overlaps instead of reporting it */
+  suppress?: boolean
+}
+/** Additions to the built-in sensitive field dictionary */
+export interface JsSensitiveFieldConfig {
+  rules: Array<JsSensitiveFieldRule>
+}
+/** Scan files for data boundaries, with configured sensitive field rules */
+export declare function scanBoundariesWithConfig(files: Array<string>, config: JsSensitiveFieldConfig, options?: JsBoundaryScanOptions | undefined | null): JsBoundaryScanResult
 /** Scan a single source string for boundaries using AST-first approach */
 export declare function scanBoundariesSource(source: string, filePath: string): JsBoundaryScanResult
+/** Types of one language within a module, exposed to JavaScript */
+export interface JsModuleLanguageMetrics {
+  language: string
+  files: number
+  types: number
+  abstractTypes: number
+  abstractness: number
+}
 /** Module metrics exposed to JavaScript */
 export interface JsModuleMetrics {
   path: string
@@ -211,6 +535,7 @@ export interface JsModuleMetrics {
   abstractness: number
   distance: number
   files: Array<string>
+  languages: Array<JsModuleLanguageMetrics>
 }
 /** Dependency cycle exposed to JavaScript */
 export interface JsDependencyCycle {
@@ -218,12 +543,28 @@ export interface JsDependencyCycle {
   severity: string
   filesAffected: number
 }
+/** Coupling edge weight exposed to JavaScript */
+export interface JsEdgeWeight {
+  module: string
+  /** "incoming" or "outgoing" */
+  direction: string
+  fileCount: number
+  symbolCount: number
+}
+/** Symbol imported from a hotspot, exposed to JavaScript */
+export interface JsHotSymbol {
+  name: string
+  fileCount: number
+}
 /** Coupling hotspot exposed to JavaScript */
 export interface JsCouplingHotspot {
   module: string
+  /** Importing files summed over incoming and outgoing edges */
   totalCoupling: number
   incoming: Array<string>
   outgoing: Array<string>
+  edgeWeights: Array<JsEdgeWeight>
+  hotSymbols: Array<JsHotSymbol>
 }
 /** Unused export exposed to JavaScript */
 export interface JsUnusedExport {
@@ -231,6 +572,23 @@ export interface JsUnusedExport {
   file: string
   line: number
   exportType: string
+  /** "unused", or "possibly-used" when only a computed dynamic import reaches it */
+  status: string
+  confidence: number
+}
+/** Module layer exposed to JavaScript */
+export interface JsModuleLayer {
+  module: string
+  /** 0 for modules importing no other analyzed module */
+  layer: number
+}
+/** Upward dependency edge exposed to JavaScript */
+export interface JsLayerViolation {
+  fromModule: string
+  toModule: string
+  fromLayer: number
+  toLayer: number
+  imports: Array<JsImportEvidence>
 }
 /** Coupling analysis result exposed to JavaScript */
 export interface JsCouplingResult {
@@ -241,9 +599,63 @@ export interface JsCouplingResult {
   healthScore: number
   filesAnalyzed: number
   durationMs: number
+  /** Inferred layer of each module, top layer first */
+  layers?: Array<JsModuleLayer>
+  /** Imports going upward against the inferred layering */
+  layerViolations?: Array<JsLayerViolation>
 }
 /** Analyze module coupling using AST-first approach */
+/** Dependency rule from JavaScript */
+export interface JsDependencyRule {
+  fromGlob: string
+  toGlob: string
+  /** "allow" or "forbid" */
+  kind: string
+}
+/** Import creating a dependency edge, exposed to JavaScript */
+export interface JsImportEvidence {
+  file: string
+  line: number
+  specifier: string
+}
+/** Dependency rule violation exposed to JavaScript */
+export interface JsDependencyViolation {
+  fromModule: string
+  toModule: string
+  rule: JsDependencyRule
+  imports: Array<JsImportEvidence>
+  cycle?: JsDependencyCycle
+}
+/** Module metric deltas exposed to JavaScript */
+export interface JsModuleDelta {
+  path: string
+  /** "added", "removed" or "changed" */
+  change: string
+  ca: number
+  ce: number
+  instability: number
+  distance: number
+}
+/** Coupling diff exposed to JavaScript */
+export interface JsCouplingDiff {
+  modules: Array<JsModuleDelta>
+  introducedCycles: Array<JsDependencyCycle>
+  resolvedCycles: Array<JsDependencyCycle>
+  healthScoreDelta: number
+  /** A cycle was introduced or the health score dropped */
+  isWorse: boolean
+}
 export declare function analyzeCoupling(files: Array<string>): JsCouplingResult
+/** Check module dependency edges against allow/forbid rules */
+export declare function checkDependencyRules(files: Array<string>, rules: Array<JsDependencyRule>): Array<JsDependencyViolation>
+/**
+ * Compare two serialized coupling results (old, then new)
+ *
+ * Accepts the JSON of an `analyzeCoupling` result or of the Rust
+ * `CouplingAnalysisResult`. Metric deltas smaller than `epsilon`
+ * (default 0.01) are ignored.
+ */
+export declare function diffCouplingResults(oldJson: string, newJson: string, epsilon?: number | undefined | null): JsCouplingDiff
 /** Test file exposed to JavaScript */
 export interface JsTestFile {
   path: string
@@ -251,6 +663,62 @@ export interface JsTestFile {
   framework: string
   testCount: number
   mockCount: number
+  testCases: Array<JsTestCase>
+  mocks: Array<JsMockInfo>
+  flakySignals: Array<JsFlakySignal>
+  /** Mean assertions per test case that isn't skipped */
+  assertionDensity: number
+  /** Tests that aren't skipped and assert nothing */
+  emptyTests: Array<string>
+  /** Tests asserting only through snapshot matchers */
+  snapshotOnlyTests: Array<string>
+}
+/** Structural sign of a flaky test exposed to JavaScript */
+export interface JsFlakySignal {
+  /** "retry", "sleep", "time_assertion" or "unmocked_network" */
+  kind: string
+  line: number
+  snippet: string
+}
+/** Mock or stub in a test file exposed to JavaScript */
+export interface JsMockInfo {
+  /** Test file the mock is in */
+  file: string
+  line: number
+  framework: string
+  /** Module path, patch target or class being mocked */
+  target: string
+  /** "function", "module", "class", "http", "database" or "unknown" */
+  mockType: string
+  /** File the target resolves to */
+  resolvedFile?: string
+}
+/** Test mocking the source file it is mapped to, exposed to JavaScript */
+export interface JsMockOverlap {
+  testFile: string
+  sourceFile: string
+  target: string
+  line: number
+}
+/** Test case exposed to JavaScript */
+export interface JsTestCase {
+  name: string
+  /** "unit", "integration", "e2e" or "unknown" */
+  testType: string
+  line: number
+  isSkipped: boolean
+  /** Focused with an `only` marker (`it.only`, `fit`, `describe.only`) */
+  isOnly: boolean
+  /** Parameter sets of a parametrized test, when listed literally */
+  parameterSets?: number
+  assertionCount: number
+  /** Asserts only through snapshot matchers (`toMatchSnapshot`) */
+  isSnapshotOnly: boolean
+}
+/** Inclusive 1-based line range exposed to JavaScript */
+export interface JsLineRange {
+  start: number
+  end: number
 }
 /** Test coverage exposed to JavaScript */
 export interface JsTestCoverage {
@@ -258,6 +726,17 @@ export interface JsTestCoverage {
   testFiles: Array<string>
   coveragePercent?: number
   riskLevel: string
+  /**
+   * "convention" (test file naming), "callgraph" (calls from tests) or
+   * "coverage" (only executed according to a coverage report)
+   */
+  mappingMethod: string
+  /** Distinct test functions touching the source file */
+  testFunctionCount: number
+  /** Lines a coverage report lists as never executed */
+  uncoveredLineRanges: Array<JsLineRange>
+  /** Factors raising the risk level ("untested", "fan-in of 12") */
+  riskFactors: Array<string>
 }
 /** Test topology result exposed to JavaScript */
 export interface JsTestTopologyResult {
@@ -266,11 +745,56 @@ export interface JsTestTopologyResult {
   uncoveredFiles: Array<string>
   totalTests: number
   skippedTests: number
+  /** Tests mocking the source files they cover */
+  mockOverlaps: Array<JsMockOverlap>
+  /** Coverage report paths matching none of the analyzed files */
+  unmatchedCoveragePaths: Array<string>
+  /** Flaky signals across test files */
+  flakySignalCount: number
   filesAnalyzed: number
   durationMs: number
 }
-/** Analyze test topology using AST-first approach */
-export declare function analyzeTestTopology(files: Array<string>): JsTestTopologyResult
+/** Rewrite of a coverage report path prefix (`/ci/build/` -> `/home/me/repo/`) */
+export interface JsCoveragePathPrefix {
+  from: string
+  to: string
+}
+/** Coverage reports to join onto the test topology */
+export interface JsCoverageOptions {
+  /** lcov, Cobertura XML or coverage.py JSON reports */
+  reports: Array<string>
+  /** Prefix rewrites tried before matching report paths to analyzed files */
+  pathPrefixes?: Array<JsCoveragePathPrefix>
+}
+/**
+ * Risk factor weights from JavaScript (defaults: coverage 0.4, fan-in 0.3,
+ * sensitive data 0.3, high fan-in 10 callers)
+ */
+export interface JsRiskOptions {
+  coverageWeight?: number
+  fanInWeight?: number
+  sensitiveDataWeight?: number
+  /** Callers from other source files at which fan-in scores fully */
+  highFanIn?: number
+}
+/** Flaky signal options from JavaScript */
+export interface JsFlakyOptions {
+  /**
+   * Signal kinds to leave out ("retry", "sleep", "time_assertion",
+   * "unmocked_network"), e.g. for teams retrying on purpose
+   */
+  suppress?: Array<string>
+}
+/**
+ * Analyze test topology using AST-first approach
+ *
+ * With `root_dir` and a call graph built under it, tests map to the source
+ * files whose functions they call; otherwise by test file naming convention.
+ * With `coverage` reports, coverage percent and uncovered lines come from
+ * the reports. Risk levels weigh coverage, fan-in and reachable sensitive
+ * data by `risk`; the latter two need the call graph.
+ */
+export declare function analyzeTestTopology(files: Array<string>, rootDir?: string | undefined | null, coverage?: JsCoverageOptions | undefined | null, risk?: JsRiskOptions | undefined | null, flaky?: JsFlakyOptions | undefined | null): JsTestTopologyResult
 /** Error boundary exposed to JavaScript */
 export interface JsErrorBoundary {
   file: string
@@ -281,6 +805,12 @@ export interface JsErrorBoundary {
   rethrows: boolean
   logsError: boolean
   isSwallowed: boolean
+  /** Framework of an error handler: express, koa, fastify or nestjs */
+  framework?: string
+  /** Routes an error handler covers: global, router or use_filters */
+  scope?: string
+  /** Router a router-scoped handler is registered on */
+  scopeRouter?: string
 }
 /** Error gap exposed to JavaScript */
 export interface JsErrorGap {
@@ -290,6 +820,13 @@ export interface JsErrorGap {
   gapType: string
   severity: string
   description: string
+  reachableWrites: Array<JsReachableWrite>
+}
+/** Data write or external call reachable from a try block, exposed to JavaScript */
+export interface JsReachableWrite {
+  function: string
+  table: string
+  operation: string
 }
 /** Error type exposed to JavaScript */
 export interface JsErrorType {
@@ -298,6 +835,12 @@ export interface JsErrorType {
   line: number
   extends?: string
   isExported: boolean
+  /** Error types directly extending this one */
+  children: Array<string>
+  /** Places constructing or raising the type */
+  throwCount: number
+  /** Neither the type nor any type below it is ever constructed */
+  isUnused: boolean
 }
 /** Error handling result exposed to JavaScript */
 export interface JsErrorHandlingResult {
@@ -307,8 +850,48 @@ export interface JsErrorHandlingResult {
   filesAnalyzed: number
   durationMs: number
 }
-/** Analyze error handling using AST-first approach */
-export declare function analyzeErrorHandling(files: Array<string>): JsErrorHandlingResult
+/**
+ * Analyze error handling using AST-first approach
+ *
+ * With `root_dir` and a call graph built under it, swallowed errors are
+ * critical when their try block reaches data writes or external calls, and
+ * Rust panics escalate where an entry point reaches them.
+ */
+export declare function analyzeErrorHandling(files: Array<string>, rootDir?: string | undefined | null): JsErrorHandlingResult
+/** Error coverage options */
+export interface JsErrorCoverageOptions {
+  /** Deepest call chain followed from an entry point (unlimited by default) */
+  maxDepth?: number
+  /** Skip entry points in test files */
+  excludeTests?: boolean
+}
+/** Step of an uncovered write path exposed to JavaScript */
+export interface JsPathStep {
+  function: string
+  line: number
+}
+/** Entry point with uncovered write paths exposed to JavaScript */
+export interface JsUncoveredEntryPoint {
+  entryPoint: string
+  file: string
+  line: number
+  uncoveredWrites: Array<JsReachableWrite>
+  examplePath: Array<JsPathStep>
+  gaps: Array<JsErrorGap>
+}
+/** Error coverage result exposed to JavaScript */
+export interface JsErrorCoverageResult {
+  entryPoints: number
+  coveredEntryPoints: number
+  uncovered: Array<JsUncoveredEntryPoint>
+  filesAnalyzed: number
+  durationMs: number
+}
+/**
+ * Report entry points with a path to a data write that no error boundary
+ * logging or rethrowing covers, with the shortest such path
+ */
+export declare function analyzeErrorCoverage(rootDir: string, options?: JsErrorCoverageOptions | undefined | null): JsErrorCoverageResult
 /** Code location exposed to JavaScript */
 export interface JsCodeLocation {
   file: string
@@ -332,8 +915,17 @@ export interface JsReachableDataAccess {
   line: number
   confidence: number
   framework?: string
+  /** A shortest call path to the access */
   path: Array<JsCallPathNode>
+  /** Distinct call paths, shortest first (`path` is the first) */
+  paths: Array<Array<JsCallPathNode>>
   depth: number
+  /** Confidence of the calls along `path` and of the access, multiplied */
+  pathConfidence: number
+  /** Whether `path` crosses an async boundary (job enqueue to handler) */
+  crossesAsync: boolean
+  /** For tables loaded by an eager ORM relation, the relation (`Post.author`) */
+  loadedVia?: string
 }
 /** Sensitive field access exposed to JavaScript */
 export interface JsSensitiveFieldAccess {
@@ -354,13 +946,52 @@ export interface JsReachabilityResult {
   sensitiveFields: Array<JsSensitiveFieldAccess>
   maxDepth: number
   functionsTraversed: number
+  /** Whether the result was served from the reachability cache */
+  cacheHit: boolean
+  /** Whether max_functions or timeout_ms stopped the traversal early */
+  truncated: boolean
+  /** "max-functions" or "timeout" when truncated */
+  truncationReason?: string
+  /** Unresolved calls followed to a candidate */
+  unresolvedEdgesTraversed: number
 }
 /** Reachability options from JavaScript */
 export interface JsReachabilityOptions {
   maxDepth?: number
   sensitiveOnly?: boolean
   tables?: Array<string>
+  /** Shorthand for unresolved_mode "pessimistic" (true) or "ignore" */
   includeUnresolved?: boolean
+  /**
+   * How to follow calls without a resolved target: "ignore",
+   * "pessimistic" or "unique-candidate"; overrides include_unresolved
+   */
+  unresolvedMode?: string
+  /**
+   * Call kinds to follow: "direct", "method", "callback", "dispatch",
+   * "async"; all but "async" when unset (SQLite-backed analysis only)
+   */
+  edgeKinds?: Array<string>
+  /**
+   * Also follow async calls from job enqueue sites to their handlers
+   * (SQLite-backed analysis only)
+   */
+  includeAsyncEdges?: boolean
+  /** Call paths to list per reachable access (default 1) */
+  maxPathsPerAccess?: number
+  /** Only access with these operations: "read", "write", "delete" */
+  operations?: Array<string>
+  /** Stop after visiting this many functions, returning a partial result */
+  maxFunctions?: number
+  /** Stop after this many milliseconds, returning a partial result */
+  timeoutMs?: number
+  /** Only access whose path_confidence is at least this */
+  minConfidence?: number
+  /**
+   * Reads of a model's table also reach the tables its eager ORM
+   * relations load (SQLite-backed analysis only)
+   */
+  expandEagerRelations?: boolean
 }
 /** Inverse access path exposed to JavaScript */
 export interface JsInverseAccessPath {
@@ -371,14 +1002,43 @@ export interface JsInverseAccessPath {
   accessFields: Array<string>
   accessFile: string
   accessLine: number
+  /** Confidence of the calls along `path` and of the access, multiplied */
+  pathConfidence: number
+}
+/** Inverse access paths from one entry point, summarized, exposed to JavaScript */
+export interface JsEntryPointAccessGroup {
+  entryPoint: string
+  operations: Array<string>
+  accessSites: number
+  pathCount: number
+  minDepth: number
+  maxDepth: number
+  /** Only filled when include_paths is set */
+  paths: Array<JsInverseAccessPath>
+  truncated: boolean
+}
+/** Inverse access paths into one table, summarized, exposed to JavaScript */
+export interface JsInverseTableGroup {
+  table: string
+  /** Fields matching the target field; empty without one */
+  fields: Array<string>
+  accessors: number
+  entryPoints: Array<string>
+  pathCount: number
 }
 /** Inverse reachability result exposed to JavaScript */
 export interface JsInverseReachabilityResult {
-  targetTable: string
+  targetTable?: string
   targetField?: string
   accessPaths: Array<JsInverseAccessPath>
+  /** Per entry point, when grouping by entry point */
+  groups: Array<JsEntryPointAccessGroup>
+  /** Per table reached, sorted by name */
+  tables: Array<JsInverseTableGroup>
   entryPoints: Array<string>
   totalAccessors: number
+  /** Whether any entry point had more paths than max_paths_per_entry_point */
+  truncated: boolean
 }
 /** Call graph function node from JavaScript */
 export interface JsCallGraphFunction {
@@ -398,6 +1058,8 @@ export interface JsCallGraphCallSite {
   resolved: boolean
   resolvedCandidates: Array<string>
   line: number
+  /** Resolution confidence (default 1.0) */
+  confidence?: number
 }
 /** Data access for call graph from JavaScript */
 export interface JsCallGraphDataAccess {
@@ -417,8 +1079,12 @@ export interface JsCallGraphInput {
 }
 /** Analyze reachability from a function */
 export declare function analyzeReachability(graphInput: JsCallGraphInput, functionId: string, options: JsReachabilityOptions): JsReachabilityResult
-/** Analyze inverse reachability - who can access this data? */
-export declare function analyzeInverseReachability(graphInput: JsCallGraphInput, table: string, field?: string | undefined | null, maxDepth?: number | undefined | null): JsInverseReachabilityResult
+/**
+ * Analyze inverse reachability - who can access this data?
+ * Without a table, every table with a matching field is searched; fields
+ * may use `*` wildcards (`*_token`)
+ */
+export declare function analyzeInverseReachability(graphInput: JsCallGraphInput, table?: string | undefined | null, field?: string | undefined | null, maxDepth?: number | undefined | null, operations?: Array<string> | undefined | null, groupByEntryPoint?: boolean | undefined | null, includePaths?: boolean | undefined | null, maxPathsPerEntryPoint?: number | undefined | null, minConfidence?: number | undefined | null): JsInverseReachabilityResult
 /**
  * Analyze reachability from a function using SQLite storage
  *
@@ -428,15 +1094,148 @@ export declare function analyzeInverseReachability(graphInput: JsCallGraphInput,
  * Requires: Call graph must be built first using build_call_graph()
  */
 export declare function analyzeReachabilitySqlite(rootDir: string, functionId: string, options: JsReachabilityOptions): JsReachabilityResult
+/** Reachability from one entry point, summarized, exposed to JavaScript */
+export interface JsEntryPointReachability {
+  entryPoint: string
+  name: string
+  file: string
+  tables: Array<string>
+  /** Distinct sensitive fields reached per sensitivity type ("pii", "credentials", ...) */
+  sensitiveFieldCounts: Record<string, number>
+  maxDepth: number
+  functionsTraversed: number
+}
+/**
+ * Analyze reachability from every entry point at once using SQLite storage
+ *
+ * Opens the database once and shares each function's access between
+ * traversals - much faster than calling analyze_reachability_sqlite() per
+ * entry point. Returns summaries keyed by entry point ID.
+ *
+ * Requires: Call graph must be built first using build_call_graph()
+ */
+export declare function analyzeReachabilityAllEntryPoints(rootDir: string, options: JsReachabilityOptions): Record<string, JsEntryPointReachability>
 /**
  * Analyze inverse reachability using SQLite storage - who can access this data?
  *
  * This queries the SQLite call graph database directly, avoiding the need
  * to load the entire call graph into memory. Recommended for large codebases.
+ * Field-only queries look fields up through an index.
+ *
+ * Requires: Call graph must be built first using build_call_graph()
+ */
+export declare function analyzeInverseReachabilitySqlite(rootDir: string, table?: string | undefined | null, field?: string | undefined | null, maxDepth?: number | undefined | null, operations?: Array<string> | undefined | null, groupByEntryPoint?: boolean | undefined | null, includePaths?: boolean | undefined | null, maxPathsPerEntryPoint?: number | undefined | null, minConfidence?: number | undefined | null): JsInverseReachabilityResult
+/** Entry point reaching both queried data and outbound HTTP calls, exposed to JavaScript */
+export interface JsExternalExposure {
+  entryPoint: string
+  hosts: Array<string>
+}
+/**
+ * Which entry points can send this data to a third-party host?
+ *
+ * Entry points that reach the target data and also reach an outbound HTTP
+ * call (to `host` when given). Reachability only: the data isn't traced
+ * into the request itself.
+ *
+ * Requires: Call graph must be built first using build_call_graph()
+ */
+export declare function analyzeExternalExposureSqlite(rootDir: string, table?: string | undefined | null, field?: string | undefined | null, host?: string | undefined | null, maxDepth?: number | undefined | null): Array<JsExternalExposure>
+/** Streamed inverse reachability options from JavaScript */
+export interface JsInverseStreamOptions {
+  maxDepth?: number
+  /** Only access with these operations: "read", "write", "delete" */
+  operations?: Array<string>
+  /** Summarize per entry point in the final result */
+  groupByEntryPoint?: boolean
+  /** Paths streamed per entry point; every path when unset */
+  maxPathsPerEntryPoint?: number
+  /** Only paths whose path_confidence is at least this */
+  minConfidence?: number
+  /** Paths per callback invocation (default 100) */
+  batchSize?: number
+}
+/**
+ * Analyze inverse reachability using SQLite storage, streaming access paths
+ * to a callback in batches
+ *
+ * For hot tables whose paths are too many to return at once. Paths reach
+ * the callback while the rest are still being searched; the returned promise
+ * resolves to the summaries (tables, entry points, groups) without paths.
+ *
+ * Requires: Call graph must be built first using build_call_graph()
+ */
+export declare function analyzeInverseReachabilityStream(rootDir: string, table: string | undefined | null, field: string | undefined | null, options: JsInverseStreamOptions | undefined | null, onPaths: (paths: Array<JsInverseAccessPath>) => void): Promise<JsInverseReachabilityResult>
+/** Function-to-function reachability options from JavaScript */
+export interface JsFunctionPathOptions {
+  maxDepth?: number
+  /** Stop counting paths at this many (default 100) */
+  maxPaths?: number
+  /**
+   * Call kinds to follow: "direct", "method", "callback", "dispatch",
+   * "async"; all but "async" when unset
+   */
+  edgeKinds?: Array<string>
+}
+/** Function-to-function reachability exposed to JavaScript */
+export interface JsFunctionReachability {
+  fromId: string
+  toId: string
+  reachable: boolean
+  shortestPath: Array<JsCallPathNode>
+  /** Distinct paths, counted up to `maxPaths` */
+  pathCount: number
+  minDepth?: number
+}
+/**
+ * Whether one function can reach another through resolved calls, and via
+ * which path, using SQLite storage
+ *
+ * Requires: Call graph must be built first using build_call_graph()
+ */
+export declare function analyzeFunctionReachability(rootDir: string, fromId: string, toId: string, options?: JsFunctionPathOptions | undefined | null): JsFunctionReachability
+/** Sensitivity matrix options from JavaScript */
+export interface JsSensitivityMatrixOptions {
+  maxDepth?: number
+  /**
+   * Call kinds to follow: "direct", "method", "callback", "dispatch",
+   * "async"; all but "async" when unset
+   */
+  edgeKinds?: Array<string>
+  /** Only these sensitivity types: "pii", "credentials", "financial", "health" */
+  sensitivityTypes?: Array<string>
+  /**
+   * Reads of a model's table also reach the fields of the models its
+   * eager ORM relations load
+   */
+  expandEagerRelations?: boolean
+}
+/** One entry point reaching one sensitive field, exposed to JavaScript */
+export interface JsSensitivityMatrixRow {
+  entryPoint: string
+  sensitivityType: string
+  table: string
+  field: string
+  operation: string
+  /** Shortest call paths from the entry point to the access */
+  pathCount: number
+  minDepth: number
+}
+/**
+ * Entry point x sensitive field matrix for compliance reports
+ *
+ * Rescans the call graph's files for sensitive fields and lists, for every
+ * entry point, each sensitive field it reaches per table and operation.
  *
  * Requires: Call graph must be built first using build_call_graph()
  */
-export declare function analyzeInverseReachabilitySqlite(rootDir: string, table: string, field?: string | undefined | null, maxDepth?: number | undefined | null): JsInverseReachabilityResult
+export declare function generateSensitivityMatrix(rootDir: string, options?: JsSensitivityMatrixOptions | undefined | null): Array<JsSensitivityMatrixRow>
+/**
+ * Drop the cached analyze_reachability_sqlite() results for a project
+ *
+ * Results are cached per call graph version, so this is only needed to
+ * free memory. Returns how many results were dropped.
+ */
+export declare function clearReachabilityCache(rootDir: string): number
 /** Check if SQLite call graph database exists and has data */
 export declare function isCallGraphAvailable(rootDir: string): boolean
 /** Call graph stats from SQLite database */
@@ -449,12 +1248,190 @@ export interface JsCallGraphStats {
 }
 /** Get call graph statistics from SQLite database */
 export declare function getCallGraphStats(rootDir: string): JsCallGraphStats
+/** Rows removed from the call graph database by `pruneCallGraph` */
+export interface JsPruneStats {
+  functions: number
+  calls: number
+  dataAccess: number
+  fileImports: number
+  types: number
+  diBindings: number
+  asyncHandlers: number
+  /** Calls from remaining files whose target was removed, now unresolved */
+  callsUnresolved: number
+  /** Why compacting the database failed; the rows are removed regardless */
+  vacuumError?: string
+}
+/**
+ * Remove call graph rows for files that no longer exist, then compact the database
+ *
+ * `files` lists the project files to keep (relative to `rootDir`); without it,
+ * every recorded file still present on disk is kept. Safe to run while
+ * readonly queries are open.
+ */
+export declare function pruneCallGraph(rootDir: string, files?: Array<string> | undefined | null): JsPruneStats
+/** Hotspot metrics for one function */
+export interface JsFunctionMetrics {
+  id: string
+  name: string
+  file: string
+  /** Distinct callers */
+  fanIn: number
+  /** Distinct callees */
+  fanOut: number
+  /** Fewest calls from an entry point; absent when none reaches it */
+  depth?: number
+}
+/** Top functions by each call graph metric */
+export interface JsGraphMetrics {
+  totalFunctions: number
+  maxDepth: number
+  topFanIn: Array<JsFunctionMetrics>
+  topFanOut: Array<JsFunctionMetrics>
+  topDepth: Array<JsFunctionMetrics>
+}
+/**
+ * Compute fan-in, fan-out and entry point depth for every function, store
+ * them in the database, and return the top `topN` (default: 20) by each
+ */
+export declare function computeGraphMetrics(rootDir: string, topN?: number | undefined | null): JsGraphMetrics
+/** Get call resolution statistics, broken down by strategy, from SQLite database */
+export declare function getCallGraphResolutionStats(rootDir: string): JsResolutionStats
+/** Subgraph selection for call graph export */
+export interface JsExportOptions {
+  /** Only functions reachable from this function ID */
+  rootId?: string
+  /** Maximum call depth from `rootId`, or from the entry points without one */
+  maxDepth?: number
+  /** Only entry points and data accessors, linked where one reaches another */
+  boundariesOnly?: boolean
+  /** Include unresolved calls as dashed edges (default: true) */
+  includeUnresolved?: boolean
+}
+/** Export the SQLite call graph as "dot", "json" or "mermaid" text */
+export declare function exportCallGraph(rootDir: string, format: string, options?: JsExportOptions | undefined | null): string
+/** Dead code detection options */
+export interface JsDeadCodeOptions {
+  /** Skip functions in test files */
+  excludeTests?: boolean
+  /** Skip functions whose name matches any of these regexes (e.g. "^migrate_") */
+  excludePatterns?: Array<string>
+  /** Path prefixes of library packages, whose exported functions are roots */
+  libraryPackages?: Array<string>
+}
+/** A function no entry point reaches */
+export interface JsUnreachableFunction {
+  id: string
+  name: string
+  file: string
+  line: number
+  isExported: boolean
+}
+/** Find functions unreachable from any entry point, sorted by file and line */
+export declare function findUnreachableFunctions(rootDir: string, options?: JsDeadCodeOptions | undefined | null): Array<JsUnreachableFunction>
+/** A function inside a recursion cycle */
+export interface JsCycleMember {
+  id: string
+  name: string
+  file: string
+}
+/** Strongly connected component of the call graph */
+export interface JsCallCycle {
+  functions: Array<JsCycleMember>
+  /** Distinct caller -> callee pairs between members */
+  internalEdges: number
+}
+/** Find recursion cycles with at least `minSize` functions (default: 2), largest first */
+export declare function getCallGraphCycles(rootDir: string, minSize?: number | undefined | null): Array<JsCallCycle>
+/** Function search filters */
+export interface JsFunctionSearchOptions {
+  /** Maximum results (default: 50) */
+  limit?: number
+  /** Only functions in files matching this glob */
+  fileGlob?: string
+  /** Only exported functions */
+  exportedOnly?: boolean
+}
+/** A function found by name */
+export interface JsFunctionMatch {
+  id: string
+  name: string
+  file: string
+  startLine: number
+  endLine: number
+  isEntryPoint: boolean
+  isExported: boolean
+  /** "exact" | "prefix" | "substring" */
+  matchKind: string
+  /** Calls this function makes */
+  outgoingCalls: number
+  /** Resolved calls to this function */
+  incomingCalls: number
+}
+/** Find functions by name (with or without their class), best matches first */
+export declare function findFunctions(rootDir: string, query: string, options?: JsFunctionSearchOptions | undefined | null): Array<JsFunctionMatch>
+/** Unresolved call filters */
+export interface JsUnresolvedCallOptions {
+  /** Only calls made from files matching this glob */
+  fileGlob?: string
+  /** Only calls whose callee name matches this regex */
+  calleePattern?: string
+  /** Maximum results */
+  limit?: number
+}
+/** A call site without a resolved target */
+export interface JsUnresolvedCall {
+  callerId: string
+  file: string
+  line: number
+  callee: string
+  receiver?: string
+  receiverType?: string
+  /** "no-candidates" | "receiver-type-mismatch" | "pending" */
+  reason: string
+  /** Functions sharing the callee's method name that were rejected, nearest first */
+  candidates: Array<string>
+}
+/** List calls resolution left without a target, with rejected candidates */
+export declare function getUnresolvedCalls(rootDir: string, options?: JsUnresolvedCallOptions | undefined | null): Array<JsUnresolvedCall>
+/** A function in a call graph diff */
+export interface JsDiffFunction {
+  /** "file:qualified_name" */
+  key: string
+  file: string
+  name: string
+  line: number
+}
+/** A call added or removed between two builds */
+export interface JsDiffEdge {
+  caller: string
+  callee: string
+}
+/** A data accessor newly reachable from an entry point */
+export interface JsDiffAccessor {
+  function: JsDiffFunction
+  tables: Array<string>
+}
+/** Differences between two call graph builds */
+export interface JsCallGraphDiff {
+  addedFunctions: Array<JsDiffFunction>
+  removedFunctions: Array<JsDiffFunction>
+  addedEdges: Array<JsDiffEdge>
+  removedEdges: Array<JsDiffEdge>
+  newlyReachableAccessors: Array<JsDiffAccessor>
+  addedEntryPoints: Array<JsDiffFunction>
+  removedEntryPoints: Array<JsDiffFunction>
+}
+/** Compare two call graph databases (e.g. built from a PR's base and head) */
+export declare function diffCallGraphs(baseDbPath: string, headDbPath: string): JsCallGraphDiff
 /** Entry point info from SQLite database */
 export interface JsEntryPointInfo {
   id: string
   name: string
   file: string
   line: number
+  /** Framework convention or entry point rule name that made this an entry point ("nextjs-route", "route", ...) */
+  entryKind?: string
 }
 /** Get all entry points from SQLite call graph */
 export declare function getCallGraphEntryPoints(rootDir: string): Array<JsEntryPointInfo>
@@ -502,6 +1479,10 @@ export interface JsDetectedPattern {
   matchedText: string
   confidence: number
   detectionMethod: string
+  /** Severity of a custom rule match: error, warning, info or hint */
+  severity?: string
+  /** Message of a custom rule match */
+  message?: string
 }
 /** File patterns exposed to JavaScript */
 export interface JsFilePatterns {
@@ -519,6 +1500,14 @@ export interface JsResolutionStats {
   sameFileResolutions: number
   crossFileResolutions: number
   unresolvedCalls: number
+  /** Resolved calls per resolution strategy */
+  byStrategy: Array<JsStrategyCount>
+}
+/** Resolved call count for one resolution strategy */
+export interface JsStrategyCount {
+  /** "receiver-type", "import", "same-file", "same-directory", ... */
+  strategy: string
+  count: number
 }
 /** Call graph summary exposed to JavaScript */
 export interface JsCallGraphSummary {
@@ -535,6 +1524,11 @@ export interface JsAnalysisMetrics {
   detectTimeMs: number
   resolveTimeMs: number
   totalTimeMs: number
+  /** Files whose cached analysis was reused */
+  cacheHits: number
+  /** Files analyzed afresh */
+  cacheMisses: number
+  cacheHitRatio: number
 }
 /** Unified analysis result exposed to JavaScript */
 export interface JsUnifiedResult {
@@ -544,6 +1538,77 @@ export interface JsUnifiedResult {
   metrics: JsAnalysisMetrics
   totalPatterns: number
   totalViolations: number
+  /** Violations of custom rules that no comment suppresses */
+  violations: Array<JsViolation>
+  /** Violations suppressed by comments, counted apart from the total */
+  suppressed: JsSuppressionStats
+  /** Custom rules skipped as invalid */
+  ruleErrors: Array<JsRuleError>
+  /** How violations compared to the baseline, when one was given */
+  baseline?: JsBaselineReport
+}
+/** Violations compared to a baseline, exposed to JavaScript */
+export interface JsBaselineReport {
+  baselined: number
+  /** Violations the baseline doesn't know */
+  newViolations: number
+  /** Baselined violations no longer reported */
+  fixed: Array<JsBaselineEntry>
+}
+/** Violation recorded in a baseline, exposed to JavaScript */
+export interface JsBaselineEntry {
+  fingerprint: string
+  ruleId: string
+  file: string
+  /** Where the violation was when recorded */
+  line: number
+}
+/** Pattern violation exposed to JavaScript */
+export interface JsViolation {
+  id: string
+  ruleId: string
+  /** error, warning, info or hint */
+  severity: string
+  message: string
+  file: string
+  line: number
+  column: number
+  /** Type of the pattern that triggered the violation */
+  patternId: string
+  expected: string
+  /** The matched code */
+  actual: string
+  suggestedFix?: string
+  /** Already recorded in the baseline, so not new */
+  baselined: boolean
+}
+/** Violations hidden by `drift-ignore` comments, exposed to JavaScript */
+export interface JsSuppressionStats {
+  inline: number
+  fileLevel: number
+  /** Files whose `drift-ignore-file` comment hid violations */
+  files: Array<string>
+}
+/** Custom rule that couldn't be compiled, exposed to JavaScript */
+export interface JsRuleError {
+  rule: string
+  /** Language whose grammar rejected the query */
+  language?: string
+  message: string
+}
+/** User-defined pattern rule from JavaScript */
+export interface JsCustomRule {
+  /** Rule id, reported as the pattern type */
+  id: string
+  category: string
+  /** "query" (tree-sitter) or "regex" */
+  kind: string
+  pattern: string
+  /** error, warning (default), info or hint */
+  severity?: string
+  message: string
+  /** Languages the rule applies to (default: all) */
+  languages?: Array<string>
 }
 /** Unified analysis options from JavaScript */
 export interface JsUnifiedOptions {
@@ -552,6 +1617,18 @@ export interface JsUnifiedOptions {
   maxResolutionDepth?: number
   parallel?: boolean
   threads?: number
+  includeGenerated?: boolean
+  /** Report custom rule matches as violations (default: true) */
+  includeViolations?: boolean
+  /** House patterns to detect on top of the built-in ones */
+  customRules?: Array<JsCustomRule>
+  /** Reanalyze every file instead of reusing cached results (default: false) */
+  force?: boolean
+  /**
+   * Baseline from `generateUnifiedBaseline`; violations it records are
+   * marked `baselined`
+   */
+  baseline?: string
 }
 /**
  * Analyze a codebase with unified pattern detection and resolution
@@ -560,6 +1637,27 @@ export interface JsUnifiedOptions {
  * Combines pattern detection and call resolution in a single pass.
  */
 export declare function analyzeUnified(root: string, options: JsUnifiedOptions): JsUnifiedResult
+/** SARIF export options from JavaScript */
+export interface JsSarifOptions {
+  /** Analysis options, when exporting a root rather than a result */
+  analysis?: JsUnifiedOptions
+  /** Report detected patterns as notes, not only violations */
+  includePatterns?: boolean
+  /** Absolute URI of the analyzed root, which file paths are relative to */
+  srcRoot?: string
+  pretty?: boolean
+}
+/**
+ * Export unified analysis results as SARIF 2.1.0
+ *
+ * Takes a result from `analyzeUnified`, or a root to analyze first.
+ */
+export declare function exportUnifiedSarif(resultOrRoot: JsUnifiedResult | string, options?: JsSarifOptions | undefined | null): string
+/**
+ * Record the violations of a result `analyzeUnified` returned as a
+ * baseline at `path`, returning how many were recorded
+ */
+export declare function generateUnifiedBaseline(result: JsUnifiedResult, path: string): number
 /** Constant info exposed to JavaScript */
 export interface JsConstantInfo {
   name: string
@@ -310,9 +310,12 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { scan, parse, supportedLanguages, version, buildCallGraph, buildCallGraphLegacy, scanBoundaries, scanBoundariesSource, analyzeCoupling, analyzeTestTopology, analyzeErrorHandling, analyzeReachability, analyzeInverseReachability, analyzeReachabilitySqlite, analyzeInverseReachabilitySqlite, isCallGraphAvailable, getCallGraphStats, getCallGraphEntryPoints, getCallGraphDataAccessors, getCallGraphCallers, getCallGraphFileCallers, analyzeUnified, analyzeConstants, analyzeEnvironment, analyzeWrappers } = nativeBinding
+const { scan, scanIncremental, scanWithProgress, scanStreaming, parse, supportedLanguages, version, buildCallGraph, buildCallGraphLegacy, scanBoundaries, scanBoundariesSource, analyzeCoupling, analyzeTestTopology, analyzeErrorHandling, analyzeReachability, analyzeInverseReachability, analyzeReachabilitySqlite, analyzeInverseReachabilitySqlite, isCallGraphAvailable, getCallGraphStats, getCallGraphEntryPoints, getCallGraphDataAccessors, getCallGraphCallers, getCallGraphFileCallers, analyzeUnified, analyzeConstants, analyzeEnvironment, analyzeWrappers, parseFiles, parseFilesAsync, queryAst, buildCallGraphIncremental, buildCallGraphAsync, buildCallGraphWithProgress, getCallGraphResolutionStats, exportCallGraph, findUnreachableFunctions, getCallGraphCycles, pruneCallGraph, findFunctions, diffCallGraphs, migrateCallGraphToSqlite, computeGraphMetrics, getUnresolvedCalls, analyzeFunctionReachability, analyzeReachabilityAllEntryPoints, generateSensitivityMatrix, clearReachabilityCache, analyzeInverseReachabilityStream, scanBoundariesWithConfig, analyzeExternalExposureSqlite, checkTenantScoping, diffCouplingResults, checkDependencyRules, analyzeErrorCoverage, exportUnifiedSarif, generateUnifiedBaseline } = nativeBinding
 
 module.exports.scan = scan
+module.exports.scanIncremental = scanIncremental
+module.exports.scanWithProgress = scanWithProgress
+module.exports.scanStreaming = scanStreaming
 module.exports.parse = parse
 module.exports.supportedLanguages = supportedLanguages
 module.exports.version = version
@@ -337,3 +340,32 @@ module.exports.analyzeUnified = analyzeUnified
 module.exports.analyzeConstants = analyzeConstants
 module.exports.analyzeEnvironment = analyzeEnvironment
 module.exports.analyzeWrappers = analyzeWrappers
+module.exports.parseFiles = parseFiles
+module.exports.parseFilesAsync = parseFilesAsync
+module.exports.queryAst = queryAst
+module.exports.buildCallGraphIncremental = buildCallGraphIncremental
+module.exports.buildCallGraphAsync = buildCallGraphAsync
+module.exports.buildCallGraphWithProgress = buildCallGraphWithProgress
+module.exports.getCallGraphResolutionStats = getCallGraphResolutionStats
+module.exports.exportCallGraph = exportCallGraph
+module.exports.findUnreachableFunctions = findUnreachableFunctions
+module.exports.getCallGraphCycles = getCallGraphCycles
+module.exports.pruneCallGraph = pruneCallGraph
+module.exports.findFunctions = findFunctions
+module.exports.diffCallGraphs = diffCallGraphs
+module.exports.migrateCallGraphToSqlite = migrateCallGraphToSqlite
+module.exports.computeGraphMetrics = computeGraphMetrics
+module.exports.getUnresolvedCalls = getUnresolvedCalls
+module.exports.analyzeFunctionReachability = analyzeFunctionReachability
+module.exports.analyzeReachabilityAllEntryPoints = analyzeReachabilityAllEntryPoints
+module.exports.generateSensitivityMatrix = generateSensitivityMatrix
+module.exports.clearReachabilityCache = clearReachabilityCache
+module.exports.analyzeInverseReachabilityStream = analyzeInverseReachabilityStream
+module.exports.scanBoundariesWithConfig = scanBoundariesWithConfig
+module.exports.analyzeExternalExposureSqlite = analyzeExternalExposureSqlite
+module.exports.checkTenantScoping = checkTenantScoping
+module.exports.diffCouplingResults = diffCouplingResults
+module.exports.checkDependencyRules = checkDependencyRules
+module.exports.analyzeErrorCoverage = analyzeErrorCoverage
+module.exports.exportUnifiedSarif = exportUnifiedSarif
+module.exports.generateUnifiedBaseline = generateUnifiedBaseline
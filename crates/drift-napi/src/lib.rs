@@ -3,18 +3,29 @@
 //! This crate exposes drift-core functionality to Node.js via napi-rs.
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction, Task};
 use napi_derive::napi;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use drift_core::scanner::{ScanConfig, Scanner};
-use drift_core::parsers::ParserManager;
-use drift_core::call_graph::{StreamingBuilder, BuilderConfig};
-use drift_core::boundaries::{BoundaryScanner, DataOperation, SensitivityType};
+use drift_core::scanner::{
+    ChangeStatus, FileInfo, HashAlgorithm, ScanConfig, ScanResult, ScanStats, Scanner,
+};
+use drift_core::parsers::{FunctionInfo, Language, ParseResult, ParserManager};
+use drift_core::call_graph::{BuildResult, StreamingBuilder, BuilderConfig, EntryPointRule, AsyncEdgeRule, CallKind};
+use drift_core::boundaries::{
+    BoundaryScanner, BoundaryScanResult, DataAccessPoint, DataOperation, ExternalAccessPoint, SensitiveField,
+    SensitiveFieldConfig, SensitiveFieldRule, TenantScopingConfig,
+};
 use drift_core::reachability::{
-    ReachabilityEngine, ReachabilityOptions, InverseReachabilityOptions,
+    ReachabilityEngine, ReachabilityOptions, InverseReachabilityOptions, FunctionPathOptions,
+    InverseAccessPath, InverseReachabilityResult, SensitivityMatrixOptions,
+    SensitivityType as ReachSensitivityType, ReachabilityCache,
     CallGraph as ReachCallGraph, FunctionNode, CallSite as ReachCallSite,
     DataAccessPoint as ReachDataAccessPoint, DataOperation as ReachDataOperation,
-    SqliteReachabilityEngine,
+    SqliteReachabilityEngine, UnresolvedMode, EagerRelation, scan_eager_relations,
 };
 
 // ============================================================================
@@ -26,6 +37,8 @@ use drift_core::reachability::{
 pub struct JsScanResult {
     pub root: String,
     pub files: Vec<JsFileInfo>,
+    pub deleted_files: Vec<JsFileInfo>,
+    pub hash_algorithm: Option<String>,
     pub stats: JsScanStats,
     pub errors: Vec<String>,
 }
@@ -37,6 +50,11 @@ pub struct JsFileInfo {
     pub size: i64,
     pub hash: Option<String>,
     pub language: Option<String>,
+    pub mtime: Option<i64>,
+    pub change_status: Option<String>,
+    pub lines: i64,
+    pub is_minified: bool,
+    pub is_generated: bool,
 }
 
 /// Scan stats exposed to JavaScript
@@ -44,11 +62,31 @@ pub struct JsFileInfo {
 pub struct JsScanStats {
     pub total_files: i64,
     pub total_bytes: i64,
+    pub by_language: Vec<JsLanguageBreakdown>,
     pub dirs_skipped: i64,
     pub files_skipped: i64,
+    pub changed_files: i64,
+    pub skipped_by_source: Vec<JsIgnoreSourceStats>,
     pub duration_ms: i64,
 }
 
+/// Files, bytes and lines of one language, exposed to JavaScript
+#[napi(object)]
+pub struct JsLanguageBreakdown {
+    pub language: String,
+    pub files: i64,
+    pub bytes: i64,
+    pub lines: i64,
+}
+
+/// Files/directories skipped by one ignore source, exposed to JavaScript
+#[napi(object)]
+pub struct JsIgnoreSourceStats {
+    pub source: String,
+    pub files_skipped: i64,
+    pub dirs_skipped: i64,
+}
+
 /// Scan configuration from JavaScript
 #[napi(object)]
 pub struct JsScanConfig {
@@ -56,8 +94,25 @@ pub struct JsScanConfig {
     pub patterns: Vec<String>,
     pub extra_ignores: Option<Vec<String>>,
     pub compute_hashes: Option<bool>,
+    pub hash_algorithm: Option<String>,
     pub max_file_size: Option<i64>,
     pub threads: Option<i64>,
+    pub follow_symlinks: Option<bool>,
+    pub respect_gitignore: Option<bool>,
+    pub progress_interval: Option<i64>,
+    pub max_depth: Option<i64>,
+    pub max_files_per_dir: Option<i64>,
+}
+
+/// Scan progress event exposed to JavaScript
+#[napi(object)]
+pub struct JsScanProgress {
+    pub files_seen: i64,
+    pub total_files: i64,
+    pub bytes_read: i64,
+    pub current_dir: String,
+    pub dirs_skipped: i64,
+    pub files_skipped: i64,
 }
 
 // ============================================================================
@@ -70,10 +125,13 @@ pub struct JsParseResult {
     pub language: String,
     pub functions: Vec<JsFunctionInfo>,
     pub classes: Vec<JsClassInfo>,
+    pub enums: Vec<JsEnumInfo>,
     pub imports: Vec<JsImportInfo>,
     pub exports: Vec<JsExportInfo>,
     pub calls: Vec<JsCallSite>,
     pub errors: Vec<JsParseError>,
+    /// Syntax error regions tree-sitter recovered from (0 for a clean parse)
+    pub error_node_count: i64,
     pub parse_time_us: i64,
 }
 
@@ -95,6 +153,9 @@ pub struct JsFunctionInfo {
     pub return_type: Option<String>,
     pub is_exported: bool,
     pub is_async: bool,
+    pub visibility: String,
+    pub is_static: bool,
+    pub type_parameters: Vec<String>,
     pub start_line: i64,
     pub end_line: i64,
     pub decorators: Vec<String>,
@@ -112,6 +173,10 @@ pub struct JsClassInfo {
     pub end_line: i64,
     pub decorators: Vec<String>,
     pub properties: Vec<JsPropertyInfo>,
+    pub methods: Vec<JsFunctionInfo>,
+    pub namespace: Option<String>,
+    pub is_partial: bool,
+    pub type_parameters: Vec<String>,
 }
 
 /// Property info exposed to JavaScript (for struct fields, class properties)
@@ -123,6 +188,8 @@ pub struct JsPropertyInfo {
     pub is_readonly: bool,
     pub visibility: String,
     pub tags: Option<Vec<JsStructTag>>,
+    pub is_computed: bool,
+    pub decorators: Vec<String>,
 }
 
 /// Struct tag exposed to JavaScript (for Go struct field tags)
@@ -132,6 +199,25 @@ pub struct JsStructTag {
     pub value: String,
 }
 
+/// Enum (or literal union) info exposed to JavaScript
+#[napi(object)]
+pub struct JsEnumInfo {
+    pub name: String,
+    pub variants: Vec<JsEnumVariant>,
+    pub is_exported: bool,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub decorators: Vec<String>,
+}
+
+/// Enum member exposed to JavaScript
+#[napi(object)]
+pub struct JsEnumVariant {
+    pub name: String,
+    pub value: Option<String>,
+    pub line: i64,
+}
+
 /// Import info exposed to JavaScript
 #[napi(object)]
 pub struct JsImportInfo {
@@ -140,6 +226,7 @@ pub struct JsImportInfo {
     pub default: Option<String>,
     pub namespace: Option<String>,
     pub is_type_only: bool,
+    pub relative_level: i64,
     pub line: i64,
 }
 
@@ -159,6 +246,7 @@ pub struct JsCallSite {
     pub receiver: Option<String>,
     pub arg_count: i64,
     pub line: i64,
+    pub string_arg: Option<String>,
 }
 
 /// Parse error exposed to JavaScript
@@ -166,6 +254,8 @@ pub struct JsCallSite {
 pub struct JsParseError {
     pub message: String,
     pub line: i64,
+    pub end_line: i64,
+    pub snippet: Option<String>,
 }
 
 // ============================================================================
@@ -175,35 +265,208 @@ pub struct JsParseError {
 /// Scan a directory for source files
 #[napi]
 pub fn scan(config: JsScanConfig) -> Result<JsScanResult> {
-    let rust_config = ScanConfig {
+    let scanner = Scanner::new(to_scan_config(config)?);
+    let result = scanner.scan();
+    
+    Ok(to_js_scan_result(result))
+}
+
+/// Incrementally scan a directory using the persisted scan cache
+/// 
+/// Only files whose size or mtime changed since the last cached scan are
+/// re-hashed. Each file carries a change status (added/modified/unchanged),
+/// deleted files are reported separately, and the cache is updated afterwards.
+#[napi]
+pub fn scan_incremental(config: JsScanConfig) -> Result<JsScanResult> {
+    let scanner = Scanner::new(to_scan_config(config)?);
+    let result = scanner.scan_cached();
+    
+    Ok(to_js_scan_result(result))
+}
+
+/// Background scan task that reports progress through a threadsafe function
+pub struct ScanTask {
+    config: Option<ScanConfig>,
+}
+
+impl Task for ScanTask {
+    type Output = ScanResult;
+    type JsValue = JsScanResult;
+    
+    fn compute(&mut self) -> Result<Self::Output> {
+        let config = self.config.take()
+            .ok_or_else(|| napi::Error::from_reason("Scan task already run"))?;
+        Ok(Scanner::new(config).scan())
+    }
+    
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(to_js_scan_result(output))
+    }
+}
+
+/// Scan a directory for source files, reporting progress via callback
+/// 
+/// The scan runs on a worker thread so the callback can fire while it is
+/// in progress. It is invoked every `progressInterval` files (default 100).
+#[napi(ts_args_type = "config: JsScanConfig, callback: (progress: JsScanProgress) => void")]
+pub fn scan_with_progress(config: JsScanConfig, callback: JsFunction) -> Result<AsyncTask<ScanTask>> {
+    let tsfn: ThreadsafeFunction<JsScanProgress, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    
+    let mut rust_config = to_scan_config(config)?;
+    rust_config.on_progress = Some(Arc::new(move |p| {
+        tsfn.call(JsScanProgress {
+            files_seen: p.files_seen as i64,
+            total_files: p.total_files as i64,
+            bytes_read: p.bytes_read as i64,
+            current_dir: p.current_dir,
+            dirs_skipped: p.dirs_skipped as i64,
+            files_skipped: p.files_skipped as i64,
+        }, ThreadsafeFunctionCallMode::NonBlocking);
+    }));
+    
+    Ok(AsyncTask::new(ScanTask { config: Some(rust_config) }))
+}
+
+/// Number of files per `scanStreaming` batch
+const STREAM_BATCH_SIZE: usize = 1000;
+
+/// Background scan task that streams files to JavaScript in batches
+pub struct StreamingScanTask {
+    config: Option<ScanConfig>,
+    on_batch: ThreadsafeFunction<Vec<JsFileInfo>, ErrorStrategy::Fatal>,
+}
+
+impl Task for StreamingScanTask {
+    type Output = ScanStats;
+    type JsValue = JsScanStats;
+    
+    fn compute(&mut self) -> Result<Self::Output> {
+        let config = self.config.take()
+            .ok_or_else(|| napi::Error::from_reason("Scan task already run"))?;
+        
+        let mut batch = Vec::with_capacity(STREAM_BATCH_SIZE);
+        let result = Scanner::new(config).scan_with(|info| {
+            batch.push(to_js_file_info(info));
+            if batch.len() == STREAM_BATCH_SIZE {
+                let full = std::mem::replace(&mut batch, Vec::with_capacity(STREAM_BATCH_SIZE));
+                self.on_batch.call(full, ThreadsafeFunctionCallMode::Blocking);
+            }
+        });
+        if !batch.is_empty() {
+            self.on_batch.call(batch, ThreadsafeFunctionCallMode::Blocking);
+        }
+        
+        Ok(result.stats)
+    }
+    
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(to_js_scan_stats(output))
+    }
+}
+
+/// Scan a directory, streaming files to a callback in batches
+/// 
+/// Avoids materializing the full file list for very large repositories.
+/// The callback receives arrays of up to 1000 files; the returned promise
+/// resolves to the final scan stats.
+#[napi(ts_args_type = "config: JsScanConfig, onBatch: (files: Array<JsFileInfo>) => void")]
+pub fn scan_streaming(config: JsScanConfig, on_batch: JsFunction) -> Result<AsyncTask<StreamingScanTask>> {
+    // A bounded queue applies backpressure if JavaScript falls behind
+    let on_batch: ThreadsafeFunction<Vec<JsFileInfo>, ErrorStrategy::Fatal> = on_batch
+        .create_threadsafe_function(4, |ctx| Ok(vec![ctx.value]))?;
+    
+    Ok(AsyncTask::new(StreamingScanTask {
+        config: Some(to_scan_config(config)?),
+        on_batch,
+    }))
+}
+
+fn to_scan_config(config: JsScanConfig) -> Result<ScanConfig> {
+    let hash_algorithm = match config.hash_algorithm {
+        Some(name) => HashAlgorithm::from_name(&name)
+            .ok_or_else(|| napi::Error::from_reason(format!("Unsupported hash algorithm: {}", name)))?,
+        None => HashAlgorithm::default(),
+    };
+    
+    Ok(ScanConfig {
         root: PathBuf::from(&config.root),
         patterns: config.patterns,
         extra_ignores: config.extra_ignores.unwrap_or_default(),
         compute_hashes: config.compute_hashes.unwrap_or(true),
+        hash_algorithm,
         max_file_size: config.max_file_size.unwrap_or(10 * 1024 * 1024) as u64,
         threads: config.threads.unwrap_or(0) as usize,
-    };
-    
-    let scanner = Scanner::new(rust_config);
-    let result = scanner.scan();
-    
-    Ok(JsScanResult {
-        root: result.root,
-        files: result.files.into_iter().map(|f| JsFileInfo {
-            path: f.path,
-            size: f.size as i64,
-            hash: f.hash,
-            language: f.language,
+        follow_symlinks: config.follow_symlinks.unwrap_or(false),
+        respect_gitignore: config.respect_gitignore.unwrap_or(true),
+        on_progress: None,
+        progress_interval: config.progress_interval.unwrap_or(100) as usize,
+        max_depth: to_scan_limit("maxDepth", config.max_depth)?,
+        max_files_per_dir: to_scan_limit("maxFilesPerDir", config.max_files_per_dir)?,
+    })
+}
+
+/// A scan limit from JavaScript, rejecting negative values instead of
+/// letting them wrap into no limit at all
+fn to_scan_limit(name: &str, value: Option<i64>) -> Result<Option<usize>> {
+    value
+        .map(|n| usize::try_from(n).map_err(|_| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("{} must not be negative, got {}", name, n),
+        )))
+        .transpose()
+}
+
+fn to_js_file_info(f: FileInfo) -> JsFileInfo {
+    JsFileInfo {
+        path: f.path,
+        size: f.size as i64,
+        hash: f.hash,
+        language: f.language,
+        mtime: f.mtime.map(|m| m as i64),
+        change_status: f.change_status.map(|s| match s {
+            ChangeStatus::Added => "added".to_string(),
+            ChangeStatus::Modified => "modified".to_string(),
+            ChangeStatus::Unchanged => "unchanged".to_string(),
+            ChangeStatus::Deleted => "deleted".to_string(),
+        }),
+        lines: f.lines as i64,
+        is_minified: f.is_minified,
+        is_generated: f.is_generated,
+    }
+}
+
+fn to_js_scan_stats(stats: ScanStats) -> JsScanStats {
+    JsScanStats {
+        total_files: stats.total_files as i64,
+        total_bytes: stats.total_bytes as i64,
+        by_language: stats.by_language.into_iter().map(|(language, l)| JsLanguageBreakdown {
+            language,
+            files: l.files as i64,
+            bytes: l.bytes as i64,
+            lines: l.lines as i64,
         }).collect(),
-        stats: JsScanStats {
-            total_files: result.stats.total_files as i64,
-            total_bytes: result.stats.total_bytes as i64,
-            dirs_skipped: result.stats.dirs_skipped as i64,
-            files_skipped: result.stats.files_skipped as i64,
-            duration_ms: result.stats.duration.as_millis() as i64,
-        },
+        dirs_skipped: stats.dirs_skipped as i64,
+        files_skipped: stats.files_skipped as i64,
+        changed_files: stats.changed_files as i64,
+        skipped_by_source: stats.skipped_by_source.into_iter().map(|(source, s)| JsIgnoreSourceStats {
+            source,
+            files_skipped: s.files_skipped as i64,
+            dirs_skipped: s.dirs_skipped as i64,
+        }).collect(),
+        duration_ms: stats.duration.as_millis() as i64,
+    }
+}
+
+fn to_js_scan_result(result: ScanResult) -> JsScanResult {
+    JsScanResult {
+        root: result.root,
+        files: result.files.into_iter().map(to_js_file_info).collect(),
+        deleted_files: result.deleted_files.into_iter().map(to_js_file_info).collect(),
+        hash_algorithm: result.hash_algorithm.map(|a| a.as_str().to_string()),
+        stats: to_js_scan_stats(result.stats),
         errors: result.errors,
-    })
+    }
 }
 
 // ============================================================================
@@ -211,8 +474,13 @@ pub fn scan(config: JsScanConfig) -> Result<JsScanResult> {
 // ============================================================================
 
 /// Parse source code and extract functions, classes, imports, exports, and calls
+/// 
+/// The language is inferred from the file extension (or shebang) unless
+/// `language` is given explicitly, e.g. "python" or "typescript".
 #[napi]
-pub fn parse(source: String, file_path: String) -> Result<Option<JsParseResult>> {
+pub fn parse(source: String, file_path: String, language: Option<String>) -> Result<Option<JsParseResult>> {
+    let language = to_language(language)?;
+    
     // Use thread-local parser manager for better performance
     thread_local! {
         static PARSER_MANAGER: std::cell::RefCell<ParserManager> = 
@@ -221,81 +489,232 @@ pub fn parse(source: String, file_path: String) -> Result<Option<JsParseResult>>
     
     PARSER_MANAGER.with(|manager| {
         let mut manager = manager.borrow_mut();
-        let result = match manager.parse_file(&file_path, &source) {
-            Some(r) => r,
-            None => return Ok(None),
-        };
-        
-        Ok(Some(JsParseResult {
-            language: format!("{:?}", result.language).to_lowercase(),
-            functions: result.functions.into_iter().map(|f| JsFunctionInfo {
-                name: f.name,
-                qualified_name: f.qualified_name,
-                parameters: f.parameters.into_iter().map(|p| JsParameterInfo {
-                    name: p.name,
-                    type_annotation: p.type_annotation,
-                    default_value: p.default_value,
-                    is_rest: p.is_rest,
-                }).collect(),
-                return_type: f.return_type,
-                is_exported: f.is_exported,
-                is_async: f.is_async,
-                start_line: f.range.start.line as i64,
-                end_line: f.range.end.line as i64,
-                decorators: f.decorators,
-                doc_comment: f.doc_comment,
-            }).collect(),
-            classes: result.classes.into_iter().map(|c| JsClassInfo {
-                name: c.name,
-                extends: c.extends,
-                implements: c.implements,
-                is_exported: c.is_exported,
-                start_line: c.range.start.line as i64,
-                end_line: c.range.end.line as i64,
-                decorators: c.decorators,
-                properties: c.properties.into_iter().map(|p| JsPropertyInfo {
-                    name: p.name,
-                    type_annotation: p.type_annotation,
-                    is_static: p.is_static,
-                    is_readonly: p.is_readonly,
-                    visibility: match p.visibility {
-                        drift_core::parsers::Visibility::Public => "public".to_string(),
-                        drift_core::parsers::Visibility::Private => "private".to_string(),
-                        drift_core::parsers::Visibility::Protected => "protected".to_string(),
-                    },
-                    tags: p.tags.map(|tags| tags.into_iter().map(|t| JsStructTag {
-                        key: t.key,
-                        value: t.value,
-                    }).collect()),
-                }).collect(),
-            }).collect(),
-            imports: result.imports.into_iter().map(|i| JsImportInfo {
-                source: i.source,
-                named: i.named,
-                default: i.default,
-                namespace: i.namespace,
-                is_type_only: i.is_type_only,
-                line: i.range.start.line as i64,
-            }).collect(),
-            exports: result.exports.into_iter().map(|e| JsExportInfo {
-                name: e.name,
-                from_source: e.from_source,
-                is_default: e.is_default,
-                line: e.range.start.line as i64,
-            }).collect(),
-            calls: result.calls.into_iter().map(|c| JsCallSite {
-                callee: c.callee,
-                receiver: c.receiver,
-                arg_count: c.arg_count as i64,
-                line: c.range.start.line as i64,
+        Ok(manager.parse_file_with_language(&file_path, &source, language).map(to_js_parse_result))
+    })
+}
+
+/// Node captured by `queryAst`
+#[napi(object)]
+pub struct JsAstCapture {
+    pub name: String,
+    pub text: String,
+    pub start_line: i64,
+    pub start_col: i64,
+    pub end_line: i64,
+    pub end_col: i64,
+}
+
+/// Run a tree-sitter query (S-expression syntax) against a parsed file
+/// 
+/// The grammar is chosen from the file extension, as in `parse`. Invalid
+/// queries fail with the offset tree-sitter reports.
+#[napi]
+pub fn query_ast(source: String, file_path: String, ts_query: String) -> Result<Vec<JsAstCapture>> {
+    thread_local! {
+        static PARSER_MANAGER: std::cell::RefCell<ParserManager> = 
+            std::cell::RefCell::new(ParserManager::new());
+    }
+    
+    let result = PARSER_MANAGER.with(|manager| manager.borrow_mut().parse_file(&file_path, &source))
+        .ok_or_else(|| napi::Error::from_reason(format!("Unsupported language: {}", file_path)))?;
+    
+    let captures = result.query(&ts_query, &source).map_err(napi::Error::from_reason)?;
+    Ok(captures.into_iter().map(|c| JsAstCapture {
+        name: c.name,
+        text: c.text,
+        start_line: c.range.start.line as i64,
+        start_col: c.range.start.column as i64,
+        end_line: c.range.end.line as i64,
+        end_col: c.range.end.column as i64,
+    }).collect())
+}
+
+/// Options for `parseFiles`
+#[napi(object)]
+pub struct JsParseFilesOptions {
+    /// Directory relative paths are resolved against
+    pub root_dir: Option<String>,
+    /// Parse every file as this language instead of inferring it
+    pub language: Option<String>,
+}
+
+/// Result for one file of a `parseFiles` batch
+#[napi(object)]
+pub struct JsFileParseResult {
+    pub path: String,
+    pub result: Option<JsParseResult>,
+    /// Set when the file could not be read or its language is unsupported
+    pub error: Option<String>,
+}
+
+/// Read and parse many files in parallel
+/// 
+/// Files are read and parsed on Rust worker threads, so sources never cross
+/// the JavaScript boundary. Results are returned in the order of `paths`.
+#[napi]
+pub fn parse_files(paths: Vec<String>, options: Option<JsParseFilesOptions>) -> Result<Vec<JsFileParseResult>> {
+    let (root, language) = to_parse_files_options(options)?;
+    Ok(parse_files_batch(&paths, root, language))
+}
+
+/// Background task behind `parseFilesAsync`
+pub struct ParseFilesTask {
+    paths: Vec<String>,
+    root: Option<PathBuf>,
+    language: Option<Language>,
+}
+
+impl Task for ParseFilesTask {
+    type Output = Vec<JsFileParseResult>;
+    type JsValue = Vec<JsFileParseResult>;
+    
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(parse_files_batch(&self.paths, self.root.take(), self.language))
+    }
+    
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Read and parse many files in parallel without blocking the event loop
+#[napi]
+pub fn parse_files_async(paths: Vec<String>, options: Option<JsParseFilesOptions>) -> Result<AsyncTask<ParseFilesTask>> {
+    let (root, language) = to_parse_files_options(options)?;
+    Ok(AsyncTask::new(ParseFilesTask { paths, root, language }))
+}
+
+fn to_language(name: Option<String>) -> Result<Option<Language>> {
+    match name {
+        Some(name) => Language::from_name(&name)
+            .map(Some)
+            .ok_or_else(|| napi::Error::from_reason(format!("Unsupported language: {}", name))),
+        None => Ok(None),
+    }
+}
+
+fn to_parse_files_options(options: Option<JsParseFilesOptions>) -> Result<(Option<PathBuf>, Option<Language>)> {
+    match options {
+        Some(options) => Ok((options.root_dir.map(PathBuf::from), to_language(options.language)?)),
+        None => Ok((None, None)),
+    }
+}
+
+fn parse_files_batch(paths: &[String], root: Option<PathBuf>, language: Option<Language>) -> Vec<JsFileParseResult> {
+    ParserManager::parse_files(paths, root.as_deref(), language)
+        .into_iter()
+        .map(|f| JsFileParseResult {
+            path: f.path,
+            result: f.result.map(to_js_parse_result),
+            error: f.error,
+        })
+        .collect()
+}
+
+fn to_js_parse_result(result: ParseResult) -> JsParseResult {
+    JsParseResult {
+        language: format!("{:?}", result.language).to_lowercase(),
+        functions: result.functions.into_iter().map(to_js_function_info).collect(),
+        classes: result.classes.into_iter().map(|c| JsClassInfo {
+            name: c.name,
+            extends: c.extends,
+            implements: c.implements,
+            is_exported: c.is_exported,
+            start_line: c.range.start.line as i64,
+            end_line: c.range.end.line as i64,
+            decorators: c.decorators,
+            namespace: c.namespace,
+            is_partial: c.is_partial,
+            type_parameters: c.type_parameters,
+            properties: c.properties.into_iter().map(|p| JsPropertyInfo {
+                name: p.name,
+                type_annotation: p.type_annotation,
+                is_static: p.is_static,
+                is_readonly: p.is_readonly,
+                visibility: match p.visibility {
+                    drift_core::parsers::Visibility::Public => "public".to_string(),
+                    drift_core::parsers::Visibility::Private => "private".to_string(),
+                    drift_core::parsers::Visibility::Protected => "protected".to_string(),
+                },
+                tags: p.tags.map(|tags| tags.into_iter().map(|t| JsStructTag {
+                    key: t.key,
+                    value: t.value,
+                }).collect()),
+                is_computed: p.is_computed,
+                decorators: p.decorators,
             }).collect(),
-            errors: result.errors.into_iter().map(|e| JsParseError {
-                message: e.message,
-                line: e.range.start.line as i64,
+            methods: c.methods.into_iter().map(to_js_function_info).collect(),
+        }).collect(),
+        enums: result.enums.into_iter().map(|e| JsEnumInfo {
+            name: e.name,
+            variants: e.variants.into_iter().map(|v| JsEnumVariant {
+                name: v.name,
+                value: v.value,
+                line: v.range.start.line as i64,
             }).collect(),
-            parse_time_us: result.parse_time_us as i64,
-        }))
-    })
+            is_exported: e.is_exported,
+            start_line: e.range.start.line as i64,
+            end_line: e.range.end.line as i64,
+            decorators: e.decorators,
+        }).collect(),
+        imports: result.imports.into_iter().map(|i| JsImportInfo {
+            source: i.source,
+            named: i.named,
+            default: i.default,
+            namespace: i.namespace,
+            is_type_only: i.is_type_only,
+            relative_level: i.relative_level as i64,
+            line: i.range.start.line as i64,
+        }).collect(),
+        exports: result.exports.into_iter().map(|e| JsExportInfo {
+            name: e.name,
+            from_source: e.from_source,
+            is_default: e.is_default,
+            line: e.range.start.line as i64,
+        }).collect(),
+        calls: result.calls.into_iter().map(|c| JsCallSite {
+            callee: c.callee,
+            receiver: c.receiver,
+            arg_count: c.arg_count as i64,
+            line: c.range.start.line as i64,
+            string_arg: c.string_arg,
+        }).collect(),
+        errors: result.errors.into_iter().map(|e| JsParseError {
+            message: e.message,
+            line: e.range.start.line as i64,
+            end_line: e.range.end.line as i64,
+            snippet: e.snippet,
+        }).collect(),
+        error_node_count: result.error_node_count as i64,
+        parse_time_us: result.parse_time_us as i64,
+    }
+}
+
+fn to_js_function_info(f: FunctionInfo) -> JsFunctionInfo {
+    JsFunctionInfo {
+        name: f.name,
+        qualified_name: f.qualified_name,
+        parameters: f.parameters.into_iter().map(|p| JsParameterInfo {
+            name: p.name,
+            type_annotation: p.type_annotation,
+            default_value: p.default_value,
+            is_rest: p.is_rest,
+        }).collect(),
+        return_type: f.return_type,
+        is_exported: f.is_exported,
+        is_async: f.is_async,
+        visibility: match f.visibility {
+            drift_core::parsers::Visibility::Public => "public".to_string(),
+            drift_core::parsers::Visibility::Private => "private".to_string(),
+            drift_core::parsers::Visibility::Protected => "protected".to_string(),
+        },
+        is_static: f.is_static,
+        type_parameters: f.type_parameters,
+        start_line: f.range.start.line as i64,
+        end_line: f.range.end.line as i64,
+        decorators: f.decorators,
+        doc_comment: f.doc_comment,
+    }
 }
 
 /// Get list of supported languages
@@ -330,6 +749,38 @@ pub struct JsBuildResult {
     pub data_accessors: i64,
     pub errors: Vec<String>,
     pub duration_ms: i64,
+    /// Incremental builds: functions new in the changed files
+    pub functions_added: i64,
+    /// Incremental builds: functions gone from the changed files
+    pub functions_removed: i64,
+    /// Incremental builds: functions re-extracted from the changed files
+    pub functions_updated: i64,
+}
+
+/// Project-specific entry point convention; every given criterion must match
+#[napi(object)]
+pub struct JsEntryPointRule {
+    /// Reported as the entry kind of matching functions
+    pub name: String,
+    /// Regexes matched against the function's decorators, or its class's
+    pub decorators: Option<Vec<String>>,
+    /// Names of a base class or interface of the function's class
+    pub base_classes: Option<Vec<String>>,
+    /// Globs matched against the project-relative file path
+    pub file_globs: Option<Vec<String>>,
+    /// Regexes matched against the function name (without its class)
+    pub function_names: Option<Vec<String>>,
+}
+
+/// Project-specific async job convention: regexes with a `job` group, and
+/// for handlers a `handler` group naming the function that runs the job
+#[napi(object)]
+pub struct JsAsyncEdgeRule {
+    pub name: String,
+    /// Patterns matching where a job is enqueued
+    pub enqueue: Option<Vec<String>>,
+    /// Patterns matching where a handler is registered for a job
+    pub handlers: Option<Vec<String>>,
 }
 
 /// Call graph build configuration from JavaScript
@@ -338,6 +789,24 @@ pub struct JsBuildConfig {
     pub root: String,
     pub patterns: Vec<String>,
     pub resolution_batch_size: Option<i64>,
+    /// Files written to SQLite per transaction (default: 100)
+    pub write_batch_size: Option<i64>,
+    pub include_generated: Option<bool>,
+    /// Entry point conventions checked in order, before the built-in ones
+    pub entry_point_rules: Option<Vec<JsEntryPointRule>>,
+    /// Enqueue and job handler conventions, added to the built-in ones
+    pub async_edge_rules: Option<Vec<JsAsyncEdgeRule>>,
+}
+
+/// Call graph build progress event exposed to JavaScript
+#[napi(object)]
+pub struct JsBuildProgress {
+    /// "scanning" | "parsing" | "writing" | "resolving"
+    pub phase: String,
+    pub files_processed: i64,
+    pub total_files: i64,
+    pub elapsed_ms: i64,
+    pub current_file: Option<String>,
 }
 
 // ============================================================================
@@ -350,11 +819,7 @@ pub struct JsBuildConfig {
 /// for optimal performance on large codebases.
 #[napi]
 pub fn build_call_graph(config: JsBuildConfig) -> Result<JsBuildResult> {
-    let rust_config = BuilderConfig {
-        root_dir: PathBuf::from(&config.root),
-        resolution_batch_size: config.resolution_batch_size.unwrap_or(50) as usize,
-        on_progress: None,
-    };
+    let rust_config = to_builder_config(&config);
     
     let builder = StreamingBuilder::new(rust_config);
     let patterns: Vec<&str> = config.patterns.iter().map(|s| s.as_str()).collect();
@@ -362,7 +827,107 @@ pub fn build_call_graph(config: JsBuildConfig) -> Result<JsBuildResult> {
     // Use SQLite mode for better performance
     let result = builder.build_sqlite(&patterns);
     
-    Ok(JsBuildResult {
+    Ok(to_js_build_result(result))
+}
+
+/// Background call graph build (SQLite mode), optionally reporting progress
+pub struct BuildCallGraphTask {
+    config: Option<BuilderConfig>,
+    patterns: Vec<String>,
+}
+
+impl Task for BuildCallGraphTask {
+    type Output = BuildResult;
+    type JsValue = JsBuildResult;
+    
+    fn compute(&mut self) -> Result<Self::Output> {
+        let config = self.config.take()
+            .ok_or_else(|| napi::Error::from_reason("Build task already run"))?;
+        let patterns: Vec<&str> = self.patterns.iter().map(|s| s.as_str()).collect();
+        Ok(StreamingBuilder::new(config).build_sqlite(&patterns))
+    }
+    
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(to_js_build_result(output))
+    }
+}
+
+/// Build call graph on a worker thread (same as build_call_graph, without
+/// blocking the event loop)
+#[napi]
+pub fn build_call_graph_async(config: JsBuildConfig) -> AsyncTask<BuildCallGraphTask> {
+    AsyncTask::new(BuildCallGraphTask {
+        config: Some(to_builder_config(&config)),
+        patterns: config.patterns,
+    })
+}
+
+/// Build call graph on a worker thread, reporting progress via callback
+/// 
+/// The callback receives the current phase (scanning, parsing, writing,
+/// resolving), files parsed so far, total files and elapsed milliseconds.
+/// During parsing it fires once per file.
+#[napi(ts_args_type = "config: JsBuildConfig, callback: (progress: JsBuildProgress) => void")]
+pub fn build_call_graph_with_progress(config: JsBuildConfig, callback: JsFunction) -> Result<AsyncTask<BuildCallGraphTask>> {
+    let tsfn: ThreadsafeFunction<JsBuildProgress, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    
+    let mut rust_config = to_builder_config(&config);
+    rust_config.on_progress = Some(Box::new(move |p| {
+        tsfn.call(JsBuildProgress {
+            phase: p.phase.as_str().to_string(),
+            files_processed: p.files_processed as i64,
+            total_files: p.total_files as i64,
+            elapsed_ms: p.elapsed_ms as i64,
+            current_file: p.current_file,
+        }, ThreadsafeFunctionCallMode::NonBlocking);
+    }));
+    
+    Ok(AsyncTask::new(BuildCallGraphTask {
+        config: Some(rust_config),
+        patterns: config.patterns,
+    }))
+}
+
+/// Update the SQLite call graph for changed (or deleted) files only
+/// 
+/// Rows for `changed_files` are replaced and resolution re-runs only for calls
+/// those files make or calls targeting a function they define. Requires a
+/// graph built with build_call_graph(); `patterns` in the config is ignored.
+#[napi]
+pub fn build_call_graph_incremental(config: JsBuildConfig, changed_files: Vec<String>) -> Result<JsBuildResult> {
+    let rust_config = to_builder_config(&config);
+    
+    let builder = StreamingBuilder::new(rust_config);
+    let result = builder.update_files(&changed_files);
+    
+    Ok(to_js_build_result(result))
+}
+
+fn to_builder_config(config: &JsBuildConfig) -> BuilderConfig {
+    BuilderConfig {
+        root_dir: PathBuf::from(&config.root),
+        resolution_batch_size: config.resolution_batch_size.unwrap_or(50) as usize,
+        write_batch_size: config.write_batch_size.unwrap_or(100).max(1) as usize,
+        on_progress: None,
+        include_generated: config.include_generated.unwrap_or(false),
+        entry_point_rules: config.entry_point_rules.iter().flatten().map(|rule| EntryPointRule {
+            name: rule.name.clone(),
+            decorators: rule.decorators.clone().unwrap_or_default(),
+            base_classes: rule.base_classes.clone().unwrap_or_default(),
+            file_globs: rule.file_globs.clone().unwrap_or_default(),
+            function_names: rule.function_names.clone().unwrap_or_default(),
+        }).collect(),
+        async_edge_rules: config.async_edge_rules.iter().flatten().map(|rule| AsyncEdgeRule {
+            name: rule.name.clone(),
+            enqueue: rule.enqueue.clone().unwrap_or_default(),
+            handlers: rule.handlers.clone().unwrap_or_default(),
+        }).collect(),
+    }
+}
+
+fn to_js_build_result(result: BuildResult) -> JsBuildResult {
+    JsBuildResult {
         files_processed: result.files_processed as i64,
         total_functions: result.total_functions as i64,
         total_calls: result.total_calls as i64,
@@ -372,7 +937,10 @@ pub fn build_call_graph(config: JsBuildConfig) -> Result<JsBuildResult> {
         data_accessors: result.data_accessors as i64,
         errors: result.errors,
         duration_ms: result.duration_ms as i64,
-    })
+        functions_added: result.functions_added as i64,
+        functions_removed: result.functions_removed as i64,
+        functions_updated: result.functions_updated as i64,
+    }
 }
 
 /// Build call graph using legacy JSON shard storage
@@ -381,27 +949,49 @@ pub fn build_call_graph(config: JsBuildConfig) -> Result<JsBuildResult> {
 /// Use build_call_graph() for better performance.
 #[napi]
 pub fn build_call_graph_legacy(config: JsBuildConfig) -> Result<JsBuildResult> {
-    let rust_config = BuilderConfig {
-        root_dir: PathBuf::from(&config.root),
-        resolution_batch_size: config.resolution_batch_size.unwrap_or(50) as usize,
-        on_progress: None,
-    };
+    let rust_config = to_builder_config(&config);
     
     let mut builder = StreamingBuilder::new(rust_config);
     let patterns: Vec<&str> = config.patterns.iter().map(|s| s.as_str()).collect();
     let result = builder.build(&patterns);
     
-    Ok(JsBuildResult {
-        files_processed: result.files_processed as i64,
-        total_functions: result.total_functions as i64,
-        total_calls: result.total_calls as i64,
-        resolved_calls: result.resolved_calls as i64,
-        resolution_rate: result.resolution_rate as f64,
-        entry_points: result.entry_points as i64,
-        data_accessors: result.data_accessors as i64,
-        errors: result.errors,
-        duration_ms: result.duration_ms as i64,
-    })
+    Ok(to_js_build_result(result))
+}
+
+/// A legacy shard that could not be migrated
+#[napi(object)]
+pub struct JsShardError {
+    pub path: String,
+    pub error: String,
+}
+
+/// Result of migrating legacy JSON shards to SQLite
+#[napi(object)]
+pub struct JsMigrationResult {
+    pub shards_migrated: i64,
+    pub functions_migrated: i64,
+    pub edges_migrated: i64,
+    pub resolved_edges: i64,
+    pub failed_shards: Vec<JsShardError>,
+}
+
+/// Migrate a call graph built with build_call_graph_legacy() into the SQLite
+/// database, replacing its contents
+#[napi]
+pub fn migrate_call_graph_to_sqlite(root_dir: String) -> Result<JsMigrationResult> {
+    let result = drift_core::call_graph::migrate_call_graph_to_sqlite(&PathBuf::from(&root_dir))
+        .map_err(napi::Error::from_reason)?;
+    
+    Ok(JsMigrationResult {
+        shards_migrated: result.shards_migrated as i64,
+        functions_migrated: result.functions_migrated as i64,
+        edges_migrated: result.edges_migrated as i64,
+        resolved_edges: result.resolved_edges as i64,
+        failed_shards: result.failed_shards.into_iter().map(|e| JsShardError {
+            path: e.path,
+            error: e.error,
+        }).collect(),
+    })
 }
 
 // ============================================================================
@@ -418,6 +1008,9 @@ pub struct JsDataAccessPoint {
     pub line: i64,
     pub confidence: f64,
     pub framework: Option<String>,
+    pub in_transaction: bool,
+    /// `file:line` where the enclosing transaction opens
+    pub transaction_id: Option<String>,
 }
 
 /// Sensitive field exposed to JavaScript
@@ -425,6 +1018,7 @@ pub struct JsDataAccessPoint {
 pub struct JsSensitiveField {
     pub field: String,
     pub table: Option<String>,
+    /// "pii", "credentials", "financial", "health" or a configured custom label
     pub sensitivity_type: String,
     pub file: String,
     pub line: i64,
@@ -437,6 +1031,43 @@ pub struct JsORMModel {
     pub name: String,
     pub table_name: String,
     pub fields: Vec<String>,
+    pub relations: Vec<JsModelRelation>,
+    pub file: String,
+    pub line: i64,
+    pub framework: String,
+    pub confidence: f64,
+}
+
+/// Model relation exposed to JavaScript
+#[napi(object)]
+pub struct JsModelRelation {
+    pub field: String,
+    pub target: String,
+    /// "belongs-to", "has-one", "has-many" or "many-to-many"
+    pub kind: String,
+    pub is_list: bool,
+    pub fields: Vec<String>,
+    pub references: Vec<String>,
+    /// Loaded whenever the model is
+    pub eager: bool,
+}
+
+/// Model field missing from SQL migrations exposed to JavaScript
+#[napi(object)]
+pub struct JsUnmigratedField {
+    pub model: String,
+    pub table: String,
+    pub field: String,
+    pub file: String,
+    pub line: i64,
+}
+
+/// Outbound HTTP request exposed to JavaScript
+#[napi(object)]
+pub struct JsExternalAccessPoint {
+    pub url_or_host: String,
+    /// Uppercase HTTP method, or "UNKNOWN"
+    pub method: String,
     pub file: String,
     pub line: i64,
     pub framework: String,
@@ -449,7 +1080,16 @@ pub struct JsBoundaryScanResult {
     pub access_points: Vec<JsDataAccessPoint>,
     pub sensitive_fields: Vec<JsSensitiveField>,
     pub models: Vec<JsORMModel>,
+    pub unmigrated_fields: Vec<JsUnmigratedField>,
+    pub external_calls: Vec<JsExternalAccessPoint>,
+    /// Writes outside any transaction in files that use transactions; only
+    /// filled when `reportUntransactedWrites` is set
+    pub untransacted_writes: Vec<JsDataAccessPoint>,
     pub files_scanned: i64,
+    /// Files whose cached result was reused
+    pub cache_hits: i64,
+    /// Files scanned afresh
+    pub cache_misses: i64,
     pub duration_ms: i64,
 }
 
@@ -460,55 +1100,208 @@ pub struct JsBoundaryScanResult {
 /// Scan files for data boundaries (data access points and sensitive fields)
 /// Uses AST-first approach with regex fallbacks for SQL strings
 #[napi]
-pub fn scan_boundaries(files: Vec<String>) -> Result<JsBoundaryScanResult> {
-    let mut scanner = BoundaryScanner::new();
-    let result = scanner.scan_files(&files);
-    
-    Ok(JsBoundaryScanResult {
-        access_points: result.access_points.into_iter().map(|a| JsDataAccessPoint {
-            table: a.table,
-            operation: match a.operation {
-                DataOperation::Read => "read".to_string(),
-                DataOperation::Write => "write".to_string(),
-                DataOperation::Delete => "delete".to_string(),
-            },
-            fields: a.fields,
-            file: a.file,
-            line: a.line as i64,
-            confidence: a.confidence as f64,
-            framework: a.framework,
-        }).collect(),
-        sensitive_fields: result.sensitive_fields.into_iter().map(|s| JsSensitiveField {
-            field: s.field,
-            table: s.table,
-            sensitivity_type: match s.sensitivity_type {
-                SensitivityType::Pii => "pii".to_string(),
-                SensitivityType::Credentials => "credentials".to_string(),
-                SensitivityType::Financial => "financial".to_string(),
-                SensitivityType::Health => "health".to_string(),
-            },
-            file: s.file,
-            line: s.line as i64,
-            confidence: s.confidence as f64,
+pub fn scan_boundaries(files: Vec<String>, options: Option<JsBoundaryScanOptions>) -> Result<JsBoundaryScanResult> {
+    let mut scanner = configure_boundary_scanner(BoundaryScanner::new(), options)?;
+    Ok(js_boundary_scan_result(scanner.scan_files(&files)))
+}
+
+/// Boundary scan options from JavaScript
+#[napi(object)]
+pub struct JsBoundaryScanOptions {
+    /// Report writes outside any transaction in files that use transactions
+    pub report_untransacted_writes: Option<bool>,
+    /// Project root holding the `.drift/lake/boundaries/` cache (default: cwd)
+    pub root_dir: Option<String>,
+    /// Rescan every file instead of reusing cached results
+    pub no_cache: Option<bool>,
+}
+
+fn configure_boundary_scanner(mut scanner: BoundaryScanner, options: Option<JsBoundaryScanOptions>) -> Result<BoundaryScanner> {
+    let options = options.unwrap_or(JsBoundaryScanOptions {
+        report_untransacted_writes: None,
+        root_dir: None,
+        no_cache: None,
+    });
+    if options.report_untransacted_writes.unwrap_or(false) {
+        scanner = scanner.with_untransacted_writes();
+    }
+    if !options.no_cache.unwrap_or(false) {
+        let root = match options.root_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => std::env::current_dir()
+                .map_err(|e| napi::Error::from_reason(format!("Failed to get current directory: {}", e)))?,
+        };
+        scanner = scanner.with_cache(&root);
+    }
+    Ok(scanner)
+}
+
+/// Sensitive field rule from JavaScript: a regex over source lines and field
+/// names, labelled with a built-in type or a custom one ("biometric")
+#[napi(object)]
+pub struct JsSensitiveFieldRule {
+    pub pattern: String,
+    pub sensitivity: String,
+    /// Base confidence (default 0.8); matches below 0.5 are dropped
+    pub confidence: Option<f64>,
+    /// Drop the built-in matches this pattern overlaps instead of reporting it
+    pub suppress: Option<bool>,
+}
+
+/// Additions to the built-in sensitive field dictionary
+#[napi(object)]
+pub struct JsSensitiveFieldConfig {
+    pub rules: Vec<JsSensitiveFieldRule>,
+}
+
+/// Scan files for data boundaries, with configured sensitive field rules
+#[napi]
+pub fn scan_boundaries_with_config(
+    files: Vec<String>,
+    config: JsSensitiveFieldConfig,
+    options: Option<JsBoundaryScanOptions>,
+) -> Result<JsBoundaryScanResult> {
+    let config = SensitiveFieldConfig {
+        rules: config.rules.into_iter().map(|r| SensitiveFieldRule {
+            pattern: r.pattern,
+            sensitivity: r.sensitivity,
+            confidence: r.confidence.unwrap_or(0.8) as f32,
+            suppress: r.suppress.unwrap_or(false),
         }).collect(),
+    };
+    let scanner = BoundaryScanner::with_config(&config).map_err(napi::Error::from_reason)?;
+    let mut scanner = configure_boundary_scanner(scanner, options)?;
+    Ok(js_boundary_scan_result(scanner.scan_files(&files)))
+}
+
+/// Tenant-scoped tables and their scoping column from JavaScript
+#[napi(object)]
+pub struct JsTenantScopingConfig {
+    pub tables: Vec<String>,
+    /// Scoping column ("tenant_id"); camelCase and PascalCase spellings match too
+    pub column: String,
+}
+
+/// Query on a tenant-scoped table missing the scoping column, exposed to JavaScript
+#[napi(object)]
+pub struct JsTenantViolation {
+    pub file: String,
+    pub line: i64,
+    pub table: String,
+    pub operation: String,
+    /// The query or call chain that was checked
+    pub evidence: String,
+}
+
+/// Flag queries on tenant-scoped tables that don't filter by the scoping column
+///
+/// Findings can be suppressed with a `drift-ignore tenant-scope` comment on
+/// the query or the line above it.
+#[napi]
+pub fn check_tenant_scoping(files: Vec<String>, config: JsTenantScopingConfig) -> Result<Vec<JsTenantViolation>> {
+    let config = TenantScopingConfig {
+        tables: config.tables,
+        column: config.column,
+    };
+    let mut scanner = BoundaryScanner::new();
+    let violations = scanner.check_tenant_scoping(&files, &config).map_err(napi::Error::from_reason)?;
+    Ok(violations.into_iter().map(|v| JsTenantViolation {
+        file: v.file,
+        line: v.line as i64,
+        table: v.table,
+        operation: match v.operation {
+            DataOperation::Read => "read".to_string(),
+            DataOperation::Write => "write".to_string(),
+            DataOperation::Delete => "delete".to_string(),
+        },
+        evidence: v.evidence,
+    }).collect())
+}
+
+fn js_data_access_point(a: DataAccessPoint) -> JsDataAccessPoint {
+    JsDataAccessPoint {
+        in_transaction: a.in_transaction(),
+        table: a.table,
+        operation: match a.operation {
+            DataOperation::Read => "read".to_string(),
+            DataOperation::Write => "write".to_string(),
+            DataOperation::Delete => "delete".to_string(),
+        },
+        fields: a.fields,
+        file: a.file,
+        line: a.line as i64,
+        confidence: a.confidence as f64,
+        framework: a.framework,
+        transaction_id: a.transaction_id,
+    }
+}
+
+fn js_sensitive_field(s: SensitiveField) -> JsSensitiveField {
+    JsSensitiveField {
+        sensitivity_type: s.sensitivity_label().to_string(),
+        field: s.field,
+        table: s.table,
+        file: s.file,
+        line: s.line as i64,
+        confidence: s.confidence as f64,
+    }
+}
+
+fn js_external_access_point(e: ExternalAccessPoint) -> JsExternalAccessPoint {
+    JsExternalAccessPoint {
+        url_or_host: e.url_or_host,
+        method: e.method,
+        file: e.file,
+        line: e.line as i64,
+        framework: e.framework,
+        confidence: e.confidence as f64,
+    }
+}
+
+fn js_boundary_scan_result(result: BoundaryScanResult) -> JsBoundaryScanResult {
+    JsBoundaryScanResult {
+        access_points: result.access_points.into_iter().map(js_data_access_point).collect(),
+        sensitive_fields: result.sensitive_fields.into_iter().map(js_sensitive_field).collect(),
         models: result.models.into_iter().map(|m| JsORMModel {
             name: m.name,
             table_name: m.table_name,
             fields: m.fields,
+            relations: m.relations.into_iter().map(|r| JsModelRelation {
+                field: r.field,
+                target: r.target,
+                kind: r.kind.as_str().to_string(),
+                is_list: r.is_list,
+                fields: r.fields,
+                references: r.references,
+                eager: r.eager,
+            }).collect(),
             file: m.file,
             line: m.line as i64,
             framework: m.framework,
             confidence: m.confidence as f64,
         }).collect(),
+        unmigrated_fields: result.unmigrated_fields.into_iter().map(|u| JsUnmigratedField {
+            model: u.model,
+            table: u.table,
+            field: u.field,
+            file: u.file,
+            line: u.line as i64,
+        }).collect(),
+        external_calls: result.external_calls.into_iter().map(js_external_access_point).collect(),
+        untransacted_writes: result.untransacted_writes.into_iter().map(js_data_access_point).collect(),
         files_scanned: result.files_scanned as i64,
+        cache_hits: result.cache_hits as i64,
+        cache_misses: result.cache_misses as i64,
         duration_ms: result.duration_ms as i64,
-    })
+    }
 }
 
 /// Scan a single source string for boundaries using AST-first approach
 #[napi]
 pub fn scan_boundaries_source(source: String, file_path: String) -> Result<JsBoundaryScanResult> {
-    use drift_core::boundaries::{DataAccessDetector, SensitiveFieldDetector};
+    use drift_core::boundaries::{
+        annotate_transactions, DataAccessDetector, ExternalCallDetector, SensitiveFieldDetector, TransactionDetector,
+    };
     use drift_core::parsers::ParserManager;
     use std::time::Instant;
     
@@ -518,50 +1311,39 @@ pub fn scan_boundaries_source(source: String, file_path: String) -> Result<JsBou
     let mut parser = ParserManager::new();
     let access_detector = DataAccessDetector::new();
     let sensitive_detector = SensitiveFieldDetector::new();
+    let external_detector = ExternalCallDetector::new();
+    let transaction_detector = TransactionDetector::new();
     
     // Try AST parsing first
-    let mut access_points = if let Some(result) = parser.parse_file(&file_path, &source) {
-        access_detector.detect_from_ast(&result, &file_path)
+    let (mut access_points, external_calls, transactions) = if let Some(result) = parser.parse_file(&file_path, &source) {
+        (
+            access_detector.detect_from_ast(&result, &file_path),
+            external_detector.detect_from_ast(&result, &file_path),
+            transaction_detector.detect_from_ast(&result, &source, &file_path),
+        )
     } else {
-        Vec::new()
+        (Vec::new(), Vec::new(), Vec::new())
     };
+    annotate_transactions(&mut access_points, &transactions, 0);
     
     // Fallback: detect SQL in raw source
-    let sql_access = access_detector.detect_sql_in_source(&source, &file_path);
+    let mut sql_access = access_detector.detect_sql_in_source(&source, &file_path);
+    annotate_transactions(&mut sql_access, &transactions, 1);
     access_points.extend(sql_access);
     
-    // Sensitive fields (regex-based for field names)
-    let sensitive_fields = sensitive_detector.detect(&source, &file_path);
+    // Sensitive fields (regex-based for field names), tied to queried tables
+    let sensitive_fields = sensitive_detector.detect_with_access(&source, &file_path, &access_points);
     
     Ok(JsBoundaryScanResult {
-        access_points: access_points.into_iter().map(|a| JsDataAccessPoint {
-            table: a.table,
-            operation: match a.operation {
-                DataOperation::Read => "read".to_string(),
-                DataOperation::Write => "write".to_string(),
-                DataOperation::Delete => "delete".to_string(),
-            },
-            fields: a.fields,
-            file: a.file,
-            line: a.line as i64,
-            confidence: a.confidence as f64,
-            framework: a.framework,
-        }).collect(),
-        sensitive_fields: sensitive_fields.into_iter().map(|s| JsSensitiveField {
-            field: s.field,
-            table: s.table,
-            sensitivity_type: match s.sensitivity_type {
-                SensitivityType::Pii => "pii".to_string(),
-                SensitivityType::Credentials => "credentials".to_string(),
-                SensitivityType::Financial => "financial".to_string(),
-                SensitivityType::Health => "health".to_string(),
-            },
-            file: s.file,
-            line: s.line as i64,
-            confidence: s.confidence as f64,
-        }).collect(),
+        access_points: access_points.into_iter().map(js_data_access_point).collect(),
+        sensitive_fields: sensitive_fields.into_iter().map(js_sensitive_field).collect(),
         models: Vec::new(),
+        unmigrated_fields: Vec::new(),
+        external_calls: external_calls.into_iter().map(js_external_access_point).collect(),
+        untransacted_writes: Vec::new(),
         files_scanned: 1,
+        cache_hits: 0,
+        cache_misses: 1,
         duration_ms: start.elapsed().as_millis() as i64,
     })
 }
@@ -571,6 +1353,16 @@ pub fn scan_boundaries_source(source: String, file_path: String) -> Result<JsBou
 // Coupling Analysis Types
 // ============================================================================
 
+/// Types of one language within a module, exposed to JavaScript
+#[napi(object)]
+pub struct JsModuleLanguageMetrics {
+    pub language: String,
+    pub files: i64,
+    pub types: i64,
+    pub abstract_types: i64,
+    pub abstractness: f64,
+}
+
 /// Module metrics exposed to JavaScript
 #[napi(object)]
 pub struct JsModuleMetrics {
@@ -581,6 +1373,7 @@ pub struct JsModuleMetrics {
     pub abstractness: f64,
     pub distance: f64,
     pub files: Vec<String>,
+    pub languages: Vec<JsModuleLanguageMetrics>,
 }
 
 /// Dependency cycle exposed to JavaScript
@@ -591,13 +1384,33 @@ pub struct JsDependencyCycle {
     pub files_affected: i64,
 }
 
+/// Coupling edge weight exposed to JavaScript
+#[napi(object)]
+pub struct JsEdgeWeight {
+    pub module: String,
+    /// "incoming" or "outgoing"
+    pub direction: String,
+    pub file_count: i64,
+    pub symbol_count: i64,
+}
+
+/// Symbol imported from a hotspot, exposed to JavaScript
+#[napi(object)]
+pub struct JsHotSymbol {
+    pub name: String,
+    pub file_count: i64,
+}
+
 /// Coupling hotspot exposed to JavaScript
 #[napi(object)]
 pub struct JsCouplingHotspot {
     pub module: String,
+    /// Importing files summed over incoming and outgoing edges
     pub total_coupling: i64,
     pub incoming: Vec<String>,
     pub outgoing: Vec<String>,
+    pub edge_weights: Vec<JsEdgeWeight>,
+    pub hot_symbols: Vec<JsHotSymbol>,
 }
 
 /// Unused export exposed to JavaScript
@@ -607,6 +1420,27 @@ pub struct JsUnusedExport {
     pub file: String,
     pub line: i64,
     pub export_type: String,
+    /// "unused", or "possibly-used" when only a computed dynamic import reaches it
+    pub status: String,
+    pub confidence: f64,
+}
+
+/// Module layer exposed to JavaScript
+#[napi(object)]
+pub struct JsModuleLayer {
+    pub module: String,
+    /// 0 for modules importing no other analyzed module
+    pub layer: i64,
+}
+
+/// Upward dependency edge exposed to JavaScript
+#[napi(object)]
+pub struct JsLayerViolation {
+    pub from_module: String,
+    pub to_module: String,
+    pub from_layer: i64,
+    pub to_layer: i64,
+    pub imports: Vec<JsImportEvidence>,
 }
 
 /// Coupling analysis result exposed to JavaScript
@@ -619,6 +1453,82 @@ pub struct JsCouplingResult {
     pub health_score: f64,
     pub files_analyzed: i64,
     pub duration_ms: i64,
+    /// Inferred layer of each module, top layer first
+    pub layers: Option<Vec<JsModuleLayer>>,
+    /// Imports going upward against the inferred layering
+    pub layer_violations: Option<Vec<JsLayerViolation>>,
+}
+
+/// Module metric deltas exposed to JavaScript
+#[napi(object)]
+pub struct JsModuleDelta {
+    pub path: String,
+    /// "added", "removed" or "changed"
+    pub change: String,
+    pub ca: i64,
+    pub ce: i64,
+    pub instability: f64,
+    pub distance: f64,
+}
+
+/// Coupling diff exposed to JavaScript
+#[napi(object)]
+pub struct JsCouplingDiff {
+    pub modules: Vec<JsModuleDelta>,
+    pub introduced_cycles: Vec<JsDependencyCycle>,
+    pub resolved_cycles: Vec<JsDependencyCycle>,
+    pub health_score_delta: f64,
+    /// A cycle was introduced or the health score dropped
+    pub is_worse: bool,
+}
+
+/// Dependency rule from JavaScript
+#[napi(object)]
+pub struct JsDependencyRule {
+    pub from_glob: String,
+    pub to_glob: String,
+    /// "allow" or "forbid"
+    pub kind: String,
+}
+
+/// Import creating a dependency edge, exposed to JavaScript
+#[napi(object)]
+pub struct JsImportEvidence {
+    pub file: String,
+    pub line: i64,
+    pub specifier: String,
+}
+
+/// Dependency rule violation exposed to JavaScript
+#[napi(object)]
+pub struct JsDependencyViolation {
+    pub from_module: String,
+    pub to_module: String,
+    pub rule: JsDependencyRule,
+    pub imports: Vec<JsImportEvidence>,
+    pub cycle: Option<JsDependencyCycle>,
+}
+
+fn js_import_evidence(i: drift_core::coupling::ImportEvidence) -> JsImportEvidence {
+    JsImportEvidence {
+        file: i.file,
+        line: i.line as i64,
+        specifier: i.specifier,
+    }
+}
+
+fn js_dependency_cycle(c: drift_core::coupling::DependencyCycle) -> JsDependencyCycle {
+    use drift_core::coupling::CycleSeverity;
+
+    JsDependencyCycle {
+        modules: c.modules,
+        severity: match c.severity {
+            CycleSeverity::Info => "info".to_string(),
+            CycleSeverity::Warning => "warning".to_string(),
+            CycleSeverity::Critical => "critical".to_string(),
+        },
+        files_affected: c.files_affected as i64,
+    }
 }
 
 // ============================================================================
@@ -628,7 +1538,7 @@ pub struct JsCouplingResult {
 /// Analyze module coupling using AST-first approach
 #[napi]
 pub fn analyze_coupling(files: Vec<String>) -> Result<JsCouplingResult> {
-    use drift_core::coupling::{CouplingAnalyzer, CycleSeverity};
+    use drift_core::coupling::{CouplingAnalyzer, EdgeDirection, ExportUsageStatus};
     
     let mut analyzer = CouplingAnalyzer::new();
     let result = analyzer.analyze(&files);
@@ -642,31 +1552,133 @@ pub fn analyze_coupling(files: Vec<String>) -> Result<JsCouplingResult> {
             abstractness: m.abstractness as f64,
             distance: m.distance as f64,
             files: m.files,
+            languages: m.languages.into_iter().map(|l| JsModuleLanguageMetrics {
+                language: l.language,
+                files: l.files as i64,
+                types: l.types as i64,
+                abstract_types: l.abstract_types as i64,
+                abstractness: l.abstractness as f64,
+            }).collect(),
         }).collect(),
-        cycles: result.cycles.into_iter().map(|c| JsDependencyCycle {
-            modules: c.modules,
-            severity: match c.severity {
-                CycleSeverity::Info => "info".to_string(),
-                CycleSeverity::Warning => "warning".to_string(),
-                CycleSeverity::Critical => "critical".to_string(),
-            },
-            files_affected: c.files_affected as i64,
-        }).collect(),
+        cycles: result.cycles.into_iter().map(js_dependency_cycle).collect(),
         hotspots: result.hotspots.into_iter().map(|h| JsCouplingHotspot {
             module: h.module,
             total_coupling: h.total_coupling as i64,
             incoming: h.incoming,
             outgoing: h.outgoing,
+            edge_weights: h.edge_weights.into_iter().map(|w| JsEdgeWeight {
+                module: w.module,
+                direction: match w.direction {
+                    EdgeDirection::Incoming => "incoming".to_string(),
+                    EdgeDirection::Outgoing => "outgoing".to_string(),
+                },
+                file_count: w.file_count as i64,
+                symbol_count: w.symbol_count as i64,
+            }).collect(),
+            hot_symbols: h.hot_symbols.into_iter().map(|s| JsHotSymbol {
+                name: s.name,
+                file_count: s.file_count as i64,
+            }).collect(),
         }).collect(),
         unused_exports: result.unused_exports.into_iter().map(|u| JsUnusedExport {
             name: u.name,
             file: u.file,
             line: u.line as i64,
             export_type: u.export_type,
+            status: match u.status {
+                ExportUsageStatus::Unused => "unused".to_string(),
+                ExportUsageStatus::PossiblyUsed => "possibly-used".to_string(),
+            },
+            confidence: u.confidence as f64,
         }).collect(),
         health_score: result.health_score as f64,
         files_analyzed: result.files_analyzed as i64,
         duration_ms: result.duration_ms as i64,
+        layers: Some(result.layers.into_iter().map(|l| JsModuleLayer {
+            module: l.module,
+            layer: l.layer as i64,
+        }).collect()),
+        layer_violations: Some(result.layer_violations.into_iter().map(|v| JsLayerViolation {
+            from_module: v.from_module,
+            to_module: v.to_module,
+            from_layer: v.from_layer as i64,
+            to_layer: v.to_layer as i64,
+            imports: v.imports.into_iter().map(js_import_evidence).collect(),
+        }).collect()),
+    })
+}
+
+/// Check module dependency edges against allow/forbid rules
+#[napi]
+pub fn check_dependency_rules(files: Vec<String>, rules: Vec<JsDependencyRule>) -> Result<Vec<JsDependencyViolation>> {
+    use drift_core::coupling::{CouplingAnalyzer, DependencyRule, DependencyRuleKind};
+
+    let rules = rules.into_iter()
+        .map(|r| {
+            let kind = match r.kind.as_str() {
+                "allow" => DependencyRuleKind::Allow,
+                "forbid" => DependencyRuleKind::Forbid,
+                other => return Err(Error::from_reason(format!("Unknown dependency rule kind '{}'", other))),
+            };
+            Ok(DependencyRule { from_glob: r.from_glob, to_glob: r.to_glob, kind })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut analyzer = CouplingAnalyzer::new();
+    let violations = analyzer.check_dependency_rules(&files, &rules)
+        .map_err(Error::from_reason)?;
+
+    Ok(violations.into_iter().map(|v| JsDependencyViolation {
+        from_module: v.from_module,
+        to_module: v.to_module,
+        rule: JsDependencyRule {
+            from_glob: v.rule.from_glob,
+            to_glob: v.rule.to_glob,
+            kind: match v.rule.kind {
+                DependencyRuleKind::Allow => "allow".to_string(),
+                DependencyRuleKind::Forbid => "forbid".to_string(),
+            },
+        },
+        imports: v.imports.into_iter().map(js_import_evidence).collect(),
+        cycle: v.cycle.map(js_dependency_cycle),
+    }).collect())
+}
+
+/// Compare two serialized coupling results (old, then new)
+///
+/// Accepts the JSON of an `analyzeCoupling` result or of the Rust
+/// `CouplingAnalysisResult`. Metric deltas smaller than `epsilon`
+/// (default 0.01) are ignored.
+#[napi]
+pub fn diff_coupling_results(old_json: String, new_json: String, epsilon: Option<f64>) -> Result<JsCouplingDiff> {
+    use drift_core::coupling::{CouplingAnalysisResult, ModuleChange, DEFAULT_COUPLING_TOLERANCE};
+
+    let parse = |json: &str, which: &str| serde_json::from_str::<CouplingAnalysisResult>(json)
+        .map_err(|e| Error::from_reason(format!("Invalid {} coupling result: {}", which, e)));
+    let old = parse(&old_json, "old")?;
+    let new = parse(&new_json, "new")?;
+
+    let tolerance = epsilon.map_or(DEFAULT_COUPLING_TOLERANCE, |e| e as f32);
+    let diff = CouplingAnalysisResult::diff_with_tolerance(&old, &new, tolerance);
+    let is_worse = diff.is_worse();
+
+    Ok(JsCouplingDiff {
+        modules: diff.modules.into_iter().map(|m| JsModuleDelta {
+            path: m.path,
+            change: match m.change {
+                ModuleChange::Added => "added".to_string(),
+                ModuleChange::Removed => "removed".to_string(),
+                ModuleChange::Changed => "changed".to_string(),
+            },
+            ca: m.ca,
+            ce: m.ce,
+            instability: m.instability as f64,
+            distance: m.distance as f64,
+        }).collect(),
+        introduced_cycles: diff.introduced_cycles.into_iter().map(js_dependency_cycle).collect(),
+        resolved_cycles: diff.resolved_cycles.into_iter().map(js_dependency_cycle).collect(),
+        health_score_delta: diff.health_score_delta as f64,
+        is_worse,
     })
 }
 
@@ -682,6 +1694,72 @@ pub struct JsTestFile {
     pub framework: String,
     pub test_count: i64,
     pub mock_count: i64,
+    pub test_cases: Vec<JsTestCase>,
+    pub mocks: Vec<JsMockInfo>,
+    pub flaky_signals: Vec<JsFlakySignal>,
+    /// Mean assertions per test case that isn't skipped
+    pub assertion_density: f64,
+    /// Tests that aren't skipped and assert nothing
+    pub empty_tests: Vec<String>,
+    /// Tests asserting only through snapshot matchers
+    pub snapshot_only_tests: Vec<String>,
+}
+
+/// Structural sign of a flaky test exposed to JavaScript
+#[napi(object)]
+pub struct JsFlakySignal {
+    /// "retry", "sleep", "time_assertion" or "unmocked_network"
+    pub kind: String,
+    pub line: i64,
+    pub snippet: String,
+}
+
+/// Mock or stub in a test file exposed to JavaScript
+#[napi(object)]
+pub struct JsMockInfo {
+    /// Test file the mock is in
+    pub file: String,
+    pub line: i64,
+    pub framework: String,
+    /// Module path, patch target or class being mocked
+    pub target: String,
+    /// "function", "module", "class", "http", "database" or "unknown"
+    pub mock_type: String,
+    /// File the target resolves to
+    pub resolved_file: Option<String>,
+}
+
+/// Test mocking the source file it is mapped to, exposed to JavaScript
+#[napi(object)]
+pub struct JsMockOverlap {
+    pub test_file: String,
+    pub source_file: String,
+    pub target: String,
+    pub line: i64,
+}
+
+/// Test case exposed to JavaScript
+#[napi(object)]
+pub struct JsTestCase {
+    pub name: String,
+    /// "unit", "integration", "e2e" or "unknown"
+    pub test_type: String,
+    pub line: i64,
+    pub is_skipped: bool,
+    /// Focused with an `only` marker (`it.only`, `fit`, `describe.only`)
+    pub is_only: bool,
+    /// Parameter sets of a parametrized test, when listed literally
+    pub parameter_sets: Option<i64>,
+    pub assertion_count: i64,
+    /// Asserts only through snapshot matchers (`toMatchSnapshot`)
+    pub is_snapshot_only: bool,
+}
+
+/// Inclusive 1-based line range exposed to JavaScript
+#[napi(object)]
+pub struct JsLineRange {
+    pub start: i64,
+    pub end: i64,
 }
 
 /// Test coverage exposed to JavaScript
@@ -691,6 +1769,15 @@ pub struct JsTestCoverage {
     pub test_files: Vec<String>,
     pub coverage_percent: Option<f64>,
     pub risk_level: String,
+    /// "convention" (test file naming), "callgraph" (calls from tests) or
+    /// "coverage" (only executed according to a coverage report)
+    pub mapping_method: String,
+    /// Distinct test functions touching the source file
+    pub test_function_count: i64,
+    /// Lines a coverage report lists as never executed
+    pub uncovered_line_ranges: Vec<JsLineRange>,
+    /// Factors raising the risk level ("untested", "fan-in of 12")
+    pub risk_factors: Vec<String>,
 }
 
 /// Test topology result exposed to JavaScript
@@ -701,27 +1788,124 @@ pub struct JsTestTopologyResult {
     pub uncovered_files: Vec<String>,
     pub total_tests: i64,
     pub skipped_tests: i64,
+    /// Tests mocking the source files they cover
+    pub mock_overlaps: Vec<JsMockOverlap>,
+    /// Coverage report paths matching none of the analyzed files
+    pub unmatched_coverage_paths: Vec<String>,
+    /// Flaky signals across test files
+    pub flaky_signal_count: i64,
     pub files_analyzed: i64,
     pub duration_ms: i64,
 }
 
+/// Rewrite of a coverage report path prefix (`/ci/build/` -> `/home/me/repo/`)
+#[napi(object)]
+pub struct JsCoveragePathPrefix {
+    pub from: String,
+    pub to: String,
+}
+
+/// Coverage reports to join onto the test topology
+#[napi(object)]
+pub struct JsCoverageOptions {
+    /// lcov, Cobertura XML or coverage.py JSON reports
+    pub reports: Vec<String>,
+    /// Prefix rewrites tried before matching report paths to analyzed files
+    pub path_prefixes: Option<Vec<JsCoveragePathPrefix>>,
+}
+
+/// Risk factor weights from JavaScript (defaults: coverage 0.4, fan-in 0.3,
+/// sensitive data 0.3, high fan-in 10 callers)
+#[napi(object)]
+pub struct JsRiskOptions {
+    pub coverage_weight: Option<f64>,
+    pub fan_in_weight: Option<f64>,
+    pub sensitive_data_weight: Option<f64>,
+    /// Callers from other source files at which fan-in scores fully
+    pub high_fan_in: Option<u32>,
+}
+
+/// Flaky signal options from JavaScript
+#[napi(object)]
+pub struct JsFlakyOptions {
+    /// Signal kinds to leave out ("retry", "sleep", "time_assertion",
+    /// "unmocked_network"), e.g. for teams retrying on purpose
+    pub suppress: Option<Vec<String>>,
+}
+
 // ============================================================================
 // Test Topology Functions
 // ============================================================================
 
 /// Analyze test topology using AST-first approach
+///
+/// With `root_dir` and a call graph built under it, tests map to the source
+/// files whose functions they call; otherwise by test file naming convention.
+/// With `coverage` reports, coverage percent and uncovered lines come from
+/// the reports. Risk levels weigh coverage, fan-in and reachable sensitive
+/// data by `risk`; the latter two need the call graph.
 #[napi]
-pub fn analyze_test_topology(files: Vec<String>) -> Result<JsTestTopologyResult> {
-    use drift_core::test_topology::{TestTopologyAnalyzer, TestFramework, RiskLevel};
+pub fn analyze_test_topology(
+    files: Vec<String>,
+    root_dir: Option<String>,
+    coverage: Option<JsCoverageOptions>,
+    risk: Option<JsRiskOptions>,
+    flaky: Option<JsFlakyOptions>,
+) -> Result<JsTestTopologyResult> {
+    use drift_core::call_graph::CallGraphDb;
+    use drift_core::test_topology::{TestTopologyAnalyzer, TestFramework, TestType, MockType, RiskLevel, MappingMethod, CoveragePathPrefix, RiskOptions, FlakySignalKind};
     
-    let mut analyzer = TestTopologyAnalyzer::new();
-    let result = analyzer.analyze(&files);
+    let mut risk_options = RiskOptions::default();
+    if let Some(risk) = risk {
+        if let Some(weight) = risk.coverage_weight {
+            risk_options.coverage_weight = weight as f32;
+        }
+        if let Some(weight) = risk.fan_in_weight {
+            risk_options.fan_in_weight = weight as f32;
+        }
+        if let Some(weight) = risk.sensitive_data_weight {
+            risk_options.sensitive_data_weight = weight as f32;
+        }
+        if let Some(callers) = risk.high_fan_in {
+            risk_options.high_fan_in = callers as usize;
+        }
+    }
+    let suppressed = flaky.and_then(|f| f.suppress).unwrap_or_default().iter()
+        .map(|kind| FlakySignalKind::parse(kind))
+        .collect::<std::result::Result<Vec<_>, String>>()
+        .map_err(napi::Error::from_reason)?;
+    let mut analyzer = TestTopologyAnalyzer::new().with_suppressed_flaky_signals(suppressed);
+    let mut unmatched_coverage_paths = Vec::new();
+    if let Some(coverage) = coverage {
+        let prefixes = coverage.path_prefixes.unwrap_or_default().into_iter()
+            .map(|p| CoveragePathPrefix { from: p.from, to: p.to })
+            .collect();
+        analyzer = analyzer.with_coverage_path_prefixes(prefixes);
+        unmatched_coverage_paths = analyzer.load_coverage(&files, &coverage.reports)
+            .map_err(napi::Error::from_reason)?
+            .unmatched_paths;
+    }
+    let call_graph = root_dir.map(PathBuf::from).and_then(|root| {
+        let db_path = root
+            .join(".drift")
+            .join("lake")
+            .join("callgraph")
+            .join("callgraph.db");
+        db_path.exists().then_some((root, db_path))
+    });
+    let result = match call_graph {
+        Some((root, db_path)) => {
+            let db = CallGraphDb::open_readonly(&db_path)
+                .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+            analyzer.analyze_with_call_graph(&root, &files, &db, &risk_options)
+                .map_err(napi::Error::from_reason)?
+        }
+        None => analyzer.analyze(&files, &risk_options),
+    };
     
     Ok(JsTestTopologyResult {
-        test_files: result.test_files.into_iter().map(|t| JsTestFile {
-            path: t.path,
-            tests_file: t.tests_file,
-            framework: match t.framework {
+        test_files: result.test_files.into_iter().map(|t| {
+            let framework = match t.framework {
                 TestFramework::Jest => "jest".to_string(),
                 TestFramework::Vitest => "vitest".to_string(),
                 TestFramework::Mocha => "mocha".to_string(),
@@ -735,9 +1919,60 @@ pub fn analyze_test_topology(files: Vec<String>) -> Result<JsTestTopologyResult>
                 TestFramework::Catch2 => "catch2".to_string(),
                 TestFramework::GoogleTest => "googletest".to_string(),
                 TestFramework::Unknown => "unknown".to_string(),
-            },
-            test_count: t.test_cases.len() as i64,
-            mock_count: t.mocks.len() as i64,
+            };
+            let empty_tests = t.test_cases.iter()
+                .filter(|c| !c.is_skipped && c.assertion_count == 0)
+                .map(|c| c.name.clone())
+                .collect();
+            let snapshot_only_tests = t.test_cases.iter()
+                .filter(|c| c.is_snapshot_only)
+                .map(|c| c.name.clone())
+                .collect();
+            JsTestFile {
+                path: t.path.clone(),
+                tests_file: t.tests_file,
+                framework: framework.clone(),
+                test_count: t.test_cases.len() as i64,
+                mock_count: t.mocks.len() as i64,
+                test_cases: t.test_cases.into_iter().map(|c| JsTestCase {
+                    name: c.name,
+                    test_type: match c.test_type {
+                        TestType::Unit => "unit".to_string(),
+                        TestType::Integration => "integration".to_string(),
+                        TestType::E2E => "e2e".to_string(),
+                        TestType::Unknown => "unknown".to_string(),
+                    },
+                    line: c.line as i64,
+                    is_skipped: c.is_skipped,
+                    is_only: c.is_only,
+                    parameter_sets: c.parameter_sets.map(|n| n as i64),
+                    assertion_count: c.assertion_count as i64,
+                    is_snapshot_only: c.is_snapshot_only,
+                }).collect(),
+                mocks: t.mocks.into_iter().map(|m| JsMockInfo {
+                    file: t.path.clone(),
+                    line: m.line as i64,
+                    framework: framework.clone(),
+                    target: m.target,
+                    mock_type: match m.mock_type {
+                        MockType::Function => "function".to_string(),
+                        MockType::Module => "module".to_string(),
+                        MockType::Class => "class".to_string(),
+                        MockType::Http => "http".to_string(),
+                        MockType::Database => "database".to_string(),
+                        MockType::Unknown => "unknown".to_string(),
+                    },
+                    resolved_file: m.resolved_file,
+                }).collect(),
+                flaky_signals: t.flaky_signals.into_iter().map(|s| JsFlakySignal {
+                    kind: s.kind.as_str().to_string(),
+                    line: s.line as i64,
+                    snippet: s.snippet,
+                }).collect(),
+                assertion_density: t.assertion_density as f64,
+                empty_tests,
+                snapshot_only_tests,
+            }
         }).collect(),
         coverage: result.coverage.into_iter().map(|c| JsTestCoverage {
             source_file: c.source_file,
@@ -749,10 +1984,28 @@ pub fn analyze_test_topology(files: Vec<String>) -> Result<JsTestTopologyResult>
                 RiskLevel::High => "high".to_string(),
                 RiskLevel::Critical => "critical".to_string(),
             },
+            mapping_method: match c.mapping_method {
+                MappingMethod::Convention => "convention".to_string(),
+                MappingMethod::CallGraph => "callgraph".to_string(),
+                MappingMethod::Coverage => "coverage".to_string(),
+            },
+            test_function_count: c.test_function_count as i64,
+            uncovered_line_ranges: c.uncovered_line_ranges.into_iter()
+                .map(|r| JsLineRange { start: r.start as i64, end: r.end as i64 })
+                .collect(),
+            risk_factors: c.risk_factors,
         }).collect(),
         uncovered_files: result.uncovered_files,
         total_tests: result.total_tests as i64,
         skipped_tests: result.skipped_tests as i64,
+        mock_overlaps: result.mock_overlaps.into_iter().map(|o| JsMockOverlap {
+            test_file: o.test_file,
+            source_file: o.source_file,
+            target: o.target,
+            line: o.line as i64,
+        }).collect(),
+        unmatched_coverage_paths,
+        flaky_signal_count: result.flaky_signal_count as i64,
         files_analyzed: result.files_analyzed as i64,
         duration_ms: result.duration_ms as i64,
     })
@@ -773,6 +2026,12 @@ pub struct JsErrorBoundary {
     pub rethrows: bool,
     pub logs_error: bool,
     pub is_swallowed: bool,
+    /// Framework of an error handler: express, koa, fastify or nestjs
+    pub framework: Option<String>,
+    /// Routes an error handler covers: global, router or use_filters
+    pub scope: Option<String>,
+    /// Router a router-scoped handler is registered on
+    pub scope_router: Option<String>,
 }
 
 /// Error gap exposed to JavaScript
@@ -784,6 +2043,15 @@ pub struct JsErrorGap {
     pub gap_type: String,
     pub severity: String,
     pub description: String,
+    pub reachable_writes: Vec<JsReachableWrite>,
+}
+
+/// Data write or external call reachable from a try block, exposed to JavaScript
+#[napi(object)]
+pub struct JsReachableWrite {
+    pub function: String,
+    pub table: String,
+    pub operation: String,
 }
 
 /// Error type exposed to JavaScript
@@ -794,6 +2062,12 @@ pub struct JsErrorType {
     pub line: i64,
     pub extends: Option<String>,
     pub is_exported: bool,
+    /// Error types directly extending this one
+    pub children: Vec<String>,
+    /// Places constructing or raising the type
+    pub throw_count: i64,
+    /// Neither the type nor any type below it is ever constructed
+    pub is_unused: bool,
 }
 
 /// Error handling result exposed to JavaScript
@@ -811,13 +2085,29 @@ pub struct JsErrorHandlingResult {
 // ============================================================================
 
 /// Analyze error handling using AST-first approach
+///
+/// With `root_dir` and a call graph built under it, swallowed errors are
+/// critical when their try block reaches data writes or external calls, and
+/// Rust panics escalate where an entry point reaches them.
 #[napi]
-pub fn analyze_error_handling(files: Vec<String>) -> Result<JsErrorHandlingResult> {
-    use drift_core::error_handling::{
-        ErrorHandlingAnalyzer, BoundaryType, GapType, GapSeverity
-    };
+pub fn analyze_error_handling(files: Vec<String>, root_dir: Option<String>) -> Result<JsErrorHandlingResult> {
+    use drift_core::call_graph::CallGraphDb;
+    use drift_core::error_handling::{ErrorHandlingAnalyzer, BoundaryType, HandlerScope};
     
     let mut analyzer = ErrorHandlingAnalyzer::new();
+    if let Some(root) = root_dir.map(PathBuf::from) {
+        let db_path = root
+            .join(".drift")
+            .join("lake")
+            .join("callgraph")
+            .join("callgraph.db");
+        if db_path.exists() {
+            let db = CallGraphDb::open_readonly(&db_path)
+                .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+            analyzer = analyzer.with_call_graph(&root, &db)
+                .map_err(napi::Error::from_reason)?;
+        }
+    }
     let result = analyzer.analyze(&files);
     
     Ok(JsErrorHandlingResult {
@@ -839,41 +2129,157 @@ pub fn analyze_error_handling(files: Vec<String>) -> Result<JsErrorHandlingResul
             rethrows: b.rethrows,
             logs_error: b.logs_error,
             is_swallowed: b.is_swallowed,
-        }).collect(),
-        gaps: result.gaps.into_iter().map(|g| JsErrorGap {
-            file: g.file,
-            line: g.line as i64,
-            function: g.function,
-            gap_type: match g.gap_type {
-                GapType::UnhandledPromise => "unhandled_promise".to_string(),
-                GapType::UnhandledAsync => "unhandled_async".to_string(),
-                GapType::MissingCatch => "missing_catch".to_string(),
-                GapType::SwallowedError => "swallowed_error".to_string(),
-                GapType::UnwrapWithoutCheck => "unwrap_without_check".to_string(),
-                GapType::UncheckedResult => "unchecked_result".to_string(),
-                GapType::MissingErrorBoundary => "missing_error_boundary".to_string(),
+            framework: b.framework,
+            scope: b.scope.as_ref().map(|scope| match scope {
+                HandlerScope::Global => "global".to_string(),
+                HandlerScope::Router(_) => "router".to_string(),
+                HandlerScope::UseFilters => "use_filters".to_string(),
+            }),
+            scope_router: match b.scope {
+                Some(HandlerScope::Router(router)) => Some(router),
+                _ => None,
             },
-            severity: match g.severity {
-                GapSeverity::Low => "low".to_string(),
-                GapSeverity::Medium => "medium".to_string(),
-                GapSeverity::High => "high".to_string(),
-                GapSeverity::Critical => "critical".to_string(),
-            },
-            description: g.description,
         }).collect(),
+        gaps: result.gaps.into_iter().map(js_error_gap).collect(),
         error_types: result.error_types.into_iter().map(|e| JsErrorType {
             name: e.name,
             file: e.file,
             line: e.line as i64,
             extends: e.extends,
             is_exported: e.is_exported,
+            children: e.children,
+            throw_count: e.throw_count as i64,
+            is_unused: e.is_unused,
         }).collect(),
         files_analyzed: result.files_analyzed as i64,
         duration_ms: result.duration_ms as i64,
     })
 }
 
-
+fn js_error_gap(g: drift_core::error_handling::ErrorGap) -> JsErrorGap {
+    use drift_core::error_handling::{GapType, GapSeverity};
+    
+    JsErrorGap {
+        file: g.file,
+        line: g.line as i64,
+        function: g.function,
+        gap_type: match g.gap_type {
+            GapType::UnhandledPromise => "unhandled_promise".to_string(),
+            GapType::UnhandledAsync => "unhandled_async".to_string(),
+            GapType::MissingCatch => "missing_catch".to_string(),
+            GapType::SwallowedError => "swallowed_error".to_string(),
+            GapType::UnwrapWithoutCheck => "unwrap_without_check".to_string(),
+            GapType::ExplicitPanic => "explicit_panic".to_string(),
+            GapType::UncheckedIndex => "unchecked_index".to_string(),
+            GapType::UncheckedResult => "unchecked_result".to_string(),
+            GapType::MissingErrorBoundary => "missing_error_boundary".to_string(),
+        },
+        severity: match g.severity {
+            GapSeverity::Low => "low".to_string(),
+            GapSeverity::Medium => "medium".to_string(),
+            GapSeverity::High => "high".to_string(),
+            GapSeverity::Critical => "critical".to_string(),
+        },
+        description: g.description,
+        reachable_writes: g.reachable_writes.into_iter().map(js_reachable_write).collect(),
+    }
+}
+
+fn js_reachable_write(w: drift_core::error_handling::ReachableWrite) -> JsReachableWrite {
+    use drift_core::call_graph::DataOperation;
+    
+    JsReachableWrite {
+        function: w.function,
+        table: w.table,
+        operation: match w.operation {
+            DataOperation::Read => "read".to_string(),
+            DataOperation::Write => "write".to_string(),
+            DataOperation::Delete => "delete".to_string(),
+        },
+    }
+}
+
+/// Error coverage options
+#[napi(object)]
+pub struct JsErrorCoverageOptions {
+    /// Deepest call chain followed from an entry point (unlimited by default)
+    pub max_depth: Option<i64>,
+    /// Skip entry points in test files
+    pub exclude_tests: Option<bool>,
+}
+
+/// Step of an uncovered write path exposed to JavaScript
+#[napi(object)]
+pub struct JsPathStep {
+    pub function: String,
+    pub line: i64,
+}
+
+/// Entry point with uncovered write paths exposed to JavaScript
+#[napi(object)]
+pub struct JsUncoveredEntryPoint {
+    pub entry_point: String,
+    pub file: String,
+    pub line: i64,
+    pub uncovered_writes: Vec<JsReachableWrite>,
+    pub example_path: Vec<JsPathStep>,
+    pub gaps: Vec<JsErrorGap>,
+}
+
+/// Error coverage result exposed to JavaScript
+#[napi(object)]
+pub struct JsErrorCoverageResult {
+    pub entry_points: i64,
+    pub covered_entry_points: i64,
+    pub uncovered: Vec<JsUncoveredEntryPoint>,
+    pub files_analyzed: i64,
+    pub duration_ms: i64,
+}
+
+/// Report entry points with a path to a data write that no error boundary
+/// logging or rethrowing covers, with the shortest such path
+#[napi]
+pub fn analyze_error_coverage(root_dir: String, options: Option<JsErrorCoverageOptions>) -> Result<JsErrorCoverageResult> {
+    use drift_core::call_graph::CallGraphDb;
+    use drift_core::error_handling::ErrorCoverageOptions;
+    
+    let options = options.map(|o| ErrorCoverageOptions {
+        max_depth: o.max_depth.map(|d| d as usize),
+        exclude_tests: o.exclude_tests.unwrap_or(false),
+    }).unwrap_or_default();
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    let result = drift_core::error_handling::analyze_error_coverage(&root, &db, &options)
+        .map_err(napi::Error::from_reason)?;
+    
+    Ok(JsErrorCoverageResult {
+        entry_points: result.entry_points as i64,
+        covered_entry_points: result.covered_entry_points as i64,
+        uncovered: result.uncovered.into_iter().map(|u| JsUncoveredEntryPoint {
+            entry_point: u.entry_point,
+            file: u.file,
+            line: u.line as i64,
+            uncovered_writes: u.uncovered_writes.into_iter().map(js_reachable_write).collect(),
+            example_path: u.example_path.into_iter().map(|s| JsPathStep {
+                function: s.function,
+                line: s.line as i64,
+            }).collect(),
+            gaps: u.gaps.into_iter().map(js_error_gap).collect(),
+        }).collect(),
+        files_analyzed: result.files_analyzed as i64,
+        duration_ms: result.duration_ms as i64,
+    })
+}
+
+
 // ============================================================================
 // Reachability Types
 // ============================================================================
@@ -906,8 +2312,17 @@ pub struct JsReachableDataAccess {
     pub line: i64,
     pub confidence: f64,
     pub framework: Option<String>,
+    /// A shortest call path to the access
     pub path: Vec<JsCallPathNode>,
+    /// Distinct call paths, shortest first (`path` is the first)
+    pub paths: Vec<Vec<JsCallPathNode>>,
     pub depth: i64,
+    /// Confidence of the calls along `path` and of the access, multiplied
+    pub path_confidence: f64,
+    /// Whether `path` crosses an async boundary (job enqueue to handler)
+    pub crosses_async: bool,
+    /// For tables loaded by an eager ORM relation, the relation (`Post.author`)
+    pub loaded_via: Option<String>,
 }
 
 /// Sensitive field access exposed to JavaScript
@@ -932,6 +2347,14 @@ pub struct JsReachabilityResult {
     pub sensitive_fields: Vec<JsSensitiveFieldAccess>,
     pub max_depth: i64,
     pub functions_traversed: i64,
+    /// Whether the result was served from the reachability cache
+    pub cache_hit: bool,
+    /// Whether max_functions or timeout_ms stopped the traversal early
+    pub truncated: bool,
+    /// "max-functions" or "timeout" when truncated
+    pub truncation_reason: Option<String>,
+    /// Unresolved calls followed to a candidate
+    pub unresolved_edges_traversed: i64,
 }
 
 /// Reachability options from JavaScript
@@ -940,7 +2363,30 @@ pub struct JsReachabilityOptions {
     pub max_depth: Option<i64>,
     pub sensitive_only: Option<bool>,
     pub tables: Option<Vec<String>>,
+    /// Shorthand for unresolved_mode "pessimistic" (true) or "ignore"
     pub include_unresolved: Option<bool>,
+    /// How to follow calls without a resolved target: "ignore",
+    /// "pessimistic" or "unique-candidate"; overrides include_unresolved
+    pub unresolved_mode: Option<String>,
+    /// Call kinds to follow: "direct", "method", "callback", "dispatch",
+    /// "async"; all but "async" when unset (SQLite-backed analysis only)
+    pub edge_kinds: Option<Vec<String>>,
+    /// Also follow async calls from job enqueue sites to their handlers
+    /// (SQLite-backed analysis only)
+    pub include_async_edges: Option<bool>,
+    /// Call paths to list per reachable access (default 1)
+    pub max_paths_per_access: Option<i64>,
+    /// Only access with these operations: "read", "write", "delete"
+    pub operations: Option<Vec<String>>,
+    /// Stop after visiting this many functions, returning a partial result
+    pub max_functions: Option<i64>,
+    /// Stop after this many milliseconds, returning a partial result
+    pub timeout_ms: Option<i64>,
+    /// Only access whose path_confidence is at least this
+    pub min_confidence: Option<f64>,
+    /// Reads of a model's table also reach the tables its eager ORM
+    /// relations load (SQLite-backed analysis only)
+    pub expand_eager_relations: Option<bool>,
 }
 
 /// Inverse access path exposed to JavaScript
@@ -953,16 +2399,49 @@ pub struct JsInverseAccessPath {
     pub access_fields: Vec<String>,
     pub access_file: String,
     pub access_line: i64,
+    /// Confidence of the calls along `path` and of the access, multiplied
+    pub path_confidence: f64,
+}
+
+/// Inverse access paths from one entry point, summarized, exposed to JavaScript
+#[napi(object)]
+pub struct JsEntryPointAccessGroup {
+    pub entry_point: String,
+    pub operations: Vec<String>,
+    pub access_sites: i64,
+    pub path_count: i64,
+    pub min_depth: i64,
+    pub max_depth: i64,
+    /// Only filled when include_paths is set
+    pub paths: Vec<JsInverseAccessPath>,
+    pub truncated: bool,
+}
+
+/// Inverse access paths into one table, summarized, exposed to JavaScript
+#[napi(object)]
+pub struct JsInverseTableGroup {
+    pub table: String,
+    /// Fields matching the target field; empty without one
+    pub fields: Vec<String>,
+    pub accessors: i64,
+    pub entry_points: Vec<String>,
+    pub path_count: i64,
 }
 
 /// Inverse reachability result exposed to JavaScript
 #[napi(object)]
 pub struct JsInverseReachabilityResult {
-    pub target_table: String,
+    pub target_table: Option<String>,
     pub target_field: Option<String>,
     pub access_paths: Vec<JsInverseAccessPath>,
+    /// Per entry point, when grouping by entry point
+    pub groups: Vec<JsEntryPointAccessGroup>,
+    /// Per table reached, sorted by name
+    pub tables: Vec<JsInverseTableGroup>,
     pub entry_points: Vec<String>,
     pub total_accessors: i64,
+    /// Whether any entry point had more paths than max_paths_per_entry_point
+    pub truncated: bool,
 }
 
 /// Call graph function node from JavaScript
@@ -986,6 +2465,8 @@ pub struct JsCallGraphCallSite {
     pub resolved: bool,
     pub resolved_candidates: Vec<String>,
     pub line: i64,
+    /// Resolution confidence (default 1.0)
+    pub confidence: Option<f64>,
 }
 
 /// Data access for call graph from JavaScript
@@ -1012,6 +2493,33 @@ pub struct JsCallGraphInput {
 // Reachability Functions
 // ============================================================================
 
+/// Call kind names normalized to how the call graph stores them
+fn to_edge_kinds(kinds: Option<Vec<String>>) -> Result<Option<Vec<String>>> {
+    kinds
+        .map(|kinds| {
+            kinds.iter()
+                .map(|k| CallKind::parse(k).map(|k| k.as_str().to_string()))
+                .collect::<std::result::Result<Vec<_>, String>>()
+        })
+        .transpose()
+        .map_err(napi::Error::from_reason)
+}
+
+fn to_operations(operations: Option<Vec<String>>) -> Result<Option<Vec<ReachDataOperation>>> {
+    operations
+        .map(|ops| ops.iter().map(|op| ReachDataOperation::parse(op)).collect::<std::result::Result<Vec<_>, String>>())
+        .transpose()
+        .map_err(napi::Error::from_reason)
+}
+
+fn to_unresolved_mode(mode: Option<String>, include_unresolved: Option<bool>) -> Result<UnresolvedMode> {
+    match mode {
+        Some(mode) => UnresolvedMode::parse(&mode).map_err(napi::Error::from_reason),
+        None if include_unresolved.unwrap_or(false) => Ok(UnresolvedMode::Pessimistic),
+        None => Ok(UnresolvedMode::Ignore),
+    }
+}
+
 /// Analyze reachability from a function
 #[napi]
 pub fn analyze_reachability(
@@ -1028,6 +2536,7 @@ pub fn analyze_reachability(
             resolved: c.resolved,
             resolved_candidates: c.resolved_candidates,
             line: c.line as u32,
+            confidence: c.confidence.unwrap_or(1.0) as f32,
         }).collect();
         
         let data_access: Vec<ReachDataAccessPoint> = func.data_access.into_iter().map(|a| {
@@ -1068,7 +2577,15 @@ pub fn analyze_reachability(
         max_depth: options.max_depth.map(|d| d as u32),
         sensitive_only: options.sensitive_only.unwrap_or(false),
         tables: options.tables.unwrap_or_default(),
-        include_unresolved: options.include_unresolved.unwrap_or(false),
+        unresolved_mode: to_unresolved_mode(options.unresolved_mode, options.include_unresolved)?,
+        edge_kinds: None,
+        include_async_edges: false,
+        max_paths_per_access: options.max_paths_per_access.map(|n| n as u32),
+        operations: to_operations(options.operations)?,
+        max_functions: options.max_functions.map(|n| n as u32),
+        timeout_ms: options.timeout_ms.map(|ms| ms as u32),
+        min_confidence: options.min_confidence,
+        eager_relations: Vec::new(),
     };
     
     let result = engine.get_reachable_data_from_function(&function_id, &rust_options);
@@ -1099,7 +2616,18 @@ pub fn analyze_reachability(
                 file: p.file,
                 line: p.line as i64,
             }).collect(),
+            paths: a.paths.into_iter().map(|path| {
+                path.into_iter().map(|p| JsCallPathNode {
+                    function_id: p.function_id,
+                    function_name: p.function_name,
+                    file: p.file,
+                    line: p.line as i64,
+                }).collect()
+            }).collect(),
             depth: a.depth as i64,
+            path_confidence: a.path_confidence,
+            crosses_async: a.crosses_async,
+            loaded_via: a.loaded_via,
         }).collect(),
         tables: result.tables,
         sensitive_fields: result.sensitive_fields.into_iter().map(|s| JsSensitiveFieldAccess {
@@ -1121,17 +2649,84 @@ pub fn analyze_reachability(
         }).collect(),
         max_depth: result.max_depth as i64,
         functions_traversed: result.functions_traversed as i64,
+        cache_hit: result.cache_hit,
+        truncated: result.truncated,
+        truncation_reason: result.truncation_reason.map(|r| r.as_str().to_string()),
+        unresolved_edges_traversed: result.unresolved_edges_traversed as i64,
     })
 }
 
+fn to_js_inverse_path(a: InverseAccessPath) -> JsInverseAccessPath {
+    JsInverseAccessPath {
+        entry_point: a.entry_point,
+        path: a.path.into_iter().map(|p| JsCallPathNode {
+            function_id: p.function_id,
+            function_name: p.function_name,
+            file: p.file,
+            line: p.line as i64,
+        }).collect(),
+        access_table: a.access_point.table,
+        access_operation: a.access_point.operation.as_str().to_string(),
+        access_fields: a.access_point.fields,
+        access_file: a.access_point.file,
+        access_line: a.access_point.line as i64,
+        path_confidence: a.path_confidence,
+    }
+}
+
+fn to_js_inverse_result(result: InverseReachabilityResult) -> JsInverseReachabilityResult {
+    JsInverseReachabilityResult {
+        target_table: result.target.table,
+        target_field: result.target.field,
+        access_paths: result.access_paths.into_iter().map(to_js_inverse_path).collect(),
+        groups: result.groups.into_iter().map(|g| JsEntryPointAccessGroup {
+            entry_point: g.entry_point,
+            operations: g.operations.iter().map(|op| op.as_str().to_string()).collect(),
+            access_sites: g.access_sites as i64,
+            path_count: g.path_count as i64,
+            min_depth: g.min_depth as i64,
+            max_depth: g.max_depth as i64,
+            paths: g.paths.into_iter().map(to_js_inverse_path).collect(),
+            truncated: g.truncated,
+        }).collect(),
+        tables: result.tables.into_iter().map(|t| JsInverseTableGroup {
+            table: t.table,
+            fields: t.fields,
+            accessors: t.accessors as i64,
+            entry_points: t.entry_points,
+            path_count: t.path_count as i64,
+        }).collect(),
+        entry_points: result.entry_points,
+        total_accessors: result.total_accessors as i64,
+        truncated: result.truncated,
+    }
+}
+
+fn require_inverse_target(table: &Option<String>, field: &Option<String>) -> Result<()> {
+    if table.is_none() && field.is_none() {
+        return Err(napi::Error::from_reason("Inverse reachability needs a table, a field, or both"));
+    }
+    Ok(())
+}
+
 /// Analyze inverse reachability - who can access this data?
+/// Without a table, every table with a matching field is searched; fields
+/// may use `*` wildcards (`*_token`)
 #[napi]
+#[allow(clippy::too_many_arguments)]
 pub fn analyze_inverse_reachability(
     graph_input: JsCallGraphInput,
-    table: String,
+    table: Option<String>,
     field: Option<String>,
     max_depth: Option<i64>,
+    operations: Option<Vec<String>>,
+    group_by_entry_point: Option<bool>,
+    include_paths: Option<bool>,
+    max_paths_per_entry_point: Option<i64>,
+    min_confidence: Option<f64>,
 ) -> Result<JsInverseReachabilityResult> {
+    require_inverse_target(&table, &field)?;
+    
     // Convert JS call graph to Rust call graph
     let mut graph = ReachCallGraph::default();
     
@@ -1141,6 +2736,7 @@ pub fn analyze_inverse_reachability(
             resolved: c.resolved,
             resolved_candidates: c.resolved_candidates,
             line: c.line as u32,
+            confidence: c.confidence.unwrap_or(1.0) as f32,
         }).collect();
         
         let data_access: Vec<ReachDataAccessPoint> = func.data_access.into_iter().map(|a| {
@@ -1181,41 +2777,29 @@ pub fn analyze_inverse_reachability(
         table: table.clone(),
         field: field.clone(),
         max_depth: max_depth.map(|d| d as u32),
+        operations: to_operations(operations)?,
+        group_by_entry_point: group_by_entry_point.unwrap_or(false),
+        include_paths: include_paths.unwrap_or(false),
+        max_paths_per_entry_point: max_paths_per_entry_point.map(|n| n as u32),
+        min_confidence,
     };
     
-    let result = engine.get_code_paths_to_data(&options);
-    
-    // Convert result to JS types
-    Ok(JsInverseReachabilityResult {
-        target_table: result.target.table,
-        target_field: result.target.field,
-        access_paths: result.access_paths.into_iter().map(|a| JsInverseAccessPath {
-            entry_point: a.entry_point,
-            path: a.path.into_iter().map(|p| JsCallPathNode {
-                function_id: p.function_id,
-                function_name: p.function_name,
-                file: p.file,
-                line: p.line as i64,
-            }).collect(),
-            access_table: a.access_point.table,
-            access_operation: match a.access_point.operation {
-                ReachDataOperation::Read => "read".to_string(),
-                ReachDataOperation::Write => "write".to_string(),
-                ReachDataOperation::Delete => "delete".to_string(),
-            },
-            access_fields: a.access_point.fields,
-            access_file: a.access_point.file,
-            access_line: a.access_point.line as i64,
-        }).collect(),
-        entry_points: result.entry_points,
-        total_accessors: result.total_accessors as i64,
-    })
+    Ok(to_js_inverse_result(engine.get_code_paths_to_data(&options)))
 }
 
 // ============================================================================
 // SQLite-Backed Reachability Functions (Recommended for large codebases)
 // ============================================================================
 
+/// Eager ORM relations of the call graph's files, when asked to expand them
+fn eager_relations(engine: &SqliteReachabilityEngine, root: &Path, expand: Option<bool>) -> Vec<EagerRelation> {
+    if expand.unwrap_or(false) {
+        scan_eager_relations(root, &engine.get_files())
+    } else {
+        Vec::new()
+    }
+}
+
 /// Analyze reachability from a function using SQLite storage
 /// 
 /// This queries the SQLite call graph database directly, avoiding the need
@@ -1231,7 +2815,8 @@ pub fn analyze_reachability_sqlite(
     let root = PathBuf::from(&root_dir);
     
     let engine = SqliteReachabilityEngine::from_project_root(&root)
-        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?
+        .with_shared_cache();
     
     if !engine.is_available() {
         return Err(napi::Error::from_reason(
@@ -1239,11 +2824,21 @@ pub fn analyze_reachability_sqlite(
         ));
     }
     
+    let edge_kinds = to_edge_kinds(options.edge_kinds)?;
+    
     let rust_options = ReachabilityOptions {
         max_depth: options.max_depth.map(|d| d as u32),
         sensitive_only: options.sensitive_only.unwrap_or(false),
         tables: options.tables.unwrap_or_default(),
-        include_unresolved: options.include_unresolved.unwrap_or(false),
+        unresolved_mode: to_unresolved_mode(options.unresolved_mode, options.include_unresolved)?,
+        edge_kinds,
+        include_async_edges: options.include_async_edges.unwrap_or(false),
+        max_paths_per_access: options.max_paths_per_access.map(|n| n as u32),
+        operations: to_operations(options.operations)?,
+        max_functions: options.max_functions.map(|n| n as u32),
+        timeout_ms: options.timeout_ms.map(|ms| ms as u32),
+        min_confidence: options.min_confidence,
+        eager_relations: eager_relations(&engine, &root, options.expand_eager_relations),
     };
     
     let result = engine.get_reachable_data_from_function(&function_id, &rust_options);
@@ -1274,7 +2869,18 @@ pub fn analyze_reachability_sqlite(
                 file: p.file,
                 line: p.line as i64,
             }).collect(),
+            paths: a.paths.into_iter().map(|path| {
+                path.into_iter().map(|p| JsCallPathNode {
+                    function_id: p.function_id,
+                    function_name: p.function_name,
+                    file: p.file,
+                    line: p.line as i64,
+                }).collect()
+            }).collect(),
             depth: a.depth as i64,
+            path_confidence: a.path_confidence,
+            crosses_async: a.crosses_async,
+            loaded_via: a.loaded_via,
         }).collect(),
         tables: result.tables,
         sensitive_fields: result.sensitive_fields.into_iter().map(|s| JsSensitiveFieldAccess {
@@ -1294,93 +2900,904 @@ pub fn analyze_reachability_sqlite(
             }).collect(),
             access_count: s.access_count as i64,
         }).collect(),
-        max_depth: result.max_depth as i64,
-        functions_traversed: result.functions_traversed as i64,
-    })
+        max_depth: result.max_depth as i64,
+        functions_traversed: result.functions_traversed as i64,
+        cache_hit: result.cache_hit,
+        truncated: result.truncated,
+        truncation_reason: result.truncation_reason.map(|r| r.as_str().to_string()),
+        unresolved_edges_traversed: result.unresolved_edges_traversed as i64,
+    })
+}
+
+/// Reachability from one entry point, summarized, exposed to JavaScript
+#[napi(object)]
+pub struct JsEntryPointReachability {
+    pub entry_point: String,
+    pub name: String,
+    pub file: String,
+    pub tables: Vec<String>,
+    /// Distinct sensitive fields reached per sensitivity type ("pii", "credentials", ...)
+    pub sensitive_field_counts: HashMap<String, i64>,
+    pub max_depth: i64,
+    pub functions_traversed: i64,
+}
+
+/// Analyze reachability from every entry point at once using SQLite storage
+/// 
+/// Opens the database once and shares each function's access between
+/// traversals - much faster than calling analyze_reachability_sqlite() per
+/// entry point. Returns summaries keyed by entry point ID.
+/// 
+/// Requires: Call graph must be built first using build_call_graph()
+#[napi]
+pub fn analyze_reachability_all_entry_points(
+    root_dir: String,
+    options: JsReachabilityOptions,
+) -> Result<HashMap<String, JsEntryPointReachability>> {
+    let root = PathBuf::from(&root_dir);
+    
+    let engine = SqliteReachabilityEngine::from_project_root(&root)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+    
+    if !engine.is_available() {
+        return Err(napi::Error::from_reason(
+            "Call graph database is empty. Run build_call_graph() first."
+        ));
+    }
+    
+    let rust_options = ReachabilityOptions {
+        max_depth: options.max_depth.map(|d| d as u32),
+        sensitive_only: options.sensitive_only.unwrap_or(false),
+        tables: options.tables.unwrap_or_default(),
+        unresolved_mode: to_unresolved_mode(options.unresolved_mode, options.include_unresolved)?,
+        edge_kinds: to_edge_kinds(options.edge_kinds)?,
+        include_async_edges: options.include_async_edges.unwrap_or(false),
+        max_paths_per_access: None,
+        operations: to_operations(options.operations)?,
+        max_functions: None,
+        timeout_ms: None,
+        min_confidence: None,
+        eager_relations: eager_relations(&engine, &root, options.expand_eager_relations),
+    };
+    
+    Ok(engine.analyze_all_entry_points(&rust_options)
+        .into_iter()
+        .map(|(id, summary)| (id, JsEntryPointReachability {
+            entry_point: summary.entry_point,
+            name: summary.name,
+            file: summary.file,
+            tables: summary.tables,
+            sensitive_field_counts: summary.sensitive_field_counts
+                .into_iter()
+                .map(|(sensitivity, count)| (sensitivity, count as i64))
+                .collect(),
+            max_depth: summary.max_depth as i64,
+            functions_traversed: summary.functions_traversed as i64,
+        }))
+        .collect())
+}
+
+/// Analyze inverse reachability using SQLite storage - who can access this data?
+/// 
+/// This queries the SQLite call graph database directly, avoiding the need
+/// to load the entire call graph into memory. Recommended for large codebases.
+/// Field-only queries look fields up through an index.
+/// 
+/// Requires: Call graph must be built first using build_call_graph()
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_inverse_reachability_sqlite(
+    root_dir: String,
+    table: Option<String>,
+    field: Option<String>,
+    max_depth: Option<i64>,
+    operations: Option<Vec<String>>,
+    group_by_entry_point: Option<bool>,
+    include_paths: Option<bool>,
+    max_paths_per_entry_point: Option<i64>,
+    min_confidence: Option<f64>,
+) -> Result<JsInverseReachabilityResult> {
+    require_inverse_target(&table, &field)?;
+    let root = PathBuf::from(&root_dir);
+    
+    let engine = SqliteReachabilityEngine::from_project_root(&root)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+    
+    if !engine.is_available() {
+        return Err(napi::Error::from_reason(
+            "Call graph database is empty. Run build_call_graph() first."
+        ));
+    }
+    
+    let options = InverseReachabilityOptions {
+        table: table.clone(),
+        field: field.clone(),
+        max_depth: max_depth.map(|d| d as u32),
+        operations: to_operations(operations)?,
+        group_by_entry_point: group_by_entry_point.unwrap_or(false),
+        include_paths: include_paths.unwrap_or(false),
+        max_paths_per_entry_point: max_paths_per_entry_point.map(|n| n as u32),
+        min_confidence,
+    };
+    
+    Ok(to_js_inverse_result(engine.get_code_paths_to_data(&options)))
+}
+
+/// Entry point reaching both queried data and outbound HTTP calls, exposed to JavaScript
+#[napi(object)]
+pub struct JsExternalExposure {
+    pub entry_point: String,
+    pub hosts: Vec<String>,
+}
+
+/// Which entry points can send this data to a third-party host?
+///
+/// Entry points that reach the target data and also reach an outbound HTTP
+/// call (to `host` when given). Reachability only: the data isn't traced
+/// into the request itself.
+///
+/// Requires: Call graph must be built first using build_call_graph()
+#[napi]
+pub fn analyze_external_exposure_sqlite(
+    root_dir: String,
+    table: Option<String>,
+    field: Option<String>,
+    host: Option<String>,
+    max_depth: Option<i64>,
+) -> Result<Vec<JsExternalExposure>> {
+    require_inverse_target(&table, &field)?;
+    let root = PathBuf::from(&root_dir);
+    
+    let engine = SqliteReachabilityEngine::from_project_root(&root)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+    
+    if !engine.is_available() {
+        return Err(napi::Error::from_reason(
+            "Call graph database is empty. Run build_call_graph() first."
+        ));
+    }
+    
+    let options = InverseReachabilityOptions {
+        table,
+        field,
+        max_depth: max_depth.map(|d| d as u32),
+        operations: None,
+        group_by_entry_point: true,
+        include_paths: false,
+        max_paths_per_entry_point: None,
+        min_confidence: None,
+    };
+    
+    Ok(engine.get_external_exposure(&options, host.as_deref())
+        .into_iter()
+        .map(|e| JsExternalExposure { entry_point: e.entry_point, hosts: e.hosts })
+        .collect())
+}
+
+/// Streamed inverse reachability options from JavaScript
+#[napi(object)]
+pub struct JsInverseStreamOptions {
+    pub max_depth: Option<i64>,
+    /// Only access with these operations: "read", "write", "delete"
+    pub operations: Option<Vec<String>>,
+    /// Summarize per entry point in the final result
+    pub group_by_entry_point: Option<bool>,
+    /// Paths streamed per entry point; every path when unset
+    pub max_paths_per_entry_point: Option<i64>,
+    /// Only paths whose path_confidence is at least this
+    pub min_confidence: Option<f64>,
+    /// Paths per callback invocation (default 100)
+    pub batch_size: Option<i64>,
+}
+
+/// Paths per `analyzeInverseReachabilityStream` callback by default
+const INVERSE_STREAM_BATCH_SIZE: usize = 100;
+
+/// Background inverse reachability task that streams paths to JavaScript
+pub struct InverseStreamTask {
+    root: PathBuf,
+    options: InverseReachabilityOptions,
+    batch_size: usize,
+    on_paths: ThreadsafeFunction<Vec<JsInverseAccessPath>, ErrorStrategy::Fatal>,
+}
+
+impl Task for InverseStreamTask {
+    type Output = InverseReachabilityResult;
+    type JsValue = JsInverseReachabilityResult;
+    
+    fn compute(&mut self) -> Result<Self::Output> {
+        let engine = SqliteReachabilityEngine::from_project_root(&self.root)
+            .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+        if !engine.is_available() {
+            return Err(napi::Error::from_reason(
+                "Call graph database is empty. Run build_call_graph() first."
+            ));
+        }
+        
+        let batch_size = self.batch_size;
+        let on_paths = &self.on_paths;
+        let mut batch = Vec::with_capacity(batch_size);
+        let result = engine.get_code_paths_to_data_with(&self.options, |path| {
+            batch.push(to_js_inverse_path(path));
+            if batch.len() == batch_size {
+                let full = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                on_paths.call(full, ThreadsafeFunctionCallMode::Blocking);
+            }
+        });
+        if !batch.is_empty() {
+            self.on_paths.call(batch, ThreadsafeFunctionCallMode::Blocking);
+        }
+        
+        Ok(result)
+    }
+    
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(to_js_inverse_result(output))
+    }
+}
+
+/// Analyze inverse reachability using SQLite storage, streaming access paths
+/// to a callback in batches
+/// 
+/// For hot tables whose paths are too many to return at once. Paths reach
+/// the callback while the rest are still being searched; the returned promise
+/// resolves to the summaries (tables, entry points, groups) without paths.
+/// 
+/// Requires: Call graph must be built first using build_call_graph()
+#[napi(ts_args_type = "rootDir: string, table: string | undefined | null, field: string | undefined | null, options: JsInverseStreamOptions | undefined | null, onPaths: (paths: Array<JsInverseAccessPath>) => void")]
+pub fn analyze_inverse_reachability_stream(
+    root_dir: String,
+    table: Option<String>,
+    field: Option<String>,
+    options: Option<JsInverseStreamOptions>,
+    on_paths: JsFunction,
+) -> Result<AsyncTask<InverseStreamTask>> {
+    require_inverse_target(&table, &field)?;
+    let options = options.unwrap_or(JsInverseStreamOptions {
+        max_depth: None,
+        operations: None,
+        group_by_entry_point: None,
+        max_paths_per_entry_point: None,
+        min_confidence: None,
+        batch_size: None,
+    });
+    
+    // A bounded queue applies backpressure if JavaScript falls behind
+    let on_paths: ThreadsafeFunction<Vec<JsInverseAccessPath>, ErrorStrategy::Fatal> = on_paths
+        .create_threadsafe_function(4, |ctx| Ok(vec![ctx.value]))?;
+    
+    Ok(AsyncTask::new(InverseStreamTask {
+        root: PathBuf::from(&root_dir),
+        options: InverseReachabilityOptions {
+            table,
+            field,
+            max_depth: options.max_depth.map(|d| d as u32),
+            operations: to_operations(options.operations)?,
+            group_by_entry_point: options.group_by_entry_point.unwrap_or(false),
+            include_paths: true,
+            max_paths_per_entry_point: options.max_paths_per_entry_point.map(|n| n as u32),
+            min_confidence: options.min_confidence,
+        },
+        batch_size: options.batch_size.map_or(INVERSE_STREAM_BATCH_SIZE, |n| n.max(1) as usize),
+        on_paths,
+    }))
+}
+
+/// Function-to-function reachability options from JavaScript
+#[napi(object)]
+pub struct JsFunctionPathOptions {
+    pub max_depth: Option<i64>,
+    /// Stop counting paths at this many (default 100)
+    pub max_paths: Option<i64>,
+    /// Call kinds to follow: "direct", "method", "callback", "dispatch",
+    /// "async"; all but "async" when unset
+    pub edge_kinds: Option<Vec<String>>,
+}
+
+/// Function-to-function reachability exposed to JavaScript
+#[napi(object)]
+pub struct JsFunctionReachability {
+    pub from_id: String,
+    pub to_id: String,
+    pub reachable: bool,
+    pub shortest_path: Vec<JsCallPathNode>,
+    /// Distinct paths, counted up to `maxPaths`
+    pub path_count: i64,
+    pub min_depth: Option<i64>,
+}
+
+/// Whether one function can reach another through resolved calls, and via
+/// which path, using SQLite storage
+/// 
+/// Requires: Call graph must be built first using build_call_graph()
+#[napi]
+pub fn analyze_function_reachability(
+    root_dir: String,
+    from_id: String,
+    to_id: String,
+    options: Option<JsFunctionPathOptions>,
+) -> Result<JsFunctionReachability> {
+    let root = PathBuf::from(&root_dir);
+    
+    let engine = SqliteReachabilityEngine::from_project_root(&root)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+    
+    if !engine.is_available() {
+        return Err(napi::Error::from_reason(
+            "Call graph database is empty. Run build_call_graph() first."
+        ));
+    }
+    
+    let options = options.unwrap_or(JsFunctionPathOptions { max_depth: None, max_paths: None, edge_kinds: None });
+    let edge_kinds = to_edge_kinds(options.edge_kinds)?;
+    
+    let result = engine.find_paths(&from_id, &to_id, &FunctionPathOptions {
+        max_depth: options.max_depth.map(|d| d as u32),
+        max_paths: options.max_paths.map(|n| n as u32),
+        include_unresolved: false,
+        edge_kinds,
+    });
+    
+    Ok(JsFunctionReachability {
+        from_id: result.from_id,
+        to_id: result.to_id,
+        reachable: result.reachable,
+        shortest_path: result.shortest_path.into_iter().map(|p| JsCallPathNode {
+            function_id: p.function_id,
+            function_name: p.function_name,
+            file: p.file,
+            line: p.line as i64,
+        }).collect(),
+        path_count: result.path_count as i64,
+        min_depth: result.min_depth.map(|d| d as i64),
+    })
+}
+
+/// Sensitivity matrix options from JavaScript
+#[napi(object)]
+pub struct JsSensitivityMatrixOptions {
+    pub max_depth: Option<i64>,
+    /// Call kinds to follow: "direct", "method", "callback", "dispatch",
+    /// "async"; all but "async" when unset
+    pub edge_kinds: Option<Vec<String>>,
+    /// Only these sensitivity types: "pii", "credentials", "financial", "health"
+    pub sensitivity_types: Option<Vec<String>>,
+    /// Reads of a model's table also reach the fields of the models its
+    /// eager ORM relations load
+    pub expand_eager_relations: Option<bool>,
+}
+
+/// One entry point reaching one sensitive field, exposed to JavaScript
+#[napi(object)]
+pub struct JsSensitivityMatrixRow {
+    pub entry_point: String,
+    pub sensitivity_type: String,
+    pub table: String,
+    pub field: String,
+    pub operation: String,
+    /// Shortest call paths from the entry point to the access
+    pub path_count: i64,
+    pub min_depth: i64,
+}
+
+/// Entry point x sensitive field matrix for compliance reports
+/// 
+/// Rescans the call graph's files for sensitive fields and lists, for every
+/// entry point, each sensitive field it reaches per table and operation.
+/// 
+/// Requires: Call graph must be built first using build_call_graph()
+#[napi]
+pub fn generate_sensitivity_matrix(
+    root_dir: String,
+    options: Option<JsSensitivityMatrixOptions>,
+) -> Result<Vec<JsSensitivityMatrixRow>> {
+    let options = options.unwrap_or(JsSensitivityMatrixOptions {
+        max_depth: None,
+        edge_kinds: None,
+        sensitivity_types: None,
+        expand_eager_relations: None,
+    });
+    let rust_options = SensitivityMatrixOptions {
+        max_depth: options.max_depth.map(|d| d as u32),
+        edge_kinds: to_edge_kinds(options.edge_kinds)?,
+        sensitivity_types: options.sensitivity_types
+            .map(|types| types.iter().map(|t| ReachSensitivityType::parse(t)).collect::<std::result::Result<Vec<_>, String>>())
+            .transpose()
+            .map_err(napi::Error::from_reason)?,
+        expand_eager_relations: options.expand_eager_relations.unwrap_or(false),
+    };
+    
+    let rows = drift_core::reachability::generate_sensitivity_matrix(&PathBuf::from(&root_dir), &rust_options)
+        .map_err(|e| napi::Error::from_reason(format!("{}. Run build_call_graph() first.", e)))?;
+    
+    Ok(rows.into_iter().map(|r| JsSensitivityMatrixRow {
+        entry_point: r.entry_point,
+        sensitivity_type: r.sensitivity_type.as_str().to_string(),
+        table: r.table,
+        field: r.field,
+        operation: r.operation.as_str().to_string(),
+        path_count: r.path_count.min(i64::MAX as u64) as i64,
+        min_depth: r.min_depth as i64,
+    }).collect())
+}
+
+/// Drop the cached analyze_reachability_sqlite() results for a project
+/// 
+/// Results are cached per call graph version, so this is only needed to
+/// free memory. Returns how many results were dropped.
+#[napi]
+pub fn clear_reachability_cache(root_dir: String) -> u32 {
+    let db_path = PathBuf::from(&root_dir)
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    ReachabilityCache::clear_shared(&db_path) as u32
+}
+
+/// Check if SQLite call graph database exists and has data
+#[napi]
+pub fn is_call_graph_available(root_dir: String) -> bool {
+    let root = PathBuf::from(&root_dir);
+    SqliteReachabilityEngine::from_project_root(&root)
+        .map(|e| e.is_available())
+        .unwrap_or(false)
+}
+
+/// Call graph stats from SQLite database
+#[napi(object)]
+pub struct JsCallGraphStats {
+    pub total_functions: i64,
+    pub total_calls: i64,
+    pub resolved_calls: i64,
+    pub entry_points: i64,
+    pub data_accessors: i64,
+}
+
+/// Get call graph statistics from SQLite database
+#[napi]
+pub fn get_call_graph_stats(root_dir: String) -> Result<JsCallGraphStats> {
+    use drift_core::call_graph::CallGraphDb;
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    
+    let stats = db.get_stats()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to get stats: {}", e)))?;
+    
+    Ok(JsCallGraphStats {
+        total_functions: stats.total_functions as i64,
+        total_calls: stats.total_calls as i64,
+        resolved_calls: stats.resolved_calls as i64,
+        entry_points: stats.entry_points as i64,
+        data_accessors: stats.data_accessors as i64,
+    })
+}
+
+/// Rows removed from the call graph database by `pruneCallGraph`
+#[napi(object)]
+pub struct JsPruneStats {
+    pub functions: i64,
+    pub calls: i64,
+    pub data_access: i64,
+    pub file_imports: i64,
+    pub types: i64,
+    pub di_bindings: i64,
+    pub async_handlers: i64,
+    /// Calls from remaining files whose target was removed, now unresolved
+    pub calls_unresolved: i64,
+    /// Why compacting the database failed; the rows are removed regardless
+    pub vacuum_error: Option<String>,
+}
+
+/// Remove call graph rows for files that no longer exist, then compact the database
+///
+/// `files` lists the project files to keep (relative to `rootDir`); without it,
+/// every recorded file still present on disk is kept. Safe to run while
+/// readonly queries are open.
+#[napi]
+pub fn prune_call_graph(root_dir: String, files: Option<Vec<String>>) -> Result<JsPruneStats> {
+    use drift_core::call_graph::CallGraphDb;
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    if !db_path.exists() {
+        return Err(napi::Error::from_reason("Call graph database not found".to_string()));
+    }
+    
+    let mut db = CallGraphDb::open(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    
+    let existing: Vec<String> = match files {
+        Some(files) => files
+            .into_iter()
+            .map(|f| {
+                std::path::Path::new(&f)
+                    .strip_prefix(&root)
+                    .map(|p| p.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or(f)
+            })
+            .collect(),
+        None => db.get_files()
+            .map_err(|e| napi::Error::from_reason(format!("Failed to list files: {}", e)))?
+            .into_iter()
+            .filter(|f| root.join(f).exists())
+            .collect(),
+    };
+    
+    let stats = db.prune(&existing)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to prune call graph: {}", e)))?;
+    
+    Ok(JsPruneStats {
+        functions: stats.functions as i64,
+        calls: stats.calls as i64,
+        data_access: stats.data_access as i64,
+        file_imports: stats.file_imports as i64,
+        types: stats.types as i64,
+        di_bindings: stats.di_bindings as i64,
+        async_handlers: stats.async_handlers as i64,
+        calls_unresolved: stats.calls_unresolved as i64,
+        vacuum_error: stats.vacuum_error,
+    })
+}
+
+/// Hotspot metrics for one function
+#[napi(object)]
+pub struct JsFunctionMetrics {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    /// Distinct callers
+    pub fan_in: i64,
+    /// Distinct callees
+    pub fan_out: i64,
+    /// Fewest calls from an entry point; absent when none reaches it
+    pub depth: Option<i64>,
+}
+
+/// Top functions by each call graph metric
+#[napi(object)]
+pub struct JsGraphMetrics {
+    pub total_functions: i64,
+    pub max_depth: i64,
+    pub top_fan_in: Vec<JsFunctionMetrics>,
+    pub top_fan_out: Vec<JsFunctionMetrics>,
+    pub top_depth: Vec<JsFunctionMetrics>,
+}
+
+/// Compute fan-in, fan-out and entry point depth for every function, store
+/// them in the database, and return the top `topN` (default: 20) by each
+#[napi]
+pub fn compute_graph_metrics(root_dir: String, top_n: Option<u32>) -> Result<JsGraphMetrics> {
+    use drift_core::call_graph::{CallGraphDb, FunctionMetrics, DEFAULT_TOP_N};
+    
+    let db_path = PathBuf::from(&root_dir)
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    if !db_path.exists() {
+        return Err(napi::Error::from_reason("Call graph database not found".to_string()));
+    }
+    
+    let mut db = CallGraphDb::open(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    let metrics = drift_core::call_graph::compute_graph_metrics(&mut db, top_n.map_or(DEFAULT_TOP_N, |n| n as usize))
+        .map_err(napi::Error::from_reason)?;
+    
+    let to_js = |m: FunctionMetrics| JsFunctionMetrics {
+        id: m.id,
+        name: m.name,
+        file: m.file,
+        fan_in: m.fan_in as i64,
+        fan_out: m.fan_out as i64,
+        depth: m.depth.map(|d| d as i64),
+    };
+    
+    Ok(JsGraphMetrics {
+        total_functions: metrics.total_functions as i64,
+        max_depth: metrics.max_depth as i64,
+        top_fan_in: metrics.top_fan_in.into_iter().map(to_js).collect(),
+        top_fan_out: metrics.top_fan_out.into_iter().map(to_js).collect(),
+        top_depth: metrics.top_depth.into_iter().map(to_js).collect(),
+    })
+}
+
+/// Get call resolution statistics, broken down by strategy, from SQLite database
+#[napi]
+pub fn get_call_graph_resolution_stats(root_dir: String) -> Result<JsResolutionStats> {
+    use drift_core::call_graph::CallGraphDb;
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    
+    let stats = db.get_stats()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to get stats: {}", e)))?;
+    let by_strategy = db.get_strategy_counts()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to get strategy counts: {}", e)))?;
+    
+    let same_file = by_strategy.iter()
+        .filter(|(strategy, _)| strategy == "same-file" || strategy == "method-same-file")
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    
+    Ok(JsResolutionStats {
+        total_calls: stats.total_calls as i64,
+        resolved_calls: stats.resolved_calls as i64,
+        resolution_rate: if stats.total_calls > 0 {
+            stats.resolved_calls as f64 / stats.total_calls as f64
+        } else {
+            0.0
+        },
+        same_file_resolutions: same_file as i64,
+        cross_file_resolutions: (stats.resolved_calls - same_file) as i64,
+        unresolved_calls: (stats.total_calls - stats.resolved_calls) as i64,
+        by_strategy: by_strategy.into_iter().map(|(strategy, count)| JsStrategyCount {
+            strategy,
+            count: count as i64,
+        }).collect(),
+    })
+}
+
+/// Subgraph selection for call graph export
+#[napi(object)]
+pub struct JsExportOptions {
+    /// Only functions reachable from this function ID
+    pub root_id: Option<String>,
+    /// Maximum call depth from `rootId`, or from the entry points without one
+    pub max_depth: Option<u32>,
+    /// Only entry points and data accessors, linked where one reaches another
+    pub boundaries_only: Option<bool>,
+    /// Include unresolved calls as dashed edges (default: true)
+    pub include_unresolved: Option<bool>,
+}
+
+/// Export the SQLite call graph as "dot", "json" or "mermaid" text
+#[napi]
+pub fn export_call_graph(root_dir: String, format: String, options: Option<JsExportOptions>) -> Result<String> {
+    use drift_core::call_graph::{CallGraphDb, ExportFormat, ExportOptions};
+    
+    let format = ExportFormat::parse(&format).map_err(napi::Error::from_reason)?;
+    let options = options.map(|o| ExportOptions {
+        root: o.root_id,
+        max_depth: o.max_depth.map(|d| d as usize),
+        boundaries_only: o.boundaries_only.unwrap_or(false),
+        include_unresolved: o.include_unresolved.unwrap_or(true),
+    }).unwrap_or_default();
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    
+    drift_core::call_graph::export_call_graph(&db, format, &options)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to export call graph: {}", e)))
+}
+
+/// Dead code detection options
+#[napi(object)]
+pub struct JsDeadCodeOptions {
+    /// Skip functions in test files
+    pub exclude_tests: Option<bool>,
+    /// Skip functions whose name matches any of these regexes (e.g. "^migrate_")
+    pub exclude_patterns: Option<Vec<String>>,
+    /// Path prefixes of library packages, whose exported functions are roots
+    pub library_packages: Option<Vec<String>>,
+}
+
+/// A function no entry point reaches
+#[napi(object)]
+pub struct JsUnreachableFunction {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub line: i64,
+    pub is_exported: bool,
+}
+
+/// Find functions unreachable from any entry point, sorted by file and line
+#[napi]
+pub fn find_unreachable_functions(root_dir: String, options: Option<JsDeadCodeOptions>) -> Result<Vec<JsUnreachableFunction>> {
+    use drift_core::call_graph::{CallGraphDb, DeadCodeOptions};
+    
+    let options = options.map(|o| DeadCodeOptions {
+        exclude_tests: o.exclude_tests.unwrap_or(false),
+        exclude_patterns: o.exclude_patterns.unwrap_or_default(),
+        library_packages: o.library_packages.unwrap_or_default(),
+    }).unwrap_or_default();
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    
+    let unreachable = drift_core::call_graph::find_unreachable_functions(&db, &options)
+        .map_err(napi::Error::from_reason)?;
+    
+    Ok(unreachable.into_iter().map(|f| JsUnreachableFunction {
+        id: f.id,
+        name: f.name,
+        file: f.file,
+        line: f.line as i64,
+        is_exported: f.is_exported,
+    }).collect())
+}
+
+/// A function inside a recursion cycle
+#[napi(object)]
+pub struct JsCycleMember {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+}
+
+/// Strongly connected component of the call graph
+#[napi(object)]
+pub struct JsCallCycle {
+    pub functions: Vec<JsCycleMember>,
+    /// Distinct caller -> callee pairs between members
+    pub internal_edges: i64,
+}
+
+/// Find recursion cycles with at least `minSize` functions (default: 2), largest first
+#[napi]
+pub fn get_call_graph_cycles(root_dir: String, min_size: Option<u32>) -> Result<Vec<JsCallCycle>> {
+    use drift_core::call_graph::CallGraphDb;
+    
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
+    
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
+    
+    let cycles = drift_core::call_graph::find_call_cycles(&db, min_size.unwrap_or(2) as usize)
+        .map_err(napi::Error::from_reason)?;
+    
+    Ok(cycles.into_iter().map(|c| JsCallCycle {
+        functions: c.functions.into_iter().map(|f| JsCycleMember {
+            id: f.id,
+            name: f.name,
+            file: f.file,
+        }).collect(),
+        internal_edges: c.internal_edges as i64,
+    }).collect())
 }
 
-/// Analyze inverse reachability using SQLite storage - who can access this data?
-/// 
-/// This queries the SQLite call graph database directly, avoiding the need
-/// to load the entire call graph into memory. Recommended for large codebases.
-/// 
-/// Requires: Call graph must be built first using build_call_graph()
+/// Function search filters
+#[napi(object)]
+pub struct JsFunctionSearchOptions {
+    /// Maximum results (default: 50)
+    pub limit: Option<u32>,
+    /// Only functions in files matching this glob
+    pub file_glob: Option<String>,
+    /// Only exported functions
+    pub exported_only: Option<bool>,
+}
+
+/// A function found by name
+#[napi(object)]
+pub struct JsFunctionMatch {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub is_entry_point: bool,
+    pub is_exported: bool,
+    /// "exact" | "prefix" | "substring"
+    pub match_kind: String,
+    /// Calls this function makes
+    pub outgoing_calls: i64,
+    /// Resolved calls to this function
+    pub incoming_calls: i64,
+}
+
+/// Find functions by name (with or without their class), best matches first
 #[napi]
-pub fn analyze_inverse_reachability_sqlite(
-    root_dir: String,
-    table: String,
-    field: Option<String>,
-    max_depth: Option<i64>,
-) -> Result<JsInverseReachabilityResult> {
-    let root = PathBuf::from(&root_dir);
+pub fn find_functions(root_dir: String, query: String, options: Option<JsFunctionSearchOptions>) -> Result<Vec<JsFunctionMatch>> {
+    use drift_core::call_graph::{CallGraphDb, FunctionSearchOptions};
     
-    let engine = SqliteReachabilityEngine::from_project_root(&root)
-        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}. Run build_call_graph() first.", e)))?;
+    let options = options.map(|o| FunctionSearchOptions {
+        limit: o.limit.map(|l| l as usize),
+        file_glob: o.file_glob,
+        exported_only: o.exported_only.unwrap_or(false),
+    }).unwrap_or_default();
     
-    if !engine.is_available() {
-        return Err(napi::Error::from_reason(
-            "Call graph database is empty. Run build_call_graph() first."
-        ));
-    }
+    let root = PathBuf::from(&root_dir);
+    let db_path = root
+        .join(".drift")
+        .join("lake")
+        .join("callgraph")
+        .join("callgraph.db");
     
-    let options = InverseReachabilityOptions {
-        table: table.clone(),
-        field: field.clone(),
-        max_depth: max_depth.map(|d| d as u32),
-    };
+    let db = CallGraphDb::open_readonly(&db_path)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
     
-    let result = engine.get_code_paths_to_data(&options);
+    let found = drift_core::call_graph::find_functions(&db, &query, &options)
+        .map_err(napi::Error::from_reason)?;
     
-    // Convert result to JS types
-    Ok(JsInverseReachabilityResult {
-        target_table: result.target.table,
-        target_field: result.target.field,
-        access_paths: result.access_paths.into_iter().map(|a| JsInverseAccessPath {
-            entry_point: a.entry_point,
-            path: a.path.into_iter().map(|p| JsCallPathNode {
-                function_id: p.function_id,
-                function_name: p.function_name,
-                file: p.file,
-                line: p.line as i64,
-            }).collect(),
-            access_table: a.access_point.table,
-            access_operation: match a.access_point.operation {
-                ReachDataOperation::Read => "read".to_string(),
-                ReachDataOperation::Write => "write".to_string(),
-                ReachDataOperation::Delete => "delete".to_string(),
-            },
-            access_fields: a.access_point.fields,
-            access_file: a.access_point.file,
-            access_line: a.access_point.line as i64,
-        }).collect(),
-        entry_points: result.entry_points,
-        total_accessors: result.total_accessors as i64,
-    })
+    Ok(found.into_iter().map(|f| JsFunctionMatch {
+        id: f.id,
+        name: f.name,
+        file: f.file,
+        start_line: f.start_line as i64,
+        end_line: f.end_line as i64,
+        is_entry_point: f.is_entry_point,
+        is_exported: f.is_exported,
+        match_kind: f.match_kind.as_str().to_string(),
+        outgoing_calls: f.outgoing_calls as i64,
+        incoming_calls: f.incoming_calls as i64,
+    }).collect())
 }
 
-/// Check if SQLite call graph database exists and has data
-#[napi]
-pub fn is_call_graph_available(root_dir: String) -> bool {
-    let root = PathBuf::from(&root_dir);
-    SqliteReachabilityEngine::from_project_root(&root)
-        .map(|e| e.is_available())
-        .unwrap_or(false)
+/// Unresolved call filters
+#[napi(object)]
+pub struct JsUnresolvedCallOptions {
+    /// Only calls made from files matching this glob
+    pub file_glob: Option<String>,
+    /// Only calls whose callee name matches this regex
+    pub callee_pattern: Option<String>,
+    /// Maximum results
+    pub limit: Option<u32>,
 }
 
-/// Call graph stats from SQLite database
+/// A call site without a resolved target
 #[napi(object)]
-pub struct JsCallGraphStats {
-    pub total_functions: i64,
-    pub total_calls: i64,
-    pub resolved_calls: i64,
-    pub entry_points: i64,
-    pub data_accessors: i64,
+pub struct JsUnresolvedCall {
+    pub caller_id: String,
+    pub file: String,
+    pub line: i64,
+    pub callee: String,
+    pub receiver: Option<String>,
+    pub receiver_type: Option<String>,
+    /// "no-candidates" | "receiver-type-mismatch" | "pending"
+    pub reason: String,
+    /// Functions sharing the callee's method name that were rejected, nearest first
+    pub candidates: Vec<String>,
 }
 
-/// Get call graph statistics from SQLite database
+/// List calls resolution left without a target, with rejected candidates
 #[napi]
-pub fn get_call_graph_stats(root_dir: String) -> Result<JsCallGraphStats> {
-    use drift_core::call_graph::CallGraphDb;
+pub fn get_unresolved_calls(root_dir: String, options: Option<JsUnresolvedCallOptions>) -> Result<Vec<JsUnresolvedCall>> {
+    use drift_core::call_graph::{CallGraphDb, UnresolvedCallOptions};
+    
+    let options = options.map(|o| UnresolvedCallOptions {
+        file_glob: o.file_glob,
+        callee_pattern: o.callee_pattern,
+        limit: o.limit.map(|l| l as usize),
+    }).unwrap_or_default();
     
     let root = PathBuf::from(&root_dir);
     let db_path = root
@@ -1392,15 +3809,84 @@ pub fn get_call_graph_stats(root_dir: String) -> Result<JsCallGraphStats> {
     let db = CallGraphDb::open_readonly(&db_path)
         .map_err(|e| napi::Error::from_reason(format!("Failed to open call graph database: {}", e)))?;
     
-    let stats = db.get_stats()
-        .map_err(|e| napi::Error::from_reason(format!("Failed to get stats: {}", e)))?;
+    let calls = drift_core::call_graph::find_unresolved_calls(&db, &options)
+        .map_err(napi::Error::from_reason)?;
     
-    Ok(JsCallGraphStats {
-        total_functions: stats.total_functions as i64,
-        total_calls: stats.total_calls as i64,
-        resolved_calls: stats.resolved_calls as i64,
-        entry_points: stats.entry_points as i64,
-        data_accessors: stats.data_accessors as i64,
+    Ok(calls.into_iter().map(|c| JsUnresolvedCall {
+        caller_id: c.caller_id,
+        file: c.file,
+        line: c.line as i64,
+        callee: c.callee,
+        receiver: c.receiver,
+        receiver_type: c.receiver_type,
+        reason: c.reason.as_str().to_string(),
+        candidates: c.candidates,
+    }).collect())
+}
+
+/// A function in a call graph diff
+#[napi(object)]
+pub struct JsDiffFunction {
+    /// "file:qualified_name"
+    pub key: String,
+    pub file: String,
+    pub name: String,
+    pub line: i64,
+}
+
+/// A call added or removed between two builds
+#[napi(object)]
+pub struct JsDiffEdge {
+    pub caller: String,
+    pub callee: String,
+}
+
+/// A data accessor newly reachable from an entry point
+#[napi(object)]
+pub struct JsDiffAccessor {
+    pub function: JsDiffFunction,
+    pub tables: Vec<String>,
+}
+
+/// Differences between two call graph builds
+#[napi(object)]
+pub struct JsCallGraphDiff {
+    pub added_functions: Vec<JsDiffFunction>,
+    pub removed_functions: Vec<JsDiffFunction>,
+    pub added_edges: Vec<JsDiffEdge>,
+    pub removed_edges: Vec<JsDiffEdge>,
+    pub newly_reachable_accessors: Vec<JsDiffAccessor>,
+    pub added_entry_points: Vec<JsDiffFunction>,
+    pub removed_entry_points: Vec<JsDiffFunction>,
+}
+
+/// Compare two call graph databases (e.g. built from a PR's base and head)
+#[napi]
+pub fn diff_call_graphs(base_db_path: String, head_db_path: String) -> Result<JsCallGraphDiff> {
+    use drift_core::call_graph::{DiffEdge, DiffFunction};
+    
+    let diff = drift_core::call_graph::diff_call_graphs(&PathBuf::from(&base_db_path), &PathBuf::from(&head_db_path))
+        .map_err(napi::Error::from_reason)?;
+    
+    let function = |f: DiffFunction| JsDiffFunction {
+        key: f.key,
+        file: f.file,
+        name: f.name,
+        line: f.line as i64,
+    };
+    let edge = |e: DiffEdge| JsDiffEdge { caller: e.caller, callee: e.callee };
+    
+    Ok(JsCallGraphDiff {
+        added_functions: diff.added_functions.into_iter().map(function).collect(),
+        removed_functions: diff.removed_functions.into_iter().map(function).collect(),
+        added_edges: diff.added_edges.into_iter().map(edge).collect(),
+        removed_edges: diff.removed_edges.into_iter().map(edge).collect(),
+        newly_reachable_accessors: diff.newly_reachable_accessors.into_iter().map(|a| JsDiffAccessor {
+            function: function(a.function),
+            tables: a.tables,
+        }).collect(),
+        added_entry_points: diff.added_entry_points.into_iter().map(function).collect(),
+        removed_entry_points: diff.removed_entry_points.into_iter().map(function).collect(),
     })
 }
 
@@ -1411,6 +3897,8 @@ pub struct JsEntryPointInfo {
     pub name: String,
     pub file: String,
     pub line: i64,
+    /// Framework convention or entry point rule name that made this an entry point ("nextjs-route", "route", ...)
+    pub entry_kind: Option<String>,
 }
 
 /// Get all entry points from SQLite call graph
@@ -1441,6 +3929,7 @@ pub fn get_call_graph_entry_points(root_dir: String) -> Result<Vec<JsEntryPointI
                 name: func.name,
                 file,
                 line: func.start_line as i64,
+                entry_kind: func.entry_kind,
             });
         }
     }
@@ -1628,6 +4117,10 @@ pub struct JsDetectedPattern {
     pub matched_text: String,
     pub confidence: f64,
     pub detection_method: String,
+    /// Severity of a custom rule match: error, warning, info or hint
+    pub severity: Option<String>,
+    /// Message of a custom rule match
+    pub message: Option<String>,
 }
 
 /// File patterns exposed to JavaScript
@@ -1649,6 +4142,16 @@ pub struct JsResolutionStats {
     pub same_file_resolutions: i64,
     pub cross_file_resolutions: i64,
     pub unresolved_calls: i64,
+    /// Resolved calls per resolution strategy
+    pub by_strategy: Vec<JsStrategyCount>,
+}
+
+/// Resolved call count for one resolution strategy
+#[napi(object)]
+pub struct JsStrategyCount {
+    /// "receiver-type", "import", "same-file", "same-directory", ...
+    pub strategy: String,
+    pub count: i64,
 }
 
 /// Call graph summary exposed to JavaScript
@@ -1669,6 +4172,11 @@ pub struct JsAnalysisMetrics {
     pub detect_time_ms: i64,
     pub resolve_time_ms: i64,
     pub total_time_ms: i64,
+    /// Files whose cached analysis was reused
+    pub cache_hits: i64,
+    /// Files analyzed afresh
+    pub cache_misses: i64,
+    pub cache_hit_ratio: f64,
 }
 
 /// Unified analysis result exposed to JavaScript
@@ -1680,6 +4188,89 @@ pub struct JsUnifiedResult {
     pub metrics: JsAnalysisMetrics,
     pub total_patterns: i64,
     pub total_violations: i64,
+    /// Violations of custom rules that no comment suppresses
+    pub violations: Vec<JsViolation>,
+    /// Violations suppressed by comments, counted apart from the total
+    pub suppressed: JsSuppressionStats,
+    /// Custom rules skipped as invalid
+    pub rule_errors: Vec<JsRuleError>,
+    /// How violations compared to the baseline, when one was given
+    pub baseline: Option<JsBaselineReport>,
+}
+
+/// Violations compared to a baseline, exposed to JavaScript
+#[napi(object)]
+pub struct JsBaselineReport {
+    pub baselined: i64,
+    /// Violations the baseline doesn't know
+    pub new_violations: i64,
+    /// Baselined violations no longer reported
+    pub fixed: Vec<JsBaselineEntry>,
+}
+
+/// Violation recorded in a baseline, exposed to JavaScript
+#[napi(object)]
+pub struct JsBaselineEntry {
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub file: String,
+    /// Where the violation was when recorded
+    pub line: i64,
+}
+
+/// Pattern violation exposed to JavaScript
+#[napi(object)]
+pub struct JsViolation {
+    pub id: String,
+    pub rule_id: String,
+    /// error, warning, info or hint
+    pub severity: String,
+    pub message: String,
+    pub file: String,
+    pub line: i64,
+    pub column: i64,
+    /// Type of the pattern that triggered the violation
+    pub pattern_id: String,
+    pub expected: String,
+    /// The matched code
+    pub actual: String,
+    pub suggested_fix: Option<String>,
+    /// Already recorded in the baseline, so not new
+    pub baselined: bool,
+}
+
+/// Violations hidden by `drift-ignore` comments, exposed to JavaScript
+#[napi(object)]
+pub struct JsSuppressionStats {
+    pub inline: i64,
+    pub file_level: i64,
+    /// Files whose `drift-ignore-file` comment hid violations
+    pub files: Vec<String>,
+}
+
+/// Custom rule that couldn't be compiled, exposed to JavaScript
+#[napi(object)]
+pub struct JsRuleError {
+    pub rule: String,
+    /// Language whose grammar rejected the query
+    pub language: Option<String>,
+    pub message: String,
+}
+
+/// User-defined pattern rule from JavaScript
+#[napi(object)]
+pub struct JsCustomRule {
+    /// Rule id, reported as the pattern type
+    pub id: String,
+    pub category: String,
+    /// "query" (tree-sitter) or "regex"
+    pub kind: String,
+    pub pattern: String,
+    /// error, warning (default), info or hint
+    pub severity: Option<String>,
+    pub message: String,
+    /// Languages the rule applies to (default: all)
+    pub languages: Option<Vec<String>>,
 }
 
 /// Unified analysis options from JavaScript
@@ -1690,6 +4281,16 @@ pub struct JsUnifiedOptions {
     pub max_resolution_depth: Option<i64>,
     pub parallel: Option<bool>,
     pub threads: Option<i64>,
+    pub include_generated: Option<bool>,
+    /// Report custom rule matches as violations (default: true)
+    pub include_violations: Option<bool>,
+    /// House patterns to detect on top of the built-in ones
+    pub custom_rules: Option<Vec<JsCustomRule>>,
+    /// Reanalyze every file instead of reusing cached results (default: false)
+    pub force: Option<bool>,
+    /// Baseline from `generateUnifiedBaseline`; violations it records are
+    /// marked `baselined`
+    pub baseline: Option<String>,
 }
 
 // ============================================================================
@@ -1702,51 +4303,39 @@ pub struct JsUnifiedOptions {
 /// Combines pattern detection and call resolution in a single pass.
 #[napi]
 pub fn analyze_unified(root: String, options: JsUnifiedOptions) -> Result<JsUnifiedResult> {
-    use drift_core::unified::{UnifiedAnalyzer, UnifiedOptions, PatternCategory, DetectionMethod};
+    use drift_core::unified::{DetectionMethod, ViolationSeverity};
     
-    // Convert categories from strings
-    let categories: Vec<PatternCategory> = options.categories
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|s| match s.to_lowercase().as_str() {
-            "api" => Some(PatternCategory::Api),
-            "auth" => Some(PatternCategory::Auth),
-            "components" => Some(PatternCategory::Components),
-            "config" => Some(PatternCategory::Config),
-            "data-access" | "dataaccess" => Some(PatternCategory::DataAccess),
-            "documentation" => Some(PatternCategory::Documentation),
-            "errors" => Some(PatternCategory::Errors),
-            "logging" => Some(PatternCategory::Logging),
-            "performance" => Some(PatternCategory::Performance),
-            "security" => Some(PatternCategory::Security),
-            "structural" => Some(PatternCategory::Structural),
-            "styling" => Some(PatternCategory::Styling),
-            "testing" => Some(PatternCategory::Testing),
-            "types" => Some(PatternCategory::Types),
-            "validation" => Some(PatternCategory::Validation),
-            _ => None,
+    let (result, rule_errors) = run_unified(&root, options)?;
+    let violations = result.file_patterns.iter()
+        .flat_map(|fp| fp.violations.iter())
+        .map(|v| JsViolation {
+            id: v.id.clone(),
+            rule_id: v.rule_id.clone(),
+            severity: match v.severity {
+                ViolationSeverity::Error => "error",
+                ViolationSeverity::Warning => "warning",
+                ViolationSeverity::Info => "info",
+                ViolationSeverity::Hint => "hint",
+            }.to_string(),
+            message: v.message.clone(),
+            file: v.file.clone(),
+            line: v.line as i64,
+            column: v.column as i64,
+            pattern_id: v.pattern_id.clone(),
+            expected: v.expected.clone(),
+            actual: v.actual.clone(),
+            suggested_fix: v.suggested_fix.clone(),
+            baselined: v.baselined,
         })
         .collect();
     
-    let rust_options = UnifiedOptions {
-        patterns: options.patterns,
-        categories,
-        max_resolution_depth: options.max_resolution_depth.unwrap_or(10) as u32,
-        parallel: options.parallel.unwrap_or(true),
-        threads: options.threads.unwrap_or(0) as usize,
-        include_violations: false,
-    };
-    
-    let mut analyzer = UnifiedAnalyzer::new()
-        .map_err(|e| napi::Error::from_reason(e))?;
-    
-    let result = analyzer.analyze(std::path::Path::new(&root), rust_options);
-    
     Ok(JsUnifiedResult {
         file_patterns: result.file_patterns.into_iter().map(|fp| JsFilePatterns {
             file: fp.file,
             language: format!("{:?}", fp.language).to_lowercase(),
             patterns: fp.patterns.into_iter().map(|p| JsDetectedPattern {
+                severity: rule_metadata(&p.metadata, "severity"),
+                message: rule_metadata(&p.metadata, "message"),
                 category: format!("{:?}", p.category).to_lowercase(),
                 pattern_type: p.pattern_type,
                 subcategory: p.subcategory,
@@ -1761,6 +4350,8 @@ pub fn analyze_unified(root: String, options: JsUnifiedOptions) -> Result<JsUnif
                     DetectionMethod::AstQuery => "ast".to_string(),
                     DetectionMethod::RegexFallback => "regex".to_string(),
                     DetectionMethod::Structural => "structural".to_string(),
+                    DetectionMethod::CustomQuery => "custom-query".to_string(),
+                    DetectionMethod::CustomRegex => "custom-regex".to_string(),
                 },
             }).collect(),
             parse_time_us: fp.parse_time_us as i64,
@@ -1773,6 +4364,10 @@ pub fn analyze_unified(root: String, options: JsUnifiedOptions) -> Result<JsUnif
             same_file_resolutions: result.resolution.same_file_resolutions as i64,
             cross_file_resolutions: result.resolution.cross_file_resolutions as i64,
             unresolved_calls: result.resolution.unresolved_calls as i64,
+            by_strategy: result.resolution.by_strategy.into_iter().map(|s| JsStrategyCount {
+                strategy: s.strategy,
+                count: s.count as i64,
+            }).collect(),
         },
         call_graph: JsCallGraphSummary {
             total_functions: result.call_graph.total_functions as i64,
@@ -1787,12 +4382,305 @@ pub fn analyze_unified(root: String, options: JsUnifiedOptions) -> Result<JsUnif
             detect_time_ms: result.metrics.detect_time_ms as i64,
             resolve_time_ms: result.metrics.resolve_time_ms as i64,
             total_time_ms: result.metrics.total_time_ms as i64,
+            cache_hits: result.metrics.cache_hits as i64,
+            cache_misses: result.metrics.cache_misses as i64,
+            cache_hit_ratio: result.metrics.cache_hit_ratio as f64,
         },
         total_patterns: result.total_patterns as i64,
         total_violations: result.total_violations as i64,
+        violations,
+        suppressed: JsSuppressionStats {
+            inline: result.suppressed.inline as i64,
+            file_level: result.suppressed.file_level as i64,
+            files: result.suppressed.files,
+        },
+        rule_errors: rule_errors.into_iter().chain(result.rule_errors.into_iter().map(|e| JsRuleError {
+            rule: e.rule,
+            language: e.language.map(|l| format!("{:?}", l).to_lowercase()),
+            message: e.message,
+        })).collect(),
+        baseline: result.baseline.map(|report| JsBaselineReport {
+            baselined: report.baselined as i64,
+            new_violations: report.new_violations as i64,
+            fixed: report.fixed.into_iter().map(|entry| JsBaselineEntry {
+                fingerprint: entry.fingerprint,
+                rule_id: entry.rule_id,
+                file: entry.file,
+                line: entry.line as i64,
+            }).collect(),
+        }),
+    })
+}
+
+/// SARIF export options from JavaScript
+#[napi(object)]
+pub struct JsSarifOptions {
+    /// Analysis options, when exporting a root rather than a result
+    pub analysis: Option<JsUnifiedOptions>,
+    /// Report detected patterns as notes, not only violations
+    pub include_patterns: Option<bool>,
+    /// Absolute URI of the analyzed root, which file paths are relative to
+    pub src_root: Option<String>,
+    pub pretty: Option<bool>,
+}
+
+/// Export unified analysis results as SARIF 2.1.0
+///
+/// Takes a result from `analyzeUnified`, or a root to analyze first.
+#[napi]
+pub fn export_unified_sarif(result_or_root: Either<JsUnifiedResult, String>, options: Option<JsSarifOptions>) -> Result<String> {
+    use drift_core::unified::{unified_to_sarif, SarifOptions};
+    
+    let options = options.unwrap_or(JsSarifOptions { analysis: None, include_patterns: None, src_root: None, pretty: None });
+    let result = match result_or_root {
+        Either::A(result) => core_unified_result(result)?,
+        Either::B(root) => {
+            let analysis = options.analysis.unwrap_or(JsUnifiedOptions {
+                patterns: Vec::new(),
+                categories: None,
+                max_resolution_depth: None,
+                parallel: None,
+                threads: None,
+                include_generated: None,
+                include_violations: None,
+                custom_rules: None,
+                force: None,
+                baseline: None,
+            });
+            run_unified(&root, analysis)?.0
+        }
+    };
+    let sarif_options = SarifOptions {
+        include_patterns: options.include_patterns.unwrap_or(false),
+        src_root: options.src_root,
+        pretty: options.pretty.unwrap_or(false),
+    };
+    unified_to_sarif(&result, &sarif_options).map_err(napi::Error::from_reason)
+}
+
+/// Record the violations of a result `analyzeUnified` returned as a
+/// baseline at `path`, returning how many were recorded
+#[napi]
+pub fn generate_unified_baseline(result: JsUnifiedResult, path: String) -> Result<i64> {
+    let baseline = drift_core::unified::generate_baseline(&core_unified_result(result)?);
+    baseline.save(std::path::Path::new(&path)).map_err(napi::Error::from_reason)?;
+    Ok(baseline.violations.len() as i64)
+}
+
+/// Run the unified analyzer, with the custom rules that didn't convert
+fn run_unified(root: &str, options: JsUnifiedOptions) -> Result<(drift_core::unified::UnifiedResult, Vec<JsRuleError>)> {
+    use drift_core::unified::{Baseline, UnifiedAnalyzer, UnifiedOptions};
+    
+    // Convert categories from strings
+    let categories = options.categories
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|s| unified_category(s))
+        .collect();
+    
+    // Rules that don't convert are reported like rules that don't compile
+    let mut custom_rules = Vec::new();
+    let mut rule_errors = Vec::new();
+    for rule in options.custom_rules.unwrap_or_default() {
+        let id = rule.id.clone();
+        match custom_rule(rule) {
+            Ok(rule) => custom_rules.push(rule),
+            Err(message) => rule_errors.push(JsRuleError { rule: id, language: None, message }),
+        }
+    }
+    
+    let rust_options = UnifiedOptions {
+        patterns: options.patterns,
+        categories,
+        max_resolution_depth: options.max_resolution_depth.unwrap_or(10) as u32,
+        parallel: options.parallel.unwrap_or(true),
+        threads: options.threads.unwrap_or(0) as usize,
+        include_violations: options.include_violations.unwrap_or(true),
+        include_generated: options.include_generated.unwrap_or(false),
+        custom_rules,
+        force: options.force.unwrap_or(false),
+        baseline: options.baseline
+            .map(|path| Baseline::load(std::path::Path::new(&path)))
+            .transpose()
+            .map_err(napi::Error::from_reason)?,
+    };
+    
+    let mut analyzer = UnifiedAnalyzer::new()
+        .map_err(napi::Error::from_reason)?;
+    
+    Ok((analyzer.analyze(std::path::Path::new(root), rust_options), rule_errors))
+}
+
+fn unified_category(name: &str) -> Option<drift_core::unified::PatternCategory> {
+    use drift_core::unified::PatternCategory;
+    
+    match name.to_lowercase().as_str() {
+        "api" => Some(PatternCategory::Api),
+        "auth" => Some(PatternCategory::Auth),
+        "components" => Some(PatternCategory::Components),
+        "config" => Some(PatternCategory::Config),
+        "data-access" | "dataaccess" => Some(PatternCategory::DataAccess),
+        "documentation" => Some(PatternCategory::Documentation),
+        "errors" => Some(PatternCategory::Errors),
+        "logging" => Some(PatternCategory::Logging),
+        "performance" => Some(PatternCategory::Performance),
+        "security" => Some(PatternCategory::Security),
+        "structural" => Some(PatternCategory::Structural),
+        "styling" => Some(PatternCategory::Styling),
+        "testing" => Some(PatternCategory::Testing),
+        "types" => Some(PatternCategory::Types),
+        "validation" => Some(PatternCategory::Validation),
+        _ => None,
+    }
+}
+
+fn custom_rule(rule: JsCustomRule) -> std::result::Result<drift_core::unified::CustomRule, String> {
+    use drift_core::unified::{CustomRule, RuleMatcher};
+    
+    let category = unified_category(&rule.category)
+        .ok_or_else(|| format!("Unknown category '{}'", rule.category))?;
+    let matcher = match rule.kind.as_str() {
+        "query" => RuleMatcher::Query(rule.pattern),
+        "regex" => RuleMatcher::Regex(rule.pattern),
+        kind => return Err(format!("Unknown rule kind '{}', expected 'query' or 'regex'", kind)),
+    };
+    let severity = rule.severity.as_deref().unwrap_or("warning");
+    let severity = violation_severity(severity).ok_or_else(|| format!("Unknown severity '{}'", severity))?;
+    let languages = rule.languages.unwrap_or_default().iter()
+        .map(|name| unified_language(name).ok_or_else(|| format!("Unknown language '{}'", name)))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(CustomRule { id: rule.id, category, matcher, severity, message: rule.message, languages })
+}
+
+fn unified_language(name: &str) -> Option<drift_core::unified::Language> {
+    drift_core::unified::Language::all().iter()
+        .find(|l| format!("{:?}", l).eq_ignore_ascii_case(name))
+        .copied()
+}
+
+fn violation_severity(name: &str) -> Option<drift_core::unified::ViolationSeverity> {
+    use drift_core::unified::ViolationSeverity;
+    
+    match name {
+        "error" => Some(ViolationSeverity::Error),
+        "warning" => Some(ViolationSeverity::Warning),
+        "info" => Some(ViolationSeverity::Info),
+        "hint" => Some(ViolationSeverity::Hint),
+        _ => None,
+    }
+}
+
+/// The patterns and violations of a result `analyzeUnified` returned
+fn core_unified_result(result: JsUnifiedResult) -> Result<drift_core::unified::UnifiedResult> {
+    use drift_core::unified::{
+        BaselineEntry, BaselineReport, DetectedPattern, DetectionMethod, FilePatterns, Language, SuppressionStats,
+        UnifiedResult, Violation,
+    };
+    
+    let invalid = |what: &str, value: &str| napi::Error::from_reason(format!("Unknown {} '{}'", what, value));
+    let mut file_patterns = Vec::new();
+    for fp in result.file_patterns {
+        let language = unified_language(&fp.language).ok_or_else(|| invalid("language", &fp.language))?;
+        let mut patterns = Vec::new();
+        for p in fp.patterns {
+            let metadata: HashMap<String, serde_json::Value> = [("severity", p.severity), ("message", p.message)]
+                .into_iter()
+                .filter_map(|(key, value)| Some((key.to_string(), serde_json::Value::String(value?))))
+                .collect();
+            patterns.push(DetectedPattern {
+                category: unified_category(&p.category).ok_or_else(|| invalid("category", &p.category))?,
+                pattern_type: p.pattern_type,
+                subcategory: p.subcategory,
+                file: p.file,
+                line: p.line as u32,
+                column: p.column as u32,
+                end_line: p.end_line as u32,
+                end_column: p.end_column as u32,
+                matched_text: p.matched_text,
+                confidence: p.confidence as f32,
+                detection_method: match p.detection_method.as_str() {
+                    "ast" => DetectionMethod::AstQuery,
+                    "regex" => DetectionMethod::RegexFallback,
+                    "structural" => DetectionMethod::Structural,
+                    "custom-query" => DetectionMethod::CustomQuery,
+                    "custom-regex" => DetectionMethod::CustomRegex,
+                    method => return Err(invalid("detection method", method)),
+                },
+                metadata: (!metadata.is_empty()).then_some(metadata),
+            });
+        }
+        file_patterns.push(FilePatterns {
+            file: fp.file,
+            language,
+            patterns,
+            violations: Vec::new(),
+            suppressed_inline: 0,
+            suppressed_by_file: 0,
+            parse_time_us: fp.parse_time_us as u64,
+            detect_time_us: fp.detect_time_us as u64,
+        });
+    }
+    for v in result.violations {
+        let violation = Violation {
+            id: v.id,
+            pattern_id: v.pattern_id,
+            rule_id: v.rule_id,
+            severity: violation_severity(&v.severity).ok_or_else(|| invalid("severity", &v.severity))?,
+            file: v.file,
+            line: v.line as u32,
+            column: v.column as u32,
+            message: v.message,
+            expected: v.expected,
+            actual: v.actual,
+            suggested_fix: v.suggested_fix,
+            baselined: v.baselined,
+        };
+        match file_patterns.iter_mut().find(|fp| fp.file == violation.file) {
+            Some(fp) => fp.violations.push(violation),
+            None => file_patterns.push(FilePatterns {
+                file: violation.file.clone(),
+                language: Language::TypeScript,
+                patterns: Vec::new(),
+                violations: vec![violation],
+                suppressed_inline: 0,
+                suppressed_by_file: 0,
+                parse_time_us: 0,
+                detect_time_us: 0,
+            }),
+        }
+    }
+    
+    Ok(UnifiedResult {
+        file_patterns,
+        resolution: Default::default(),
+        call_graph: Default::default(),
+        metrics: Default::default(),
+        total_patterns: result.total_patterns as u64,
+        total_violations: result.total_violations as u64,
+        suppressed: SuppressionStats {
+            inline: result.suppressed.inline as u64,
+            file_level: result.suppressed.file_level as u64,
+            files: result.suppressed.files,
+        },
+        rule_errors: Vec::new(),
+        baseline: result.baseline.map(|report| BaselineReport {
+            baselined: report.baselined as u64,
+            new_violations: report.new_violations as u64,
+            fixed: report.fixed.into_iter().map(|entry| BaselineEntry {
+                fingerprint: entry.fingerprint,
+                rule_id: entry.rule_id,
+                file: entry.file,
+                line: entry.line as u32,
+            }).collect(),
+        }),
     })
 }
 
+/// A custom rule's string metadata on a detected pattern
+fn rule_metadata(metadata: &Option<HashMap<String, serde_json::Value>>, key: &str) -> Option<String> {
+    metadata.as_ref()?.get(key)?.as_str().map(str::to_string)
+}
+
 // ============================================================================
 // Constants Analysis Types
 // ============================================================================